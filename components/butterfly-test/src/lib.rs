@@ -20,6 +20,8 @@ extern crate time;
 extern crate habitat_butterfly;
 extern crate habitat_core;
 
+pub mod sim;
+
 use std::sync::atomic::{AtomicUsize, Ordering, ATOMIC_USIZE_INIT};
 use std::thread;
 use std::ops::{Deref, DerefMut, Range};
@@ -27,8 +29,6 @@ use std::path::PathBuf;
 use std::time::Duration;
 use std::str::FromStr;
 
-use time::SteadyTime;
-
 use habitat_butterfly::server::{Server, Suitability};
 use habitat_butterfly::member::{Member, Health};
 use habitat_butterfly::server::timing::Timing;
@@ -42,7 +42,13 @@ use habitat_core::package::{Identifiable, PackageIdent};
 use habitat_core::crypto::keys::sym_key::SymKey;
 use habitat_butterfly::trace::Trace;
 
+use sim::SimNetwork;
+
 static SERVER_PORT: AtomicUsize = ATOMIC_USIZE_INIT;
+/// The first port of the members of a simulated network.
+const SIM_PORT: usize = 20000;
+/// How far a simulated network's clock moves each time a test polls its members.
+const SIM_STEP_MS: u64 = 10;
 
 #[derive(Debug)]
 struct NSuitability(u64);
@@ -53,14 +59,66 @@ impl Suitability for NSuitability {
 }
 
 pub fn start_server(name: &str, ring_key: Option<SymKey>, suitability: u64) -> Server {
-    SERVER_PORT.compare_and_swap(0, 6666, Ordering::Relaxed);
-    let swim_port = SERVER_PORT.fetch_add(1, Ordering::Relaxed);
-    let gossip_port = SERVER_PORT.fetch_add(1, Ordering::Relaxed);
+    start_server_on(name, ring_key, suitability, None, test_timing())
+}
+
+/// Start a server with the given timing, rather than the one the tests usually run with.
+pub fn start_server_with_timing(name: &str, timing: Timing) -> Server {
+    start_server_on(name, None, 0, None, timing)
+}
+
+/// Start a server whose SWIM probes and gossip travel over the simulated network, as the member
+/// numbered `member`. Its timers run on the network's clock, and its id, ports and member list
+/// shuffles all follow from the network's seed and its number, so that the same seed runs it the
+/// same way. Returns once all of its threads wait on the network.
+pub fn start_simulated_server(
+    name: &str,
+    network: &SimNetwork,
+    member: usize,
+    suitability: u64,
+) -> Server {
+    let mut timing = test_timing();
+    timing.clock = network.clock();
+    let server = start_server_on(name, None, suitability, Some((network, member)), timing);
+    network.settle();
+    server
+}
+
+/// The timing the tests run with. They wait a fixed number of protocol rounds for a lost member to
+/// be confirmed, however many of its peers noticed, so the suspicion timeout isn't stretched past
+/// its minimum.
+pub fn test_timing() -> Timing {
+    let mut timing = Timing::default();
+    timing.suspicion_max_timeout_multiplier = 1;
+    timing
+}
+
+fn start_server_on(
+    name: &str,
+    ring_key: Option<SymKey>,
+    suitability: u64,
+    network: Option<(&SimNetwork, usize)>,
+    timing: Timing,
+) -> Server {
+    let (swim_port, gossip_port) = match network {
+        // Nothing listens on a simulated port, so they can be the same on every run.
+        Some((_, member)) => (SIM_PORT + 2 * member, SIM_PORT + 2 * member + 1),
+        None => {
+            SERVER_PORT.compare_and_swap(0, 6666, Ordering::Relaxed);
+            (
+                SERVER_PORT.fetch_add(1, Ordering::Relaxed),
+                SERVER_PORT.fetch_add(1, Ordering::Relaxed),
+            )
+        }
+    };
     let listen_swim = format!("127.0.0.1:{}", swim_port);
     let listen_gossip = format!("127.0.0.1:{}", gossip_port);
     let mut member = Member::default();
     member.set_swim_port(swim_port as i32);
     member.set_gossip_port(gossip_port as i32);
+    if let Some((network, member_number)) = network {
+        member.set_id(network.member_id(member_number));
+    }
     let mut server = Server::new(
         &listen_swim[..],
         &listen_gossip[..],
@@ -71,9 +129,12 @@ pub fn start_server(name: &str, ring_key: Option<SymKey>, suitability: u64) -> S
         None::<PathBuf>,
         Box::new(NSuitability(suitability)),
     ).unwrap();
-    server.start(Timing::default()).expect(
-        "Cannot start server",
-    );
+    if let Some((network, member)) = network {
+        server.set_swim_transport(network.transport(member));
+        server.set_gossip_transport(network.transport(member));
+        server.member_list.set_seed(network.member_seed(member));
+    }
+    server.start(timing).expect("Cannot start server");
     server
 }
 
@@ -92,6 +153,8 @@ pub fn member_from_server(server: &Server) -> Member {
 #[derive(Debug)]
 pub struct SwimNet {
     pub members: Vec<Server>,
+    /// The simulated network the members talk over, if they aren't using real sockets.
+    pub sim: Option<SimNetwork>,
 }
 
 impl Deref for SwimNet {
@@ -115,7 +178,10 @@ impl SwimNet {
         for x in 0..count {
             members.push(start_server(&format!("{}", x), None, suitabilities[x]));
         }
-        SwimNet { members: members }
+        SwimNet {
+            members: members,
+            sim: None,
+        }
     }
 
     pub fn new(count: usize) -> SwimNet {
@@ -123,13 +189,67 @@ impl SwimNet {
         SwimNet::new_with_suitability(suitabilities)
     }
 
+    /// Create a network of `count` members which all run with the given timing.
+    pub fn new_with_timing(count: usize, timing: Timing) -> SwimNet {
+        let mut members = Vec::with_capacity(count);
+        for x in 0..count {
+            members.push(start_server_with_timing(&format!("{}", x), timing.clone()));
+        }
+        SwimNet {
+            members: members,
+            sim: None,
+        }
+    }
+
     pub fn new_ring_encryption(count: usize, ring_key: Option<SymKey>) -> SwimNet {
         let mut members = Vec::with_capacity(count);
         for x in 0..count {
             let rk = ring_key.clone();
             members.push(start_server(&format!("{}", x), rk, 0));
         }
-        SwimNet { members: members }
+        SwimNet {
+            members: members,
+            sim: None,
+        }
+    }
+
+    /// Create a network of `count` members that talk over a simulated network, whose losses and
+    /// delays are drawn from `seed`. The members run on the network's clock, which only moves
+    /// while the test waits on them, through `wait_for_rounds` and the other `wait_for_*`
+    /// functions, or when it runs the network itself.
+    pub fn new_simulated(count: usize, seed: u64) -> SwimNet {
+        SwimNet::new_simulated_with_suitability(vec![0; count], seed)
+    }
+
+    /// Create a simulated network, as `new_simulated` does, with a member for each of the
+    /// given suitabilities.
+    pub fn new_simulated_with_suitability(suitabilities: Vec<u64>, seed: u64) -> SwimNet {
+        let sim = SimNetwork::new(seed);
+        let mut members = Vec::with_capacity(suitabilities.len());
+        for (x, suitability) in suitabilities.into_iter().enumerate() {
+            members.push(start_simulated_server(
+                &format!("{}", x),
+                &sim,
+                x,
+                suitability,
+            ));
+        }
+        SwimNet {
+            members: members,
+            sim: Some(sim),
+        }
+    }
+
+    /// The simulated network the members talk over.
+    ///
+    /// # Panics
+    ///
+    /// If the network was not created with `new_simulated` or
+    /// `new_simulated_with_suitability`.
+    pub fn sim(&self) -> &SimNetwork {
+        self.sim.as_ref().expect(
+            "Asked for the simulated network of a real network",
+        )
     }
 
     pub fn connect(&mut self, from_entry: usize, to_entry: usize) {
@@ -140,9 +260,11 @@ impl SwimNet {
 
     pub fn add_member(&mut self) {
         let number = self.members.len() + 1;
-        self.members.push(
-            start_server(&format!("{}", number), None, 0),
-        );
+        let server = match self.sim {
+            Some(ref sim) => start_simulated_server(&format!("{}", number), sim, number - 1, 0),
+            None => start_server(&format!("{}", number), None, 0),
+        };
+        self.members.push(server);
     }
 
     // Fully mesh the network
@@ -212,6 +334,23 @@ impl SwimNet {
         health_summary
     }
 
+    /// Give the members time to make progress while a test waits on them: a simulated network's
+    /// clock is moved on by `ms`, while a real network just sleeps.
+    fn let_time_pass(&self, ms: u64) {
+        match self.sim {
+            Some(ref sim) => sim.run_for(ms),
+            None => thread::sleep(Duration::from_millis(ms)),
+        }
+    }
+
+    /// Let a simulated network's clock tick between two polls of its members; a real network
+    /// runs on its own.
+    fn step(&self) {
+        if let Some(ref sim) = self.sim {
+            sim.run_for(SIM_STEP_MS);
+        }
+    }
+
     pub fn max_rounds(&self) -> isize {
         4
     }
@@ -262,7 +401,7 @@ impl SwimNet {
             if self.check_rounds(&rounds_in) {
                 return;
             }
-            thread::sleep(Duration::from_millis(500));
+            self.let_time_pass(500);
         }
     }
 
@@ -293,7 +432,7 @@ impl SwimNet {
             if self.check_gossip_rounds(&rounds_in) {
                 return;
             }
-            thread::sleep(Duration::from_millis(500));
+            self.let_time_pass(500);
         }
     }
 
@@ -329,6 +468,7 @@ impl SwimNet {
                 );
                 return false;
             }
+            self.step();
         }
     }
 
@@ -363,12 +503,20 @@ impl SwimNet {
                          );
                 return false;
             }
+            self.step();
         }
     }
 
+    /// Cut the members in `left_range` off from the members in `right_range`. On a simulated
+    /// network, their messages are dropped on the wire; otherwise, they blacklist each other.
     pub fn partition(&self, left_range: Range<usize>, right_range: Range<usize>) {
         let left: Vec<usize> = left_range.collect();
         let right: Vec<usize> = right_range.collect();
+        if let Some(ref sim) = self.sim {
+            trace_it!(TEST_NET: self, format!("Partition {:?} {:?}", left, right));
+            sim.partition(&left, &right);
+            return;
+        }
         for l in left.iter() {
             for r in right.iter() {
                 println!("Partitioning {} from {}", *l, *r);
@@ -384,6 +532,11 @@ impl SwimNet {
     pub fn unpartition(&self, left_range: Range<usize>, right_range: Range<usize>) {
         let left: Vec<usize> = left_range.collect();
         let right: Vec<usize> = right_range.collect();
+        if let Some(ref sim) = self.sim {
+            trace_it!(TEST_NET: self, format!("UnPartition {:?} {:?}", left, right));
+            sim.unpartition(&left, &right);
+            return;
+        }
         for l in left.iter() {
             for r in right.iter() {
                 println!("UnPartitioning {} from {}", *l, *r);
@@ -412,6 +565,7 @@ impl SwimNet {
                 );
                 return false;
             }
+            self.step();
         }
     }
 
@@ -448,20 +602,13 @@ impl SwimNet {
                 // println!("Failed network health check dump: {:#?}", self);
                 return false;
             }
+            self.step();
         }
     }
 
     #[allow(dead_code)]
     pub fn wait_protocol_period(&self) {
-        let timing = Timing::default();
-        let next_period = timing.next_protocol_period();
-        loop {
-            if SteadyTime::now() <= next_period {
-                thread::sleep(Duration::from_millis(100));
-            } else {
-                return;
-            }
-        }
+        self.let_time_pass(test_timing().protocol_period_ms() as u64);
     }
 
     pub fn add_service(&mut self, member: usize, package: &str) {
@@ -0,0 +1,719 @@
+// Copyright (c) 2017 Chef Software Inc. and/or applicable contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A simulated network for running butterfly rings in-process, one thread at a time.
+//!
+//! Every member gets a `SimTransport`, which carries both its SWIM probes and its gossip through
+//! a shared `SimNetwork` instead of real sockets. The network decides the fate of every message
+//! when it is sent: messages between partitioned members, or to addresses nobody is listening on,
+//! are dropped; the rest are dropped with the configured loss rate, or delayed by a latency drawn
+//! from the configured range. Those draws come from the seed, the link and the message itself, so
+//! the same message on the same link always meets the same fate, whatever else was sent before it.
+//!
+//! The network also schedules the members. Their timers run on its clock, a `ManualClock`, and
+//! each of their threads parks on the network whenever it sleeps on that clock or waits for a
+//! message. `run_for` is the one loop that drives the whole ring: it hands the turn to one ready
+//! thread at a time, in order of thread name, and waits for it to park again; once no thread is
+//! ready, it moves the clock on to the next timer or delivery. Together with member ids and
+//! member list shuffles drawn from the same seed, the same seed and the same test always run the
+//! ring the same way, however fast the machine is.
+//!
+//! Threads a member thread spawns and joins, like the push workers, run during its turn. Members
+//! have to be started with `settle`, so that all of their threads have parked before the clock
+//! first moves.
+
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::io;
+use std::net::SocketAddr;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::thread;
+
+use time::{Duration as TimeDuration, SteadyTime};
+
+use habitat_butterfly::error::{Error, Result};
+use habitat_butterfly::server::timing::{Clock, ManualClock};
+use habitat_butterfly::transport::{GossipListener, GossipSender, GossipTransport, SwimSocket,
+                                   SwimTransport};
+
+/// The threads of a member which park on the network: inbound, outbound, expire, pull, push and
+/// anti-entropy.
+pub const THREADS_PER_MEMBER: usize = 6;
+/// How long a simulated SWIM socket waits for a datagram before timing out, in milliseconds of
+/// virtual time.
+const SWIM_READ_TIMEOUT_MS: u64 = 1000;
+
+#[derive(Debug)]
+enum Payload {
+    Swim(SocketAddr, Vec<u8>),
+    Gossip(Vec<u8>),
+}
+
+impl Payload {
+    /// A digest of what the message carries, which stands in for the message in every draw.
+    fn digest(&self) -> u64 {
+        match *self {
+            Payload::Swim(_, ref bytes) => fnv1a(0, bytes),
+            Payload::Gossip(ref bytes) => fnv1a(1, bytes),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Packet {
+    to: SocketAddr,
+    payload: Payload,
+}
+
+/// What a parked thread waits for.
+#[derive(Debug, Clone, Copy)]
+enum Wake {
+    /// The clock to reach this time.
+    At(u64),
+    /// A datagram on this SWIM address, or the clock to reach the read timeout.
+    Swim(SocketAddr, u64),
+    /// A message on this gossip address.
+    Gossip(SocketAddr),
+}
+
+/// Counters of what the network did with the messages it was given.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SimStats {
+    pub delivered: usize,
+    pub lost: usize,
+    pub partitioned: usize,
+    pub unroutable: usize,
+}
+
+#[derive(Debug)]
+struct SimState {
+    now: u64,
+    seed: u64,
+    sequence: u64,
+    loss: f64,
+    latency: (u64, u64),
+    blocked: HashSet<(usize, usize)>,
+    members: HashSet<usize>,
+    owners: HashMap<SocketAddr, usize>,
+    swim_inboxes: HashMap<SocketAddr, VecDeque<(Vec<u8>, SocketAddr)>>,
+    gossip_inboxes: HashMap<SocketAddr, VecDeque<Vec<u8>>>,
+    copies: HashMap<(usize, usize, u64), u64>,
+    in_flight: BTreeMap<(u64, u64, u64), Packet>,
+    parked: BTreeMap<String, Wake>,
+    turn: Option<String>,
+    stats: SimStats,
+}
+
+impl SimState {
+    /// The `n`th random number for a message from `from` to `to`, which is the `copy`th one with
+    /// this digest on that link.
+    fn draw(&self, from: usize, to: usize, digest: u64, copy: u64, n: u64) -> u64 {
+        [from as u64, to as u64, digest, copy, n].iter().fold(
+            self.seed,
+            |x, v| splitmix64(x ^ v),
+        )
+    }
+
+    fn send(&mut self, from: usize, to: SocketAddr, payload: Payload) {
+        let to_member = match self.owners.get(&to) {
+            Some(to_member) => *to_member,
+            None => {
+                self.stats.unroutable += 1;
+                return;
+            }
+        };
+        if self.blocked.contains(&(from, to_member)) {
+            self.stats.partitioned += 1;
+            return;
+        }
+        let digest = payload.digest();
+        let copy = {
+            let copies = self.copies.entry((from, to_member, digest)).or_insert(0);
+            *copies += 1;
+            *copies
+        };
+        let roll = (self.draw(from, to_member, digest, copy, 0) >> 11) as f64 /
+            (1u64 << 53) as f64;
+        if roll < self.loss {
+            self.stats.lost += 1;
+            return;
+        }
+        let (min, max) = self.latency;
+        let jitter = self.draw(from, to_member, digest, copy, 1);
+        let deliver_at = self.now + min + jitter % (max - min + 1);
+        // Messages due at the same time arrive in order of digest, rather than in the order
+        // racing push workers happened to send them.
+        self.sequence += 1;
+        self.in_flight.insert(
+            (deliver_at, digest, self.sequence),
+            Packet {
+                to: to,
+                payload: payload,
+            },
+        );
+    }
+
+    fn deliver_due(&mut self) {
+        let later = self.in_flight.split_off(&(self.now + 1, 0, 0));
+        let due = ::std::mem::replace(&mut self.in_flight, later);
+        for (_, packet) in due.into_iter() {
+            let delivered = match packet.payload {
+                Payload::Swim(from, bytes) => {
+                    self.swim_inboxes.get_mut(&packet.to).map_or(false, |inbox| {
+                        inbox.push_back((bytes, from));
+                        true
+                    })
+                }
+                Payload::Gossip(bytes) => {
+                    self.gossip_inboxes.get_mut(&packet.to).map_or(false, |inbox| {
+                        inbox.push_back(bytes);
+                        true
+                    })
+                }
+            };
+            if delivered {
+                self.stats.delivered += 1;
+            } else {
+                self.stats.unroutable += 1;
+            }
+        }
+    }
+
+    fn is_ready(&self, wake: &Wake) -> bool {
+        match *wake {
+            Wake::At(at) => at <= self.now,
+            Wake::Swim(addr, timeout_at) => {
+                timeout_at <= self.now ||
+                    self.swim_inboxes.get(&addr).map_or(
+                        false,
+                        |inbox| !inbox.is_empty(),
+                    )
+            }
+            Wake::Gossip(addr) => {
+                self.gossip_inboxes.get(&addr).map_or(
+                    false,
+                    |inbox| !inbox.is_empty(),
+                )
+            }
+        }
+    }
+
+    /// The first parked thread, by name, which has something to do.
+    fn next_ready(&self) -> Option<String> {
+        self.parked
+            .iter()
+            .find(|&(_, wake)| self.is_ready(wake))
+            .map(|(name, _)| name.clone())
+    }
+
+    /// When the next timer runs out or the next message arrives, if anything is pending.
+    fn next_event(&self) -> Option<u64> {
+        let timers = self.parked.values().filter_map(|wake| match *wake {
+            Wake::At(at) |
+            Wake::Swim(_, at) => Some(at),
+            Wake::Gossip(_) => None,
+        });
+        let deliveries = self.in_flight.keys().next().map(|&(at, _, _)| at);
+        timers.chain(deliveries).min()
+    }
+}
+
+/// The state shared by a network, its clock and its transports.
+#[derive(Debug)]
+struct Shared {
+    state: Mutex<SimState>,
+    changed: Condvar,
+    clock: ManualClock,
+}
+
+impl Shared {
+    fn state(&self) -> MutexGuard<SimState> {
+        self.state.lock().expect("Simulated network lock is poisoned")
+    }
+
+    fn wait<'a>(&self, state: MutexGuard<'a, SimState>) -> MutexGuard<'a, SimState> {
+        self.changed.wait(state).expect(
+            "Simulated network lock is poisoned",
+        )
+    }
+
+    /// Park the calling thread until `run_for` hands it the turn, giving up the turn if it held
+    /// it.
+    fn park<'a>(
+        &self,
+        mut state: MutexGuard<'a, SimState>,
+        wake: Wake,
+    ) -> MutexGuard<'a, SimState> {
+        let name = String::from(thread::current().name().expect(
+            "Only named threads can wait on a simulated network",
+        ));
+        if state.turn.as_ref() == Some(&name) {
+            state.turn = None;
+        }
+        state.parked.insert(name.clone(), wake);
+        self.changed.notify_all();
+        while state.turn.as_ref() != Some(&name) {
+            state = self.wait(state);
+        }
+        state.parked.remove(&name);
+        state
+    }
+}
+
+/// The network's clock, as its members see it. Sleeping on it parks the thread on the network.
+#[derive(Debug)]
+struct SimClock {
+    shared: Arc<Shared>,
+}
+
+impl Clock for SimClock {
+    fn now(&self) -> SteadyTime {
+        self.shared.clock.now()
+    }
+
+    fn sleep(&self, duration: TimeDuration) {
+        let ms = duration.num_milliseconds();
+        if ms <= 0 {
+            return;
+        }
+        let state = self.shared.state();
+        let wake_at = state.now + ms as u64;
+        let _ = self.shared.park(state, Wake::At(wake_at));
+    }
+}
+
+/// A simulated network, shared by every member attached to it.
+#[derive(Debug, Clone)]
+pub struct SimNetwork {
+    shared: Arc<Shared>,
+}
+
+impl SimNetwork {
+    /// Create a lossless network with no latency, whose draws come from `seed`.
+    pub fn new(seed: u64) -> SimNetwork {
+        SimNetwork {
+            shared: Arc::new(Shared {
+                state: Mutex::new(SimState {
+                    now: 0,
+                    seed: seed,
+                    sequence: 0,
+                    loss: 0.0,
+                    latency: (0, 0),
+                    blocked: HashSet::new(),
+                    members: HashSet::new(),
+                    owners: HashMap::new(),
+                    swim_inboxes: HashMap::new(),
+                    gossip_inboxes: HashMap::new(),
+                    copies: HashMap::new(),
+                    in_flight: BTreeMap::new(),
+                    parked: BTreeMap::new(),
+                    turn: None,
+                    stats: SimStats::default(),
+                }),
+                changed: Condvar::new(),
+                clock: ManualClock::new(),
+            }),
+        }
+    }
+
+    /// Returns the transport for member number `member`. Use it as both the SWIM and the gossip
+    /// transport of that member's server.
+    pub fn transport(&self, member: usize) -> SimTransport {
+        SimTransport {
+            network: self.clone(),
+            member: member,
+        }
+    }
+
+    /// The id of member number `member`, drawn from the seed, so that a seed always names a
+    /// ring's members - and so breaks its elections' ties - the same way.
+    pub fn member_id(&self, member: usize) -> String {
+        let seed = self.state().seed;
+        let draw = |n: u64| splitmix64(splitmix64(seed ^ member as u64) ^ n);
+        format!("{:016x}{:016x}", draw(1), draw(2))
+    }
+
+    /// The seed for the member list of member number `member`, see `MemberList::set_seed`.
+    pub fn member_seed(&self, member: usize) -> u64 {
+        let seed = self.state().seed;
+        splitmix64(splitmix64(seed ^ member as u64) ^ 3)
+    }
+
+    /// Lose each message with probability `loss`, between 0 and 1.
+    pub fn set_loss(&self, loss: f64) {
+        self.state().loss = loss;
+    }
+
+    /// Delay each message by between `min_ms` and `max_ms` milliseconds of virtual time.
+    pub fn set_latency(&self, min_ms: u64, max_ms: u64) {
+        assert!(min_ms <= max_ms, "Minimum latency is larger than the maximum");
+        self.state().latency = (min_ms, max_ms);
+    }
+
+    /// Drop every message sent from member `from` to member `to`, but not the other way around.
+    pub fn block(&self, from: usize, to: usize) {
+        self.state().blocked.insert((from, to));
+    }
+
+    /// Drop every message between the members in `left` and the members in `right`.
+    pub fn partition(&self, left: &[usize], right: &[usize]) {
+        let mut state = self.state();
+        for l in left.iter() {
+            for r in right.iter() {
+                if l != r {
+                    state.blocked.insert((*l, *r));
+                    state.blocked.insert((*r, *l));
+                }
+            }
+        }
+    }
+
+    /// Heal the partition between the members in `left` and the members in `right`.
+    pub fn unpartition(&self, left: &[usize], right: &[usize]) {
+        let mut state = self.state();
+        for l in left.iter() {
+            for r in right.iter() {
+                state.blocked.remove(&(*l, *r));
+                state.blocked.remove(&(*r, *l));
+            }
+        }
+    }
+
+    /// Heal every partition, and unblock every blocked link.
+    pub fn heal(&self) {
+        self.state().blocked.clear();
+    }
+
+    /// The current virtual time, in milliseconds.
+    pub fn now(&self) -> u64 {
+        self.state().now
+    }
+
+    /// The virtual clock, for the `Timing` of the members on this network.
+    pub fn clock(&self) -> Arc<Clock> {
+        Arc::new(SimClock { shared: self.shared.clone() })
+    }
+
+    /// Wait until the threads of every member started on this network have parked. Call it after
+    /// starting a member, before the clock moves again.
+    pub fn settle(&self) {
+        let mut state = self.state();
+        while state.turn.is_some() ||
+            state.parked.len() < state.members.len() * THREADS_PER_MEMBER
+        {
+            state = self.shared.wait(state);
+        }
+    }
+
+    /// Run the ring for `ms` milliseconds of virtual time. Every parked thread with something to
+    /// do gets the turn, one at a time and in order of name, until none are left; then the clock
+    /// moves on to the next timer or delivery, and it starts over.
+    pub fn run_for(&self, ms: u64) {
+        let mut state = self.state();
+        let until = state.now + ms;
+        loop {
+            while state.turn.is_some() {
+                state = self.shared.wait(state);
+            }
+            if let Some(name) = state.next_ready() {
+                state.turn = Some(name);
+                self.shared.changed.notify_all();
+                continue;
+            }
+            let next = match state.next_event() {
+                Some(at) if at < until => at,
+                _ => until,
+            };
+            if next <= state.now {
+                return;
+            }
+            self.shared.clock.advance((next - state.now) as i64);
+            state.now = next;
+            state.deliver_due();
+        }
+    }
+
+    /// The number of messages sent, but not yet delivered.
+    pub fn in_flight(&self) -> usize {
+        self.state().in_flight.len()
+    }
+
+    pub fn stats(&self) -> SimStats {
+        self.state().stats
+    }
+
+    fn state(&self) -> MutexGuard<SimState> {
+        self.shared.state()
+    }
+
+    fn send(&self, from: usize, to: SocketAddr, payload: Payload) {
+        let mut state = self.state();
+        state.send(from, to, payload);
+        // Messages without latency arrive right away, for their receivers to take their turn.
+        state.deliver_due();
+    }
+}
+
+/// A member's connection to a `SimNetwork`.
+#[derive(Debug, Clone)]
+pub struct SimTransport {
+    network: SimNetwork,
+    member: usize,
+}
+
+impl SwimTransport for SimTransport {
+    fn bind(&self, addr: &SocketAddr) -> Result<Arc<SwimSocket>> {
+        let mut state = self.network.state();
+        state.members.insert(self.member);
+        state.owners.insert(*addr, self.member);
+        state.swim_inboxes.insert(*addr, VecDeque::new());
+        Ok(Arc::new(SimSwimSocket {
+            network: self.network.clone(),
+            member: self.member,
+            addr: *addr,
+        }))
+    }
+}
+
+impl GossipTransport for SimTransport {
+    fn listen(&self, addr: &SocketAddr) -> Result<Box<GossipListener>> {
+        let mut state = self.network.state();
+        state.owners.insert(*addr, self.member);
+        state.gossip_inboxes.insert(*addr, VecDeque::new());
+        Ok(Box::new(SimGossipListener {
+            network: self.network.clone(),
+            addr: *addr,
+        }))
+    }
+
+    fn connect(&self, addr: &str) -> Result<Box<GossipSender>> {
+        let to = addr.parse().map_err(|_| {
+            Error::TcpConnectError(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("Not a socket address: {}", addr),
+            ))
+        })?;
+        Ok(Box::new(SimGossipSender {
+            network: self.network.clone(),
+            member: self.member,
+            to: to,
+        }))
+    }
+}
+
+#[derive(Debug)]
+struct SimSwimSocket {
+    network: SimNetwork,
+    member: usize,
+    addr: SocketAddr,
+}
+
+impl SwimSocket for SimSwimSocket {
+    fn send_to(&self, buf: &[u8], addr: SocketAddr) -> io::Result<usize> {
+        self.network.send(
+            self.member,
+            addr,
+            Payload::Swim(self.addr, buf.to_vec()),
+        );
+        Ok(buf.len())
+    }
+
+    fn recv_from(&self, buf: &mut [u8]) -> io::Result<(usize, SocketAddr)> {
+        let mut state = self.network.state();
+        let timeout_at = state.now + SWIM_READ_TIMEOUT_MS;
+        loop {
+            let datagram = state.swim_inboxes.get_mut(&self.addr).and_then(
+                |inbox| inbox.pop_front(),
+            );
+            if let Some((bytes, from)) = datagram {
+                // Like UDP, anything that doesn't fit in the buffer is lost.
+                let length = if bytes.len() < buf.len() {
+                    bytes.len()
+                } else {
+                    buf.len()
+                };
+                buf[..length].copy_from_slice(&bytes[..length]);
+                return Ok((length, from));
+            }
+            if state.now >= timeout_at {
+                return Err(io::Error::new(
+                    io::ErrorKind::WouldBlock,
+                    "No datagram arrived",
+                ));
+            }
+            state = self.network.shared.park(
+                state,
+                Wake::Swim(self.addr, timeout_at),
+            );
+        }
+    }
+}
+
+struct SimGossipListener {
+    network: SimNetwork,
+    addr: SocketAddr,
+}
+
+impl GossipListener for SimGossipListener {
+    fn recv(&mut self) -> Result<Vec<u8>> {
+        let mut state = self.network.state();
+        loop {
+            let message = state.gossip_inboxes.get_mut(&self.addr).and_then(
+                |inbox| inbox.pop_front(),
+            );
+            if let Some(bytes) = message {
+                return Ok(bytes);
+            }
+            state = self.network.shared.park(state, Wake::Gossip(self.addr));
+        }
+    }
+}
+
+struct SimGossipSender {
+    network: SimNetwork,
+    member: usize,
+    to: SocketAddr,
+}
+
+impl GossipSender for SimGossipSender {
+    fn send(&mut self, payload: &[u8]) -> Result<()> {
+        self.network.send(
+            self.member,
+            self.to,
+            Payload::Gossip(payload.to_vec()),
+        );
+        Ok(())
+    }
+}
+
+/// The 64 bit FNV-1a hash of `bytes`, after a leading `tag` byte.
+fn fnv1a(tag: u8, bytes: &[u8]) -> u64 {
+    ::std::iter::once(&tag).chain(bytes.iter()).fold(
+        0xcbf29ce484222325,
+        |hash, byte| (hash ^ *byte as u64).wrapping_mul(0x100000001b3),
+    )
+}
+
+/// One step of the splitmix64 generator, which mixes `x` into a well-spread random number.
+fn splitmix64(x: u64) -> u64 {
+    let mut z = x.wrapping_add(0x9E3779B97F4A7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+
+    use habitat_butterfly::server::timing::Clock;
+    use habitat_butterfly::transport::{GossipListener, GossipTransport};
+    use super::{SimNetwork, SimStats};
+
+    fn listen(network: &SimNetwork, member: usize) -> (SocketAddr, Box<GossipListener>) {
+        let addr: SocketAddr = format!("127.0.0.1:{}", 9000 + member).parse().unwrap();
+        let listener = network.transport(member).listen(&addr).unwrap();
+        (addr, listener)
+    }
+
+    fn send(network: &SimNetwork, from: usize, to: &SocketAddr, count: usize) {
+        let mut sender = network.transport(from).connect(&to.to_string()).unwrap();
+        for n in 0..count {
+            sender.send(&[n as u8]).unwrap();
+        }
+    }
+
+    #[test]
+    fn messages_are_held_until_the_clock_reaches_them() {
+        let network = SimNetwork::new(1);
+        network.set_latency(100, 100);
+        let (addr, mut listener) = listen(&network, 1);
+        send(&network, 0, &addr, 1);
+        network.run_for(99);
+        assert_eq!(network.in_flight(), 1);
+        network.run_for(1);
+        assert_eq!(network.in_flight(), 0);
+        assert_eq!(listener.recv().unwrap(), vec![0]);
+    }
+
+    #[test]
+    fn the_members_clock_moves_with_the_network() {
+        let network = SimNetwork::new(1);
+        let clock = network.clock();
+        let start = clock.now();
+        network.run_for(250);
+        network.run_for(50);
+        assert_eq!(network.now(), 300);
+        assert_eq!((clock.now() - start).num_milliseconds(), 300);
+    }
+
+    #[test]
+    fn partitions_drop_messages_until_healed() {
+        let network = SimNetwork::new(1);
+        let (addr, mut listener) = listen(&network, 1);
+        network.partition(&[0], &[1]);
+        send(&network, 0, &addr, 1);
+        assert_eq!(network.stats().partitioned, 1);
+        network.heal();
+        send(&network, 0, &addr, 1);
+        assert_eq!(network.stats().delivered, 1);
+        assert_eq!(listener.recv().unwrap(), vec![0]);
+    }
+
+    #[test]
+    fn blocking_a_link_only_drops_one_direction() {
+        let network = SimNetwork::new(1);
+        let (addr_0, _listener_0) = listen(&network, 0);
+        let (addr_1, _listener_1) = listen(&network, 1);
+        network.block(0, 1);
+        send(&network, 0, &addr_1, 1);
+        send(&network, 1, &addr_0, 1);
+        assert_eq!(network.stats().partitioned, 1);
+        assert_eq!(network.stats().delivered, 1);
+    }
+
+    #[test]
+    fn the_same_seed_loses_the_same_messages() {
+        let run = |seed| {
+            let network = SimNetwork::new(seed);
+            network.set_loss(0.5);
+            network.set_latency(0, 50);
+            let (addr, mut listener) = listen(&network, 1);
+            send(&network, 0, &addr, 100);
+            network.run_for(50);
+            let stats = network.stats();
+            let received: Vec<Vec<u8>> = (0..stats.delivered)
+                .map(|_| listener.recv().unwrap())
+                .collect();
+            (stats, received)
+        };
+        let (stats, received) = run(42);
+        assert!(stats.lost > 0 && stats.delivered > 0);
+        assert_eq!(stats.lost + stats.delivered, 100);
+        assert_eq!(run(42), (stats, received));
+    }
+
+    #[test]
+    fn messages_to_nobody_are_unroutable() {
+        let network = SimNetwork::new(1);
+        let addr: SocketAddr = "127.0.0.1:9999".parse().unwrap();
+        send(&network, 0, &addr, 1);
+        assert_eq!(
+            network.stats(),
+            SimStats {
+                unroutable: 1,
+                ..SimStats::default()
+            }
+        );
+    }
+}
@@ -18,50 +18,57 @@
 
 use habitat_core::crypto::SymKey;
 use habitat_core::service::ServiceGroup;
-use zmq;
 
-use ZMQ_CONTEXT;
 use message;
 use rumor::Rumor;
 use rumor::departure::Departure;
+use rumor::ring_key::RingKey;
 use rumor::service_config::ServiceConfig;
 use rumor::service_file::ServiceFile;
-use error::{Result, Error};
+use error::Result;
+use transport::{GossipSender, GossipTransport, GossipTransportKind};
 
-/// Holds a ZMQ Push socket, and an optional ring encryption key.
+/// Holds a connection to a member's gossip listener, and an optional ring encryption key.
 pub struct Client {
-    socket: zmq::Socket,
+    sender: Box<GossipSender>,
     ring_key: Option<SymKey>,
 }
 
 impl Client {
-    /// Connect this client to the address, and optionally encrypt the traffic.
+    /// Connect this client to the address over the default gossip transport, and optionally
+    /// encrypt the traffic.
     pub fn new<A>(addr: A, ring_key: Option<SymKey>) -> Result<Client>
     where
         A: ToString,
     {
-        let socket = (**ZMQ_CONTEXT).as_mut().socket(zmq::PUSH).expect(
-            "Failure to create the ZMQ push socket",
-        );
-        socket.set_linger(-1).expect(
-            "Failure to set the ZMQ push socket to not linger",
-        );
-        socket.set_tcp_keepalive(0).expect(
-            "Failure to set the ZMQ push socket to not use keepalive",
-        );
-        socket.set_immediate(true).expect(
-            "Failure to set the ZMQ push socket to immediate",
-        );
-        socket.set_sndhwm(1000).expect(
-            "Failure to set the ZMQ push socket hwm",
-        );
-        socket.set_sndtimeo(500).expect(
-            "Failure to set the ZMQ send timeout",
-        );
-        let to_addr = format!("tcp://{}", addr.to_string());
-        socket.connect(&to_addr).map_err(Error::ZmqConnectError)?;
+        Self::with_transport_kind(GossipTransportKind::default(), addr, ring_key)
+    }
+
+    /// Connect this client to the address over the named kind of gossip transport, and
+    /// optionally encrypt the traffic.
+    pub fn with_transport_kind<A>(
+        kind: GossipTransportKind,
+        addr: A,
+        ring_key: Option<SymKey>,
+    ) -> Result<Client>
+    where
+        A: ToString,
+    {
+        // Wait for every message to be delivered, as the client usually exits right after sending.
+        let transport = kind.transport(-1)?;
+        Self::with_transport(&*transport, addr, ring_key)
+    }
+
+    /// Connect this client to the address through the given gossip transport, and optionally
+    /// encrypt the traffic.
+    pub fn with_transport<T, A>(transport: &T, addr: A, ring_key: Option<SymKey>) -> Result<Client>
+    where
+        T: GossipTransport + ?Sized,
+        A: ToString,
+    {
+        let sender = transport.connect(&addr.to_string())?;
         Ok(Client {
-            socket: socket,
+            sender: sender,
             ring_key: ring_key,
         })
     }
@@ -104,10 +111,30 @@ impl Client {
         self.send(sf)
     }
 
+    /// Create a ring key rumor announcing a new revision of the ring key, and send it to the
+    /// server. `key` is the content of the new revision's secret key file.
+    pub fn send_ring_key(
+        &mut self,
+        new_key: &SymKey,
+        key: Vec<u8>,
+        activate_at: i64,
+        grace_period: i64,
+    ) -> Result<()> {
+        let rk = RingKey::new(
+            "butterflyclient",
+            new_key.name.clone(),
+            new_key.rev.clone(),
+            key,
+            activate_at,
+            grace_period,
+        );
+        self.send(rk)
+    }
+
     /// Send any `Rumor` to the server.
     pub fn send<T: Rumor>(&mut self, rumor: T) -> Result<()> {
         let bytes = rumor.write_to_bytes()?;
         let wire_msg = message::generate_wire(bytes, self.ring_key.as_ref())?;
-        self.socket.send(&wire_msg, 0).map_err(Error::ZmqSendError)
+        self.sender.send(&wire_msg)
     }
 }
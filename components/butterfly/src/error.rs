@@ -21,7 +21,9 @@ use std::str;
 
 use habitat_core;
 use protobuf;
+use serde_json;
 use toml;
+#[cfg(feature = "zeromq")]
 use zmq;
 
 pub type Result<T> = result::Result<T, Error>;
@@ -31,8 +33,11 @@ pub enum Error {
     BadDataPath(PathBuf, io::Error),
     BadDatFile(PathBuf, io::Error),
     BadMessage(String),
+    BadQuorumBasis(String),
+    BadTraceFile(PathBuf, usize, serde_json::Error),
     CannotBind(io::Error),
     DatFileIO(PathBuf, io::Error),
+    GossipListenerClosed,
     HabitatCore(habitat_core::error::Error),
     NonExistentRumor(String, String),
     ProtobufError(protobuf::ProtobufError),
@@ -41,7 +46,18 @@ pub enum Error {
     SocketSetReadTimeout(io::Error),
     SocketSetWriteTimeout(io::Error),
     SocketCloneError,
+    TcpConnectError(io::Error),
+    TcpSendError(io::Error),
+    TraceFileIO(PathBuf, io::Error),
+    UnknownGossipTransport(String),
+    UnknownRingKey(String),
+    #[cfg(feature = "zeromq")]
+    ZmqBindError(zmq::Error),
+    #[cfg(feature = "zeromq")]
     ZmqConnectError(zmq::Error),
+    #[cfg(feature = "zeromq")]
+    ZmqRecvError(zmq::Error),
+    #[cfg(feature = "zeromq")]
     ZmqSendError(zmq::Error),
 }
 
@@ -63,6 +79,17 @@ impl fmt::Display for Error {
                 )
             }
             Error::BadMessage(ref err) => format!("Bad Message: {:?}", err),
+            Error::BadQuorumBasis(ref value) => {
+                format!("Unknown quorum basis '{}'; use 'known' or 'alive'", value)
+            }
+            Error::BadTraceFile(ref path, line, ref err) => {
+                format!(
+                    "Unable to decode trace file, {}, line {}: {}",
+                    path.display(),
+                    line,
+                    err
+                )
+            }
             Error::CannotBind(ref err) => format!("Cannot bind to port: {:?}", err),
             Error::DatFileIO(ref path, ref err) => {
                 format!(
@@ -71,6 +98,7 @@ impl fmt::Display for Error {
                     err
                 )
             }
+            Error::GossipListenerClosed => format!("The gossip listener has stopped"),
             Error::HabitatCore(ref err) => format!("{}", err),
             Error::NonExistentRumor(ref member_id, ref rumor_id) => {
                 format!(
@@ -93,7 +121,28 @@ impl fmt::Display for Error {
                 format!("Cannot set UDP socket write timeout: {}", err)
             }
             Error::SocketCloneError => format!("Cannot clone the underlying UDP socket"),
+            Error::TcpConnectError(ref err) => format!("Cannot connect TCP gossip stream: {}", err),
+            Error::TcpSendError(ref err) => {
+                format!("Cannot send message through TCP gossip stream: {}", err)
+            }
+            Error::TraceFileIO(ref path, ref err) => {
+                format!("Error reading trace file, {}, {}", path.display(), err)
+            }
+            Error::UnknownGossipTransport(ref name) => {
+                format!("Unknown or unsupported gossip transport: {}", name)
+            }
+            Error::UnknownRingKey(ref rev) => {
+                format!("Message was encrypted with an unknown ring key revision: {}", rev)
+            }
+            #[cfg(feature = "zeromq")]
+            Error::ZmqBindError(ref err) => format!("Cannot bind ZMQ socket: {}", err),
+            #[cfg(feature = "zeromq")]
             Error::ZmqConnectError(ref err) => format!("Cannot connect ZMQ socket: {}", err),
+            #[cfg(feature = "zeromq")]
+            Error::ZmqRecvError(ref err) => {
+                format!("Cannot receive message from ZMQ socket: {}", err)
+            }
+            #[cfg(feature = "zeromq")]
             Error::ZmqSendError(ref err) => {
                 format!("Cannot send message through ZMQ socket: {}", err)
             }
@@ -109,8 +158,11 @@ impl error::Error for Error {
             Error::BadDataPath(_, _) => "Unable to read or write to data directory",
             Error::BadDatFile(_, _) => "Unable to decode contents of DatFile",
             Error::BadMessage(_) => "Bad Protobuf Message; should be Ping/Ack/PingReq",
+            Error::BadQuorumBasis(_) => "Unknown quorum basis",
+            Error::BadTraceFile(_, _, _) => "Unable to decode trace file",
             Error::CannotBind(_) => "Cannot bind to port",
             Error::DatFileIO(_, _) => "Error reading or writing to DatFile",
+            Error::GossipListenerClosed => "The gossip listener has stopped",
             Error::HabitatCore(_) => "Habitat core error",
             Error::NonExistentRumor(_, _) => {
                 "Cannot write rumor to bytes because it does not exist"
@@ -121,7 +173,18 @@ impl error::Error for Error {
             Error::SocketSetReadTimeout(_) => "Cannot set UDP socket read timeout",
             Error::SocketSetWriteTimeout(_) => "Cannot set UDP socket write timeout",
             Error::SocketCloneError => "Cannot clone the underlying UDP socket",
+            Error::TcpConnectError(_) => "Cannot connect TCP gossip stream",
+            Error::TcpSendError(_) => "Cannot send message through TCP gossip stream",
+            Error::TraceFileIO(_, _) => "Error reading trace file",
+            Error::UnknownGossipTransport(_) => "Unknown or unsupported gossip transport",
+            Error::UnknownRingKey(_) => "Message was encrypted with an unknown ring key revision",
+            #[cfg(feature = "zeromq")]
+            Error::ZmqBindError(_) => "Cannot bind ZMQ socket",
+            #[cfg(feature = "zeromq")]
             Error::ZmqConnectError(_) => "Cannot connect ZMQ socket",
+            #[cfg(feature = "zeromq")]
+            Error::ZmqRecvError(_) => "Cannot receive message from ZMQ socket",
+            #[cfg(feature = "zeromq")]
             Error::ZmqSendError(_) => "Cannot send message through ZMQ socket",
         }
     }
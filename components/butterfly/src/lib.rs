@@ -13,7 +13,8 @@
 // limitations under the License.
 
 //! Butterfly is the [SWIM](https://www.cs.cornell.edu/~asdas/research/dsn02-swim.pdf)
-//! implementation for Habitat, along with a ZeroMQ based gossip protocol.
+//! implementation for Habitat, along with a gossip protocol that runs over a pluggable transport
+//! (ZeroMQ by default, or plain TCP).
 //!
 //! It implements SWIM+Susp+Inf. It uses Newscast-style "heat" tracking to share membership rumors,
 //! while trying to keep UDP packet sizes below 512 bytes. It has the following changes:
@@ -54,9 +55,14 @@ extern crate rand;
 extern crate serde;
 #[macro_use]
 extern crate serde_derive;
+#[macro_use]
+extern crate serde_json;
+#[cfg(test)]
+extern crate tempdir;
 extern crate time;
 extern crate toml;
 extern crate uuid;
+#[cfg(feature = "zeromq")]
 extern crate zmq;
 
 #[macro_use]
@@ -67,11 +73,14 @@ pub mod member;
 pub mod message;
 pub mod rumor;
 pub mod server;
+pub mod transport;
 
+#[cfg(feature = "zeromq")]
 use std::cell::UnsafeCell;
 
 pub use server::Server;
 
+#[cfg(feature = "zeromq")]
 lazy_static! {
     /// A threadsafe shared ZMQ context for consuming services.
     ///
@@ -85,13 +94,17 @@ lazy_static! {
 
 /// This is a wrapper to provide interior mutability of an underlying `zmq::Context` and allows
 /// for sharing/sending of a `zmq::Context` between threads.
+#[cfg(feature = "zeromq")]
 pub struct ServerContext(UnsafeCell<zmq::Context>);
 
+#[cfg(feature = "zeromq")]
 impl ServerContext {
     pub fn as_mut(&self) -> &mut zmq::Context {
         unsafe { &mut *self.0.get() }
     }
 }
 
+#[cfg(feature = "zeromq")]
 unsafe impl Send for ServerContext {}
+#[cfg(feature = "zeromq")]
 unsafe impl Sync for ServerContext {}
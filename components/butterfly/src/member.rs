@@ -14,19 +14,19 @@
 
 //! Tracks membership. Contains both the `Member` struct and the `MemberList`.
 
-use std::collections::{hash_map, HashMap};
+use std::collections::{hash_map, HashMap, HashSet};
 use std::fmt;
 use std::iter::IntoIterator;
 use std::net::SocketAddr;
 use std::ops::{Deref, DerefMut};
 use std::result;
 use std::str::FromStr;
-use std::sync::{Arc, RwLock};
+use std::sync::{Arc, Mutex, RwLock};
 use std::sync::atomic::{AtomicUsize, Ordering};
 
 use protobuf::ProtobufEnum;
 use rand::{thread_rng, Rng};
-use time::SteadyTime;
+use time::{Duration as TimeDuration, SteadyTime};
 use uuid::Uuid;
 use serde::{Serialize, Serializer};
 use serde::ser::SerializeStruct;
@@ -35,6 +35,7 @@ use error::Error;
 use message::swim::{Member as ProtoMember, Membership as ProtoMembership,
                     Membership_Health as ProtoMembership_Health, Rumor_Type};
 use rumor::RumorKey;
+use server::timing::{Clock, WallClock};
 
 /// How many nodes do we target when we need to run PingReq.
 const PINGREQ_TARGETS: usize = 5;
@@ -204,15 +205,91 @@ impl<'a> From<&'a &'a Member> for RumorKey {
 // This is a Uuid type turned to a string
 pub type UuidSimple = String;
 
+/// A member we suspect of having failed: when we started suspecting it, which member's failed
+/// probe started the suspicion, and which other members have independently told us they suspect
+/// it too.
+#[derive(Debug, Clone)]
+pub struct Suspicion {
+    pub started: SteadyTime,
+    suspected_by: Option<UuidSimple>,
+    confirmations: HashSet<UuidSimple>,
+}
+
+impl Suspicion {
+    /// Start a new suspicion at `started`, with no confirmations. Who suspects the member isn't
+    /// known until the first call to `confirm`.
+    pub fn new(started: SteadyTime) -> Suspicion {
+        Suspicion {
+            started: started,
+            suspected_by: None,
+            confirmations: HashSet::new(),
+        }
+    }
+
+    /// The member whose failed probe started the suspicion, if known.
+    pub fn suspected_by(&self) -> Option<&str> {
+        self.suspected_by.as_ref().map(String::as_str)
+    }
+
+    /// The number of distinct members who have confirmed this suspicion.
+    pub fn confirmations(&self) -> usize {
+        self.confirmations.len()
+    }
+
+    /// Records that the given member suspects the member too. The first one is taken to have
+    /// started the suspicion; every other member counts as a confirmation. Returns true if this
+    /// is a confirmation from a member which had not confirmed the suspicion before.
+    pub fn confirm<S: Into<String>>(&mut self, member_id: S) -> bool {
+        let member_id = member_id.into();
+        match self.suspected_by {
+            None => {
+                self.suspected_by = Some(member_id);
+                false
+            }
+            Some(ref suspected_by) if *suspected_by == member_id => false,
+            Some(_) => self.confirmations.insert(member_id),
+        }
+    }
+}
+
+/// An xorshift64* generator, for member lists whose random choices have to be replayable.
+#[derive(Debug)]
+struct SeededRng(u64);
+
+impl SeededRng {
+    fn new(seed: u64) -> SeededRng {
+        // xorshift gets stuck on zero
+        SeededRng(if seed == 0 { 0x9E3779B97F4A7C15 } else { seed })
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545F4914F6CDD1D)
+    }
+
+    /// A Fisher-Yates shuffle.
+    fn shuffle<T>(&mut self, values: &mut [T]) {
+        for i in (1..values.len()).rev() {
+            let j = (self.next() % (i as u64 + 1)) as usize;
+            values.swap(i, j);
+        }
+    }
+}
+
 /// Tracks lists of members, their health, and how long they have been suspect.
 #[derive(Debug, Clone)]
 pub struct MemberList {
     pub members: Arc<RwLock<HashMap<UuidSimple, Member>>>,
     pub health: Arc<RwLock<HashMap<UuidSimple, Health>>>,
-    suspect: Arc<RwLock<HashMap<UuidSimple, SteadyTime>>>,
+    suspect: Arc<RwLock<HashMap<UuidSimple, Suspicion>>>,
     depart: Arc<RwLock<HashMap<UuidSimple, SteadyTime>>>,
+    dead: Arc<RwLock<HashMap<UuidSimple, SteadyTime>>>,
     initial_members: Arc<RwLock<Vec<Member>>>,
     update_counter: Arc<AtomicUsize>,
+    clock: Arc<Clock>,
+    rng: Option<Arc<Mutex<SeededRng>>>,
 }
 
 impl Serialize for MemberList {
@@ -245,8 +322,38 @@ impl MemberList {
             health: Arc::new(RwLock::new(HashMap::new())),
             suspect: Arc::new(RwLock::new(HashMap::new())),
             depart: Arc::new(RwLock::new(HashMap::new())),
+            dead: Arc::new(RwLock::new(HashMap::new())),
             initial_members: Arc::new(RwLock::new(Vec::new())),
             update_counter: Arc::new(AtomicUsize::new(0)),
+            clock: Arc::new(WallClock),
+            rng: None,
+        }
+    }
+
+    /// Time suspicions and departures with `clock` rather than the wall clock. Set it before the
+    /// list is shared; clones made earlier keep the clock they had.
+    pub fn set_clock(&mut self, clock: Arc<Clock>) {
+        self.clock = clock;
+    }
+
+    /// Draw the order in which members are checked, and the pingreq targets, from `seed` rather
+    /// than the thread's random generator, so the same seed makes the same choices. Like the
+    /// clock, set it before the list is shared.
+    pub fn set_seed(&mut self, seed: u64) {
+        self.rng = Some(Arc::new(Mutex::new(SeededRng::new(seed))));
+    }
+
+    fn shuffle(&self, members: &mut Vec<Member>) {
+        match self.rng {
+            Some(ref rng) => {
+                // The members come out of a HashMap in no particular order, so sort them first
+                // for the seed to decide where each one ends up.
+                members.sort_by(|a, b| a.get_id().cmp(b.get_id()));
+                rng.lock().expect("Member list RNG lock is poisoned").shuffle(
+                    members,
+                );
+            }
+            None => thread_rng().shuffle(members),
         }
     }
 
@@ -385,7 +492,10 @@ impl MemberList {
                 self.suspect
                     .write()
                     .expect("Suspect lock is poisoned")
-                    .insert(String::from(member.get_id()), SteadyTime::now());
+                    .insert(
+                        String::from(member.get_id()),
+                        Suspicion::new(self.clock.now()),
+                    );
             }
             if stop_suspicion == true {
                 self.suspect
@@ -399,6 +509,7 @@ impl MemberList {
                     .expect("Departure lock is poisoned")
                     .remove(member.get_id());
             }
+            self.track_death(member.get_id(), health);
             self.members
                 .write()
                 .expect("Member list lock is poisoned")
@@ -491,16 +602,30 @@ impl MemberList {
         }
         if health == Health::Suspect {
             let mut sl = self.suspect.write().expect("Suspect lock is poisoned");
-            sl.insert(String::from(member_id), SteadyTime::now());
+            sl.insert(String::from(member_id), Suspicion::new(self.clock.now()));
         }
         self.health
             .write()
             .expect("Health write lock is poisoned")
             .insert(String::from(member_id), health);
+        self.track_death(member_id, health);
         self.increment_update_counter();
         true
     }
 
+    /// Remembers when we first saw a member as `Confirmed` or `Departed`, and forgets it again if
+    /// the member comes back to life.
+    fn track_death(&self, member_id: &str, health: Health) {
+        let mut dead = self.dead.write().expect("Dead list lock is poisoned");
+        if health == Health::Confirmed || health == Health::Departed {
+            dead.entry(String::from(member_id)).or_insert_with(
+                || self.clock.now(),
+            );
+        } else {
+            dead.remove(member_id);
+        }
+    }
+
     /// The same as `insert_health_by_id`, but takes a member rather than an id.
     pub fn insert_health(&self, member: &Member, health: Health) -> bool {
         self.insert_health_by_id(member.get_id(), health)
@@ -521,6 +646,9 @@ impl MemberList {
             Some(member) => {
                 pm.set_health(mhealth);
                 pm.set_member(member.proto.clone());
+                if let Some(suspected_by) = self.suspected_by(member_id) {
+                    pm.set_suspected_by(suspected_by);
+                }
                 Some(pm)
             }
             None => None,
@@ -544,8 +672,7 @@ impl MemberList {
             .filter(|v| v.get_id() != exclude_id)
             .map(|v| v.clone())
             .collect();
-        let mut rng = thread_rng();
-        rng.shuffle(&mut members);
+        self.shuffle(&mut members);
         members
     }
 
@@ -564,8 +691,7 @@ impl MemberList {
             let ml = self.members.read().expect("Member list lock is poisoned");
             ml.values().map(|v| v.clone()).collect()
         };
-        let mut rng = thread_rng();
-        rng.shuffle(&mut members);
+        self.shuffle(&mut members);
         for member in members
             .into_iter()
             .filter(|m| {
@@ -629,10 +755,45 @@ impl MemberList {
         }
     }
 
+    /// Records that `suspected_by` suspects the given member at `incarnation`. The first member
+    /// to do so started the suspicion, and every other distinct member counts as a confirmation.
+    /// Suspicions by the suspect itself, of a stale incarnation, or of members we don't currently
+    /// suspect are ignored. Returns true if this was a new confirmation.
+    pub fn confirm_suspicion(&self, member_id: &str, incarnation: u64, suspected_by: &str) -> bool {
+        if suspected_by.is_empty() || member_id == suspected_by {
+            return false;
+        }
+        let current = self.members
+            .read()
+            .expect("Member list lock is poisoned")
+            .get(member_id)
+            .map(|member| member.get_incarnation());
+        if current != Some(incarnation) {
+            return false;
+        }
+        match self.suspect
+            .write()
+            .expect("Suspect lock is poisoned")
+            .get_mut(member_id) {
+            Some(suspicion) => suspicion.confirm(suspected_by),
+            None => false,
+        }
+    }
+
+    /// Returns the member whose failed probe started our suspicion of the given member, if we
+    /// suspect it and know who does.
+    pub fn suspected_by(&self, member_id: &str) -> Option<String> {
+        self.suspect
+            .read()
+            .expect("Suspect lock is poisoned")
+            .get(member_id)
+            .and_then(|suspicion| suspicion.suspected_by().map(String::from))
+    }
+
     /// Iterates over every suspected membership entry, calling the given closure.
     pub fn with_suspects<F>(&self, mut with_closure: F) -> ()
     where
-        F: FnMut((&str, &SteadyTime)) -> (),
+        F: FnMut((&str, &Suspicion)) -> (),
     {
         for (id, suspect) in self.suspect
             .read()
@@ -669,7 +830,7 @@ impl MemberList {
         let mut depart = self.depart.write().expect(
             "Departure list lock is poisoned",
         );
-        depart.insert(member_id.to_string(), SteadyTime::now());
+        depart.insert(member_id.to_string(), self.clock.now());
     }
 
     /// Removes a member from the departure list
@@ -680,6 +841,43 @@ impl MemberList {
         depart.remove(member_id);
     }
 
+    /// Returns the ids of every member that has been `Confirmed` or `Departed` for longer than
+    /// `timeout`.
+    pub fn dead_longer_than(&self, timeout: TimeDuration) -> Vec<String> {
+        let now = self.clock.now();
+        self.dead
+            .read()
+            .expect("Dead list lock is poisoned")
+            .iter()
+            .filter(|&(_, since)| now >= *since + timeout)
+            .map(|(id, _)| id.clone())
+            .collect()
+    }
+
+    /// Returns every `Confirmed` or `Departed` member along with how long it has been dead.
+    pub fn dead_for(&self) -> Vec<(String, TimeDuration)> {
+        let now = self.clock.now();
+        self.dead
+            .read()
+            .expect("Dead list lock is poisoned")
+            .iter()
+            .map(|(id, since)| (id.clone(), now - *since))
+            .collect()
+    }
+
+    /// Backdates the death of a `Confirmed` or `Departed` member to `age` ago, as read back from
+    /// the rumor file. Members which are alive are left alone.
+    pub fn set_dead_for(&self, member_id: &str, age: TimeDuration) {
+        let now = self.clock.now();
+        if let Some(since) = self.dead
+            .write()
+            .expect("Dead list lock is poisoned")
+            .get_mut(member_id)
+        {
+            *since = now - age;
+        }
+    }
+
     pub fn contains_member(&self, member_id: &str) -> bool {
         self.members
             .read()
@@ -717,7 +915,12 @@ mod tests {
     }
 
     mod member_list {
+        use std::sync::Arc;
+
+        use time::Duration;
+
         use member::{Member, MemberList, Health, PINGREQ_TARGETS};
+        use server::timing::{Clock, ManualClock};
 
         fn populated_member_list(size: u64) -> MemberList {
             let ml = MemberList::new();
@@ -748,6 +951,29 @@ mod tests {
             assert!(list_a != list_b);
         }
 
+        #[test]
+        fn check_list_with_a_seed_is_the_same_for_the_same_seed() {
+            let members: Vec<Member> = (0..100).map(|_| Member::default()).collect();
+            let mut ml_a = MemberList::new();
+            let mut ml_b = MemberList::new();
+            ml_a.set_seed(7);
+            ml_b.set_seed(7);
+            for member in members.iter() {
+                ml_a.insert(member.clone(), Health::Alive);
+            }
+            for member in members.iter().rev() {
+                ml_b.insert(member.clone(), Health::Alive);
+            }
+            let ids = |ml: &MemberList| -> Vec<String> {
+                ml.check_list("foo")
+                    .iter()
+                    .map(|m| String::from(m.get_id()))
+                    .collect()
+            };
+            assert_eq!(ids(&ml_a), ids(&ml_b));
+            assert_eq!(ids(&ml_a), ids(&ml_b));
+        }
+
         #[test]
         fn health_of() {
             let ml = populated_member_list(1);
@@ -1034,5 +1260,136 @@ mod tests {
             assert!(ml.check_health_of(&mcheck_two, Health::Departed));
         }
 
+        #[test]
+        fn confirm_suspicion_counts_distinct_members() {
+            let ml = MemberList::new();
+            let member = Member::default();
+            let mcheck = member.clone();
+            assert_eq!(ml.insert(member, Health::Alive), true);
+            assert_eq!(ml.insert(mcheck.clone(), Health::Suspect), true);
+
+            let incarnation = mcheck.get_incarnation();
+            assert_eq!(ml.confirm_suspicion(mcheck.get_id(), incarnation, "pat"), false);
+            assert_eq!(ml.confirm_suspicion(mcheck.get_id(), incarnation, "chris"), true);
+            assert_eq!(ml.confirm_suspicion(mcheck.get_id(), incarnation, "chris"), false);
+            assert_eq!(ml.confirm_suspicion(mcheck.get_id(), incarnation, "dana"), true);
+            let mut confirmations = 0;
+            ml.with_suspects(|(_, suspicion)| confirmations = suspicion.confirmations());
+            assert_eq!(confirmations, 2);
+            assert_eq!(ml.suspected_by(mcheck.get_id()), Some(String::from("pat")));
+        }
+
+        #[test]
+        fn confirm_suspicion_ignores_the_originator_and_stale_incarnations() {
+            let ml = MemberList::new();
+            let mut member = Member::default();
+            member.set_incarnation(2);
+            let mcheck = member.clone();
+            assert_eq!(ml.insert(member, Health::Alive), true);
+            assert_eq!(ml.insert(mcheck.clone(), Health::Suspect), true);
+
+            assert_eq!(ml.confirm_suspicion(mcheck.get_id(), 2, "pat"), false);
+            // The suspicion relayed back to us by others isn't an independent one
+            assert_eq!(ml.confirm_suspicion(mcheck.get_id(), 2, "pat"), false);
+            assert_eq!(ml.confirm_suspicion(mcheck.get_id(), 1, "chris"), false);
+            assert_eq!(ml.confirm_suspicion(mcheck.get_id(), 2, ""), false);
+            let mut confirmations = 0;
+            ml.with_suspects(|(_, suspicion)| confirmations = suspicion.confirmations());
+            assert_eq!(confirmations, 0);
+        }
+
+        #[test]
+        fn confirm_suspicion_ignores_the_suspect_and_unsuspected_members() {
+            let ml = MemberList::new();
+            let member = Member::default();
+            let mcheck = member.clone();
+            let incarnation = mcheck.get_incarnation();
+            assert_eq!(ml.insert(member, Health::Alive), true);
+            assert_eq!(ml.confirm_suspicion(mcheck.get_id(), incarnation, "pat"), false);
+
+            assert_eq!(ml.insert(mcheck.clone(), Health::Suspect), true);
+            assert_eq!(
+                ml.confirm_suspicion(mcheck.get_id(), incarnation, mcheck.get_id()),
+                false
+            );
+            assert_eq!(ml.suspected_by(mcheck.get_id()), None);
+        }
+
+        #[test]
+        fn dead_longer_than_tracks_confirmed_and_departed_members() {
+            let ml = MemberList::new();
+            let alive = Member::default();
+            let confirmed = Member::default();
+            let departed = Member::default();
+            ml.insert(alive, Health::Alive);
+            ml.insert(confirmed.clone(), Health::Alive);
+            ml.insert_health(&confirmed, Health::Confirmed);
+            ml.insert(departed.clone(), Health::Departed);
+
+            let mut dead = ml.dead_longer_than(Duration::zero());
+            dead.sort();
+            let mut expected = vec![
+                String::from(confirmed.get_id()),
+                String::from(departed.get_id()),
+            ];
+            expected.sort();
+            assert_eq!(dead, expected);
+            assert!(ml.dead_longer_than(Duration::hours(1)).is_empty());
+        }
+
+        #[test]
+        fn dead_longer_than_forgets_members_that_come_back() {
+            let ml = MemberList::new();
+            let mut member = Member::default();
+            ml.insert(member.clone(), Health::Confirmed);
+            assert_eq!(ml.dead_longer_than(Duration::zero()).len(), 1);
+            member.set_incarnation(1);
+            ml.insert(member, Health::Alive);
+            assert!(ml.dead_longer_than(Duration::zero()).is_empty());
+        }
+
+        #[test]
+        fn set_dead_for_backdates_dead_members_only() {
+            let clock = Arc::new(ManualClock::new());
+            let mut ml = MemberList::new();
+            ml.set_clock(clock.clone());
+            let alive = Member::default();
+            let departed = Member::default();
+            ml.insert(alive.clone(), Health::Alive);
+            ml.insert(departed.clone(), Health::Departed);
+
+            ml.set_dead_for(alive.get_id(), Duration::hours(2));
+            ml.set_dead_for(departed.get_id(), Duration::hours(2));
+            assert_eq!(
+                ml.dead_longer_than(Duration::hours(1)),
+                vec![String::from(departed.get_id())]
+            );
+            assert_eq!(
+                ml.dead_for(),
+                vec![(String::from(departed.get_id()), Duration::hours(2))]
+            );
+        }
+
+        #[test]
+        fn suspicions_and_departures_are_timed_by_the_clock() {
+            let clock = Arc::new(ManualClock::new());
+            let mut ml = MemberList::new();
+            ml.set_clock(clock.clone());
+            let suspect = Member::default();
+            let departed = Member::default();
+            ml.insert(suspect.clone(), Health::Alive);
+            clock.advance(1000);
+            ml.insert_health(&suspect, Health::Suspect);
+            ml.insert(departed.clone(), Health::Departed);
+            ml.with_suspects(|(_, suspicion)| assert_eq!(suspicion.started, clock.now()));
+
+            clock.advance(59_999);
+            assert!(ml.dead_longer_than(Duration::minutes(1)).is_empty());
+            clock.advance(1);
+            assert_eq!(
+                ml.dead_longer_than(Duration::minutes(1)),
+                vec![String::from(departed.get_id())]
+            );
+        }
     }
 }
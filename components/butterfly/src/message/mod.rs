@@ -14,6 +14,7 @@
 
 pub mod swim;
 
+use std::collections::{BTreeMap, HashMap};
 use std::result;
 use std::str;
 
@@ -22,10 +23,12 @@ use serde::{Serialize, Serializer};
 use serde::ser::SerializeStruct;
 use toml;
 
-use error::Result;
+use error::{Error, Result};
 use message::swim::Wire;
 use protobuf::{self, Message};
 
+/// Wraps a payload for the wire, encrypting it with the ring key if there is one. Encrypted
+/// messages carry the revision of the key they were encrypted with.
 pub fn generate_wire(payload: Vec<u8>, ring_key: Option<&SymKey>) -> Result<Vec<u8>> {
     let mut wire = Wire::new();
     if let Some(ring_key) = ring_key {
@@ -33,18 +36,38 @@ pub fn generate_wire(payload: Vec<u8>, ring_key: Option<&SymKey>) -> Result<Vec<
         let (nonce, encrypted_payload) = ring_key.encrypt(&payload)?;
         wire.set_nonce(nonce);
         wire.set_payload(encrypted_payload);
+        wire.set_key_rev(ring_key.name_with_rev());
     } else {
         wire.set_payload(payload);
     }
     Ok(wire.write_to_bytes()?)
 }
 
-pub fn unwrap_wire(payload: &[u8], ring_key: Option<&SymKey>) -> Result<Vec<u8>> {
+/// Unwraps a payload from the wire, decrypting it with whichever of `ring_keys` matches the
+/// revision it was encrypted with. The first key is the one we currently send with.
+///
+/// # Errors
+///
+/// * Returns `Error::UnknownRingKey` if the message names a revision we don't hold
+pub fn unwrap_wire<'a, I>(payload: &[u8], ring_keys: I) -> Result<Vec<u8>>
+where
+    I: IntoIterator<Item = &'a SymKey>,
+{
     let mut wire: Wire = protobuf::parse_from_bytes(payload)?;
-    if let Some(ring_key) = ring_key {
-        Ok(ring_key.decrypt(wire.get_nonce(), wire.get_payload())?)
+    let mut ring_keys = ring_keys.into_iter().peekable();
+    if ring_keys.peek().is_none() {
+        return Ok(wire.take_payload());
+    }
+    // Members that predate key revisions don't say which key they used; that can only ever be
+    // the one we send with.
+    let ring_key = if wire.get_key_rev().is_empty() {
+        ring_keys.next()
     } else {
-        Ok(wire.take_payload())
+        ring_keys.find(|k| k.name_with_rev() == wire.get_key_rev())
+    };
+    match ring_key {
+        Some(ring_key) => Ok(ring_key.decrypt(wire.get_nonce(), wire.get_payload())?),
+        None => Err(Error::UnknownRingKey(wire.get_key_rev().to_string())),
     }
 }
 
@@ -75,7 +98,7 @@ impl Serialize for swim::Member {
     where
         S: Serializer,
     {
-        let mut strukt = serializer.serialize_struct("member", 6)?;
+        let mut strukt = serializer.serialize_struct("member", 7)?;
         strukt.serialize_field("id", self.get_id())?;
         strukt.serialize_field(
             "incarnation",
@@ -88,6 +111,7 @@ impl Serialize for swim::Member {
             &self.get_gossip_port(),
         )?;
         strukt.serialize_field("persistent", &self.get_persistent())?;
+        strukt.serialize_field("tags", &sorted_tags(self.get_tags()))?;
         strukt.end()
     }
 }
@@ -217,7 +241,7 @@ impl Serialize for swim::SysInfo {
     where
         S: Serializer,
     {
-        let mut strukt = serializer.serialize_struct("sys_info", 6)?;
+        let mut strukt = serializer.serialize_struct("sys_info", 7)?;
         strukt.serialize_field("ip", self.get_ip())?;
         strukt.serialize_field("hostname", self.get_hostname())?;
         strukt.serialize_field("gossip_ip", self.get_gossip_ip())?;
@@ -233,10 +257,17 @@ impl Serialize for swim::SysInfo {
             "http_gateway_port",
             &self.get_http_gateway_port(),
         )?;
+        strukt.serialize_field("tags", &sorted_tags(self.get_tags()))?;
         strukt.end()
     }
 }
 
+/// Tags are kept in a `HashMap` by the protobuf messages; sort them so that rendering a member
+/// always produces the same output.
+fn sorted_tags(tags: &HashMap<String, String>) -> BTreeMap<&String, &String> {
+    tags.iter().collect()
+}
+
 impl Serialize for swim::Election_Status {
     fn serialize<S>(&self, serializer: S) -> result::Result<S::Ok, S::Error>
     where
@@ -263,3 +294,85 @@ impl Serialize for swim::Rumor_Type {
         serializer.serialize_u8(*self as u8)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use habitat_core::crypto::SymKey;
+    use protobuf::{self, Message};
+
+    use error::Error;
+    use message::swim::Wire;
+    use server::keyring::Keyring;
+    use super::{generate_wire, unwrap_wire};
+
+    fn key(rev: &str) -> SymKey {
+        let mut key = SymKey::generate_pair_for_ring("wolverine").expect("Cannot generate key");
+        key.rev = rev.to_string();
+        key
+    }
+
+    #[test]
+    fn unwrap_wire_accepts_the_pending_revision_before_it_activates() {
+        let current = key("20170101000000");
+        let pending = key("20170201000000");
+        let mut keyring = Keyring::new(Some(current.clone()));
+        keyring.stage(pending.clone(), 100, 50);
+
+        let from_current = generate_wire(b"current".to_vec(), Some(&current)).unwrap();
+        let from_pending = generate_wire(b"pending".to_vec(), Some(&pending)).unwrap();
+        assert_eq!(
+            unwrap_wire(&from_current, keyring.accepted()).unwrap(),
+            b"current".to_vec()
+        );
+        assert_eq!(
+            unwrap_wire(&from_pending, keyring.accepted()).unwrap(),
+            b"pending".to_vec()
+        );
+    }
+
+    #[test]
+    fn unwrap_wire_accepts_the_previous_revision_until_its_grace_period_ends() {
+        let previous = key("20170101000000");
+        let current = key("20170201000000");
+        let mut keyring = Keyring::new(Some(previous.clone()));
+        keyring.stage(current.clone(), 100, 50);
+        keyring.advance(100);
+
+        let from_previous = generate_wire(b"previous".to_vec(), Some(&previous)).unwrap();
+        assert_eq!(
+            unwrap_wire(&from_previous, keyring.accepted()).unwrap(),
+            b"previous".to_vec()
+        );
+        keyring.advance(150);
+        match unwrap_wire(&from_previous, keyring.accepted()) {
+            Err(Error::UnknownRingKey(rev)) => assert_eq!(rev, previous.name_with_rev()),
+            other => panic!("Expected an unknown ring key, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn unwrap_wire_uses_the_current_key_for_messages_without_a_revision() {
+        let current = key("20170201000000");
+        let pending = key("20170301000000");
+        let mut keyring = Keyring::new(Some(current.clone()));
+        keyring.stage(pending, 100, 50);
+
+        let bytes = generate_wire(b"legacy".to_vec(), Some(&current)).unwrap();
+        let mut wire: Wire = protobuf::parse_from_bytes(&bytes).unwrap();
+        wire.clear_key_rev();
+        let bytes = wire.write_to_bytes().unwrap();
+        assert_eq!(
+            unwrap_wire(&bytes, keyring.accepted()).unwrap(),
+            b"legacy".to_vec()
+        );
+    }
+
+    #[test]
+    fn unwrap_wire_passes_plaintext_through_without_keys() {
+        let bytes = generate_wire(b"plain".to_vec(), None).unwrap();
+        assert_eq!(
+            unwrap_wire(&bytes, Vec::<&SymKey>::new()).unwrap(),
+            b"plain".to_vec()
+        );
+    }
+}
@@ -31,6 +31,8 @@ pub struct Member {
     gossip_port: ::std::option::Option<i32>,
     persistent: ::std::option::Option<bool>,
     departed: ::std::option::Option<bool>,
+    pub tags: ::std::collections::HashMap<::std::string::String, ::std::string::String>,
+    digest_sync: ::std::option::Option<bool>,
     // special fields
     unknown_fields: ::protobuf::UnknownFields,
     cached_size: ::protobuf::CachedSize,
@@ -276,6 +278,66 @@ impl Member {
     fn mut_departed_for_reflect(&mut self) -> &mut ::std::option::Option<bool> {
         &mut self.departed
     }
+
+    // repeated .Member.TagsEntry tags = 8;
+
+    pub fn clear_tags(&mut self) {
+        self.tags.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_tags(&mut self, v: ::std::collections::HashMap<::std::string::String, ::std::string::String>) {
+        self.tags = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_tags(&mut self) -> &mut ::std::collections::HashMap<::std::string::String, ::std::string::String> {
+        &mut self.tags
+    }
+
+    // Take field
+    pub fn take_tags(&mut self) -> ::std::collections::HashMap<::std::string::String, ::std::string::String> {
+        ::std::mem::replace(&mut self.tags, ::std::collections::HashMap::new())
+    }
+
+    pub fn get_tags(&self) -> &::std::collections::HashMap<::std::string::String, ::std::string::String> {
+        &self.tags
+    }
+
+    fn get_tags_for_reflect(&self) -> &::std::collections::HashMap<::std::string::String, ::std::string::String> {
+        &self.tags
+    }
+
+    fn mut_tags_for_reflect(&mut self) -> &mut ::std::collections::HashMap<::std::string::String, ::std::string::String> {
+        &mut self.tags
+    }
+
+    // optional bool digest_sync = 9;
+
+    pub fn clear_digest_sync(&mut self) {
+        self.digest_sync = ::std::option::Option::None;
+    }
+
+    pub fn has_digest_sync(&self) -> bool {
+        self.digest_sync.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_digest_sync(&mut self, v: bool) {
+        self.digest_sync = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_digest_sync(&self) -> bool {
+        self.digest_sync.unwrap_or(false)
+    }
+
+    fn get_digest_sync_for_reflect(&self) -> &::std::option::Option<bool> {
+        &self.digest_sync
+    }
+
+    fn mut_digest_sync_for_reflect(&mut self) -> &mut ::std::option::Option<bool> {
+        &mut self.digest_sync
+    }
 }
 
 impl ::protobuf::Message for Member {
@@ -328,6 +390,16 @@ impl ::protobuf::Message for Member {
                     let tmp = is.read_bool()?;
                     self.departed = ::std::option::Option::Some(tmp);
                 },
+                8 => {
+                    ::protobuf::rt::read_map_into::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(wire_type, is, &mut self.tags)?;
+                },
+                9 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_bool()?;
+                    self.digest_sync = ::std::option::Option::Some(tmp);
+                },
                 _ => {
                     ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
@@ -361,6 +433,10 @@ impl ::protobuf::Message for Member {
         if let Some(v) = self.departed {
             my_size += 2;
         }
+        my_size += ::protobuf::rt::compute_map_size::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(8, &self.tags);
+        if let Some(v) = self.digest_sync {
+            my_size += 2;
+        }
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
         my_size
@@ -388,6 +464,10 @@ impl ::protobuf::Message for Member {
         if let Some(v) = self.departed {
             os.write_bool(7, v)?;
         }
+        ::protobuf::rt::write_map_with_cached_sizes::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(8, &self.tags, os)?;
+        if let Some(v) = self.digest_sync {
+            os.write_bool(9, v)?;
+        }
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
     }
@@ -467,6 +547,16 @@ impl ::protobuf::MessageStatic for Member {
                     Member::get_departed_for_reflect,
                     Member::mut_departed_for_reflect,
                 ));
+                fields.push(::protobuf::reflect::accessor::make_map_accessor::<_, ::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(
+                    "tags",
+                    Member::get_tags_for_reflect,
+                    Member::mut_tags_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeBool>(
+                    "digest_sync",
+                    Member::get_digest_sync_for_reflect,
+                    Member::mut_digest_sync_for_reflect,
+                ));
                 ::protobuf::reflect::MessageDescriptor::new::<Member>(
                     "Member",
                     fields,
@@ -486,6 +576,8 @@ impl ::protobuf::Clear for Member {
         self.clear_gossip_port();
         self.clear_persistent();
         self.clear_departed();
+        self.clear_tags();
+        self.clear_digest_sync();
         self.unknown_fields.clear();
     }
 }
@@ -1275,6 +1367,7 @@ pub struct Membership {
     // message fields
     member: ::protobuf::SingularPtrField<Member>,
     health: ::std::option::Option<Membership_Health>,
+    suspected_by: ::protobuf::SingularField<::std::string::String>,
     // special fields
     unknown_fields: ::protobuf::UnknownFields,
     cached_size: ::protobuf::CachedSize,
@@ -1365,6 +1458,50 @@ impl Membership {
     fn mut_health_for_reflect(&mut self) -> &mut ::std::option::Option<Membership_Health> {
         &mut self.health
     }
+
+    // optional string suspected_by = 3;
+
+    pub fn clear_suspected_by(&mut self) {
+        self.suspected_by.clear();
+    }
+
+    pub fn has_suspected_by(&self) -> bool {
+        self.suspected_by.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_suspected_by(&mut self, v: ::std::string::String) {
+        self.suspected_by = ::protobuf::SingularField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_suspected_by(&mut self) -> &mut ::std::string::String {
+        if self.suspected_by.is_none() {
+            self.suspected_by.set_default();
+        }
+        self.suspected_by.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_suspected_by(&mut self) -> ::std::string::String {
+        self.suspected_by.take().unwrap_or_else(|| ::std::string::String::new())
+    }
+
+    pub fn get_suspected_by(&self) -> &str {
+        match self.suspected_by.as_ref() {
+            Some(v) => &v,
+            None => "",
+        }
+    }
+
+    fn get_suspected_by_for_reflect(&self) -> &::protobuf::SingularField<::std::string::String> {
+        &self.suspected_by
+    }
+
+    fn mut_suspected_by_for_reflect(&mut self) -> &mut ::protobuf::SingularField<::std::string::String> {
+        &mut self.suspected_by
+    }
 }
 
 impl ::protobuf::Message for Membership {
@@ -1391,6 +1528,9 @@ impl ::protobuf::Message for Membership {
                     let tmp = is.read_enum()?;
                     self.health = ::std::option::Option::Some(tmp);
                 },
+                3 => {
+                    ::protobuf::rt::read_singular_string_into(wire_type, is, &mut self.suspected_by)?;
+                },
                 _ => {
                     ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
@@ -1410,6 +1550,9 @@ impl ::protobuf::Message for Membership {
         if let Some(v) = self.health {
             my_size += ::protobuf::rt::enum_size(2, v);
         }
+        if let Some(ref v) = self.suspected_by.as_ref() {
+            my_size += ::protobuf::rt::string_size(3, &v);
+        }
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
         my_size
@@ -1424,6 +1567,9 @@ impl ::protobuf::Message for Membership {
         if let Some(v) = self.health {
             os.write_enum(2, v.value())?;
         }
+        if let Some(ref v) = self.suspected_by.as_ref() {
+            os.write_string(3, &v)?;
+        }
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
     }
@@ -1478,6 +1624,11 @@ impl ::protobuf::MessageStatic for Membership {
                     Membership::get_health_for_reflect,
                     Membership::mut_health_for_reflect,
                 ));
+                fields.push(::protobuf::reflect::accessor::make_singular_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "suspected_by",
+                    Membership::get_suspected_by_for_reflect,
+                    Membership::mut_suspected_by_for_reflect,
+                ));
                 ::protobuf::reflect::MessageDescriptor::new::<Membership>(
                     "Membership",
                     fields,
@@ -1492,6 +1643,7 @@ impl ::protobuf::Clear for Membership {
     fn clear(&mut self) {
         self.clear_member();
         self.clear_health();
+        self.clear_suspected_by();
         self.unknown_fields.clear();
     }
 }
@@ -1572,6 +1724,7 @@ pub struct Election {
     suitability: ::std::option::Option<u64>,
     status: ::std::option::Option<Election_Status>,
     votes: ::protobuf::RepeatedField<::std::string::String>,
+    lease_renewal: ::std::option::Option<u64>,
     // special fields
     unknown_fields: ::protobuf::UnknownFields,
     cached_size: ::protobuf::CachedSize,
@@ -1796,6 +1949,33 @@ impl Election {
     fn mut_votes_for_reflect(&mut self) -> &mut ::protobuf::RepeatedField<::std::string::String> {
         &mut self.votes
     }
+
+    // optional uint64 lease_renewal = 7;
+
+    pub fn clear_lease_renewal(&mut self) {
+        self.lease_renewal = ::std::option::Option::None;
+    }
+
+    pub fn has_lease_renewal(&self) -> bool {
+        self.lease_renewal.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_lease_renewal(&mut self, v: u64) {
+        self.lease_renewal = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_lease_renewal(&self) -> u64 {
+        self.lease_renewal.unwrap_or(0)
+    }
+
+    fn get_lease_renewal_for_reflect(&self) -> &::std::option::Option<u64> {
+        &self.lease_renewal
+    }
+
+    fn mut_lease_renewal_for_reflect(&mut self) -> &mut ::std::option::Option<u64> {
+        &mut self.lease_renewal
+    }
 }
 
 impl ::protobuf::Message for Election {
@@ -1837,6 +2017,13 @@ impl ::protobuf::Message for Election {
                 6 => {
                     ::protobuf::rt::read_repeated_string_into(wire_type, is, &mut self.votes)?;
                 },
+                7 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.lease_renewal = ::std::option::Option::Some(tmp);
+                },
                 _ => {
                     ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
@@ -1867,6 +2054,9 @@ impl ::protobuf::Message for Election {
         for value in &self.votes {
             my_size += ::protobuf::rt::string_size(6, &value);
         };
+        if let Some(v) = self.lease_renewal {
+            my_size += ::protobuf::rt::value_size(7, v, ::protobuf::wire_format::WireTypeVarint);
+        }
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
         my_size
@@ -1891,6 +2081,9 @@ impl ::protobuf::Message for Election {
         for v in &self.votes {
             os.write_string(6, &v)?;
         };
+        if let Some(v) = self.lease_renewal {
+            os.write_uint64(7, v)?;
+        }
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
     }
@@ -1965,6 +2158,11 @@ impl ::protobuf::MessageStatic for Election {
                     Election::get_votes_for_reflect,
                     Election::mut_votes_for_reflect,
                 ));
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                    "lease_renewal",
+                    Election::get_lease_renewal_for_reflect,
+                    Election::mut_lease_renewal_for_reflect,
+                ));
                 ::protobuf::reflect::MessageDescriptor::new::<Election>(
                     "Election",
                     fields,
@@ -1983,6 +2181,7 @@ impl ::protobuf::Clear for Election {
         self.clear_suitability();
         self.clear_status();
         self.clear_votes();
+        self.clear_lease_renewal();
         self.unknown_fields.clear();
     }
 }
@@ -2061,6 +2260,7 @@ pub struct Service {
     pkg: ::protobuf::SingularField<::std::string::String>,
     cfg: ::protobuf::SingularField<::std::vec::Vec<u8>>,
     sys: ::protobuf::SingularPtrField<SysInfo>,
+    health_check: ::std::option::Option<u32>,
     // special fields
     unknown_fields: ::protobuf::UnknownFields,
     cached_size: ::protobuf::CachedSize,
@@ -2354,6 +2554,33 @@ impl Service {
     fn mut_sys_for_reflect(&mut self) -> &mut ::protobuf::SingularPtrField<SysInfo> {
         &mut self.sys
     }
+
+    // optional uint32 health_check = 13;
+
+    pub fn clear_health_check(&mut self) {
+        self.health_check = ::std::option::Option::None;
+    }
+
+    pub fn has_health_check(&self) -> bool {
+        self.health_check.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_health_check(&mut self, v: u32) {
+        self.health_check = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_health_check(&self) -> u32 {
+        self.health_check.unwrap_or(3u32)
+    }
+
+    fn get_health_check_for_reflect(&self) -> &::std::option::Option<u32> {
+        &self.health_check
+    }
+
+    fn mut_health_check_for_reflect(&mut self) -> &mut ::std::option::Option<u32> {
+        &mut self.health_check
+    }
 }
 
 impl ::protobuf::Message for Service {
@@ -2399,6 +2626,13 @@ impl ::protobuf::Message for Service {
                 12 => {
                     ::protobuf::rt::read_singular_message_into(wire_type, is, &mut self.sys)?;
                 },
+                13 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.health_check = ::std::option::Option::Some(tmp);
+                },
                 _ => {
                     ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
@@ -2433,6 +2667,9 @@ impl ::protobuf::Message for Service {
             let len = v.compute_size();
             my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
         }
+        if let Some(v) = self.health_check {
+            my_size += ::protobuf::rt::value_size(13, v, ::protobuf::wire_format::WireTypeVarint);
+        }
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
         my_size
@@ -2462,6 +2699,9 @@ impl ::protobuf::Message for Service {
             os.write_raw_varint32(v.get_cached_size())?;
             v.write_to_with_cached_sizes(os)?;
         }
+        if let Some(v) = self.health_check {
+            os.write_uint32(13, v)?;
+        }
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
     }
@@ -2541,6 +2781,11 @@ impl ::protobuf::MessageStatic for Service {
                     Service::get_sys_for_reflect,
                     Service::mut_sys_for_reflect,
                 ));
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                    "health_check",
+                    Service::get_health_check_for_reflect,
+                    Service::mut_health_check_for_reflect,
+                ));
                 ::protobuf::reflect::MessageDescriptor::new::<Service>(
                     "Service",
                     fields,
@@ -2560,6 +2805,7 @@ impl ::protobuf::Clear for Service {
         self.clear_pkg();
         self.clear_cfg();
         self.clear_sys();
+        self.clear_health_check();
         self.unknown_fields.clear();
     }
 }
@@ -3325,6 +3571,7 @@ pub struct SysInfo {
     gossip_port: ::std::option::Option<u32>,
     http_gateway_ip: ::protobuf::SingularField<::std::string::String>,
     http_gateway_port: ::std::option::Option<u32>,
+    pub tags: ::std::collections::HashMap<::std::string::String, ::std::string::String>,
     // special fields
     unknown_fields: ::protobuf::UnknownFields,
     cached_size: ::protobuf::CachedSize,
@@ -3577,6 +3824,39 @@ impl SysInfo {
     fn mut_http_gateway_port_for_reflect(&mut self) -> &mut ::std::option::Option<u32> {
         &mut self.http_gateway_port
     }
+
+    // repeated .SysInfo.TagsEntry tags = 7;
+
+    pub fn clear_tags(&mut self) {
+        self.tags.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_tags(&mut self, v: ::std::collections::HashMap<::std::string::String, ::std::string::String>) {
+        self.tags = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_tags(&mut self) -> &mut ::std::collections::HashMap<::std::string::String, ::std::string::String> {
+        &mut self.tags
+    }
+
+    // Take field
+    pub fn take_tags(&mut self) -> ::std::collections::HashMap<::std::string::String, ::std::string::String> {
+        ::std::mem::replace(&mut self.tags, ::std::collections::HashMap::new())
+    }
+
+    pub fn get_tags(&self) -> &::std::collections::HashMap<::std::string::String, ::std::string::String> {
+        &self.tags
+    }
+
+    fn get_tags_for_reflect(&self) -> &::std::collections::HashMap<::std::string::String, ::std::string::String> {
+        &self.tags
+    }
+
+    fn mut_tags_for_reflect(&mut self) -> &mut ::std::collections::HashMap<::std::string::String, ::std::string::String> {
+        &mut self.tags
+    }
 }
 
 impl ::protobuf::Message for SysInfo {
@@ -3614,6 +3894,9 @@ impl ::protobuf::Message for SysInfo {
                     let tmp = is.read_uint32()?;
                     self.http_gateway_port = ::std::option::Option::Some(tmp);
                 },
+                7 => {
+                    ::protobuf::rt::read_map_into::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(wire_type, is, &mut self.tags)?;
+                },
                 _ => {
                     ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
@@ -3644,6 +3927,7 @@ impl ::protobuf::Message for SysInfo {
         if let Some(v) = self.http_gateway_port {
             my_size += ::protobuf::rt::value_size(6, v, ::protobuf::wire_format::WireTypeVarint);
         }
+        my_size += ::protobuf::rt::compute_map_size::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(7, &self.tags);
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
         my_size
@@ -3668,6 +3952,7 @@ impl ::protobuf::Message for SysInfo {
         if let Some(v) = self.http_gateway_port {
             os.write_uint32(6, v)?;
         }
+        ::protobuf::rt::write_map_with_cached_sizes::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(7, &self.tags, os)?;
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
     }
@@ -3742,6 +4027,11 @@ impl ::protobuf::MessageStatic for SysInfo {
                     SysInfo::get_http_gateway_port_for_reflect,
                     SysInfo::mut_http_gateway_port_for_reflect,
                 ));
+                fields.push(::protobuf::reflect::accessor::make_map_accessor::<_, ::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(
+                    "tags",
+                    SysInfo::get_tags_for_reflect,
+                    SysInfo::mut_tags_for_reflect,
+                ));
                 ::protobuf::reflect::MessageDescriptor::new::<SysInfo>(
                     "SysInfo",
                     fields,
@@ -3760,6 +4050,7 @@ impl ::protobuf::Clear for SysInfo {
         self.clear_gossip_port();
         self.clear_http_gateway_ip();
         self.clear_http_gateway_port();
+        self.clear_tags();
         self.unknown_fields.clear();
     }
 }
@@ -3963,275 +4254,1668 @@ impl ::protobuf::reflect::ProtobufValue for Departure {
 }
 
 #[derive(PartialEq,Clone,Default)]
-pub struct Swim {
+pub struct Canary {
     // message fields
-    field_type: ::std::option::Option<Swim_Type>,
-    membership: ::protobuf::RepeatedField<Membership>,
-    // message oneof groups
-    payload: ::std::option::Option<Swim_oneof_payload>,
+    member_id: ::protobuf::SingularField<::std::string::String>,
+    service_group: ::protobuf::SingularField<::std::string::String>,
+    incarnation: ::std::option::Option<u64>,
+    pkg: ::protobuf::SingularField<::std::string::String>,
+    previous_pkg: ::protobuf::SingularField<::std::string::String>,
+    canaries: ::protobuf::RepeatedField<::std::string::String>,
+    status: ::std::option::Option<Canary_Status>,
     // special fields
     unknown_fields: ::protobuf::UnknownFields,
     cached_size: ::protobuf::CachedSize,
 }
 
 // see codegen.rs for the explanation why impl Sync explicitly
-unsafe impl ::std::marker::Sync for Swim {}
-
-#[derive(Clone,PartialEq)]
-pub enum Swim_oneof_payload {
-    ping(Ping),
-    ack(Ack),
-    pingreq(PingReq),
-}
+unsafe impl ::std::marker::Sync for Canary {}
 
-impl Swim {
-    pub fn new() -> Swim {
+impl Canary {
+    pub fn new() -> Canary {
         ::std::default::Default::default()
     }
 
-    pub fn default_instance() -> &'static Swim {
-        static mut instance: ::protobuf::lazy::Lazy<Swim> = ::protobuf::lazy::Lazy {
+    pub fn default_instance() -> &'static Canary {
+        static mut instance: ::protobuf::lazy::Lazy<Canary> = ::protobuf::lazy::Lazy {
             lock: ::protobuf::lazy::ONCE_INIT,
-            ptr: 0 as *const Swim,
+            ptr: 0 as *const Canary,
         };
         unsafe {
-            instance.get(Swim::new)
+            instance.get(Canary::new)
         }
     }
 
-    // required .Swim.Type type = 1;
+    // optional string member_id = 1;
 
-    pub fn clear_field_type(&mut self) {
-        self.field_type = ::std::option::Option::None;
+    pub fn clear_member_id(&mut self) {
+        self.member_id.clear();
     }
 
-    pub fn has_field_type(&self) -> bool {
-        self.field_type.is_some()
+    pub fn has_member_id(&self) -> bool {
+        self.member_id.is_some()
     }
 
     // Param is passed by value, moved
-    pub fn set_field_type(&mut self, v: Swim_Type) {
-        self.field_type = ::std::option::Option::Some(v);
+    pub fn set_member_id(&mut self, v: ::std::string::String) {
+        self.member_id = ::protobuf::SingularField::some(v);
     }
 
-    pub fn get_field_type(&self) -> Swim_Type {
-        self.field_type.unwrap_or(Swim_Type::PING)
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_member_id(&mut self) -> &mut ::std::string::String {
+        if self.member_id.is_none() {
+            self.member_id.set_default();
+        }
+        self.member_id.as_mut().unwrap()
     }
 
-    fn get_field_type_for_reflect(&self) -> &::std::option::Option<Swim_Type> {
-        &self.field_type
+    // Take field
+    pub fn take_member_id(&mut self) -> ::std::string::String {
+        self.member_id.take().unwrap_or_else(|| ::std::string::String::new())
     }
 
-    fn mut_field_type_for_reflect(&mut self) -> &mut ::std::option::Option<Swim_Type> {
-        &mut self.field_type
+    pub fn get_member_id(&self) -> &str {
+        match self.member_id.as_ref() {
+            Some(v) => &v,
+            None => "",
+        }
     }
 
-    // optional .Ping ping = 2;
+    fn get_member_id_for_reflect(&self) -> &::protobuf::SingularField<::std::string::String> {
+        &self.member_id
+    }
 
-    pub fn clear_ping(&mut self) {
-        self.payload = ::std::option::Option::None;
+    fn mut_member_id_for_reflect(&mut self) -> &mut ::protobuf::SingularField<::std::string::String> {
+        &mut self.member_id
     }
 
-    pub fn has_ping(&self) -> bool {
-        match self.payload {
-            ::std::option::Option::Some(Swim_oneof_payload::ping(..)) => true,
-            _ => false,
-        }
+    // optional string service_group = 2;
+
+    pub fn clear_service_group(&mut self) {
+        self.service_group.clear();
+    }
+
+    pub fn has_service_group(&self) -> bool {
+        self.service_group.is_some()
     }
 
     // Param is passed by value, moved
-    pub fn set_ping(&mut self, v: Ping) {
-        self.payload = ::std::option::Option::Some(Swim_oneof_payload::ping(v))
+    pub fn set_service_group(&mut self, v: ::std::string::String) {
+        self.service_group = ::protobuf::SingularField::some(v);
     }
 
     // Mutable pointer to the field.
-    pub fn mut_ping(&mut self) -> &mut Ping {
-        if let ::std::option::Option::Some(Swim_oneof_payload::ping(_)) = self.payload {
-        } else {
-            self.payload = ::std::option::Option::Some(Swim_oneof_payload::ping(Ping::new()));
-        }
-        match self.payload {
-            ::std::option::Option::Some(Swim_oneof_payload::ping(ref mut v)) => v,
-            _ => panic!(),
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_service_group(&mut self) -> &mut ::std::string::String {
+        if self.service_group.is_none() {
+            self.service_group.set_default();
         }
+        self.service_group.as_mut().unwrap()
     }
 
     // Take field
-    pub fn take_ping(&mut self) -> Ping {
-        if self.has_ping() {
-            match self.payload.take() {
-                ::std::option::Option::Some(Swim_oneof_payload::ping(v)) => v,
-                _ => panic!(),
-            }
-        } else {
-            Ping::new()
-        }
+    pub fn take_service_group(&mut self) -> ::std::string::String {
+        self.service_group.take().unwrap_or_else(|| ::std::string::String::new())
     }
 
-    pub fn get_ping(&self) -> &Ping {
-        match self.payload {
+    pub fn get_service_group(&self) -> &str {
+        match self.service_group.as_ref() {
+            Some(v) => &v,
+            None => "",
+        }
+    }
+
+    fn get_service_group_for_reflect(&self) -> &::protobuf::SingularField<::std::string::String> {
+        &self.service_group
+    }
+
+    fn mut_service_group_for_reflect(&mut self) -> &mut ::protobuf::SingularField<::std::string::String> {
+        &mut self.service_group
+    }
+
+    // optional uint64 incarnation = 3;
+
+    pub fn clear_incarnation(&mut self) {
+        self.incarnation = ::std::option::Option::None;
+    }
+
+    pub fn has_incarnation(&self) -> bool {
+        self.incarnation.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_incarnation(&mut self, v: u64) {
+        self.incarnation = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_incarnation(&self) -> u64 {
+        self.incarnation.unwrap_or(0)
+    }
+
+    fn get_incarnation_for_reflect(&self) -> &::std::option::Option<u64> {
+        &self.incarnation
+    }
+
+    fn mut_incarnation_for_reflect(&mut self) -> &mut ::std::option::Option<u64> {
+        &mut self.incarnation
+    }
+
+    // optional string pkg = 4;
+
+    pub fn clear_pkg(&mut self) {
+        self.pkg.clear();
+    }
+
+    pub fn has_pkg(&self) -> bool {
+        self.pkg.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_pkg(&mut self, v: ::std::string::String) {
+        self.pkg = ::protobuf::SingularField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_pkg(&mut self) -> &mut ::std::string::String {
+        if self.pkg.is_none() {
+            self.pkg.set_default();
+        }
+        self.pkg.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_pkg(&mut self) -> ::std::string::String {
+        self.pkg.take().unwrap_or_else(|| ::std::string::String::new())
+    }
+
+    pub fn get_pkg(&self) -> &str {
+        match self.pkg.as_ref() {
+            Some(v) => &v,
+            None => "",
+        }
+    }
+
+    fn get_pkg_for_reflect(&self) -> &::protobuf::SingularField<::std::string::String> {
+        &self.pkg
+    }
+
+    fn mut_pkg_for_reflect(&mut self) -> &mut ::protobuf::SingularField<::std::string::String> {
+        &mut self.pkg
+    }
+
+    // optional string previous_pkg = 5;
+
+    pub fn clear_previous_pkg(&mut self) {
+        self.previous_pkg.clear();
+    }
+
+    pub fn has_previous_pkg(&self) -> bool {
+        self.previous_pkg.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_previous_pkg(&mut self, v: ::std::string::String) {
+        self.previous_pkg = ::protobuf::SingularField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_previous_pkg(&mut self) -> &mut ::std::string::String {
+        if self.previous_pkg.is_none() {
+            self.previous_pkg.set_default();
+        }
+        self.previous_pkg.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_previous_pkg(&mut self) -> ::std::string::String {
+        self.previous_pkg.take().unwrap_or_else(|| ::std::string::String::new())
+    }
+
+    pub fn get_previous_pkg(&self) -> &str {
+        match self.previous_pkg.as_ref() {
+            Some(v) => &v,
+            None => "",
+        }
+    }
+
+    fn get_previous_pkg_for_reflect(&self) -> &::protobuf::SingularField<::std::string::String> {
+        &self.previous_pkg
+    }
+
+    fn mut_previous_pkg_for_reflect(&mut self) -> &mut ::protobuf::SingularField<::std::string::String> {
+        &mut self.previous_pkg
+    }
+
+    // repeated string canaries = 6;
+
+    pub fn clear_canaries(&mut self) {
+        self.canaries.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_canaries(&mut self, v: ::protobuf::RepeatedField<::std::string::String>) {
+        self.canaries = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_canaries(&mut self) -> &mut ::protobuf::RepeatedField<::std::string::String> {
+        &mut self.canaries
+    }
+
+    // Take field
+    pub fn take_canaries(&mut self) -> ::protobuf::RepeatedField<::std::string::String> {
+        ::std::mem::replace(&mut self.canaries, ::protobuf::RepeatedField::new())
+    }
+
+    pub fn get_canaries(&self) -> &[::std::string::String] {
+        &self.canaries
+    }
+
+    fn get_canaries_for_reflect(&self) -> &::protobuf::RepeatedField<::std::string::String> {
+        &self.canaries
+    }
+
+    fn mut_canaries_for_reflect(&mut self) -> &mut ::protobuf::RepeatedField<::std::string::String> {
+        &mut self.canaries
+    }
+
+    // optional .Canary.Status status = 7;
+
+    pub fn clear_status(&mut self) {
+        self.status = ::std::option::Option::None;
+    }
+
+    pub fn has_status(&self) -> bool {
+        self.status.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_status(&mut self, v: Canary_Status) {
+        self.status = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_status(&self) -> Canary_Status {
+        self.status.unwrap_or(Canary_Status::Testing)
+    }
+
+    fn get_status_for_reflect(&self) -> &::std::option::Option<Canary_Status> {
+        &self.status
+    }
+
+    fn mut_status_for_reflect(&mut self) -> &mut ::std::option::Option<Canary_Status> {
+        &mut self.status
+    }
+}
+
+impl ::protobuf::Message for Canary {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_singular_string_into(wire_type, is, &mut self.member_id)?;
+                },
+                2 => {
+                    ::protobuf::rt::read_singular_string_into(wire_type, is, &mut self.service_group)?;
+                },
+                3 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.incarnation = ::std::option::Option::Some(tmp);
+                },
+                4 => {
+                    ::protobuf::rt::read_singular_string_into(wire_type, is, &mut self.pkg)?;
+                },
+                5 => {
+                    ::protobuf::rt::read_singular_string_into(wire_type, is, &mut self.previous_pkg)?;
+                },
+                6 => {
+                    ::protobuf::rt::read_repeated_string_into(wire_type, is, &mut self.canaries)?;
+                },
+                7 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_enum()?;
+                    self.status = ::std::option::Option::Some(tmp);
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if let Some(ref v) = self.member_id.as_ref() {
+            my_size += ::protobuf::rt::string_size(1, &v);
+        }
+        if let Some(ref v) = self.service_group.as_ref() {
+            my_size += ::protobuf::rt::string_size(2, &v);
+        }
+        if let Some(v) = self.incarnation {
+            my_size += ::protobuf::rt::value_size(3, v, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if let Some(ref v) = self.pkg.as_ref() {
+            my_size += ::protobuf::rt::string_size(4, &v);
+        }
+        if let Some(ref v) = self.previous_pkg.as_ref() {
+            my_size += ::protobuf::rt::string_size(5, &v);
+        }
+        for value in &self.canaries {
+            my_size += ::protobuf::rt::string_size(6, &value);
+        };
+        if let Some(v) = self.status {
+            my_size += ::protobuf::rt::enum_size(7, v);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
+        if let Some(ref v) = self.member_id.as_ref() {
+            os.write_string(1, &v)?;
+        }
+        if let Some(ref v) = self.service_group.as_ref() {
+            os.write_string(2, &v)?;
+        }
+        if let Some(v) = self.incarnation {
+            os.write_uint64(3, v)?;
+        }
+        if let Some(ref v) = self.pkg.as_ref() {
+            os.write_string(4, &v)?;
+        }
+        if let Some(ref v) = self.previous_pkg.as_ref() {
+            os.write_string(5, &v)?;
+        }
+        for v in &self.canaries {
+            os.write_string(6, &v)?;
+        };
+        if let Some(v) = self.status {
+            os.write_enum(7, v.value())?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &::std::any::Any {
+        self as &::std::any::Any
+    }
+    fn as_any_mut(&mut self) -> &mut ::std::any::Any {
+        self as &mut ::std::any::Any
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        ::protobuf::MessageStatic::descriptor_static(None::<Self>)
+    }
+}
+
+impl ::protobuf::MessageStatic for Canary {
+    fn new() -> Canary {
+        Canary::new()
+    }
+
+    fn descriptor_static(_: ::std::option::Option<Canary>) -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_singular_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "member_id",
+                    Canary::get_member_id_for_reflect,
+                    Canary::mut_member_id_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_singular_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "service_group",
+                    Canary::get_service_group_for_reflect,
+                    Canary::mut_service_group_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                    "incarnation",
+                    Canary::get_incarnation_for_reflect,
+                    Canary::mut_incarnation_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_singular_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "pkg",
+                    Canary::get_pkg_for_reflect,
+                    Canary::mut_pkg_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_singular_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "previous_pkg",
+                    Canary::get_previous_pkg_for_reflect,
+                    Canary::mut_previous_pkg_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "canaries",
+                    Canary::get_canaries_for_reflect,
+                    Canary::mut_canaries_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeEnum<Canary_Status>>(
+                    "status",
+                    Canary::get_status_for_reflect,
+                    Canary::mut_status_for_reflect,
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<Canary>(
+                    "Canary",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+}
+
+impl ::protobuf::Clear for Canary {
+    fn clear(&mut self) {
+        self.clear_member_id();
+        self.clear_service_group();
+        self.clear_incarnation();
+        self.clear_pkg();
+        self.clear_previous_pkg();
+        self.clear_canaries();
+        self.clear_status();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for Canary {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for Canary {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(Clone,PartialEq,Eq,Debug,Hash)]
+pub enum Canary_Status {
+    Testing = 1,
+    Promoted = 2,
+    RolledBack = 3,
+}
+
+impl ::protobuf::ProtobufEnum for Canary_Status {
+    fn value(&self) -> i32 {
+        *self as i32
+    }
+
+    fn from_i32(value: i32) -> ::std::option::Option<Canary_Status> {
+        match value {
+            1 => ::std::option::Option::Some(Canary_Status::Testing),
+            2 => ::std::option::Option::Some(Canary_Status::Promoted),
+            3 => ::std::option::Option::Some(Canary_Status::RolledBack),
+            _ => ::std::option::Option::None
+        }
+    }
+
+    fn values() -> &'static [Self] {
+        static values: &'static [Canary_Status] = &[
+            Canary_Status::Testing,
+            Canary_Status::Promoted,
+            Canary_Status::RolledBack,
+        ];
+        values
+    }
+
+    fn enum_descriptor_static(_: ::std::option::Option<Canary_Status>) -> &'static ::protobuf::reflect::EnumDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::EnumDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::EnumDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                ::protobuf::reflect::EnumDescriptor::new("Canary_Status", file_descriptor_proto())
+            })
+        }
+    }
+}
+
+impl ::std::marker::Copy for Canary_Status {
+}
+
+impl ::protobuf::reflect::ProtobufValue for Canary_Status {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Enum(self.descriptor())
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct Swim {
+    // message fields
+    field_type: ::std::option::Option<Swim_Type>,
+    membership: ::protobuf::RepeatedField<Membership>,
+    // message oneof groups
+    payload: ::std::option::Option<Swim_oneof_payload>,
+    // special fields
+    unknown_fields: ::protobuf::UnknownFields,
+    cached_size: ::protobuf::CachedSize,
+}
+
+// see codegen.rs for the explanation why impl Sync explicitly
+unsafe impl ::std::marker::Sync for Swim {}
+
+#[derive(Clone,PartialEq)]
+pub enum Swim_oneof_payload {
+    ping(Ping),
+    ack(Ack),
+    pingreq(PingReq),
+}
+
+impl Swim {
+    pub fn new() -> Swim {
+        ::std::default::Default::default()
+    }
+
+    pub fn default_instance() -> &'static Swim {
+        static mut instance: ::protobuf::lazy::Lazy<Swim> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const Swim,
+        };
+        unsafe {
+            instance.get(Swim::new)
+        }
+    }
+
+    // required .Swim.Type type = 1;
+
+    pub fn clear_field_type(&mut self) {
+        self.field_type = ::std::option::Option::None;
+    }
+
+    pub fn has_field_type(&self) -> bool {
+        self.field_type.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_field_type(&mut self, v: Swim_Type) {
+        self.field_type = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_field_type(&self) -> Swim_Type {
+        self.field_type.unwrap_or(Swim_Type::PING)
+    }
+
+    fn get_field_type_for_reflect(&self) -> &::std::option::Option<Swim_Type> {
+        &self.field_type
+    }
+
+    fn mut_field_type_for_reflect(&mut self) -> &mut ::std::option::Option<Swim_Type> {
+        &mut self.field_type
+    }
+
+    // optional .Ping ping = 2;
+
+    pub fn clear_ping(&mut self) {
+        self.payload = ::std::option::Option::None;
+    }
+
+    pub fn has_ping(&self) -> bool {
+        match self.payload {
+            ::std::option::Option::Some(Swim_oneof_payload::ping(..)) => true,
+            _ => false,
+        }
+    }
+
+    // Param is passed by value, moved
+    pub fn set_ping(&mut self, v: Ping) {
+        self.payload = ::std::option::Option::Some(Swim_oneof_payload::ping(v))
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_ping(&mut self) -> &mut Ping {
+        if let ::std::option::Option::Some(Swim_oneof_payload::ping(_)) = self.payload {
+        } else {
+            self.payload = ::std::option::Option::Some(Swim_oneof_payload::ping(Ping::new()));
+        }
+        match self.payload {
+            ::std::option::Option::Some(Swim_oneof_payload::ping(ref mut v)) => v,
+            _ => panic!(),
+        }
+    }
+
+    // Take field
+    pub fn take_ping(&mut self) -> Ping {
+        if self.has_ping() {
+            match self.payload.take() {
+                ::std::option::Option::Some(Swim_oneof_payload::ping(v)) => v,
+                _ => panic!(),
+            }
+        } else {
+            Ping::new()
+        }
+    }
+
+    pub fn get_ping(&self) -> &Ping {
+        match self.payload {
             ::std::option::Option::Some(Swim_oneof_payload::ping(ref v)) => v,
             _ => Ping::default_instance(),
         }
     }
 
-    // optional .Ack ack = 3;
+    // optional .Ack ack = 3;
+
+    pub fn clear_ack(&mut self) {
+        self.payload = ::std::option::Option::None;
+    }
+
+    pub fn has_ack(&self) -> bool {
+        match self.payload {
+            ::std::option::Option::Some(Swim_oneof_payload::ack(..)) => true,
+            _ => false,
+        }
+    }
+
+    // Param is passed by value, moved
+    pub fn set_ack(&mut self, v: Ack) {
+        self.payload = ::std::option::Option::Some(Swim_oneof_payload::ack(v))
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_ack(&mut self) -> &mut Ack {
+        if let ::std::option::Option::Some(Swim_oneof_payload::ack(_)) = self.payload {
+        } else {
+            self.payload = ::std::option::Option::Some(Swim_oneof_payload::ack(Ack::new()));
+        }
+        match self.payload {
+            ::std::option::Option::Some(Swim_oneof_payload::ack(ref mut v)) => v,
+            _ => panic!(),
+        }
+    }
+
+    // Take field
+    pub fn take_ack(&mut self) -> Ack {
+        if self.has_ack() {
+            match self.payload.take() {
+                ::std::option::Option::Some(Swim_oneof_payload::ack(v)) => v,
+                _ => panic!(),
+            }
+        } else {
+            Ack::new()
+        }
+    }
+
+    pub fn get_ack(&self) -> &Ack {
+        match self.payload {
+            ::std::option::Option::Some(Swim_oneof_payload::ack(ref v)) => v,
+            _ => Ack::default_instance(),
+        }
+    }
+
+    // optional .PingReq pingreq = 4;
+
+    pub fn clear_pingreq(&mut self) {
+        self.payload = ::std::option::Option::None;
+    }
+
+    pub fn has_pingreq(&self) -> bool {
+        match self.payload {
+            ::std::option::Option::Some(Swim_oneof_payload::pingreq(..)) => true,
+            _ => false,
+        }
+    }
+
+    // Param is passed by value, moved
+    pub fn set_pingreq(&mut self, v: PingReq) {
+        self.payload = ::std::option::Option::Some(Swim_oneof_payload::pingreq(v))
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_pingreq(&mut self) -> &mut PingReq {
+        if let ::std::option::Option::Some(Swim_oneof_payload::pingreq(_)) = self.payload {
+        } else {
+            self.payload = ::std::option::Option::Some(Swim_oneof_payload::pingreq(PingReq::new()));
+        }
+        match self.payload {
+            ::std::option::Option::Some(Swim_oneof_payload::pingreq(ref mut v)) => v,
+            _ => panic!(),
+        }
+    }
+
+    // Take field
+    pub fn take_pingreq(&mut self) -> PingReq {
+        if self.has_pingreq() {
+            match self.payload.take() {
+                ::std::option::Option::Some(Swim_oneof_payload::pingreq(v)) => v,
+                _ => panic!(),
+            }
+        } else {
+            PingReq::new()
+        }
+    }
+
+    pub fn get_pingreq(&self) -> &PingReq {
+        match self.payload {
+            ::std::option::Option::Some(Swim_oneof_payload::pingreq(ref v)) => v,
+            _ => PingReq::default_instance(),
+        }
+    }
+
+    // repeated .Membership membership = 5;
+
+    pub fn clear_membership(&mut self) {
+        self.membership.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_membership(&mut self, v: ::protobuf::RepeatedField<Membership>) {
+        self.membership = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_membership(&mut self) -> &mut ::protobuf::RepeatedField<Membership> {
+        &mut self.membership
+    }
+
+    // Take field
+    pub fn take_membership(&mut self) -> ::protobuf::RepeatedField<Membership> {
+        ::std::mem::replace(&mut self.membership, ::protobuf::RepeatedField::new())
+    }
+
+    pub fn get_membership(&self) -> &[Membership] {
+        &self.membership
+    }
+
+    fn get_membership_for_reflect(&self) -> &::protobuf::RepeatedField<Membership> {
+        &self.membership
+    }
+
+    fn mut_membership_for_reflect(&mut self) -> &mut ::protobuf::RepeatedField<Membership> {
+        &mut self.membership
+    }
+}
+
+impl ::protobuf::Message for Swim {
+    fn is_initialized(&self) -> bool {
+        if self.field_type.is_none() {
+            return false;
+        }
+        if let Some(Swim_oneof_payload::ping(ref v)) = self.payload {
+            if !v.is_initialized() {
+                return false;
+            }
+        }
+        if let Some(Swim_oneof_payload::ack(ref v)) = self.payload {
+            if !v.is_initialized() {
+                return false;
+            }
+        }
+        if let Some(Swim_oneof_payload::pingreq(ref v)) = self.payload {
+            if !v.is_initialized() {
+                return false;
+            }
+        }
+        for v in &self.membership {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_enum()?;
+                    self.field_type = ::std::option::Option::Some(tmp);
+                },
+                2 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    self.payload = ::std::option::Option::Some(Swim_oneof_payload::ping(is.read_message()?));
+                },
+                3 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    self.payload = ::std::option::Option::Some(Swim_oneof_payload::ack(is.read_message()?));
+                },
+                4 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    self.payload = ::std::option::Option::Some(Swim_oneof_payload::pingreq(is.read_message()?));
+                },
+                5 => {
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.membership)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if let Some(v) = self.field_type {
+            my_size += ::protobuf::rt::enum_size(1, v);
+        }
+        for value in &self.membership {
+            let len = value.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        };
+        if let ::std::option::Option::Some(ref v) = self.payload {
+            match v {
+                &Swim_oneof_payload::ping(ref v) => {
+                    let len = v.compute_size();
+                    my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+                },
+                &Swim_oneof_payload::ack(ref v) => {
+                    let len = v.compute_size();
+                    my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+                },
+                &Swim_oneof_payload::pingreq(ref v) => {
+                    let len = v.compute_size();
+                    my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+                },
+            };
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
+        if let Some(v) = self.field_type {
+            os.write_enum(1, v.value())?;
+        }
+        for v in &self.membership {
+            os.write_tag(5, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        };
+        if let ::std::option::Option::Some(ref v) = self.payload {
+            match v {
+                &Swim_oneof_payload::ping(ref v) => {
+                    os.write_tag(2, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+                    os.write_raw_varint32(v.get_cached_size())?;
+                    v.write_to_with_cached_sizes(os)?;
+                },
+                &Swim_oneof_payload::ack(ref v) => {
+                    os.write_tag(3, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+                    os.write_raw_varint32(v.get_cached_size())?;
+                    v.write_to_with_cached_sizes(os)?;
+                },
+                &Swim_oneof_payload::pingreq(ref v) => {
+                    os.write_tag(4, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+                    os.write_raw_varint32(v.get_cached_size())?;
+                    v.write_to_with_cached_sizes(os)?;
+                },
+            };
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &::std::any::Any {
+        self as &::std::any::Any
+    }
+    fn as_any_mut(&mut self) -> &mut ::std::any::Any {
+        self as &mut ::std::any::Any
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        ::protobuf::MessageStatic::descriptor_static(None::<Self>)
+    }
+}
+
+impl ::protobuf::MessageStatic for Swim {
+    fn new() -> Swim {
+        Swim::new()
+    }
+
+    fn descriptor_static(_: ::std::option::Option<Swim>) -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeEnum<Swim_Type>>(
+                    "type",
+                    Swim::get_field_type_for_reflect,
+                    Swim::mut_field_type_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_singular_message_accessor::<_, Ping>(
+                    "ping",
+                    Swim::has_ping,
+                    Swim::get_ping,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_singular_message_accessor::<_, Ack>(
+                    "ack",
+                    Swim::has_ack,
+                    Swim::get_ack,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_singular_message_accessor::<_, PingReq>(
+                    "pingreq",
+                    Swim::has_pingreq,
+                    Swim::get_pingreq,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<Membership>>(
+                    "membership",
+                    Swim::get_membership_for_reflect,
+                    Swim::mut_membership_for_reflect,
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<Swim>(
+                    "Swim",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+}
+
+impl ::protobuf::Clear for Swim {
+    fn clear(&mut self) {
+        self.clear_field_type();
+        self.clear_ping();
+        self.clear_ack();
+        self.clear_pingreq();
+        self.clear_membership();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for Swim {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for Swim {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(Clone,PartialEq,Eq,Debug,Hash)]
+pub enum Swim_Type {
+    PING = 1,
+    ACK = 2,
+    PINGREQ = 3,
+}
+
+impl ::protobuf::ProtobufEnum for Swim_Type {
+    fn value(&self) -> i32 {
+        *self as i32
+    }
+
+    fn from_i32(value: i32) -> ::std::option::Option<Swim_Type> {
+        match value {
+            1 => ::std::option::Option::Some(Swim_Type::PING),
+            2 => ::std::option::Option::Some(Swim_Type::ACK),
+            3 => ::std::option::Option::Some(Swim_Type::PINGREQ),
+            _ => ::std::option::Option::None
+        }
+    }
+
+    fn values() -> &'static [Self] {
+        static values: &'static [Swim_Type] = &[
+            Swim_Type::PING,
+            Swim_Type::ACK,
+            Swim_Type::PINGREQ,
+        ];
+        values
+    }
+
+    fn enum_descriptor_static(_: ::std::option::Option<Swim_Type>) -> &'static ::protobuf::reflect::EnumDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::EnumDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::EnumDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                ::protobuf::reflect::EnumDescriptor::new("Swim_Type", file_descriptor_proto())
+            })
+        }
+    }
+}
+
+impl ::std::marker::Copy for Swim_Type {
+}
+
+impl ::protobuf::reflect::ProtobufValue for Swim_Type {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Enum(self.descriptor())
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct RingKey {
+    // message fields
+    name: ::protobuf::SingularField<::std::string::String>,
+    revision: ::protobuf::SingularField<::std::string::String>,
+    key: ::protobuf::SingularField<::std::vec::Vec<u8>>,
+    activate_at: ::std::option::Option<i64>,
+    grace_period: ::std::option::Option<i64>,
+    // special fields
+    unknown_fields: ::protobuf::UnknownFields,
+    cached_size: ::protobuf::CachedSize,
+}
+
+// see codegen.rs for the explanation why impl Sync explicitly
+unsafe impl ::std::marker::Sync for RingKey {}
+
+impl RingKey {
+    pub fn new() -> RingKey {
+        ::std::default::Default::default()
+    }
+
+    pub fn default_instance() -> &'static RingKey {
+        static mut instance: ::protobuf::lazy::Lazy<RingKey> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const RingKey,
+        };
+        unsafe {
+            instance.get(RingKey::new)
+        }
+    }
+
+    // optional string name = 1;
+
+    pub fn clear_name(&mut self) {
+        self.name.clear();
+    }
+
+    pub fn has_name(&self) -> bool {
+        self.name.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_name(&mut self, v: ::std::string::String) {
+        self.name = ::protobuf::SingularField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_name(&mut self) -> &mut ::std::string::String {
+        if self.name.is_none() {
+            self.name.set_default();
+        }
+        self.name.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_name(&mut self) -> ::std::string::String {
+        self.name.take().unwrap_or_else(|| ::std::string::String::new())
+    }
+
+    pub fn get_name(&self) -> &str {
+        match self.name.as_ref() {
+            Some(v) => &v,
+            None => "",
+        }
+    }
+
+    fn get_name_for_reflect(&self) -> &::protobuf::SingularField<::std::string::String> {
+        &self.name
+    }
+
+    fn mut_name_for_reflect(&mut self) -> &mut ::protobuf::SingularField<::std::string::String> {
+        &mut self.name
+    }
+
+    // optional string revision = 2;
+
+    pub fn clear_revision(&mut self) {
+        self.revision.clear();
+    }
+
+    pub fn has_revision(&self) -> bool {
+        self.revision.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_revision(&mut self, v: ::std::string::String) {
+        self.revision = ::protobuf::SingularField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_revision(&mut self) -> &mut ::std::string::String {
+        if self.revision.is_none() {
+            self.revision.set_default();
+        }
+        self.revision.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_revision(&mut self) -> ::std::string::String {
+        self.revision.take().unwrap_or_else(|| ::std::string::String::new())
+    }
+
+    pub fn get_revision(&self) -> &str {
+        match self.revision.as_ref() {
+            Some(v) => &v,
+            None => "",
+        }
+    }
+
+    fn get_revision_for_reflect(&self) -> &::protobuf::SingularField<::std::string::String> {
+        &self.revision
+    }
+
+    fn mut_revision_for_reflect(&mut self) -> &mut ::protobuf::SingularField<::std::string::String> {
+        &mut self.revision
+    }
+
+    // optional bytes key = 3;
+
+    pub fn clear_key(&mut self) {
+        self.key.clear();
+    }
+
+    pub fn has_key(&self) -> bool {
+        self.key.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_key(&mut self, v: ::std::vec::Vec<u8>) {
+        self.key = ::protobuf::SingularField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_key(&mut self) -> &mut ::std::vec::Vec<u8> {
+        if self.key.is_none() {
+            self.key.set_default();
+        }
+        self.key.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_key(&mut self) -> ::std::vec::Vec<u8> {
+        self.key.take().unwrap_or_else(|| ::std::vec::Vec::new())
+    }
+
+    pub fn get_key(&self) -> &[u8] {
+        match self.key.as_ref() {
+            Some(v) => &v,
+            None => &[],
+        }
+    }
+
+    fn get_key_for_reflect(&self) -> &::protobuf::SingularField<::std::vec::Vec<u8>> {
+        &self.key
+    }
+
+    fn mut_key_for_reflect(&mut self) -> &mut ::protobuf::SingularField<::std::vec::Vec<u8>> {
+        &mut self.key
+    }
+
+    // optional int64 activate_at = 4;
+
+    pub fn clear_activate_at(&mut self) {
+        self.activate_at = ::std::option::Option::None;
+    }
+
+    pub fn has_activate_at(&self) -> bool {
+        self.activate_at.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_activate_at(&mut self, v: i64) {
+        self.activate_at = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_activate_at(&self) -> i64 {
+        self.activate_at.unwrap_or(0)
+    }
+
+    fn get_activate_at_for_reflect(&self) -> &::std::option::Option<i64> {
+        &self.activate_at
+    }
+
+    fn mut_activate_at_for_reflect(&mut self) -> &mut ::std::option::Option<i64> {
+        &mut self.activate_at
+    }
+
+    // optional int64 grace_period = 5;
+
+    pub fn clear_grace_period(&mut self) {
+        self.grace_period = ::std::option::Option::None;
+    }
+
+    pub fn has_grace_period(&self) -> bool {
+        self.grace_period.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_grace_period(&mut self, v: i64) {
+        self.grace_period = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_grace_period(&self) -> i64 {
+        self.grace_period.unwrap_or(0)
+    }
+
+    fn get_grace_period_for_reflect(&self) -> &::std::option::Option<i64> {
+        &self.grace_period
+    }
+
+    fn mut_grace_period_for_reflect(&mut self) -> &mut ::std::option::Option<i64> {
+        &mut self.grace_period
+    }
+}
+
+impl ::protobuf::Message for RingKey {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_singular_string_into(wire_type, is, &mut self.name)?;
+                },
+                2 => {
+                    ::protobuf::rt::read_singular_string_into(wire_type, is, &mut self.revision)?;
+                },
+                3 => {
+                    ::protobuf::rt::read_singular_bytes_into(wire_type, is, &mut self.key)?;
+                },
+                4 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_int64()?;
+                    self.activate_at = ::std::option::Option::Some(tmp);
+                },
+                5 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_int64()?;
+                    self.grace_period = ::std::option::Option::Some(tmp);
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if let Some(ref v) = self.name.as_ref() {
+            my_size += ::protobuf::rt::string_size(1, &v);
+        }
+        if let Some(ref v) = self.revision.as_ref() {
+            my_size += ::protobuf::rt::string_size(2, &v);
+        }
+        if let Some(ref v) = self.key.as_ref() {
+            my_size += ::protobuf::rt::bytes_size(3, &v);
+        }
+        if let Some(v) = self.activate_at {
+            my_size += ::protobuf::rt::value_size(4, v, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if let Some(v) = self.grace_period {
+            my_size += ::protobuf::rt::value_size(5, v, ::protobuf::wire_format::WireTypeVarint);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
 
-    pub fn clear_ack(&mut self) {
-        self.payload = ::std::option::Option::None;
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
+        if let Some(ref v) = self.name.as_ref() {
+            os.write_string(1, &v)?;
+        }
+        if let Some(ref v) = self.revision.as_ref() {
+            os.write_string(2, &v)?;
+        }
+        if let Some(ref v) = self.key.as_ref() {
+            os.write_bytes(3, &v)?;
+        }
+        if let Some(v) = self.activate_at {
+            os.write_int64(4, v)?;
+        }
+        if let Some(v) = self.grace_period {
+            os.write_int64(5, v)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
     }
 
-    pub fn has_ack(&self) -> bool {
-        match self.payload {
-            ::std::option::Option::Some(Swim_oneof_payload::ack(..)) => true,
-            _ => false,
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &::std::any::Any {
+        self as &::std::any::Any
+    }
+    fn as_any_mut(&mut self) -> &mut ::std::any::Any {
+        self as &mut ::std::any::Any
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        ::protobuf::MessageStatic::descriptor_static(None::<Self>)
+    }
+}
+
+impl ::protobuf::MessageStatic for RingKey {
+    fn new() -> RingKey {
+        RingKey::new()
+    }
+
+    fn descriptor_static(_: ::std::option::Option<RingKey>) -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_singular_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "name",
+                    RingKey::get_name_for_reflect,
+                    RingKey::mut_name_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_singular_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "revision",
+                    RingKey::get_revision_for_reflect,
+                    RingKey::mut_revision_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_singular_field_accessor::<_, ::protobuf::types::ProtobufTypeBytes>(
+                    "key",
+                    RingKey::get_key_for_reflect,
+                    RingKey::mut_key_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeInt64>(
+                    "activate_at",
+                    RingKey::get_activate_at_for_reflect,
+                    RingKey::mut_activate_at_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeInt64>(
+                    "grace_period",
+                    RingKey::get_grace_period_for_reflect,
+                    RingKey::mut_grace_period_for_reflect,
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<RingKey>(
+                    "RingKey",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+}
+
+impl ::protobuf::Clear for RingKey {
+    fn clear(&mut self) {
+        self.clear_name();
+        self.clear_revision();
+        self.clear_key();
+        self.clear_activate_at();
+        self.clear_grace_period();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for RingKey {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for RingKey {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct RumorDigest {
+    // message fields
+    kind: ::std::option::Option<Rumor_Type>,
+    key: ::protobuf::SingularField<::std::string::String>,
+    id: ::protobuf::SingularField<::std::string::String>,
+    incarnation: ::std::option::Option<u64>,
+    // special fields
+    unknown_fields: ::protobuf::UnknownFields,
+    cached_size: ::protobuf::CachedSize,
+}
+
+// see codegen.rs for the explanation why impl Sync explicitly
+unsafe impl ::std::marker::Sync for RumorDigest {}
+
+impl RumorDigest {
+    pub fn new() -> RumorDigest {
+        ::std::default::Default::default()
+    }
+
+    pub fn default_instance() -> &'static RumorDigest {
+        static mut instance: ::protobuf::lazy::Lazy<RumorDigest> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const RumorDigest,
+        };
+        unsafe {
+            instance.get(RumorDigest::new)
         }
     }
 
+    // optional .Rumor.Type kind = 1;
+
+    pub fn clear_kind(&mut self) {
+        self.kind = ::std::option::Option::None;
+    }
+
+    pub fn has_kind(&self) -> bool {
+        self.kind.is_some()
+    }
+
     // Param is passed by value, moved
-    pub fn set_ack(&mut self, v: Ack) {
-        self.payload = ::std::option::Option::Some(Swim_oneof_payload::ack(v))
+    pub fn set_kind(&mut self, v: Rumor_Type) {
+        self.kind = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_kind(&self) -> Rumor_Type {
+        self.kind.unwrap_or(Rumor_Type::Member)
+    }
+
+    fn get_kind_for_reflect(&self) -> &::std::option::Option<Rumor_Type> {
+        &self.kind
+    }
+
+    fn mut_kind_for_reflect(&mut self) -> &mut ::std::option::Option<Rumor_Type> {
+        &mut self.kind
+    }
+
+    // optional string key = 2;
+
+    pub fn clear_key(&mut self) {
+        self.key.clear();
+    }
+
+    pub fn has_key(&self) -> bool {
+        self.key.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_key(&mut self, v: ::std::string::String) {
+        self.key = ::protobuf::SingularField::some(v);
     }
 
     // Mutable pointer to the field.
-    pub fn mut_ack(&mut self) -> &mut Ack {
-        if let ::std::option::Option::Some(Swim_oneof_payload::ack(_)) = self.payload {
-        } else {
-            self.payload = ::std::option::Option::Some(Swim_oneof_payload::ack(Ack::new()));
-        }
-        match self.payload {
-            ::std::option::Option::Some(Swim_oneof_payload::ack(ref mut v)) => v,
-            _ => panic!(),
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_key(&mut self) -> &mut ::std::string::String {
+        if self.key.is_none() {
+            self.key.set_default();
         }
+        self.key.as_mut().unwrap()
     }
 
     // Take field
-    pub fn take_ack(&mut self) -> Ack {
-        if self.has_ack() {
-            match self.payload.take() {
-                ::std::option::Option::Some(Swim_oneof_payload::ack(v)) => v,
-                _ => panic!(),
-            }
-        } else {
-            Ack::new()
-        }
+    pub fn take_key(&mut self) -> ::std::string::String {
+        self.key.take().unwrap_or_else(|| ::std::string::String::new())
     }
 
-    pub fn get_ack(&self) -> &Ack {
-        match self.payload {
-            ::std::option::Option::Some(Swim_oneof_payload::ack(ref v)) => v,
-            _ => Ack::default_instance(),
+    pub fn get_key(&self) -> &str {
+        match self.key.as_ref() {
+            Some(v) => &v,
+            None => "",
         }
     }
 
-    // optional .PingReq pingreq = 4;
+    fn get_key_for_reflect(&self) -> &::protobuf::SingularField<::std::string::String> {
+        &self.key
+    }
 
-    pub fn clear_pingreq(&mut self) {
-        self.payload = ::std::option::Option::None;
+    fn mut_key_for_reflect(&mut self) -> &mut ::protobuf::SingularField<::std::string::String> {
+        &mut self.key
     }
 
-    pub fn has_pingreq(&self) -> bool {
-        match self.payload {
-            ::std::option::Option::Some(Swim_oneof_payload::pingreq(..)) => true,
-            _ => false,
-        }
+    // optional string id = 3;
+
+    pub fn clear_id(&mut self) {
+        self.id.clear();
+    }
+
+    pub fn has_id(&self) -> bool {
+        self.id.is_some()
     }
 
     // Param is passed by value, moved
-    pub fn set_pingreq(&mut self, v: PingReq) {
-        self.payload = ::std::option::Option::Some(Swim_oneof_payload::pingreq(v))
+    pub fn set_id(&mut self, v: ::std::string::String) {
+        self.id = ::protobuf::SingularField::some(v);
     }
 
     // Mutable pointer to the field.
-    pub fn mut_pingreq(&mut self) -> &mut PingReq {
-        if let ::std::option::Option::Some(Swim_oneof_payload::pingreq(_)) = self.payload {
-        } else {
-            self.payload = ::std::option::Option::Some(Swim_oneof_payload::pingreq(PingReq::new()));
-        }
-        match self.payload {
-            ::std::option::Option::Some(Swim_oneof_payload::pingreq(ref mut v)) => v,
-            _ => panic!(),
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_id(&mut self) -> &mut ::std::string::String {
+        if self.id.is_none() {
+            self.id.set_default();
         }
+        self.id.as_mut().unwrap()
     }
 
     // Take field
-    pub fn take_pingreq(&mut self) -> PingReq {
-        if self.has_pingreq() {
-            match self.payload.take() {
-                ::std::option::Option::Some(Swim_oneof_payload::pingreq(v)) => v,
-                _ => panic!(),
-            }
-        } else {
-            PingReq::new()
-        }
+    pub fn take_id(&mut self) -> ::std::string::String {
+        self.id.take().unwrap_or_else(|| ::std::string::String::new())
     }
 
-    pub fn get_pingreq(&self) -> &PingReq {
-        match self.payload {
-            ::std::option::Option::Some(Swim_oneof_payload::pingreq(ref v)) => v,
-            _ => PingReq::default_instance(),
+    pub fn get_id(&self) -> &str {
+        match self.id.as_ref() {
+            Some(v) => &v,
+            None => "",
         }
     }
 
-    // repeated .Membership membership = 5;
+    fn get_id_for_reflect(&self) -> &::protobuf::SingularField<::std::string::String> {
+        &self.id
+    }
 
-    pub fn clear_membership(&mut self) {
-        self.membership.clear();
+    fn mut_id_for_reflect(&mut self) -> &mut ::protobuf::SingularField<::std::string::String> {
+        &mut self.id
     }
 
-    // Param is passed by value, moved
-    pub fn set_membership(&mut self, v: ::protobuf::RepeatedField<Membership>) {
-        self.membership = v;
+    // optional uint64 incarnation = 4;
+
+    pub fn clear_incarnation(&mut self) {
+        self.incarnation = ::std::option::Option::None;
     }
 
-    // Mutable pointer to the field.
-    pub fn mut_membership(&mut self) -> &mut ::protobuf::RepeatedField<Membership> {
-        &mut self.membership
+    pub fn has_incarnation(&self) -> bool {
+        self.incarnation.is_some()
     }
 
-    // Take field
-    pub fn take_membership(&mut self) -> ::protobuf::RepeatedField<Membership> {
-        ::std::mem::replace(&mut self.membership, ::protobuf::RepeatedField::new())
+    // Param is passed by value, moved
+    pub fn set_incarnation(&mut self, v: u64) {
+        self.incarnation = ::std::option::Option::Some(v);
     }
 
-    pub fn get_membership(&self) -> &[Membership] {
-        &self.membership
+    pub fn get_incarnation(&self) -> u64 {
+        self.incarnation.unwrap_or(0)
     }
 
-    fn get_membership_for_reflect(&self) -> &::protobuf::RepeatedField<Membership> {
-        &self.membership
+    fn get_incarnation_for_reflect(&self) -> &::std::option::Option<u64> {
+        &self.incarnation
     }
 
-    fn mut_membership_for_reflect(&mut self) -> &mut ::protobuf::RepeatedField<Membership> {
-        &mut self.membership
+    fn mut_incarnation_for_reflect(&mut self) -> &mut ::std::option::Option<u64> {
+        &mut self.incarnation
     }
 }
 
-impl ::protobuf::Message for Swim {
+impl ::protobuf::Message for RumorDigest {
     fn is_initialized(&self) -> bool {
-        if self.field_type.is_none() {
-            return false;
-        }
-        if let Some(Swim_oneof_payload::ping(ref v)) = self.payload {
-            if !v.is_initialized() {
-                return false;
-            }
-        }
-        if let Some(Swim_oneof_payload::ack(ref v)) = self.payload {
-            if !v.is_initialized() {
-                return false;
-            }
-        }
-        if let Some(Swim_oneof_payload::pingreq(ref v)) = self.payload {
-            if !v.is_initialized() {
-                return false;
-            }
-        }
-        for v in &self.membership {
-            if !v.is_initialized() {
-                return false;
-            }
-        };
         true
     }
 
@@ -4244,28 +5928,20 @@ impl ::protobuf::Message for Swim {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
                     let tmp = is.read_enum()?;
-                    self.field_type = ::std::option::Option::Some(tmp);
+                    self.kind = ::std::option::Option::Some(tmp);
                 },
                 2 => {
-                    if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
-                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
-                    }
-                    self.payload = ::std::option::Option::Some(Swim_oneof_payload::ping(is.read_message()?));
+                    ::protobuf::rt::read_singular_string_into(wire_type, is, &mut self.key)?;
                 },
                 3 => {
-                    if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
-                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
-                    }
-                    self.payload = ::std::option::Option::Some(Swim_oneof_payload::ack(is.read_message()?));
+                    ::protobuf::rt::read_singular_string_into(wire_type, is, &mut self.id)?;
                 },
                 4 => {
-                    if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
                         return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
                     }
-                    self.payload = ::std::option::Option::Some(Swim_oneof_payload::pingreq(is.read_message()?));
-                },
-                5 => {
-                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.membership)?;
+                    let tmp = is.read_uint64()?;
+                    self.incarnation = ::std::option::Option::Some(tmp);
                 },
                 _ => {
                     ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
@@ -4279,28 +5955,17 @@ impl ::protobuf::Message for Swim {
     #[allow(unused_variables)]
     fn compute_size(&self) -> u32 {
         let mut my_size = 0;
-        if let Some(v) = self.field_type {
+        if let Some(v) = self.kind {
             my_size += ::protobuf::rt::enum_size(1, v);
         }
-        for value in &self.membership {
-            let len = value.compute_size();
-            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
-        };
-        if let ::std::option::Option::Some(ref v) = self.payload {
-            match v {
-                &Swim_oneof_payload::ping(ref v) => {
-                    let len = v.compute_size();
-                    my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
-                },
-                &Swim_oneof_payload::ack(ref v) => {
-                    let len = v.compute_size();
-                    my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
-                },
-                &Swim_oneof_payload::pingreq(ref v) => {
-                    let len = v.compute_size();
-                    my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
-                },
-            };
+        if let Some(ref v) = self.key.as_ref() {
+            my_size += ::protobuf::rt::string_size(2, &v);
+        }
+        if let Some(ref v) = self.id.as_ref() {
+            my_size += ::protobuf::rt::string_size(3, &v);
+        }
+        if let Some(v) = self.incarnation {
+            my_size += ::protobuf::rt::value_size(4, v, ::protobuf::wire_format::WireTypeVarint);
         }
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
@@ -4308,32 +5973,17 @@ impl ::protobuf::Message for Swim {
     }
 
     fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
-        if let Some(v) = self.field_type {
+        if let Some(v) = self.kind {
             os.write_enum(1, v.value())?;
         }
-        for v in &self.membership {
-            os.write_tag(5, ::protobuf::wire_format::WireTypeLengthDelimited)?;
-            os.write_raw_varint32(v.get_cached_size())?;
-            v.write_to_with_cached_sizes(os)?;
-        };
-        if let ::std::option::Option::Some(ref v) = self.payload {
-            match v {
-                &Swim_oneof_payload::ping(ref v) => {
-                    os.write_tag(2, ::protobuf::wire_format::WireTypeLengthDelimited)?;
-                    os.write_raw_varint32(v.get_cached_size())?;
-                    v.write_to_with_cached_sizes(os)?;
-                },
-                &Swim_oneof_payload::ack(ref v) => {
-                    os.write_tag(3, ::protobuf::wire_format::WireTypeLengthDelimited)?;
-                    os.write_raw_varint32(v.get_cached_size())?;
-                    v.write_to_with_cached_sizes(os)?;
-                },
-                &Swim_oneof_payload::pingreq(ref v) => {
-                    os.write_tag(4, ::protobuf::wire_format::WireTypeLengthDelimited)?;
-                    os.write_raw_varint32(v.get_cached_size())?;
-                    v.write_to_with_cached_sizes(os)?;
-                },
-            };
+        if let Some(ref v) = self.key.as_ref() {
+            os.write_string(2, &v)?;
+        }
+        if let Some(ref v) = self.id.as_ref() {
+            os.write_string(3, &v)?;
+        }
+        if let Some(v) = self.incarnation {
+            os.write_uint64(4, v)?;
         }
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
@@ -4366,12 +6016,12 @@ impl ::protobuf::Message for Swim {
     }
 }
 
-impl ::protobuf::MessageStatic for Swim {
-    fn new() -> Swim {
-        Swim::new()
+impl ::protobuf::MessageStatic for RumorDigest {
+    fn new() -> RumorDigest {
+        RumorDigest::new()
     }
 
-    fn descriptor_static(_: ::std::option::Option<Swim>) -> &'static ::protobuf::reflect::MessageDescriptor {
+    fn descriptor_static(_: ::std::option::Option<RumorDigest>) -> &'static ::protobuf::reflect::MessageDescriptor {
         static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
             lock: ::protobuf::lazy::ONCE_INIT,
             ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
@@ -4379,33 +6029,28 @@ impl ::protobuf::MessageStatic for Swim {
         unsafe {
             descriptor.get(|| {
                 let mut fields = ::std::vec::Vec::new();
-                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeEnum<Swim_Type>>(
-                    "type",
-                    Swim::get_field_type_for_reflect,
-                    Swim::mut_field_type_for_reflect,
-                ));
-                fields.push(::protobuf::reflect::accessor::make_singular_message_accessor::<_, Ping>(
-                    "ping",
-                    Swim::has_ping,
-                    Swim::get_ping,
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeEnum<Rumor_Type>>(
+                    "kind",
+                    RumorDigest::get_kind_for_reflect,
+                    RumorDigest::mut_kind_for_reflect,
                 ));
-                fields.push(::protobuf::reflect::accessor::make_singular_message_accessor::<_, Ack>(
-                    "ack",
-                    Swim::has_ack,
-                    Swim::get_ack,
+                fields.push(::protobuf::reflect::accessor::make_singular_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "key",
+                    RumorDigest::get_key_for_reflect,
+                    RumorDigest::mut_key_for_reflect,
                 ));
-                fields.push(::protobuf::reflect::accessor::make_singular_message_accessor::<_, PingReq>(
-                    "pingreq",
-                    Swim::has_pingreq,
-                    Swim::get_pingreq,
+                fields.push(::protobuf::reflect::accessor::make_singular_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "id",
+                    RumorDigest::get_id_for_reflect,
+                    RumorDigest::mut_id_for_reflect,
                 ));
-                fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<Membership>>(
-                    "membership",
-                    Swim::get_membership_for_reflect,
-                    Swim::mut_membership_for_reflect,
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                    "incarnation",
+                    RumorDigest::get_incarnation_for_reflect,
+                    RumorDigest::mut_incarnation_for_reflect,
                 ));
-                ::protobuf::reflect::MessageDescriptor::new::<Swim>(
-                    "Swim",
+                ::protobuf::reflect::MessageDescriptor::new::<RumorDigest>(
+                    "RumorDigest",
                     fields,
                     file_descriptor_proto()
                 )
@@ -4414,78 +6059,208 @@ impl ::protobuf::MessageStatic for Swim {
     }
 }
 
-impl ::protobuf::Clear for Swim {
+impl ::protobuf::Clear for RumorDigest {
     fn clear(&mut self) {
-        self.clear_field_type();
-        self.clear_ping();
-        self.clear_ack();
-        self.clear_pingreq();
-        self.clear_membership();
+        self.clear_kind();
+        self.clear_key();
+        self.clear_id();
+        self.clear_incarnation();
         self.unknown_fields.clear();
     }
-}
+}
+
+impl ::std::fmt::Debug for RumorDigest {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for RumorDigest {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct Digest {
+    // message fields
+    rumors: ::protobuf::RepeatedField<RumorDigest>,
+    // special fields
+    unknown_fields: ::protobuf::UnknownFields,
+    cached_size: ::protobuf::CachedSize,
+}
+
+// see codegen.rs for the explanation why impl Sync explicitly
+unsafe impl ::std::marker::Sync for Digest {}
+
+impl Digest {
+    pub fn new() -> Digest {
+        ::std::default::Default::default()
+    }
+
+    pub fn default_instance() -> &'static Digest {
+        static mut instance: ::protobuf::lazy::Lazy<Digest> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const Digest,
+        };
+        unsafe {
+            instance.get(Digest::new)
+        }
+    }
+
+    // repeated .RumorDigest rumors = 1;
+
+    pub fn clear_rumors(&mut self) {
+        self.rumors.clear();
+    }
+
+    // Param is passed by value, moved
+    pub fn set_rumors(&mut self, v: ::protobuf::RepeatedField<RumorDigest>) {
+        self.rumors = v;
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_rumors(&mut self) -> &mut ::protobuf::RepeatedField<RumorDigest> {
+        &mut self.rumors
+    }
+
+    // Take field
+    pub fn take_rumors(&mut self) -> ::protobuf::RepeatedField<RumorDigest> {
+        ::std::mem::replace(&mut self.rumors, ::protobuf::RepeatedField::new())
+    }
+
+    pub fn get_rumors(&self) -> &[RumorDigest] {
+        &self.rumors
+    }
+
+    fn get_rumors_for_reflect(&self) -> &::protobuf::RepeatedField<RumorDigest> {
+        &self.rumors
+    }
+
+    fn mut_rumors_for_reflect(&mut self) -> &mut ::protobuf::RepeatedField<RumorDigest> {
+        &mut self.rumors
+    }
+}
+
+impl ::protobuf::Message for Digest {
+    fn is_initialized(&self) -> bool {
+        for v in &self.rumors {
+            if !v.is_initialized() {
+                return false;
+            }
+        };
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    ::protobuf::rt::read_repeated_message_into(wire_type, is, &mut self.rumors)?;
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        for value in &self.rumors {
+            let len = value.compute_size();
+            my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+        };
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
+        for v in &self.rumors {
+            os.write_tag(1, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+            os.write_raw_varint32(v.get_cached_size())?;
+            v.write_to_with_cached_sizes(os)?;
+        };
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
 
-impl ::std::fmt::Debug for Swim {
-    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
-        ::protobuf::text_format::fmt(self, f)
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
     }
-}
 
-impl ::protobuf::reflect::ProtobufValue for Swim {
-    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
-        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
     }
-}
 
-#[derive(Clone,PartialEq,Eq,Debug,Hash)]
-pub enum Swim_Type {
-    PING = 1,
-    ACK = 2,
-    PINGREQ = 3,
-}
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
 
-impl ::protobuf::ProtobufEnum for Swim_Type {
-    fn value(&self) -> i32 {
-        *self as i32
+    fn as_any(&self) -> &::std::any::Any {
+        self as &::std::any::Any
+    }
+    fn as_any_mut(&mut self) -> &mut ::std::any::Any {
+        self as &mut ::std::any::Any
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+        self
     }
 
-    fn from_i32(value: i32) -> ::std::option::Option<Swim_Type> {
-        match value {
-            1 => ::std::option::Option::Some(Swim_Type::PING),
-            2 => ::std::option::Option::Some(Swim_Type::ACK),
-            3 => ::std::option::Option::Some(Swim_Type::PINGREQ),
-            _ => ::std::option::Option::None
-        }
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        ::protobuf::MessageStatic::descriptor_static(None::<Self>)
     }
+}
 
-    fn values() -> &'static [Self] {
-        static values: &'static [Swim_Type] = &[
-            Swim_Type::PING,
-            Swim_Type::ACK,
-            Swim_Type::PINGREQ,
-        ];
-        values
+impl ::protobuf::MessageStatic for Digest {
+    fn new() -> Digest {
+        Digest::new()
     }
 
-    fn enum_descriptor_static(_: ::std::option::Option<Swim_Type>) -> &'static ::protobuf::reflect::EnumDescriptor {
-        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::EnumDescriptor> = ::protobuf::lazy::Lazy {
+    fn descriptor_static(_: ::std::option::Option<Digest>) -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
             lock: ::protobuf::lazy::ONCE_INIT,
-            ptr: 0 as *const ::protobuf::reflect::EnumDescriptor,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
         };
         unsafe {
             descriptor.get(|| {
-                ::protobuf::reflect::EnumDescriptor::new("Swim_Type", file_descriptor_proto())
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_repeated_field_accessor::<_, ::protobuf::types::ProtobufTypeMessage<RumorDigest>>(
+                    "rumors",
+                    Digest::get_rumors_for_reflect,
+                    Digest::mut_rumors_for_reflect,
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<Digest>(
+                    "Digest",
+                    fields,
+                    file_descriptor_proto()
+                )
             })
         }
     }
 }
 
-impl ::std::marker::Copy for Swim_Type {
+impl ::protobuf::Clear for Digest {
+    fn clear(&mut self) {
+        self.clear_rumors();
+        self.unknown_fields.clear();
+    }
 }
 
-impl ::protobuf::reflect::ProtobufValue for Swim_Type {
+impl ::std::fmt::Debug for Digest {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for Digest {
     fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
-        ::protobuf::reflect::ProtobufValueRef::Enum(self.descriptor())
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
     }
 }
 
@@ -4513,6 +6288,9 @@ pub enum Rumor_oneof_payload {
     service_file(ServiceFile),
     election(Election),
     departure(Departure),
+    canary(Canary),
+    ring_key(RingKey),
+    digest(Digest),
 }
 
 impl Rumor {
@@ -4927,6 +6705,153 @@ impl Rumor {
             _ => Departure::default_instance(),
         }
     }
+
+    // optional .Canary canary = 10;
+
+    pub fn clear_canary(&mut self) {
+        self.payload = ::std::option::Option::None;
+    }
+
+    pub fn has_canary(&self) -> bool {
+        match self.payload {
+            ::std::option::Option::Some(Rumor_oneof_payload::canary(..)) => true,
+            _ => false,
+        }
+    }
+
+    // Param is passed by value, moved
+    pub fn set_canary(&mut self, v: Canary) {
+        self.payload = ::std::option::Option::Some(Rumor_oneof_payload::canary(v))
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_canary(&mut self) -> &mut Canary {
+        if let ::std::option::Option::Some(Rumor_oneof_payload::canary(_)) = self.payload {
+        } else {
+            self.payload = ::std::option::Option::Some(Rumor_oneof_payload::canary(Canary::new()));
+        }
+        match self.payload {
+            ::std::option::Option::Some(Rumor_oneof_payload::canary(ref mut v)) => v,
+            _ => panic!(),
+        }
+    }
+
+    // Take field
+    pub fn take_canary(&mut self) -> Canary {
+        if self.has_canary() {
+            match self.payload.take() {
+                ::std::option::Option::Some(Rumor_oneof_payload::canary(v)) => v,
+                _ => panic!(),
+            }
+        } else {
+            Canary::new()
+        }
+    }
+
+    pub fn get_canary(&self) -> &Canary {
+        match self.payload {
+            ::std::option::Option::Some(Rumor_oneof_payload::canary(ref v)) => v,
+            _ => Canary::default_instance(),
+        }
+    }
+
+    // optional .RingKey ring_key = 11;
+
+    pub fn clear_ring_key(&mut self) {
+        self.payload = ::std::option::Option::None;
+    }
+
+    pub fn has_ring_key(&self) -> bool {
+        match self.payload {
+            ::std::option::Option::Some(Rumor_oneof_payload::ring_key(..)) => true,
+            _ => false,
+        }
+    }
+
+    // Param is passed by value, moved
+    pub fn set_ring_key(&mut self, v: RingKey) {
+        self.payload = ::std::option::Option::Some(Rumor_oneof_payload::ring_key(v))
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_ring_key(&mut self) -> &mut RingKey {
+        if let ::std::option::Option::Some(Rumor_oneof_payload::ring_key(_)) = self.payload {
+        } else {
+            self.payload = ::std::option::Option::Some(Rumor_oneof_payload::ring_key(RingKey::new()));
+        }
+        match self.payload {
+            ::std::option::Option::Some(Rumor_oneof_payload::ring_key(ref mut v)) => v,
+            _ => panic!(),
+        }
+    }
+
+    // Take field
+    pub fn take_ring_key(&mut self) -> RingKey {
+        if self.has_ring_key() {
+            match self.payload.take() {
+                ::std::option::Option::Some(Rumor_oneof_payload::ring_key(v)) => v,
+                _ => panic!(),
+            }
+        } else {
+            RingKey::new()
+        }
+    }
+
+    pub fn get_ring_key(&self) -> &RingKey {
+        match self.payload {
+            ::std::option::Option::Some(Rumor_oneof_payload::ring_key(ref v)) => v,
+            _ => RingKey::default_instance(),
+        }
+    }
+
+    // optional .Digest digest = 12;
+
+    pub fn clear_digest(&mut self) {
+        self.payload = ::std::option::Option::None;
+    }
+
+    pub fn has_digest(&self) -> bool {
+        match self.payload {
+            ::std::option::Option::Some(Rumor_oneof_payload::digest(..)) => true,
+            _ => false,
+        }
+    }
+
+    // Param is passed by value, moved
+    pub fn set_digest(&mut self, v: Digest) {
+        self.payload = ::std::option::Option::Some(Rumor_oneof_payload::digest(v))
+    }
+
+    // Mutable pointer to the field.
+    pub fn mut_digest(&mut self) -> &mut Digest {
+        if let ::std::option::Option::Some(Rumor_oneof_payload::digest(_)) = self.payload {
+        } else {
+            self.payload = ::std::option::Option::Some(Rumor_oneof_payload::digest(Digest::new()));
+        }
+        match self.payload {
+            ::std::option::Option::Some(Rumor_oneof_payload::digest(ref mut v)) => v,
+            _ => panic!(),
+        }
+    }
+
+    // Take field
+    pub fn take_digest(&mut self) -> Digest {
+        if self.has_digest() {
+            match self.payload.take() {
+                ::std::option::Option::Some(Rumor_oneof_payload::digest(v)) => v,
+                _ => panic!(),
+            }
+        } else {
+            Digest::new()
+        }
+    }
+
+    pub fn get_digest(&self) -> &Digest {
+        match self.payload {
+            ::std::option::Option::Some(Rumor_oneof_payload::digest(ref v)) => v,
+            _ => Digest::default_instance(),
+        }
+    }
 }
 
 impl ::protobuf::Message for Rumor {
@@ -4964,6 +6889,21 @@ impl ::protobuf::Message for Rumor {
                 return false;
             }
         }
+        if let Some(Rumor_oneof_payload::canary(ref v)) = self.payload {
+            if !v.is_initialized() {
+                return false;
+            }
+        }
+        if let Some(Rumor_oneof_payload::ring_key(ref v)) = self.payload {
+            if !v.is_initialized() {
+                return false;
+            }
+        }
+        if let Some(Rumor_oneof_payload::digest(ref v)) = self.payload {
+            if !v.is_initialized() {
+                return false;
+            }
+        }
         true
     }
 
@@ -5020,6 +6960,24 @@ impl ::protobuf::Message for Rumor {
                     }
                     self.payload = ::std::option::Option::Some(Rumor_oneof_payload::departure(is.read_message()?));
                 },
+                10 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    self.payload = ::std::option::Option::Some(Rumor_oneof_payload::canary(is.read_message()?));
+                },
+                11 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    self.payload = ::std::option::Option::Some(Rumor_oneof_payload::ring_key(is.read_message()?));
+                },
+                12 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeLengthDelimited {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    self.payload = ::std::option::Option::Some(Rumor_oneof_payload::digest(is.read_message()?));
+                },
                 _ => {
                     ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
@@ -5067,6 +7025,18 @@ impl ::protobuf::Message for Rumor {
                     let len = v.compute_size();
                     my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
                 },
+                &Rumor_oneof_payload::canary(ref v) => {
+                    let len = v.compute_size();
+                    my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+                },
+                &Rumor_oneof_payload::ring_key(ref v) => {
+                    let len = v.compute_size();
+                    my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+                },
+                &Rumor_oneof_payload::digest(ref v) => {
+                    let len = v.compute_size();
+                    my_size += 1 + ::protobuf::rt::compute_raw_varint32_size(len) + len;
+                },
             };
         }
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
@@ -5116,6 +7086,21 @@ impl ::protobuf::Message for Rumor {
                     os.write_raw_varint32(v.get_cached_size())?;
                     v.write_to_with_cached_sizes(os)?;
                 },
+                &Rumor_oneof_payload::canary(ref v) => {
+                    os.write_tag(10, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+                    os.write_raw_varint32(v.get_cached_size())?;
+                    v.write_to_with_cached_sizes(os)?;
+                },
+                &Rumor_oneof_payload::ring_key(ref v) => {
+                    os.write_tag(11, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+                    os.write_raw_varint32(v.get_cached_size())?;
+                    v.write_to_with_cached_sizes(os)?;
+                },
+                &Rumor_oneof_payload::digest(ref v) => {
+                    os.write_tag(12, ::protobuf::wire_format::WireTypeLengthDelimited)?;
+                    os.write_raw_varint32(v.get_cached_size())?;
+                    v.write_to_with_cached_sizes(os)?;
+                },
             };
         }
         os.write_unknown_fields(self.get_unknown_fields())?;
@@ -5207,6 +7192,21 @@ impl ::protobuf::MessageStatic for Rumor {
                     Rumor::has_departure,
                     Rumor::get_departure,
                 ));
+                fields.push(::protobuf::reflect::accessor::make_singular_message_accessor::<_, Canary>(
+                    "canary",
+                    Rumor::has_canary,
+                    Rumor::get_canary,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_singular_message_accessor::<_, RingKey>(
+                    "ring_key",
+                    Rumor::has_ring_key,
+                    Rumor::get_ring_key,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_singular_message_accessor::<_, Digest>(
+                    "digest",
+                    Rumor::has_digest,
+                    Rumor::get_digest,
+                ));
                 ::protobuf::reflect::MessageDescriptor::new::<Rumor>(
                     "Rumor",
                     fields,
@@ -5228,6 +7228,9 @@ impl ::protobuf::Clear for Rumor {
         self.clear_service_file();
         self.clear_election();
         self.clear_departure();
+        self.clear_canary();
+        self.clear_ring_key();
+        self.clear_digest();
         self.unknown_fields.clear();
     }
 }
@@ -5255,6 +7258,11 @@ pub enum Rumor_Type {
     Fake2 = 7,
     ElectionUpdate = 8,
     Departure = 9,
+    Canary = 10,
+    RingKey = 11,
+    DigestRequest = 12,
+    Digest = 13,
+    DigestFetch = 14,
 }
 
 impl ::protobuf::ProtobufEnum for Rumor_Type {
@@ -5273,6 +7281,11 @@ impl ::protobuf::ProtobufEnum for Rumor_Type {
             7 => ::std::option::Option::Some(Rumor_Type::Fake2),
             8 => ::std::option::Option::Some(Rumor_Type::ElectionUpdate),
             9 => ::std::option::Option::Some(Rumor_Type::Departure),
+            10 => ::std::option::Option::Some(Rumor_Type::Canary),
+            11 => ::std::option::Option::Some(Rumor_Type::RingKey),
+            12 => ::std::option::Option::Some(Rumor_Type::DigestRequest),
+            13 => ::std::option::Option::Some(Rumor_Type::Digest),
+            14 => ::std::option::Option::Some(Rumor_Type::DigestFetch),
             _ => ::std::option::Option::None
         }
     }
@@ -5288,6 +7301,11 @@ impl ::protobuf::ProtobufEnum for Rumor_Type {
             Rumor_Type::Fake2,
             Rumor_Type::ElectionUpdate,
             Rumor_Type::Departure,
+            Rumor_Type::Canary,
+            Rumor_Type::RingKey,
+            Rumor_Type::DigestRequest,
+            Rumor_Type::Digest,
+            Rumor_Type::DigestFetch,
         ];
         values
     }
@@ -5320,6 +7338,7 @@ pub struct Wire {
     encrypted: ::std::option::Option<bool>,
     nonce: ::protobuf::SingularField<::std::vec::Vec<u8>>,
     payload: ::protobuf::SingularField<::std::vec::Vec<u8>>,
+    key_rev: ::protobuf::SingularField<::std::string::String>,
     // special fields
     unknown_fields: ::protobuf::UnknownFields,
     cached_size: ::protobuf::CachedSize,
@@ -5457,6 +7476,50 @@ impl Wire {
     fn mut_payload_for_reflect(&mut self) -> &mut ::protobuf::SingularField<::std::vec::Vec<u8>> {
         &mut self.payload
     }
+
+    // optional string key_rev = 4;
+
+    pub fn clear_key_rev(&mut self) {
+        self.key_rev.clear();
+    }
+
+    pub fn has_key_rev(&self) -> bool {
+        self.key_rev.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_key_rev(&mut self, v: ::std::string::String) {
+        self.key_rev = ::protobuf::SingularField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_key_rev(&mut self) -> &mut ::std::string::String {
+        if self.key_rev.is_none() {
+            self.key_rev.set_default();
+        }
+        self.key_rev.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_key_rev(&mut self) -> ::std::string::String {
+        self.key_rev.take().unwrap_or_else(|| ::std::string::String::new())
+    }
+
+    pub fn get_key_rev(&self) -> &str {
+        match self.key_rev.as_ref() {
+            Some(v) => &v,
+            None => "",
+        }
+    }
+
+    fn get_key_rev_for_reflect(&self) -> &::protobuf::SingularField<::std::string::String> {
+        &self.key_rev
+    }
+
+    fn mut_key_rev_for_reflect(&mut self) -> &mut ::protobuf::SingularField<::std::string::String> {
+        &mut self.key_rev
+    }
 }
 
 impl ::protobuf::Message for Wire {
@@ -5481,6 +7544,9 @@ impl ::protobuf::Message for Wire {
                 3 => {
                     ::protobuf::rt::read_singular_bytes_into(wire_type, is, &mut self.payload)?;
                 },
+                4 => {
+                    ::protobuf::rt::read_singular_string_into(wire_type, is, &mut self.key_rev)?;
+                },
                 _ => {
                     ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
@@ -5502,6 +7568,9 @@ impl ::protobuf::Message for Wire {
         if let Some(ref v) = self.payload.as_ref() {
             my_size += ::protobuf::rt::bytes_size(3, &v);
         }
+        if let Some(ref v) = self.key_rev.as_ref() {
+            my_size += ::protobuf::rt::string_size(4, &v);
+        }
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
         my_size
@@ -5517,6 +7586,9 @@ impl ::protobuf::Message for Wire {
         if let Some(ref v) = self.payload.as_ref() {
             os.write_bytes(3, &v)?;
         }
+        if let Some(ref v) = self.key_rev.as_ref() {
+            os.write_string(4, &v)?;
+        }
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
     }
@@ -5576,6 +7648,11 @@ impl ::protobuf::MessageStatic for Wire {
                     Wire::get_payload_for_reflect,
                     Wire::mut_payload_for_reflect,
                 ));
+                fields.push(::protobuf::reflect::accessor::make_singular_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "key_rev",
+                    Wire::get_key_rev_for_reflect,
+                    Wire::mut_key_rev_for_reflect,
+                ));
                 ::protobuf::reflect::MessageDescriptor::new::<Wire>(
                     "Wire",
                     fields,
@@ -5591,6 +7668,7 @@ impl ::protobuf::Clear for Wire {
         self.clear_encrypted();
         self.clear_nonce();
         self.clear_payload();
+        self.clear_key_rev();
         self.unknown_fields.clear();
     }
 }
@@ -5608,340 +7686,505 @@ impl ::protobuf::reflect::ProtobufValue for Wire {
 }
 
 static file_descriptor_proto_data: &'static [u8] = b"\
-    \n\x14protocols/swim.proto\"\xdc\x01\n\x06Member\x12\x0e\n\x02id\x18\x01\
+    \n\x14protocols/swim.proto\"\xe4\x02\n\x06Member\x12\x0e\n\x02id\x18\x01\
     \x20\x01(\tR\x02id\x12\x20\n\x0bincarnation\x18\x02\x20\x01(\x04R\x0binc\
     arnation\x12\x18\n\x07address\x18\x03\x20\x01(\tR\x07address\x12\x1b\n\t\
     swim_port\x18\x04\x20\x01(\x05R\x08swimPort\x12\x1f\n\x0bgossip_port\x18\
     \x05\x20\x01(\x05R\ngossipPort\x12%\n\npersistent\x18\x06\x20\x01(\x08:\
     \x05falseR\npersistent\x12!\n\x08departed\x18\x07\x20\x01(\x08:\x05false\
-    R\x08departed\"K\n\x04Ping\x12\x1b\n\x04from\x18\x01\x20\x01(\x0b2\x07.M\
-    emberR\x04from\x12&\n\nforward_to\x18\x02\x20\x01(\x0b2\x07.MemberR\tfor\
-    wardTo\"J\n\x03Ack\x12\x1b\n\x04from\x18\x01\x20\x01(\x0b2\x07.MemberR\
-    \x04from\x12&\n\nforward_to\x18\x02\x20\x01(\x0b2\x07.MemberR\tforwardTo\
-    \"G\n\x07PingReq\x12\x1b\n\x04from\x18\x01\x20\x01(\x0b2\x07.MemberR\x04\
-    from\x12\x1f\n\x06target\x18\x02\x20\x01(\x0b2\x07.MemberR\x06target\"\
-    \x98\x01\n\nMembership\x12\x1f\n\x06member\x18\x01\x20\x01(\x0b2\x07.Mem\
-    berR\x06member\x12*\n\x06health\x18\x02\x20\x01(\x0e2\x12.Membership.Hea\
-    lthR\x06health\"=\n\x06Health\x12\t\n\x05ALIVE\x10\x01\x12\x0b\n\x07SUSP\
-    ECT\x10\x02\x12\r\n\tCONFIRMED\x10\x03\x12\x0c\n\x08DEPARTED\x10\x04\"\
-    \xf5\x01\n\x08Election\x12\x1b\n\tmember_id\x18\x01\x20\x01(\tR\x08membe\
-    rId\x12#\n\rservice_group\x18\x02\x20\x01(\tR\x0cserviceGroup\x12\x12\n\
-    \x04term\x18\x03\x20\x01(\x04R\x04term\x12\x20\n\x0bsuitability\x18\x04\
-    \x20\x01(\x04R\x0bsuitability\x12(\n\x06status\x18\x05\x20\x01(\x0e2\x10\
-    .Election.StatusR\x06status\x12\x14\n\x05votes\x18\x06\x20\x03(\tR\x05vo\
-    tes\"1\n\x06Status\x12\x0b\n\x07Running\x10\x01\x12\x0c\n\x08NoQuorum\
-    \x10\x02\x12\x0c\n\x08Finished\x10\x03\"\xcf\x01\n\x07Service\x12\x1b\n\
-    \tmember_id\x18\x01\x20\x01(\tR\x08memberId\x12#\n\rservice_group\x18\
-    \x02\x20\x01(\tR\x0cserviceGroup\x12\x20\n\x0bincarnation\x18\x03\x20\
-    \x01(\x04R\x0bincarnation\x12\x20\n\x0binitialized\x18\x08\x20\x01(\x08R\
-    \x0binitialized\x12\x10\n\x03pkg\x18\t\x20\x01(\tR\x03pkg\x12\x10\n\x03c\
-    fg\x18\n\x20\x01(\x0cR\x03cfg\x12\x1a\n\x03sys\x18\x0c\x20\x01(\x0b2\x08\
-    .SysInfoR\x03sys\"\x8c\x01\n\rServiceConfig\x12#\n\rservice_group\x18\
-    \x01\x20\x01(\tR\x0cserviceGroup\x12\x20\n\x0bincarnation\x18\x02\x20\
-    \x01(\x04R\x0bincarnation\x12\x1c\n\tencrypted\x18\x03\x20\x01(\x08R\ten\
-    crypted\x12\x16\n\x06config\x18\x04\x20\x01(\x0cR\x06config\"\xa2\x01\n\
-    \x0bServiceFile\x12#\n\rservice_group\x18\x01\x20\x01(\tR\x0cserviceGrou\
-    p\x12\x20\n\x0bincarnation\x18\x02\x20\x01(\x04R\x0bincarnation\x12\x1c\
-    \n\tencrypted\x18\x03\x20\x01(\x08R\tencrypted\x12\x1a\n\x08filename\x18\
-    \x04\x20\x01(\tR\x08filename\x12\x12\n\x04body\x18\x05\x20\x01(\x0cR\x04\
-    body\"\xf3\x01\n\x07SysInfo\x12\x19\n\x02ip\x18\x01\x20\x01(\t:\t127.0.0\
-    .1R\x02ip\x12%\n\x08hostname\x18\x02\x20\x01(\t:\tlocalhostR\x08hostname\
-    \x12&\n\tgossip_ip\x18\x03\x20\x01(\t:\t127.0.0.1R\x08gossipIp\x12\x1f\n\
-    \x0bgossip_port\x18\x04\x20\x01(\rR\ngossipPort\x121\n\x0fhttp_gateway_i\
-    p\x18\x05\x20\x01(\t:\t127.0.0.1R\rhttpGatewayIp\x12*\n\x11http_gateway_\
-    port\x18\x06\x20\x01(\rR\x0fhttpGatewayPort\"(\n\tDeparture\x12\x1b\n\tm\
-    ember_id\x18\x01\x20\x01(\tR\x08memberId\"\xe3\x01\n\x04Swim\x12\x1e\n\
-    \x04type\x18\x01\x20\x02(\x0e2\n.Swim.TypeR\x04type\x12\x1b\n\x04ping\
-    \x18\x02\x20\x01(\x0b2\x05.PingH\0R\x04ping\x12\x18\n\x03ack\x18\x03\x20\
-    \x01(\x0b2\x04.AckH\0R\x03ack\x12$\n\x07pingreq\x18\x04\x20\x01(\x0b2\
-    \x08.PingReqH\0R\x07pingreq\x12+\n\nmembership\x18\x05\x20\x03(\x0b2\x0b\
-    .MembershipR\nmembership\"&\n\x04Type\x12\x08\n\x04PING\x10\x01\x12\x07\
-    \n\x03ACK\x10\x02\x12\x0b\n\x07PINGREQ\x10\x03B\t\n\x07payload\"\xf8\x03\
-    \n\x05Rumor\x12\x1f\n\x04type\x18\x01\x20\x02(\x0e2\x0b.Rumor.TypeR\x04t\
-    ype\x12\x10\n\x03tag\x18\x02\x20\x03(\tR\x03tag\x12\x17\n\x07from_id\x18\
-    \x03\x20\x01(\tR\x06fromId\x12%\n\x06member\x18\x04\x20\x01(\x0b2\x0b.Me\
-    mbershipH\0R\x06member\x12$\n\x07service\x18\x05\x20\x01(\x0b2\x08.Servi\
-    ceH\0R\x07service\x127\n\x0eservice_config\x18\x06\x20\x01(\x0b2\x0e.Ser\
-    viceConfigH\0R\rserviceConfig\x121\n\x0cservice_file\x18\x07\x20\x01(\
-    \x0b2\x0c.ServiceFileH\0R\x0bserviceFile\x12'\n\x08election\x18\x08\x20\
-    \x01(\x0b2\t.ElectionH\0R\x08election\x12*\n\tdeparture\x18\t\x20\x01(\
-    \x0b2\n.DepartureH\0R\tdeparture\"\x89\x01\n\x04Type\x12\n\n\x06Member\
-    \x10\x01\x12\x0b\n\x07Service\x10\x02\x12\x0c\n\x08Election\x10\x03\x12\
-    \x11\n\rServiceConfig\x10\x04\x12\x0f\n\x0bServiceFile\x10\x05\x12\x08\n\
-    \x04Fake\x10\x06\x12\t\n\x05Fake2\x10\x07\x12\x12\n\x0eElectionUpdate\
-    \x10\x08\x12\r\n\tDeparture\x10\tB\t\n\x07payload\"T\n\x04Wire\x12\x1c\n\
-    \tencrypted\x18\x01\x20\x01(\x08R\tencrypted\x12\x14\n\x05nonce\x18\x02\
-    \x20\x01(\x0cR\x05nonce\x12\x18\n\x07payload\x18\x03\x20\x01(\x0cR\x07pa\
-    yloadJ\xc7,\n\x06\x12\x04\0\0~\x01\n\x08\n\x01\x0c\x12\x03\0\0\x12\n\n\n\
-    \x02\x04\0\x12\x04\x02\0\n\x01\n\n\n\x03\x04\0\x01\x12\x03\x02\x08\x0e\n\
-    \x0b\n\x04\x04\0\x02\0\x12\x03\x03\x02\x19\n\x0c\n\x05\x04\0\x02\0\x04\
-    \x12\x03\x03\x02\n\n\x0c\n\x05\x04\0\x02\0\x05\x12\x03\x03\x0b\x11\n\x0c\
-    \n\x05\x04\0\x02\0\x01\x12\x03\x03\x12\x14\n\x0c\n\x05\x04\0\x02\0\x03\
-    \x12\x03\x03\x17\x18\n\x0b\n\x04\x04\0\x02\x01\x12\x03\x04\x02\"\n\x0c\n\
-    \x05\x04\0\x02\x01\x04\x12\x03\x04\x02\n\n\x0c\n\x05\x04\0\x02\x01\x05\
-    \x12\x03\x04\x0b\x11\n\x0c\n\x05\x04\0\x02\x01\x01\x12\x03\x04\x12\x1d\n\
-    \x0c\n\x05\x04\0\x02\x01\x03\x12\x03\x04\x20!\n\x0b\n\x04\x04\0\x02\x02\
-    \x12\x03\x05\x02\x1e\n\x0c\n\x05\x04\0\x02\x02\x04\x12\x03\x05\x02\n\n\
-    \x0c\n\x05\x04\0\x02\x02\x05\x12\x03\x05\x0b\x11\n\x0c\n\x05\x04\0\x02\
-    \x02\x01\x12\x03\x05\x12\x19\n\x0c\n\x05\x04\0\x02\x02\x03\x12\x03\x05\
-    \x1c\x1d\n\x0b\n\x04\x04\0\x02\x03\x12\x03\x06\x02\x1f\n\x0c\n\x05\x04\0\
-    \x02\x03\x04\x12\x03\x06\x02\n\n\x0c\n\x05\x04\0\x02\x03\x05\x12\x03\x06\
-    \x0b\x10\n\x0c\n\x05\x04\0\x02\x03\x01\x12\x03\x06\x11\x1a\n\x0c\n\x05\
-    \x04\0\x02\x03\x03\x12\x03\x06\x1d\x1e\n\x0b\n\x04\x04\0\x02\x04\x12\x03\
-    \x07\x02!\n\x0c\n\x05\x04\0\x02\x04\x04\x12\x03\x07\x02\n\n\x0c\n\x05\
-    \x04\0\x02\x04\x05\x12\x03\x07\x0b\x10\n\x0c\n\x05\x04\0\x02\x04\x01\x12\
-    \x03\x07\x11\x1c\n\x0c\n\x05\x04\0\x02\x04\x03\x12\x03\x07\x1f\x20\n\x0b\
-    \n\x04\x04\0\x02\x05\x12\x03\x08\x021\n\x0c\n\x05\x04\0\x02\x05\x04\x12\
-    \x03\x08\x02\n\n\x0c\n\x05\x04\0\x02\x05\x05\x12\x03\x08\x0b\x0f\n\x0c\n\
-    \x05\x04\0\x02\x05\x01\x12\x03\x08\x10\x1a\n\x0c\n\x05\x04\0\x02\x05\x03\
-    \x12\x03\x08\x1d\x1e\n\x0c\n\x05\x04\0\x02\x05\x08\x12\x03\x08\x1f0\n\
-    \x0c\n\x05\x04\0\x02\x05\x07\x12\x03\x08*/\n\x0b\n\x04\x04\0\x02\x06\x12\
-    \x03\t\x02/\n\x0c\n\x05\x04\0\x02\x06\x04\x12\x03\t\x02\n\n\x0c\n\x05\
-    \x04\0\x02\x06\x05\x12\x03\t\x0b\x0f\n\x0c\n\x05\x04\0\x02\x06\x01\x12\
-    \x03\t\x10\x18\n\x0c\n\x05\x04\0\x02\x06\x03\x12\x03\t\x1b\x1c\n\x0c\n\
-    \x05\x04\0\x02\x06\x08\x12\x03\t\x1d.\n\x0c\n\x05\x04\0\x02\x06\x07\x12\
-    \x03\t(-\n\n\n\x02\x04\x01\x12\x04\x0c\0\x0f\x01\n\n\n\x03\x04\x01\x01\
-    \x12\x03\x0c\x08\x0c\n\x0b\n\x04\x04\x01\x02\0\x12\x03\r\x02\x1b\n\x0c\n\
-    \x05\x04\x01\x02\0\x04\x12\x03\r\x02\n\n\x0c\n\x05\x04\x01\x02\0\x06\x12\
-    \x03\r\x0b\x11\n\x0c\n\x05\x04\x01\x02\0\x01\x12\x03\r\x12\x16\n\x0c\n\
-    \x05\x04\x01\x02\0\x03\x12\x03\r\x19\x1a\n\x0b\n\x04\x04\x01\x02\x01\x12\
-    \x03\x0e\x02!\n\x0c\n\x05\x04\x01\x02\x01\x04\x12\x03\x0e\x02\n\n\x0c\n\
-    \x05\x04\x01\x02\x01\x06\x12\x03\x0e\x0b\x11\n\x0c\n\x05\x04\x01\x02\x01\
-    \x01\x12\x03\x0e\x12\x1c\n\x0c\n\x05\x04\x01\x02\x01\x03\x12\x03\x0e\x1f\
-    \x20\n\n\n\x02\x04\x02\x12\x04\x11\0\x14\x01\n\n\n\x03\x04\x02\x01\x12\
-    \x03\x11\x08\x0b\n\x0b\n\x04\x04\x02\x02\0\x12\x03\x12\x02\x1b\n\x0c\n\
-    \x05\x04\x02\x02\0\x04\x12\x03\x12\x02\n\n\x0c\n\x05\x04\x02\x02\0\x06\
-    \x12\x03\x12\x0b\x11\n\x0c\n\x05\x04\x02\x02\0\x01\x12\x03\x12\x12\x16\n\
-    \x0c\n\x05\x04\x02\x02\0\x03\x12\x03\x12\x19\x1a\n\x0b\n\x04\x04\x02\x02\
-    \x01\x12\x03\x13\x02!\n\x0c\n\x05\x04\x02\x02\x01\x04\x12\x03\x13\x02\n\
-    \n\x0c\n\x05\x04\x02\x02\x01\x06\x12\x03\x13\x0b\x11\n\x0c\n\x05\x04\x02\
-    \x02\x01\x01\x12\x03\x13\x12\x1c\n\x0c\n\x05\x04\x02\x02\x01\x03\x12\x03\
-    \x13\x1f\x20\n\n\n\x02\x04\x03\x12\x04\x16\0\x19\x01\n\n\n\x03\x04\x03\
-    \x01\x12\x03\x16\x08\x0f\n\x0b\n\x04\x04\x03\x02\0\x12\x03\x17\x02\x1b\n\
-    \x0c\n\x05\x04\x03\x02\0\x04\x12\x03\x17\x02\n\n\x0c\n\x05\x04\x03\x02\0\
-    \x06\x12\x03\x17\x0b\x11\n\x0c\n\x05\x04\x03\x02\0\x01\x12\x03\x17\x12\
-    \x16\n\x0c\n\x05\x04\x03\x02\0\x03\x12\x03\x17\x19\x1a\n\x0b\n\x04\x04\
-    \x03\x02\x01\x12\x03\x18\x02\x1d\n\x0c\n\x05\x04\x03\x02\x01\x04\x12\x03\
-    \x18\x02\n\n\x0c\n\x05\x04\x03\x02\x01\x06\x12\x03\x18\x0b\x11\n\x0c\n\
-    \x05\x04\x03\x02\x01\x01\x12\x03\x18\x12\x18\n\x0c\n\x05\x04\x03\x02\x01\
-    \x03\x12\x03\x18\x1b\x1c\n\n\n\x02\x04\x04\x12\x04\x1b\0\x20\x01\n\n\n\
-    \x03\x04\x04\x01\x12\x03\x1b\x08\x12\n\x0b\n\x04\x04\x04\x04\0\x12\x03\
-    \x1c\x02F\n\x0c\n\x05\x04\x04\x04\0\x01\x12\x03\x1c\x07\r\n\r\n\x06\x04\
-    \x04\x04\0\x02\0\x12\x03\x1c\x10\x1a\n\x0e\n\x07\x04\x04\x04\0\x02\0\x01\
-    \x12\x03\x1c\x10\x15\n\x0e\n\x07\x04\x04\x04\0\x02\0\x02\x12\x03\x1c\x18\
-    \x19\n\r\n\x06\x04\x04\x04\0\x02\x01\x12\x03\x1c\x1b'\n\x0e\n\x07\x04\
-    \x04\x04\0\x02\x01\x01\x12\x03\x1c\x1b\"\n\x0e\n\x07\x04\x04\x04\0\x02\
-    \x01\x02\x12\x03\x1c%&\n\r\n\x06\x04\x04\x04\0\x02\x02\x12\x03\x1c(6\n\
-    \x0e\n\x07\x04\x04\x04\0\x02\x02\x01\x12\x03\x1c(1\n\x0e\n\x07\x04\x04\
-    \x04\0\x02\x02\x02\x12\x03\x1c45\n\r\n\x06\x04\x04\x04\0\x02\x03\x12\x03\
-    \x1c7D\n\x0e\n\x07\x04\x04\x04\0\x02\x03\x01\x12\x03\x1c7?\n\x0e\n\x07\
-    \x04\x04\x04\0\x02\x03\x02\x12\x03\x1cBC\n\x0b\n\x04\x04\x04\x02\0\x12\
-    \x03\x1e\x02\x1d\n\x0c\n\x05\x04\x04\x02\0\x04\x12\x03\x1e\x02\n\n\x0c\n\
-    \x05\x04\x04\x02\0\x06\x12\x03\x1e\x0b\x11\n\x0c\n\x05\x04\x04\x02\0\x01\
-    \x12\x03\x1e\x12\x18\n\x0c\n\x05\x04\x04\x02\0\x03\x12\x03\x1e\x1b\x1c\n\
-    \x0b\n\x04\x04\x04\x02\x01\x12\x03\x1f\x02\x1d\n\x0c\n\x05\x04\x04\x02\
-    \x01\x04\x12\x03\x1f\x02\n\n\x0c\n\x05\x04\x04\x02\x01\x06\x12\x03\x1f\
-    \x0b\x11\n\x0c\n\x05\x04\x04\x02\x01\x01\x12\x03\x1f\x12\x18\n\x0c\n\x05\
-    \x04\x04\x02\x01\x03\x12\x03\x1f\x1b\x1c\n\n\n\x02\x04\x05\x12\x04\"\0+\
-    \x01\n\n\n\x03\x04\x05\x01\x12\x03\"\x08\x10\n\x0b\n\x04\x04\x05\x04\0\
-    \x12\x03#\x02:\n\x0c\n\x05\x04\x05\x04\0\x01\x12\x03#\x07\r\n\r\n\x06\
-    \x04\x05\x04\0\x02\0\x12\x03#\x10\x1c\n\x0e\n\x07\x04\x05\x04\0\x02\0\
-    \x01\x12\x03#\x10\x17\n\x0e\n\x07\x04\x05\x04\0\x02\0\x02\x12\x03#\x1a\
-    \x1b\n\r\n\x06\x04\x05\x04\0\x02\x01\x12\x03#\x1d*\n\x0e\n\x07\x04\x05\
-    \x04\0\x02\x01\x01\x12\x03#\x1d%\n\x0e\n\x07\x04\x05\x04\0\x02\x01\x02\
-    \x12\x03#()\n\r\n\x06\x04\x05\x04\0\x02\x02\x12\x03#+8\n\x0e\n\x07\x04\
-    \x05\x04\0\x02\x02\x01\x12\x03#+3\n\x0e\n\x07\x04\x05\x04\0\x02\x02\x02\
-    \x12\x03#67\n\x0b\n\x04\x04\x05\x02\0\x12\x03%\x02\x20\n\x0c\n\x05\x04\
-    \x05\x02\0\x04\x12\x03%\x02\n\n\x0c\n\x05\x04\x05\x02\0\x05\x12\x03%\x0b\
-    \x11\n\x0c\n\x05\x04\x05\x02\0\x01\x12\x03%\x12\x1b\n\x0c\n\x05\x04\x05\
-    \x02\0\x03\x12\x03%\x1e\x1f\n\x0b\n\x04\x04\x05\x02\x01\x12\x03&\x02$\n\
-    \x0c\n\x05\x04\x05\x02\x01\x04\x12\x03&\x02\n\n\x0c\n\x05\x04\x05\x02\
-    \x01\x05\x12\x03&\x0b\x11\n\x0c\n\x05\x04\x05\x02\x01\x01\x12\x03&\x12\
-    \x1f\n\x0c\n\x05\x04\x05\x02\x01\x03\x12\x03&\"#\n\x0b\n\x04\x04\x05\x02\
-    \x02\x12\x03'\x02\x1b\n\x0c\n\x05\x04\x05\x02\x02\x04\x12\x03'\x02\n\n\
-    \x0c\n\x05\x04\x05\x02\x02\x05\x12\x03'\x0b\x11\n\x0c\n\x05\x04\x05\x02\
-    \x02\x01\x12\x03'\x12\x16\n\x0c\n\x05\x04\x05\x02\x02\x03\x12\x03'\x19\
-    \x1a\n\x0b\n\x04\x04\x05\x02\x03\x12\x03(\x02\"\n\x0c\n\x05\x04\x05\x02\
-    \x03\x04\x12\x03(\x02\n\n\x0c\n\x05\x04\x05\x02\x03\x05\x12\x03(\x0b\x11\
-    \n\x0c\n\x05\x04\x05\x02\x03\x01\x12\x03(\x12\x1d\n\x0c\n\x05\x04\x05\
-    \x02\x03\x03\x12\x03(\x20!\n\x0b\n\x04\x04\x05\x02\x04\x12\x03)\x02\x1d\
-    \n\x0c\n\x05\x04\x05\x02\x04\x04\x12\x03)\x02\n\n\x0c\n\x05\x04\x05\x02\
-    \x04\x06\x12\x03)\x0b\x11\n\x0c\n\x05\x04\x05\x02\x04\x01\x12\x03)\x12\
-    \x18\n\x0c\n\x05\x04\x05\x02\x04\x03\x12\x03)\x1b\x1c\n\x0b\n\x04\x04\
-    \x05\x02\x05\x12\x03*\x02\x1c\n\x0c\n\x05\x04\x05\x02\x05\x04\x12\x03*\
-    \x02\n\n\x0c\n\x05\x04\x05\x02\x05\x05\x12\x03*\x0b\x11\n\x0c\n\x05\x04\
-    \x05\x02\x05\x01\x12\x03*\x12\x17\n\x0c\n\x05\x04\x05\x02\x05\x03\x12\
-    \x03*\x1a\x1b\n\n\n\x02\x04\x06\x12\x04-\05\x01\n\n\n\x03\x04\x06\x01\
-    \x12\x03-\x08\x0f\n\x0b\n\x04\x04\x06\x02\0\x12\x03.\x02\x20\n\x0c\n\x05\
-    \x04\x06\x02\0\x04\x12\x03.\x02\n\n\x0c\n\x05\x04\x06\x02\0\x05\x12\x03.\
-    \x0b\x11\n\x0c\n\x05\x04\x06\x02\0\x01\x12\x03.\x12\x1b\n\x0c\n\x05\x04\
-    \x06\x02\0\x03\x12\x03.\x1e\x1f\n\x0b\n\x04\x04\x06\x02\x01\x12\x03/\x02\
-    $\n\x0c\n\x05\x04\x06\x02\x01\x04\x12\x03/\x02\n\n\x0c\n\x05\x04\x06\x02\
-    \x01\x05\x12\x03/\x0b\x11\n\x0c\n\x05\x04\x06\x02\x01\x01\x12\x03/\x12\
-    \x1f\n\x0c\n\x05\x04\x06\x02\x01\x03\x12\x03/\"#\n\x0b\n\x04\x04\x06\x02\
-    \x02\x12\x030\x02\"\n\x0c\n\x05\x04\x06\x02\x02\x04\x12\x030\x02\n\n\x0c\
-    \n\x05\x04\x06\x02\x02\x05\x12\x030\x0b\x11\n\x0c\n\x05\x04\x06\x02\x02\
-    \x01\x12\x030\x12\x1d\n\x0c\n\x05\x04\x06\x02\x02\x03\x12\x030\x20!\n\
-    \x0b\n\x04\x04\x06\x02\x03\x12\x031\x02\x20\n\x0c\n\x05\x04\x06\x02\x03\
-    \x04\x12\x031\x02\n\n\x0c\n\x05\x04\x06\x02\x03\x05\x12\x031\x0b\x0f\n\
-    \x0c\n\x05\x04\x06\x02\x03\x01\x12\x031\x10\x1b\n\x0c\n\x05\x04\x06\x02\
-    \x03\x03\x12\x031\x1e\x1f\n\x0b\n\x04\x04\x06\x02\x04\x12\x032\x02\x1a\n\
-    \x0c\n\x05\x04\x06\x02\x04\x04\x12\x032\x02\n\n\x0c\n\x05\x04\x06\x02\
-    \x04\x05\x12\x032\x0b\x11\n\x0c\n\x05\x04\x06\x02\x04\x01\x12\x032\x12\
-    \x15\n\x0c\n\x05\x04\x06\x02\x04\x03\x12\x032\x18\x19\n\x0b\n\x04\x04\
-    \x06\x02\x05\x12\x033\x02\x1a\n\x0c\n\x05\x04\x06\x02\x05\x04\x12\x033\
-    \x02\n\n\x0c\n\x05\x04\x06\x02\x05\x05\x12\x033\x0b\x10\n\x0c\n\x05\x04\
-    \x06\x02\x05\x01\x12\x033\x11\x14\n\x0c\n\x05\x04\x06\x02\x05\x03\x12\
-    \x033\x17\x19\n\x0b\n\x04\x04\x06\x02\x06\x12\x034\x02\x1c\n\x0c\n\x05\
-    \x04\x06\x02\x06\x04\x12\x034\x02\n\n\x0c\n\x05\x04\x06\x02\x06\x06\x12\
-    \x034\x0b\x12\n\x0c\n\x05\x04\x06\x02\x06\x01\x12\x034\x13\x16\n\x0c\n\
-    \x05\x04\x06\x02\x06\x03\x12\x034\x19\x1b\n\n\n\x02\x04\x07\x12\x047\0<\
-    \x01\n\n\n\x03\x04\x07\x01\x12\x037\x08\x15\n\x0b\n\x04\x04\x07\x02\0\
-    \x12\x038\x02$\n\x0c\n\x05\x04\x07\x02\0\x04\x12\x038\x02\n\n\x0c\n\x05\
-    \x04\x07\x02\0\x05\x12\x038\x0b\x11\n\x0c\n\x05\x04\x07\x02\0\x01\x12\
-    \x038\x12\x1f\n\x0c\n\x05\x04\x07\x02\0\x03\x12\x038\"#\n\x0b\n\x04\x04\
-    \x07\x02\x01\x12\x039\x02\"\n\x0c\n\x05\x04\x07\x02\x01\x04\x12\x039\x02\
-    \n\n\x0c\n\x05\x04\x07\x02\x01\x05\x12\x039\x0b\x11\n\x0c\n\x05\x04\x07\
-    \x02\x01\x01\x12\x039\x12\x1d\n\x0c\n\x05\x04\x07\x02\x01\x03\x12\x039\
-    \x20!\n\x0b\n\x04\x04\x07\x02\x02\x12\x03:\x02\x1e\n\x0c\n\x05\x04\x07\
-    \x02\x02\x04\x12\x03:\x02\n\n\x0c\n\x05\x04\x07\x02\x02\x05\x12\x03:\x0b\
-    \x0f\n\x0c\n\x05\x04\x07\x02\x02\x01\x12\x03:\x10\x19\n\x0c\n\x05\x04\
-    \x07\x02\x02\x03\x12\x03:\x1c\x1d\n\x0b\n\x04\x04\x07\x02\x03\x12\x03;\
-    \x02\x1c\n\x0c\n\x05\x04\x07\x02\x03\x04\x12\x03;\x02\n\n\x0c\n\x05\x04\
-    \x07\x02\x03\x05\x12\x03;\x0b\x10\n\x0c\n\x05\x04\x07\x02\x03\x01\x12\
-    \x03;\x11\x17\n\x0c\n\x05\x04\x07\x02\x03\x03\x12\x03;\x1a\x1b\n\n\n\x02\
-    \x04\x08\x12\x04>\0D\x01\n\n\n\x03\x04\x08\x01\x12\x03>\x08\x13\n\x0b\n\
-    \x04\x04\x08\x02\0\x12\x03?\x02$\n\x0c\n\x05\x04\x08\x02\0\x04\x12\x03?\
-    \x02\n\n\x0c\n\x05\x04\x08\x02\0\x05\x12\x03?\x0b\x11\n\x0c\n\x05\x04\
-    \x08\x02\0\x01\x12\x03?\x12\x1f\n\x0c\n\x05\x04\x08\x02\0\x03\x12\x03?\"\
-    #\n\x0b\n\x04\x04\x08\x02\x01\x12\x03@\x02\"\n\x0c\n\x05\x04\x08\x02\x01\
-    \x04\x12\x03@\x02\n\n\x0c\n\x05\x04\x08\x02\x01\x05\x12\x03@\x0b\x11\n\
-    \x0c\n\x05\x04\x08\x02\x01\x01\x12\x03@\x12\x1d\n\x0c\n\x05\x04\x08\x02\
-    \x01\x03\x12\x03@\x20!\n\x0b\n\x04\x04\x08\x02\x02\x12\x03A\x02\x1e\n\
-    \x0c\n\x05\x04\x08\x02\x02\x04\x12\x03A\x02\n\n\x0c\n\x05\x04\x08\x02\
-    \x02\x05\x12\x03A\x0b\x0f\n\x0c\n\x05\x04\x08\x02\x02\x01\x12\x03A\x10\
-    \x19\n\x0c\n\x05\x04\x08\x02\x02\x03\x12\x03A\x1c\x1d\n\x0b\n\x04\x04\
-    \x08\x02\x03\x12\x03B\x02\x1f\n\x0c\n\x05\x04\x08\x02\x03\x04\x12\x03B\
-    \x02\n\n\x0c\n\x05\x04\x08\x02\x03\x05\x12\x03B\x0b\x11\n\x0c\n\x05\x04\
-    \x08\x02\x03\x01\x12\x03B\x12\x1a\n\x0c\n\x05\x04\x08\x02\x03\x03\x12\
-    \x03B\x1d\x1e\n\x0b\n\x04\x04\x08\x02\x04\x12\x03C\x02\x1a\n\x0c\n\x05\
-    \x04\x08\x02\x04\x04\x12\x03C\x02\n\n\x0c\n\x05\x04\x08\x02\x04\x05\x12\
-    \x03C\x0b\x10\n\x0c\n\x05\x04\x08\x02\x04\x01\x12\x03C\x11\x15\n\x0c\n\
-    \x05\x04\x08\x02\x04\x03\x12\x03C\x18\x19\n\n\n\x02\x04\t\x12\x04F\0M\
-    \x01\n\n\n\x03\x04\t\x01\x12\x03F\x08\x0f\n\x0b\n\x04\x04\t\x02\0\x12\
-    \x03G\x021\n\x0c\n\x05\x04\t\x02\0\x04\x12\x03G\x02\n\n\x0c\n\x05\x04\t\
-    \x02\0\x05\x12\x03G\x0b\x11\n\x0c\n\x05\x04\t\x02\0\x01\x12\x03G\x12\x14\
-    \n\x0c\n\x05\x04\t\x02\0\x03\x12\x03G\x17\x18\n\x0c\n\x05\x04\t\x02\0\
-    \x08\x12\x03G\x190\n\x0c\n\x05\x04\t\x02\0\x07\x12\x03G$/\n\x0b\n\x04\
-    \x04\t\x02\x01\x12\x03H\x027\n\x0c\n\x05\x04\t\x02\x01\x04\x12\x03H\x02\
-    \n\n\x0c\n\x05\x04\t\x02\x01\x05\x12\x03H\x0b\x11\n\x0c\n\x05\x04\t\x02\
-    \x01\x01\x12\x03H\x12\x1a\n\x0c\n\x05\x04\t\x02\x01\x03\x12\x03H\x1d\x1e\
-    \n\x0c\n\x05\x04\t\x02\x01\x08\x12\x03H\x1f6\n\x0c\n\x05\x04\t\x02\x01\
-    \x07\x12\x03H*5\n\x0b\n\x04\x04\t\x02\x02\x12\x03I\x028\n\x0c\n\x05\x04\
-    \t\x02\x02\x04\x12\x03I\x02\n\n\x0c\n\x05\x04\t\x02\x02\x05\x12\x03I\x0b\
-    \x11\n\x0c\n\x05\x04\t\x02\x02\x01\x12\x03I\x12\x1b\n\x0c\n\x05\x04\t\
-    \x02\x02\x03\x12\x03I\x1e\x1f\n\x0c\n\x05\x04\t\x02\x02\x08\x12\x03I\x20\
-    7\n\x0c\n\x05\x04\t\x02\x02\x07\x12\x03I+6\n\x0b\n\x04\x04\t\x02\x03\x12\
-    \x03J\x02\"\n\x0c\n\x05\x04\t\x02\x03\x04\x12\x03J\x02\n\n\x0c\n\x05\x04\
-    \t\x02\x03\x05\x12\x03J\x0b\x11\n\x0c\n\x05\x04\t\x02\x03\x01\x12\x03J\
-    \x12\x1d\n\x0c\n\x05\x04\t\x02\x03\x03\x12\x03J\x20!\n\x0b\n\x04\x04\t\
-    \x02\x04\x12\x03K\x02>\n\x0c\n\x05\x04\t\x02\x04\x04\x12\x03K\x02\n\n\
-    \x0c\n\x05\x04\t\x02\x04\x05\x12\x03K\x0b\x11\n\x0c\n\x05\x04\t\x02\x04\
-    \x01\x12\x03K\x12!\n\x0c\n\x05\x04\t\x02\x04\x03\x12\x03K$%\n\x0c\n\x05\
-    \x04\t\x02\x04\x08\x12\x03K&=\n\x0c\n\x05\x04\t\x02\x04\x07\x12\x03K1<\n\
-    \x0b\n\x04\x04\t\x02\x05\x12\x03L\x02(\n\x0c\n\x05\x04\t\x02\x05\x04\x12\
-    \x03L\x02\n\n\x0c\n\x05\x04\t\x02\x05\x05\x12\x03L\x0b\x11\n\x0c\n\x05\
-    \x04\t\x02\x05\x01\x12\x03L\x12#\n\x0c\n\x05\x04\t\x02\x05\x03\x12\x03L&\
-    '\n\n\n\x02\x04\n\x12\x04O\0Q\x01\n\n\n\x03\x04\n\x01\x12\x03O\x08\x11\n\
-    \x0b\n\x04\x04\n\x02\0\x12\x03P\x02\x20\n\x0c\n\x05\x04\n\x02\0\x04\x12\
-    \x03P\x02\n\n\x0c\n\x05\x04\n\x02\0\x05\x12\x03P\x0b\x11\n\x0c\n\x05\x04\
-    \n\x02\0\x01\x12\x03P\x12\x1b\n\x0c\n\x05\x04\n\x02\0\x03\x12\x03P\x1e\
-    \x1f\n\n\n\x02\x04\x0b\x12\x04S\0^\x01\n\n\n\x03\x04\x0b\x01\x12\x03S\
-    \x08\x0c\n\x0b\n\x04\x04\x0b\x04\0\x12\x03T\x02/\n\x0c\n\x05\x04\x0b\x04\
-    \0\x01\x12\x03T\x07\x0b\n\r\n\x06\x04\x0b\x04\0\x02\0\x12\x03T\x0e\x17\n\
-    \x0e\n\x07\x04\x0b\x04\0\x02\0\x01\x12\x03T\x0e\x12\n\x0e\n\x07\x04\x0b\
-    \x04\0\x02\0\x02\x12\x03T\x15\x16\n\r\n\x06\x04\x0b\x04\0\x02\x01\x12\
-    \x03T\x18\x20\n\x0e\n\x07\x04\x0b\x04\0\x02\x01\x01\x12\x03T\x18\x1b\n\
-    \x0e\n\x07\x04\x0b\x04\0\x02\x01\x02\x12\x03T\x1e\x1f\n\r\n\x06\x04\x0b\
-    \x04\0\x02\x02\x12\x03T!-\n\x0e\n\x07\x04\x0b\x04\0\x02\x02\x01\x12\x03T\
-    !(\n\x0e\n\x07\x04\x0b\x04\0\x02\x02\x02\x12\x03T+,\n3\n\x04\x04\x0b\x02\
-    \0\x12\x03W\x02\x19\x1a&\x20Identifies\x20which\x20field\x20is\x20filled\
-    \x20in.\n\n\x0c\n\x05\x04\x0b\x02\0\x04\x12\x03W\x02\n\n\x0c\n\x05\x04\
-    \x0b\x02\0\x06\x12\x03W\x0b\x0f\n\x0c\n\x05\x04\x0b\x02\0\x01\x12\x03W\
-    \x10\x14\n\x0c\n\x05\x04\x0b\x02\0\x03\x12\x03W\x17\x18\n\x0c\n\x04\x04\
-    \x0b\x08\0\x12\x04X\x02\\\x03\n\x0c\n\x05\x04\x0b\x08\0\x01\x12\x03X\x08\
-    \x0f\n\x0b\n\x04\x04\x0b\x02\x01\x12\x03Y\x04\x12\n\x0c\n\x05\x04\x0b\
-    \x02\x01\x06\x12\x03Y\x04\x08\n\x0c\n\x05\x04\x0b\x02\x01\x01\x12\x03Y\t\
-    \r\n\x0c\n\x05\x04\x0b\x02\x01\x03\x12\x03Y\x10\x11\n\x0b\n\x04\x04\x0b\
-    \x02\x02\x12\x03Z\x04\x10\n\x0c\n\x05\x04\x0b\x02\x02\x06\x12\x03Z\x04\
-    \x07\n\x0c\n\x05\x04\x0b\x02\x02\x01\x12\x03Z\x08\x0b\n\x0c\n\x05\x04\
-    \x0b\x02\x02\x03\x12\x03Z\x0e\x0f\n\x0b\n\x04\x04\x0b\x02\x03\x12\x03[\
-    \x04\x18\n\x0c\n\x05\x04\x0b\x02\x03\x06\x12\x03[\x04\x0b\n\x0c\n\x05\
-    \x04\x0b\x02\x03\x01\x12\x03[\x0c\x13\n\x0c\n\x05\x04\x0b\x02\x03\x03\
-    \x12\x03[\x16\x17\n\x0b\n\x04\x04\x0b\x02\x04\x12\x03]\x02%\n\x0c\n\x05\
-    \x04\x0b\x02\x04\x04\x12\x03]\x02\n\n\x0c\n\x05\x04\x0b\x02\x04\x06\x12\
-    \x03]\x0b\x15\n\x0c\n\x05\x04\x0b\x02\x04\x01\x12\x03]\x16\x20\n\x0c\n\
-    \x05\x04\x0b\x02\x04\x03\x12\x03]#$\n\n\n\x02\x04\x0c\x12\x04`\0x\x01\n\
-    \n\n\x03\x04\x0c\x01\x12\x03`\x08\r\n\x0c\n\x04\x04\x0c\x04\0\x12\x04a\
-    \x02k\x03\n\x0c\n\x05\x04\x0c\x04\0\x01\x12\x03a\x07\x0b\n\r\n\x06\x04\
-    \x0c\x04\0\x02\0\x12\x03b\x04\x0f\n\x0e\n\x07\x04\x0c\x04\0\x02\0\x01\
-    \x12\x03b\x04\n\n\x0e\n\x07\x04\x0c\x04\0\x02\0\x02\x12\x03b\r\x0e\n\r\n\
-    \x06\x04\x0c\x04\0\x02\x01\x12\x03c\x04\x10\n\x0e\n\x07\x04\x0c\x04\0\
-    \x02\x01\x01\x12\x03c\x04\x0b\n\x0e\n\x07\x04\x0c\x04\0\x02\x01\x02\x12\
-    \x03c\x0e\x0f\n\r\n\x06\x04\x0c\x04\0\x02\x02\x12\x03d\x04\x11\n\x0e\n\
-    \x07\x04\x0c\x04\0\x02\x02\x01\x12\x03d\x04\x0c\n\x0e\n\x07\x04\x0c\x04\
-    \0\x02\x02\x02\x12\x03d\x0f\x10\n\r\n\x06\x04\x0c\x04\0\x02\x03\x12\x03e\
-    \x04\x16\n\x0e\n\x07\x04\x0c\x04\0\x02\x03\x01\x12\x03e\x04\x11\n\x0e\n\
-    \x07\x04\x0c\x04\0\x02\x03\x02\x12\x03e\x14\x15\n\r\n\x06\x04\x0c\x04\0\
-    \x02\x04\x12\x03f\x04\x14\n\x0e\n\x07\x04\x0c\x04\0\x02\x04\x01\x12\x03f\
-    \x04\x0f\n\x0e\n\x07\x04\x0c\x04\0\x02\x04\x02\x12\x03f\x12\x13\n\r\n\
-    \x06\x04\x0c\x04\0\x02\x05\x12\x03g\x04\r\n\x0e\n\x07\x04\x0c\x04\0\x02\
-    \x05\x01\x12\x03g\x04\x08\n\x0e\n\x07\x04\x0c\x04\0\x02\x05\x02\x12\x03g\
-    \x0b\x0c\n\r\n\x06\x04\x0c\x04\0\x02\x06\x12\x03h\x04\x0e\n\x0e\n\x07\
-    \x04\x0c\x04\0\x02\x06\x01\x12\x03h\x04\t\n\x0e\n\x07\x04\x0c\x04\0\x02\
-    \x06\x02\x12\x03h\x0c\r\n\r\n\x06\x04\x0c\x04\0\x02\x07\x12\x03i\x04\x17\
-    \n\x0e\n\x07\x04\x0c\x04\0\x02\x07\x01\x12\x03i\x04\x12\n\x0e\n\x07\x04\
-    \x0c\x04\0\x02\x07\x02\x12\x03i\x15\x16\n\r\n\x06\x04\x0c\x04\0\x02\x08\
-    \x12\x03j\x04\x12\n\x0e\n\x07\x04\x0c\x04\0\x02\x08\x01\x12\x03j\x04\r\n\
-    \x0e\n\x07\x04\x0c\x04\0\x02\x08\x02\x12\x03j\x10\x11\n\x0b\n\x04\x04\
-    \x0c\x02\0\x12\x03m\x02\x19\n\x0c\n\x05\x04\x0c\x02\0\x04\x12\x03m\x02\n\
-    \n\x0c\n\x05\x04\x0c\x02\0\x06\x12\x03m\x0b\x0f\n\x0c\n\x05\x04\x0c\x02\
-    \0\x01\x12\x03m\x10\x14\n\x0c\n\x05\x04\x0c\x02\0\x03\x12\x03m\x17\x18\n\
-    \x0b\n\x04\x04\x0c\x02\x01\x12\x03n\x02\x1a\n\x0c\n\x05\x04\x0c\x02\x01\
-    \x04\x12\x03n\x02\n\n\x0c\n\x05\x04\x0c\x02\x01\x05\x12\x03n\x0b\x11\n\
-    \x0c\n\x05\x04\x0c\x02\x01\x01\x12\x03n\x12\x15\n\x0c\n\x05\x04\x0c\x02\
-    \x01\x03\x12\x03n\x18\x19\n\x0b\n\x04\x04\x0c\x02\x02\x12\x03o\x02\x1e\n\
-    \x0c\n\x05\x04\x0c\x02\x02\x04\x12\x03o\x02\n\n\x0c\n\x05\x04\x0c\x02\
-    \x02\x05\x12\x03o\x0b\x11\n\x0c\n\x05\x04\x0c\x02\x02\x01\x12\x03o\x12\
-    \x19\n\x0c\n\x05\x04\x0c\x02\x02\x03\x12\x03o\x1c\x1d\n\x0c\n\x04\x04\
-    \x0c\x08\0\x12\x04p\x02w\x03\n\x0c\n\x05\x04\x0c\x08\0\x01\x12\x03p\x08\
-    \x0f\n\x0b\n\x04\x04\x0c\x02\x03\x12\x03q\x04\x1a\n\x0c\n\x05\x04\x0c\
-    \x02\x03\x06\x12\x03q\x04\x0e\n\x0c\n\x05\x04\x0c\x02\x03\x01\x12\x03q\
-    \x0f\x15\n\x0c\n\x05\x04\x0c\x02\x03\x03\x12\x03q\x18\x19\n\x0b\n\x04\
-    \x04\x0c\x02\x04\x12\x03r\x04\x18\n\x0c\n\x05\x04\x0c\x02\x04\x06\x12\
-    \x03r\x04\x0b\n\x0c\n\x05\x04\x0c\x02\x04\x01\x12\x03r\x0c\x13\n\x0c\n\
-    \x05\x04\x0c\x02\x04\x03\x12\x03r\x16\x17\n\x0b\n\x04\x04\x0c\x02\x05\
-    \x12\x03s\x04%\n\x0c\n\x05\x04\x0c\x02\x05\x06\x12\x03s\x04\x11\n\x0c\n\
-    \x05\x04\x0c\x02\x05\x01\x12\x03s\x12\x20\n\x0c\n\x05\x04\x0c\x02\x05\
-    \x03\x12\x03s#$\n\x0b\n\x04\x04\x0c\x02\x06\x12\x03t\x04!\n\x0c\n\x05\
-    \x04\x0c\x02\x06\x06\x12\x03t\x04\x0f\n\x0c\n\x05\x04\x0c\x02\x06\x01\
-    \x12\x03t\x10\x1c\n\x0c\n\x05\x04\x0c\x02\x06\x03\x12\x03t\x1f\x20\n\x0b\
-    \n\x04\x04\x0c\x02\x07\x12\x03u\x04\x1a\n\x0c\n\x05\x04\x0c\x02\x07\x06\
-    \x12\x03u\x04\x0c\n\x0c\n\x05\x04\x0c\x02\x07\x01\x12\x03u\r\x15\n\x0c\n\
-    \x05\x04\x0c\x02\x07\x03\x12\x03u\x18\x19\n\x0b\n\x04\x04\x0c\x02\x08\
-    \x12\x03v\x04\x1c\n\x0c\n\x05\x04\x0c\x02\x08\x06\x12\x03v\x04\r\n\x0c\n\
-    \x05\x04\x0c\x02\x08\x01\x12\x03v\x0e\x17\n\x0c\n\x05\x04\x0c\x02\x08\
-    \x03\x12\x03v\x1a\x1b\n\n\n\x02\x04\r\x12\x04z\0~\x01\n\n\n\x03\x04\r\
-    \x01\x12\x03z\x08\x0c\n\x0b\n\x04\x04\r\x02\0\x12\x03{\x02\x1e\n\x0c\n\
-    \x05\x04\r\x02\0\x04\x12\x03{\x02\n\n\x0c\n\x05\x04\r\x02\0\x05\x12\x03{\
-    \x0b\x0f\n\x0c\n\x05\x04\r\x02\0\x01\x12\x03{\x10\x19\n\x0c\n\x05\x04\r\
-    \x02\0\x03\x12\x03{\x1c\x1d\n\x0b\n\x04\x04\r\x02\x01\x12\x03|\x02\x1b\n\
-    \x0c\n\x05\x04\r\x02\x01\x04\x12\x03|\x02\n\n\x0c\n\x05\x04\r\x02\x01\
-    \x05\x12\x03|\x0b\x10\n\x0c\n\x05\x04\r\x02\x01\x01\x12\x03|\x11\x16\n\
-    \x0c\n\x05\x04\r\x02\x01\x03\x12\x03|\x19\x1a\n\x0b\n\x04\x04\r\x02\x02\
-    \x12\x03}\x02\x1d\n\x0c\n\x05\x04\r\x02\x02\x04\x12\x03}\x02\n\n\x0c\n\
-    \x05\x04\r\x02\x02\x05\x12\x03}\x0b\x10\n\x0c\n\x05\x04\r\x02\x02\x01\
-    \x12\x03}\x11\x18\n\x0c\n\x05\x04\r\x02\x02\x03\x12\x03}\x1b\x1c\
+    R\x08departed\x12%\n\x04tags\x18\x08\x20\x03(\x0b2\x11.Member.TagsEntryR\
+    \x04tags\x12&\n\x0bdigest_sync\x18\t\x20\x01(\x08:\x05falseR\ndigestSync\
+    \x1a7\n\tTagsEntry\x12\x10\n\x03key\x18\x01\x20\x01(\tR\x03key\x12\x14\n\
+    \x05value\x18\x02\x20\x01(\tR\x05value:\x028\x01\"K\n\x04Ping\x12\x1b\n\
+    \x04from\x18\x01\x20\x01(\x0b2\x07.MemberR\x04from\x12&\n\nforward_to\
+    \x18\x02\x20\x01(\x0b2\x07.MemberR\tforwardTo\"J\n\x03Ack\x12\x1b\n\x04f\
+    rom\x18\x01\x20\x01(\x0b2\x07.MemberR\x04from\x12&\n\nforward_to\x18\x02\
+    \x20\x01(\x0b2\x07.MemberR\tforwardTo\"G\n\x07PingReq\x12\x1b\n\x04from\
+    \x18\x01\x20\x01(\x0b2\x07.MemberR\x04from\x12\x1f\n\x06target\x18\x02\
+    \x20\x01(\x0b2\x07.MemberR\x06target\"\xbb\x01\n\nMembership\x12\x1f\n\
+    \x06member\x18\x01\x20\x01(\x0b2\x07.MemberR\x06member\x12*\n\x06health\
+    \x18\x02\x20\x01(\x0e2\x12.Membership.HealthR\x06health\x12!\n\x0csuspec\
+    ted_by\x18\x03\x20\x01(\tR\x0bsuspectedBy\"=\n\x06Health\x12\t\n\x05ALIV\
+    E\x10\x01\x12\x0b\n\x07SUSPECT\x10\x02\x12\r\n\tCONFIRMED\x10\x03\x12\
+    \x0c\n\x08DEPARTED\x10\x04\"\x9a\x02\n\x08Election\x12\x1b\n\tmember_id\
+    \x18\x01\x20\x01(\tR\x08memberId\x12#\n\rservice_group\x18\x02\x20\x01(\
+    \tR\x0cserviceGroup\x12\x12\n\x04term\x18\x03\x20\x01(\x04R\x04term\x12\
+    \x20\n\x0bsuitability\x18\x04\x20\x01(\x04R\x0bsuitability\x12(\n\x06sta\
+    tus\x18\x05\x20\x01(\x0e2\x10.Election.StatusR\x06status\x12\x14\n\x05vo\
+    tes\x18\x06\x20\x03(\tR\x05votes\x12#\n\rlease_renewal\x18\x07\x20\x01(\
+    \x04R\x0cleaseRenewal\"1\n\x06Status\x12\x0b\n\x07Running\x10\x01\x12\
+    \x0c\n\x08NoQuorum\x10\x02\x12\x0c\n\x08Finished\x10\x03\"\xf5\x01\n\x07\
+    Service\x12\x1b\n\tmember_id\x18\x01\x20\x01(\tR\x08memberId\x12#\n\rser\
+    vice_group\x18\x02\x20\x01(\tR\x0cserviceGroup\x12\x20\n\x0bincarnation\
+    \x18\x03\x20\x01(\x04R\x0bincarnation\x12\x20\n\x0binitialized\x18\x08\
+    \x20\x01(\x08R\x0binitialized\x12\x10\n\x03pkg\x18\t\x20\x01(\tR\x03pkg\
+    \x12\x10\n\x03cfg\x18\n\x20\x01(\x0cR\x03cfg\x12\x1a\n\x03sys\x18\x0c\
+    \x20\x01(\x0b2\x08.SysInfoR\x03sys\x12$\n\x0chealth_check\x18\r\x20\x01(\
+    \r:\x013R\x0bhealthCheck\"\x8c\x01\n\rServiceConfig\x12#\n\rservice_grou\
+    p\x18\x01\x20\x01(\tR\x0cserviceGroup\x12\x20\n\x0bincarnation\x18\x02\
+    \x20\x01(\x04R\x0bincarnation\x12\x1c\n\tencrypted\x18\x03\x20\x01(\x08R\
+    \tencrypted\x12\x16\n\x06config\x18\x04\x20\x01(\x0cR\x06config\"\xa2\
+    \x01\n\x0bServiceFile\x12#\n\rservice_group\x18\x01\x20\x01(\tR\x0cservi\
+    ceGroup\x12\x20\n\x0bincarnation\x18\x02\x20\x01(\x04R\x0bincarnation\
+    \x12\x1c\n\tencrypted\x18\x03\x20\x01(\x08R\tencrypted\x12\x1a\n\x08file\
+    name\x18\x04\x20\x01(\tR\x08filename\x12\x12\n\x04body\x18\x05\x20\x01(\
+    \x0cR\x04body\"\xd4\x02\n\x07SysInfo\x12\x19\n\x02ip\x18\x01\x20\x01(\t:\
+    \t127.0.0.1R\x02ip\x12%\n\x08hostname\x18\x02\x20\x01(\t:\tlocalhostR\
+    \x08hostname\x12&\n\tgossip_ip\x18\x03\x20\x01(\t:\t127.0.0.1R\x08gossip\
+    Ip\x12\x1f\n\x0bgossip_port\x18\x04\x20\x01(\rR\ngossipPort\x121\n\x0fht\
+    tp_gateway_ip\x18\x05\x20\x01(\t:\t127.0.0.1R\rhttpGatewayIp\x12*\n\x11h\
+    ttp_gateway_port\x18\x06\x20\x01(\rR\x0fhttpGatewayPort\x12&\n\x04tags\
+    \x18\x07\x20\x03(\x0b2\x12.SysInfo.TagsEntryR\x04tags\x1a7\n\tTagsEntry\
+    \x12\x10\n\x03key\x18\x01\x20\x01(\tR\x03key\x12\x14\n\x05value\x18\x02\
+    \x20\x01(\tR\x05value:\x028\x01\"(\n\tDeparture\x12\x1b\n\tmember_id\x18\
+    \x01\x20\x01(\tR\x08memberId\"\x9a\x02\n\x06Canary\x12\x1b\n\tmember_id\
+    \x18\x01\x20\x01(\tR\x08memberId\x12#\n\rservice_group\x18\x02\x20\x01(\
+    \tR\x0cserviceGroup\x12\x20\n\x0bincarnation\x18\x03\x20\x01(\x04R\x0bin\
+    carnation\x12\x10\n\x03pkg\x18\x04\x20\x01(\tR\x03pkg\x12!\n\x0cprevious\
+    _pkg\x18\x05\x20\x01(\tR\x0bpreviousPkg\x12\x1a\n\x08canaries\x18\x06\
+    \x20\x03(\tR\x08canaries\x12&\n\x06status\x18\x07\x20\x01(\x0e2\x0e.Cana\
+    ry.StatusR\x06status\"3\n\x06Status\x12\x0b\n\x07Testing\x10\x01\x12\x0c\
+    \n\x08Promoted\x10\x02\x12\x0e\n\nRolledBack\x10\x03\"\xe3\x01\n\x04Swim\
+    \x12\x1e\n\x04type\x18\x01\x20\x02(\x0e2\n.Swim.TypeR\x04type\x12\x1b\n\
+    \x04ping\x18\x02\x20\x01(\x0b2\x05.PingH\0R\x04ping\x12\x18\n\x03ack\x18\
+    \x03\x20\x01(\x0b2\x04.AckH\0R\x03ack\x12$\n\x07pingreq\x18\x04\x20\x01(\
+    \x0b2\x08.PingReqH\0R\x07pingreq\x12+\n\nmembership\x18\x05\x20\x03(\x0b\
+    2\x0b.MembershipR\nmembership\"&\n\x04Type\x12\x08\n\x04PING\x10\x01\x12\
+    \x07\n\x03ACK\x10\x02\x12\x0b\n\x07PINGREQ\x10\x03B\t\n\x07payload\"\x8f\
+    \x01\n\x07RingKey\x12\x12\n\x04name\x18\x01\x20\x01(\tR\x04name\x12\x1a\
+    \n\x08revision\x18\x02\x20\x01(\tR\x08revision\x12\x10\n\x03key\x18\x03\
+    \x20\x01(\x0cR\x03key\x12\x1f\n\x0bactivate_at\x18\x04\x20\x01(\x03R\nac\
+    tivateAt\x12!\n\x0cgrace_period\x18\x05\x20\x01(\x03R\x0bgracePeriod\"r\
+    \n\x0bRumorDigest\x12\x1f\n\x04kind\x18\x01\x20\x01(\x0e2\x0b.Rumor.Type\
+    R\x04kind\x12\x10\n\x03key\x18\x02\x20\x01(\tR\x03key\x12\x0e\n\x02id\
+    \x18\x03\x20\x01(\tR\x02id\x12\x20\n\x0bincarnation\x18\x04\x20\x01(\x04\
+    R\x0bincarnation\".\n\x06Digest\x12$\n\x06rumors\x18\x01\x20\x03(\x0b2\
+    \x0c.RumorDigestR\x06rumors\"\xae\x05\n\x05Rumor\x12\x1f\n\x04type\x18\
+    \x01\x20\x02(\x0e2\x0b.Rumor.TypeR\x04type\x12\x10\n\x03tag\x18\x02\x20\
+    \x03(\tR\x03tag\x12\x17\n\x07from_id\x18\x03\x20\x01(\tR\x06fromId\x12%\
+    \n\x06member\x18\x04\x20\x01(\x0b2\x0b.MembershipH\0R\x06member\x12$\n\
+    \x07service\x18\x05\x20\x01(\x0b2\x08.ServiceH\0R\x07service\x127\n\x0es\
+    ervice_config\x18\x06\x20\x01(\x0b2\x0e.ServiceConfigH\0R\rserviceConfig\
+    \x121\n\x0cservice_file\x18\x07\x20\x01(\x0b2\x0c.ServiceFileH\0R\x0bser\
+    viceFile\x12'\n\x08election\x18\x08\x20\x01(\x0b2\t.ElectionH\0R\x08elec\
+    tion\x12*\n\tdeparture\x18\t\x20\x01(\x0b2\n.DepartureH\0R\tdeparture\
+    \x12!\n\x06canary\x18\n\x20\x01(\x0b2\x07.CanaryH\0R\x06canary\x12%\n\
+    \x08ring_key\x18\x0b\x20\x01(\x0b2\x08.RingKeyH\0R\x07ringKey\x12!\n\x06\
+    digest\x18\x0c\x20\x01(\x0b2\x07.DigestH\0R\x06digest\"\xd2\x01\n\x04Typ\
+    e\x12\n\n\x06Member\x10\x01\x12\x0b\n\x07Service\x10\x02\x12\x0c\n\x08El\
+    ection\x10\x03\x12\x11\n\rServiceConfig\x10\x04\x12\x0f\n\x0bServiceFile\
+    \x10\x05\x12\x08\n\x04Fake\x10\x06\x12\t\n\x05Fake2\x10\x07\x12\x12\n\
+    \x0eElectionUpdate\x10\x08\x12\r\n\tDeparture\x10\t\x12\n\n\x06Canary\
+    \x10\n\x12\x0b\n\x07RingKey\x10\x0b\x12\x11\n\rDigestRequest\x10\x0c\x12\
+    \n\n\x06Digest\x10\r\x12\x0f\n\x0bDigestFetch\x10\x0eB\t\n\x07payload\"m\
+    \n\x04Wire\x12\x1c\n\tencrypted\x18\x01\x20\x01(\x08R\tencrypted\x12\x14\
+    \n\x05nonce\x18\x02\x20\x01(\x0cR\x05nonce\x12\x18\n\x07payload\x18\x03\
+    \x20\x01(\x0cR\x07payload\x12\x17\n\x07key_rev\x18\x04\x20\x01(\tR\x06ke\
+    yRevJ\xadD\n\x07\x12\x05\0\0\xb3\x01\x01\n\x08\n\x01\x0c\x12\x03\0\0\x12\
+    \n\n\n\x02\x04\0\x12\x04\x02\0\x0f\x01\n\n\n\x03\x04\0\x01\x12\x03\x02\
+    \x08\x0e\n\x0b\n\x04\x04\0\x02\0\x12\x03\x03\x02\x19\n\x0c\n\x05\x04\0\
+    \x02\0\x04\x12\x03\x03\x02\n\n\x0c\n\x05\x04\0\x02\0\x05\x12\x03\x03\x0b\
+    \x11\n\x0c\n\x05\x04\0\x02\0\x01\x12\x03\x03\x12\x14\n\x0c\n\x05\x04\0\
+    \x02\0\x03\x12\x03\x03\x17\x18\n\x0b\n\x04\x04\0\x02\x01\x12\x03\x04\x02\
+    \"\n\x0c\n\x05\x04\0\x02\x01\x04\x12\x03\x04\x02\n\n\x0c\n\x05\x04\0\x02\
+    \x01\x05\x12\x03\x04\x0b\x11\n\x0c\n\x05\x04\0\x02\x01\x01\x12\x03\x04\
+    \x12\x1d\n\x0c\n\x05\x04\0\x02\x01\x03\x12\x03\x04\x20!\n\x0b\n\x04\x04\
+    \0\x02\x02\x12\x03\x05\x02\x1e\n\x0c\n\x05\x04\0\x02\x02\x04\x12\x03\x05\
+    \x02\n\n\x0c\n\x05\x04\0\x02\x02\x05\x12\x03\x05\x0b\x11\n\x0c\n\x05\x04\
+    \0\x02\x02\x01\x12\x03\x05\x12\x19\n\x0c\n\x05\x04\0\x02\x02\x03\x12\x03\
+    \x05\x1c\x1d\n\x0b\n\x04\x04\0\x02\x03\x12\x03\x06\x02\x1f\n\x0c\n\x05\
+    \x04\0\x02\x03\x04\x12\x03\x06\x02\n\n\x0c\n\x05\x04\0\x02\x03\x05\x12\
+    \x03\x06\x0b\x10\n\x0c\n\x05\x04\0\x02\x03\x01\x12\x03\x06\x11\x1a\n\x0c\
+    \n\x05\x04\0\x02\x03\x03\x12\x03\x06\x1d\x1e\n\x0b\n\x04\x04\0\x02\x04\
+    \x12\x03\x07\x02!\n\x0c\n\x05\x04\0\x02\x04\x04\x12\x03\x07\x02\n\n\x0c\
+    \n\x05\x04\0\x02\x04\x05\x12\x03\x07\x0b\x10\n\x0c\n\x05\x04\0\x02\x04\
+    \x01\x12\x03\x07\x11\x1c\n\x0c\n\x05\x04\0\x02\x04\x03\x12\x03\x07\x1f\
+    \x20\n\x0b\n\x04\x04\0\x02\x05\x12\x03\x08\x021\n\x0c\n\x05\x04\0\x02\
+    \x05\x04\x12\x03\x08\x02\n\n\x0c\n\x05\x04\0\x02\x05\x05\x12\x03\x08\x0b\
+    \x0f\n\x0c\n\x05\x04\0\x02\x05\x01\x12\x03\x08\x10\x1a\n\x0c\n\x05\x04\0\
+    \x02\x05\x03\x12\x03\x08\x1d\x1e\n\x0c\n\x05\x04\0\x02\x05\x08\x12\x03\
+    \x08\x1f0\n\x0c\n\x05\x04\0\x02\x05\x07\x12\x03\x08*/\n\x0b\n\x04\x04\0\
+    \x02\x06\x12\x03\t\x02/\n\x0c\n\x05\x04\0\x02\x06\x04\x12\x03\t\x02\n\n\
+    \x0c\n\x05\x04\0\x02\x06\x05\x12\x03\t\x0b\x0f\n\x0c\n\x05\x04\0\x02\x06\
+    \x01\x12\x03\t\x10\x18\n\x0c\n\x05\x04\0\x02\x06\x03\x12\x03\t\x1b\x1c\n\
+    \x0c\n\x05\x04\0\x02\x06\x08\x12\x03\t\x1d.\n\x0c\n\x05\x04\0\x02\x06\
+    \x07\x12\x03\t(-\nU\n\x04\x04\0\x02\x07\x12\x03\x0b\x02\x1f\x1aH\x20User\
+    -defined\x20labels\x20of\x20the\x20Supervisor,\x20such\x20as\x20its\x20z\
+    one,\x20rack\x20or\x20role.\n\n\x0c\n\x05\x04\0\x02\x07\x06\x12\x03\x0b\
+    \x02\x15\n\x0c\n\x05\x04\0\x02\x07\x01\x12\x03\x0b\x16\x1a\n\x0c\n\x05\
+    \x04\0\x02\x07\x03\x12\x03\x0b\x1d\x1e\n\xa7\x01\n\x04\x04\0\x02\x08\x12\
+    \x03\x0e\x022\x1a\x99\x01\x20Set\x20by\x20members\x20which\x20answer\x20\
+    the\x20DigestRequest,\x20Digest\x20and\x20DigestFetch\x20rumors\x20of\
+    \x20the\n\x20anti-entropy\x20sync.\x20Older\x20members\x20drop\x20those\
+    \x20rumors\x20as\x20unparseable.\n\n\x0c\n\x05\x04\0\x02\x08\x04\x12\x03\
+    \x0e\x02\n\n\x0c\n\x05\x04\0\x02\x08\x05\x12\x03\x0e\x0b\x0f\n\x0c\n\x05\
+    \x04\0\x02\x08\x01\x12\x03\x0e\x10\x1b\n\x0c\n\x05\x04\0\x02\x08\x03\x12\
+    \x03\x0e\x1e\x1f\n\x0c\n\x05\x04\0\x02\x08\x08\x12\x03\x0e\x201\n\x0c\n\
+    \x05\x04\0\x02\x08\x07\x12\x03\x0e+0\n\n\n\x02\x04\x01\x12\x04\x11\0\x14\
+    \x01\n\n\n\x03\x04\x01\x01\x12\x03\x11\x08\x0c\n\x0b\n\x04\x04\x01\x02\0\
+    \x12\x03\x12\x02\x1b\n\x0c\n\x05\x04\x01\x02\0\x04\x12\x03\x12\x02\n\n\
+    \x0c\n\x05\x04\x01\x02\0\x06\x12\x03\x12\x0b\x11\n\x0c\n\x05\x04\x01\x02\
+    \0\x01\x12\x03\x12\x12\x16\n\x0c\n\x05\x04\x01\x02\0\x03\x12\x03\x12\x19\
+    \x1a\n\x0b\n\x04\x04\x01\x02\x01\x12\x03\x13\x02!\n\x0c\n\x05\x04\x01\
+    \x02\x01\x04\x12\x03\x13\x02\n\n\x0c\n\x05\x04\x01\x02\x01\x06\x12\x03\
+    \x13\x0b\x11\n\x0c\n\x05\x04\x01\x02\x01\x01\x12\x03\x13\x12\x1c\n\x0c\n\
+    \x05\x04\x01\x02\x01\x03\x12\x03\x13\x1f\x20\n\n\n\x02\x04\x02\x12\x04\
+    \x16\0\x19\x01\n\n\n\x03\x04\x02\x01\x12\x03\x16\x08\x0b\n\x0b\n\x04\x04\
+    \x02\x02\0\x12\x03\x17\x02\x1b\n\x0c\n\x05\x04\x02\x02\0\x04\x12\x03\x17\
+    \x02\n\n\x0c\n\x05\x04\x02\x02\0\x06\x12\x03\x17\x0b\x11\n\x0c\n\x05\x04\
+    \x02\x02\0\x01\x12\x03\x17\x12\x16\n\x0c\n\x05\x04\x02\x02\0\x03\x12\x03\
+    \x17\x19\x1a\n\x0b\n\x04\x04\x02\x02\x01\x12\x03\x18\x02!\n\x0c\n\x05\
+    \x04\x02\x02\x01\x04\x12\x03\x18\x02\n\n\x0c\n\x05\x04\x02\x02\x01\x06\
+    \x12\x03\x18\x0b\x11\n\x0c\n\x05\x04\x02\x02\x01\x01\x12\x03\x18\x12\x1c\
+    \n\x0c\n\x05\x04\x02\x02\x01\x03\x12\x03\x18\x1f\x20\n\n\n\x02\x04\x03\
+    \x12\x04\x1b\0\x1e\x01\n\n\n\x03\x04\x03\x01\x12\x03\x1b\x08\x0f\n\x0b\n\
+    \x04\x04\x03\x02\0\x12\x03\x1c\x02\x1b\n\x0c\n\x05\x04\x03\x02\0\x04\x12\
+    \x03\x1c\x02\n\n\x0c\n\x05\x04\x03\x02\0\x06\x12\x03\x1c\x0b\x11\n\x0c\n\
+    \x05\x04\x03\x02\0\x01\x12\x03\x1c\x12\x16\n\x0c\n\x05\x04\x03\x02\0\x03\
+    \x12\x03\x1c\x19\x1a\n\x0b\n\x04\x04\x03\x02\x01\x12\x03\x1d\x02\x1d\n\
+    \x0c\n\x05\x04\x03\x02\x01\x04\x12\x03\x1d\x02\n\n\x0c\n\x05\x04\x03\x02\
+    \x01\x06\x12\x03\x1d\x0b\x11\n\x0c\n\x05\x04\x03\x02\x01\x01\x12\x03\x1d\
+    \x12\x18\n\x0c\n\x05\x04\x03\x02\x01\x03\x12\x03\x1d\x1b\x1c\n\n\n\x02\
+    \x04\x04\x12\x04\x20\0'\x01\n\n\n\x03\x04\x04\x01\x12\x03\x20\x08\x12\n\
+    \x0b\n\x04\x04\x04\x04\0\x12\x03!\x02F\n\x0c\n\x05\x04\x04\x04\0\x01\x12\
+    \x03!\x07\r\n\r\n\x06\x04\x04\x04\0\x02\0\x12\x03!\x10\x1a\n\x0e\n\x07\
+    \x04\x04\x04\0\x02\0\x01\x12\x03!\x10\x15\n\x0e\n\x07\x04\x04\x04\0\x02\
+    \0\x02\x12\x03!\x18\x19\n\r\n\x06\x04\x04\x04\0\x02\x01\x12\x03!\x1b'\n\
+    \x0e\n\x07\x04\x04\x04\0\x02\x01\x01\x12\x03!\x1b\"\n\x0e\n\x07\x04\x04\
+    \x04\0\x02\x01\x02\x12\x03!%&\n\r\n\x06\x04\x04\x04\0\x02\x02\x12\x03!(6\
+    \n\x0e\n\x07\x04\x04\x04\0\x02\x02\x01\x12\x03!(1\n\x0e\n\x07\x04\x04\
+    \x04\0\x02\x02\x02\x12\x03!45\n\r\n\x06\x04\x04\x04\0\x02\x03\x12\x03!7D\
+    \n\x0e\n\x07\x04\x04\x04\0\x02\x03\x01\x12\x03!7?\n\x0e\n\x07\x04\x04\
+    \x04\0\x02\x03\x02\x12\x03!BC\n\x0b\n\x04\x04\x04\x02\0\x12\x03#\x02\x1d\
+    \n\x0c\n\x05\x04\x04\x02\0\x04\x12\x03#\x02\n\n\x0c\n\x05\x04\x04\x02\0\
+    \x06\x12\x03#\x0b\x11\n\x0c\n\x05\x04\x04\x02\0\x01\x12\x03#\x12\x18\n\
+    \x0c\n\x05\x04\x04\x02\0\x03\x12\x03#\x1b\x1c\n\x0b\n\x04\x04\x04\x02\
+    \x01\x12\x03$\x02\x1d\n\x0c\n\x05\x04\x04\x02\x01\x04\x12\x03$\x02\n\n\
+    \x0c\n\x05\x04\x04\x02\x01\x06\x12\x03$\x0b\x11\n\x0c\n\x05\x04\x04\x02\
+    \x01\x01\x12\x03$\x12\x18\n\x0c\n\x05\x04\x04\x02\x01\x03\x12\x03$\x1b\
+    \x1c\nj\n\x04\x04\x04\x02\x02\x12\x03&\x02#\x1a]\x20Id\x20of\x20the\x20m\
+    ember\x20whose\x20failed\x20probe\x20started\x20the\x20suspicion,\x20set\
+    \x20while\x20the\x20health\x20is\x20SUSPECT.\n\n\x0c\n\x05\x04\x04\x02\
+    \x02\x04\x12\x03&\x02\n\n\x0c\n\x05\x04\x04\x02\x02\x05\x12\x03&\x0b\x11\
+    \n\x0c\n\x05\x04\x04\x02\x02\x01\x12\x03&\x12\x1e\n\x0c\n\x05\x04\x04\
+    \x02\x02\x03\x12\x03&!\"\n\n\n\x02\x04\x05\x12\x04)\04\x01\n\n\n\x03\x04\
+    \x05\x01\x12\x03)\x08\x10\n\x0b\n\x04\x04\x05\x04\0\x12\x03*\x02:\n\x0c\
+    \n\x05\x04\x05\x04\0\x01\x12\x03*\x07\r\n\r\n\x06\x04\x05\x04\0\x02\0\
+    \x12\x03*\x10\x1c\n\x0e\n\x07\x04\x05\x04\0\x02\0\x01\x12\x03*\x10\x17\n\
+    \x0e\n\x07\x04\x05\x04\0\x02\0\x02\x12\x03*\x1a\x1b\n\r\n\x06\x04\x05\
+    \x04\0\x02\x01\x12\x03*\x1d*\n\x0e\n\x07\x04\x05\x04\0\x02\x01\x01\x12\
+    \x03*\x1d%\n\x0e\n\x07\x04\x05\x04\0\x02\x01\x02\x12\x03*()\n\r\n\x06\
+    \x04\x05\x04\0\x02\x02\x12\x03*+8\n\x0e\n\x07\x04\x05\x04\0\x02\x02\x01\
+    \x12\x03*+3\n\x0e\n\x07\x04\x05\x04\0\x02\x02\x02\x12\x03*67\n\x0b\n\x04\
+    \x04\x05\x02\0\x12\x03,\x02\x20\n\x0c\n\x05\x04\x05\x02\0\x04\x12\x03,\
+    \x02\n\n\x0c\n\x05\x04\x05\x02\0\x05\x12\x03,\x0b\x11\n\x0c\n\x05\x04\
+    \x05\x02\0\x01\x12\x03,\x12\x1b\n\x0c\n\x05\x04\x05\x02\0\x03\x12\x03,\
+    \x1e\x1f\n\x0b\n\x04\x04\x05\x02\x01\x12\x03-\x02$\n\x0c\n\x05\x04\x05\
+    \x02\x01\x04\x12\x03-\x02\n\n\x0c\n\x05\x04\x05\x02\x01\x05\x12\x03-\x0b\
+    \x11\n\x0c\n\x05\x04\x05\x02\x01\x01\x12\x03-\x12\x1f\n\x0c\n\x05\x04\
+    \x05\x02\x01\x03\x12\x03-\"#\n\x0b\n\x04\x04\x05\x02\x02\x12\x03.\x02\
+    \x1b\n\x0c\n\x05\x04\x05\x02\x02\x04\x12\x03.\x02\n\n\x0c\n\x05\x04\x05\
+    \x02\x02\x05\x12\x03.\x0b\x11\n\x0c\n\x05\x04\x05\x02\x02\x01\x12\x03.\
+    \x12\x16\n\x0c\n\x05\x04\x05\x02\x02\x03\x12\x03.\x19\x1a\n\x0b\n\x04\
+    \x04\x05\x02\x03\x12\x03/\x02\"\n\x0c\n\x05\x04\x05\x02\x03\x04\x12\x03/\
+    \x02\n\n\x0c\n\x05\x04\x05\x02\x03\x05\x12\x03/\x0b\x11\n\x0c\n\x05\x04\
+    \x05\x02\x03\x01\x12\x03/\x12\x1d\n\x0c\n\x05\x04\x05\x02\x03\x03\x12\
+    \x03/\x20!\n\x0b\n\x04\x04\x05\x02\x04\x12\x030\x02\x1d\n\x0c\n\x05\x04\
+    \x05\x02\x04\x04\x12\x030\x02\n\n\x0c\n\x05\x04\x05\x02\x04\x06\x12\x030\
+    \x0b\x11\n\x0c\n\x05\x04\x05\x02\x04\x01\x12\x030\x12\x18\n\x0c\n\x05\
+    \x04\x05\x02\x04\x03\x12\x030\x1b\x1c\n\x0b\n\x04\x04\x05\x02\x05\x12\
+    \x031\x02\x1c\n\x0c\n\x05\x04\x05\x02\x05\x04\x12\x031\x02\n\n\x0c\n\x05\
+    \x04\x05\x02\x05\x05\x12\x031\x0b\x11\n\x0c\n\x05\x04\x05\x02\x05\x01\
+    \x12\x031\x12\x17\n\x0c\n\x05\x04\x05\x02\x05\x03\x12\x031\x1a\x1b\nb\n\
+    \x04\x04\x05\x02\x06\x12\x033\x02$\x1aU\x20Bumped\x20by\x20the\x20leader\
+    \x20every\x20time\x20it\x20renews\x20its\x20lease;\x20only\x20meaningful\
+    \x20once\x20finished.\n\n\x0c\n\x05\x04\x05\x02\x06\x04\x12\x033\x02\n\n\
+    \x0c\n\x05\x04\x05\x02\x06\x05\x12\x033\x0b\x11\n\x0c\n\x05\x04\x05\x02\
+    \x06\x01\x12\x033\x12\x1f\n\x0c\n\x05\x04\x05\x02\x06\x03\x12\x033\"#\n\
+    \n\n\x02\x04\x06\x12\x046\0A\x01\n\n\n\x03\x04\x06\x01\x12\x036\x08\x0f\
+    \n\x0b\n\x04\x04\x06\x02\0\x12\x037\x02\x20\n\x0c\n\x05\x04\x06\x02\0\
+    \x04\x12\x037\x02\n\n\x0c\n\x05\x04\x06\x02\0\x05\x12\x037\x0b\x11\n\x0c\
+    \n\x05\x04\x06\x02\0\x01\x12\x037\x12\x1b\n\x0c\n\x05\x04\x06\x02\0\x03\
+    \x12\x037\x1e\x1f\n\x0b\n\x04\x04\x06\x02\x01\x12\x038\x02$\n\x0c\n\x05\
+    \x04\x06\x02\x01\x04\x12\x038\x02\n\n\x0c\n\x05\x04\x06\x02\x01\x05\x12\
+    \x038\x0b\x11\n\x0c\n\x05\x04\x06\x02\x01\x01\x12\x038\x12\x1f\n\x0c\n\
+    \x05\x04\x06\x02\x01\x03\x12\x038\"#\n\x0b\n\x04\x04\x06\x02\x02\x12\x03\
+    9\x02\"\n\x0c\n\x05\x04\x06\x02\x02\x04\x12\x039\x02\n\n\x0c\n\x05\x04\
+    \x06\x02\x02\x05\x12\x039\x0b\x11\n\x0c\n\x05\x04\x06\x02\x02\x01\x12\
+    \x039\x12\x1d\n\x0c\n\x05\x04\x06\x02\x02\x03\x12\x039\x20!\n\x0b\n\x04\
+    \x04\x06\x02\x03\x12\x03:\x02\x20\n\x0c\n\x05\x04\x06\x02\x03\x04\x12\
+    \x03:\x02\n\n\x0c\n\x05\x04\x06\x02\x03\x05\x12\x03:\x0b\x0f\n\x0c\n\x05\
+    \x04\x06\x02\x03\x01\x12\x03:\x10\x1b\n\x0c\n\x05\x04\x06\x02\x03\x03\
+    \x12\x03:\x1e\x1f\n\x0b\n\x04\x04\x06\x02\x04\x12\x03;\x02\x1a\n\x0c\n\
+    \x05\x04\x06\x02\x04\x04\x12\x03;\x02\n\n\x0c\n\x05\x04\x06\x02\x04\x05\
+    \x12\x03;\x0b\x11\n\x0c\n\x05\x04\x06\x02\x04\x01\x12\x03;\x12\x15\n\x0c\
+    \n\x05\x04\x06\x02\x04\x03\x12\x03;\x18\x19\n\x0b\n\x04\x04\x06\x02\x05\
+    \x12\x03<\x02\x1a\n\x0c\n\x05\x04\x06\x02\x05\x04\x12\x03<\x02\n\n\x0c\n\
+    \x05\x04\x06\x02\x05\x05\x12\x03<\x0b\x10\n\x0c\n\x05\x04\x06\x02\x05\
+    \x01\x12\x03<\x11\x14\n\x0c\n\x05\x04\x06\x02\x05\x03\x12\x03<\x17\x19\n\
+    \x0b\n\x04\x04\x06\x02\x06\x12\x03=\x02\x1c\n\x0c\n\x05\x04\x06\x02\x06\
+    \x04\x12\x03=\x02\n\n\x0c\n\x05\x04\x06\x02\x06\x06\x12\x03=\x0b\x12\n\
+    \x0c\n\x05\x04\x06\x02\x06\x01\x12\x03=\x13\x16\n\x0c\n\x05\x04\x06\x02\
+    \x06\x03\x12\x03=\x19\x1b\n\x8a\x01\n\x04\x04\x06\x02\x07\x12\x03@\x022\
+    \x1a}\x20Result\x20of\x20the\x20service's\x20last\x20health\x20check:\
+    \x200\x20ok,\x201\x20warning,\x202\x20critical\x20or\x203\x20unknown,\
+    \x20as\n\x20returned\x20by\x20the\x20health\x20check\x20hook.\n\n\x0c\n\
+    \x05\x04\x06\x02\x07\x04\x12\x03@\x02\n\n\x0c\n\x05\x04\x06\x02\x07\x05\
+    \x12\x03@\x0b\x11\n\x0c\n\x05\x04\x06\x02\x07\x01\x12\x03@\x12\x1e\n\x0c\
+    \n\x05\x04\x06\x02\x07\x03\x12\x03@!#\n\x0c\n\x05\x04\x06\x02\x07\x08\
+    \x12\x03@$1\n\x0c\n\x05\x04\x06\x02\x07\x07\x12\x03@/0\n\n\n\x02\x04\x07\
+    \x12\x04C\0H\x01\n\n\n\x03\x04\x07\x01\x12\x03C\x08\x15\n\x0b\n\x04\x04\
+    \x07\x02\0\x12\x03D\x02$\n\x0c\n\x05\x04\x07\x02\0\x04\x12\x03D\x02\n\n\
+    \x0c\n\x05\x04\x07\x02\0\x05\x12\x03D\x0b\x11\n\x0c\n\x05\x04\x07\x02\0\
+    \x01\x12\x03D\x12\x1f\n\x0c\n\x05\x04\x07\x02\0\x03\x12\x03D\"#\n\x0b\n\
+    \x04\x04\x07\x02\x01\x12\x03E\x02\"\n\x0c\n\x05\x04\x07\x02\x01\x04\x12\
+    \x03E\x02\n\n\x0c\n\x05\x04\x07\x02\x01\x05\x12\x03E\x0b\x11\n\x0c\n\x05\
+    \x04\x07\x02\x01\x01\x12\x03E\x12\x1d\n\x0c\n\x05\x04\x07\x02\x01\x03\
+    \x12\x03E\x20!\n\x0b\n\x04\x04\x07\x02\x02\x12\x03F\x02\x1e\n\x0c\n\x05\
+    \x04\x07\x02\x02\x04\x12\x03F\x02\n\n\x0c\n\x05\x04\x07\x02\x02\x05\x12\
+    \x03F\x0b\x0f\n\x0c\n\x05\x04\x07\x02\x02\x01\x12\x03F\x10\x19\n\x0c\n\
+    \x05\x04\x07\x02\x02\x03\x12\x03F\x1c\x1d\n\x0b\n\x04\x04\x07\x02\x03\
+    \x12\x03G\x02\x1c\n\x0c\n\x05\x04\x07\x02\x03\x04\x12\x03G\x02\n\n\x0c\n\
+    \x05\x04\x07\x02\x03\x05\x12\x03G\x0b\x10\n\x0c\n\x05\x04\x07\x02\x03\
+    \x01\x12\x03G\x11\x17\n\x0c\n\x05\x04\x07\x02\x03\x03\x12\x03G\x1a\x1b\n\
+    \n\n\x02\x04\x08\x12\x04J\0P\x01\n\n\n\x03\x04\x08\x01\x12\x03J\x08\x13\
+    \n\x0b\n\x04\x04\x08\x02\0\x12\x03K\x02$\n\x0c\n\x05\x04\x08\x02\0\x04\
+    \x12\x03K\x02\n\n\x0c\n\x05\x04\x08\x02\0\x05\x12\x03K\x0b\x11\n\x0c\n\
+    \x05\x04\x08\x02\0\x01\x12\x03K\x12\x1f\n\x0c\n\x05\x04\x08\x02\0\x03\
+    \x12\x03K\"#\n\x0b\n\x04\x04\x08\x02\x01\x12\x03L\x02\"\n\x0c\n\x05\x04\
+    \x08\x02\x01\x04\x12\x03L\x02\n\n\x0c\n\x05\x04\x08\x02\x01\x05\x12\x03L\
+    \x0b\x11\n\x0c\n\x05\x04\x08\x02\x01\x01\x12\x03L\x12\x1d\n\x0c\n\x05\
+    \x04\x08\x02\x01\x03\x12\x03L\x20!\n\x0b\n\x04\x04\x08\x02\x02\x12\x03M\
+    \x02\x1e\n\x0c\n\x05\x04\x08\x02\x02\x04\x12\x03M\x02\n\n\x0c\n\x05\x04\
+    \x08\x02\x02\x05\x12\x03M\x0b\x0f\n\x0c\n\x05\x04\x08\x02\x02\x01\x12\
+    \x03M\x10\x19\n\x0c\n\x05\x04\x08\x02\x02\x03\x12\x03M\x1c\x1d\n\x0b\n\
+    \x04\x04\x08\x02\x03\x12\x03N\x02\x1f\n\x0c\n\x05\x04\x08\x02\x03\x04\
+    \x12\x03N\x02\n\n\x0c\n\x05\x04\x08\x02\x03\x05\x12\x03N\x0b\x11\n\x0c\n\
+    \x05\x04\x08\x02\x03\x01\x12\x03N\x12\x1a\n\x0c\n\x05\x04\x08\x02\x03\
+    \x03\x12\x03N\x1d\x1e\n\x0b\n\x04\x04\x08\x02\x04\x12\x03O\x02\x1a\n\x0c\
+    \n\x05\x04\x08\x02\x04\x04\x12\x03O\x02\n\n\x0c\n\x05\x04\x08\x02\x04\
+    \x05\x12\x03O\x0b\x10\n\x0c\n\x05\x04\x08\x02\x04\x01\x12\x03O\x11\x15\n\
+    \x0c\n\x05\x04\x08\x02\x04\x03\x12\x03O\x18\x19\n\n\n\x02\x04\t\x12\x04R\
+    \0Z\x01\n\n\n\x03\x04\t\x01\x12\x03R\x08\x0f\n\x0b\n\x04\x04\t\x02\0\x12\
+    \x03S\x021\n\x0c\n\x05\x04\t\x02\0\x04\x12\x03S\x02\n\n\x0c\n\x05\x04\t\
+    \x02\0\x05\x12\x03S\x0b\x11\n\x0c\n\x05\x04\t\x02\0\x01\x12\x03S\x12\x14\
+    \n\x0c\n\x05\x04\t\x02\0\x03\x12\x03S\x17\x18\n\x0c\n\x05\x04\t\x02\0\
+    \x08\x12\x03S\x190\n\x0c\n\x05\x04\t\x02\0\x07\x12\x03S$/\n\x0b\n\x04\
+    \x04\t\x02\x01\x12\x03T\x027\n\x0c\n\x05\x04\t\x02\x01\x04\x12\x03T\x02\
+    \n\n\x0c\n\x05\x04\t\x02\x01\x05\x12\x03T\x0b\x11\n\x0c\n\x05\x04\t\x02\
+    \x01\x01\x12\x03T\x12\x1a\n\x0c\n\x05\x04\t\x02\x01\x03\x12\x03T\x1d\x1e\
+    \n\x0c\n\x05\x04\t\x02\x01\x08\x12\x03T\x1f6\n\x0c\n\x05\x04\t\x02\x01\
+    \x07\x12\x03T*5\n\x0b\n\x04\x04\t\x02\x02\x12\x03U\x028\n\x0c\n\x05\x04\
+    \t\x02\x02\x04\x12\x03U\x02\n\n\x0c\n\x05\x04\t\x02\x02\x05\x12\x03U\x0b\
+    \x11\n\x0c\n\x05\x04\t\x02\x02\x01\x12\x03U\x12\x1b\n\x0c\n\x05\x04\t\
+    \x02\x02\x03\x12\x03U\x1e\x1f\n\x0c\n\x05\x04\t\x02\x02\x08\x12\x03U\x20\
+    7\n\x0c\n\x05\x04\t\x02\x02\x07\x12\x03U+6\n\x0b\n\x04\x04\t\x02\x03\x12\
+    \x03V\x02\"\n\x0c\n\x05\x04\t\x02\x03\x04\x12\x03V\x02\n\n\x0c\n\x05\x04\
+    \t\x02\x03\x05\x12\x03V\x0b\x11\n\x0c\n\x05\x04\t\x02\x03\x01\x12\x03V\
+    \x12\x1d\n\x0c\n\x05\x04\t\x02\x03\x03\x12\x03V\x20!\n\x0b\n\x04\x04\t\
+    \x02\x04\x12\x03W\x02>\n\x0c\n\x05\x04\t\x02\x04\x04\x12\x03W\x02\n\n\
+    \x0c\n\x05\x04\t\x02\x04\x05\x12\x03W\x0b\x11\n\x0c\n\x05\x04\t\x02\x04\
+    \x01\x12\x03W\x12!\n\x0c\n\x05\x04\t\x02\x04\x03\x12\x03W$%\n\x0c\n\x05\
+    \x04\t\x02\x04\x08\x12\x03W&=\n\x0c\n\x05\x04\t\x02\x04\x07\x12\x03W1<\n\
+    \x0b\n\x04\x04\t\x02\x05\x12\x03X\x02(\n\x0c\n\x05\x04\t\x02\x05\x04\x12\
+    \x03X\x02\n\n\x0c\n\x05\x04\t\x02\x05\x05\x12\x03X\x0b\x11\n\x0c\n\x05\
+    \x04\t\x02\x05\x01\x12\x03X\x12#\n\x0c\n\x05\x04\t\x02\x05\x03\x12\x03X&\
+    '\n\x0b\n\x04\x04\t\x02\x06\x12\x03Y\x02\x1f\n\x0c\n\x05\x04\t\x02\x06\
+    \x06\x12\x03Y\x02\x15\n\x0c\n\x05\x04\t\x02\x06\x01\x12\x03Y\x16\x1a\n\
+    \x0c\n\x05\x04\t\x02\x06\x03\x12\x03Y\x1d\x1e\n\n\n\x02\x04\n\x12\x04\\\
+    \0^\x01\n\n\n\x03\x04\n\x01\x12\x03\\\x08\x11\n\x0b\n\x04\x04\n\x02\0\
+    \x12\x03]\x02\x20\n\x0c\n\x05\x04\n\x02\0\x04\x12\x03]\x02\n\n\x0c\n\x05\
+    \x04\n\x02\0\x05\x12\x03]\x0b\x11\n\x0c\n\x05\x04\n\x02\0\x01\x12\x03]\
+    \x12\x1b\n\x0c\n\x05\x04\n\x02\0\x03\x12\x03]\x1e\x1f\n\n\n\x02\x04\x0b\
+    \x12\x04`\0j\x01\n\n\n\x03\x04\x0b\x01\x12\x03`\x08\x0e\n\x0b\n\x04\x04\
+    \x0b\x04\0\x12\x03a\x02<\n\x0c\n\x05\x04\x0b\x04\0\x01\x12\x03a\x07\r\n\
+    \r\n\x06\x04\x0b\x04\0\x02\0\x12\x03a\x10\x1c\n\x0e\n\x07\x04\x0b\x04\0\
+    \x02\0\x01\x12\x03a\x10\x17\n\x0e\n\x07\x04\x0b\x04\0\x02\0\x02\x12\x03a\
+    \x1a\x1b\n\r\n\x06\x04\x0b\x04\0\x02\x01\x12\x03a\x1d*\n\x0e\n\x07\x04\
+    \x0b\x04\0\x02\x01\x01\x12\x03a\x1d%\n\x0e\n\x07\x04\x0b\x04\0\x02\x01\
+    \x02\x12\x03a()\n\r\n\x06\x04\x0b\x04\0\x02\x02\x12\x03a+:\n\x0e\n\x07\
+    \x04\x0b\x04\0\x02\x02\x01\x12\x03a+5\n\x0e\n\x07\x04\x0b\x04\0\x02\x02\
+    \x02\x12\x03a89\n\x0b\n\x04\x04\x0b\x02\0\x12\x03c\x02\x20\n\x0c\n\x05\
+    \x04\x0b\x02\0\x04\x12\x03c\x02\n\n\x0c\n\x05\x04\x0b\x02\0\x05\x12\x03c\
+    \x0b\x11\n\x0c\n\x05\x04\x0b\x02\0\x01\x12\x03c\x12\x1b\n\x0c\n\x05\x04\
+    \x0b\x02\0\x03\x12\x03c\x1e\x1f\n\x0b\n\x04\x04\x0b\x02\x01\x12\x03d\x02\
+    $\n\x0c\n\x05\x04\x0b\x02\x01\x04\x12\x03d\x02\n\n\x0c\n\x05\x04\x0b\x02\
+    \x01\x05\x12\x03d\x0b\x11\n\x0c\n\x05\x04\x0b\x02\x01\x01\x12\x03d\x12\
+    \x1f\n\x0c\n\x05\x04\x0b\x02\x01\x03\x12\x03d\"#\n\x0b\n\x04\x04\x0b\x02\
+    \x02\x12\x03e\x02\"\n\x0c\n\x05\x04\x0b\x02\x02\x04\x12\x03e\x02\n\n\x0c\
+    \n\x05\x04\x0b\x02\x02\x05\x12\x03e\x0b\x11\n\x0c\n\x05\x04\x0b\x02\x02\
+    \x01\x12\x03e\x12\x1d\n\x0c\n\x05\x04\x0b\x02\x02\x03\x12\x03e\x20!\n\
+    \x0b\n\x04\x04\x0b\x02\x03\x12\x03f\x02\x1a\n\x0c\n\x05\x04\x0b\x02\x03\
+    \x04\x12\x03f\x02\n\n\x0c\n\x05\x04\x0b\x02\x03\x05\x12\x03f\x0b\x11\n\
+    \x0c\n\x05\x04\x0b\x02\x03\x01\x12\x03f\x12\x15\n\x0c\n\x05\x04\x0b\x02\
+    \x03\x03\x12\x03f\x18\x19\n\x0b\n\x04\x04\x0b\x02\x04\x12\x03g\x02#\n\
+    \x0c\n\x05\x04\x0b\x02\x04\x04\x12\x03g\x02\n\n\x0c\n\x05\x04\x0b\x02\
+    \x04\x05\x12\x03g\x0b\x11\n\x0c\n\x05\x04\x0b\x02\x04\x01\x12\x03g\x12\
+    \x1e\n\x0c\n\x05\x04\x0b\x02\x04\x03\x12\x03g!\"\n\x0b\n\x04\x04\x0b\x02\
+    \x05\x12\x03h\x02\x1f\n\x0c\n\x05\x04\x0b\x02\x05\x04\x12\x03h\x02\n\n\
+    \x0c\n\x05\x04\x0b\x02\x05\x05\x12\x03h\x0b\x11\n\x0c\n\x05\x04\x0b\x02\
+    \x05\x01\x12\x03h\x12\x1a\n\x0c\n\x05\x04\x0b\x02\x05\x03\x12\x03h\x1d\
+    \x1e\n\x0b\n\x04\x04\x0b\x02\x06\x12\x03i\x02\x1d\n\x0c\n\x05\x04\x0b\
+    \x02\x06\x04\x12\x03i\x02\n\n\x0c\n\x05\x04\x0b\x02\x06\x06\x12\x03i\x0b\
+    \x11\n\x0c\n\x05\x04\x0b\x02\x06\x01\x12\x03i\x12\x18\n\x0c\n\x05\x04\
+    \x0b\x02\x06\x03\x12\x03i\x1b\x1c\n\n\n\x02\x04\x0c\x12\x04l\0w\x01\n\n\
+    \n\x03\x04\x0c\x01\x12\x03l\x08\x0c\n\x0b\n\x04\x04\x0c\x04\0\x12\x03m\
+    \x02/\n\x0c\n\x05\x04\x0c\x04\0\x01\x12\x03m\x07\x0b\n\r\n\x06\x04\x0c\
+    \x04\0\x02\0\x12\x03m\x0e\x17\n\x0e\n\x07\x04\x0c\x04\0\x02\0\x01\x12\
+    \x03m\x0e\x12\n\x0e\n\x07\x04\x0c\x04\0\x02\0\x02\x12\x03m\x15\x16\n\r\n\
+    \x06\x04\x0c\x04\0\x02\x01\x12\x03m\x18\x20\n\x0e\n\x07\x04\x0c\x04\0\
+    \x02\x01\x01\x12\x03m\x18\x1b\n\x0e\n\x07\x04\x0c\x04\0\x02\x01\x02\x12\
+    \x03m\x1e\x1f\n\r\n\x06\x04\x0c\x04\0\x02\x02\x12\x03m!-\n\x0e\n\x07\x04\
+    \x0c\x04\0\x02\x02\x01\x12\x03m!(\n\x0e\n\x07\x04\x0c\x04\0\x02\x02\x02\
+    \x12\x03m+,\n3\n\x04\x04\x0c\x02\0\x12\x03p\x02\x19\x1a&\x20Identifies\
+    \x20which\x20field\x20is\x20filled\x20in.\n\n\x0c\n\x05\x04\x0c\x02\0\
+    \x04\x12\x03p\x02\n\n\x0c\n\x05\x04\x0c\x02\0\x06\x12\x03p\x0b\x0f\n\x0c\
+    \n\x05\x04\x0c\x02\0\x01\x12\x03p\x10\x14\n\x0c\n\x05\x04\x0c\x02\0\x03\
+    \x12\x03p\x17\x18\n\x0c\n\x04\x04\x0c\x08\0\x12\x04q\x02u\x03\n\x0c\n\
+    \x05\x04\x0c\x08\0\x01\x12\x03q\x08\x0f\n\x0b\n\x04\x04\x0c\x02\x01\x12\
+    \x03r\x04\x12\n\x0c\n\x05\x04\x0c\x02\x01\x06\x12\x03r\x04\x08\n\x0c\n\
+    \x05\x04\x0c\x02\x01\x01\x12\x03r\t\r\n\x0c\n\x05\x04\x0c\x02\x01\x03\
+    \x12\x03r\x10\x11\n\x0b\n\x04\x04\x0c\x02\x02\x12\x03s\x04\x10\n\x0c\n\
+    \x05\x04\x0c\x02\x02\x06\x12\x03s\x04\x07\n\x0c\n\x05\x04\x0c\x02\x02\
+    \x01\x12\x03s\x08\x0b\n\x0c\n\x05\x04\x0c\x02\x02\x03\x12\x03s\x0e\x0f\n\
+    \x0b\n\x04\x04\x0c\x02\x03\x12\x03t\x04\x18\n\x0c\n\x05\x04\x0c\x02\x03\
+    \x06\x12\x03t\x04\x0b\n\x0c\n\x05\x04\x0c\x02\x03\x01\x12\x03t\x0c\x13\n\
+    \x0c\n\x05\x04\x0c\x02\x03\x03\x12\x03t\x16\x17\n\x0b\n\x04\x04\x0c\x02\
+    \x04\x12\x03v\x02%\n\x0c\n\x05\x04\x0c\x02\x04\x04\x12\x03v\x02\n\n\x0c\
+    \n\x05\x04\x0c\x02\x04\x06\x12\x03v\x0b\x15\n\x0c\n\x05\x04\x0c\x02\x04\
+    \x01\x12\x03v\x16\x20\n\x0c\n\x05\x04\x0c\x02\x04\x03\x12\x03v#$\n\n\n\
+    \x02\x04\r\x12\x04y\0\x7f\x01\n\n\n\x03\x04\r\x01\x12\x03y\x08\x0f\n\x0b\
+    \n\x04\x04\r\x02\0\x12\x03z\x02\x1b\n\x0c\n\x05\x04\r\x02\0\x04\x12\x03z\
+    \x02\n\n\x0c\n\x05\x04\r\x02\0\x05\x12\x03z\x0b\x11\n\x0c\n\x05\x04\r\
+    \x02\0\x01\x12\x03z\x12\x16\n\x0c\n\x05\x04\r\x02\0\x03\x12\x03z\x19\x1a\
+    \n\x0b\n\x04\x04\r\x02\x01\x12\x03{\x02\x1f\n\x0c\n\x05\x04\r\x02\x01\
+    \x04\x12\x03{\x02\n\n\x0c\n\x05\x04\r\x02\x01\x05\x12\x03{\x0b\x11\n\x0c\
+    \n\x05\x04\r\x02\x01\x01\x12\x03{\x12\x1a\n\x0c\n\x05\x04\r\x02\x01\x03\
+    \x12\x03{\x1d\x1e\n\x0b\n\x04\x04\r\x02\x02\x12\x03|\x02\x19\n\x0c\n\x05\
+    \x04\r\x02\x02\x04\x12\x03|\x02\n\n\x0c\n\x05\x04\r\x02\x02\x05\x12\x03|\
+    \x0b\x10\n\x0c\n\x05\x04\r\x02\x02\x01\x12\x03|\x11\x14\n\x0c\n\x05\x04\
+    \r\x02\x02\x03\x12\x03|\x17\x18\n\x0b\n\x04\x04\r\x02\x03\x12\x03}\x02!\
+    \n\x0c\n\x05\x04\r\x02\x03\x04\x12\x03}\x02\n\n\x0c\n\x05\x04\r\x02\x03\
+    \x05\x12\x03}\x0b\x10\n\x0c\n\x05\x04\r\x02\x03\x01\x12\x03}\x11\x1c\n\
+    \x0c\n\x05\x04\r\x02\x03\x03\x12\x03}\x1f\x20\n\x0b\n\x04\x04\r\x02\x04\
+    \x12\x03~\x02\"\n\x0c\n\x05\x04\r\x02\x04\x04\x12\x03~\x02\n\n\x0c\n\x05\
+    \x04\r\x02\x04\x05\x12\x03~\x0b\x10\n\x0c\n\x05\x04\r\x02\x04\x01\x12\
+    \x03~\x11\x1d\n\x0c\n\x05\x04\r\x02\x04\x03\x12\x03~\x20!\n\x0c\n\x02\
+    \x04\x0e\x12\x06\x81\x01\0\x86\x01\x01\n\x0b\n\x03\x04\x0e\x01\x12\x04\
+    \x81\x01\x08\x13\n\x0c\n\x04\x04\x0e\x02\0\x12\x04\x82\x01\x02\x1f\n\r\n\
+    \x05\x04\x0e\x02\0\x04\x12\x04\x82\x01\x02\n\n\r\n\x05\x04\x0e\x02\0\x06\
+    \x12\x04\x82\x01\x0b\x15\n\r\n\x05\x04\x0e\x02\0\x01\x12\x04\x82\x01\x16\
+    \x1a\n\r\n\x05\x04\x0e\x02\0\x03\x12\x04\x82\x01\x1d\x1e\n\x0c\n\x04\x04\
+    \x0e\x02\x01\x12\x04\x83\x01\x02\x1a\n\r\n\x05\x04\x0e\x02\x01\x04\x12\
+    \x04\x83\x01\x02\n\n\r\n\x05\x04\x0e\x02\x01\x05\x12\x04\x83\x01\x0b\x11\
+    \n\r\n\x05\x04\x0e\x02\x01\x01\x12\x04\x83\x01\x12\x15\n\r\n\x05\x04\x0e\
+    \x02\x01\x03\x12\x04\x83\x01\x18\x19\n\x0c\n\x04\x04\x0e\x02\x02\x12\x04\
+    \x84\x01\x02\x19\n\r\n\x05\x04\x0e\x02\x02\x04\x12\x04\x84\x01\x02\n\n\r\
+    \n\x05\x04\x0e\x02\x02\x05\x12\x04\x84\x01\x0b\x11\n\r\n\x05\x04\x0e\x02\
+    \x02\x01\x12\x04\x84\x01\x12\x14\n\r\n\x05\x04\x0e\x02\x02\x03\x12\x04\
+    \x84\x01\x17\x18\n\x0c\n\x04\x04\x0e\x02\x03\x12\x04\x85\x01\x02\"\n\r\n\
+    \x05\x04\x0e\x02\x03\x04\x12\x04\x85\x01\x02\n\n\r\n\x05\x04\x0e\x02\x03\
+    \x05\x12\x04\x85\x01\x0b\x11\n\r\n\x05\x04\x0e\x02\x03\x01\x12\x04\x85\
+    \x01\x12\x1d\n\r\n\x05\x04\x0e\x02\x03\x03\x12\x04\x85\x01\x20!\n\x0c\n\
+    \x02\x04\x0f\x12\x06\x88\x01\0\x8a\x01\x01\n\x0b\n\x03\x04\x0f\x01\x12\
+    \x04\x88\x01\x08\x0e\n\x0c\n\x04\x04\x0f\x02\0\x12\x04\x89\x01\x02\"\n\r\
+    \n\x05\x04\x0f\x02\0\x04\x12\x04\x89\x01\x02\n\n\r\n\x05\x04\x0f\x02\0\
+    \x06\x12\x04\x89\x01\x0b\x16\n\r\n\x05\x04\x0f\x02\0\x01\x12\x04\x89\x01\
+    \x17\x1d\n\r\n\x05\x04\x0f\x02\0\x03\x12\x04\x89\x01\x20!\n\x0c\n\x02\
+    \x04\x10\x12\x06\x8c\x01\0\xac\x01\x01\n\x0b\n\x03\x04\x10\x01\x12\x04\
+    \x8c\x01\x08\r\n\x0e\n\x04\x04\x10\x04\0\x12\x06\x8d\x01\x02\x9c\x01\x03\
+    \n\r\n\x05\x04\x10\x04\0\x01\x12\x04\x8d\x01\x07\x0b\n\x0e\n\x06\x04\x10\
+    \x04\0\x02\0\x12\x04\x8e\x01\x04\x0f\n\x0f\n\x07\x04\x10\x04\0\x02\0\x01\
+    \x12\x04\x8e\x01\x04\n\n\x0f\n\x07\x04\x10\x04\0\x02\0\x02\x12\x04\x8e\
+    \x01\r\x0e\n\x0e\n\x06\x04\x10\x04\0\x02\x01\x12\x04\x8f\x01\x04\x10\n\
+    \x0f\n\x07\x04\x10\x04\0\x02\x01\x01\x12\x04\x8f\x01\x04\x0b\n\x0f\n\x07\
+    \x04\x10\x04\0\x02\x01\x02\x12\x04\x8f\x01\x0e\x0f\n\x0e\n\x06\x04\x10\
+    \x04\0\x02\x02\x12\x04\x90\x01\x04\x11\n\x0f\n\x07\x04\x10\x04\0\x02\x02\
+    \x01\x12\x04\x90\x01\x04\x0c\n\x0f\n\x07\x04\x10\x04\0\x02\x02\x02\x12\
+    \x04\x90\x01\x0f\x10\n\x0e\n\x06\x04\x10\x04\0\x02\x03\x12\x04\x91\x01\
+    \x04\x16\n\x0f\n\x07\x04\x10\x04\0\x02\x03\x01\x12\x04\x91\x01\x04\x11\n\
+    \x0f\n\x07\x04\x10\x04\0\x02\x03\x02\x12\x04\x91\x01\x14\x15\n\x0e\n\x06\
+    \x04\x10\x04\0\x02\x04\x12\x04\x92\x01\x04\x14\n\x0f\n\x07\x04\x10\x04\0\
+    \x02\x04\x01\x12\x04\x92\x01\x04\x0f\n\x0f\n\x07\x04\x10\x04\0\x02\x04\
+    \x02\x12\x04\x92\x01\x12\x13\n\x0e\n\x06\x04\x10\x04\0\x02\x05\x12\x04\
+    \x93\x01\x04\r\n\x0f\n\x07\x04\x10\x04\0\x02\x05\x01\x12\x04\x93\x01\x04\
+    \x08\n\x0f\n\x07\x04\x10\x04\0\x02\x05\x02\x12\x04\x93\x01\x0b\x0c\n\x0e\
+    \n\x06\x04\x10\x04\0\x02\x06\x12\x04\x94\x01\x04\x0e\n\x0f\n\x07\x04\x10\
+    \x04\0\x02\x06\x01\x12\x04\x94\x01\x04\t\n\x0f\n\x07\x04\x10\x04\0\x02\
+    \x06\x02\x12\x04\x94\x01\x0c\r\n\x0e\n\x06\x04\x10\x04\0\x02\x07\x12\x04\
+    \x95\x01\x04\x17\n\x0f\n\x07\x04\x10\x04\0\x02\x07\x01\x12\x04\x95\x01\
+    \x04\x12\n\x0f\n\x07\x04\x10\x04\0\x02\x07\x02\x12\x04\x95\x01\x15\x16\n\
+    \x0e\n\x06\x04\x10\x04\0\x02\x08\x12\x04\x96\x01\x04\x12\n\x0f\n\x07\x04\
+    \x10\x04\0\x02\x08\x01\x12\x04\x96\x01\x04\r\n\x0f\n\x07\x04\x10\x04\0\
+    \x02\x08\x02\x12\x04\x96\x01\x10\x11\n\x0e\n\x06\x04\x10\x04\0\x02\t\x12\
+    \x04\x97\x01\x04\x10\n\x0f\n\x07\x04\x10\x04\0\x02\t\x01\x12\x04\x97\x01\
+    \x04\n\n\x0f\n\x07\x04\x10\x04\0\x02\t\x02\x12\x04\x97\x01\r\x0f\n\x0e\n\
+    \x06\x04\x10\x04\0\x02\n\x12\x04\x98\x01\x04\x11\n\x0f\n\x07\x04\x10\x04\
+    \0\x02\n\x01\x12\x04\x98\x01\x04\x0b\n\x0f\n\x07\x04\x10\x04\0\x02\n\x02\
+    \x12\x04\x98\x01\x0e\x10\n\x0e\n\x06\x04\x10\x04\0\x02\x0b\x12\x04\x99\
+    \x01\x04\x17\n\x0f\n\x07\x04\x10\x04\0\x02\x0b\x01\x12\x04\x99\x01\x04\
+    \x11\n\x0f\n\x07\x04\x10\x04\0\x02\x0b\x02\x12\x04\x99\x01\x14\x16\n\x0e\
+    \n\x06\x04\x10\x04\0\x02\x0c\x12\x04\x9a\x01\x04\x10\n\x0f\n\x07\x04\x10\
+    \x04\0\x02\x0c\x01\x12\x04\x9a\x01\x04\n\n\x0f\n\x07\x04\x10\x04\0\x02\
+    \x0c\x02\x12\x04\x9a\x01\r\x0f\n\x0e\n\x06\x04\x10\x04\0\x02\r\x12\x04\
+    \x9b\x01\x04\x15\n\x0f\n\x07\x04\x10\x04\0\x02\r\x01\x12\x04\x9b\x01\x04\
+    \x0f\n\x0f\n\x07\x04\x10\x04\0\x02\r\x02\x12\x04\x9b\x01\x12\x14\n\x0c\n\
+    \x04\x04\x10\x02\0\x12\x04\x9e\x01\x02\x19\n\r\n\x05\x04\x10\x02\0\x04\
+    \x12\x04\x9e\x01\x02\n\n\r\n\x05\x04\x10\x02\0\x06\x12\x04\x9e\x01\x0b\
+    \x0f\n\r\n\x05\x04\x10\x02\0\x01\x12\x04\x9e\x01\x10\x14\n\r\n\x05\x04\
+    \x10\x02\0\x03\x12\x04\x9e\x01\x17\x18\n\x0c\n\x04\x04\x10\x02\x01\x12\
+    \x04\x9f\x01\x02\x1a\n\r\n\x05\x04\x10\x02\x01\x04\x12\x04\x9f\x01\x02\n\
+    \n\r\n\x05\x04\x10\x02\x01\x05\x12\x04\x9f\x01\x0b\x11\n\r\n\x05\x04\x10\
+    \x02\x01\x01\x12\x04\x9f\x01\x12\x15\n\r\n\x05\x04\x10\x02\x01\x03\x12\
+    \x04\x9f\x01\x18\x19\n\x0c\n\x04\x04\x10\x02\x02\x12\x04\xa0\x01\x02\x1e\
+    \n\r\n\x05\x04\x10\x02\x02\x04\x12\x04\xa0\x01\x02\n\n\r\n\x05\x04\x10\
+    \x02\x02\x05\x12\x04\xa0\x01\x0b\x11\n\r\n\x05\x04\x10\x02\x02\x01\x12\
+    \x04\xa0\x01\x12\x19\n\r\n\x05\x04\x10\x02\x02\x03\x12\x04\xa0\x01\x1c\
+    \x1d\n\x0e\n\x04\x04\x10\x08\0\x12\x06\xa1\x01\x02\xab\x01\x03\n\r\n\x05\
+    \x04\x10\x08\0\x01\x12\x04\xa1\x01\x08\x0f\n\x0c\n\x04\x04\x10\x02\x03\
+    \x12\x04\xa2\x01\x04\x1a\n\r\n\x05\x04\x10\x02\x03\x06\x12\x04\xa2\x01\
+    \x04\x0e\n\r\n\x05\x04\x10\x02\x03\x01\x12\x04\xa2\x01\x0f\x15\n\r\n\x05\
+    \x04\x10\x02\x03\x03\x12\x04\xa2\x01\x18\x19\n\x0c\n\x04\x04\x10\x02\x04\
+    \x12\x04\xa3\x01\x04\x18\n\r\n\x05\x04\x10\x02\x04\x06\x12\x04\xa3\x01\
+    \x04\x0b\n\r\n\x05\x04\x10\x02\x04\x01\x12\x04\xa3\x01\x0c\x13\n\r\n\x05\
+    \x04\x10\x02\x04\x03\x12\x04\xa3\x01\x16\x17\n\x0c\n\x04\x04\x10\x02\x05\
+    \x12\x04\xa4\x01\x04%\n\r\n\x05\x04\x10\x02\x05\x06\x12\x04\xa4\x01\x04\
+    \x11\n\r\n\x05\x04\x10\x02\x05\x01\x12\x04\xa4\x01\x12\x20\n\r\n\x05\x04\
+    \x10\x02\x05\x03\x12\x04\xa4\x01#$\n\x0c\n\x04\x04\x10\x02\x06\x12\x04\
+    \xa5\x01\x04!\n\r\n\x05\x04\x10\x02\x06\x06\x12\x04\xa5\x01\x04\x0f\n\r\
+    \n\x05\x04\x10\x02\x06\x01\x12\x04\xa5\x01\x10\x1c\n\r\n\x05\x04\x10\x02\
+    \x06\x03\x12\x04\xa5\x01\x1f\x20\n\x0c\n\x04\x04\x10\x02\x07\x12\x04\xa6\
+    \x01\x04\x1a\n\r\n\x05\x04\x10\x02\x07\x06\x12\x04\xa6\x01\x04\x0c\n\r\n\
+    \x05\x04\x10\x02\x07\x01\x12\x04\xa6\x01\r\x15\n\r\n\x05\x04\x10\x02\x07\
+    \x03\x12\x04\xa6\x01\x18\x19\n\x0c\n\x04\x04\x10\x02\x08\x12\x04\xa7\x01\
+    \x04\x1c\n\r\n\x05\x04\x10\x02\x08\x06\x12\x04\xa7\x01\x04\r\n\r\n\x05\
+    \x04\x10\x02\x08\x01\x12\x04\xa7\x01\x0e\x17\n\r\n\x05\x04\x10\x02\x08\
+    \x03\x12\x04\xa7\x01\x1a\x1b\n\x0c\n\x04\x04\x10\x02\t\x12\x04\xa8\x01\
+    \x04\x17\n\r\n\x05\x04\x10\x02\t\x06\x12\x04\xa8\x01\x04\n\n\r\n\x05\x04\
+    \x10\x02\t\x01\x12\x04\xa8\x01\x0b\x11\n\r\n\x05\x04\x10\x02\t\x03\x12\
+    \x04\xa8\x01\x14\x16\n\x0c\n\x04\x04\x10\x02\n\x12\x04\xa9\x01\x04\x1a\n\
+    \r\n\x05\x04\x10\x02\n\x06\x12\x04\xa9\x01\x04\x0b\n\r\n\x05\x04\x10\x02\
+    \n\x01\x12\x04\xa9\x01\x0c\x14\n\r\n\x05\x04\x10\x02\n\x03\x12\x04\xa9\
+    \x01\x17\x19\n\x0c\n\x04\x04\x10\x02\x0b\x12\x04\xaa\x01\x04\x17\n\r\n\
+    \x05\x04\x10\x02\x0b\x06\x12\x04\xaa\x01\x04\n\n\r\n\x05\x04\x10\x02\x0b\
+    \x01\x12\x04\xaa\x01\x0b\x11\n\r\n\x05\x04\x10\x02\x0b\x03\x12\x04\xaa\
+    \x01\x14\x16\n\x0c\n\x02\x04\x11\x12\x06\xae\x01\0\xb3\x01\x01\n\x0b\n\
+    \x03\x04\x11\x01\x12\x04\xae\x01\x08\x0c\n\x0c\n\x04\x04\x11\x02\0\x12\
+    \x04\xaf\x01\x02\x1e\n\r\n\x05\x04\x11\x02\0\x04\x12\x04\xaf\x01\x02\n\n\
+    \r\n\x05\x04\x11\x02\0\x05\x12\x04\xaf\x01\x0b\x0f\n\r\n\x05\x04\x11\x02\
+    \0\x01\x12\x04\xaf\x01\x10\x19\n\r\n\x05\x04\x11\x02\0\x03\x12\x04\xaf\
+    \x01\x1c\x1d\n\x0c\n\x04\x04\x11\x02\x01\x12\x04\xb0\x01\x02\x1b\n\r\n\
+    \x05\x04\x11\x02\x01\x04\x12\x04\xb0\x01\x02\n\n\r\n\x05\x04\x11\x02\x01\
+    \x05\x12\x04\xb0\x01\x0b\x10\n\r\n\x05\x04\x11\x02\x01\x01\x12\x04\xb0\
+    \x01\x11\x16\n\r\n\x05\x04\x11\x02\x01\x03\x12\x04\xb0\x01\x19\x1a\n\x0c\
+    \n\x04\x04\x11\x02\x02\x12\x04\xb1\x01\x02\x1d\n\r\n\x05\x04\x11\x02\x02\
+    \x04\x12\x04\xb1\x01\x02\n\n\r\n\x05\x04\x11\x02\x02\x05\x12\x04\xb1\x01\
+    \x0b\x10\n\r\n\x05\x04\x11\x02\x02\x01\x12\x04\xb1\x01\x11\x18\n\r\n\x05\
+    \x04\x11\x02\x02\x03\x12\x04\xb1\x01\x1b\x1c\n\x0c\n\x04\x04\x11\x02\x03\
+    \x12\x04\xb2\x01\x02\x1e\n\r\n\x05\x04\x11\x02\x03\x04\x12\x04\xb2\x01\
+    \x02\n\n\r\n\x05\x04\x11\x02\x03\x05\x12\x04\xb2\x01\x0b\x11\n\r\n\x05\
+    \x04\x11\x02\x03\x01\x12\x04\xb2\x01\x12\x19\n\r\n\x05\x04\x11\x02\x03\
+    \x03\x12\x04\xb2\x01\x1c\x1d\
 ";
 
 static mut file_descriptor_proto_lazy: ::protobuf::lazy::Lazy<::protobuf::descriptor::FileDescriptorProto> = ::protobuf::lazy::Lazy {
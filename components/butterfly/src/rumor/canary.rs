@@ -0,0 +1,245 @@
+// Copyright (c) 2017 Chef Software Inc. and/or applicable contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The Canary rumor.
+//!
+//! Coordinates a canary update of a service group. The update leader picks the members which test
+//! a new package first, and the rumor then records whether the package was promoted to the rest
+//! of the group or rolled back because a canary went unhealthy.
+
+use std::cmp::Ordering;
+use std::mem;
+use std::ops::{Deref, DerefMut};
+
+use habitat_core::service::ServiceGroup;
+use protobuf::{self, Message, RepeatedField};
+
+use error::Result;
+use message::swim::{Canary as ProtoCanary, Rumor as ProtoRumor, Rumor_Type as ProtoRumor_Type};
+use rumor::Rumor;
+
+pub use message::swim::Canary_Status;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Canary(ProtoRumor);
+
+/// Rank of a status when two rumors share an incarnation. A rollback always wins, so that an
+/// unhealthy canary can't be overruled by a concurrent promotion.
+fn status_rank(status: Canary_Status) -> u8 {
+    match status {
+        Canary_Status::Testing => 0,
+        Canary_Status::Promoted => 1,
+        Canary_Status::RolledBack => 2,
+    }
+}
+
+impl PartialOrd for Canary {
+    fn partial_cmp(&self, other: &Canary) -> Option<Ordering> {
+        if self.get_service_group() != other.get_service_group() {
+            None
+        } else {
+            match self.get_incarnation().cmp(&other.get_incarnation()) {
+                Ordering::Equal => {
+                    Some(status_rank(self.get_status()).cmp(
+                        &status_rank(other.get_status()),
+                    ))
+                }
+                ordering => Some(ordering),
+            }
+        }
+    }
+}
+
+impl PartialEq for Canary {
+    fn eq(&self, other: &Canary) -> bool {
+        self.get_service_group() == other.get_service_group() &&
+            self.get_incarnation() == other.get_incarnation() &&
+            self.get_status() == other.get_status() &&
+            self.get_pkg() == other.get_pkg() &&
+            self.get_previous_pkg() == other.get_previous_pkg() &&
+            self.get_canaries() == other.get_canaries()
+    }
+}
+
+impl From<ProtoRumor> for Canary {
+    fn from(pr: ProtoRumor) -> Canary {
+        Canary(pr)
+    }
+}
+
+impl From<Canary> for ProtoRumor {
+    fn from(canary: Canary) -> ProtoRumor {
+        canary.0
+    }
+}
+
+impl Deref for Canary {
+    type Target = ProtoCanary;
+
+    fn deref(&self) -> &ProtoCanary {
+        self.0.get_canary()
+    }
+}
+
+impl DerefMut for Canary {
+    fn deref_mut(&mut self) -> &mut ProtoCanary {
+        self.0.mut_canary()
+    }
+}
+
+impl Canary {
+    /// Creates a new Canary rumor testing `pkg` on the given members.
+    pub fn new<S1, S2, S3>(
+        member_id: S1,
+        service_group: ServiceGroup,
+        pkg: S2,
+        previous_pkg: S3,
+        canaries: Vec<String>,
+    ) -> Self
+    where
+        S1: Into<String>,
+        S2: Into<String>,
+        S3: Into<String>,
+    {
+        let mut rumor = ProtoRumor::new();
+        let from_id = member_id.into();
+        rumor.set_from_id(from_id.clone());
+        rumor.set_field_type(ProtoRumor_Type::Canary);
+
+        let mut proto = ProtoCanary::new();
+        proto.set_member_id(from_id);
+        proto.set_service_group(format!("{}", service_group));
+        proto.set_incarnation(0);
+        proto.set_pkg(pkg.into());
+        proto.set_previous_pkg(previous_pkg.into());
+        proto.set_canaries(RepeatedField::from_vec(canaries));
+        proto.set_status(Canary_Status::Testing);
+
+        rumor.set_canary(proto);
+        Canary(rumor)
+    }
+}
+
+impl Rumor for Canary {
+    fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let rumor = protobuf::parse_from_bytes::<ProtoRumor>(bytes)?;
+        Ok(Canary::from(rumor))
+    }
+
+    /// The rumor with the higher incarnation wins; on a tie a rollback beats a promotion, which
+    /// beats a test.
+    fn merge(&mut self, mut other: Canary) -> bool {
+        if *self >= other {
+            false
+        } else {
+            mem::swap(self, &mut other);
+            true
+        }
+    }
+
+    fn kind(&self) -> ProtoRumor_Type {
+        ProtoRumor_Type::Canary
+    }
+
+    fn id(&self) -> &str {
+        "canary"
+    }
+
+    fn key(&self) -> &str {
+        self.get_service_group()
+    }
+
+    fn incarnation(&self) -> u64 {
+        self.get_incarnation()
+    }
+
+    fn write_to_bytes(&self) -> Result<Vec<u8>> {
+        Ok(self.0.write_to_bytes()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Ordering;
+
+    use habitat_core::service::ServiceGroup;
+
+    use super::{Canary, Canary_Status};
+    use rumor::Rumor;
+
+    fn create_canary(member_id: &str) -> Canary {
+        Canary::new(
+            member_id,
+            ServiceGroup::new(None, "neurosis", "production", None).unwrap(),
+            "core/neurosis/1.0.1/20171201000000",
+            "core/neurosis/1.0.0/20171101000000",
+            vec![member_id.to_string()],
+        )
+    }
+
+    #[test]
+    fn identical_canaries_are_equal() {
+        let s1 = create_canary("adam");
+        let s2 = create_canary("adam");
+        assert_eq!(s1, s2);
+    }
+
+    #[test]
+    fn canaries_with_different_incarnations_are_not_equal_via_cmp() {
+        let s1 = create_canary("adam");
+        let mut s2 = create_canary("adam");
+        s2.set_incarnation(1);
+        assert_eq!(s1.partial_cmp(&s2), Some(Ordering::Less));
+        assert_eq!(s2.partial_cmp(&s1), Some(Ordering::Greater));
+    }
+
+    #[test]
+    fn merge_chooses_the_higher_incarnation() {
+        let mut s1 = create_canary("adam");
+        let mut s2 = create_canary("adam");
+        s2.set_incarnation(1);
+        s2.set_status(Canary_Status::Promoted);
+        let s2_check = s2.clone();
+        assert_eq!(s1.merge(s2), true);
+        assert_eq!(s1, s2_check);
+    }
+
+    #[test]
+    fn merge_prefers_a_rollback_over_a_promotion_of_the_same_incarnation() {
+        let mut promoted = create_canary("adam");
+        promoted.set_incarnation(1);
+        promoted.set_status(Canary_Status::Promoted);
+        let mut rolled_back = create_canary("adam");
+        rolled_back.set_incarnation(1);
+        rolled_back.set_status(Canary_Status::RolledBack);
+        let rolled_back_check = rolled_back.clone();
+
+        assert_eq!(promoted.merge(rolled_back.clone()), true);
+        assert_eq!(promoted, rolled_back_check);
+        let mut promoted_again = create_canary("adam");
+        promoted_again.set_incarnation(1);
+        promoted_again.set_status(Canary_Status::Promoted);
+        assert_eq!(rolled_back.merge(promoted_again), false);
+    }
+
+    #[test]
+    fn merge_returns_false_if_nothing_changed() {
+        let mut s1 = create_canary("adam");
+        s1.set_incarnation(1);
+        let s1_check = s1.clone();
+        let s2 = create_canary("adam");
+        assert_eq!(s1.merge(s2), false);
+        assert_eq!(s1, s1_check);
+    }
+}
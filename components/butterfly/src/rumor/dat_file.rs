@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::cmp;
+use std::collections::{HashMap, HashSet};
 use std::fs::{self, File, OpenOptions};
 use std::io::{self, BufReader, BufWriter, Read, Seek, SeekFrom, Write};
 use std::mem;
@@ -20,15 +22,16 @@ use std::path::{Path, PathBuf};
 use byteorder::{ByteOrder, LittleEndian};
 use protobuf::{self, Message};
 use rand::{Rng, thread_rng};
+use time::{self, Duration as TimeDuration, SteadyTime};
 
 use error::{Result, Error};
 use member::{Health, Member, MemberList};
-use message::swim::Membership as ProtoMembership;
-use rumor::{Election, ElectionUpdate, Rumor, RumorStore, Service, ServiceConfig, ServiceFile,
-            Departure};
+use message::swim::{Membership as ProtoMembership, Rumor as ProtoRumor, Rumor_Type};
+use rumor::{Canary, Election, ElectionUpdate, Rumor, RumorStore, Service, ServiceConfig,
+            ServiceFile, Departure};
 use server::Server;
 
-const HEADER_VERSION: u8 = 2;
+const HEADER_VERSION: u8 = 4;
 
 /// A versioned binary file containing rumors exchanged by the butterfly server which have
 /// been periodically persisted to disk.
@@ -56,6 +59,15 @@ impl DatFile {
         }
     }
 
+    /// Opens the dat file at `path` directly, for working on it without a running server.
+    pub fn from_path<T: AsRef<Path>>(path: T) -> Self {
+        DatFile {
+            path: path.as_ref().to_path_buf(),
+            header_size: 0,
+            header: Header::default(),
+        }
+    }
+
     pub fn path(&self) -> &Path {
         &self.path
     }
@@ -71,6 +83,9 @@ impl DatFile {
         let file = File::open(&self.path).map_err(|err| {
             Error::DatFileIO(self.path.clone(), err)
         })?;
+        let file_len = file.metadata()
+            .map_err(|err| Error::DatFileIO(self.path.clone(), err))?
+            .len();
         let mut reader = BufReader::new(file);
         reader.read_exact(&mut version).map_err(|err| {
             Error::DatFileIO(self.path.clone(), err)
@@ -84,6 +99,7 @@ impl DatFile {
         self.header_size = header_size;
         debug!("Header Size: {:?}", self.header_size);
         debug!("Header: {:?}", self.header);
+        self.check_header(file_len)?;
 
         reader.seek(SeekFrom::Start(self.member_offset())).map_err(
             |err| {
@@ -95,10 +111,8 @@ impl DatFile {
             if bytes_read >= self.header.member_len {
                 break;
             }
-            reader.read_exact(&mut size_buf).map_err(|err| {
-                Error::DatFileIO(self.path.clone(), err)
-            })?;
-            let rumor_size = LittleEndian::read_u64(&size_buf);
+            let rumor_size =
+                self.read_record_size(&mut reader, self.header.member_len, bytes_read)?;
             rumor_buf.resize(rumor_size as usize, 0);
             reader.read_exact(&mut rumor_buf).map_err(|err| {
                 Error::DatFileIO(self.path.clone(), err)
@@ -116,10 +130,8 @@ impl DatFile {
             if bytes_read >= self.header.service_len {
                 break;
             }
-            reader.read_exact(&mut size_buf).map_err(|err| {
-                Error::DatFileIO(self.path.clone(), err)
-            })?;
-            let rumor_size = LittleEndian::read_u64(&size_buf);
+            let rumor_size =
+                self.read_record_size(&mut reader, self.header.service_len, bytes_read)?;
             rumor_buf.resize(rumor_size as usize, 0);
             reader.read_exact(&mut rumor_buf).map_err(|err| {
                 Error::DatFileIO(self.path.clone(), err)
@@ -138,10 +150,8 @@ impl DatFile {
             if bytes_read >= self.header.service_config_len {
                 break;
             }
-            reader.read_exact(&mut size_buf).map_err(|err| {
-                Error::DatFileIO(self.path.clone(), err)
-            })?;
-            let rumor_size = LittleEndian::read_u64(&size_buf);
+            let rumor_size =
+                self.read_record_size(&mut reader, self.header.service_config_len, bytes_read)?;
             rumor_buf.resize(rumor_size as usize, 0);
             reader.read_exact(&mut rumor_buf).map_err(|err| {
                 Error::DatFileIO(self.path.clone(), err)
@@ -157,10 +167,8 @@ impl DatFile {
             if bytes_read >= self.header.service_file_len {
                 break;
             }
-            reader.read_exact(&mut size_buf).map_err(|err| {
-                Error::DatFileIO(self.path.clone(), err)
-            })?;
-            let rumor_size = LittleEndian::read_u64(&size_buf);
+            let rumor_size =
+                self.read_record_size(&mut reader, self.header.service_file_len, bytes_read)?;
             rumor_buf.resize(rumor_size as usize, 0);
             reader.read_exact(&mut rumor_buf).map_err(|err| {
                 Error::DatFileIO(self.path.clone(), err)
@@ -176,10 +184,8 @@ impl DatFile {
             if bytes_read >= self.header.election_len {
                 break;
             }
-            reader.read_exact(&mut size_buf).map_err(|err| {
-                Error::DatFileIO(self.path.clone(), err)
-            })?;
-            let rumor_size = LittleEndian::read_u64(&size_buf);
+            let rumor_size =
+                self.read_record_size(&mut reader, self.header.election_len, bytes_read)?;
             rumor_buf.resize(rumor_size as usize, 0);
             reader.read_exact(&mut rumor_buf).map_err(|err| {
                 Error::DatFileIO(self.path.clone(), err)
@@ -198,10 +204,8 @@ impl DatFile {
             if bytes_read >= self.header.update_len {
                 break;
             }
-            reader.read_exact(&mut size_buf).map_err(|err| {
-                Error::DatFileIO(self.path.clone(), err)
-            })?;
-            let rumor_size = LittleEndian::read_u64(&size_buf);
+            let rumor_size =
+                self.read_record_size(&mut reader, self.header.update_len, bytes_read)?;
             rumor_buf.resize(rumor_size as usize, 0);
             reader.read_exact(&mut rumor_buf).map_err(|err| {
                 Error::DatFileIO(self.path.clone(), err)
@@ -221,10 +225,8 @@ impl DatFile {
                 if bytes_read >= self.header.departure_len {
                     break;
                 }
-                reader.read_exact(&mut size_buf).map_err(|err| {
-                    Error::DatFileIO(self.path.clone(), err)
-                })?;
-                let rumor_size = LittleEndian::read_u64(&size_buf);
+                let rumor_size =
+                    self.read_record_size(&mut reader, self.header.departure_len, bytes_read)?;
                 rumor_buf.resize(rumor_size as usize, 0);
                 reader.read_exact(&mut rumor_buf).map_err(|err| {
                     Error::DatFileIO(self.path.clone(), err)
@@ -235,6 +237,92 @@ impl DatFile {
             }
         }
 
+        if version[0] >= 3 {
+            debug!("Reading canary rumors list from {}", self.path().display());
+            reader.seek(SeekFrom::Start(self.canary_offset())).map_err(
+                |err| {
+                    Error::DatFileIO(self.path.clone(), err)
+                },
+            )?;
+            bytes_read = 0;
+            loop {
+                if bytes_read >= self.header.canary_len {
+                    break;
+                }
+                let rumor_size =
+                    self.read_record_size(&mut reader, self.header.canary_len, bytes_read)?;
+                rumor_buf.resize(rumor_size as usize, 0);
+                reader.read_exact(&mut rumor_buf).map_err(|err| {
+                    Error::DatFileIO(self.path.clone(), err)
+                })?;
+                let rumor = Canary::from_bytes(&rumor_buf)?;
+                server.insert_canary(rumor);
+                bytes_read += size_buf.len() as u64 + rumor_size;
+            }
+        }
+
+        if version[0] >= 4 {
+            let wall_now = time::get_time().sec;
+            debug!("Reading member deaths from {}", self.path().display());
+            reader.seek(SeekFrom::Start(self.death_offset())).map_err(
+                |err| {
+                    Error::DatFileIO(self.path.clone(), err)
+                },
+            )?;
+            for record in self.read_records(&mut reader, self.header.death_len)? {
+                let (died, member_id) = self.split_dated(&record)?;
+                server.member_list.set_dead_for(
+                    &String::from_utf8_lossy(member_id),
+                    age_at(wall_now, died),
+                );
+            }
+            debug!("Reading tombstones from {}", self.path().display());
+            let now = server.now();
+            for record in self.read_records(&mut reader, self.header.tombstone_len)? {
+                let (tombstoned, bytes) = self.split_dated(&record)?;
+                let since = now - age_at(wall_now, tombstoned);
+                match protobuf::parse_from_bytes::<ProtoRumor>(bytes)?.get_field_type() {
+                    Rumor_Type::Service => {
+                        server.service_store.insert_tombstone(
+                            Service::from_bytes(bytes)?,
+                            since,
+                        )
+                    }
+                    Rumor_Type::ServiceConfig => {
+                        server.service_config_store.insert_tombstone(
+                            ServiceConfig::from_bytes(bytes)?,
+                            since,
+                        )
+                    }
+                    Rumor_Type::ServiceFile => {
+                        server.service_file_store.insert_tombstone(
+                            ServiceFile::from_bytes(bytes)?,
+                            since,
+                        )
+                    }
+                    Rumor_Type::Election => {
+                        server.election_store.insert_tombstone(
+                            Election::from_bytes(bytes)?,
+                            since,
+                        )
+                    }
+                    Rumor_Type::ElectionUpdate => {
+                        server.update_store.insert_tombstone(
+                            ElectionUpdate::from_bytes(bytes)?,
+                            since,
+                        )
+                    }
+                    Rumor_Type::Canary => {
+                        server.canary_store.insert_tombstone(
+                            Canary::from_bytes(bytes)?,
+                            since,
+                        )
+                    }
+                    kind => debug!("Ignoring the tombstone of a {:?} rumor", kind),
+                }
+            }
+        }
+
         Ok(())
     }
 
@@ -268,6 +356,9 @@ impl DatFile {
             header.election_len = self.write_rumor_store(&mut writer, &server.election_store)?;
             header.update_len = self.write_rumor_store(&mut writer, &server.update_store)?;
             header.departure_len = self.write_rumor_store(&mut writer, &server.departure_store)?;
+            header.canary_len = self.write_rumor_store(&mut writer, &server.canary_store)?;
+            header.death_len = self.write_deaths(&mut writer, &server.member_list)?;
+            header.tombstone_len = self.write_tombstones(&mut writer, server)?;
             writer.seek(SeekFrom::Start(1)).map_err(|err| {
                 Error::DatFileIO(self.path.clone(), err)
             })?;
@@ -282,6 +373,285 @@ impl DatFile {
         Ok(0)
     }
 
+    /// Compacts the dat file in place, without a running server, by the same rule a running
+    /// server expires rumors.
+    ///
+    /// Service rumors owned by members that have been confirmed or departed for longer than `ttl`
+    /// are tombstoned, along with the configuration, files, elections and canaries of any service
+    /// group that leaves without services. Tombstones older than `ttl` are dropped. Files written
+    /// before deaths were dated don't say how long a member has been dead; there, departed
+    /// members count as dead for longer than `ttl`, and confirmed ones don't. Membership and
+    /// departure rumors are kept, so that departed members stay departed.
+    pub fn compact(&mut self, ttl: TimeDuration) -> Result<Compaction> {
+        let mut compaction = Compaction::default();
+        compaction.bytes_before = fs::metadata(&self.path)
+            .map_err(|err| Error::DatFileIO(self.path.clone(), err))?
+            .len();
+        let mut version = [0; 1];
+        let file = File::open(&self.path).map_err(|err| {
+            Error::DatFileIO(self.path.clone(), err)
+        })?;
+        let mut reader = BufReader::new(file);
+        reader.read_exact(&mut version).map_err(|err| {
+            Error::DatFileIO(self.path.clone(), err)
+        })?;
+        let (header_size, real_header) =
+            Header::from_file(&mut reader, version[0]).map_err(|err| {
+                Error::DatFileIO(self.path.clone(), err)
+            })?;
+        self.header = real_header;
+        self.header_size = header_size;
+        self.check_header(compaction.bytes_before)?;
+        reader.seek(SeekFrom::Start(self.member_offset())).map_err(
+            |err| {
+                Error::DatFileIO(self.path.clone(), err)
+            },
+        )?;
+
+        // Older versions of the file simply have no departure or canary rumors; their lengths
+        // are 0 in the header.
+        let members = self.read_records(&mut reader, self.header.member_len)?;
+        let services = self.read_records(&mut reader, self.header.service_len)?;
+        let service_configs = self.read_records(&mut reader, self.header.service_config_len)?;
+        let service_files = self.read_records(&mut reader, self.header.service_file_len)?;
+        let elections = self.read_records(&mut reader, self.header.election_len)?;
+        let updates = self.read_records(&mut reader, self.header.update_len)?;
+        let departures = self.read_records(&mut reader, self.header.departure_len)?;
+        let canaries = self.read_records(&mut reader, self.header.canary_len)?;
+        let deaths = self.read_records(&mut reader, self.header.death_len)?;
+        let tombstones = self.read_records(&mut reader, self.header.tombstone_len)?;
+
+        let wall_now = time::get_time().sec;
+        let mut died: HashMap<String, i64> = HashMap::new();
+        for record in deaths.iter() {
+            let (secs, member_id) = self.split_dated(record)?;
+            died.insert(String::from_utf8_lossy(member_id).into_owned(), secs);
+        }
+        let mut dead: HashSet<String> = HashSet::new();
+        for bytes in members.iter() {
+            let proto = protobuf::parse_from_bytes::<ProtoMembership>(bytes)?;
+            let member_id = proto.get_member().get_id();
+            let expired = match died.get(member_id) {
+                Some(secs) => age_at(wall_now, *secs) >= ttl,
+                None => Health::from(proto.get_health()) == Health::Departed,
+            };
+            if expired {
+                dead.insert(String::from(member_id));
+            }
+        }
+
+        let mut kept_tombstones = Vec::new();
+        for record in tombstones {
+            if age_at(wall_now, self.split_dated(&record)?.0) < ttl {
+                kept_tombstones.push(record);
+            } else {
+                compaction.tombstones += 1;
+            }
+        }
+
+        let mut live_groups: HashSet<String> = HashSet::new();
+        let mut dead_groups: HashSet<String> = HashSet::new();
+        let mut kept_services = Vec::new();
+        for bytes in services {
+            let service = Service::from_bytes(&bytes)?;
+            if dead.contains(service.get_member_id()) {
+                dead_groups.insert(String::from(service.key()));
+                kept_tombstones.push(dated_record(wall_now, &bytes));
+                compaction.rumors += 1;
+            } else {
+                live_groups.insert(String::from(service.key()));
+                kept_services.push(bytes);
+            }
+        }
+        let emptied: HashSet<String> = dead_groups.difference(&live_groups).cloned().collect();
+        compaction.service_groups = emptied.len();
+        let service_configs = self.retain_records::<ServiceConfig>(
+            service_configs,
+            &emptied,
+            &mut kept_tombstones,
+            &mut compaction,
+        )?;
+        let service_files = self.retain_records::<ServiceFile>(
+            service_files,
+            &emptied,
+            &mut kept_tombstones,
+            &mut compaction,
+        )?;
+        let elections = self.retain_records::<Election>(
+            elections,
+            &emptied,
+            &mut kept_tombstones,
+            &mut compaction,
+        )?;
+        let updates = self.retain_records::<ElectionUpdate>(
+            updates,
+            &emptied,
+            &mut kept_tombstones,
+            &mut compaction,
+        )?;
+        let canaries = self.retain_records::<Canary>(
+            canaries,
+            &emptied,
+            &mut kept_tombstones,
+            &mut compaction,
+        )?;
+
+        let mut header = Header::default();
+        let tmp_path = self.path.with_extension(
+            thread_rng()
+                .gen_ascii_chars()
+                .take(8)
+                .collect::<String>(),
+        );
+        {
+            let file = OpenOptions::new()
+                .create(true)
+                .write(true)
+                .truncate(true)
+                .open(&tmp_path)
+                .map_err(|err| Error::DatFileIO(tmp_path.clone(), err))?;
+            let mut writer = BufWriter::new(file);
+            self.init(&mut writer)?;
+            header.member_len = self.write_records(&mut writer, &members)?;
+            header.service_len = self.write_records(&mut writer, &kept_services)?;
+            header.service_config_len = self.write_records(&mut writer, &service_configs)?;
+            header.service_file_len = self.write_records(&mut writer, &service_files)?;
+            header.election_len = self.write_records(&mut writer, &elections)?;
+            header.update_len = self.write_records(&mut writer, &updates)?;
+            header.departure_len = self.write_records(&mut writer, &departures)?;
+            header.canary_len = self.write_records(&mut writer, &canaries)?;
+            header.death_len = self.write_records(&mut writer, &deaths)?;
+            header.tombstone_len = self.write_records(&mut writer, &kept_tombstones)?;
+            writer.seek(SeekFrom::Start(1)).map_err(|err| {
+                Error::DatFileIO(self.path.clone(), err)
+            })?;
+            self.write_header(&mut writer, &header)?;
+            writer.flush().map_err(
+                |err| Error::DatFileIO(self.path.clone(), err),
+            )?;
+        }
+        fs::rename(&tmp_path, &self.path).map_err(|err| {
+            Error::DatFileIO(self.path.clone(), err)
+        })?;
+        compaction.bytes_after = fs::metadata(&self.path)
+            .map_err(|err| Error::DatFileIO(self.path.clone(), err))?
+            .len();
+        Ok(compaction)
+    }
+
+    /// Reads `len` bytes worth of length-prefixed records.
+    fn read_records<R>(&self, reader: &mut R, len: u64) -> Result<Vec<Vec<u8>>>
+    where
+        R: Read,
+    {
+        let mut records = Vec::new();
+        let mut bytes_read = 0;
+        while bytes_read < len {
+            let rumor_size = self.read_record_size(reader, len, bytes_read)?;
+            let mut rumor_buf = vec![0; rumor_size as usize];
+            reader.read_exact(&mut rumor_buf).map_err(|err| {
+                Error::DatFileIO(self.path.clone(), err)
+            })?;
+            records.push(rumor_buf);
+            bytes_read += 8 + rumor_size;
+        }
+        Ok(records)
+    }
+
+    /// Reads the size prefix of the next record in a section `len` bytes long, of which
+    /// `bytes_read` have been read. A record that claims to run past the end of its section means
+    /// the file is corrupt, and is refused before we allocate a buffer for it.
+    fn read_record_size<R>(&self, reader: &mut R, len: u64, bytes_read: u64) -> Result<u64>
+    where
+        R: Read,
+    {
+        let mut size_buf = [0; 8];
+        reader.read_exact(&mut size_buf).map_err(|err| {
+            Error::DatFileIO(self.path.clone(), err)
+        })?;
+        let rumor_size = LittleEndian::read_u64(&size_buf);
+        let remaining = len.saturating_sub(bytes_read).saturating_sub(
+            size_buf.len() as u64,
+        );
+        if rumor_size > remaining {
+            return Err(Error::BadDatFile(
+                self.path.clone(),
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!(
+                        "record of {} bytes overruns its section by {} bytes",
+                        rumor_size,
+                        rumor_size - remaining
+                    ),
+                ),
+            ));
+        }
+        Ok(rumor_size)
+    }
+
+    /// Checks that the sections the header describes fit in a file `file_len` bytes long, so a
+    /// corrupt header can't make us read sections that aren't there.
+    fn check_header(&self, file_len: u64) -> Result<()> {
+        let lens = [
+            self.header.member_len,
+            self.header.service_len,
+            self.header.service_config_len,
+            self.header.service_file_len,
+            self.header.election_len,
+            self.header.update_len,
+            self.header.departure_len,
+            self.header.canary_len,
+            self.header.death_len,
+            self.header.tombstone_len,
+        ];
+        let end = lens.iter().fold(Some(self.member_offset()), |end, len| {
+            end.and_then(|end| end.checked_add(*len))
+        });
+        match end {
+            Some(end) if end <= file_len => Ok(()),
+            _ => Err(Error::BadDatFile(
+                self.path.clone(),
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("header describes more than the {} bytes in the file", file_len),
+                ),
+            )),
+        }
+    }
+
+    /// Drops the records of rumors belonging to the given service groups, adding a tombstone
+    /// dated now for each of them to `tombstones`.
+    fn retain_records<T: Rumor>(
+        &self,
+        records: Vec<Vec<u8>>,
+        service_groups: &HashSet<String>,
+        tombstones: &mut Vec<Vec<u8>>,
+        compaction: &mut Compaction,
+    ) -> Result<Vec<Vec<u8>>> {
+        let wall_now = time::get_time().sec;
+        let mut kept = Vec::new();
+        for bytes in records {
+            if service_groups.contains(T::from_bytes(&bytes)?.key()) {
+                tombstones.push(dated_record(wall_now, &bytes));
+                compaction.rumors += 1;
+            } else {
+                kept.push(bytes);
+            }
+        }
+        Ok(kept)
+    }
+
+    fn write_records<W>(&self, writer: &mut W, records: &[Vec<u8>]) -> Result<u64>
+    where
+        W: Write,
+    {
+        let mut total = 0;
+        for bytes in records {
+            total += self.write_record(writer, bytes)?;
+        }
+        Ok(total)
+    }
+
     fn init<W>(&self, writer: &mut W) -> Result<usize>
     where
         W: Write,
@@ -331,6 +701,18 @@ impl DatFile {
         self.update_offset() + self.header.update_len
     }
 
+    fn canary_offset(&self) -> u64 {
+        self.departure_offset() + self.header.departure_len
+    }
+
+    fn death_offset(&self) -> u64 {
+        self.canary_offset() + self.header.canary_len
+    }
+
+    fn tombstone_offset(&self) -> u64 {
+        self.death_offset() + self.header.death_len
+    }
+
     fn write_header<W>(&self, writer: &mut W, header: &Header) -> Result<usize>
     where
         W: Write,
@@ -362,17 +744,8 @@ impl DatFile {
     where
         W: Write,
     {
-        let mut total = 0;
-        let mut len_buf = [0; 8];
         let bytes = membership.write_to_bytes().unwrap();
-        LittleEndian::write_u64(&mut len_buf, bytes.len() as u64);
-        total += writer.write(&len_buf).map_err(|err| {
-            Error::DatFileIO(self.path.clone(), err)
-        })? as u64;
-        total += writer.write(&bytes).map_err(|err| {
-            Error::DatFileIO(self.path.clone(), err)
-        })? as u64;
-        Ok(total)
+        self.write_record(writer, &bytes)
     }
 
     fn write_rumor_store<T, W>(&self, writer: &mut W, store: &RumorStore<T>) -> Result<u64>
@@ -399,20 +772,112 @@ impl DatFile {
         T: Rumor,
         W: Write,
     {
-        let mut total = 0;
-        let mut rumor_len = [0; 8];
         let bytes = rumor.write_to_bytes().unwrap();
-        LittleEndian::write_u64(&mut rumor_len, bytes.len() as u64);
-        total += writer.write(&rumor_len).map_err(|err| {
+        self.write_record(writer, &bytes)
+    }
+
+    /// Writes when each dead member died, so that its rumors expire on time even across restarts.
+    fn write_deaths<W>(&self, writer: &mut W, member_list: &MemberList) -> Result<u64>
+    where
+        W: Write,
+    {
+        let wall_now = time::get_time().sec;
+        let records: Vec<Vec<u8>> = member_list
+            .dead_for()
+            .iter()
+            .map(|&(ref id, age)| {
+                dated_record(wall_now - age.num_seconds(), id.as_bytes())
+            })
+            .collect();
+        self.write_records(writer, &records)
+    }
+
+    /// Writes the tombstones of every rumor store, so that expired rumors aren't taken back from
+    /// the first member which still gossips them after a restart.
+    fn write_tombstones<W>(&self, writer: &mut W, server: &Server) -> Result<u64>
+    where
+        W: Write,
+    {
+        let now = server.now();
+        let mut records = Vec::new();
+        tombstone_records(&server.service_store, now, &mut records);
+        tombstone_records(&server.service_config_store, now, &mut records);
+        tombstone_records(&server.service_file_store, now, &mut records);
+        tombstone_records(&server.election_store, now, &mut records);
+        tombstone_records(&server.update_store, now, &mut records);
+        tombstone_records(&server.canary_store, now, &mut records);
+        self.write_records(writer, &records)
+    }
+
+    /// Splits a dated record into the seconds since the epoch it is dated, and its contents.
+    fn split_dated<'a>(&self, record: &'a [u8]) -> Result<(i64, &'a [u8])> {
+        if record.len() < 8 {
+            return Err(Error::BadDatFile(
+                self.path.clone(),
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("dated record of {} bytes is too short", record.len()),
+                ),
+            ));
+        }
+        Ok((LittleEndian::read_i64(&record[..8]), &record[8..]))
+    }
+
+    /// Writes a single record, prefixed with its length.
+    fn write_record<W>(&self, writer: &mut W, bytes: &[u8]) -> Result<u64>
+    where
+        W: Write,
+    {
+        let mut total = 0;
+        let mut record_len = [0; 8];
+        LittleEndian::write_u64(&mut record_len, bytes.len() as u64);
+        total += writer.write(&record_len).map_err(|err| {
             Error::DatFileIO(self.path.clone(), err)
         })? as u64;
-        total += writer.write(&bytes).map_err(|err| {
+        total += writer.write(bytes).map_err(|err| {
             Error::DatFileIO(self.path.clone(), err)
         })? as u64;
         Ok(total)
     }
 }
 
+/// Prefixes `bytes` with a date in seconds since the epoch. Dates are written in wall clock time,
+/// as a server's own clock only measures the time since it started.
+fn dated_record(secs: i64, bytes: &[u8]) -> Vec<u8> {
+    let mut record = vec![0; 8];
+    LittleEndian::write_i64(&mut record, secs);
+    record.extend_from_slice(bytes);
+    record
+}
+
+/// Adds a dated record for every tombstone of `store` to `records`.
+fn tombstone_records<T: Rumor>(store: &RumorStore<T>, now: SteadyTime, records: &mut Vec<Vec<u8>>) {
+    let wall_now = time::get_time().sec;
+    store.with_tombstones(now, |rumor, age| {
+        let bytes = rumor.write_to_bytes().unwrap();
+        records.push(dated_record(wall_now - age.num_seconds(), &bytes));
+    });
+}
+
+/// How long ago at `wall_now` a date read back from a dated record was; never negative, should
+/// the wall clock have been set back since.
+fn age_at(wall_now: i64, secs: i64) -> TimeDuration {
+    TimeDuration::seconds(cmp::max(wall_now - secs, 0))
+}
+
+/// What `DatFile::compact` removed from a dat file.
+#[derive(Debug, Default, PartialEq)]
+pub struct Compaction {
+    /// How many rumors were dropped, leaving a tombstone.
+    pub rumors: usize,
+    /// How many tombstones were dropped for being older than the TTL.
+    pub tombstones: usize,
+    /// How many service groups were dropped entirely.
+    pub service_groups: usize,
+    pub bytes_before: u64,
+    pub bytes_after: u64,
+}
+
 /// Describes contents and structure of dat file.
 ///
 /// The information in this header is used to enable IO seeking operations on a binary dat
@@ -426,6 +891,9 @@ pub struct Header {
     pub election_len: u64,
     pub update_len: u64,
     pub departure_len: u64,
+    pub canary_len: u64,
+    pub death_len: u64,
+    pub tombstone_len: u64,
 }
 
 impl Header {
@@ -434,7 +902,9 @@ impl Header {
         R: Read,
     {
         let mut bytes = match version {
-            1 => vec![0; mem::size_of::<Self>()],
+            1 => vec![0; 48],
+            2 => vec![0; 64],
+            3 => vec![0; 72],
             _ => vec![0; mem::size_of::<Self>() + 8],
         };
         reader.read_exact(&mut bytes)?;
@@ -458,6 +928,43 @@ impl Header {
                         election_len: LittleEndian::read_u64(&bytes[32..40]),
                         update_len: LittleEndian::read_u64(&bytes[40..48]),
                         departure_len: 0,
+                        canary_len: 0,
+                        death_len: 0,
+                        tombstone_len: 0,
+                    },
+                )
+            }
+            2 => {
+                (
+                    LittleEndian::read_u64(&bytes[0..8]),
+                    Header {
+                        member_len: LittleEndian::read_u64(&bytes[8..16]),
+                        service_len: LittleEndian::read_u64(&bytes[16..24]),
+                        service_config_len: LittleEndian::read_u64(&bytes[24..32]),
+                        service_file_len: LittleEndian::read_u64(&bytes[32..40]),
+                        election_len: LittleEndian::read_u64(&bytes[40..48]),
+                        update_len: LittleEndian::read_u64(&bytes[48..56]),
+                        departure_len: LittleEndian::read_u64(&bytes[56..64]),
+                        canary_len: 0,
+                        death_len: 0,
+                        tombstone_len: 0,
+                    },
+                )
+            }
+            3 => {
+                (
+                    LittleEndian::read_u64(&bytes[0..8]),
+                    Header {
+                        member_len: LittleEndian::read_u64(&bytes[8..16]),
+                        service_len: LittleEndian::read_u64(&bytes[16..24]),
+                        service_config_len: LittleEndian::read_u64(&bytes[24..32]),
+                        service_file_len: LittleEndian::read_u64(&bytes[32..40]),
+                        election_len: LittleEndian::read_u64(&bytes[40..48]),
+                        update_len: LittleEndian::read_u64(&bytes[48..56]),
+                        departure_len: LittleEndian::read_u64(&bytes[56..64]),
+                        canary_len: LittleEndian::read_u64(&bytes[64..72]),
+                        death_len: 0,
+                        tombstone_len: 0,
                     },
                 )
             }
@@ -480,6 +987,9 @@ impl Header {
                         election_len: LittleEndian::read_u64(&bytes[40..48]),
                         update_len: LittleEndian::read_u64(&bytes[48..56]),
                         departure_len: LittleEndian::read_u64(&bytes[56..64]),
+                        canary_len: LittleEndian::read_u64(&bytes[64..72]),
+                        death_len: LittleEndian::read_u64(&bytes[72..80]),
+                        tombstone_len: LittleEndian::read_u64(&bytes[80..88]),
                     },
                 )
             }
@@ -498,17 +1008,228 @@ impl Header {
         LittleEndian::write_u64(&mut bytes[40..48], self.election_len);
         LittleEndian::write_u64(&mut bytes[48..56], self.update_len);
         LittleEndian::write_u64(&mut bytes[56..64], self.departure_len);
+        LittleEndian::write_u64(&mut bytes[64..72], self.canary_len);
+        LittleEndian::write_u64(&mut bytes[72..80], self.death_len);
+        LittleEndian::write_u64(&mut bytes[80..88], self.tombstone_len);
         Ok(bytes)
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::env;
     use std::mem;
+    use std::str::FromStr;
 
+    use byteorder::{ByteOrder, LittleEndian};
+    use habitat_core::package::{Identifiable, PackageIdent};
+    use habitat_core::service::ServiceGroup;
     use rand;
+    use uuid::Uuid;
+
+    use rumor::service::SysInfo;
     use super::*;
 
+    fn service(member: &Member, name: &str) -> Service {
+        let pkg = PackageIdent::from_str(&format!("core/{}/1.2.3/20161208121212", name)).unwrap();
+        let sg = ServiceGroup::new(None, pkg.name(), "production", None).unwrap();
+        Service::new(member.get_id(), &pkg, &sg, &SysInfo::default(), None)
+    }
+
+    fn service_config(member: &Member, name: &str) -> ServiceConfig {
+        let sg = ServiceGroup::new(None, name, "production", None).unwrap();
+        ServiceConfig::new(member.get_id(), sg, Vec::from("port = 80"))
+    }
+
+    /// Reads back the records of the section `len` bytes long at `offset`.
+    fn read_section<R>(dat_file: &DatFile, reader: &mut R, offset: u64, len: u64) -> Vec<Vec<u8>>
+    where
+        R: Read + Seek,
+    {
+        reader.seek(SeekFrom::Start(offset)).unwrap();
+        dat_file.read_records(reader, len).unwrap()
+    }
+
+    #[test]
+    fn compact_drops_the_rumors_of_departed_members() {
+        let path = env::temp_dir().join(format!("{}.rst", Uuid::new_v4().simple()));
+        let alive = Member::default();
+        let departed = Member::default();
+        let member_list = MemberList::new();
+        member_list.insert(alive.clone(), Health::Alive);
+        member_list.insert(departed.clone(), Health::Departed);
+        let services: RumorStore<Service> = RumorStore::default();
+        services.insert(service(&alive, "neurosis"));
+        services.insert(service(&departed, "neurosis"));
+        services.insert(service(&departed, "redis"));
+        let service_configs: RumorStore<ServiceConfig> = RumorStore::default();
+        service_configs.insert(service_config(&departed, "neurosis"));
+        service_configs.insert(service_config(&departed, "redis"));
+        service_configs.insert(service_config(&departed, "postgres"));
+
+        let mut dat_file = DatFile::from_path(&path);
+        {
+            let mut header = Header::default();
+            let file = File::create(&path).unwrap();
+            let mut writer = BufWriter::new(file);
+            dat_file.init(&mut writer).unwrap();
+            header.member_len = dat_file
+                .write_member_list(&mut writer, &member_list)
+                .unwrap();
+            header.service_len = dat_file.write_rumor_store(&mut writer, &services).unwrap();
+            header.service_config_len = dat_file
+                .write_rumor_store(&mut writer, &service_configs)
+                .unwrap();
+            writer.seek(SeekFrom::Start(1)).unwrap();
+            dat_file.write_header(&mut writer, &header).unwrap();
+            writer.flush().unwrap();
+        }
+
+        let compaction = dat_file.compact(TimeDuration::days(1)).unwrap();
+        assert_eq!(compaction.rumors, 3);
+        assert_eq!(compaction.service_groups, 1);
+        assert_eq!(compaction.tombstones, 0);
+
+        let mut reader = BufReader::new(File::open(&path).unwrap());
+        let mut version = [0; 1];
+        reader.read_exact(&mut version).unwrap();
+        assert_eq!(version[0], HEADER_VERSION);
+        let (header_size, header) = Header::from_file(&mut reader, version[0]).unwrap();
+        let members = dat_file.read_records(&mut reader, header.member_len).unwrap();
+        let services = dat_file.read_records(&mut reader, header.service_len).unwrap();
+        let service_configs = dat_file
+            .read_records(&mut reader, header.service_config_len)
+            .unwrap();
+        assert_eq!(members.len(), 2);
+        assert_eq!(services.len(), 1);
+        assert_eq!(
+            Service::from_bytes(&services[0]).unwrap().get_member_id(),
+            alive.get_id()
+        );
+        // Configuration for a group that never had services is left alone.
+        let mut groups: Vec<String> = service_configs
+            .iter()
+            .map(|bytes| {
+                String::from(ServiceConfig::from_bytes(bytes).unwrap().key())
+            })
+            .collect();
+        groups.sort();
+        assert_eq!(groups, vec!["neurosis.production", "postgres.production"]);
+        dat_file.header_size = header_size;
+        dat_file.header = header;
+        let (offset, len) = (dat_file.tombstone_offset(), dat_file.header.tombstone_len);
+        assert_eq!(read_section(&dat_file, &mut reader, offset, len).len(), 3);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn compact_drops_the_rumors_of_members_dead_for_longer_than_the_ttl() {
+        let path = env::temp_dir().join(format!("{}.rst", Uuid::new_v4().simple()));
+        let long_dead = Member::default();
+        let freshly_dead = Member::default();
+        let member_list = MemberList::new();
+        member_list.insert(long_dead.clone(), Health::Confirmed);
+        member_list.insert(freshly_dead.clone(), Health::Confirmed);
+        let services: RumorStore<Service> = RumorStore::default();
+        services.insert(service(&long_dead, "neurosis"));
+        services.insert(service(&freshly_dead, "redis"));
+        let wall_now = time::get_time().sec;
+        let deaths = vec![
+            dated_record(wall_now - 2 * 86400, long_dead.get_id().as_bytes()),
+            dated_record(wall_now - 3600, freshly_dead.get_id().as_bytes()),
+        ];
+        let tombstones = vec![
+            dated_record(
+                wall_now - 2 * 86400,
+                &service(&long_dead, "postgres").write_to_bytes().unwrap(),
+            ),
+            dated_record(
+                wall_now - 3600,
+                &service(&long_dead, "mysql").write_to_bytes().unwrap(),
+            ),
+        ];
+
+        let mut dat_file = DatFile::from_path(&path);
+        {
+            let mut header = Header::default();
+            let file = File::create(&path).unwrap();
+            let mut writer = BufWriter::new(file);
+            dat_file.init(&mut writer).unwrap();
+            header.member_len = dat_file
+                .write_member_list(&mut writer, &member_list)
+                .unwrap();
+            header.service_len = dat_file.write_rumor_store(&mut writer, &services).unwrap();
+            header.death_len = dat_file.write_records(&mut writer, &deaths).unwrap();
+            header.tombstone_len = dat_file.write_records(&mut writer, &tombstones).unwrap();
+            writer.seek(SeekFrom::Start(1)).unwrap();
+            dat_file.write_header(&mut writer, &header).unwrap();
+            writer.flush().unwrap();
+        }
+
+        let compaction = dat_file.compact(TimeDuration::days(1)).unwrap();
+        assert_eq!(compaction.rumors, 1);
+        assert_eq!(compaction.service_groups, 1);
+        assert_eq!(compaction.tombstones, 1);
+
+        let mut reader = BufReader::new(File::open(&path).unwrap());
+        let mut version = [0; 1];
+        reader.read_exact(&mut version).unwrap();
+        let (header_size, header) = Header::from_file(&mut reader, version[0]).unwrap();
+        dat_file.header_size = header_size;
+        dat_file.header = header;
+        let offset = dat_file.service_offset();
+        let services = read_section(&dat_file, &mut reader, offset, dat_file.header.service_len);
+        assert_eq!(services.len(), 1);
+        assert_eq!(
+            Service::from_bytes(&services[0]).unwrap().get_member_id(),
+            freshly_dead.get_id()
+        );
+        let offset = dat_file.death_offset();
+        let deaths = read_section(&dat_file, &mut reader, offset, dat_file.header.death_len);
+        assert_eq!(deaths.len(), 2);
+        let offset = dat_file.tombstone_offset();
+        let mut tombstoned: Vec<String> =
+            read_section(&dat_file, &mut reader, offset, dat_file.header.tombstone_len)
+                .iter()
+                .map(|record| {
+                    let (_, bytes) = dat_file.split_dated(record).unwrap();
+                    String::from(Service::from_bytes(bytes).unwrap().key())
+                })
+                .collect();
+        tombstoned.sort();
+        assert_eq!(tombstoned, vec!["mysql.production", "neurosis.production"]);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn read_records_refuses_a_record_longer_than_its_section() {
+        let dat_file = DatFile::from_path("/nonexistent/failed_to_create");
+        let mut bytes = vec![0; 8];
+        LittleEndian::write_u64(&mut bytes, u64::max_value());
+        bytes.extend_from_slice(b"rumor");
+        match dat_file.read_records(&mut io::Cursor::new(&bytes), bytes.len() as u64) {
+            Err(Error::BadDatFile(_, _)) => (),
+            other => panic!("Expected a bad dat file, got {:?}", other),
+        }
+
+        LittleEndian::write_u64(&mut bytes[0..8], 5);
+        let records = dat_file
+            .read_records(&mut io::Cursor::new(&bytes), bytes.len() as u64)
+            .unwrap();
+        assert_eq!(records, vec![b"rumor".to_vec()]);
+    }
+
+    #[test]
+    fn check_header_refuses_sections_past_the_end_of_the_file() {
+        let mut dat_file = DatFile::from_path("/nonexistent/failed_to_create");
+        dat_file.header_size = 72;
+        dat_file.header.member_len = 100;
+        assert!(dat_file.check_header(173).is_ok());
+        assert!(dat_file.check_header(172).is_err());
+        dat_file.header.canary_len = u64::max_value();
+        assert!(dat_file.check_header(u64::max_value()).is_err());
+    }
+
     #[test]
     fn read_write_header() {
         let mut original = Header::default();
@@ -517,9 +1238,39 @@ mod tests {
         original.service_file_len = rand::random::<u64>();
         original.election_len = rand::random::<u64>();
         original.update_len = rand::random::<u64>();
+        original.departure_len = rand::random::<u64>();
+        original.canary_len = rand::random::<u64>();
+        original.death_len = rand::random::<u64>();
+        original.tombstone_len = rand::random::<u64>();
         let bytes = original.write_to_bytes().unwrap();
         let (_size_of_header, restored) = Header::from_bytes(&bytes, HEADER_VERSION);
         assert_eq!(bytes.len(), mem::size_of::<Header>() + 8);
         assert_eq!(original, restored);
     }
+
+    #[test]
+    fn read_version_3_header() {
+        let mut original = Header::default();
+        original.service_len = rand::random::<u64>();
+        original.canary_len = rand::random::<u64>();
+        let mut bytes = original.write_to_bytes().unwrap();
+        bytes.truncate(72);
+        LittleEndian::write_u64(&mut bytes[0..8], 72);
+        let (size_of_header, restored) = Header::from_bytes(&bytes, 3);
+        assert_eq!(size_of_header, 72);
+        assert_eq!(original, restored);
+    }
+
+    #[test]
+    fn read_version_2_header() {
+        let mut original = Header::default();
+        original.service_len = rand::random::<u64>();
+        original.departure_len = rand::random::<u64>();
+        let mut bytes = original.write_to_bytes().unwrap();
+        bytes.truncate(64);
+        LittleEndian::write_u64(&mut bytes[0..8], 64);
+        let (size_of_header, restored) = Header::from_bytes(&bytes, 2);
+        assert_eq!(size_of_header, 64);
+        assert_eq!(original, restored);
+    }
 }
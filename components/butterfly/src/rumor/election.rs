@@ -22,16 +22,74 @@
 //! devolve to a single, universal rumor, which when it is received by the winner will result in
 //! the election finishing. There can, in the end, be only one.
 
+use std::fmt;
 use std::ops::{Deref, DerefMut};
+use std::result;
+use std::str::FromStr;
+use std::time::Duration;
 
 use habitat_core::service::ServiceGroup;
 use protobuf::{self, Message, RepeatedField};
 
 pub use message::swim::Election_Status;
-use error::Result;
+use error::{Error, Result};
 use message::swim::{Election as ProtoElection, Rumor as ProtoRumor, Rumor_Type as ProtoRumor_Type};
 use rumor::Rumor;
 
+/// The members a service group's quorum is counted against. An election, or a leader, has quorum
+/// when more than half of them are alive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum QuorumBasis {
+    /// Every member that hasn't departed, including the ones confirmed dead. The minority side of
+    /// a partition never has quorum, but the group stops electing leaders once half of its
+    /// members have died, until they are departed.
+    Known,
+    /// Only the members that aren't confirmed dead. The group keeps electing leaders as members
+    /// die, but so does each side of a long enough partition.
+    Alive,
+}
+
+impl Default for QuorumBasis {
+    fn default() -> QuorumBasis {
+        QuorumBasis::Known
+    }
+}
+
+impl fmt::Display for QuorumBasis {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let value = match *self {
+            QuorumBasis::Known => "known",
+            QuorumBasis::Alive => "alive",
+        };
+        write!(f, "{}", value)
+    }
+}
+
+impl FromStr for QuorumBasis {
+    type Err = Error;
+
+    fn from_str(value: &str) -> result::Result<Self, Self::Err> {
+        match value.to_lowercase().as_ref() {
+            "known" => Ok(QuorumBasis::Known),
+            "alive" => Ok(QuorumBasis::Alive),
+            _ => Err(Error::BadQuorumBasis(value.to_string())),
+        }
+    }
+}
+
+/// How the elections of a service group are run.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ElectionConfig {
+    pub quorum_basis: QuorumBasis,
+    /// How long a leader stays leader without renewing its lease. A leader only renews its lease
+    /// while it has quorum and no member of the group is suspect, and steps down once half of the
+    /// lease has gone by without a renewal. A new leader is only elected once the lease of the
+    /// previous one has run out, counted from the last renewal the winner saw, or from when it
+    /// first looked if it never saw one. Without a lease, a leader steps down as soon as it loses
+    /// quorum.
+    pub lease: Option<Duration>,
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct Election(ProtoRumor);
 
@@ -121,6 +179,12 @@ impl Election {
     pub fn is_finished(&self) -> bool {
         self.get_status() == Election_Status::Finished
     }
+
+    /// Renews the lease of the leader.
+    pub fn renew_lease(&mut self) {
+        let renewal = self.get_lease_renewal() + 1;
+        self.set_lease_renewal(renewal);
+    }
 }
 
 impl PartialEq for Election {
@@ -130,7 +194,8 @@ impl PartialEq for Election {
             self.get_member_id() == other.get_member_id() &&
             self.get_suitability() == other.get_suitability() &&
             self.get_votes() == other.get_votes() &&
-            self.get_status() == other.get_status() && self.get_term() == other.get_term()
+            self.get_status() == other.get_status() && self.get_term() == other.get_term() &&
+            self.get_lease_renewal() == other.get_lease_renewal()
     }
 }
 
@@ -146,6 +211,16 @@ impl Rumor for Election {
             // If we are the same object, just return false
             // println!("Equal: {:?} {:?}", self, other);
             false
+        } else if other.get_term() == self.get_term() && self.is_finished() &&
+                   other.is_finished()
+        {
+            // If we agree on the leader, only a newer lease renewal is news.
+            if other.get_lease_renewal() > self.get_lease_renewal() {
+                *self = other;
+                true
+            } else {
+                false
+            }
         } else if other.get_term() >= self.get_term() &&
                    other.get_status() == Election_Status::Finished
         {
@@ -210,6 +285,10 @@ impl Rumor for Election {
         self.get_service_group()
     }
 
+    fn incarnation(&self) -> u64 {
+        self.get_term()
+    }
+
     fn write_to_bytes(&self) -> Result<Vec<u8>> {
         Ok(self.0.write_to_bytes()?)
     }
@@ -280,6 +359,10 @@ impl Rumor for ElectionUpdate {
         self.0.key()
     }
 
+    fn incarnation(&self) -> u64 {
+        self.0.incarnation()
+    }
+
     fn write_to_bytes(&self) -> Result<Vec<u8>> {
         self.0.write_to_bytes()
     }
@@ -287,8 +370,10 @@ impl Rumor for ElectionUpdate {
 
 #[cfg(test)]
 mod tests {
+    use std::str::FromStr;
+
     use rumor::Rumor;
-    use rumor::election::Election;
+    use rumor::election::{Election, QuorumBasis};
     use habitat_core::service::ServiceGroup;
 
     fn create_election(member_id: &str, suitability: u64) -> Election {
@@ -331,4 +416,25 @@ mod tests {
         assert_eq!(e1.get_member_id(), "d");
         assert_eq!(e1.get_votes().len(), 4);
     }
+
+    #[test]
+    fn merge_finished_takes_newer_lease_renewals_only() {
+        let mut e1 = create_election("a", 0);
+        e1.finish();
+        let mut e2 = e1.clone();
+        e2.renew_lease();
+        let stale = e1.clone();
+        assert_eq!(e1.merge(e2), true);
+        assert_eq!(e1.get_lease_renewal(), 1);
+        assert_eq!(e1.merge(stale), false);
+        assert_eq!(e1.get_lease_renewal(), 1);
+    }
+
+    #[test]
+    fn quorum_basis_from_str() {
+        assert_eq!(QuorumBasis::from_str("known").unwrap(), QuorumBasis::Known);
+        assert_eq!(QuorumBasis::from_str("Alive").unwrap(), QuorumBasis::Alive);
+        assert!(QuorumBasis::from_str("everyone").is_err());
+        assert_eq!(QuorumBasis::default(), QuorumBasis::Known);
+    }
 }
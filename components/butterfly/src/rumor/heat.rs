@@ -76,8 +76,9 @@ impl RumorHeat {
     /// -2` times, and so on, with those that have _never_ been
     /// shared with the member coming last.
     ///
-    /// **NOTE**: The ordering of rumors within each of these "heat"
-    /// cohorts is currently undefined.
+    /// Within each of these "heat" cohorts, rumors are ordered by
+    /// kind, id and key, so the same rumors always come out in the
+    /// same order.
     pub fn currently_hot_rumors(&self, id: &str) -> Vec<RumorKey> {
         let mut rumor_heat: Vec<(RumorKey, usize)> = self.0
             .read()
@@ -90,7 +91,12 @@ impl RumorHeat {
             .collect();
 
         // Reverse sorting by heat; 0s come last!
-        rumor_heat.sort_by(|&(_, ref h1), &(_, ref h2)| h2.cmp(h1));
+        rumor_heat.sort_by(|&(ref k1, ref h1), &(ref k2, ref h2)| {
+            h2.cmp(h1)
+                .then_with(|| (k1.kind as i32).cmp(&(k2.kind as i32)))
+                .then_with(|| k1.id.cmp(&k2.id))
+                .then_with(|| k1.key.cmp(&k2.key))
+        });
 
         // We don't need the heat anymore, just return the rumors.
         rumor_heat.into_iter().map(|(k, _)| k).collect()
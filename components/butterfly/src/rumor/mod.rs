@@ -19,20 +19,28 @@
 //!
 //! New rumors need to implement the `From` trait for `RumorKey`, and then can track the arrival of
 //! new rumors, and dispatch them according to their `kind`.
+//!
+//! Rumors that are no longer useful can be tombstoned. A tombstoned rumor is removed from its
+//! store, but remembered for a while so that a copy gossiped back to us by a member that hasn't
+//! forgotten it yet can't resurrect it; only a newer version of the rumor is accepted again.
 
+pub mod canary;
 pub mod dat_file;
 pub mod departure;
 pub mod heat;
 pub mod election;
+pub mod ring_key;
 pub mod service;
 pub mod service_config;
 pub mod service_file;
 
+pub use self::canary::Canary;
 pub use self::election::{Election, ElectionUpdate};
 pub use self::service::Service;
 pub use self::service_config::ServiceConfig;
 pub use self::service_file::ServiceFile;
 pub use self::departure::Departure;
+pub use self::ring_key::RingKey;
 
 use std::collections::HashMap;
 use std::collections::hash_map::Entry;
@@ -44,6 +52,7 @@ use std::sync::atomic::{AtomicUsize, Ordering};
 
 use serde::{Serialize, Serializer};
 use serde::ser::SerializeStruct;
+use time::{Duration as TimeDuration, SteadyTime};
 
 use message::swim::Rumor_Type;
 use error::{Result, Error};
@@ -87,6 +96,12 @@ pub trait Rumor: Serialize + Sized {
     fn id(&self) -> &str;
     fn merge(&mut self, other: Self) -> bool;
     fn write_to_bytes(&self) -> Result<Vec<u8>>;
+
+    /// The version of this rumor as compared by anti-entropy digests. Rumors that carry no
+    /// version of their own report 0, and are only ever fetched when missing.
+    fn incarnation(&self) -> u64 {
+        0
+    }
 }
 
 impl<'a, T: Rumor> From<&'a T> for RumorKey {
@@ -102,6 +117,7 @@ impl<'a, T: Rumor> From<&'a T> for RumorKey {
 #[derive(Debug, Clone)]
 pub struct RumorStore<T: Rumor> {
     pub list: Arc<RwLock<HashMap<String, HashMap<String, T>>>>,
+    tombstones: Arc<RwLock<HashMap<String, HashMap<String, (T, SteadyTime)>>>>,
     update_counter: Arc<AtomicUsize>,
 }
 
@@ -109,6 +125,7 @@ impl<T: Rumor> Default for RumorStore<T> {
     fn default() -> RumorStore<T> {
         RumorStore {
             list: Arc::new(RwLock::new(HashMap::new())),
+            tombstones: Arc::new(RwLock::new(HashMap::new())),
             update_counter: Arc::new(AtomicUsize::new(0)),
         }
     }
@@ -154,6 +171,10 @@ impl<T: Rumor> RumorStore<T> {
     pub fn clear(&self) -> usize {
         let mut list = self.list.write().expect("Rumor store lock poisoned");
         list.clear();
+        self.tombstones
+            .write()
+            .expect("Rumor store tombstone lock poisoned")
+            .clear();
         self.update_counter.swap(0, Ordering::Relaxed)
     }
 
@@ -179,7 +200,14 @@ impl<T: Rumor> RumorStore<T> {
 
     /// Insert a rumor into the Rumor Store. Returns true if the value didn't exist or if it was
     /// mutated; if nothing changed, returns false.
+    ///
+    /// A rumor that has been tombstoned is only accepted again if it is newer than the rumor we
+    /// tombstoned.
     pub fn insert(&self, rumor: T) -> bool {
+        let rumor = match self.check_tombstone(rumor) {
+            Some(rumor) => rumor,
+            None => return false,
+        };
         let mut list = self.list.write().expect("Rumor store lock poisoned");
         let rumors = list.entry(String::from(rumor.key())).or_insert(
             HashMap::new(),
@@ -203,6 +231,131 @@ impl<T: Rumor> RumorStore<T> {
         list.get_mut(key).and_then(|r| r.remove(id));
     }
 
+    /// Removes a rumor from the store, leaving a tombstone dated `now` in its place. Returns true
+    /// if the rumor existed.
+    pub fn tombstone(&self, key: &str, id: &str, now: SteadyTime) -> bool {
+        let mut list = self.list.write().expect("Rumor store lock poisoned");
+        let rumor = match list.get_mut(key).and_then(|r| r.remove(id)) {
+            Some(rumor) => rumor,
+            None => return false,
+        };
+        if list.get(key).map_or(false, |r| r.is_empty()) {
+            list.remove(key);
+        }
+        self.tombstones
+            .write()
+            .expect("Rumor store tombstone lock poisoned")
+            .entry(String::from(key))
+            .or_insert(HashMap::new())
+            .insert(String::from(id), (rumor, now));
+        self.increment_update_counter();
+        true
+    }
+
+    /// Tombstones every rumor for the given key, returning how many there were.
+    pub fn tombstone_key(&self, key: &str, now: SteadyTime) -> usize {
+        let mut ids: Vec<String> = Vec::new();
+        self.with_rumors(key, |rumor| ids.push(String::from(rumor.id())));
+        ids.iter().filter(|id| self.tombstone(key, id, now)).count()
+    }
+
+    /// Forgets every tombstone that is older than `timeout` at `now`, after which the rumors they
+    /// stood for may be gossiped back into the store.
+    pub fn expire_tombstones(&self, timeout: TimeDuration, now: SteadyTime) {
+        let mut tombstones = self.tombstones.write().expect(
+            "Rumor store tombstone lock poisoned",
+        );
+        for rumors in tombstones.values_mut() {
+            rumors.retain(|_, &mut (_, since)| now < since + timeout);
+        }
+        tombstones.retain(|_, rumors| !rumors.is_empty());
+    }
+
+    /// Leaves a tombstone dated `since` for a rumor that isn't in the store, as read back from the
+    /// rumor file. A rumor which is in the store is newer than the tombstone, and is kept.
+    pub fn insert_tombstone(&self, rumor: T, since: SteadyTime) {
+        let list = self.list.read().expect("Rumor store lock poisoned");
+        if list.get(rumor.key()).map_or(false, |r| r.contains_key(rumor.id())) {
+            return;
+        }
+        self.tombstones
+            .write()
+            .expect("Rumor store tombstone lock poisoned")
+            .entry(String::from(rumor.key()))
+            .or_insert(HashMap::new())
+            .insert(String::from(rumor.id()), (rumor, since));
+    }
+
+    /// Calls `with_closure` with every tombstoned rumor and how long ago at `now` it was
+    /// tombstoned.
+    pub fn with_tombstones<F>(&self, now: SteadyTime, mut with_closure: F)
+    where
+        F: FnMut(&T, TimeDuration),
+    {
+        let tombstones = self.tombstones.read().expect(
+            "Rumor store tombstone lock poisoned",
+        );
+        for &(ref rumor, since) in tombstones.values().flat_map(|r| r.values()) {
+            with_closure(rumor, now - since);
+        }
+    }
+
+    /// Forgets the tombstones of every rumor with the given id, so that a member which comes back
+    /// to life may gossip its rumors again.
+    pub fn forget_tombstones_of(&self, id: &str) {
+        let mut tombstones = self.tombstones.write().expect(
+            "Rumor store tombstone lock poisoned",
+        );
+        for rumors in tombstones.values_mut() {
+            rumors.remove(id);
+        }
+        tombstones.retain(|_, rumors| !rumors.is_empty());
+    }
+
+    /// Returns the count of tombstones in this RumorStore.
+    pub fn len_tombstones(&self) -> usize {
+        self.tombstones
+            .read()
+            .expect("Rumor store tombstone lock poisoned")
+            .values()
+            .map(|rumors| rumors.len())
+            .sum()
+    }
+
+    /// Returns the incarnation of the tombstoned rumor with the given key and id, if there is one.
+    pub fn tombstoned_incarnation(&self, key: &str, id: &str) -> Option<u64> {
+        self.tombstones
+            .read()
+            .expect("Rumor store tombstone lock poisoned")
+            .get(key)
+            .and_then(|r| r.get(id))
+            .map(|&(ref rumor, _)| rumor.incarnation())
+    }
+
+    /// Lets a rumor through unless it has been tombstoned; a rumor newer than its tombstone is
+    /// let through, and the tombstone is dropped.
+    fn check_tombstone(&self, rumor: T) -> Option<T> {
+        let key = String::from(rumor.key());
+        let id = String::from(rumor.id());
+        let mut tombstones = self.tombstones.write().expect(
+            "Rumor store tombstone lock poisoned",
+        );
+        let newer = match tombstones.get_mut(&key).and_then(|r| r.get_mut(&id)) {
+            Some(tombstone) => tombstone.0.merge(rumor),
+            None => return Some(rumor),
+        };
+        if !newer {
+            return None;
+        }
+        let revived = tombstones.get_mut(&key).and_then(|r| r.remove(&id)).map(
+            |(rumor, _)| rumor,
+        );
+        if tombstones.get(&key).map_or(false, |r| r.is_empty()) {
+            tombstones.remove(&key);
+        }
+        revived
+    }
+
     pub fn with_keys<F>(&self, mut with_closure: F)
     where
         F: FnMut((&String, &HashMap<String, T>)),
@@ -352,6 +505,8 @@ mod tests {
     }
 
     mod rumor_store {
+        use time::{Duration, SteadyTime};
+
         use super::FakeRumor;
         use rumor::RumorStore;
         use rumor::Rumor;
@@ -454,5 +609,84 @@ mod tests {
             let rs = create_rumor_store();
             rs.with_rumor("bar", "foo", |o| assert!(o.is_none()));
         }
+
+        #[test]
+        fn tombstone_removes_the_rumor_and_blocks_it_coming_back() {
+            let rs = create_rumor_store();
+            let f1 = FakeRumor::default();
+            let f2 = f1.clone();
+            let key = f1.key.clone();
+            let id = f1.id.clone();
+            rs.insert(f1);
+            assert!(rs.tombstone(&key, &id, SteadyTime::now()));
+            assert_eq!(rs.len(), 0);
+            assert_eq!(rs.len_tombstones(), 1);
+            assert!(!rs.contains_rumor(&key, &id));
+            assert_eq!(rs.insert(f2), false);
+            assert_eq!(rs.len(), 0);
+        }
+
+        #[test]
+        fn tombstone_returns_false_if_rumor_missing() {
+            let rs = create_rumor_store();
+            assert_eq!(rs.tombstone("bar", "foo", SteadyTime::now()), false);
+            assert_eq!(rs.len_tombstones(), 0);
+        }
+
+        #[test]
+        fn tombstone_key_tombstones_every_rumor_for_the_key() {
+            let rs = create_rumor_store();
+            let f1 = FakeRumor::default();
+            let key = f1.key.clone();
+            rs.insert(f1);
+            rs.insert(FakeRumor::default());
+            assert_eq!(rs.tombstone_key(&key, SteadyTime::now()), 2);
+            assert_eq!(rs.len_for_key(&key), 0);
+            assert_eq!(rs.len_tombstones(), 2);
+        }
+
+        #[test]
+        fn expired_tombstones_no_longer_block_rumors() {
+            let rs = create_rumor_store();
+            let f1 = FakeRumor::default();
+            let f2 = f1.clone();
+            let key = f1.key.clone();
+            let id = f1.id.clone();
+            rs.insert(f1);
+            let now = SteadyTime::now();
+            rs.tombstone(&key, &id, now);
+            rs.expire_tombstones(Duration::hours(1), now + Duration::minutes(59));
+            assert_eq!(rs.len_tombstones(), 1);
+            rs.expire_tombstones(Duration::hours(1), now + Duration::hours(1));
+            assert_eq!(rs.len_tombstones(), 0);
+            assert!(rs.insert(f2));
+        }
+
+        #[test]
+        fn insert_tombstone_restores_a_tombstone_with_its_age() {
+            let rs = create_rumor_store();
+            let f1 = FakeRumor::default();
+            let f2 = f1.clone();
+            let now = SteadyTime::now();
+            rs.insert_tombstone(f1, now - Duration::minutes(5));
+            let mut ages = Vec::new();
+            rs.with_tombstones(now, |_, age| ages.push(age));
+            assert_eq!(ages, vec![Duration::minutes(5)]);
+            assert_eq!(rs.insert(f2), false);
+        }
+
+        #[test]
+        fn forget_tombstones_of_lets_a_member_gossip_its_rumors_again() {
+            let rs = create_rumor_store();
+            let f1 = FakeRumor::default();
+            let f2 = f1.clone();
+            let key = f1.key.clone();
+            let id = f1.id.clone();
+            rs.insert(f1);
+            rs.tombstone(&key, &id, SteadyTime::now());
+            rs.forget_tombstones_of(&id);
+            assert_eq!(rs.len_tombstones(), 0);
+            assert!(rs.insert(f2));
+        }
     }
 }
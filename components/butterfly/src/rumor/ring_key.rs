@@ -0,0 +1,217 @@
+// Copyright (c) 2017 Chef Software Inc. and/or applicable contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The RingKey rumor.
+//!
+//! Spreads a new revision of the ring key. Members start accepting messages encrypted with the
+//! new revision as soon as they hear about it, switch to sending with it at `activate_at`, and
+//! keep accepting the revision it replaced for `grace_period` seconds after that.
+//!
+//! The rumor carries secret key material, so it only ever travels inside the (encrypted) wire
+//! protocol. It is never written to the rumor file, and the key is left out when rumors are
+//! serialized.
+
+use std::cmp::Ordering;
+use std::ops::{Deref, DerefMut};
+
+use protobuf::{self, Message};
+
+use error::Result;
+use message::swim::{RingKey as ProtoRingKey, Rumor as ProtoRumor, Rumor_Type as ProtoRumor_Type};
+use rumor::Rumor;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RingKey(ProtoRumor);
+
+impl PartialOrd for RingKey {
+    fn partial_cmp(&self, other: &RingKey) -> Option<Ordering> {
+        if self.get_name() != other.get_name() {
+            None
+        } else {
+            Some(self.get_revision().cmp(&other.get_revision()))
+        }
+    }
+}
+
+impl PartialEq for RingKey {
+    fn eq(&self, other: &RingKey) -> bool {
+        self.get_name() == other.get_name() && self.get_revision() == other.get_revision()
+    }
+}
+
+impl From<ProtoRumor> for RingKey {
+    fn from(pr: ProtoRumor) -> RingKey {
+        RingKey(pr)
+    }
+}
+
+impl From<RingKey> for ProtoRumor {
+    fn from(ring_key: RingKey) -> ProtoRumor {
+        ring_key.0
+    }
+}
+
+impl Deref for RingKey {
+    type Target = ProtoRingKey;
+
+    fn deref(&self) -> &ProtoRingKey {
+        self.0.get_ring_key()
+    }
+}
+
+impl DerefMut for RingKey {
+    fn deref_mut(&mut self) -> &mut ProtoRingKey {
+        self.0.mut_ring_key()
+    }
+}
+
+impl RingKey {
+    /// Creates a new RingKey rumor. `key` is the content of the secret key file for the new
+    /// revision; `activate_at` is a unix timestamp, and `grace_period` a number of seconds.
+    pub fn new<S1, S2, S3>(
+        member_id: S1,
+        name: S2,
+        revision: S3,
+        key: Vec<u8>,
+        activate_at: i64,
+        grace_period: i64,
+    ) -> Self
+    where
+        S1: Into<String>,
+        S2: Into<String>,
+        S3: Into<String>,
+    {
+        let mut rumor = ProtoRumor::new();
+        rumor.set_from_id(member_id.into());
+        rumor.set_field_type(ProtoRumor_Type::RingKey);
+
+        let mut proto = ProtoRingKey::new();
+        proto.set_name(name.into());
+        proto.set_revision(revision.into());
+        proto.set_key(key);
+        proto.set_activate_at(activate_at);
+        proto.set_grace_period(grace_period);
+        rumor.set_ring_key(proto);
+        RingKey(rumor)
+    }
+
+    /// The name and revision of the key, as used by `SymKey::name_with_rev`.
+    pub fn name_with_rev(&self) -> String {
+        format!("{}-{}", self.get_name(), self.get_revision())
+    }
+}
+
+impl Rumor for RingKey {
+    fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let rumor = protobuf::parse_from_bytes::<ProtoRumor>(bytes)?;
+        Ok(RingKey::from(rumor))
+    }
+
+    /// The newer revision wins.
+    fn merge(&mut self, other: RingKey) -> bool {
+        if *self >= other {
+            false
+        } else {
+            *self = other;
+            true
+        }
+    }
+
+    fn kind(&self) -> ProtoRumor_Type {
+        ProtoRumor_Type::RingKey
+    }
+
+    fn id(&self) -> &str {
+        "ring_key"
+    }
+
+    fn key(&self) -> &str {
+        self.get_name()
+    }
+
+    /// Revisions are timestamps, so a newer revision compares as a larger number.
+    fn incarnation(&self) -> u64 {
+        self.get_revision().parse().unwrap_or(0)
+    }
+
+    fn write_to_bytes(&self) -> Result<Vec<u8>> {
+        Ok(self.0.write_to_bytes()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Ordering;
+
+    use super::RingKey;
+    use rumor::Rumor;
+
+    fn create_ring_key(revision: &str) -> RingKey {
+        RingKey::new(
+            "butterflyclient",
+            "wolverine",
+            revision,
+            b"SYM-SEC-1".to_vec(),
+            1500000000,
+            300,
+        )
+    }
+
+    #[test]
+    fn identical_ring_keys_are_equal() {
+        let s1 = create_ring_key("20170101000000");
+        let s2 = create_ring_key("20170101000000");
+        assert_eq!(s1, s2);
+    }
+
+    #[test]
+    fn ring_keys_are_ordered_by_revision() {
+        let s1 = create_ring_key("20170101000000");
+        let s2 = create_ring_key("20170201000000");
+        assert_eq!(s1.partial_cmp(&s2), Some(Ordering::Less));
+        assert_eq!(s2.partial_cmp(&s1), Some(Ordering::Greater));
+    }
+
+    #[test]
+    fn merge_chooses_the_newer_revision() {
+        let mut s1 = create_ring_key("20170101000000");
+        let s2 = create_ring_key("20170201000000");
+        let s2_check = s2.clone();
+        assert_eq!(s1.merge(s2), true);
+        assert_eq!(s1, s2_check);
+    }
+
+    #[test]
+    fn merge_returns_false_if_nothing_changed() {
+        let mut s1 = create_ring_key("20170201000000");
+        let s1_check = s1.clone();
+        let s2 = create_ring_key("20170101000000");
+        assert_eq!(s1.merge(s2), false);
+        assert_eq!(s1, s1_check);
+    }
+
+    #[test]
+    fn incarnation_is_the_revision() {
+        let s1 = create_ring_key("20170101000000");
+        let s2 = create_ring_key("20170201000000");
+        assert_eq!(s1.incarnation(), 20170101000000);
+        assert!(s1.incarnation() < s2.incarnation());
+    }
+
+    #[test]
+    fn name_with_rev_matches_sym_key_naming() {
+        let s1 = create_ring_key("20170101000000");
+        assert_eq!(s1.name_with_rev(), "wolverine-20170101000000");
+    }
+}
@@ -155,6 +155,10 @@ impl Rumor for ServiceFile {
         self.get_service_group()
     }
 
+    fn incarnation(&self) -> u64 {
+        self.get_incarnation()
+    }
+
     fn write_to_bytes(&self) -> Result<Vec<u8>> {
         Ok(self.0.write_to_bytes()?)
     }
@@ -0,0 +1,382 @@
+// Copyright (c) 2017 Chef Software Inc. and/or applicable contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The anti-entropy thread.
+//!
+//! Hot rumors are only gossiped for a few rounds, so a member that joins the ring late (or that
+//! was cut off while a rumor was hot) would otherwise never learn about rumors that have already
+//! cooled. To catch up, a member sends a `DigestRequest` to a random alive peer, which answers
+//! with a `Digest` listing the key, id and incarnation of every rumor it holds. The member then
+//! sends back a `DigestFetch` naming the rumors it is missing, or holds an older version of, and
+//! the peer pushes exactly those.
+//!
+//! A sync is done as soon as the server starts, whenever we have to refute a suspicion of
+//! ourselves, and every `Timing::anti_entropy_period_ms` regardless. Only peers which set
+//! `digest_sync` on their membership are asked; older members can't parse these rumors.
+//!
+//! Answering a `DigestFetch` can mean pushing a lot of rumors, so fetches are queued for the
+//! anti-entropy thread, which answers them once per gossip period. When the queue is full further
+//! fetches are dropped, and the member that sent them catches up on its next periodic sync instead.
+
+use std::sync::atomic::Ordering;
+use std::sync::mpsc::{Receiver, SyncSender, TrySendError};
+
+use protobuf::RepeatedField;
+
+use member::{Health, Member};
+use message::swim::{Digest as ProtoDigest, Rumor as ProtoRumor, Rumor_Type as ProtoRumor_Type,
+                    RumorDigest as ProtoRumorDigest};
+use rumor::{Rumor, RumorKey, RumorStore};
+use server::Server;
+use server::push::PushWorker;
+use server::timing::Timing;
+
+/// How many `DigestFetch`es may wait to be answered before further ones are dropped.
+pub const FETCH_QUEUE_DEPTH: usize = 4;
+
+/// A `DigestFetch` waiting to be answered: the member to push to, and the rumors it asked for.
+pub type Fetch = (Member, Vec<RumorKey>);
+
+/// The anti-entropy server
+#[derive(Debug)]
+pub struct AntiEntropy {
+    pub server: Server,
+    pub timing: Timing,
+    fetches: Receiver<Fetch>,
+}
+
+impl AntiEntropy {
+    /// Creates a new AntiEntropy instance from a Server, Timing, and the queue of fetches to answer
+    pub fn new(server: Server, timing: Timing, fetches: Receiver<Fetch>) -> AntiEntropy {
+        AntiEntropy {
+            server: server,
+            timing: timing,
+            fetches: fetches,
+        }
+    }
+
+    /// Run the anti-entropy thread. Once every gossip period, answer the queued fetches and, if a
+    /// sync is due, ask a random alive member for its digest. The due flag is only cleared once that
+    /// member's digest arrives, so a request that gets lost is retried on the next period.
+    pub fn run(&self) {
+        let mut last_request = self.timing.now();
+        loop {
+            if self.server.pause.load(Ordering::Relaxed) {
+                self.server.sleep_while_paused();
+                continue;
+            }
+            let due = self.server.anti_entropy_due.load(Ordering::SeqCst) ||
+                self.timing.now() >= last_request + self.timing.anti_entropy_period_duration();
+            if due {
+                if let Some(member) = self.peer() {
+                    debug!("Requesting a rumor digest from {}", member.get_id());
+                    last_request = self.timing.now();
+                    *self.server.anti_entropy_peer.write().expect(
+                        "Anti-entropy peer lock is poisoned",
+                    ) = Some(String::from(member.get_id()));
+                    let rumor = digest_rumor(&self.server, ProtoRumor_Type::DigestRequest, None);
+                    PushWorker::new(self.server.clone()).send_rumor(member, rumor);
+                }
+            }
+            while let Ok((member, rumors)) = self.fetches.try_recv() {
+                PushWorker::new(self.server.clone()).send_rumors(member, rumors);
+            }
+            self.timing.sleep_ms(self.timing.gossip_period_ms);
+        }
+    }
+
+    /// A random alive member which understands digests to sync with, if there is one.
+    fn peer(&self) -> Option<Member> {
+        self.server
+            .member_list
+            .check_list(self.server.member_id())
+            .into_iter()
+            .find(|member| {
+                member.get_digest_sync() && !self.server.check_blacklist(member.get_id()) &&
+                    self.server.member_list.check_health_of(
+                        member,
+                        Health::Alive,
+                    )
+            })
+    }
+}
+
+/// Answer a `DigestRequest` from `from_id` with a digest of everything we know.
+pub fn process_digest_request(server: &Server, from_id: &str) {
+    match member_for(server, from_id) {
+        Some(member) => {
+            let rumor = digest_rumor(server, ProtoRumor_Type::Digest, Some(digest(server)));
+            PushWorker::new(server.clone()).send_rumor(member, rumor);
+        }
+        None => debug!("Not answering digest request from unknown member {}", from_id),
+    }
+}
+
+/// Compare the `Digest` from `from_id` against our own rumors, and fetch any that we are missing
+/// or hold an older version of. Only the digest of the member we last asked completes a sync;
+/// a late answer from an earlier request is still used, but leaves the sync due.
+pub fn process_digest(server: &Server, from_id: &str, digest: &ProtoDigest) {
+    {
+        let mut peer = server.anti_entropy_peer.write().expect(
+            "Anti-entropy peer lock is poisoned",
+        );
+        if peer.as_ref().map_or(false, |id| id == from_id) {
+            *peer = None;
+            server.anti_entropy_due.store(false, Ordering::SeqCst);
+        }
+    }
+    let fetch = wanted(server, digest);
+    if fetch.get_rumors().is_empty() {
+        debug!("Rumors are in sync with {}", from_id);
+        return;
+    }
+    match member_for(server, from_id) {
+        Some(member) => {
+            debug!(
+                "Fetching {} rumors from {}",
+                fetch.get_rumors().len(),
+                from_id
+            );
+            let rumor = digest_rumor(server, ProtoRumor_Type::DigestFetch, Some(fetch));
+            PushWorker::new(server.clone()).send_rumor(member, rumor);
+        }
+        None => debug!("Not fetching rumors from unknown member {}", from_id),
+    }
+}
+
+/// Queue the rumors `from_id` asked for in a `DigestFetch`, for the anti-entropy thread to push.
+pub fn process_digest_fetch(
+    server: &Server,
+    fetches: &SyncSender<Fetch>,
+    from_id: &str,
+    fetch: &ProtoDigest,
+) {
+    let member = match member_for(server, from_id) {
+        Some(member) => member,
+        None => {
+            debug!("Not sending rumors to unknown member {}", from_id);
+            return;
+        }
+    };
+    let rumors: Vec<RumorKey> = fetch
+        .get_rumors()
+        .iter()
+        .filter(|entry| digested(entry.get_kind()))
+        .map(|entry| {
+            RumorKey::new(entry.get_kind(), entry.get_id(), entry.get_key())
+        })
+        .collect();
+    match fetches.try_send((member, rumors)) {
+        Ok(()) => (),
+        Err(TrySendError::Full(_)) => {
+            debug!("Too many digest fetches queued; dropping the one from {}", from_id)
+        }
+        Err(TrySendError::Disconnected(_)) => {
+            error!("Anti-entropy thread is gone; dropping the fetch from {}", from_id)
+        }
+    }
+}
+
+/// Returns a digest of every rumor we would share with a member that is catching up. Members are
+/// left out, as the failure detector already spreads them. Ring keys are included, so that a
+/// member which missed a rotation while it was hot still learns the new revision before it
+/// activates.
+pub fn digest(server: &Server) -> ProtoDigest {
+    let mut rumors = Vec::new();
+    digest_store(&server.service_store, &mut rumors);
+    digest_store(&server.service_config_store, &mut rumors);
+    digest_store(&server.service_file_store, &mut rumors);
+    digest_store(&server.election_store, &mut rumors);
+    digest_store(&server.update_store, &mut rumors);
+    digest_store(&server.departure_store, &mut rumors);
+    digest_store(&server.canary_store, &mut rumors);
+    digest_store(&server.ring_key_store, &mut rumors);
+    let mut digest = ProtoDigest::new();
+    digest.set_rumors(RepeatedField::from_vec(rumors));
+    digest
+}
+
+/// Returns the entries of a remote digest that we should fetch.
+pub fn wanted(server: &Server, digest: &ProtoDigest) -> ProtoDigest {
+    let rumors = digest
+        .get_rumors()
+        .iter()
+        .filter(|entry| match entry.get_kind() {
+            ProtoRumor_Type::Service => wanted_from(&server.service_store, entry),
+            ProtoRumor_Type::ServiceConfig => wanted_from(&server.service_config_store, entry),
+            ProtoRumor_Type::ServiceFile => wanted_from(&server.service_file_store, entry),
+            ProtoRumor_Type::Election => wanted_from(&server.election_store, entry),
+            ProtoRumor_Type::ElectionUpdate => wanted_from(&server.update_store, entry),
+            ProtoRumor_Type::Departure => wanted_from(&server.departure_store, entry),
+            ProtoRumor_Type::Canary => wanted_from(&server.canary_store, entry),
+            ProtoRumor_Type::RingKey => wanted_from(&server.ring_key_store, entry),
+            _ => false,
+        })
+        .cloned()
+        .collect();
+    let mut wanted = ProtoDigest::new();
+    wanted.set_rumors(RepeatedField::from_vec(rumors));
+    wanted
+}
+
+/// Whether rumors of this kind are included in digests, and so may be fetched.
+fn digested(kind: ProtoRumor_Type) -> bool {
+    match kind {
+        ProtoRumor_Type::Service |
+        ProtoRumor_Type::ServiceConfig |
+        ProtoRumor_Type::ServiceFile |
+        ProtoRumor_Type::Election |
+        ProtoRumor_Type::ElectionUpdate |
+        ProtoRumor_Type::Departure |
+        ProtoRumor_Type::Canary |
+        ProtoRumor_Type::RingKey => true,
+        _ => false,
+    }
+}
+
+fn digest_store<T: Rumor>(store: &RumorStore<T>, digests: &mut Vec<ProtoRumorDigest>) {
+    store.with_keys(|(key, rumors)| for rumor in rumors.values() {
+        let mut entry = ProtoRumorDigest::new();
+        entry.set_kind(rumor.kind());
+        entry.set_key(key.clone());
+        entry.set_id(String::from(rumor.id()));
+        entry.set_incarnation(rumor.incarnation());
+        digests.push(entry);
+    });
+}
+
+/// A rumor is wanted if we don't have it, or if ours is older than the one in the digest. A
+/// rumor we tombstoned is only wanted if the digest has a newer one, as the store would refuse
+/// anything else; peers which haven't expired it yet would otherwise have us fetch it forever.
+fn wanted_from<T: Rumor>(store: &RumorStore<T>, entry: &ProtoRumorDigest) -> bool {
+    if let Some(incarnation) = store.tombstoned_incarnation(entry.get_key(), entry.get_id()) {
+        return incarnation < entry.get_incarnation();
+    }
+    let mut wanted = true;
+    store.with_rumor(entry.get_key(), entry.get_id(), |rumor| if let Some(rumor) = rumor {
+        wanted = rumor.incarnation() < entry.get_incarnation();
+    });
+    wanted
+}
+
+fn member_for(server: &Server, member_id: &str) -> Option<Member> {
+    let mut found = None;
+    server.member_list.with_member(
+        member_id,
+        |member| found = member.cloned(),
+    );
+    found
+}
+
+fn digest_rumor(
+    server: &Server,
+    kind: ProtoRumor_Type,
+    digest: Option<ProtoDigest>,
+) -> ProtoRumor {
+    let mut rumor = ProtoRumor::new();
+    rumor.set_from_id(String::from(server.member_id()));
+    rumor.set_field_type(kind);
+    if let Some(digest) = digest {
+        rumor.set_digest(digest);
+    }
+    rumor
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use habitat_core::service::ServiceGroup;
+    use time::SteadyTime;
+
+    use message::swim::{Rumor_Type as ProtoRumor_Type, RumorDigest as ProtoRumorDigest};
+    use rumor::RumorStore;
+    use rumor::ring_key::RingKey;
+    use rumor::service_config::ServiceConfig;
+    use super::{digest_store, wanted_from};
+
+    fn service_config(service_group: &str, incarnation: u64) -> ServiceConfig {
+        let sg = ServiceGroup::from_str(service_group).unwrap();
+        let mut config = ServiceConfig::new("adam", sg, vec![]);
+        config.set_incarnation(incarnation);
+        config
+    }
+
+    #[test]
+    fn digest_store_lists_every_rumor_with_its_incarnation() {
+        let store = RumorStore::default();
+        store.insert(service_config("neurosis.production", 3));
+        store.insert(service_config("tool.production", 1));
+        let mut digests = Vec::new();
+        digest_store(&store, &mut digests);
+        digests.sort_by(|a, b| a.get_key().cmp(b.get_key()));
+        assert_eq!(digests.len(), 2);
+        assert_eq!(digests[0].get_key(), "neurosis.production");
+        assert_eq!(digests[0].get_id(), "service_config");
+        assert_eq!(digests[0].get_incarnation(), 3);
+        assert_eq!(digests[1].get_key(), "tool.production");
+        assert_eq!(digests[1].get_incarnation(), 1);
+    }
+
+    #[test]
+    fn wanted_from_fetches_missing_and_newer_rumors_only() {
+        let store = RumorStore::default();
+        store.insert(service_config("neurosis.production", 3));
+        let mut entry = ProtoRumorDigest::new();
+        entry.set_key(String::from("neurosis.production"));
+        entry.set_id(String::from("service_config"));
+        entry.set_incarnation(3);
+        assert!(!wanted_from(&store, &entry));
+        entry.set_incarnation(2);
+        assert!(!wanted_from(&store, &entry));
+        entry.set_incarnation(4);
+        assert!(wanted_from(&store, &entry));
+        entry.set_key(String::from("tool.production"));
+        entry.set_incarnation(0);
+        assert!(wanted_from(&store, &entry));
+    }
+
+    #[test]
+    fn wanted_from_skips_tombstoned_rumors_unless_newer() {
+        let store = RumorStore::default();
+        store.insert(service_config("neurosis.production", 3));
+        store.tombstone("neurosis.production", "service_config", SteadyTime::now());
+        let mut entry = ProtoRumorDigest::new();
+        entry.set_key(String::from("neurosis.production"));
+        entry.set_id(String::from("service_config"));
+        entry.set_incarnation(3);
+        assert!(!wanted_from(&store, &entry));
+        entry.set_incarnation(4);
+        assert!(wanted_from(&store, &entry));
+    }
+
+    #[test]
+    fn digest_store_lists_ring_keys() {
+        let store = RumorStore::default();
+        store.insert(RingKey::new(
+            "adam",
+            "wolverine",
+            "20170201000000",
+            b"SYM-SEC-1".to_vec(),
+            0,
+            0,
+        ));
+        let mut digests = Vec::new();
+        digest_store(&store, &mut digests);
+        assert_eq!(digests.len(), 1);
+        assert_eq!(digests[0].get_kind(), ProtoRumor_Type::RingKey);
+        assert_eq!(digests[0].get_key(), "wolverine");
+        assert_eq!(digests[0].get_incarnation(), 20170201000000);
+    }
+}
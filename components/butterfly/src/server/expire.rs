@@ -15,12 +15,9 @@
 //! Expire suspected members.
 //!
 //! This module keeps track of suspected members, and sets their status to confirmed if they remain
-//! suspect long enough.
-
-use std::thread;
-use std::time::Duration;
-
-use time::SteadyTime;
+//! suspect long enough. How long is long enough depends on how many other members have confirmed
+//! the suspicion. It also expires the rumors of members that have been confirmed or departed for
+//! longer than the rumor TTL.
 
 use message::swim::Rumor_Type;
 use member::Health;
@@ -47,9 +44,12 @@ impl Expire {
     pub fn run(&self) {
         loop {
             let mut expired_list: Vec<String> = Vec::new();
-            self.server.member_list.with_suspects(|(id, suspect)| {
-                let now = SteadyTime::now();
-                if now >= *suspect + self.timing.suspicion_timeout_duration() {
+            self.server.member_list.with_suspects(|(id, suspicion)| {
+                let now = self.timing.now();
+                let timeout = self.timing.suspicion_timeout_duration(
+                    suspicion.confirmations(),
+                );
+                if now >= suspicion.started + timeout {
                     expired_list.push(String::from(id));
                     self.server.member_list.insert_health_by_id(
                         id,
@@ -78,7 +78,7 @@ impl Expire {
             let mut departed_list: Vec<String> = Vec::new();
             self.server.member_list.with_departures(
                 |(id, departure_time)| {
-                    let now = SteadyTime::now();
+                    let now = self.timing.now();
                     if now >= *departure_time + self.timing.departure_timeout_duration() {
                         departed_list.push(String::from(id));
                         self.server.member_list.insert_health_by_id(
@@ -106,7 +106,9 @@ impl Expire {
                 ));
             }
 
-            thread::sleep(Duration::from_millis(500));
+            self.server.expire_rumors(self.timing.rumor_ttl_duration());
+
+            self.timing.sleep_ms(500);
         }
     }
 }
@@ -16,11 +16,11 @@
 //!
 //! This module handles all the inbound SWIM messages.
 
+use std::io;
 use std::sync::mpsc;
 use std::sync::atomic::Ordering;
-use std::net::{SocketAddr, UdpSocket};
-use std::thread;
-use std::time::Duration;
+use std::net::SocketAddr;
+use std::sync::Arc;
 
 use protobuf;
 
@@ -28,11 +28,12 @@ use member::{Member, Health};
 use message::swim::{Swim, Swim_Type};
 use server::{Server, outbound};
 use trace::TraceKind;
+use transport::SwimSocket;
 
 /// Takes the Server and a channel to send received Acks to the outbound thread.
 pub struct Inbound {
     pub server: Server,
-    pub socket: UdpSocket,
+    pub socket: Arc<SwimSocket>,
     pub tx_outbound: mpsc::Sender<(SocketAddr, Swim)>,
 }
 
@@ -40,7 +41,7 @@ impl Inbound {
     /// Create a new Inbound.
     pub fn new(
         server: Server,
-        socket: UdpSocket,
+        socket: Arc<SwimSocket>,
         tx_outbound: mpsc::Sender<(SocketAddr, Swim)>,
     ) -> Inbound {
         Inbound {
@@ -55,7 +56,7 @@ impl Inbound {
         let mut recv_buffer: Vec<u8> = vec![0; 1024];
         loop {
             if self.server.pause.load(Ordering::Relaxed) {
-                thread::sleep(Duration::from_millis(100));
+                self.server.sleep_while_paused();
                 continue;
             }
             match self.socket.recv_from(&mut recv_buffer[..]) {
@@ -122,6 +123,10 @@ impl Inbound {
                         }
                     }
                 }
+                Err(ref e) if e.kind() == io::ErrorKind::WouldBlock ||
+                                  e.kind() == io::ErrorKind::TimedOut => {
+                    // Nothing arrived before the socket's read timeout
+                }
                 Err(e) => {
                     match e.raw_os_error() {
                         Some(35) | Some(11) | Some(10035) | Some(10060) => {
@@ -210,29 +215,28 @@ impl Inbound {
                 return;
             }
         }
-        let membership = {
-            let membership: Vec<(Member, Health)> = msg.take_membership()
-                .iter()
-                .map(|m| {
-                    (Member::from(m.get_member()), Health::from(m.get_health()))
-                })
-                .collect();
-            membership
-        };
+        let membership = msg.take_membership();
         match self.tx_outbound.send((addr, msg)) {
             Ok(()) => {}
             Err(e) => panic!("Outbound thread has died - this shouldn't happen: #{:?}", e),
         }
-        self.server.insert_member_from_rumors(membership);
+        self.server.insert_member_from_rumors(&membership);
     }
 
     /// Process ping messages.
+    ///
+    /// The membership carried by the ping is processed before we ack. If the pinger suspects us,
+    /// it sends us our own suspect membership (the buddy system); refuting it first means the ack
+    /// already carries our new incarnation, and clears the suspicion a whole protocol period
+    /// sooner.
     fn process_ping(&self, addr: SocketAddr, mut msg: Swim) {
         trace_it!(SWIM: &self.server,
                   TraceKind::RecvPing,
                   msg.get_ping().get_from().get_id(),
                   addr,
                   &msg);
+        let membership = msg.take_membership();
+        self.server.insert_member_from_rumors(&membership);
         let target: Member = msg.get_ping().get_from().into();
         if msg.get_ping().has_forward_to() {
             outbound::ack(
@@ -258,12 +262,5 @@ impl Inbound {
         } else {
             self.server.insert_member(from.into(), Health::Alive);
         }
-        let membership: Vec<(Member, Health)> = msg.take_membership()
-            .iter()
-            .map(|m| {
-                (Member::from(m.get_member()), Health::from(m.get_health()))
-            })
-            .collect();
-        self.server.insert_member_from_rumors(membership);
     }
 }
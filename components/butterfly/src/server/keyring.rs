@@ -0,0 +1,346 @@
+// Copyright (c) 2017 Chef Software Inc. and/or applicable contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The ring keys a server encrypts and decrypts with.
+//!
+//! A ring key rotation moves through three revisions: the `current` one we send with, a `pending`
+//! one we already accept but only start sending with at its activation time, and the `previous`
+//! one, which we keep accepting until its grace period runs out.
+//!
+//! A revision which arrives in a rotation is written to the key cache straight away, so a
+//! schedule file is written next to it recording when it activates. Revisions without one were
+//! generated or imported by hand, and are active as soon as they exist.
+
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+use habitat_core::crypto::SymKey;
+
+use error::{Error, Result};
+
+#[derive(Debug)]
+pub struct Keyring {
+    current: Option<SymKey>,
+    /// The next revision, with the unix time it activates at and its grace period in seconds.
+    pending: Option<(SymKey, i64, i64)>,
+    /// The revision we rotated away from, with the unix time we stop accepting it.
+    previous: Option<(SymKey, i64)>,
+    /// Where we write new revisions of the key, so that a restart picks them up.
+    pub cache: Option<PathBuf>,
+}
+
+impl Keyring {
+    pub fn new(current: Option<SymKey>) -> Keyring {
+        Keyring {
+            current: current,
+            pending: None,
+            previous: None,
+            cache: None,
+        }
+    }
+
+    /// The key we encrypt with.
+    pub fn current(&self) -> Option<&SymKey> {
+        self.current.as_ref()
+    }
+
+    /// Returns true if `rev` is a newer revision of our ring than any key we hold.
+    pub fn is_newer(&self, name: &str, rev: &str) -> bool {
+        let current = match self.current {
+            Some(ref current) => current,
+            None => return false,
+        };
+        if current.name != name || current.rev.as_str() >= rev {
+            return false;
+        }
+        match self.pending {
+            Some((ref pending, _, _)) => pending.rev.as_str() < rev,
+            None => true,
+        }
+    }
+
+    /// Stages a new revision of the ring key, to be activated at the unix time `activate_at`.
+    /// Returns false if it isn't newer than the keys we already hold.
+    pub fn stage(&mut self, key: SymKey, activate_at: i64, grace_period: i64) -> bool {
+        if !self.is_newer(&key.name, &key.rev) {
+            return false;
+        }
+        self.pending = Some((key, activate_at, grace_period));
+        true
+    }
+
+    /// Activates the pending key if its time has come, and forgets the previous key once its
+    /// grace period is over.
+    pub fn advance(&mut self, now: i64) {
+        let due = match self.pending {
+            Some((_, activate_at, _)) => now >= activate_at,
+            None => false,
+        };
+        if due {
+            let (key, activate_at, grace_period) = self.pending.take().unwrap();
+            if let Some(current) = self.current.take() {
+                self.previous = Some((current, activate_at + grace_period));
+            }
+            self.current = Some(key);
+        }
+        let expired = match self.previous {
+            Some((_, expires_at)) => now >= expires_at,
+            None => false,
+        };
+        if expired {
+            self.previous = None;
+        }
+    }
+
+    /// Picks up a rotation which was under way when we restarted: the cached revision newer than
+    /// our current key which hasn't activated yet is staged again, and the revision we rotated
+    /// away from is accepted until its grace period is over.
+    pub fn restore(&mut self, now: i64) -> Result<()> {
+        let (name, rev) = match (self.current.as_ref(), self.cache.as_ref()) {
+            (Some(current), Some(_)) => (current.name.clone(), current.rev.clone()),
+            _ => return Ok(()),
+        };
+        let cache = self.cache.clone().unwrap();
+        let revisions = revisions(&name, &cache)?;
+        for key in revisions.iter().filter(|key| key.rev > rev) {
+            if let Some((activate_at, grace_period)) = read_schedule(&cache, &key.name_with_rev()) {
+                if activate_at > now {
+                    self.stage(key.clone(), activate_at, grace_period);
+                }
+            }
+        }
+        if let Some((activate_at, grace_period)) =
+            read_schedule(&cache, &format!("{}-{}", name, rev))
+        {
+            let expires_at = activate_at + grace_period;
+            if now < expires_at {
+                if let Some(previous) = revisions.iter().filter(|key| key.rev < rev).last() {
+                    self.previous = Some((previous.clone(), expires_at));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Every key we accept messages encrypted with.
+    pub fn accepted(&self) -> Vec<&SymKey> {
+        let mut keys = Vec::new();
+        if let Some(ref current) = self.current {
+            keys.push(current);
+        }
+        if let Some((ref pending, _, _)) = self.pending {
+            keys.push(pending);
+        }
+        if let Some((ref previous, _)) = self.previous {
+            keys.push(previous);
+        }
+        keys
+    }
+}
+
+/// Returns the newest revision of the ring key `name` in `cache` which has activated by the unix
+/// time `now`. A revision which is still waiting for its activation time isn't used, even though
+/// it's already in the cache. If no revision has activated yet, the oldest one is returned.
+///
+/// # Errors
+///
+/// * Returns `Error::HabitatCore` if there is no revision of the key in the cache
+pub fn active_ring_key<P: AsRef<Path>>(name: &str, cache: P, now: i64) -> Result<SymKey> {
+    let cache = cache.as_ref();
+    let mut revisions = revisions(name, cache)?;
+    if revisions.is_empty() {
+        return Ok(SymKey::get_latest_pair_for(name, cache)?);
+    }
+    match revisions.iter().rposition(|key| is_active(cache, key, now)) {
+        Some(index) => Ok(revisions.swap_remove(index)),
+        None => Ok(revisions.swap_remove(0)),
+    }
+}
+
+/// Records when the cached revision `key` activates, and for how long after that the revision it
+/// replaces is still accepted.
+pub fn write_schedule<P: AsRef<Path>>(
+    cache: P,
+    key: &SymKey,
+    activate_at: i64,
+    grace_period: i64,
+) -> io::Result<()> {
+    let path = schedule_path(cache.as_ref(), &key.name_with_rev());
+    let tmp = path.with_extension("activate.tmp");
+    {
+        let mut file = File::create(&tmp)?;
+        writeln!(file, "{} {}", activate_at, grace_period)?;
+    }
+    fs::rename(&tmp, &path)
+}
+
+/// Every revision of the ring key `name` in `cache`, oldest first.
+fn revisions(name: &str, cache: &Path) -> Result<Vec<SymKey>> {
+    let mut keys = SymKey::get_pairs_for(name, cache).map_err(Error::HabitatCore)?;
+    keys.sort_by(|a, b| a.rev.cmp(&b.rev));
+    Ok(keys)
+}
+
+fn is_active(cache: &Path, key: &SymKey, now: i64) -> bool {
+    match read_schedule(cache, &key.name_with_rev()) {
+        Some((activate_at, _)) => activate_at <= now,
+        None => true,
+    }
+}
+
+fn schedule_path(cache: &Path, name_with_rev: &str) -> PathBuf {
+    cache.join(format!("{}.activate", name_with_rev))
+}
+
+/// Reads the activation time and grace period of a cached revision, if it arrived in a rotation.
+fn read_schedule(cache: &Path, name_with_rev: &str) -> Option<(i64, i64)> {
+    let file = match File::open(schedule_path(cache, name_with_rev)) {
+        Ok(file) => file,
+        Err(_) => return None,
+    };
+    let mut line = String::new();
+    if BufReader::new(file).read_line(&mut line).is_err() {
+        return None;
+    }
+    let mut fields = line.split_whitespace().map(|field| field.parse::<i64>());
+    match (fields.next(), fields.next()) {
+        (Some(Ok(activate_at)), Some(Ok(grace_period))) => Some((activate_at, grace_period)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use habitat_core::crypto::SymKey;
+    use tempdir::TempDir;
+
+    use super::{active_ring_key, write_schedule, Keyring};
+
+    fn key(rev: &str) -> SymKey {
+        let mut key = SymKey::generate_pair_for_ring("wolverine").expect("Cannot generate key");
+        key.rev = rev.to_string();
+        key
+    }
+
+    #[test]
+    fn stage_rejects_older_revisions() {
+        let mut keyring = Keyring::new(Some(key("20170201000000")));
+        assert_eq!(keyring.stage(key("20170101000000"), 0, 0), false);
+        assert_eq!(keyring.stage(key("20170201000000"), 0, 0), false);
+        assert_eq!(keyring.stage(key("20170301000000"), 0, 0), true);
+    }
+
+    #[test]
+    fn stage_rejects_keys_for_other_rings() {
+        let mut keyring = Keyring::new(Some(key("20170101000000")));
+        let mut other = key("20170301000000");
+        other.name = String::from("cyclops");
+        assert_eq!(keyring.stage(other, 0, 0), false);
+    }
+
+    #[test]
+    fn stage_is_ignored_without_a_current_key() {
+        let mut keyring = Keyring::new(None);
+        assert_eq!(keyring.stage(key("20170301000000"), 0, 0), false);
+        assert!(keyring.accepted().is_empty());
+    }
+
+    #[test]
+    fn pending_keys_are_accepted_before_they_activate() {
+        let mut keyring = Keyring::new(Some(key("20170101000000")));
+        keyring.stage(key("20170201000000"), 100, 50);
+        keyring.advance(99);
+        assert_eq!(keyring.current().unwrap().rev, "20170101000000");
+        assert_eq!(keyring.accepted().len(), 2);
+    }
+
+    fn cached_key(cache: &TempDir, rev: &str) -> SymKey {
+        let key = key(rev);
+        key.to_pair_files(cache.path()).expect("Cannot write key");
+        key
+    }
+
+    #[test]
+    fn active_ring_key_skips_revisions_which_have_not_activated() {
+        let cache = TempDir::new("keyring").unwrap();
+        cached_key(&cache, "20170101000000");
+        let staged = cached_key(&cache, "20170201000000");
+        write_schedule(cache.path(), &staged, 100, 50).unwrap();
+
+        let key = active_ring_key("wolverine", cache.path(), 99).unwrap();
+        assert_eq!(key.rev, "20170101000000");
+        let key = active_ring_key("wolverine", cache.path(), 100).unwrap();
+        assert_eq!(key.rev, "20170201000000");
+    }
+
+    #[test]
+    fn active_ring_key_uses_revisions_without_a_schedule() {
+        let cache = TempDir::new("keyring").unwrap();
+        cached_key(&cache, "20170101000000");
+        cached_key(&cache, "20170201000000");
+        let key = active_ring_key("wolverine", cache.path(), 0).unwrap();
+        assert_eq!(key.rev, "20170201000000");
+    }
+
+    #[test]
+    fn restore_stages_the_pending_revision_again() {
+        let cache = TempDir::new("keyring").unwrap();
+        let current = cached_key(&cache, "20170101000000");
+        let staged = cached_key(&cache, "20170201000000");
+        write_schedule(cache.path(), &staged, 100, 50).unwrap();
+
+        let mut keyring = Keyring::new(Some(current));
+        keyring.cache = Some(cache.path().to_path_buf());
+        keyring.restore(99).unwrap();
+        assert_eq!(keyring.current().unwrap().rev, "20170101000000");
+        assert_eq!(keyring.accepted().len(), 2);
+        keyring.advance(100);
+        assert_eq!(keyring.current().unwrap().rev, "20170201000000");
+    }
+
+    #[test]
+    fn restore_accepts_the_previous_revision_for_the_rest_of_its_grace_period() {
+        let cache = TempDir::new("keyring").unwrap();
+        cached_key(&cache, "20170101000000");
+        let current = cached_key(&cache, "20170201000000");
+        write_schedule(cache.path(), &current, 100, 50).unwrap();
+
+        let mut keyring = Keyring::new(Some(current.clone()));
+        keyring.cache = Some(cache.path().to_path_buf());
+        keyring.restore(120).unwrap();
+        let revs: Vec<&str> = keyring.accepted().iter().map(|k| k.rev.as_str()).collect();
+        assert_eq!(revs, vec!["20170201000000", "20170101000000"]);
+
+        let mut keyring = Keyring::new(Some(current));
+        keyring.cache = Some(cache.path().to_path_buf());
+        keyring.restore(150).unwrap();
+        assert_eq!(keyring.accepted().len(), 1);
+    }
+
+    #[test]
+    fn advance_activates_the_pending_key_and_keeps_the_previous_for_the_grace_period() {
+        let mut keyring = Keyring::new(Some(key("20170101000000")));
+        keyring.stage(key("20170201000000"), 100, 50);
+        keyring.advance(100);
+        assert_eq!(keyring.current().unwrap().rev, "20170201000000");
+        let revs: Vec<&str> = keyring.accepted().iter().map(|k| k.rev.as_str()).collect();
+        assert_eq!(revs, vec!["20170201000000", "20170101000000"]);
+
+        keyring.advance(149);
+        assert_eq!(keyring.accepted().len(), 2);
+        keyring.advance(150);
+        assert_eq!(keyring.accepted().len(), 1);
+    }
+}
@@ -19,25 +19,27 @@
 //! protocol), expire (turning Suspect members into Confirmed members), push (the fan-out rumors),
 //! and pull (the inbound receipt of rumors.).
 
+mod anti_entropy;
 mod expire;
 mod inbound;
+pub mod keyring;
 mod outbound;
 mod pull;
 mod push;
 pub mod timing;
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::ffi;
 use std::fmt::{self, Debug};
 use std::fs;
 use std::io;
-use std::net::{ToSocketAddrs, UdpSocket, SocketAddr};
+use std::net::{ToSocketAddrs, SocketAddr};
 use std::path::PathBuf;
 use std::result;
 use std::str::FromStr;
 use std::sync::{Arc, RwLock};
-use std::sync::atomic::{AtomicBool, AtomicIsize, Ordering};
-use std::sync::mpsc::channel;
+use std::sync::atomic::{AtomicBool, AtomicIsize, AtomicUsize, Ordering};
+use std::sync::mpsc::{channel, sync_channel};
 use std::time::{Instant, Duration};
 use std::thread;
 
@@ -45,24 +47,56 @@ use habitat_core::service::ServiceGroup;
 use habitat_core::crypto::SymKey;
 use serde::{Serialize, Serializer};
 use serde::ser::SerializeStruct;
+use time::{self, Duration as TimeDuration, SteadyTime};
 
 use error::{Result, Error};
 use member::{Member, Health, MemberList};
 use message;
+use message::swim::Membership as ProtoMembership;
 use rumor::{Rumor, RumorKey, RumorStore};
 use rumor::heat::RumorHeat;
+use rumor::canary::{Canary, Canary_Status};
 use rumor::dat_file::DatFile;
 use rumor::departure::Departure;
+use rumor::ring_key::RingKey;
 use rumor::service::Service;
 use rumor::service_config::ServiceConfig;
 use rumor::service_file::ServiceFile;
-use rumor::election::{Election, ElectionUpdate};
+use rumor::election::{Election, ElectionConfig, ElectionUpdate, QuorumBasis};
 use trace::{Trace, TraceKind};
+use transport::{GossipTransport, GossipTransportKind, SwimSocket, SwimTransport, UdpTransport,
+                LINGER_DEFAULT_MS};
+use self::keyring::Keyring;
+use self::timing::{Clock, WallClock};
+
+/// The highest our local health score can climb; probe timeouts are stretched by at most
+/// `LOCAL_HEALTH_MAX + 1` times.
+const LOCAL_HEALTH_MAX: usize = 8;
+
+/// How long the protocol threads wait between checks while the server is paused.
+const PAUSED_SLEEP_MS: i64 = 100;
 
 pub trait Suitability: Debug + Send + Sync {
     fn get(&self, service_group: &ServiceGroup) -> u64;
 }
 
+/// A leader renews its lease this many times per lease.
+const LEASE_RENEWALS: i32 = 4;
+
+/// A leader steps down once this fraction of its lease has gone by without a renewal. The rest of
+/// the lease is the margin in which the members it can no longer reach must notice, and stop
+/// counting on it, before they elect someone else.
+const LEASE_STEP_DOWN: i32 = 2;
+
+/// The last renewal of a leader's lease this server has seen, as its term and renewal, and when it
+/// saw it. A lease we have not seen renewed yet has no term, and lives from when we first asked
+/// about it.
+#[derive(Debug, Clone, Copy)]
+struct Lease {
+    renewal: Option<(u64, u64)>,
+    seen: SteadyTime,
+}
+
 /// The server struct. Is thread-safe.
 #[derive(Debug)]
 pub struct Server {
@@ -70,7 +104,7 @@ pub struct Server {
     member_id: Arc<String>,
     pub member: Arc<RwLock<Member>>,
     pub member_list: MemberList,
-    ring_key: Arc<Option<SymKey>>,
+    keyring: Arc<RwLock<Keyring>>,
     rumor_heat: RumorHeat,
     pub service_store: RumorStore<Service>,
     pub service_config_store: RumorStore<ServiceConfig>,
@@ -78,13 +112,23 @@ pub struct Server {
     pub election_store: RumorStore<Election>,
     pub update_store: RumorStore<ElectionUpdate>,
     pub departure_store: RumorStore<Departure>,
+    pub canary_store: RumorStore<Canary>,
+    pub ring_key_store: RumorStore<RingKey>,
     swim_addr: Arc<RwLock<SocketAddr>>,
     gossip_addr: Arc<RwLock<SocketAddr>>,
     suitability_lookup: Arc<Box<Suitability>>,
+    election_config: Arc<RwLock<HashMap<String, ElectionConfig>>>,
+    leases: Arc<RwLock<HashMap<String, Lease>>>,
+    clock: Arc<Clock>,
+    swim_transport: Arc<Box<SwimTransport>>,
+    gossip_transport: Arc<Box<GossipTransport>>,
     data_path: Arc<Option<PathBuf>>,
     dat_file: Arc<RwLock<Option<DatFile>>>,
-    socket: Option<UdpSocket>,
+    socket: Option<Arc<SwimSocket>>,
     departed: Arc<AtomicBool>,
+    local_health: Arc<AtomicUsize>,
+    anti_entropy_due: Arc<AtomicBool>,
+    anti_entropy_peer: Arc<RwLock<Option<String>>>,
     // These are all here for testing support
     pause: Arc<AtomicBool>,
     pub trace: Arc<RwLock<Trace>>,
@@ -100,7 +144,7 @@ impl Clone for Server {
             member_id: self.member_id.clone(),
             member: self.member.clone(),
             member_list: self.member_list.clone(),
-            ring_key: self.ring_key.clone(),
+            keyring: self.keyring.clone(),
             rumor_heat: self.rumor_heat.clone(),
             service_store: self.service_store.clone(),
             service_config_store: self.service_config_store.clone(),
@@ -108,12 +152,22 @@ impl Clone for Server {
             election_store: self.election_store.clone(),
             update_store: self.update_store.clone(),
             departure_store: self.departure_store.clone(),
+            canary_store: self.canary_store.clone(),
+            ring_key_store: self.ring_key_store.clone(),
             swim_addr: self.swim_addr.clone(),
             gossip_addr: self.gossip_addr.clone(),
             suitability_lookup: self.suitability_lookup.clone(),
+            election_config: self.election_config.clone(),
+            leases: self.leases.clone(),
+            clock: self.clock.clone(),
+            swim_transport: self.swim_transport.clone(),
+            gossip_transport: self.gossip_transport.clone(),
             data_path: self.data_path.clone(),
             dat_file: self.dat_file.clone(),
             departed: self.departed.clone(),
+            local_health: self.local_health.clone(),
+            anti_entropy_due: self.anti_entropy_due.clone(),
+            anti_entropy_peer: self.anti_entropy_peer.clone(),
             pause: self.pause.clone(),
             trace: self.trace.clone(),
             swim_rounds: self.swim_rounds.clone(),
@@ -149,12 +203,13 @@ impl Server {
             (Ok(Some(swim_socket_addr)), Ok(Some(gossip_socket_addr))) => {
                 member.set_swim_port(swim_socket_addr.port() as i32);
                 member.set_gossip_port(gossip_socket_addr.port() as i32);
+                member.set_digest_sync(true);
                 Ok(Server {
                     name: Arc::new(name.unwrap_or(String::from(member.get_id()))),
                     member_id: Arc::new(String::from(member.get_id())),
                     member: Arc::new(RwLock::new(member)),
                     member_list: MemberList::new(),
-                    ring_key: Arc::new(ring_key),
+                    keyring: Arc::new(RwLock::new(Keyring::new(ring_key))),
                     rumor_heat: RumorHeat::default(),
                     service_store: RumorStore::default(),
                     service_config_store: RumorStore::default(),
@@ -162,12 +217,26 @@ impl Server {
                     election_store: RumorStore::default(),
                     update_store: RumorStore::default(),
                     departure_store: RumorStore::default(),
+                    canary_store: RumorStore::default(),
+                    ring_key_store: RumorStore::default(),
                     swim_addr: Arc::new(RwLock::new(swim_socket_addr)),
                     gossip_addr: Arc::new(RwLock::new(gossip_socket_addr)),
                     suitability_lookup: Arc::new(suitability_lookup),
+                    election_config: Arc::new(RwLock::new(HashMap::new())),
+                    leases: Arc::new(RwLock::new(HashMap::new())),
+                    clock: Arc::new(WallClock),
+                    swim_transport: Arc::new(Box::new(UdpTransport::default())),
+                    gossip_transport: Arc::new(
+                        GossipTransportKind::default()
+                            .transport(LINGER_DEFAULT_MS)
+                            .expect("The default gossip transport is always built in"),
+                    ),
                     data_path: Arc::new(data_path.as_ref().map(|p| p.into())),
                     dat_file: Arc::new(RwLock::new(None)),
                     departed: Arc::new(AtomicBool::new(false)),
+                    local_health: Arc::new(AtomicUsize::new(0)),
+                    anti_entropy_due: Arc::new(AtomicBool::new(true)),
+                    anti_entropy_peer: Arc::new(RwLock::new(None)),
                     pause: Arc::new(AtomicBool::new(false)),
                     trace: Arc::new(RwLock::new(trace)),
                     swim_rounds: Arc::new(AtomicIsize::new(0)),
@@ -212,6 +281,47 @@ impl Server {
         }
     }
 
+    /// Our local health score, between 0 and `LOCAL_HEALTH_MAX`. Zero means we are keeping up
+    /// with the protocol; anything higher means we have recently missed acks or had to refute
+    /// suspicion of ourselves, and probe timeouts are stretched accordingly.
+    pub fn local_health(&self) -> usize {
+        self.local_health.load(Ordering::SeqCst)
+    }
+
+    /// Raises our local health score by one, up to `LOCAL_HEALTH_MAX`.
+    fn degrade_local_health(&self) {
+        self.update_local_health(|current| if current < LOCAL_HEALTH_MAX {
+            Some(current + 1)
+        } else {
+            None
+        });
+    }
+
+    /// Lowers our local health score by one, down to zero.
+    fn improve_local_health(&self) {
+        self.update_local_health(|current| current.checked_sub(1));
+    }
+
+    /// Replaces our local health score with the one computed from it, retrying if the inbound and
+    /// outbound threads race each other. Nothing changes when `update` returns `None`.
+    fn update_local_health<F>(&self, update: F)
+    where
+        F: Fn(usize) -> Option<usize>,
+    {
+        let mut current = self.local_health.load(Ordering::SeqCst);
+        while let Some(new) = update(current) {
+            match self.local_health.compare_exchange(
+                current,
+                new,
+                Ordering::SeqCst,
+                Ordering::SeqCst,
+            ) {
+                Ok(_) => return,
+                Err(actual) => current = actual,
+            }
+        }
+    }
+
     /// Every iteration of the gossip protocol (which means every member has been sent if they
     /// are available) increments the round. If we exceed an isize in rounds, we reset to 0.
     ///
@@ -239,7 +349,8 @@ impl Server {
     }
 
     /// Start the server, along with a `Timing` for outbound connections. Spawns the `inbound`,
-    /// `outbound`, and `expire` threads.
+    /// `outbound`, and `expire` threads. From here on, every timer of the server runs on the
+    /// timing's clock.
     ///
     /// # Errors
     ///
@@ -247,7 +358,10 @@ impl Server {
     /// * Returns `Error::SocketSetReadTimeout` if the socket read timeout cannot be set
     /// * Returns `Error::SocketSetWriteTimeout` if the socket write timeout cannot be set
     pub fn start(&mut self, timing: timing::Timing) -> Result<()> {
+        self.clock = timing.clock.clone();
+        self.member_list.set_clock(timing.clock.clone());
         let (tx_outbound, rx_inbound) = channel();
+        let (tx_fetch, rx_fetch) = sync_channel(anti_entropy::FETCH_QUEUE_DEPTH);
         if let Some(ref path) = *self.data_path {
             if let Some(err) = fs::create_dir_all(path).err() {
                 return Err(Error::BadDataPath(path.to_path_buf(), err));
@@ -260,29 +374,15 @@ impl Server {
             *dat_file = Some(file);
         }
 
-        let socket = match UdpSocket::bind(*self.swim_addr.read().expect(
-            "Swim address lock is poisoned",
-        )) {
-            Ok(socket) => socket,
-            Err(e) => return Err(Error::CannotBind(e)),
-        };
-        socket
-            .set_read_timeout(Some(Duration::from_millis(1000)))
-            .map_err(|e| Error::SocketSetReadTimeout(e))?;
-        socket
-            .set_write_timeout(Some(Duration::from_millis(1000)))
-            .map_err(|e| Error::SocketSetReadTimeout(e))?;
+        let socket = self.swim_transport.bind(
+            &*self.swim_addr.read().expect(
+                "Swim address lock is poisoned",
+            ),
+        )?;
 
         let server_a = self.clone();
-        let socket_a = match socket.try_clone() {
-            Ok(socket_a) => socket_a,
-            Err(_) => return Err(Error::SocketCloneError),
-        };
-        let socket_expire = match socket.try_clone() {
-            Ok(socket_expire) => socket_expire,
-            Err(_) => return Err(Error::SocketCloneError),
-        };
-        self.socket = Some(socket_expire);
+        let socket_a = socket.clone();
+        self.socket = Some(socket.clone());
 
         let _ = thread::Builder::new()
             .name(format!("inbound-{}", self.name()))
@@ -292,10 +392,7 @@ impl Server {
             });
 
         let server_b = self.clone();
-        let socket_b = match socket.try_clone() {
-            Ok(socket_b) => socket_b,
-            Err(_) => return Err(Error::SocketCloneError),
-        };
+        let socket_b = socket;
         let timing_b = timing.clone();
         let _ = thread::Builder::new()
             .name(format!("outbound-{}", self.name()))
@@ -317,18 +414,27 @@ impl Server {
         let _ = thread::Builder::new()
             .name(format!("pull-{}", self.name()))
             .spawn(move || {
-                pull::Pull::new(server_d).run();
+                pull::Pull::new(server_d, tx_fetch).run();
                 panic!("You should never, ever get here, davey");
             });
 
         let server_e = self.clone();
+        let timing_e = timing.clone();
         let _ = thread::Builder::new()
             .name(format!("push-{}", self.name()))
             .spawn(move || {
-                push::Push::new(server_e, timing).run();
+                push::Push::new(server_e, timing_e).run();
                 panic!("You should never, ever get here, liu");
             });
 
+        let server_g = self.clone();
+        let _ = thread::Builder::new()
+            .name(format!("anti-entropy-{}", self.name()))
+            .spawn(move || {
+                anti_entropy::AntiEntropy::new(server_g, timing, rx_fetch).run();
+                panic!("You should never, ever get here, ada");
+            });
+
         if self.dat_file
             .read()
             .expect("DatFile lock poisoned")
@@ -346,6 +452,19 @@ impl Server {
         Ok(())
     }
 
+    /// Send SWIM probes over `transport` rather than UDP. Like `set_gossip_transport`, this must
+    /// be set before the server starts.
+    pub fn set_swim_transport<T: SwimTransport + 'static>(&mut self, transport: T) {
+        self.swim_transport = Arc::new(Box::new(transport));
+    }
+
+    /// Gossip over `transport` rather than the default one. Every member of the ring, and every
+    /// client talking to it, has to use the same transport, so this must be set before the server
+    /// starts.
+    pub fn set_gossip_transport<T: GossipTransport + 'static>(&mut self, transport: T) {
+        self.gossip_transport = Arc::new(Box::new(transport));
+    }
+
     pub fn need_peer_seeding(&self) -> bool {
         let m = self.member_list.members.read().expect(
             "Members lock is poisoned",
@@ -422,6 +541,17 @@ impl Server {
         &self.member_id
     }
 
+    /// Returns the current time of the clock the server's timers run on.
+    pub fn now(&self) -> SteadyTime {
+        self.clock.now()
+    }
+
+    /// Blocks the calling thread for a moment of the server's clock; the protocol threads wait
+    /// here while the server is paused.
+    fn sleep_while_paused(&self) {
+        self.clock.sleep(TimeDuration::milliseconds(PAUSED_SLEEP_MS));
+    }
+
     /// Return the name of this server.
     pub fn name(&self) -> &str {
         &self.name
@@ -506,8 +636,15 @@ impl Server {
         }
     }
 
-    /// Given a membership record and some health, insert it into the Member List.
-    fn insert_member_from_rumor(&self, member: Member, mut health: Health) {
+    /// Given a membership record and some health, received from another member, insert it into
+    /// the Member List.
+    ///
+    /// A suspect record names the member whose probe started the suspicion. Each distinct member
+    /// other than ourselves counts once towards confirming the suspicion, which shortens how long
+    /// we wait before marking the member confirmed; members relaying a suspicion don't count.
+    /// Suspicion of ourselves is refuted by bumping our incarnation, and counts against our local
+    /// health.
+    fn insert_member_from_rumor(&self, member: Member, mut health: Health, suspected_by: &str) {
         let mut incremented_incarnation = false;
         let rk: RumorKey = RumorKey::from(&member);
         if member.get_id() == self.member_id() {
@@ -518,8 +655,22 @@ impl Server {
                 me.set_incarnation(incarnation);
                 health = Health::Alive;
                 incremented_incarnation = true;
+            } else {
+                // The ring still remembers the tags we had before a restart; outbid that copy so
+                // our current ones replace it.
+                let mut me = self.member.write().expect("Member lock is poisoned");
+                if member.get_incarnation() >= me.get_incarnation() &&
+                    member.get_tags() != me.get_tags()
+                {
+                    me.set_incarnation(member.get_incarnation() + 1);
+                }
             }
         }
+        if incremented_incarnation {
+            self.degrade_local_health();
+            // Someone thought we were gone; we have likely missed rumors while they did.
+            self.anti_entropy_due.store(true, Ordering::SeqCst);
+        }
         // NOTE: This sucks so much right here. Check out how we allocate no matter what, because
         // of just how the logic goes. The value of the trace is really high, though, so we suck it
         // for now.
@@ -528,6 +679,10 @@ impl Server {
         let trace_health = health.clone();
 
         if self.member_list.insert(member, health) || incremented_incarnation {
+            if trace_health == Health::Alive {
+                // A confirmed member which comes back gossips its services again.
+                self.service_store.forget_tombstones_of(&trace_member_id);
+            }
             trace_it!(
                 MEMBERSHIP: self,
                 TraceKind::MemberUpdate,
@@ -537,12 +692,23 @@ impl Server {
             );
             self.rumor_heat.start_hot_rumor(rk);
         }
+        if trace_health == Health::Suspect && suspected_by != self.member_id() {
+            self.member_list.confirm_suspicion(
+                &trace_member_id,
+                trace_incarnation,
+                suspected_by,
+            );
+        }
     }
 
-    /// Insert members from a list of received rumors.
-    fn insert_member_from_rumors(&self, members: Vec<(Member, Health)>) {
-        for (member, health) in members.into_iter() {
-            self.insert_member_from_rumor(member, health);
+    /// Insert members from a list of membership records received from another member.
+    fn insert_member_from_rumors(&self, memberships: &[ProtoMembership]) {
+        for membership in memberships {
+            self.insert_member_from_rumor(
+                Member::from(membership.get_member()),
+                Health::from(membership.get_health()),
+                membership.get_suspected_by(),
+            );
         }
     }
 
@@ -603,6 +769,96 @@ impl Server {
         }
     }
 
+    /// Insert a ring key rumor into the ring key store, and stage the new key revision it
+    /// carries. Rumors for other rings, for revisions we already hold, or arriving on a ring
+    /// without encryption are dropped.
+    pub fn insert_ring_key(&self, ring_key: RingKey) {
+        let mut keyring = self.keyring.write().expect("Keyring lock is poisoned");
+        if !keyring.is_newer(ring_key.get_name(), ring_key.get_revision()) {
+            return;
+        }
+        let cache = match keyring.cache {
+            Some(ref cache) => cache.clone(),
+            None => {
+                warn!(
+                    "Ignoring ring key {}; no key cache to write it to",
+                    ring_key.name_with_rev()
+                );
+                return;
+            }
+        };
+        let content = String::from_utf8_lossy(ring_key.get_key()).into_owned();
+        let key = match SymKey::write_file_from_str(&content, &cache) {
+            Ok((key, _)) => key,
+            Err(e) => {
+                error!("Cannot import ring key {}: {}", ring_key.name_with_rev(), e);
+                return;
+            }
+        };
+        if key.name_with_rev() != ring_key.name_with_rev() {
+            error!(
+                "Ring key rumor for {} carried the key {}; ignoring it",
+                ring_key.name_with_rev(),
+                key.name_with_rev()
+            );
+            return;
+        }
+        if let Err(e) = keyring::write_schedule(
+            &cache,
+            &key,
+            ring_key.get_activate_at(),
+            ring_key.get_grace_period(),
+        )
+        {
+            error!(
+                "Cannot record when ring key {} activates: {}",
+                ring_key.name_with_rev(),
+                e
+            );
+            return;
+        }
+        info!(
+            "Staged ring key {}, activating at {}",
+            key.name_with_rev(),
+            ring_key.get_activate_at()
+        );
+        keyring.stage(
+            key,
+            ring_key.get_activate_at(),
+            ring_key.get_grace_period(),
+        );
+        let rk = RumorKey::from(&ring_key);
+        if self.ring_key_store.insert(ring_key) {
+            self.rumor_heat.start_hot_rumor(rk);
+        }
+    }
+
+    /// Sets the key cache that new ring key revisions are written to. Without one, ring key
+    /// rotations are ignored. A rotation that was under way when we last stopped is picked up
+    /// again from the cache.
+    pub fn set_ring_key_cache<P: Into<PathBuf>>(&self, cache: P) {
+        let mut keyring = self.keyring.write().expect("Keyring lock is poisoned");
+        keyring.cache = Some(cache.into());
+        if let Err(e) = keyring.restore(time::get_time().sec) {
+            error!("Cannot restore the ring key rotation from the key cache: {}", e);
+        }
+    }
+
+    /// The name and revision of the ring key we currently encrypt with, if any.
+    pub fn ring_key_revision(&self) -> Option<String> {
+        let mut keyring = self.keyring.write().expect("Keyring lock is poisoned");
+        keyring.advance(time::get_time().sec);
+        keyring.current().map(|key| key.name_with_rev())
+    }
+
+    /// Insert a canary rumor into the canary store.
+    pub fn insert_canary(&self, canary: Canary) {
+        let rk = RumorKey::from(&canary);
+        if self.canary_store.insert(canary) {
+            self.rumor_heat.start_hot_rumor(rk);
+        }
+    }
+
     /// Insert a departure rumor into the departure store.
     pub fn insert_departure(&self, departure: Departure) {
         let rk = RumorKey::from(&departure);
@@ -630,6 +886,55 @@ impl Server {
         }
     }
 
+    /// Tombstones the rumors owned by members that have been confirmed or departed for longer
+    /// than `ttl`. Once the last service rumor for a service group is gone, the group no longer
+    /// exists, so its configuration, files, elections and canaries are tombstoned as well.
+    /// Tombstones themselves are forgotten after another `ttl`, or as soon as their member comes
+    /// back to life.
+    pub fn expire_rumors(&self, ttl: TimeDuration) {
+        let now = self.clock.now();
+        let mut emptied: Vec<String> = Vec::new();
+        for member_id in self.member_list.dead_longer_than(ttl) {
+            let mut service_groups: Vec<String> = Vec::new();
+            self.service_store.with_keys(|(service_group, rumors)| {
+                if rumors.contains_key(&member_id) {
+                    service_groups.push(service_group.clone());
+                }
+            });
+            for service_group in service_groups {
+                if self.service_store.tombstone(&service_group, &member_id, now) {
+                    debug!(
+                        "Expired service rumor for {} from dead member {}",
+                        service_group,
+                        member_id
+                    );
+                }
+                if self.service_store.len_for_key(&service_group) == 0 {
+                    emptied.push(service_group);
+                }
+            }
+        }
+        for service_group in emptied {
+            let expired = self.service_config_store.tombstone_key(&service_group, now) +
+                self.service_file_store.tombstone_key(&service_group, now) +
+                self.election_store.tombstone_key(&service_group, now) +
+                self.update_store.tombstone_key(&service_group, now) +
+                self.canary_store.tombstone_key(&service_group, now);
+            info!(
+                "Service group {} has no live members left; expired {} of its rumors",
+                service_group,
+                expired
+            );
+        }
+
+        self.service_store.expire_tombstones(ttl, now);
+        self.service_config_store.expire_tombstones(ttl, now);
+        self.service_file_store.expire_tombstones(ttl, now);
+        self.election_store.expire_tombstones(ttl, now);
+        self.update_store.expire_tombstones(ttl, now);
+        self.canary_store.expire_tombstones(ttl, now);
+    }
+
     /// Get all the Member ID's who are present in a given service group, and eligible to vote
     /// (alive)
     fn get_electorate(&self, key: &str) -> Vec<String> {
@@ -648,22 +953,145 @@ impl Server {
 
     /// Get all the Member ID's who are present in a given service group, and count towards quorum.
     pub fn get_total_population(&self, key: &str) -> usize {
+        let quorum_basis = self.election_config(key).quorum_basis;
         let mut total_pop = 0;
         self.service_store.with_rumors(key, |s| {
-            if self.member_list.check_in_voting_population_by_id(
-                s.get_member_id(),
-            )
-            {
+            let counts = match quorum_basis {
+                QuorumBasis::Known => {
+                    self.member_list.check_in_voting_population_by_id(s.get_member_id())
+                }
+                QuorumBasis::Alive => {
+                    self.member_list.check_health_of_by_id(s.get_member_id(), Health::Alive) ||
+                        self.member_list.check_health_of_by_id(
+                            s.get_member_id(),
+                            Health::Suspect,
+                        )
+                }
+            };
+            if counts {
                 total_pop += 1;
             }
         });
         total_pop
     }
 
+    /// Set how the elections of a service group are run. Service groups default to counting
+    /// quorum against every known member, and to leaders without a lease.
+    pub fn set_election_config(&self, sg: &ServiceGroup, config: ElectionConfig) {
+        self.election_config
+            .write()
+            .expect("Election config lock is poisoned")
+            .insert(sg.to_string(), config);
+    }
+
+    fn election_config(&self, key: &str) -> ElectionConfig {
+        self.election_config
+            .read()
+            .expect("Election config lock is poisoned")
+            .get(key)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Record the lease of a finished election, if it is newer than the last one we saw.
+    fn observe_lease(&self, election: &Election) {
+        if !election.is_finished() {
+            return;
+        }
+        let renewal = (election.get_term(), election.get_lease_renewal());
+        let mut leases = self.leases.write().expect("Leases lock is poisoned");
+        if let Some(&Lease { renewal: Some(last), .. }) = leases.get(election.key()) {
+            if last >= renewal {
+                return;
+            }
+        }
+        leases.insert(
+            String::from(election.key()),
+            Lease {
+                renewal: Some(renewal),
+                seen: self.clock.now(),
+            },
+        );
+    }
+
+    /// How long the leaders of a service group hold their lease, if they hold one.
+    fn lease_duration(&self, key: &str) -> Option<TimeDuration> {
+        self.election_config(key).lease.and_then(
+            |lease| TimeDuration::from_std(lease).ok(),
+        )
+    }
+
+    /// When we last saw the lease of a service group renewed, if we ever did.
+    fn lease_seen(&self, key: &str) -> Option<SteadyTime> {
+        self.leases
+            .read()
+            .expect("Leases lock is poisoned")
+            .get(key)
+            .map(|lease| lease.seen)
+    }
+
+    /// Returns true if the last leader of a service group can no longer be leading it: its lease
+    /// has not been renewed for as long as it lasts. Always true for leaders without a lease.
+    ///
+    /// A lease we never saw renewed may still be held by a leader we have not heard from, so it
+    /// lives for a full lease from the first time we ask about it.
+    fn lease_expired(&self, key: &str) -> bool {
+        let lease = match self.lease_duration(key) {
+            Some(lease) => lease,
+            None => return true,
+        };
+        let now = self.clock.now();
+        let seen = self.leases
+            .write()
+            .expect("Leases lock is poisoned")
+            .entry(String::from(key))
+            .or_insert(Lease {
+                renewal: None,
+                seen: now,
+            })
+            .seen;
+        now >= seen + lease
+    }
+
+    /// Returns true if a leader must step down: its lease has gone unrenewed for long enough that
+    /// the members it cannot reach may soon stop counting on it. Always false for leaders without
+    /// a lease, and true for a leader that does not know when it last renewed its lease.
+    fn lease_running_out(&self, key: &str) -> bool {
+        match (self.lease_duration(key), self.lease_seen(key)) {
+            (Some(lease), Some(seen)) => self.clock.now() >= seen + lease / LEASE_STEP_DOWN,
+            (Some(_), None) => true,
+            (None, _) => false,
+        }
+    }
+
+    /// Returns true if a leader should renew its lease; it does so `LEASE_RENEWALS` times per
+    /// lease.
+    fn lease_due(&self, key: &str) -> bool {
+        match (self.lease_duration(key), self.lease_seen(key)) {
+            (Some(lease), Some(seen)) => self.clock.now() >= seen + lease / LEASE_RENEWALS,
+            (Some(_), None) => true,
+            (None, _) => false,
+        }
+    }
+
+    /// Returns true if any member of a service group is suspected to be down. A leader stops
+    /// renewing its lease while one is, as it can no longer tell that member is still following
+    /// it.
+    fn has_suspect_members(&self, key: &str) -> bool {
+        let mut suspect = false;
+        self.service_store.with_rumors(key, |s| {
+            if self.member_list.check_health_of_by_id(s.get_member_id(), Health::Suspect) {
+                suspect = true;
+            }
+        });
+        suspect
+    }
+
     /// Check if a given service group has quorum to run an election.
     ///
     /// A given group has quorum if, from this servers perspective, it has an alive population that
-    /// is over 50%, and at least 3 members.
+    /// is over 50%, and at least 3 members. The population is counted according to the group's
+    /// `QuorumBasis`.
     fn check_quorum(&self, key: &str) -> bool {
         let electorate = self.get_electorate(key);
 
@@ -706,13 +1134,56 @@ impl Server {
         self.rumor_heat.start_hot_rumor(ek);
     }
 
+    /// Start a canary update for the given service group, testing `pkg` on the `canaries` before
+    /// the rest of the group moves off `previous_pkg`.
+    pub fn start_canary(
+        &self,
+        sg: ServiceGroup,
+        pkg: String,
+        previous_pkg: String,
+        canaries: Vec<String>,
+    ) {
+        let mut canary = Canary::new(self.member_id(), sg, pkg, previous_pkg, canaries);
+        let mut incarnation = 0;
+        self.canary_store.with_rumor(canary.key(), canary.id(), |current| {
+            if let Some(current) = current {
+                incarnation = current.get_incarnation() + 1;
+            }
+        });
+        canary.set_incarnation(incarnation);
+        self.insert_canary(canary);
+    }
+
+    /// Move the running canary update of the given service group to a new status. Does nothing
+    /// if there is no canary update for the group.
+    pub fn set_canary_status(&self, sg: &ServiceGroup, status: Canary_Status) {
+        let mut canary: Option<Canary> = None;
+        self.canary_store.with_rumor(&sg.to_string(), "canary", |current| {
+            canary = current.cloned()
+        });
+        if let Some(mut canary) = canary {
+            let incarnation = canary.get_incarnation() + 1;
+            canary.set_member_id(self.member_id().to_string());
+            canary.set_incarnation(incarnation);
+            canary.set_status(status);
+            self.insert_canary(canary);
+        }
+    }
+
     /// Check to see if this server needs to restart a given election. This happens when:
     ///
-    /// a) We are the leader, and we have lost quorum with the rest of the group.
+    /// a) We are the leader, and we have lost quorum with the rest of the group; or, if we hold a
+    ///    lease, we have gone without renewing it for half of it.
     /// b) We are not the leader, and we have detected that the leader is confirmed dead.
+    ///
+    /// It also renews the lease of the elections we lead while we have quorum and no member of
+    /// the group is suspect, and finishes the elections we won that were waiting for the lease of
+    /// the previous leader to run out.
     pub fn restart_elections(&self) {
         let mut elections_to_restart = vec![];
         let mut update_elections_to_restart = vec![];
+        let mut leases_to_renew = vec![];
+        let mut elections_to_finish = vec![];
 
         self.election_store.with_keys(|(service_group, rumors)| {
             if self.service_store.contains_rumor(
@@ -726,18 +1197,36 @@ impl Server {
                 );
                 // If we are finished, and the leader is dead, we should restart the election
                 if election.is_finished() && election.get_member_id() == self.member_id() {
-                    // If we are the leader, and we have lost quorum, we should restart the election
-                    if self.check_quorum(election.key()) == false {
+                    // If we are the leader, we restart the election once we lose quorum. With a
+                    // lease, we instead step down once we have gone half of it without a renewal,
+                    // and only renew it while every member of the group is reachable.
+                    if self.lease_duration(election.key()).is_none() {
+                        if self.check_quorum(election.key()) == false {
+                            warn!(
+                                "Restarting election with a new term as the leader has lost \
+                                  quorum: {:?}",
+                                election
+                            );
+                            elections_to_restart.push((
+                                String::from(&service_group[..]),
+                                election.get_term(),
+                            ));
+                        }
+                    } else if self.lease_running_out(election.key()) {
                         warn!(
-                            "Restarting election with a new term as the leader has lost \
-                              quorum: {:?}",
+                            "Restarting election with a new term as the leader could not renew \
+                              its lease: {:?}",
                             election
                         );
                         elections_to_restart.push((
                             String::from(&service_group[..]),
                             election.get_term(),
                         ));
-
+                    } else if self.lease_due(election.key()) &&
+                               self.check_quorum(election.key()) &&
+                               !self.has_suspect_members(election.key())
+                    {
+                        leases_to_renew.push(String::from(&service_group[..]));
                     }
                 } else if election.is_finished() {
                     if self.member_list.check_health_of_by_id(
@@ -755,6 +1244,8 @@ impl Server {
                             election.get_term(),
                         ));
                     }
+                } else if election.get_member_id() == self.member_id() {
+                    elections_to_finish.push(election.clone());
                 }
             }
         });
@@ -839,6 +1330,25 @@ impl Server {
             self.update_store.remove(&service_group, "election");
             self.start_update_election(sg, 0, term);
         }
+
+        for service_group in leases_to_renew {
+            let mut election: Option<Election> = None;
+            self.election_store.with_rumor(&service_group, "election", |current| {
+                election = current.cloned()
+            });
+            if let Some(mut election) = election {
+                election.renew_lease();
+                self.observe_lease(&election);
+                let rk = RumorKey::from(&election);
+                if self.election_store.insert(election) {
+                    self.rumor_heat.start_hot_rumor(rk);
+                }
+            }
+        }
+
+        for election in elections_to_finish {
+            self.insert_election(election);
+        }
     }
 
     /// Insert an election into the election store. Handles creating a new election rumor for this
@@ -889,15 +1399,21 @@ impl Server {
                                 num_votes += 1;
                             }
                         }
-                        if num_votes == electorate.len() {
-                            debug!("Election is finished: {:#?}", election);
-                            election.finish();
-                        } else {
+                        if num_votes != electorate.len() {
                             debug!(
                                 "I have quorum, but election is not finished {}/{}",
                                 num_votes,
                                 electorate.len()
                             );
+                        } else if !self.lease_expired(election.key()) {
+                            debug!(
+                                "Election is won, but the lease of the previous leader has not \
+                                 run out: {:#?}",
+                                election
+                            );
+                        } else {
+                            debug!("Election is finished: {:#?}", election);
+                            election.finish();
                         }
                     } else {
                         election.no_quorum();
@@ -924,6 +1440,7 @@ impl Server {
                     election.no_quorum();
                 }
             }
+            self.observe_lease(&election);
         }
         if self.election_store.insert(election) {
             self.rumor_heat.start_hot_rumor(rk);
@@ -1017,11 +1534,14 @@ impl Server {
     }
 
     fn generate_wire(&self, payload: Vec<u8>) -> Result<Vec<u8>> {
-        message::generate_wire(payload, (*self.ring_key).as_ref())
+        let mut keyring = self.keyring.write().expect("Keyring lock is poisoned");
+        keyring.advance(time::get_time().sec);
+        message::generate_wire(payload, keyring.current())
     }
 
     fn unwrap_wire(&self, payload: &[u8]) -> Result<Vec<u8>> {
-        message::unwrap_wire(payload, (*self.ring_key).as_ref())
+        let keyring = self.keyring.read().expect("Keyring lock is poisoned");
+        message::unwrap_wire(payload, keyring.accepted())
     }
 
     fn persist_data(&self) {
@@ -1043,7 +1563,7 @@ impl Serialize for Server {
     where
         S: Serializer,
     {
-        let mut strukt = serializer.serialize_struct("butterfly", 6)?;
+        let mut strukt = serializer.serialize_struct("butterfly", 9)?;
         strukt.serialize_field("member", &self.member_list)?;
         strukt.serialize_field("service", &self.service_store)?;
         strukt.serialize_field(
@@ -1060,6 +1580,8 @@ impl Serialize for Server {
             &self.update_store,
         )?;
         strukt.serialize_field("departure", &self.departure_store)?;
+        strukt.serialize_field("canary", &self.canary_store)?;
+        strukt.serialize_field("local_health", &self.local_health())?;
         strukt.end()
     }
 }
@@ -1094,13 +1616,24 @@ fn persist_loop(server: Server) {
 #[cfg(test)]
 mod tests {
     mod server {
+        use habitat_core::package::{Identifiable, PackageIdent};
         use habitat_core::service::ServiceGroup;
-        use server::{Server, Suitability};
-        use server::timing::Timing;
-        use member::Member;
+        use server::{anti_entropy, Server, Suitability, LOCAL_HEALTH_MAX};
+        use server::timing::{ManualClock, Timing};
+        use member::{Health, Member};
+        use message::swim::{Digest as ProtoDigest, Membership as ProtoMembership};
+        use rumor::election::{Election, ElectionConfig, QuorumBasis};
+        use rumor::service::{Service, SysInfo};
+        use rumor::service_config::ServiceConfig;
         use trace::Trace;
         use std::path::PathBuf;
+        use std::str::FromStr;
+        use std::sync::Arc;
         use std::sync::atomic::{AtomicUsize, Ordering, ATOMIC_USIZE_INIT};
+        use std::sync::mpsc::sync_channel;
+        use std::thread;
+        use std::time::Duration as StdDuration;
+        use time::Duration;
 
         static SWIM_PORT: AtomicUsize = ATOMIC_USIZE_INIT;
         static GOSSIP_PORT: AtomicUsize = ATOMIC_USIZE_INIT;
@@ -1135,11 +1668,179 @@ mod tests {
             ).unwrap()
         }
 
+        /// A server whose timers run on `clock`, in a `witcher.prod` group of three alive members
+        /// whose leaders hold a lease of 40 seconds. Returns the server and the other two members.
+        fn start_leased_server(clock: &Arc<ManualClock>) -> (Server, Vec<Member>) {
+            let mut server = start_server();
+            server.clock = clock.clone();
+            server.member_list.set_clock(clock.clone());
+            let pkg = PackageIdent::from_str("core/witcher/1.2.3/20161208121212").unwrap();
+            let sg = ServiceGroup::new(None, pkg.name(), "prod", None).unwrap();
+            server.set_election_config(
+                &sg,
+                ElectionConfig {
+                    quorum_basis: QuorumBasis::Known,
+                    lease: Some(StdDuration::from_secs(40)),
+                },
+            );
+            let me = server.member.read().expect("Member lock is poisoned").clone();
+            let others = vec![Member::default(), Member::default()];
+            server.insert_member(me.clone(), Health::Alive);
+            for member in others.iter() {
+                server.insert_member(member.clone(), Health::Alive);
+            }
+            for member in others.iter().chain(Some(&me)) {
+                server.insert_service(Service::new(
+                    member.get_id(),
+                    &pkg,
+                    &sg,
+                    &SysInfo::default(),
+                    None,
+                ));
+            }
+            (server, others)
+        }
+
+        fn witcher() -> ServiceGroup {
+            ServiceGroup::from_str("witcher.prod").unwrap()
+        }
+
+        fn current_election(server: &Server) -> Election {
+            let mut election = None;
+            server.election_store.with_rumor("witcher.prod", "election", |e| {
+                election = e.cloned()
+            });
+            election.expect("No election for witcher.prod")
+        }
+
         #[test]
         fn new() {
             start_server();
         }
 
+        #[test]
+        fn a_leader_renews_its_lease_only_while_no_member_is_suspect() {
+            let clock = Arc::new(ManualClock::new());
+            let (server, others) = start_leased_server(&clock);
+            let mut election = Election::new(server.member_id(), witcher(), 0);
+            election.finish();
+            server.insert_election(election);
+
+            clock.advance(10_000);
+            server.restart_elections();
+            assert_eq!(current_election(&server).get_lease_renewal(), 1);
+
+            server.insert_health(&others[0], Health::Suspect);
+            clock.advance(10_000);
+            server.restart_elections();
+            let election = current_election(&server);
+            assert_eq!(election.get_lease_renewal(), 1);
+            assert!(election.is_finished());
+        }
+
+        #[test]
+        fn a_leader_steps_down_half_way_through_a_lease_it_could_not_renew() {
+            let clock = Arc::new(ManualClock::new());
+            let (server, others) = start_leased_server(&clock);
+            let mut election = Election::new(server.member_id(), witcher(), 0);
+            election.finish();
+            server.insert_election(election);
+            server.insert_health(&others[0], Health::Suspect);
+
+            clock.advance(19_999);
+            server.restart_elections();
+            let election = current_election(&server);
+            assert!(election.is_finished());
+            assert_eq!(election.get_term(), 0);
+
+            clock.advance(1);
+            server.restart_elections();
+            let election = current_election(&server);
+            assert!(!election.is_finished());
+            assert_eq!(election.get_term(), 1);
+            assert_eq!(election.get_member_id(), server.member_id());
+        }
+
+        #[test]
+        fn the_majority_waits_out_the_lease_of_a_dead_leader() {
+            let clock = Arc::new(ManualClock::new());
+            let (server, others) = start_leased_server(&clock);
+            let mut leader = Election::new(others[0].get_id(), witcher(), 0);
+            leader.finish();
+            server.insert_election(leader);
+            assert_eq!(current_election(&server).get_member_id(), others[0].get_id());
+
+            server.insert_health(&others[0], Health::Confirmed);
+            server.restart_elections();
+            let mut election = Election::new(server.member_id(), witcher(), 0);
+            election.set_term(1);
+            election.insert_vote(others[1].get_id());
+            server.insert_election(election);
+            assert!(!current_election(&server).is_finished());
+
+            clock.advance(39_999);
+            server.restart_elections();
+            assert!(!current_election(&server).is_finished());
+
+            clock.advance(1);
+            server.restart_elections();
+            let election = current_election(&server);
+            assert!(election.is_finished());
+            assert_eq!(election.get_term(), 1);
+            assert_eq!(election.get_member_id(), server.member_id());
+        }
+
+        #[test]
+        fn a_lease_nobody_saw_lives_for_a_whole_lease() {
+            let clock = Arc::new(ManualClock::new());
+            let (server, others) = start_leased_server(&clock);
+            server.start_election(witcher(), 0);
+            let mut election = Election::new(server.member_id(), witcher(), 0);
+            for member in others.iter() {
+                election.insert_vote(member.get_id());
+            }
+            server.insert_election(election);
+            assert!(!current_election(&server).is_finished());
+
+            clock.advance(39_999);
+            server.restart_elections();
+            assert!(!current_election(&server).is_finished());
+
+            clock.advance(1);
+            server.restart_elections();
+            assert!(current_election(&server).is_finished());
+        }
+
+        #[test]
+        fn local_health_stays_in_bounds_across_threads() {
+            let server = start_server();
+            let threads: Vec<_> = (0..8)
+                .map(|_| {
+                    let server = server.clone();
+                    thread::spawn(move || for _ in 0..100 {
+                        server.degrade_local_health();
+                    })
+                })
+                .collect();
+            for t in threads {
+                t.join().unwrap();
+            }
+            assert_eq!(server.local_health(), LOCAL_HEALTH_MAX);
+
+            let threads: Vec<_> = (0..8)
+                .map(|_| {
+                    let server = server.clone();
+                    thread::spawn(move || for _ in 0..100 {
+                        server.improve_local_health();
+                    })
+                })
+                .collect();
+            for t in threads {
+                t.join().unwrap();
+            }
+            assert_eq!(server.local_health(), 0);
+        }
+
         #[test]
         fn invalid_addresses_fails() {
             let swim_listen = "";
@@ -1166,5 +1867,118 @@ mod tests {
                 "Server failed to start",
             );
         }
+
+        #[test]
+        fn expire_rumors_forgets_dead_members_and_their_service_groups() {
+            let server = start_server();
+            let pkg = PackageIdent::from_str("core/neurosis/1.2.3/20161208121212").unwrap();
+            let sg = ServiceGroup::new(None, pkg.name(), "production", None).unwrap();
+            let dead = Member::default();
+            server.insert_member(dead.clone(), Health::Departed);
+            server.insert_service(Service::new(
+                dead.get_id(),
+                &pkg,
+                &sg,
+                &SysInfo::default(),
+                None,
+            ));
+            server.insert_service_config(ServiceConfig::new(dead.get_id(), sg.clone(), vec![]));
+
+            server.expire_rumors(Duration::hours(1));
+            assert_eq!(server.service_store.len(), 1);
+
+            server.expire_rumors(Duration::zero());
+            assert_eq!(server.service_store.len(), 0);
+            assert_eq!(server.service_config_store.len(), 0);
+            assert_eq!(server.service_store.len_tombstones(), 0);
+        }
+
+        #[test]
+        fn expire_rumors_forgets_confirmed_members_until_they_come_back() {
+            let clock = Arc::new(ManualClock::new());
+            let mut server = start_server();
+            server.clock = clock.clone();
+            server.member_list.set_clock(clock.clone());
+            let pkg = PackageIdent::from_str("core/neurosis/1.2.3/20161208121212").unwrap();
+            let sg = ServiceGroup::new(None, pkg.name(), "production", None).unwrap();
+            let mut confirmed = Member::default();
+            let service = Service::new(confirmed.get_id(), &pkg, &sg, &SysInfo::default(), None);
+            server.insert_member(confirmed.clone(), Health::Confirmed);
+            server.insert_service(service.clone());
+
+            clock.advance(3_599_999);
+            server.expire_rumors(Duration::hours(1));
+            assert_eq!(server.service_store.len(), 1);
+            clock.advance(1);
+            server.expire_rumors(Duration::hours(1));
+            assert_eq!(server.service_store.len(), 0);
+            assert_eq!(server.service_store.len_tombstones(), 1);
+            assert!(!server.service_store.insert(service.clone()));
+
+            confirmed.set_incarnation(1);
+            let mut membership = ProtoMembership::new();
+            membership.set_member(confirmed.proto.clone());
+            membership.set_health(Health::Alive.into());
+            server.insert_member_from_rumors(&[membership]);
+            assert_eq!(server.service_store.len_tombstones(), 0);
+            server.insert_service(service);
+            assert_eq!(server.service_store.len(), 1);
+        }
+
+        #[test]
+        fn expire_rumors_keeps_service_groups_with_live_members() {
+            let server = start_server();
+            let pkg = PackageIdent::from_str("core/neurosis/1.2.3/20161208121212").unwrap();
+            let sg = ServiceGroup::new(None, pkg.name(), "production", None).unwrap();
+            let dead = Member::default();
+            let alive = Member::default();
+            server.insert_member(dead.clone(), Health::Departed);
+            server.insert_member(alive.clone(), Health::Alive);
+            for member in vec![&dead, &alive] {
+                server.insert_service(Service::new(
+                    member.get_id(),
+                    &pkg,
+                    &sg,
+                    &SysInfo::default(),
+                    None,
+                ));
+            }
+            server.insert_service_config(ServiceConfig::new(dead.get_id(), sg.clone(), vec![]));
+
+            server.expire_rumors(Duration::zero());
+            assert_eq!(server.service_store.len(), 1);
+            assert!(server.service_store.contains_rumor(
+                &sg.to_string(),
+                alive.get_id(),
+            ));
+            assert_eq!(server.service_config_store.len(), 1);
+        }
+
+        #[test]
+        fn process_digest_completes_the_sync_only_for_the_peer_we_asked() {
+            let server = start_server();
+            *server.anti_entropy_peer.write().unwrap() = Some(String::from("asked"));
+            anti_entropy::process_digest(&server, "someone-else", &ProtoDigest::new());
+            assert!(server.anti_entropy_due.load(Ordering::SeqCst));
+            anti_entropy::process_digest(&server, "asked", &ProtoDigest::new());
+            assert!(!server.anti_entropy_due.load(Ordering::SeqCst));
+        }
+
+        #[test]
+        fn process_digest_fetch_drops_fetches_beyond_the_queue_depth() {
+            let server = start_server();
+            let member = Member::default();
+            server.insert_member(member.clone(), Health::Alive);
+            let (tx, rx) = sync_channel(anti_entropy::FETCH_QUEUE_DEPTH);
+            for _ in 0..anti_entropy::FETCH_QUEUE_DEPTH + 2 {
+                anti_entropy::process_digest_fetch(
+                    &server,
+                    &tx,
+                    member.get_id(),
+                    &ProtoDigest::new(),
+                );
+            }
+            assert_eq!(rx.try_iter().count(), anti_entropy::FETCH_QUEUE_DEPTH);
+        }
     }
 }
@@ -18,12 +18,10 @@
 
 use std::sync::atomic::Ordering;
 use std::sync::mpsc;
-use std::net::{SocketAddr, UdpSocket};
-use std::thread;
-use std::time::Duration;
+use std::net::SocketAddr;
+use std::sync::Arc;
 use std::fmt;
 
-use time::SteadyTime;
 use protobuf::{Message, RepeatedField};
 
 use message::swim::{Ack, Ping, PingReq, Swim, Swim_Type, Rumor_Type};
@@ -32,9 +30,10 @@ use server::Server;
 use server::timing::Timing;
 use member::{Member, Health};
 use trace::TraceKind;
+use transport::SwimSocket;
 
 /// How long to sleep between calls to `recv`.
-const PING_RECV_QUEUE_EMPTY_SLEEP_MS: u64 = 10;
+const PING_RECV_QUEUE_EMPTY_SLEEP_MS: i64 = 10;
 
 /// Where an Ack came from; either Ping or PingReq.
 #[derive(Debug)]
@@ -55,7 +54,7 @@ impl fmt::Display for AckFrom {
 /// The outbound thread
 pub struct Outbound {
     pub server: Server,
-    pub socket: UdpSocket,
+    pub socket: Arc<SwimSocket>,
     pub rx_inbound: mpsc::Receiver<(SocketAddr, Swim)>,
     pub timing: Timing,
 }
@@ -64,7 +63,7 @@ impl Outbound {
     /// Creates a new Outbound struct.
     pub fn new(
         server: Server,
-        socket: UdpSocket,
+        socket: Arc<SwimSocket>,
         rx_inbound: mpsc::Receiver<(SocketAddr, Swim)>,
         timing: Timing,
     ) -> Outbound {
@@ -107,13 +106,13 @@ impl Outbound {
             }
 
             if self.server.pause.load(Ordering::Relaxed) {
-                thread::sleep(Duration::from_millis(100));
+                self.server.sleep_while_paused();
                 continue;
             }
 
             self.server.update_swim_round();
 
-            let long_wait = self.timing.next_protocol_period();
+            let long_wait = self.timing.next_protocol_period(self.server.local_health());
 
             let check_list = self.server.member_list.check_list(
                 self.server
@@ -128,27 +127,17 @@ impl Outbound {
                     // This is the timeout for the next protocol period - if we
                     // complete faster than this, we want to wait in the end
                     // until this timer expires.
-                    let next_protocol_period = self.timing.next_protocol_period();
+                    let next_protocol_period =
+                        self.timing.next_protocol_period(self.server.local_health());
 
                     self.probe(member);
 
-                    if SteadyTime::now() <= next_protocol_period {
-                        let wait_time = (next_protocol_period - SteadyTime::now())
-                            .num_milliseconds();
-                        if wait_time > 0 {
-                            debug!("Waiting {} until the next protocol period", wait_time);
-                            thread::sleep(Duration::from_millis(wait_time as u64));
-                        }
-                    }
+                    debug!("Waiting until the next protocol period");
+                    self.timing.sleep_until(next_protocol_period);
                 }
             }
 
-            if SteadyTime::now() <= long_wait {
-                let wait_time = (long_wait - SteadyTime::now()).num_milliseconds();
-                if wait_time > 0 {
-                    thread::sleep(Duration::from_millis(wait_time as u64));
-                }
-            }
+            self.timing.sleep_until(long_wait);
         }
     }
 
@@ -168,6 +157,10 @@ impl Outbound {
     /// PING_RECV_QUEUE_EMPTY_SLEEP_MS, and try again.
     ///
     /// If we don't receive anything at all in the Ping/PingReq loop, we mark the member as Suspect.
+    ///
+    /// A direct Ack improves our local health score, and a probe that gets no Ack at all degrades
+    /// it; a degraded score stretches the timeouts of the probes that follow, so an overloaded
+    /// Supervisor is slower to suspect its peers.
     fn probe(&mut self, member: Member) {
         let addr = member.swim_socket_address();
 
@@ -176,6 +169,7 @@ impl Outbound {
         // Ping the member, and wait for the ack.
         ping(&self.server, &self.socket, &member, addr, None);
         if self.recv_ack(&member, addr, AckFrom::Ping) {
+            self.server.improve_local_health();
             trace_it!(PROBE: &self.server, TraceKind::ProbeAckReceived, member.get_id(), addr);
             trace_it!(PROBE: &self.server, TraceKind::ProbeComplete, member.get_id(), addr);
             return;
@@ -197,9 +191,18 @@ impl Outbound {
             // into the suspicion phase, where anyone marked as suspect has a certain number of
             // protocol periods to recover.
             warn!("Marking {} as Suspect", member.get_id());
+            self.server.degrade_local_health();
             trace_it!(PROBE: &self.server, TraceKind::ProbeSuspect, member.get_id(), addr);
             trace_it!(PROBE: &self.server, TraceKind::ProbeComplete, member.get_id(), addr);
+            let member_id = String::from(member.get_id());
+            let incarnation = member.get_incarnation();
             self.server.insert_member(member, Health::Suspect);
+            // Our own probe failed, so we suspect the member whoever else already does.
+            self.server.member_list.confirm_suspicion(
+                &member_id,
+                incarnation,
+                self.server.member_id(),
+            );
         } else {
             trace_it!(PROBE: &self.server, TraceKind::ProbeComplete, member.get_id(), addr);
         }
@@ -207,9 +210,10 @@ impl Outbound {
 
     /// Listen for an ack from the `Inbound` thread.
     fn recv_ack(&mut self, member: &Member, addr: SocketAddr, ack_from: AckFrom) -> bool {
+        let local_health = self.server.local_health();
         let timeout = match ack_from {
-            AckFrom::Ping => self.timing.ping_timeout(),
-            AckFrom::PingReq => self.timing.pingreq_timeout(),
+            AckFrom::Ping => self.timing.ping_timeout(local_health),
+            AckFrom::PingReq => self.timing.pingreq_timeout(local_health),
         };
         loop {
             match self.rx_inbound.try_recv() {
@@ -242,7 +246,7 @@ impl Outbound {
                     }
                 }
                 Err(mpsc::TryRecvError::Empty) => {
-                    if SteadyTime::now() > timeout {
+                    if self.timing.now() > timeout {
                         warn!(
                             "Timed out waiting for Ack from {}@{}",
                             member.get_id(),
@@ -250,7 +254,7 @@ impl Outbound {
                         );
                         return false;
                     }
-                    thread::sleep(Duration::from_millis(PING_RECV_QUEUE_EMPTY_SLEEP_MS));
+                    self.timing.sleep_ms(PING_RECV_QUEUE_EMPTY_SLEEP_MS);
                 }
                 Err(mpsc::TryRecvError::Disconnected) => {
                     panic!("Outbound thread has disconnected! This is fatal.");
@@ -265,7 +269,9 @@ pub fn populate_membership_rumors(server: &Server, target: &Member, swim: &mut S
     let mut membership_entries = RepeatedField::new();
     // If this isn't the first time we are communicating with this target, we want to include this
     // targets current status. This ensures that members always get a "Confirmed" rumor, before we
-    // have the chance to flip it to "Alive", which helps make sure we heal from a partition.
+    // have the chance to flip it to "Alive", which helps make sure we heal from a partition. It
+    // is also how a member we suspect learns about it directly from us, and gets the chance to
+    // refute it in its Ack.
     if server.member_list.contains_member(target.get_id()) {
         if let Some(always_target) = server.member_list.membership_for(target.get_id()) {
             membership_entries.push(always_target);
@@ -296,7 +302,7 @@ pub fn populate_membership_rumors(server: &Server, target: &Member, swim: &mut S
 }
 
 /// Send a PingReq.
-pub fn pingreq(server: &Server, socket: &UdpSocket, pingreq_target: &Member, target: &Member) {
+pub fn pingreq(server: &Server, socket: &SwimSocket, pingreq_target: &Member, target: &Member) {
     let addr = pingreq_target.swim_socket_address();
     let mut swim = Swim::new();
     swim.set_field_type(Swim_Type::PINGREQ);
@@ -355,7 +361,7 @@ pub fn pingreq(server: &Server, socket: &UdpSocket, pingreq_target: &Member, tar
 /// Send a Ping.
 pub fn ping(
     server: &Server,
-    socket: &UdpSocket,
+    socket: &SwimSocket,
     target: &Member,
     addr: SocketAddr,
     mut forward_to: Option<Member>,
@@ -414,7 +420,7 @@ pub fn ping(
 }
 
 /// Forward an ack on.
-pub fn forward_ack(server: &Server, socket: &UdpSocket, addr: SocketAddr, swim: Swim) {
+pub fn forward_ack(server: &Server, socket: &SwimSocket, addr: SocketAddr, swim: Swim) {
     trace_it!(
         SWIM: server,
         TraceKind::SendForwardAck,
@@ -460,7 +466,7 @@ pub fn forward_ack(server: &Server, socket: &UdpSocket, addr: SocketAddr, swim:
 /// Send an Ack.
 pub fn ack(
     server: &Server,
-    socket: &UdpSocket,
+    socket: &SwimSocket,
     target: &Member,
     addr: SocketAddr,
     mut forward_to: Option<Member>,
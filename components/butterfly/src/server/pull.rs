@@ -14,53 +14,51 @@
 
 //! The pull thread.
 //!
-//! This module handles pulling all the pushed rumors from every member off a ZMQ socket.
+//! This module handles pulling all the pushed rumors from every member off the gossip transport.
 
 use std::sync::atomic::Ordering;
-use std::thread;
-use std::time::Duration;
+use std::sync::mpsc::SyncSender;
 
 use protobuf;
-use zmq;
 
-use ZMQ_CONTEXT;
-use server::Server;
+use error::Error;
+use server::{anti_entropy, Server};
 use message::swim::{Rumor, Rumor_Type};
 use trace::TraceKind;
 
-/// Takes a reference to the server itself
+/// Takes a reference to the server itself, and the queue of the anti-entropy fetch worker
 pub struct Pull {
     pub server: Server,
+    fetches: SyncSender<anti_entropy::Fetch>,
 }
 
 impl Pull {
     /// Create a new Pull
-    pub fn new(server: Server) -> Pull {
-        Pull { server: server }
+    pub fn new(server: Server, fetches: SyncSender<anti_entropy::Fetch>) -> Pull {
+        Pull {
+            server: server,
+            fetches: fetches,
+        }
     }
 
-    /// Run this thread. Creates a socket, binds to the `gossip_addr`, then processes messages as
-    /// they are received. Uses a ZMQ pull socket, so inbound messages are fair-queued.
+    /// Run this thread. Listens on the `gossip_addr` through the server's gossip transport, then
+    /// processes messages as they are received. Inbound messages are fair-queued.
     pub fn run(&mut self) {
-        let socket = (**ZMQ_CONTEXT).as_mut().socket(zmq::PULL).expect(
-            "Failure to create the ZMQ pull socket",
-        );
-        socket.set_linger(0).expect(
-            "Failure to set the ZMQ Pull socket to not linger",
-        );
-        socket.set_tcp_keepalive(0).expect(
-            "Failure to set the ZMQ Pull socket to not use keepalive",
-        );
-        socket
-            .bind(&format!("tcp://{}", self.server.gossip_addr()))
-            .expect("Failure to bind the ZMQ Pull socket to the port");
+        let mut listener = self.server
+            .gossip_transport
+            .listen(&self.server.gossip_addr())
+            .expect("Failure to listen for gossip on the port");
         'recv: loop {
             if self.server.pause.load(Ordering::Relaxed) {
-                thread::sleep(Duration::from_millis(100));
+                self.server.sleep_while_paused();
                 continue;
             }
-            let msg = match socket.recv_msg(0) {
+            let msg = match listener.recv() {
                 Ok(msg) => msg,
+                Err(Error::GossipListenerClosed) => {
+                    error!("Gossip listener closed; no longer receiving rumors");
+                    break 'recv;
+                }
                 Err(e) => {
                     error!("Error receiving message: {:?}", e);
                     continue 'recv;
@@ -94,7 +92,11 @@ impl Pull {
                 Rumor_Type::Member => {
                     let member = proto.mut_member().take_member().into();
                     let health = proto.mut_member().get_health().into();
-                    self.server.insert_member_from_rumor(member, health);
+                    self.server.insert_member_from_rumor(
+                        member,
+                        health,
+                        proto.get_member().get_suspected_by(),
+                    );
                 }
                 Rumor_Type::Service => {
                     self.server.insert_service(proto.into());
@@ -114,6 +116,30 @@ impl Pull {
                 Rumor_Type::Departure => {
                     self.server.insert_departure(proto.into());
                 }
+                Rumor_Type::Canary => {
+                    self.server.insert_canary(proto.into());
+                }
+                Rumor_Type::RingKey => {
+                    self.server.insert_ring_key(proto.into());
+                }
+                Rumor_Type::DigestRequest => {
+                    anti_entropy::process_digest_request(&self.server, proto.get_from_id());
+                }
+                Rumor_Type::Digest => {
+                    anti_entropy::process_digest(
+                        &self.server,
+                        proto.get_from_id(),
+                        proto.get_digest(),
+                    );
+                }
+                Rumor_Type::DigestFetch => {
+                    anti_entropy::process_digest_fetch(
+                        &self.server,
+                        &self.fetches,
+                        proto.get_from_id(),
+                        proto.get_digest(),
+                    );
+                }
                 Rumor_Type::Fake |
                 Rumor_Type::Fake2 => debug!("Nothing to do for fake rumor types"),
             }
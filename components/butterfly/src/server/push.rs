@@ -19,13 +19,9 @@
 
 use std::sync::atomic::Ordering;
 use std::thread;
-use std::time::Duration;
 
 use protobuf::Message;
-use time::SteadyTime;
-use zmq;
 
-use ZMQ_CONTEXT;
 use message::swim::{Rumor as ProtoRumor, Rumor_Type as ProtoRumor_Type, Member as ProtoMember,
                     Membership as ProtoMembership};
 use rumor::RumorKey;
@@ -33,6 +29,7 @@ use member::Member;
 use server::Server;
 use server::timing::Timing;
 use trace::TraceKind;
+use transport::GossipSender;
 
 const FANOUT: usize = 5;
 
@@ -59,7 +56,7 @@ impl Push {
     pub fn run(&mut self) {
         'send: loop {
             if self.server.pause.load(Ordering::Relaxed) {
-                thread::sleep(Duration::from_millis(100));
+                self.server.sleep_while_paused();
                 continue;
             }
 
@@ -118,25 +115,15 @@ impl Push {
                         |e| println!("Push worker died: {:?}", e),
                     );
                 }
-                if SteadyTime::now() < next_gossip {
-                    let wait_time = (next_gossip - SteadyTime::now()).num_milliseconds();
-                    if wait_time > 0 {
-                        thread::sleep(Duration::from_millis(wait_time as u64));
-                    }
-                }
-            }
-            if SteadyTime::now() < long_wait {
-                let wait_time = (long_wait - SteadyTime::now()).num_milliseconds();
-                if wait_time > 0 {
-                    thread::sleep(Duration::from_millis(wait_time as u64));
-                }
+                self.timing.sleep_until(next_gossip);
             }
+            self.timing.sleep_until(long_wait);
         }
     }
 }
 
 /// A worker thread for pushing messages to a target
-struct PushWorker {
+pub struct PushWorker {
     pub server: Server,
 }
 
@@ -146,37 +133,54 @@ impl PushWorker {
         PushWorker { server: server }
     }
 
-    /// Send the list of rumors to a given member. This method creates an outbound socket and then
-    /// closes the connection as soon as we are done sending rumors. ZeroMQ may choose to keep the
-    /// connection and socket open for 1 second longer - so it is possible, but unlikely, that this
-    /// method can loose messages.
-    fn send_rumors(&self, member: Member, rumors: Vec<RumorKey>) {
-        let socket = (**ZMQ_CONTEXT).as_mut().socket(zmq::PUSH).expect(
-            "Failure to create the ZMQ push socket",
-        );
-        socket.set_linger(1000).expect(
-            "Failure to set the ZMQ push socket to not linger",
-        );
-        socket.set_tcp_keepalive(0).expect(
-            "Failure to set the ZMQ push socket to not use keepalive",
-        );
-        socket.set_immediate(true).expect(
-            "Failure to set the ZMQ push socket to immediate",
-        );
-        socket.set_sndhwm(1000).expect(
-            "Failure to set the ZMQ push socket hwm",
-        );
-        socket.set_sndtimeo(500).expect(
-            "Failure to set the ZMQ send timeout",
-        );
-        let to_addr = format!("{}:{}", member.get_address(), member.get_gossip_port());
-        match socket.connect(&format!("tcp://{}", to_addr)) {
-            Ok(()) => debug!("Connected push socket to {:?}", member),
+    /// Send a single rumor that isn't kept in any rumor store, like an anti-entropy digest, to a
+    /// given member.
+    pub fn send_rumor(&self, member: Member, rumor: ProtoRumor) {
+        let (mut sender, to_addr) = match self.connect(&member) {
+            Some(connection) => connection,
+            None => return,
+        };
+        trace_it!(GOSSIP: &self.server, TraceKind::SendRumor, member.get_id(), &rumor);
+        let bytes = match rumor.write_to_bytes() {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                error!(
+                    "Could not write our own rumor to bytes; abandoning sending rumor: {:?}",
+                    e
+                );
+                return;
+            }
+        };
+        let payload = match self.server.generate_wire(bytes) {
+            Ok(payload) => payload,
             Err(e) => {
-                println!("Cannot connect push socket to {:?}: {:?}", member, e);
+                error!("Generating protobuf failed: {}", e);
                 return;
             }
+        };
+        match sender.send(&payload) {
+            Ok(()) => debug!("Sent {:?} to {:?}", rumor.get_field_type(), member),
+            Err(e) => {
+                warn!(
+                    "Could not send {:?} to {:?} @ {:?}: {}",
+                    rumor.get_field_type(),
+                    member.get_id(),
+                    to_addr,
+                    e
+                )
+            }
         }
+    }
+
+    /// Send the list of rumors to a given member. This method opens a connection through the
+    /// gossip transport and then closes it as soon as we are done sending rumors. ZeroMQ may choose
+    /// to keep the connection and socket open for 1 second longer - so it is possible, but
+    /// unlikely, that this method can loose messages.
+    pub fn send_rumors(&self, member: Member, rumors: Vec<RumorKey>) {
+        let (mut sender, to_addr) = match self.connect(&member) {
+            Some(connection) => connection,
+            None => return,
+        };
         'rumorlist: for ref rumor_key in rumors.iter() {
             let rumor_as_bytes = match rumor_key.kind {
                 ProtoRumor_Type::Member => {
@@ -277,6 +281,38 @@ impl PushWorker {
                         }
                     }
                 }
+                ProtoRumor_Type::Canary => {
+                    match self.server.canary_store.write_to_bytes(
+                        &rumor_key.key,
+                        &rumor_key.id,
+                    ) {
+                        Ok(bytes) => bytes,
+                        Err(e) => {
+                            println!(
+                                "Could not write our own rumor to bytes; abandoning \
+                                            sending rumor: {:?}",
+                                e
+                            );
+                            continue 'rumorlist;
+                        }
+                    }
+                }
+                ProtoRumor_Type::RingKey => {
+                    match self.server.ring_key_store.write_to_bytes(
+                        &rumor_key.key,
+                        &rumor_key.id,
+                    ) {
+                        Ok(bytes) => bytes,
+                        Err(e) => {
+                            println!(
+                                "Could not write our own rumor to bytes; abandoning \
+                                            sending rumor: {:?}",
+                                e
+                            );
+                            continue 'rumorlist;
+                        }
+                    }
+                }
                 ProtoRumor_Type::Election => {
                     // trace_it!(GOSSIP: &self.server,
                     //           TraceKind::SendRumor,
@@ -313,6 +349,12 @@ impl PushWorker {
                         }
                     }
                 }
+                ProtoRumor_Type::DigestRequest |
+                ProtoRumor_Type::Digest |
+                ProtoRumor_Type::DigestFetch => {
+                    debug!("Digests are sent directly, never gossiped");
+                    continue 'rumorlist;
+                }
                 ProtoRumor_Type::Fake |
                 ProtoRumor_Type::Fake2 => {
                     debug!("You have fake rumors; how odd!");
@@ -326,11 +368,11 @@ impl PushWorker {
                     continue 'rumorlist;
                 }
             };
-            match socket.send(&payload, 0) {
+            match sender.send(&payload) {
                 Ok(()) => debug!("Sent rumor {:?} to {:?}", rumor_key, member),
                 Err(e) => {
                     println!(
-                        "Could not send rumor to {:?} @ {:?}; ZMQ said: {:?}",
+                        "Could not send rumor to {:?} @ {:?}: {}",
                         member.get_id(),
                         to_addr,
                         e
@@ -341,6 +383,21 @@ impl PushWorker {
         self.server.rumor_heat.cool_rumors(member.get_id(), &rumors);
     }
 
+    /// Connects to the given member's gossip port through the server's gossip transport.
+    fn connect(&self, member: &Member) -> Option<(Box<GossipSender>, String)> {
+        let to_addr = format!("{}:{}", member.get_address(), member.get_gossip_port());
+        match self.server.gossip_transport.connect(&to_addr) {
+            Ok(sender) => {
+                debug!("Connected push socket to {:?}", member);
+                Some((sender, to_addr))
+            }
+            Err(e) => {
+                warn!("Cannot connect push socket to {:?}: {:?}", member, e);
+                None
+            }
+        }
+    }
+
     /// Given a rumorkey, creates a protobuf rumor for sharing.
     fn create_member_rumor(&self, rumor_key: &RumorKey) -> Option<ProtoRumor> {
         let mut member: Option<ProtoMember> = None;
@@ -361,6 +418,9 @@ impl PushWorker {
                 .unwrap()
                 .into(),
         );
+        if let Some(suspected_by) = self.server.member_list.suspected_by(&rumor_key.key()) {
+            membership.set_suspected_by(suspected_by);
+        }
         let mut rumor = ProtoRumor::new();
         rumor.set_field_type(ProtoRumor_Type::Member);
         rumor.set_member(membership);
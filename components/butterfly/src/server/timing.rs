@@ -12,19 +12,117 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+//! The timing of a server's protocol, and the clock it runs on.
+//!
+//! Every timer in a server - probe and gossip periods, suspicion and departure timeouts, the age
+//! of tombstones - reads its `Timing`'s clock. Outside of tests that is the `WallClock`; tests can
+//! run a ring on a `ManualClock`, which only moves when they advance it.
+
+use std::fmt;
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+
 use time::{SteadyTime, Duration as TimeDuration};
 
 /// How long to wait for an Ack after we ping
 const PING_TIMING_DEFAULT_MS: i64 = 1000;
 /// How long to wait for an Ack after we PingReq - should be at least 2x the PING_TIMING_DEFAULT_MS
 const PINGREQ_TIMING_DEFAULT_MS: i64 = 2100;
-/// How many protocol periods before a suspect member is marked as confirmed.
+/// How many protocol periods before a suspect member is marked as confirmed, once enough other
+/// members have confirmed the suspicion.
 const SUSPICION_TIMEOUT_DEFAULT_PROTOCOL_PERIODS: i64 = 3;
+/// How many times longer than the minimum suspicion timeout we wait when nobody else has
+/// confirmed the suspicion.
+const SUSPICION_MAX_TIMEOUT_DEFAULT_MULTIPLIER: i64 = 6;
+/// How many independent confirmations shrink the suspicion timeout all the way to its minimum.
+const SUSPICION_CONFIRMATIONS_DEFAULT: usize = 3;
 /// How long is the gossip period
 const GOSSIP_PERIOD_DEFAULT_MS: i64 = 1000;
 /// How long before we set a confirmed member to a departed member, removing them from quorums
 ///   just for your own sanity - this is 3 days.
 const DEPARTURE_TIMEOUT_DEFAULT_MS: i64 = 259200000;
+/// How long a member has to be confirmed or departed before we forget the rumors it owned, and
+/// how long we remember those rumors were forgotten - this is 1 day.
+const RUMOR_TTL_DEFAULT_MS: i64 = 86400000;
+/// How often we compare our rumors against a random peer's, even when nothing prompted us to -
+/// this is 5 minutes.
+const ANTI_ENTROPY_PERIOD_DEFAULT_MS: i64 = 300000;
+
+/// The source of time for a server's timers.
+pub trait Clock: fmt::Debug + Send + Sync {
+    /// The current time.
+    fn now(&self) -> SteadyTime;
+
+    /// Blocks the calling thread until the clock has moved on by `duration`.
+    fn sleep(&self, duration: TimeDuration);
+}
+
+/// The clock every server runs on outside of tests.
+#[derive(Debug, Default)]
+pub struct WallClock;
+
+impl Clock for WallClock {
+    fn now(&self) -> SteadyTime {
+        SteadyTime::now()
+    }
+
+    fn sleep(&self, duration: TimeDuration) {
+        if let Ok(duration) = duration.to_std() {
+            thread::sleep(duration);
+        }
+    }
+}
+
+/// A clock which stands still until `advance` is called. Threads sleeping on it wake up once it
+/// has been advanced far enough, however much real time that takes.
+#[derive(Debug)]
+pub struct ManualClock {
+    start: SteadyTime,
+    elapsed_ms: Mutex<i64>,
+    moved: Condvar,
+}
+
+impl ManualClock {
+    pub fn new() -> ManualClock {
+        ManualClock {
+            start: SteadyTime::now(),
+            elapsed_ms: Mutex::new(0),
+            moved: Condvar::new(),
+        }
+    }
+
+    /// Moves the clock forward by `ms` milliseconds, waking every thread whose sleep is over.
+    pub fn advance(&self, ms: i64) {
+        let mut elapsed_ms = self.elapsed_ms.lock().expect("Clock lock is poisoned");
+        *elapsed_ms += ms;
+        self.moved.notify_all();
+    }
+
+    /// How far the clock has been advanced, in milliseconds.
+    pub fn elapsed_ms(&self) -> i64 {
+        *self.elapsed_ms.lock().expect("Clock lock is poisoned")
+    }
+}
+
+impl Default for ManualClock {
+    fn default() -> ManualClock {
+        ManualClock::new()
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> SteadyTime {
+        self.start + TimeDuration::milliseconds(self.elapsed_ms())
+    }
+
+    fn sleep(&self, duration: TimeDuration) {
+        let mut elapsed_ms = self.elapsed_ms.lock().expect("Clock lock is poisoned");
+        let wake_at = *elapsed_ms + duration.num_milliseconds();
+        while *elapsed_ms < wake_at {
+            elapsed_ms = self.moved.wait(elapsed_ms).expect("Clock lock is poisoned");
+        }
+    }
+}
 
 /// The timing of the outbound threads.
 #[derive(Debug, Clone)]
@@ -33,7 +131,12 @@ pub struct Timing {
     pub pingreq_ms: i64,
     pub gossip_period_ms: i64,
     pub suspicion_timeout_protocol_periods: i64,
+    pub suspicion_max_timeout_multiplier: i64,
+    pub suspicion_confirmations: usize,
     pub departure_timeout_ms: i64,
+    pub rumor_ttl_ms: i64,
+    pub anti_entropy_period_ms: i64,
+    pub clock: Arc<Clock>,
 }
 
 impl Default for Timing {
@@ -43,7 +146,12 @@ impl Default for Timing {
             pingreq_ms: PINGREQ_TIMING_DEFAULT_MS,
             gossip_period_ms: GOSSIP_PERIOD_DEFAULT_MS,
             suspicion_timeout_protocol_periods: SUSPICION_TIMEOUT_DEFAULT_PROTOCOL_PERIODS,
+            suspicion_max_timeout_multiplier: SUSPICION_MAX_TIMEOUT_DEFAULT_MULTIPLIER,
+            suspicion_confirmations: SUSPICION_CONFIRMATIONS_DEFAULT,
             departure_timeout_ms: DEPARTURE_TIMEOUT_DEFAULT_MS,
+            rumor_ttl_ms: RUMOR_TTL_DEFAULT_MS,
+            anti_entropy_period_ms: ANTI_ENTROPY_PERIOD_DEFAULT_MS,
+            clock: Arc::new(WallClock),
         }
     }
 }
@@ -62,13 +170,36 @@ impl Timing {
             pingreq_ms: pingreq_ms,
             gossip_period_ms: gossip_period_ms,
             suspicion_timeout_protocol_periods: suspicion_timeout_protocol_periods,
+            suspicion_max_timeout_multiplier: SUSPICION_MAX_TIMEOUT_DEFAULT_MULTIPLIER,
+            suspicion_confirmations: SUSPICION_CONFIRMATIONS_DEFAULT,
             departure_timeout_ms: departure_timeout_ms,
+            rumor_ttl_ms: RUMOR_TTL_DEFAULT_MS,
+            anti_entropy_period_ms: ANTI_ENTROPY_PERIOD_DEFAULT_MS,
+            clock: Arc::new(WallClock),
+        }
+    }
+
+    /// The current time on our clock.
+    pub fn now(&self) -> SteadyTime {
+        self.clock.now()
+    }
+
+    /// Blocks until our clock reaches `when`; returns right away if it already has.
+    pub fn sleep_until(&self, when: SteadyTime) {
+        let now = self.now();
+        if now < when {
+            self.clock.sleep(when - now);
         }
     }
 
+    /// Blocks for `ms` milliseconds of our clock's time.
+    pub fn sleep_ms(&self, ms: i64) {
+        self.clock.sleep(TimeDuration::milliseconds(ms));
+    }
+
     /// When should this gossip period expire
     pub fn gossip_timeout(&self) -> SteadyTime {
-        SteadyTime::now() + TimeDuration::milliseconds(self.gossip_period_ms)
+        self.now() + TimeDuration::milliseconds(self.gossip_period_ms)
     }
 
     /// How long is a protocol period, in millis.
@@ -76,29 +207,124 @@ impl Timing {
         self.ping_ms + self.pingreq_ms
     }
 
-    /// When should this ping record time out?
-    pub fn ping_timeout(&self) -> SteadyTime {
-        SteadyTime::now() + TimeDuration::milliseconds(self.ping_ms)
+    /// When should this ping record time out? The timeout is stretched by our local health score,
+    /// so that a slow Supervisor gives its peers longer to answer before suspecting them.
+    pub fn ping_timeout(&self, local_health: usize) -> SteadyTime {
+        self.now() + TimeDuration::milliseconds(self.ping_ms * (local_health as i64 + 1))
     }
 
-    /// When should this pingreq timeout?
-    pub fn pingreq_timeout(&self) -> SteadyTime {
-        SteadyTime::now() + TimeDuration::milliseconds(self.pingreq_ms)
+    /// When should this pingreq timeout? Stretched by our local health score, like
+    /// `ping_timeout`.
+    pub fn pingreq_timeout(&self, local_health: usize) -> SteadyTime {
+        self.now() + TimeDuration::milliseconds(self.pingreq_ms * (local_health as i64 + 1))
     }
 
-    /// How long before the next scheduled protocol period
-    pub fn next_protocol_period(&self) -> SteadyTime {
-        SteadyTime::now() + TimeDuration::milliseconds(self.ping_ms + self.pingreq_ms)
+    /// How long before the next scheduled protocol period; stretched by our local health score.
+    pub fn next_protocol_period(&self, local_health: usize) -> SteadyTime {
+        self.now() +
+            TimeDuration::milliseconds(self.protocol_period_ms() * (local_health as i64 + 1))
     }
 
-    /// How long before this suspect entry times out
-    pub fn suspicion_timeout_duration(&self) -> TimeDuration {
-        TimeDuration::milliseconds(
-            self.protocol_period_ms() * self.suspicion_timeout_protocol_periods,
-        )
+    /// How long before this suspect entry times out, given how many other members have confirmed
+    /// the suspicion.
+    ///
+    /// With no confirmations we wait `suspicion_max_timeout_multiplier` times the minimum; every
+    /// independent confirmation shrinks the timeout logarithmically, reaching the minimum once
+    /// `suspicion_confirmations` members agree.
+    pub fn suspicion_timeout_duration(&self, confirmations: usize) -> TimeDuration {
+        let min = self.protocol_period_ms() * self.suspicion_timeout_protocol_periods;
+        let max = min * self.suspicion_max_timeout_multiplier;
+        if self.suspicion_confirmations == 0 || max <= min {
+            return TimeDuration::milliseconds(min);
+        }
+        let fraction = ((confirmations as f64) + 1.0).ln() /
+            ((self.suspicion_confirmations as f64) + 1.0).ln();
+        let timeout = max - ((max - min) as f64 * fraction).floor() as i64;
+        TimeDuration::milliseconds(if timeout < min { min } else { timeout })
     }
 
     pub fn departure_timeout_duration(&self) -> TimeDuration {
         TimeDuration::milliseconds(self.departure_timeout_ms)
     }
+
+    pub fn rumor_ttl_duration(&self) -> TimeDuration {
+        TimeDuration::milliseconds(self.rumor_ttl_ms)
+    }
+
+    pub fn anti_entropy_period_duration(&self) -> TimeDuration {
+        TimeDuration::milliseconds(self.anti_entropy_period_ms)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::sync::mpsc;
+    use std::thread;
+    use std::time::Duration;
+
+    use super::{Clock, ManualClock, Timing};
+
+    #[test]
+    fn suspicion_timeout_starts_at_the_maximum() {
+        let timing = Timing::default();
+        let min = timing.protocol_period_ms() * timing.suspicion_timeout_protocol_periods;
+        assert_eq!(
+            timing.suspicion_timeout_duration(0).num_milliseconds(),
+            min * timing.suspicion_max_timeout_multiplier
+        );
+    }
+
+    #[test]
+    fn suspicion_timeout_shrinks_with_confirmations() {
+        let timing = Timing::default();
+        let none = timing.suspicion_timeout_duration(0);
+        let one = timing.suspicion_timeout_duration(1);
+        let two = timing.suspicion_timeout_duration(2);
+        assert!(one < none);
+        assert!(two < one);
+    }
+
+    #[test]
+    fn suspicion_timeout_never_drops_below_the_minimum() {
+        let timing = Timing::default();
+        let min = timing.protocol_period_ms() * timing.suspicion_timeout_protocol_periods;
+        assert_eq!(
+            timing.suspicion_timeout_duration(3).num_milliseconds(),
+            min
+        );
+        assert_eq!(
+            timing.suspicion_timeout_duration(100).num_milliseconds(),
+            min
+        );
+    }
+
+    #[test]
+    fn manual_clock_only_moves_when_advanced() {
+        let clock = ManualClock::new();
+        let start = clock.now();
+        thread::sleep(Duration::from_millis(10));
+        assert_eq!(clock.now(), start);
+        clock.advance(1500);
+        assert_eq!((clock.now() - start).num_milliseconds(), 1500);
+    }
+
+    #[test]
+    fn timers_on_a_manual_clock_wait_for_it_to_advance() {
+        let clock = Arc::new(ManualClock::new());
+        let mut timing = Timing::default();
+        timing.clock = clock.clone();
+        let timeout = timing.ping_timeout(0);
+        let (tx, rx) = mpsc::channel();
+        let sleeper = timing.clone();
+        thread::spawn(move || {
+            sleeper.sleep_until(timeout);
+            tx.send(()).unwrap();
+        });
+        clock.advance(timing.ping_ms - 1);
+        assert!(rx.recv_timeout(Duration::from_millis(50)).is_err());
+        clock.advance(1);
+        assert!(rx.recv_timeout(Duration::from_millis(1000)).is_ok());
+        assert!(timing.now() >= timeout);
+    }
 }
@@ -14,19 +14,28 @@
 
 //! This module handles the writing of swim trace files, which can later be post-processed to see
 //! whats happening in a network.
+//!
+//! Every member writes its own trace file, with one JSON object per line. The functions at the
+//! bottom of this module read those files back, merge the events of several members into a single
+//! timeline, and render it as text or in the Chrome trace-event format.
 
 use time;
 
+use std::collections::HashMap;
 use std::default::Default;
 use std::env;
 use std::fmt;
 use std::fs;
-use std::path::PathBuf;
-use std::io::Write;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
 
+use serde_json::{self, Value};
+
+use error::{Error, Result};
+use message::swim::{Rumor as ProtoRumor, Rumor_Type};
 use server::Server;
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum TraceKind {
     MemberUpdate,
     ProbeBegin,
@@ -73,20 +82,45 @@ impl fmt::Display for TraceKind {
     }
 }
 
-#[derive(Debug)]
+impl TraceKind {
+    /// The protocol the event belongs to.
+    pub fn category(&self) -> &'static str {
+        match *self {
+            TraceKind::RecvRumor | TraceKind::SendRumor => "gossip",
+            TraceKind::TestEvent => "test",
+            _ => "swim",
+        }
+    }
+}
+
+/// A single event, as it is written to a trace file.
+#[derive(Debug, Serialize)]
 pub struct TraceWrite<'a> {
     pub kind: TraceKind,
-    pub time: String,
+    /// Microseconds since the Unix epoch.
+    pub timestamp_us: i64,
     pub module_path: &'a str,
     pub line: u32,
     pub thread_name: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub server_name: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub member_id: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub to_member_id: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub listening: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub to_addr: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub swim: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub rumor: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rumor_key: Option<&'a str>,
+    /// The SWIM round for SWIM events, or the gossip round for gossip events.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub round: Option<isize>,
 }
 
 impl<'a> TraceWrite<'a> {
@@ -96,20 +130,10 @@ impl<'a> TraceWrite<'a> {
         line: u32,
         thread_name: &'a str,
     ) -> TraceWrite<'a> {
-        let now = time::now_utc();
-        let time_string = format!(
-            "{}-{}-{}-{}-{}-{}-{}",
-            1900 + now.tm_year,
-            now.tm_mon + 1,
-            now.tm_mday,
-            now.tm_hour,
-            now.tm_min,
-            now.tm_sec,
-            now.tm_nsec
-        );
+        let now = time::get_time();
         TraceWrite {
             kind: kind,
-            time: time_string,
+            timestamp_us: now.sec * 1_000_000 + (now.nsec / 1_000) as i64,
             module_path: module_path,
             line: line,
             thread_name: thread_name,
@@ -120,25 +144,57 @@ impl<'a> TraceWrite<'a> {
             to_addr: None,
             swim: None,
             rumor: None,
+            rumor_key: None,
+            round: None,
         }
     }
 }
 
-impl<'a> fmt::Display for TraceWrite<'a> {
+/// A single event, as it is read back from a trace file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TraceRecord {
+    pub kind: TraceKind,
+    pub timestamp_us: i64,
+    pub module_path: String,
+    pub line: u32,
+    pub thread_name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub server_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub member_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to_member_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub listening: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to_addr: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub swim: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rumor: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rumor_key: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub round: Option<isize>,
+}
+
+/// Renders the event as a line of `^` separated fields, which `bin/trace-sequence.rb` turns into
+/// a sequence diagram.
+impl fmt::Display for TraceRecord {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.time)?;
+        let none = String::new();
+        write!(f, "{}", self.timestamp_us)?;
         write!(f, "^{}", self.kind)?;
         write!(f, "^{}", self.thread_name)?;
         write!(f, "^{}", self.module_path)?;
         write!(f, "^{}", self.line)?;
-        write!(f, "^{}", self.server_name.unwrap_or(""))?;
-        write!(f, "^{}", self.member_id.unwrap_or(""))?;
-        write!(f, "^{}", self.to_member_id.unwrap_or(""))?;
-        write!(f, "^{}", self.listening.unwrap_or(""))?;
-        write!(f, "^{}", self.to_addr.unwrap_or(""))?;
-        write!(f, "^{}", self.swim.unwrap_or(""))?;
-        write!(f, "^{}", self.rumor.unwrap_or(""))?;
-        write!(f, "\n")
+        write!(f, "^{}", self.server_name.as_ref().unwrap_or(&none))?;
+        write!(f, "^{}", self.member_id.as_ref().unwrap_or(&none))?;
+        write!(f, "^{}", self.to_member_id.as_ref().unwrap_or(&none))?;
+        write!(f, "^{}", self.listening.as_ref().unwrap_or(&none))?;
+        write!(f, "^{}", self.to_addr.as_ref().unwrap_or(&none))?;
+        write!(f, "^{}", self.swim.as_ref().unwrap_or(&none))?;
+        write!(f, "^{}", self.rumor.as_ref().unwrap_or(&none))
     }
 }
 
@@ -165,7 +221,7 @@ impl Trace {
     pub fn init(&mut self, server: &Server) {
         if self.file.is_none() {
             let now = time::now_utc();
-            let filename = format!("{}-{}.swimtrace", server.name(), now.rfc3339());
+            let filename = format!("{}-{}.ndjson", server.name(), now.rfc3339());
             match fs::create_dir_all(&self.directory) {
                 Ok(_) => {
                     match fs::File::create(self.directory.join(&filename)) {
@@ -201,9 +257,13 @@ impl Trace {
     /// Write a line to the trace file.
     pub fn write(&mut self, trace_write: TraceWrite) {
         let dump = format!("{:#?}", self);
+        let line = match serde_json::to_string(&trace_write) {
+            Ok(line) => line,
+            Err(e) => panic!("Trace requested, but failed to encode {:?}: {}", trace_write, e),
+        };
         match self.file.as_mut() {
             Some(file) => {
-                match write!(file, "{}", trace_write) {
+                match write!(file, "{}\n", line) {
                     Ok(_) => {}
                     Err(e) => panic!("Trace requested, but failed to write {:?}", e),
                 }
@@ -219,6 +279,129 @@ impl Trace {
     }
 }
 
+/// Returns a key identifying the rumor a gossip event is about, so that events about the same
+/// rumor can be followed across members.
+pub fn rumor_key(rumor: &ProtoRumor) -> String {
+    match rumor.get_field_type() {
+        Rumor_Type::Member => format!("Member/{}", rumor.get_member().get_member().get_id()),
+        Rumor_Type::Service => {
+            format!(
+                "Service/{}/{}",
+                rumor.get_service().get_service_group(),
+                rumor.get_service().get_member_id()
+            )
+        }
+        Rumor_Type::ServiceConfig => {
+            format!(
+                "ServiceConfig/{}",
+                rumor.get_service_config().get_service_group()
+            )
+        }
+        Rumor_Type::ServiceFile => {
+            format!(
+                "ServiceFile/{}/{}",
+                rumor.get_service_file().get_service_group(),
+                rumor.get_service_file().get_filename()
+            )
+        }
+        Rumor_Type::Election => {
+            format!("Election/{}", rumor.get_election().get_service_group())
+        }
+        Rumor_Type::ElectionUpdate => {
+            format!(
+                "ElectionUpdate/{}",
+                rumor.get_election().get_service_group()
+            )
+        }
+        Rumor_Type::Departure => format!("Departure/{}", rumor.get_departure().get_member_id()),
+        Rumor_Type::Canary => format!("Canary/{}", rumor.get_canary().get_service_group()),
+        Rumor_Type::RingKey => {
+            format!(
+                "RingKey/{}-{}",
+                rumor.get_ring_key().get_name(),
+                rumor.get_ring_key().get_revision()
+            )
+        }
+        kind => format!("{:?}", kind),
+    }
+}
+
+/// Read back every event in a trace file.
+pub fn read_trace_file<P: AsRef<Path>>(path: P) -> Result<Vec<TraceRecord>> {
+    let path = path.as_ref();
+    let file = fs::File::open(path).map_err(
+        |e| Error::TraceFileIO(path.to_path_buf(), e),
+    )?;
+    let mut records = Vec::new();
+    for (number, line) in BufReader::new(file).lines().enumerate() {
+        let line = line.map_err(|e| Error::TraceFileIO(path.to_path_buf(), e))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let record = serde_json::from_str(&line).map_err(|e| {
+            Error::BadTraceFile(path.to_path_buf(), number + 1, e)
+        })?;
+        records.push(record);
+    }
+    Ok(records)
+}
+
+/// Merge the events of several trace files into a single timeline, ordered by time. Events that
+/// happened in the same microsecond keep the order they had in their own file.
+pub fn merge(traces: Vec<Vec<TraceRecord>>) -> Vec<TraceRecord> {
+    let mut timeline: Vec<TraceRecord> = traces.into_iter().flat_map(|t| t.into_iter()).collect();
+    timeline.sort_by_key(|r| r.timestamp_us);
+    timeline
+}
+
+/// Render a timeline in the Chrome trace-event format, which `chrome://tracing` and similar
+/// viewers can open. Every member gets a process of its own, and every thread a track.
+pub fn to_chrome_trace(timeline: &[TraceRecord]) -> Value {
+    let mut pids: HashMap<String, usize> = HashMap::new();
+    let mut events = Vec::new();
+    for record in timeline.iter() {
+        let member = record
+            .member_id
+            .clone()
+            .or(record.server_name.clone())
+            .unwrap_or(String::from("unknown"));
+        let next_pid = pids.len() + 1;
+        let pid = *pids.entry(member.clone()).or_insert_with(|| {
+            events.push(json!({
+                "name": "process_name",
+                "ph": "M",
+                "pid": next_pid,
+                "args": {
+                    "name": match record.server_name {
+                        Some(ref name) => format!("{} {}", name, member),
+                        None => member.clone(),
+                    }
+                }
+            }));
+            next_pid
+        });
+        events.push(json!({
+            "name": format!("{}", record.kind),
+            "cat": record.kind.category(),
+            "ph": "i",
+            "s": "t",
+            "ts": record.timestamp_us,
+            "pid": pid,
+            "tid": record.thread_name,
+            "args": {
+                "peer": record.to_member_id,
+                "rumor_key": record.rumor_key,
+                "round": record.round,
+                "to_addr": record.to_addr,
+                "swim": record.swim,
+                "rumor": record.rumor,
+                "source": format!("{}:{}", record.module_path, record.line),
+            }
+        }));
+    }
+    json!({ "traceEvents": events, "displayTimeUnit": "ms" })
+}
+
 #[macro_export]
 macro_rules! trace_it {
     (TEST: $server:expr, $payload:expr) => {
@@ -289,11 +472,14 @@ macro_rules! trace_it {
                 let member_id = $server.member_id();
                 let server_name = $server.name();
                 let rumor_text = format!("{}-{}-{}", $member_id, $mem_incar, $health);
+                let rumor_key = format!("Member/{}", $member_id);
 
                 let mut tw = TraceWrite::new($msg_type, module_path!(), line!(), thread_name);
                 tw.server_name = Some(&server_name);
                 tw.member_id = Some(member_id);
                 tw.rumor = Some(&rumor_text);
+                tw.rumor_key = Some(&rumor_key);
+                tw.round = Some($server.swim_rounds());
                 trace.write(tw);
             }
         }
@@ -321,6 +507,7 @@ macro_rules! trace_it {
                 tw.to_addr = Some(&to_addr);
                 tw.swim = None;
                 tw.rumor = None;
+                tw.round = Some($server.swim_rounds());
                 trace.write(tw);
             }
         }
@@ -354,6 +541,7 @@ macro_rules! trace_it {
                 tw.to_addr = Some(&to_addr);
                 tw.swim = Some(&swim_str);
                 tw.rumor = None;
+                tw.round = Some($server.swim_rounds());
                 trace.write(tw);
             }
         }
@@ -363,7 +551,7 @@ macro_rules! trace_it {
             let trace_on = $server.trace.read().expect("Trace lock is poisoned").on();
             if trace_on {
                 let mut trace = $server.trace.write().expect("Trace lock is poisoned");
-                use trace::TraceWrite;
+                use trace::{self, TraceWrite};
                 use message::swim::Rumor_Type;
                 trace.init($server);
                 let thread = thread::current();
@@ -409,8 +597,28 @@ macro_rules! trace_it {
                     Rumor_Type::Departure => {
                         format!("{}", $payload.get_departure().get_member_id())
                     }
+                    Rumor_Type::Canary => {
+                        format!("{}-{}-{}-{:?}",
+                                $payload.get_canary().get_service_group(),
+                                $payload.get_canary().get_incarnation(),
+                                $payload.get_canary().get_pkg(),
+                                $payload.get_canary().get_status())
+                    }
+                    Rumor_Type::RingKey => {
+                        format!("{}-{}-{}",
+                                $payload.get_ring_key().get_name(),
+                                $payload.get_ring_key().get_revision(),
+                                $payload.get_ring_key().get_activate_at())
+                    }
+                    Rumor_Type::DigestRequest => format!("digest-request"),
+                    Rumor_Type::Digest | Rumor_Type::DigestFetch => {
+                        format!("{:?}-{}",
+                                $payload.get_field_type(),
+                                $payload.get_digest().get_rumors().len())
+                    }
                     Rumor_Type::Fake | Rumor_Type::Fake2 => format!("nothing-to-see"),
                 };
+                let rumor_key = trace::rumor_key(&$payload);
 
                 let mut tw = TraceWrite::new($msg_type, module_path!(), line!(), thread_name);
                 tw.server_name = Some(&server_name);
@@ -419,6 +627,8 @@ macro_rules! trace_it {
                 tw.listening = Some(&listening);
                 tw.swim = None;
                 tw.rumor = Some(&rp);
+                tw.rumor_key = Some(&rumor_key);
+                tw.round = Some($server.gossip_rounds());
                 trace.write(tw);
             }
         }
@@ -437,4 +647,62 @@ mod tests {
             assert_eq!(trace.directory, Path::new("/tmp/habitat-swim-trace"));
         }
     }
+
+    mod timeline {
+        use serde_json;
+
+        use trace::{self, TraceKind, TraceRecord, TraceWrite};
+
+        fn record(member_id: &str, timestamp_us: i64) -> TraceRecord {
+            let mut tw = TraceWrite::new(TraceKind::SendRumor, "tests", 1, "push-a");
+            tw.timestamp_us = timestamp_us;
+            tw.member_id = Some(member_id);
+            tw.rumor_key = Some("Member/a");
+            tw.round = Some(3);
+            serde_json::from_str(&serde_json::to_string(&tw).unwrap()).unwrap()
+        }
+
+        #[test]
+        fn written_events_read_back() {
+            let r = record("a", 42);
+            assert_eq!(r.kind, TraceKind::SendRumor);
+            assert_eq!(r.timestamp_us, 42);
+            assert_eq!(r.member_id, Some(String::from("a")));
+            assert_eq!(r.rumor_key, Some(String::from("Member/a")));
+            assert_eq!(r.round, Some(3));
+            assert_eq!(r.to_member_id, None);
+        }
+
+        #[test]
+        fn merge_orders_events_by_time() {
+            let a = vec![record("a", 10), record("a", 30)];
+            let b = vec![record("b", 20), record("b", 30)];
+            let merged: Vec<(String, i64)> = trace::merge(vec![a, b])
+                .into_iter()
+                .map(|r| (r.member_id.unwrap(), r.timestamp_us))
+                .collect();
+            assert_eq!(
+                merged,
+                vec![
+                    (String::from("a"), 10),
+                    (String::from("b"), 20),
+                    (String::from("a"), 30),
+                    (String::from("b"), 30),
+                ]
+            );
+        }
+
+        #[test]
+        fn chrome_trace_gives_each_member_a_process() {
+            let timeline = trace::merge(vec![vec![record("a", 10)], vec![record("b", 20)]]);
+            let chrome = trace::to_chrome_trace(&timeline);
+            let events = chrome["traceEvents"].as_array().unwrap();
+            assert_eq!(events.len(), 4);
+            assert_eq!(events[0]["ph"], "M");
+            assert_eq!(events[1]["pid"], 1);
+            assert_eq!(events[1]["args"]["rumor_key"], "Member/a");
+            assert_eq!(events[3]["pid"], 2);
+            assert_eq!(events[3]["ts"], 20);
+        }
+    }
 }
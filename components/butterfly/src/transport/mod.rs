@@ -0,0 +1,194 @@
+// Copyright (c) 2017 Chef Software Inc. and/or applicable contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! SWIM and gossip transports.
+//!
+//! The push and pull threads, and the butterfly client, move already encoded wire messages
+//! between members through a `GossipTransport`. A transport only has to deliver whole messages,
+//! one at a time, from many senders to a single listener; it never has to answer them. Every
+//! member of a ring, and every client talking to it, has to use the same transport.
+//!
+//! Two gossip transports are provided, and picked by name with `GossipTransportKind`:
+//!
+//! * `ZeroMqTransport`, which uses ZeroMQ PUSH and PULL sockets. This is the default, and is only
+//!   built with the `zeromq` feature.
+//! * `TcpTransport`, which sends each message as a length-prefixed frame over a plain TCP
+//!   stream, and needs no native libraries. This is the default when built without ZeroMQ.
+//!
+//! The SWIM inbound and outbound threads send their probes over a `SwimSocket`, bound through a
+//! `SwimTransport`; `UdpTransport` is the only one provided. Both traits exist so that tests can
+//! run whole rings over an in-process, simulated network.
+
+pub mod tcp;
+pub mod udp;
+#[cfg(feature = "zeromq")]
+pub mod zeromq;
+
+use std::fmt::{self, Debug};
+use std::io;
+use std::net::SocketAddr;
+use std::result;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use error::{Error, Result};
+
+pub use self::tcp::TcpTransport;
+pub use self::udp::UdpTransport;
+#[cfg(feature = "zeromq")]
+pub use self::zeromq::ZeroMqTransport;
+
+/// How long a closed ZeroMQ push socket keeps trying to deliver its pending messages, in
+/// milliseconds.
+pub const LINGER_DEFAULT_MS: i32 = 1000;
+
+/// The gossip transports a member or client can be configured with, by name.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GossipTransportKind {
+    ZeroMq,
+    Tcp,
+}
+
+impl GossipTransportKind {
+    /// Creates a transport of this kind. `linger` is how long a ZeroMQ sender keeps trying to
+    /// deliver its pending messages once closed, in milliseconds; -1 waits for all of them.
+    ///
+    /// # Errors
+    ///
+    /// * Returns `Error::UnknownGossipTransport` for ZeroMQ if it wasn't built in
+    pub fn transport(&self, linger: i32) -> Result<Box<GossipTransport>> {
+        match *self {
+            GossipTransportKind::ZeroMq => zeromq_transport(linger),
+            GossipTransportKind::Tcp => Ok(Box::new(TcpTransport)),
+        }
+    }
+}
+
+#[cfg(feature = "zeromq")]
+fn zeromq_transport(linger: i32) -> Result<Box<GossipTransport>> {
+    Ok(Box::new(ZeroMqTransport::new(linger)))
+}
+
+#[cfg(not(feature = "zeromq"))]
+fn zeromq_transport(_linger: i32) -> Result<Box<GossipTransport>> {
+    Err(Error::UnknownGossipTransport(
+        GossipTransportKind::ZeroMq.to_string(),
+    ))
+}
+
+impl Default for GossipTransportKind {
+    #[cfg(feature = "zeromq")]
+    fn default() -> GossipTransportKind {
+        GossipTransportKind::ZeroMq
+    }
+
+    #[cfg(not(feature = "zeromq"))]
+    fn default() -> GossipTransportKind {
+        GossipTransportKind::Tcp
+    }
+}
+
+impl FromStr for GossipTransportKind {
+    type Err = Error;
+
+    fn from_str(value: &str) -> result::Result<Self, Self::Err> {
+        match value {
+            "zeromq" => Ok(GossipTransportKind::ZeroMq),
+            "tcp" => Ok(GossipTransportKind::Tcp),
+            _ => Err(Error::UnknownGossipTransport(value.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for GossipTransportKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let value = match *self {
+            GossipTransportKind::ZeroMq => "zeromq",
+            GossipTransportKind::Tcp => "tcp",
+        };
+        write!(f, "{}", value)
+    }
+}
+
+/// A way of delivering gossip messages to members.
+pub trait GossipTransport: Debug + Send + Sync {
+    /// Start listening for inbound messages on `addr`.
+    fn listen(&self, addr: &SocketAddr) -> Result<Box<GossipListener>>;
+
+    /// Open a connection for sending messages to the member listening on `addr`, given as
+    /// `host:port`.
+    fn connect(&self, addr: &str) -> Result<Box<GossipSender>>;
+}
+
+impl<T: GossipTransport + ?Sized> GossipTransport for Box<T> {
+    fn listen(&self, addr: &SocketAddr) -> Result<Box<GossipListener>> {
+        (**self).listen(addr)
+    }
+
+    fn connect(&self, addr: &str) -> Result<Box<GossipSender>> {
+        (**self).connect(addr)
+    }
+}
+
+/// The receiving end of a transport, fair-queuing messages from every sender.
+pub trait GossipListener: Send {
+    /// Block until the next message arrives, and return it.
+    fn recv(&mut self) -> Result<Vec<u8>>;
+}
+
+/// The sending end of a transport, connected to a single listener.
+pub trait GossipSender: Send {
+    /// Send a single message.
+    fn send(&mut self, payload: &[u8]) -> Result<()>;
+}
+
+/// A way of binding the socket SWIM probes are sent and received on.
+pub trait SwimTransport: Debug + Send + Sync {
+    /// Bind a socket to `addr`. Reads from the socket must time out, rather than block forever,
+    /// so the inbound thread can notice when it has been paused.
+    fn bind(&self, addr: &SocketAddr) -> Result<Arc<SwimSocket>>;
+}
+
+/// A bound SWIM socket, shared by the inbound and outbound threads. Datagrams may be lost.
+pub trait SwimSocket: Debug + Send + Sync {
+    /// Send a single datagram to `addr`.
+    fn send_to(&self, buf: &[u8], addr: SocketAddr) -> io::Result<usize>;
+
+    /// Wait for the next datagram, returning its length and where it came from. Returns an
+    /// error of kind `WouldBlock` or `TimedOut` if nothing arrived in time.
+    fn recv_from(&self, buf: &mut [u8]) -> io::Result<(usize, SocketAddr)>;
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::GossipTransportKind;
+
+    #[test]
+    fn gossip_transport_kind_round_trips_through_its_name() {
+        for kind in vec![GossipTransportKind::ZeroMq, GossipTransportKind::Tcp] {
+            assert_eq!(
+                GossipTransportKind::from_str(&kind.to_string()).unwrap(),
+                kind
+            );
+        }
+        assert!(GossipTransportKind::from_str("carrier-pigeon").is_err());
+    }
+
+    #[test]
+    fn tcp_transport_is_always_available() {
+        assert!(GossipTransportKind::Tcp.transport(-1).is_ok());
+    }
+}
@@ -0,0 +1,280 @@
+// Copyright (c) 2017 Chef Software Inc. and/or applicable contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The plain TCP gossip transport.
+//!
+//! Every message is written as a frame: its length as a big-endian `u32`, followed by the message
+//! itself. The listener accepts connections on a background thread, and reads each connection on
+//! a thread of its own, queueing whole messages for `recv` in the order they arrive. At most
+//! `MAX_CONNECTIONS` connections are read at once; any more are closed as soon as they arrive.
+//! A connection which sends nothing for `IDLE_TIMEOUT_MS` is closed, so that peers which went
+//! away without hanging up don't hold on to a reader forever.
+
+use std::io::{self, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+use std::time::Duration;
+
+use byteorder::{BigEndian, ByteOrder};
+
+use error::{Error, Result};
+use transport::{GossipListener, GossipSender, GossipTransport};
+
+/// The largest message we are willing to read; anything bigger closes the connection. Rumors are
+/// small, and even service files are kept well under this.
+const MAX_FRAME_BYTES: usize = 4 * 1024 * 1024;
+/// The most connections we read from at once, each on a thread of its own.
+const MAX_CONNECTIONS: usize = 256;
+/// How long to wait for a connection to be established.
+const CONNECT_TIMEOUT_MS: u64 = 1000;
+/// How long to wait for a single message to be written.
+const SEND_TIMEOUT_MS: u64 = 500;
+/// How long an accepted connection may stay silent before we close it.
+const IDLE_TIMEOUT_MS: u64 = 30000;
+
+#[derive(Debug, Clone, Default)]
+pub struct TcpTransport;
+
+impl GossipTransport for TcpTransport {
+    fn listen(&self, addr: &SocketAddr) -> Result<Box<GossipListener>> {
+        let listener = TcpListener::bind(addr).map_err(Error::CannotBind)?;
+        let (tx, rx) = mpsc::channel();
+        thread::Builder::new()
+            .name(format!("tcp-gossip-{}", addr))
+            .spawn(move || {
+                accept_loop(listener, tx, Duration::from_millis(IDLE_TIMEOUT_MS))
+            })
+            .map_err(Error::CannotBind)?;
+        Ok(Box::new(TcpGossipListener { rx: rx }))
+    }
+
+    fn connect(&self, addr: &str) -> Result<Box<GossipSender>> {
+        let mut last_err =
+            io::Error::new(io::ErrorKind::AddrNotAvailable, "No address discovered.");
+        for socket_addr in addr.to_socket_addrs().map_err(Error::TcpConnectError)? {
+            match TcpStream::connect_timeout(
+                &socket_addr,
+                Duration::from_millis(CONNECT_TIMEOUT_MS),
+            ) {
+                Ok(stream) => {
+                    stream
+                        .set_write_timeout(Some(Duration::from_millis(SEND_TIMEOUT_MS)))
+                        .map_err(Error::TcpConnectError)?;
+                    stream.set_nodelay(true).map_err(Error::TcpConnectError)?;
+                    return Ok(Box::new(TcpGossipSender { stream: stream }));
+                }
+                Err(e) => last_err = e,
+            }
+        }
+        Err(Error::TcpConnectError(last_err))
+    }
+}
+
+struct TcpGossipListener {
+    rx: Receiver<Vec<u8>>,
+}
+
+impl GossipListener for TcpGossipListener {
+    fn recv(&mut self) -> Result<Vec<u8>> {
+        self.rx.recv().map_err(|_| Error::GossipListenerClosed)
+    }
+}
+
+struct TcpGossipSender {
+    stream: TcpStream,
+}
+
+impl GossipSender for TcpGossipSender {
+    fn send(&mut self, payload: &[u8]) -> Result<()> {
+        let mut frame = vec![0; 4 + payload.len()];
+        BigEndian::write_u32(&mut frame[..4], payload.len() as u32);
+        frame[4..].copy_from_slice(payload);
+        self.stream.write_all(&frame).map_err(Error::TcpSendError)
+    }
+}
+
+fn accept_loop(listener: TcpListener, tx: Sender<Vec<u8>>, idle_timeout: Duration) {
+    let connections = Arc::new(AtomicUsize::new(0));
+    for stream in listener.incoming() {
+        match stream {
+            Ok(stream) => {
+                if let Err(e) = stream.set_read_timeout(Some(idle_timeout)) {
+                    error!("Dropping gossip connection; cannot set its read timeout: {}", e);
+                    continue;
+                }
+                if connections.fetch_add(1, Ordering::SeqCst) >= MAX_CONNECTIONS {
+                    connections.fetch_sub(1, Ordering::SeqCst);
+                    warn!(
+                        "Dropping gossip connection; already reading {} connections",
+                        MAX_CONNECTIONS
+                    );
+                    continue;
+                }
+                let tx = tx.clone();
+                let connections_for_reader = connections.clone();
+                if let Err(e) = thread::Builder::new()
+                    .name(String::from("tcp-gossip-reader"))
+                    .spawn(move || {
+                        read_frames(stream, tx);
+                        connections_for_reader.fetch_sub(1, Ordering::SeqCst);
+                    })
+                {
+                    connections.fetch_sub(1, Ordering::SeqCst);
+                    error!("Could not spawn thread: {}", e);
+                }
+            }
+            Err(e) => error!("Error accepting gossip connection: {}", e),
+        }
+    }
+}
+
+/// Read frames off a connection until the sender hangs up, stays silent for longer than the
+/// stream's read timeout, or sends something we can't read.
+fn read_frames(mut stream: TcpStream, tx: Sender<Vec<u8>>) {
+    let mut header = [0; 4];
+    loop {
+        if let Err(e) = stream.read_exact(&mut header) {
+            match e.kind() {
+                io::ErrorKind::UnexpectedEof => (),
+                io::ErrorKind::WouldBlock |
+                io::ErrorKind::TimedOut => debug!("Closing idle gossip connection"),
+                _ => debug!("Error reading gossip frame: {}", e),
+            }
+            return;
+        }
+        let length = BigEndian::read_u32(&header) as usize;
+        if length > MAX_FRAME_BYTES {
+            warn!(
+                "Dropping gossip connection; frame of {} bytes is larger than {}",
+                length,
+                MAX_FRAME_BYTES
+            );
+            return;
+        }
+        // Grow the buffer as the frame arrives, rather than trusting the length up front.
+        let mut payload = Vec::new();
+        match (&mut stream).take(length as u64).read_to_end(&mut payload) {
+            Ok(read) if read == length => (),
+            Ok(_) => {
+                debug!("Gossip connection closed in the middle of a frame");
+                return;
+            }
+            Err(e) => {
+                debug!("Error reading gossip frame: {}", e);
+                return;
+            }
+        }
+        if tx.send(payload).is_err() {
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Read, Write};
+    use std::net::{SocketAddr, TcpListener, TcpStream};
+    use std::sync::mpsc;
+    use std::thread;
+    use std::time::Duration;
+
+    use byteorder::{BigEndian, ByteOrder};
+
+    use transport::GossipTransport;
+    use super::{accept_loop, TcpTransport, MAX_FRAME_BYTES};
+
+    fn free_addr() -> SocketAddr {
+        TcpListener::bind("127.0.0.1:0")
+            .and_then(|l| l.local_addr())
+            .expect("Cannot find a free port")
+    }
+
+    #[test]
+    fn messages_arrive_whole_and_in_order() {
+        let addr = free_addr();
+        let transport = TcpTransport;
+        let mut listener = transport.listen(&addr).expect("Cannot listen");
+        let mut sender = transport.connect(&addr.to_string()).expect(
+            "Cannot connect",
+        );
+        sender.send(b"first").unwrap();
+        sender.send(b"").unwrap();
+        sender.send(&vec![7u8; 100000]).unwrap();
+        assert_eq!(listener.recv().unwrap(), b"first".to_vec());
+        assert_eq!(listener.recv().unwrap(), Vec::<u8>::new());
+        assert_eq!(listener.recv().unwrap(), vec![7; 100000]);
+    }
+
+    #[test]
+    fn messages_from_many_senders_are_all_received() {
+        let addr = free_addr();
+        let transport = TcpTransport;
+        let mut listener = transport.listen(&addr).expect("Cannot listen");
+        for n in 0..3u8 {
+            let mut sender = transport.connect(&addr.to_string()).expect(
+                "Cannot connect",
+            );
+            sender.send(&[n]).unwrap();
+        }
+        let mut received: Vec<Vec<u8>> = (0..3).map(|_| listener.recv().unwrap()).collect();
+        received.sort();
+        assert_eq!(received, vec![vec![0], vec![1], vec![2]]);
+    }
+
+    #[test]
+    fn frames_larger_than_the_maximum_close_the_connection() {
+        let addr = free_addr();
+        let transport = TcpTransport;
+        let mut listener = transport.listen(&addr).expect("Cannot listen");
+        let mut stream = TcpStream::connect(&addr).expect("Cannot connect");
+        let mut header = [0; 4];
+        BigEndian::write_u32(&mut header, MAX_FRAME_BYTES as u32 + 1);
+        stream.write_all(&header).unwrap();
+        let mut sender = transport.connect(&addr.to_string()).expect(
+            "Cannot connect",
+        );
+        sender.send(b"after").unwrap();
+        assert_eq!(listener.recv().unwrap(), b"after".to_vec());
+        let mut buf = [0; 1];
+        assert_eq!(stream.read(&mut buf).unwrap_or(0), 0);
+    }
+
+    #[test]
+    fn idle_connections_are_closed() {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("Cannot listen");
+        let addr = listener.local_addr().unwrap();
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || accept_loop(listener, tx, Duration::from_millis(100)));
+
+        let mut idle = TcpStream::connect(&addr).expect("Cannot connect");
+        idle.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+        let mut buf = [0; 1];
+        assert_eq!(idle.read(&mut buf).expect("The connection was not closed"), 0);
+
+        let mut sender = TcpTransport.connect(&addr.to_string()).expect(
+            "Cannot connect",
+        );
+        sender.send(b"busy").unwrap();
+        assert_eq!(rx.recv().unwrap(), b"busy".to_vec());
+    }
+
+    #[test]
+    fn connecting_to_nobody_fails() {
+        let addr = free_addr();
+        assert!(TcpTransport.connect(&addr.to_string()).is_err());
+    }
+}
@@ -0,0 +1,52 @@
+// Copyright (c) 2017 Chef Software Inc. and/or applicable contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The UDP SWIM transport.
+
+use std::io;
+use std::net::{SocketAddr, UdpSocket};
+use std::sync::Arc;
+use std::time::Duration;
+
+use error::{Error, Result};
+use transport::{SwimSocket, SwimTransport};
+
+/// How long a read or write on the socket may block.
+const SOCKET_TIMEOUT_MS: u64 = 1000;
+
+#[derive(Debug, Clone, Default)]
+pub struct UdpTransport;
+
+impl SwimTransport for UdpTransport {
+    fn bind(&self, addr: &SocketAddr) -> Result<Arc<SwimSocket>> {
+        let socket = UdpSocket::bind(addr).map_err(Error::CannotBind)?;
+        socket
+            .set_read_timeout(Some(Duration::from_millis(SOCKET_TIMEOUT_MS)))
+            .map_err(Error::SocketSetReadTimeout)?;
+        socket
+            .set_write_timeout(Some(Duration::from_millis(SOCKET_TIMEOUT_MS)))
+            .map_err(Error::SocketSetWriteTimeout)?;
+        Ok(Arc::new(socket))
+    }
+}
+
+impl SwimSocket for UdpSocket {
+    fn send_to(&self, buf: &[u8], addr: SocketAddr) -> io::Result<usize> {
+        UdpSocket::send_to(self, buf, addr)
+    }
+
+    fn recv_from(&self, buf: &mut [u8]) -> io::Result<(usize, SocketAddr)> {
+        UdpSocket::recv_from(self, buf)
+    }
+}
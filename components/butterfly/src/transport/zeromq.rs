@@ -0,0 +1,110 @@
+// Copyright (c) 2017 Chef Software Inc. and/or applicable contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The ZeroMQ gossip transport.
+//!
+//! Listeners are PULL sockets, so inbound messages are fair-queued; senders are PUSH sockets.
+//! Connecting never blocks, and never fails because the other side is down - ZeroMQ connects in
+//! the background, and drops the message if it cannot be sent within the send timeout.
+
+use std::net::SocketAddr;
+
+use zmq;
+
+use ZMQ_CONTEXT;
+use error::{Error, Result};
+use transport::{GossipListener, GossipSender, GossipTransport, LINGER_DEFAULT_MS};
+
+#[derive(Debug, Clone)]
+pub struct ZeroMqTransport {
+    linger: i32,
+}
+
+impl Default for ZeroMqTransport {
+    fn default() -> ZeroMqTransport {
+        ZeroMqTransport { linger: LINGER_DEFAULT_MS }
+    }
+}
+
+impl ZeroMqTransport {
+    /// Create a transport whose push sockets linger for `linger` milliseconds once closed; -1
+    /// waits until every pending message has been sent.
+    pub fn new(linger: i32) -> ZeroMqTransport {
+        ZeroMqTransport { linger: linger }
+    }
+}
+
+impl GossipTransport for ZeroMqTransport {
+    fn listen(&self, addr: &SocketAddr) -> Result<Box<GossipListener>> {
+        let socket = (**ZMQ_CONTEXT).as_mut().socket(zmq::PULL).expect(
+            "Failure to create the ZMQ pull socket",
+        );
+        socket.set_linger(0).expect(
+            "Failure to set the ZMQ Pull socket to not linger",
+        );
+        socket.set_tcp_keepalive(0).expect(
+            "Failure to set the ZMQ Pull socket to not use keepalive",
+        );
+        socket.bind(&format!("tcp://{}", addr)).map_err(
+            Error::ZmqBindError,
+        )?;
+        Ok(Box::new(ZeroMqListener { socket: socket }))
+    }
+
+    fn connect(&self, addr: &str) -> Result<Box<GossipSender>> {
+        let socket = (**ZMQ_CONTEXT).as_mut().socket(zmq::PUSH).expect(
+            "Failure to create the ZMQ push socket",
+        );
+        socket.set_linger(self.linger).expect(
+            "Failure to set the ZMQ push socket linger",
+        );
+        socket.set_tcp_keepalive(0).expect(
+            "Failure to set the ZMQ push socket to not use keepalive",
+        );
+        socket.set_immediate(true).expect(
+            "Failure to set the ZMQ push socket to immediate",
+        );
+        socket.set_sndhwm(1000).expect(
+            "Failure to set the ZMQ push socket hwm",
+        );
+        socket.set_sndtimeo(500).expect(
+            "Failure to set the ZMQ send timeout",
+        );
+        socket.connect(&format!("tcp://{}", addr)).map_err(
+            Error::ZmqConnectError,
+        )?;
+        Ok(Box::new(ZeroMqSender { socket: socket }))
+    }
+}
+
+struct ZeroMqListener {
+    socket: zmq::Socket,
+}
+
+impl GossipListener for ZeroMqListener {
+    fn recv(&mut self) -> Result<Vec<u8>> {
+        let msg = self.socket.recv_msg(0).map_err(Error::ZmqRecvError)?;
+        Ok(msg.to_vec())
+    }
+}
+
+struct ZeroMqSender {
+    socket: zmq::Socket,
+}
+
+impl GossipSender for ZeroMqSender {
+    fn send(&mut self, payload: &[u8]) -> Result<()> {
+        self.socket.send(payload, 0).map_err(Error::ZmqSendError)
+    }
+}
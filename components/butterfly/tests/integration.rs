@@ -47,6 +47,18 @@ fn six_members_meshed_confirm_one_member() {
     assert_wait_for_health_of!(net, 0, Health::Confirmed);
 }
 
+#[test]
+fn six_members_meshed_confirm_one_member_with_a_stretched_suspicion_timeout() {
+    // Five peers confirm the suspicion, which shrinks the stretched timeout back to its minimum.
+    let mut timing = btest::test_timing();
+    timing.suspicion_max_timeout_multiplier = 2;
+    let mut net = btest::SwimNet::new_with_timing(6, timing);
+    net.mesh();
+    trace_it!(TEST: &net[0], "Paused");
+    net[0].pause();
+    assert_wait_for_health_of!(net, 0, Health::Confirmed);
+}
+
 #[test]
 fn six_members_meshed_partition_one_node_from_another_node_remains_alive() {
     let mut net = btest::SwimNet::new(6);
@@ -130,6 +142,38 @@ fn six_members_unmeshed_partition_and_rejoin_persistent_peers() {
     assert_wait_for_health_of!(net, [0..3, 3..6], Health::Alive);
 }
 
+#[test]
+fn six_members_simulated_partition_and_heal_persistent_peers() {
+    let mut net = btest::SwimNet::new_simulated(6, 1);
+    net[0]
+        .member
+        .write()
+        .expect("Member lock is poisoned")
+        .set_persistent(true);
+    net[4]
+        .member
+        .write()
+        .expect("Member lock is poisoned")
+        .set_persistent(true);
+    net.mesh();
+    assert_wait_for_health_of!(net, [0..6, 0..6], Health::Alive);
+    net.partition(0..3, 3..6);
+    assert_wait_for_health_of!(net, [0..3, 3..6], Health::Confirmed);
+    net.sim().heal();
+    assert_wait_for_health_of!(net, [0..3, 3..6], Health::Alive);
+}
+
+#[test]
+fn six_members_simulated_with_latency_and_loss_stay_alive() {
+    let mut net = btest::SwimNet::new_simulated(6, 7);
+    net.sim().set_latency(5, 50);
+    net.sim().set_loss(0.05);
+    net.mesh();
+    assert_wait_for_health_of!(net, [0..6, 0..6], Health::Alive);
+    net.wait_for_rounds(2);
+    assert_wait_for_health_of!(net, [0..6, 0..6], Health::Alive);
+}
+
 #[test]
 fn six_members_unmeshed_allows_graceful_departure() {
     let mut net = btest::SwimNet::new(6);
@@ -154,3 +198,26 @@ fn fifty_members_meshed_confirm_one_member() {
     net[0].pause();
     assert_wait_for_health_of!(net, 0, Health::Confirmed);
 }
+
+#[test]
+fn three_members_meshed_see_each_others_tags() {
+    let mut net = btest::SwimNet::new(3);
+    net[0]
+        .member
+        .write()
+        .expect("Member lock is poisoned")
+        .mut_tags()
+        .insert(String::from("zone"), String::from("us-east-1a"));
+    net.mesh();
+    assert_wait_for_health_of!(net, [0..3, 0..3], Health::Alive);
+    let member_id = net[0].member_id().to_string();
+    for server in &net[1..3] {
+        server.member_list.with_member(&member_id, |member| {
+            let member = member.expect("Member should be in the member list");
+            assert_eq!(
+                member.get_tags().get("zone").map(|zone| zone.as_str()),
+                Some("us-east-1a")
+            );
+        });
+    }
+}
@@ -12,10 +12,15 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::time::Duration;
+
 use habitat_butterfly::member::Health;
 use habitat_butterfly::message::swim::Election_Status;
+use habitat_butterfly::rumor::election::{ElectionConfig, QuorumBasis};
+use habitat_core::service::ServiceGroup;
 
 use btest;
+use btest::sim::SimStats;
 
 #[test]
 fn three_members_run_election() {
@@ -239,3 +244,161 @@ fn five_members_elect_a_new_leader_when_they_are_quorum_partitioned() {
         },
     );
 }
+
+#[test]
+fn three_members_see_the_leader_renew_its_lease() {
+    let mut net = btest::SwimNet::new_simulated(3, 3);
+    net.mesh();
+    let sg = ServiceGroup::new(None, "witcher", "prod", None).unwrap();
+    for i in 0..3 {
+        net.add_service(i, "core/witcher/1.2.3/20161208121212");
+        net[i].set_election_config(
+            &sg,
+            ElectionConfig {
+                quorum_basis: QuorumBasis::Known,
+                lease: Some(Duration::from_secs(6)),
+            },
+        );
+    }
+    net.add_election(0, "witcher");
+    assert_wait_for_equal_election!(net, [0..3, 0..3], "witcher.prod");
+
+    // Nobody has seen a lease in this group yet, so the winner only takes over once a whole
+    // lease has gone by
+    for i in 0..3 {
+        net[i].election_store.with_rumor(
+            "witcher.prod",
+            "election",
+            |e| assert!(!e.unwrap().is_finished()),
+        );
+    }
+    net.sim().run_for(6_000);
+    for i in 0..3 {
+        net[i].restart_elections();
+    }
+    assert_wait_for_election_status!(net, [0..3], "witcher.prod", Election_Status::Finished);
+    assert_wait_for_equal_election!(net, [0..3, 0..3], "witcher.prod");
+
+    let mut leader_id = String::from("");
+    net[0].election_store.with_rumor(
+        "witcher.prod",
+        "election",
+        |e| {
+            leader_id = String::from(e.unwrap().get_member_id());
+        },
+    );
+    let leader = net.iter()
+        .position(|server| server.member_id() == &leader_id[..])
+        .unwrap();
+
+    // A quarter of the lease has passed, so the leader renews it
+    net.sim().run_for(1_500);
+    net[leader].restart_elections();
+    assert_wait_for_equal_election!(net, [0..3, 0..3], "witcher.prod");
+    for i in 0..3 {
+        net[i].election_store.with_rumor(
+            "witcher.prod",
+            "election",
+            |e| {
+                assert_eq!(e.unwrap().get_lease_renewal(), 1);
+                assert!(e.unwrap().is_finished());
+            },
+        );
+    }
+}
+
+/// Run an election between five members of a simulated network with latency, and return who won,
+/// when, and what the network did along the way.
+fn simulated_election(seed: u64) -> (String, u64, SimStats) {
+    let mut net = btest::SwimNet::new_simulated(5, seed);
+    net.sim().set_latency(5, 50);
+    net.mesh();
+    for i in 0..5 {
+        net.add_service(i, "core/witcher/1.2.3/20161208121212");
+    }
+    net.add_election(0, "witcher");
+    assert_wait_for_election_status!(net, [0..5], "witcher.prod", Election_Status::Finished);
+    assert_wait_for_equal_election!(net, [0..5, 0..5], "witcher.prod");
+
+    let mut leader_id = String::from("");
+    net[0].election_store.with_rumor(
+        "witcher.prod",
+        "election",
+        |e| {
+            leader_id = String::from(e.unwrap().get_member_id());
+        },
+    );
+    (leader_id, net.sim().now(), net.sim().stats())
+}
+
+#[test]
+fn five_members_simulated_elect_a_leader() {
+    let (leader_id, _, _) = simulated_election(11);
+    assert!(!leader_id.is_empty());
+}
+
+#[test]
+fn simulated_elections_run_the_same_way_for_the_same_seed() {
+    assert_eq!(simulated_election(11), simulated_election(11));
+}
+
+#[test]
+fn five_members_simulated_split_brain_elect_a_new_leader_in_the_majority() {
+    let mut net = btest::SwimNet::new_simulated_with_suitability(vec![1, 0, 0, 0, 0], 5);
+    net.sim().set_latency(5, 50);
+    net[0]
+        .member
+        .write()
+        .expect("Member lock is poisoned")
+        .set_persistent(true);
+    net[4]
+        .member
+        .write()
+        .expect("Member lock is poisoned")
+        .set_persistent(true);
+    for i in 0..5 {
+        net.add_service(i, "core/witcher/1.2.3/20161208121212");
+    }
+    net.add_election(0, "witcher");
+    net.connect(0, 1);
+    net.connect(1, 2);
+    net.connect(2, 3);
+    net.connect(3, 4);
+    assert_wait_for_health_of!(net, [0..5, 0..5], Health::Alive);
+    assert_wait_for_election_status!(net, [0..5], "witcher.prod", Election_Status::Finished);
+    assert_wait_for_equal_election!(net, [0..5, 0..5], "witcher.prod");
+    net[0].election_store.with_rumor(
+        "witcher.prod",
+        "election",
+        |e| assert_eq!(e.unwrap().get_member_id(), net[0].member_id()),
+    );
+
+    // The minority keeps its old leader, but can't finish an election; the majority elects a new
+    // one, which the minority follows once the partition heals
+    net.partition(0..2, 2..5);
+    assert_wait_for_health_of!(net, [0..2, 2..5], Health::Confirmed);
+    net[0].restart_elections();
+    net[4].restart_elections();
+    assert_wait_for_election_status!(net, [0..2], "witcher.prod", Election_Status::NoQuorum);
+    assert_wait_for_election_status!(net, [2..5], "witcher.prod", Election_Status::Finished);
+    let mut new_leader_id = String::from("");
+    net[2].election_store.with_rumor(
+        "witcher.prod",
+        "election",
+        |e| {
+            new_leader_id = String::from(e.unwrap().get_member_id());
+        },
+    );
+    assert!(new_leader_id != net[0].member_id());
+
+    net.unpartition(0..2, 2..5);
+    assert_wait_for_health_of!(net, [0..5, 0..5], Health::Alive);
+    assert_wait_for_election_status!(net, [0..2], "witcher.prod", Election_Status::Finished);
+    for i in 0..2 {
+        net[i].election_store.with_rumor(
+            "witcher.prod",
+            "election",
+            |e| assert_eq!(new_leader_id, e.unwrap().get_member_id()),
+        );
+    }
+}
@@ -14,10 +14,14 @@
 
 use std::path::Path;
 use std::result;
+use std::str::FromStr;
 
-use clap::App;
+use butterfly::transport::GossipTransportKind;
+use clap::{App, Arg, SubCommand};
 use hcore::service::ServiceGroup;
 
+use command;
+
 pub fn get() -> App<'static, 'static> {
     clap_app!(hab_butterfly =>
         (about: "\"A Habitat is the natural environment for your services\" - Alan Turing")
@@ -41,6 +45,9 @@ pub fn get() -> App<'static, 'static> {
                     (default: 127.0.0.1:9638)")
             (@arg RING: -r --ring +takes_value
              "Ring key name, which will encrypt communication messages")
+            (@arg GOSSIP_TRANSPORT: --("gossip-transport") +takes_value {valid_gossip_transport}
+             "How rumors are sent to the peers, either zeromq or tcp; it must match the \
+             transport of the Supervisor ring [default: zeromq]")
         )
         (@subcommand file =>
             (about: "Commands relating to Habitat files")
@@ -61,7 +68,69 @@ pub fn get() -> App<'static, 'static> {
                     (default: 127.0.0.1:9638)")
                 (@arg RING: -r --ring +takes_value
                     "Ring key name, which will encrypt communication messages")
+                (@arg GOSSIP_TRANSPORT: --("gossip-transport") +takes_value
+                    {valid_gossip_transport}
+                    "How rumors are sent to the peers, either zeromq or tcp; it must match the \
+                    transport of the Supervisor ring [default: zeromq]")
+            )
+        )
+        (@subcommand ring =>
+            (about: "Commands relating to Habitat rings")
+            (aliases: &["r", "ri", "rin"])
+            (@setting ArgRequiredElseHelp)
+            (@subcommand key =>
+                (about: "Commands relating to Habitat ring keys")
+                (aliases: &["k", "ke"])
+                (@setting ArgRequiredElseHelp)
+                (@subcommand rotate =>
+                    (about: "Generates a new revision of a ring key and spreads it to the \
+                    Supervisor ring, encrypted with the current revision")
+                    (aliases: &["r", "ro", "rot", "rota", "rotat"])
+                    (@arg RING: +required +takes_value "Ring key name")
+                    (@arg DELAY: --delay +takes_value {valid_numeric}
+                        "Seconds before Supervisors start encrypting with the new revision \
+                        (default: 60)")
+                    (@arg GRACE_PERIOD: --("grace-period") +takes_value {valid_numeric}
+                        "Seconds that Supervisors keep accepting the current revision once the \
+                        new one is active (default: 300)")
+                    (@arg PEER: -p --peer +takes_value
+                        "A comma-delimited list of one or more Habitat Supervisor peers \
+                        (default: 127.0.0.1:9638)")
+                    (@arg GOSSIP_TRANSPORT: --("gossip-transport") +takes_value
+                        {valid_gossip_transport}
+                        "How rumors are sent to the peers, either zeromq or tcp; it must match the \
+                        transport of the Supervisor ring [default: zeromq]")
+                )
             )
+            (@subcommand compact =>
+                (about: "Compacts the rumor file of a stopped Supervisor, dropping the rumors of \
+                departed members and of service groups left without services")
+                (aliases: &["c", "co", "com", "comp", "compa", "compac"])
+                (@arg DAT_FILE: +required {file_exists}
+                    "Path to the rumor file (ex: /hab/sup/default/data/<member-id>.rst)")
+            )
+            (subcommand: sub_ring_query("members",
+                "Lists the members of the ring and their health")
+                .aliases(&["m", "me", "mem", "memb", "membe", "member"]))
+            (subcommand: sub_ring_query("services",
+                "Lists the service groups in the ring and their leaders")
+                .aliases(&["s", "se", "ser", "serv", "servi", "servic", "service"]))
+            (subcommand: sub_ring_query("configs",
+                "Lists the configurations applied to service groups in the ring")
+                .aliases(&["con", "conf", "confi", "config"]))
+            (subcommand: sub_ring_query("files",
+                "Lists the files uploaded to service groups in the ring")
+                .aliases(&["f", "fi", "fil", "file"]))
+        )
+        (@subcommand trace =>
+            (about: "Merges the gossip trace files of several Supervisors into a single \
+            timeline, ordered by time")
+            (aliases: &["t", "tr", "tra", "trac"])
+            (@setting ArgRequiredElseHelp)
+            (@arg FILE: +required +multiple {file_exists}
+                "Trace files to merge (ex: /tmp/habitat-swim-trace/*.ndjson)")
+            (@arg FORMAT: -f --format +takes_value {valid_trace_format}
+                "Output format: ndjson, chrome or text (default: ndjson)")
         )
     )
 }
@@ -74,6 +143,9 @@ fn sub_config_apply() -> App<'static, 'static> {
             (default: 127.0.0.1:9638)")
         (@arg RING: -r --ring +takes_value
             "Ring key name, which will encrypt communication messages")
+        (@arg GOSSIP_TRANSPORT: --("gossip-transport") +takes_value {valid_gossip_transport}
+            "How rumors are sent to the peers, either zeromq or tcp; it must match the \
+            transport of the Supervisor ring [default: zeromq]")
         (@arg SERVICE_GROUP: +required {valid_service_group}
             "Target service group (ex: redis.default)")
         (@arg VERSION_NUMBER: +required
@@ -82,9 +154,43 @@ fn sub_config_apply() -> App<'static, 'static> {
             "Path to local file on disk (ex: /tmp/config.toml, default: <stdin>)")
         (@arg ORG: --org +takes_value "Name of service organization to use for encryption")
         (@arg USER: -u --user +takes_value "Name of a user key to use for encryption")
+        (@arg GATEWAY: -g --gateway +takes_value
+            "The HTTP gateway of a Habitat Supervisor running the service group, which checks \
+            the configuration against the package's config schema before it's applied \
+            (default: http://127.0.0.1:9631)")
+        (@arg TOKEN: --token +takes_value
+            "Bearer token for the HTTP gateway (default: $HAB_SUP_HTTP_AUTH_TOKEN)")
     )
 }
 
+fn sub_ring_query(name: &'static str, about: &'static str) -> App<'static, 'static> {
+    SubCommand::with_name(name)
+        .about(about)
+        .arg(
+            Arg::with_name("GATEWAY")
+                .help(
+                    "The HTTP gateway of a Habitat Supervisor in the ring \
+                    (default: http://127.0.0.1:9631)",
+                )
+                .short("g")
+                .long("gateway")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("TOKEN")
+                .help("Bearer token for the HTTP gateway (default: $HAB_SUP_HTTP_AUTH_TOKEN)")
+                .long("token")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("FORMAT")
+                .help("Output format; [default: table] [values: table, json]")
+                .long("format")
+                .takes_value(true)
+                .validator(valid_output_format),
+        )
+}
+
 fn file_exists(val: String) -> result::Result<(), String> {
     if Path::new(&val).is_file() {
         Ok(())
@@ -97,6 +203,41 @@ fn file_exists_or_stdin(val: String) -> result::Result<(), String> {
     if val == "-" { Ok(()) } else { file_exists(val) }
 }
 
+fn valid_numeric(val: String) -> result::Result<(), String> {
+    match val.parse::<u64>() {
+        Ok(_) => Ok(()),
+        Err(_) => Err(format!("'{}' is not a valid number", &val)),
+    }
+}
+
+fn valid_gossip_transport(val: String) -> result::Result<(), String> {
+    match GossipTransportKind::from_str(&val) {
+        Ok(_) => Ok(()),
+        Err(_) => Err(format!(
+            "Gossip transport should be either 'zeromq' or 'tcp'"
+        )),
+    }
+}
+
+fn valid_trace_format(val: String) -> result::Result<(), String> {
+    if command::trace::FORMATS.contains(&val.as_str()) {
+        Ok(())
+    } else {
+        Err(format!(
+            "'{}' is not a trace format; use one of {}",
+            &val,
+            command::trace::FORMATS.join(", ")
+        ))
+    }
+}
+
+fn valid_output_format(val: String) -> result::Result<(), String> {
+    match val.as_str() {
+        "table" | "json" => Ok(()),
+        _ => Err(format!("'{}' is not a valid output format", &val)),
+    }
+}
+
 fn valid_service_group(val: String) -> result::Result<(), String> {
     match ServiceGroup::validate(&val) {
         Ok(()) => Ok(()),
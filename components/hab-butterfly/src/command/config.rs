@@ -20,11 +20,16 @@ pub mod apply {
     use std::time;
 
     use butterfly::client::Client;
+    use butterfly::transport::GossipTransportKind;
     use common::ui::{Status, UI};
     use hcore::crypto::{SymKey, BoxKeyPair};
     use hcore::service::ServiceGroup;
+    use http_client::ApiClient;
+    use hyper::header::{Authorization, Bearer};
+    use hyper::status::StatusCode;
     use toml;
 
+    use {PRODUCT, VERSION};
     use error::{Error, Result};
 
     pub fn start(
@@ -32,7 +37,10 @@ pub mod apply {
         sg: &ServiceGroup,
         number: u64,
         file_path: Option<&Path>,
+        gateway: &str,
+        token: Option<&str>,
         peers: &Vec<String>,
+        transport: GossipTransportKind,
         ring_key: Option<&SymKey>,
         user_pair: Option<&BoxKeyPair>,
         service_pair: Option<&BoxKeyPair>,
@@ -73,9 +81,11 @@ pub mod apply {
                 return Err(Error::TomlDeserializeError(err));
             }
         }
+        let encrypt = service_pair.is_some() && user_pair.is_some();
+        validate(ui, gateway, token, sg, &body, encrypt)?;
 
         let mut encrypted = false;
-        if service_pair.is_some() && user_pair.is_some() {
+        if encrypt {
             ui.status(
                 Status::Encrypting,
                 format!(
@@ -93,11 +103,11 @@ pub mod apply {
 
         for peer in peers.iter() {
             ui.status(Status::Applying, format!("to peer {}", peer))?;
-            let mut client = Client::new(peer, ring_key.map(|k| k.clone())).map_err(
-                |e| {
-                    Error::ButterflyError(format!("{}", e))
-                },
-            )?;
+            let mut client = Client::with_transport_kind(
+                transport,
+                peer,
+                ring_key.map(|k| k.clone()),
+            ).map_err(|e| Error::ButterflyError(format!("{}", e)))?;
             client
                 .send_service_config(sg.clone(), number, body.clone(), encrypted)
                 .map_err(|e| Error::ButterflyError(format!("{}", e)))?;
@@ -111,4 +121,85 @@ pub mod apply {
         ui.end("Applied configuration")?;
         Ok(())
     }
+
+    /// Asks the HTTP gateway of a Supervisor running the service group to check the configuration
+    /// against the package's config schema, so that an invalid configuration is never gossiped.
+    /// A gateway which can't be reached, or which doesn't run the service, only gets a warning:
+    /// every Supervisor checks the configuration again before using it.
+    ///
+    /// The gateway sees the configuration in plaintext, so a configuration which is to be
+    /// encrypted is only sent to a gateway served over https.
+    fn validate(
+        ui: &mut UI,
+        gateway: &str,
+        token: Option<&str>,
+        sg: &ServiceGroup,
+        body: &[u8],
+        encrypt: bool,
+    ) -> Result<()> {
+        if encrypt && !gateway.starts_with("https://") {
+            ui.warn(format!(
+                "Skipping the config schema check, {} would receive the encrypted \
+                 configuration in plaintext; use an https gateway to check it",
+                gateway
+            ))?;
+            return Ok(());
+        }
+        let client = ApiClient::new(gateway, PRODUCT, VERSION, None).map_err(|e| {
+            Error::ButterflyError(e.to_string())
+        })?;
+        let path = match sg.org() {
+            Some(org) => {
+                format!(
+                    "services/{}/{}/{}/config/validate",
+                    sg.service(),
+                    sg.group(),
+                    org
+                )
+            }
+            None => format!("services/{}/{}/config/validate", sg.service(), sg.group()),
+        };
+        let mut request = client.post(&path).body(body);
+        if let Some(token) = token {
+            request = request.header(Authorization(Bearer { token: token.to_string() }));
+        }
+        let mut response = match request.send() {
+            Ok(response) => response,
+            Err(err) => {
+                ui.warn(format!(
+                    "Skipping the config schema check, cannot reach {}: {}",
+                    gateway,
+                    err
+                ))?;
+                return Ok(());
+            }
+        };
+        let status = response.status.clone();
+        match status {
+            StatusCode::Ok | StatusCode::NoContent => {
+                ui.status(
+                    Status::Verified,
+                    "this configuration against the package's config schema",
+                )?;
+                Ok(())
+            }
+            StatusCode::UnprocessableEntity => {
+                let mut reason = String::new();
+                response.read_to_string(&mut reason)?;
+                ui.fatal("Invalid configuration")?;
+                ui.br()?;
+                ui.warn(&reason)?;
+                ui.br()?;
+                Err(Error::ButterflyError(reason))
+            }
+            _ => {
+                ui.warn(format!(
+                    "Skipping the config schema check, {} answered {}",
+                    gateway,
+                    status
+                ))?;
+                Ok(())
+            }
+        }
+    }
 }
@@ -16,6 +16,7 @@ use std::thread;
 use std::time;
 
 use butterfly::client::Client;
+use butterfly::transport::GossipTransportKind;
 use common::ui::{Status, UI};
 use hcore::crypto::SymKey;
 
@@ -25,6 +26,7 @@ pub fn run(
     ui: &mut UI,
     member_id: &str,
     peers: Vec<String>,
+    transport: GossipTransportKind,
     ring_key: Option<SymKey>,
 ) -> Result<()> {
     ui.begin(
@@ -36,9 +38,8 @@ pub fn run(
     )?;
     for peer in peers.into_iter() {
         ui.status(Status::Applying, format!("to peer {}", peer))?;
-        let mut client = Client::new(peer, ring_key.clone()).map_err(|e| {
-            Error::ButterflyError(e.to_string())
-        })?;
+        let mut client = Client::with_transport_kind(transport, peer, ring_key.clone())
+            .map_err(|e| Error::ButterflyError(e.to_string()))?;
         client.send_departure(member_id).map_err(|e| {
             Error::ButterflyError(e.to_string())
         })?;
@@ -20,6 +20,7 @@ pub mod upload {
     use std::time;
 
     use butterfly::client::Client;
+    use butterfly::transport::GossipTransportKind;
     use common::ui::{Status, UI};
     use hcore::crypto::{SymKey, BoxKeyPair};
     use hcore::service::ServiceGroup;
@@ -32,6 +33,7 @@ pub mod upload {
         number: u64,
         file_path: &Path,
         peers: &Vec<String>,
+        transport: GossipTransportKind,
         ring_key: Option<&SymKey>,
         user_pair: Option<&BoxKeyPair>,
         service_pair: Option<&BoxKeyPair>,
@@ -74,11 +76,11 @@ pub mod upload {
 
         for peer in peers.iter() {
             ui.status(Status::Applying, format!("to peer {}", peer))?;
-            let mut client = Client::new(peer, ring_key.map(|k| k.clone())).map_err(
-                |e| {
-                    Error::ButterflyError(format!("{}", e))
-                },
-            )?;
+            let mut client = Client::with_transport_kind(
+                transport,
+                peer,
+                ring_key.map(|k| k.clone()),
+            ).map_err(|e| Error::ButterflyError(format!("{}", e)))?;
             client
                 .send_service_file(
                     sg.clone(),
@@ -15,3 +15,5 @@
 pub mod config;
 pub mod depart;
 pub mod file;
+pub mod ring;
+pub mod trace;
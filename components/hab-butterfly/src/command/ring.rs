@@ -0,0 +1,625 @@
+// Copyright (c) 2017 Chef Software Inc. and/or applicable contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+pub mod key {
+    pub mod rotate {
+        use std::fs::File;
+        use std::io::Read;
+        use std::path::Path;
+        use std::thread;
+        use std::time::{self, SystemTime, UNIX_EPOCH};
+
+        use butterfly::client::Client;
+        use butterfly::server::keyring;
+        use butterfly::transport::GossipTransportKind;
+        use common::ui::{Status, UI};
+        use hcore::crypto::SymKey;
+
+        use error::{Error, Result};
+
+        /// Generates a new revision of the ring key, and spreads it to the ring encrypted with
+        /// the current revision. Supervisors start sending with the new revision `delay` seconds
+        /// from now, and keep accepting the current one for `grace_period` seconds after that.
+        pub fn start(
+            ui: &mut UI,
+            ring: &str,
+            delay: u64,
+            grace_period: u64,
+            peers: &Vec<String>,
+            transport: GossipTransportKind,
+            cache: &Path,
+        ) -> Result<()> {
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0);
+            let activate_at = (now + delay) as i64;
+            // A rotation which hasn't activated yet is already in the cache; the ring is still
+            // sending with the revision before it.
+            let current = keyring::active_ring_key(ring, cache, now as i64).map_err(|e| {
+                Error::ButterflyError(e.to_string())
+            })?;
+            ui.begin(format!(
+                "Rotating ring key {}",
+                current.name_with_rev()
+            ))?;
+            ui.status(
+                Status::Creating,
+                format!("new revision of ring key {}", ring),
+            )?;
+            let new_key = SymKey::generate_pair_for_ring(ring)?;
+            new_key.to_pair_files(cache)?;
+            keyring::write_schedule(cache, &new_key, activate_at, grace_period as i64)?;
+            let mut key = Vec::new();
+            let path = SymKey::get_secret_key_path(&new_key.name_with_rev(), cache)?;
+            File::open(&path)?.read_to_end(&mut key)?;
+
+            for peer in peers.iter() {
+                ui.status(
+                    Status::Applying,
+                    format!("{} to peer {}", new_key.name_with_rev(), peer),
+                )?;
+                let mut client =
+                    Client::with_transport_kind(transport, peer, Some(current.clone()))
+                        .map_err(|e| Error::ButterflyError(e.to_string()))?;
+                client
+                    .send_ring_key(&new_key, key.clone(), activate_at, grace_period as i64)
+                    .map_err(|e| Error::ButterflyError(e.to_string()))?;
+                // please take a moment to weep over the following line
+                // of code. We must sleep to allow messages to be sent
+                // before freeing the socket to prevent loss.
+                // see https://github.com/zeromq/libzmq/issues/1264
+                thread::sleep(time::Duration::from_millis(100));
+            }
+            ui.end(format!(
+                "Ring key {} activates in {} seconds; {} is accepted for {} seconds after that.",
+                new_key.name_with_rev(),
+                delay,
+                current.name_with_rev(),
+                grace_period
+            ))?;
+            Ok(())
+        }
+    }
+}
+
+pub mod compact {
+    use std::path::Path;
+
+    use butterfly::rumor::dat_file::DatFile;
+    use butterfly::server::timing::Timing;
+    use common::ui::UI;
+
+    use error::{Error, Result};
+
+    /// Compacts a Supervisor's rumor file in place, expiring rumors after the same time a running
+    /// Supervisor does. The Supervisor that owns the file must not be running, or it will simply
+    /// write its rumors back.
+    pub fn start(ui: &mut UI, path: &Path) -> Result<()> {
+        ui.begin(format!("Compacting rumor file {}", path.display()))?;
+        let ttl = Timing::default().rumor_ttl_duration();
+        let compaction = DatFile::from_path(path).compact(ttl).map_err(|e| {
+            Error::ButterflyError(e.to_string())
+        })?;
+        ui.end(format!(
+            "Dropped {} rumors, {} service groups and {} expired tombstones; {} bytes to {} bytes.",
+            compaction.rumors,
+            compaction.service_groups,
+            compaction.tombstones,
+            compaction.bytes_before,
+            compaction.bytes_after
+        ))?;
+        Ok(())
+    }
+}
+
+pub mod query {
+    use std::collections::BTreeMap;
+    use std::io::{Read, Write};
+
+    use common::ui::UI;
+    use http_client::ApiClient;
+    use hyper::header::{Authorization, Bearer};
+    use hyper::status::StatusCode;
+    use serde::Serialize;
+    use serde_json::{self, Value};
+    use tabwriter::TabWriter;
+
+    use {PRODUCT, VERSION};
+    use error::{Error, Result};
+
+    /// What to list about the ring.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub enum Query {
+        Members,
+        Services,
+        Configs,
+        Files,
+    }
+
+    #[derive(Debug, PartialEq, Serialize)]
+    pub struct MemberRow {
+        pub id: String,
+        pub address: String,
+        pub swim_port: u64,
+        pub gossip_port: u64,
+        pub incarnation: u64,
+        pub persistent: bool,
+        pub health: String,
+        pub tags: BTreeMap<String, String>,
+    }
+
+    #[derive(Debug, PartialEq, Serialize)]
+    pub struct ServiceGroupRow {
+        pub service_group: String,
+        pub members: Vec<String>,
+        pub leader: Option<String>,
+        pub election: String,
+    }
+
+    #[derive(Debug, PartialEq, Serialize)]
+    pub struct ConfigRow {
+        pub service_group: String,
+        pub incarnation: u64,
+        pub encrypted: bool,
+    }
+
+    #[derive(Debug, PartialEq, Serialize)]
+    pub struct FileRow {
+        pub service_group: String,
+        pub filename: String,
+        pub incarnation: u64,
+        pub encrypted: bool,
+    }
+
+    /// Lists what a Supervisor knows about its ring, as read from its HTTP gateway. Nothing joins
+    /// the ring, so the listing is only as fresh as that Supervisor's view of it.
+    pub fn start(
+        ui: &mut UI,
+        gateway: &str,
+        token: Option<&str>,
+        query: Query,
+        format: &str,
+    ) -> Result<()> {
+        let ring = fetch(gateway, token)?;
+        let json = format == "json";
+        match query {
+            Query::Members => {
+                print(
+                    ui,
+                    json,
+                    &members(&ring),
+                    &[
+                        "member id",
+                        "address",
+                        "swim port",
+                        "gossip port",
+                        "incarnation",
+                        "persistent",
+                        "health",
+                        "tags",
+                    ],
+                    |m| {
+                        vec![
+                            m.id.clone(),
+                            m.address.clone(),
+                            m.swim_port.to_string(),
+                            m.gossip_port.to_string(),
+                            m.incarnation.to_string(),
+                            m.persistent.to_string(),
+                            m.health.clone(),
+                            if m.tags.is_empty() {
+                                "-".to_string()
+                            } else {
+                                m.tags
+                                    .iter()
+                                    .map(|(key, value)| format!("{}={}", key, value))
+                                    .collect::<Vec<String>>()
+                                    .join(",")
+                            },
+                        ]
+                    },
+                )
+            }
+            Query::Services => {
+                print(
+                    ui,
+                    json,
+                    &service_groups(&ring),
+                    &["service group", "members", "leader", "election"],
+                    |sg| {
+                        vec![
+                            sg.service_group.clone(),
+                            sg.members.len().to_string(),
+                            sg.leader.clone().unwrap_or("-".to_string()),
+                            sg.election.clone(),
+                        ]
+                    },
+                )
+            }
+            Query::Configs => {
+                print(
+                    ui,
+                    json,
+                    &configs(&ring),
+                    &["service group", "incarnation", "encrypted"],
+                    |c| {
+                        vec![
+                            c.service_group.clone(),
+                            c.incarnation.to_string(),
+                            c.encrypted.to_string(),
+                        ]
+                    },
+                )
+            }
+            Query::Files => {
+                print(
+                    ui,
+                    json,
+                    &files(&ring),
+                    &["service group", "filename", "incarnation", "encrypted"],
+                    |f| {
+                        vec![
+                            f.service_group.clone(),
+                            f.filename.clone(),
+                            f.incarnation.to_string(),
+                            f.encrypted.to_string(),
+                        ]
+                    },
+                )
+            }
+        }
+    }
+
+    /// Reads the Supervisor's `/butterfly` document.
+    fn fetch(gateway: &str, token: Option<&str>) -> Result<Value> {
+        let client = ApiClient::new(gateway, PRODUCT, VERSION, None).map_err(|e| {
+            Error::ButterflyError(e.to_string())
+        })?;
+        let mut request = client.get("butterfly");
+        if let Some(token) = token {
+            request = request.header(Authorization(Bearer { token: token.to_string() }));
+        }
+        let mut response = request.send().map_err(|e| {
+            Error::ButterflyError(format!("Cannot query {}: {}", gateway, e))
+        })?;
+        if response.status != StatusCode::Ok {
+            return Err(Error::ButterflyError(format!(
+                "Cannot query {}: the HTTP gateway answered {}",
+                gateway,
+                response.status
+            )));
+        }
+        let mut body = String::new();
+        response.read_to_string(&mut body)?;
+        serde_json::from_str(&body).map_err(|e| {
+            Error::ButterflyError(format!("Cannot decode the ring from {}: {}", gateway, e))
+        })
+    }
+
+    fn print<T, F>(ui: &mut UI, json: bool, rows: &[T], titles: &[&str], cells: F) -> Result<()>
+    where
+        T: Serialize,
+        F: Fn(&T) -> Vec<String>,
+    {
+        if json {
+            let out = serde_json::to_string_pretty(rows).map_err(|e| {
+                Error::ButterflyError(e.to_string())
+            })?;
+            write!(ui.out(), "{}\n", out)?;
+            return Ok(());
+        }
+        let mut tw = TabWriter::new(ui.out());
+        write!(tw, "{}\n", titles.join("\t"))?;
+        for row in rows.iter() {
+            write!(tw, "{}\n", cells(row).join("\t"))?;
+        }
+        tw.flush()?;
+        Ok(())
+    }
+
+    /// Returns the entries of one of the ring's rumor stores, as a map of service group to the
+    /// rumors about it.
+    fn store<'a>(ring: &'a Value, name: &str) -> Vec<(&'a String, &'a Value)> {
+        match ring[name]["list"].as_object() {
+            Some(list) => list.iter().collect(),
+            None => Vec::new(),
+        }
+    }
+
+    fn string(value: &Value) -> String {
+        value.as_str().unwrap_or("").to_string()
+    }
+
+    pub fn members(ring: &Value) -> Vec<MemberRow> {
+        let mut rows = Vec::new();
+        if let Some(members) = ring["member"]["members"].as_object() {
+            for (id, member) in members.iter() {
+                let member = if member["proto"].is_object() {
+                    &member["proto"]
+                } else {
+                    member
+                };
+                rows.push(MemberRow {
+                    id: id.clone(),
+                    address: string(&member["address"]),
+                    swim_port: member["swim_port"].as_u64().unwrap_or(0),
+                    gossip_port: member["gossip_port"].as_u64().unwrap_or(0),
+                    incarnation: member["incarnation"].as_u64().unwrap_or(0),
+                    persistent: member["persistent"].as_bool().unwrap_or(false),
+                    health: ring["member"]["health"][id]
+                        .as_str()
+                        .unwrap_or("Unknown")
+                        .to_string(),
+                    tags: member["tags"]
+                        .as_object()
+                        .map(|tags| {
+                            tags.iter().map(|(key, value)| (key.clone(), string(value))).collect()
+                        })
+                        .unwrap_or(BTreeMap::new()),
+                });
+            }
+        }
+        rows.sort_by(|a, b| a.id.cmp(&b.id));
+        rows
+    }
+
+    pub fn service_groups(ring: &Value) -> Vec<ServiceGroupRow> {
+        let mut groups: BTreeMap<String, ServiceGroupRow> = BTreeMap::new();
+        for (sg, services) in store(ring, "service") {
+            let mut members: Vec<String> = services
+                .as_object()
+                .map(|s| s.keys().cloned().collect())
+                .unwrap_or(Vec::new());
+            members.sort();
+            groups.insert(
+                sg.clone(),
+                ServiceGroupRow {
+                    service_group: sg.clone(),
+                    members: members,
+                    leader: None,
+                    election: "-".to_string(),
+                },
+            );
+        }
+        for (sg, elections) in store(ring, "election") {
+            let election = &elections["election"]["election"];
+            if let Some(row) = groups.get_mut(sg) {
+                // The status is the protocol's enum value; see `Election_Status`.
+                let (leader, status) = match election["status"].as_u64() {
+                    Some(1) => (None, "running"),
+                    Some(2) => (None, "no quorum"),
+                    Some(3) => (Some(string(&election["member_id"])), "finished"),
+                    _ => (None, "-"),
+                };
+                row.leader = leader;
+                row.election = format!("{} (term {})", status, election["term"]);
+            }
+        }
+        groups.into_iter().map(|(_, row)| row).collect()
+    }
+
+    pub fn configs(ring: &Value) -> Vec<ConfigRow> {
+        let mut rows: Vec<ConfigRow> = store(ring, "service_config")
+            .into_iter()
+            .map(|(sg, configs)| {
+                let config = &configs["service_config"]["service_config"];
+                ConfigRow {
+                    service_group: sg.clone(),
+                    incarnation: config["incarnation"].as_u64().unwrap_or(0),
+                    encrypted: config["encrypted"].as_bool().unwrap_or(false),
+                }
+            })
+            .collect();
+        rows.sort_by(|a, b| a.service_group.cmp(&b.service_group));
+        rows
+    }
+
+    pub fn files(ring: &Value) -> Vec<FileRow> {
+        let mut rows = Vec::new();
+        for (sg, files) in store(ring, "service_file") {
+            if let Some(files) = files.as_object() {
+                for (filename, file) in files.iter() {
+                    let file = &file["service_file"];
+                    rows.push(FileRow {
+                        service_group: sg.clone(),
+                        filename: filename.clone(),
+                        incarnation: file["incarnation"].as_u64().unwrap_or(0),
+                        encrypted: file["encrypted"].as_bool().unwrap_or(false),
+                    });
+                }
+            }
+        }
+        rows.sort_by(|a, b| {
+            (&a.service_group, &a.filename).cmp(&(&b.service_group, &b.filename))
+        });
+        rows
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use std::collections::BTreeMap;
+
+        use serde_json::{self, Value};
+
+        use super::{configs, files, members, service_groups, ConfigRow, FileRow, MemberRow,
+                    ServiceGroupRow};
+
+        /// A trimmed down `/butterfly` document, as a Supervisor's HTTP gateway serves it.
+        const RING: &'static str = r#"{
+            "member": {
+                "members": {
+                    "b2": {"proto": {"id": "b2", "incarnation": 3, "address": "10.0.0.2",
+                                     "swim_port": 9638, "gossip_port": 9639,
+                                     "persistent": true, "tags": {"zone": "us-east-1a"}}},
+                    "a1": {"proto": {"id": "a1", "incarnation": 0, "address": "10.0.0.1",
+                                     "swim_port": 9638, "gossip_port": 9639,
+                                     "persistent": false, "tags": {}}}
+                },
+                "health": {"a1": "Alive"},
+                "update_counter": 4
+            },
+            "service": {
+                "list": {
+                    "redis.default": {"b2": {}, "a1": {}},
+                    "nginx.default": {"a1": {}},
+                    "etcd.default": {"b2": {}},
+                    "web.default": {"a1": {}}
+                },
+                "update_counter": 4
+            },
+            "service_config": {
+                "list": {
+                    "redis.default": {"service_config": {"service_config": {
+                        "service_group": "redis.default", "incarnation": 2,
+                        "encrypted": false, "config": "port = 6379"}}},
+                    "nginx.default": {"service_config": {"service_config": {
+                        "service_group": "nginx.default", "incarnation": 7,
+                        "encrypted": true, "config": []}}}
+                },
+                "update_counter": 2
+            },
+            "service_file": {
+                "list": {
+                    "redis.default": {
+                        "redis.conf": {"service_file": {"service_group": "redis.default",
+                                       "incarnation": 1, "encrypted": false,
+                                       "filename": "redis.conf", "body": ""}},
+                        "acl.conf": {"service_file": {"service_group": "redis.default",
+                                     "incarnation": 3, "encrypted": true,
+                                     "filename": "acl.conf", "body": []}}
+                    }
+                },
+                "update_counter": 2
+            },
+            "election": {
+                "list": {
+                    "nginx.default": {"election": {"election": {
+                        "member_id": "a1", "term": 1, "status": 1}}},
+                    "etcd.default": {"election": {"election": {
+                        "member_id": "b2", "term": 2, "status": 2}}},
+                    "redis.default": {"election": {"election": {
+                        "member_id": "b2", "term": 4, "status": 3}}}
+                },
+                "update_counter": 3
+            },
+            "local_health": 0
+        }"#;
+
+        fn ring() -> Value {
+            serde_json::from_str(RING).expect("Sample ring is valid JSON")
+        }
+
+        #[test]
+        fn members_are_sorted_with_their_health_and_tags() {
+            let mut tags = BTreeMap::new();
+            tags.insert(String::from("zone"), String::from("us-east-1a"));
+            assert_eq!(
+                members(&ring()),
+                vec![
+                    MemberRow {
+                        id: String::from("a1"),
+                        address: String::from("10.0.0.1"),
+                        swim_port: 9638,
+                        gossip_port: 9639,
+                        incarnation: 0,
+                        persistent: false,
+                        health: String::from("Alive"),
+                        tags: BTreeMap::new(),
+                    },
+                    MemberRow {
+                        id: String::from("b2"),
+                        address: String::from("10.0.0.2"),
+                        swim_port: 9638,
+                        gossip_port: 9639,
+                        incarnation: 3,
+                        persistent: true,
+                        health: String::from("Unknown"),
+                        tags: tags,
+                    },
+                ]
+            );
+        }
+
+        #[test]
+        fn service_groups_map_the_election_status() {
+            let row = |sg: &str, members: &[&str], leader: Option<&str>, election: &str| {
+                ServiceGroupRow {
+                    service_group: String::from(sg),
+                    members: members.iter().map(|m| String::from(*m)).collect(),
+                    leader: leader.map(String::from),
+                    election: String::from(election),
+                }
+            };
+            assert_eq!(
+                service_groups(&ring()),
+                vec![
+                    row("etcd.default", &["b2"], None, "no quorum (term 2)"),
+                    row("nginx.default", &["a1"], None, "running (term 1)"),
+                    row("redis.default", &["a1", "b2"], Some("b2"), "finished (term 4)"),
+                    row("web.default", &["a1"], None, "-"),
+                ]
+            );
+        }
+
+        #[test]
+        fn configs_are_listed_per_service_group() {
+            assert_eq!(
+                configs(&ring()),
+                vec![
+                    ConfigRow {
+                        service_group: String::from("nginx.default"),
+                        incarnation: 7,
+                        encrypted: true,
+                    },
+                    ConfigRow {
+                        service_group: String::from("redis.default"),
+                        incarnation: 2,
+                        encrypted: false,
+                    },
+                ]
+            );
+        }
+
+        #[test]
+        fn files_are_listed_per_service_group_and_name() {
+            assert_eq!(
+                files(&ring()),
+                vec![
+                    FileRow {
+                        service_group: String::from("redis.default"),
+                        filename: String::from("acl.conf"),
+                        incarnation: 3,
+                        encrypted: true,
+                    },
+                    FileRow {
+                        service_group: String::from("redis.default"),
+                        filename: String::from("redis.conf"),
+                        incarnation: 1,
+                        encrypted: false,
+                    },
+                ]
+            );
+        }
+
+        #[test]
+        fn an_empty_ring_lists_nothing() {
+            let ring = serde_json::from_str("{}").unwrap();
+            assert!(members(&ring).is_empty());
+            assert!(service_groups(&ring).is_empty());
+            assert!(configs(&ring).is_empty());
+            assert!(files(&ring).is_empty());
+        }
+    }
+}
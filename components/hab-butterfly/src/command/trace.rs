@@ -0,0 +1,60 @@
+// Copyright (c) 2017 Chef Software Inc. and/or applicable contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::io::Write;
+use std::path::Path;
+
+use butterfly::trace;
+use common::ui::UI;
+use serde_json;
+
+use error::{Error, Result};
+
+/// The formats a merged timeline can be written in.
+pub const FORMATS: &'static [&'static str] = &["ndjson", "chrome", "text"];
+
+/// Merges the trace files written by several members into a single timeline, ordered by time,
+/// and writes it to standard output.
+///
+/// * `ndjson` writes one event per line, just like the trace files themselves.
+/// * `chrome` writes a Chrome trace-event document, for `chrome://tracing` or Perfetto.
+/// * `text` writes the `^` separated lines `bin/trace-sequence.rb` understands.
+pub fn start<P: AsRef<Path>>(ui: &mut UI, files: &[P], format: &str) -> Result<()> {
+    let mut traces = Vec::with_capacity(files.len());
+    for file in files.iter() {
+        let records = trace::read_trace_file(file).map_err(
+            |e| Error::ButterflyError(e.to_string()),
+        )?;
+        traces.push(records);
+    }
+    let timeline = trace::merge(traces);
+    let out = ui.out();
+    match format {
+        "chrome" => write!(out, "{}\n", trace::to_chrome_trace(&timeline))?,
+        "text" => {
+            for record in timeline.iter() {
+                write!(out, "{}\n", record)?;
+            }
+        }
+        _ => {
+            for record in timeline.iter() {
+                let line = serde_json::to_string(record).map_err(|e| {
+                    Error::ButterflyError(e.to_string())
+                })?;
+                write!(out, "{}\n", line)?;
+            }
+        }
+    }
+    Ok(())
+}
@@ -20,6 +20,7 @@ extern crate clap;
 extern crate env_logger;
 extern crate hab;
 extern crate hab_butterfly;
+extern crate habitat_butterfly as butterfly;
 extern crate habitat_core as hcore;
 extern crate habitat_common as common;
 #[macro_use]
@@ -33,6 +34,7 @@ use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::thread;
 
+use butterfly::transport::GossipTransportKind;
 use clap::ArgMatches;
 
 use common::ui::UI;
@@ -41,14 +43,22 @@ use hcore::crypto::{init, default_cache_key_path, BoxKeyPair, SymKey};
 use hcore::service::ServiceGroup;
 
 use hab_butterfly::{analytics, cli, command};
+use hab_butterfly::command::ring::query::Query;
 use hab_butterfly::error::{Error, Result};
 
 /// Makes the --org CLI param optional when this env var is set
 const HABITAT_ORG_ENVVAR: &'static str = "HAB_ORG";
 /// Makes the --user CLI param optional when this env var is set
 const HABITAT_USER_ENVVAR: &'static str = "HAB_USER";
+/// The bearer token the Supervisor's HTTP gateway requires, if it was started with one
+const HABITAT_HTTP_AUTH_TOKEN_ENVVAR: &'static str = "HAB_SUP_HTTP_AUTH_TOKEN";
 const HABITAT_BUTTERFLY_PORT: u64 = 9638;
+const HABITAT_HTTP_GATEWAY: &'static str = "http://127.0.0.1:9631";
 const MAX_FILE_UPLOAD_SIZE_BYTES: u64 = 4096;
+/// How long after a ring key rotation Supervisors start encrypting with the new key
+const RING_KEY_ROTATE_DELAY_SECS: u64 = 60;
+/// How long Supervisors keep accepting the old ring key once the new one is active
+const RING_KEY_GRACE_PERIOD_SECS: u64 = 300;
 
 lazy_static! {
     /// The default filesystem root path to base all commands from. This is lazily generated on
@@ -100,6 +110,23 @@ fn start(ui: &mut UI) -> Result<()> {
                 _ => unreachable!(),
             }
         }
+        ("ring", Some(matches)) => {
+            match matches.subcommand() {
+                ("key", Some(m)) => {
+                    match m.subcommand() {
+                        ("rotate", Some(sc)) => sub_ring_key_rotate(ui, sc)?,
+                        _ => unreachable!(),
+                    }
+                }
+                ("compact", Some(m)) => sub_ring_compact(ui, m)?,
+                ("members", Some(m)) => sub_ring_query(ui, m, Query::Members)?,
+                ("services", Some(m)) => sub_ring_query(ui, m, Query::Services)?,
+                ("configs", Some(m)) => sub_ring_query(ui, m, Query::Configs)?,
+                ("files", Some(m)) => sub_ring_query(ui, m, Query::Files)?,
+                _ => unreachable!(),
+            }
+        }
+        ("trace", Some(m)) => sub_trace(ui, m)?,
         _ => unreachable!(),
     };
     Ok(())
@@ -122,7 +149,7 @@ fn sub_depart(ui: &mut UI, m: &ArgMatches) -> Result<()> {
         Some(name) => Some(SymKey::get_latest_pair_for(&name, &cache)?),
         None => None,
     };
-    command::depart::run(ui, member_id, peers, ring_key)
+    command::depart::run(ui, member_id, peers, gossip_transport(m), ring_key)
 }
 
 fn sub_config_apply(ui: &mut UI, m: &ArgMatches) -> Result<()> {
@@ -160,12 +187,20 @@ fn sub_config_apply(ui: &mut UI, m: &ArgMatches) -> Result<()> {
         Some(username) => Some(BoxKeyPair::get_latest_pair_for(username, &cache)?),
         None => None,
     };
+    let gateway = m.value_of("GATEWAY").unwrap_or(HABITAT_HTTP_GATEWAY);
+    let token = match m.value_of("TOKEN") {
+        Some(t) => Some(t.to_string()),
+        None => henv::var(HABITAT_HTTP_AUTH_TOKEN_ENVVAR).ok(),
+    };
     command::config::apply::start(
         ui,
         &sg,
         number,
         file_path,
+        gateway,
+        token.as_ref().map(String::as_str),
         &peers,
+        gossip_transport(m),
         ring_key.as_ref(),
         user_pair.as_ref(),
         service_pair.as_ref(),
@@ -226,12 +261,64 @@ fn sub_file_upload(ui: &mut UI, m: &ArgMatches) -> Result<()> {
         number,
         file_path,
         &peers,
+        gossip_transport(m),
         ring_key.as_ref(),
         user_pair.as_ref(),
         service_pair.as_ref(),
     )
 }
 
+fn sub_ring_key_rotate(ui: &mut UI, m: &ArgMatches) -> Result<()> {
+    let peers_str = m.value_of("PEER").unwrap_or("127.0.0.1");
+    let mut peers: Vec<String> = peers_str.split(",").map(|p| p.into()).collect();
+    for p in peers.iter_mut() {
+        if p.find(':').is_none() {
+            p.push(':');
+            p.push_str(&HABITAT_BUTTERFLY_PORT.to_string());
+        }
+    }
+    let ring = m.value_of("RING").unwrap(); // Required via clap
+    let delay = value_t!(m, "DELAY", u64).unwrap_or(RING_KEY_ROTATE_DELAY_SECS);
+    let grace_period = value_t!(m, "GRACE_PERIOD", u64).unwrap_or(RING_KEY_GRACE_PERIOD_SECS);
+
+    init();
+    command::ring::key::rotate::start(
+        ui,
+        ring,
+        delay,
+        grace_period,
+        &peers,
+        gossip_transport(m),
+        &default_cache_key_path(Some(&*FS_ROOT)),
+    )
+}
+
+fn sub_ring_compact(ui: &mut UI, m: &ArgMatches) -> Result<()> {
+    let dat_file = Path::new(m.value_of("DAT_FILE").unwrap()); // Required via clap
+    command::ring::compact::start(ui, dat_file)
+}
+
+fn sub_ring_query(ui: &mut UI, m: &ArgMatches, query: Query) -> Result<()> {
+    let gateway = m.value_of("GATEWAY").unwrap_or(HABITAT_HTTP_GATEWAY);
+    let token = match m.value_of("TOKEN") {
+        Some(t) => Some(t.to_string()),
+        None => henv::var(HABITAT_HTTP_AUTH_TOKEN_ENVVAR).ok(),
+    };
+    let format = m.value_of("FORMAT").unwrap_or("table");
+    command::ring::query::start(ui, gateway, token.as_ref().map(String::as_str), query, format)
+}
+
+fn sub_trace(ui: &mut UI, m: &ArgMatches) -> Result<()> {
+    let files: Vec<&str> = m.values_of("FILE").unwrap().collect(); // Required via clap
+    let format = m.value_of("FORMAT").unwrap_or("ndjson");
+    command::trace::start(ui, &files, format)
+}
+
+/// The gossip transport used to reach the peers; clap has already validated the name.
+fn gossip_transport(m: &ArgMatches) -> GossipTransportKind {
+    value_t!(m, "GOSSIP_TRANSPORT", GossipTransportKind).unwrap_or_default()
+}
+
 /// Parse the raw program arguments and split off any arguments that will skip clap's parsing.
 ///
 /// **Note** with the current version of clap there is no clean way to ignore arguments after a
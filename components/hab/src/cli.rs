@@ -16,7 +16,7 @@ use std::path::Path;
 use std::result;
 use std::str::FromStr;
 
-use clap::{App, AppSettings, Arg};
+use clap::{App, AppSettings, Arg, SubCommand};
 use hcore::crypto::keys::PairType;
 use regex::Regex;
 use url::Url;
@@ -73,6 +73,10 @@ pub fn get() -> App<'static, 'static> {
                     (default: 127.0.0.1:9638)")
                 (@arg RING: -r --ring +takes_value
                     "Ring key name, which will encrypt communication messages")
+                (@arg GOSSIP_TRANSPORT: --("gossip-transport") +takes_value
+                    {valid_gossip_transport}
+                    "How rumors are sent to the peers, either zeromq or tcp; it must match the \
+                    transport of the Supervisor ring [default: zeromq]")
             )
         )
         (@subcommand bldr =>
@@ -342,6 +346,7 @@ pub fn get() -> App<'static, 'static> {
                     (ex: core/busybox-static/1.24.2/20160708162350)")
                 (@arg FULL_PATHS: -p "Show full path to file")
             )
+            (subcommand: sub_render())
             (@subcommand search =>
                 (about: "Search for a package in Builder")
                 (@arg SEARCH_TERM: +required +takes_value "Search term")
@@ -452,6 +457,7 @@ pub fn get() -> App<'static, 'static> {
                 (@arg SCAFFOLDING: --scaffolding -s +takes_value
                     "Specify explicit Scaffolding for your app (ex: node, ruby)")
             )
+            (subcommand: sub_render())
         )
         (@subcommand ring =>
             (about: "Commands relating to Habitat rings")
@@ -476,7 +482,45 @@ pub fn get() -> App<'static, 'static> {
                     (aliases: &["g", "ge", "gen", "gene", "gener", "genera", "generat"])
                     (@arg RING: +required +takes_value "Ring key name")
                 )
+                (@subcommand rotate =>
+                    (about: "Generates a new revision of a ring key and spreads it to the \
+                    Supervisor ring, encrypted with the current revision")
+                    (aliases: &["r", "ro", "rot", "rota", "rotat"])
+                    (@arg RING: +required +takes_value "Ring key name")
+                    (@arg DELAY: --delay +takes_value {valid_numeric}
+                        "Seconds before Supervisors start encrypting with the new revision \
+                        (default: 60)")
+                    (@arg GRACE_PERIOD: --("grace-period") +takes_value {valid_numeric}
+                        "Seconds that Supervisors keep accepting the current revision once the \
+                        new one is active (default: 300)")
+                    (@arg PEER: -p --peer +takes_value
+                        "A comma-delimited list of one or more Habitat Supervisor peers \
+                        (default: 127.0.0.1:9638)")
+                    (@arg GOSSIP_TRANSPORT: --("gossip-transport") +takes_value
+                        {valid_gossip_transport}
+                        "How rumors are sent to the peers, either zeromq or tcp; it must match the \
+                        transport of the Supervisor ring [default: zeromq]")
+                )
             )
+            (@subcommand compact =>
+                (about: "Compacts the rumor file of a stopped Supervisor, dropping the rumors of \
+                departed members and of service groups left without services")
+                (aliases: &["c", "co", "com", "comp", "compa", "compac"])
+                (@arg DAT_FILE: +required {file_exists}
+                    "Path to the rumor file (ex: /hab/sup/default/data/<member-id>.rst)")
+            )
+            (subcommand: sub_ring_query("members",
+                "Lists the members of the ring and their health")
+                .aliases(&["m", "me", "mem", "memb", "membe", "member"]))
+            (subcommand: sub_ring_query("services",
+                "Lists the service groups in the ring and their leaders")
+                .aliases(&["s", "se", "ser", "serv", "servi", "servic", "service"]))
+            (subcommand: sub_ring_query("configs",
+                "Lists the configurations applied to service groups in the ring")
+                .aliases(&["con", "conf", "confi", "config"]))
+            (subcommand: sub_ring_query("files",
+                "Lists the files uploaded to service groups in the ring")
+                .aliases(&["f", "fi", "fil", "file"]))
         )
         (@subcommand svc =>
             (about: "Commands relating to Habitat services")
@@ -628,6 +672,34 @@ fn sub_cli_completers() -> App<'static, 'static> {
     )
 }
 
+fn sub_ring_query(name: &'static str, about: &'static str) -> App<'static, 'static> {
+    SubCommand::with_name(name)
+        .about(about)
+        .arg(
+            Arg::with_name("GATEWAY")
+                .help(
+                    "The HTTP gateway of a Habitat Supervisor in the ring \
+                    (default: http://127.0.0.1:9631)",
+                )
+                .short("g")
+                .long("gateway")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("TOKEN")
+                .help("Bearer token for the HTTP gateway (default: $HAB_SUP_HTTP_AUTH_TOKEN)")
+                .long("token")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("FORMAT")
+                .help("Output format; [default: table] [values: table, json]")
+                .long("format")
+                .takes_value(true)
+                .validator(valid_output_format),
+        )
+}
+
 fn sub_config_apply() -> App<'static, 'static> {
     clap_app!(@subcommand apply =>
         (about: "Applies a configuration to a group of Habitat Supervisors")
@@ -636,6 +708,9 @@ fn sub_config_apply() -> App<'static, 'static> {
             (default: 127.0.0.1:9638)")
         (@arg RING: -r --ring +takes_value
             "Ring key name, which will encrypt communication messages")
+        (@arg GOSSIP_TRANSPORT: --("gossip-transport") +takes_value {valid_gossip_transport}
+            "How rumors are sent to the peers, either zeromq or tcp; it must match the \
+            transport of the Supervisor ring [default: zeromq]")
         (@arg SERVICE_GROUP: +required {valid_service_group}
             "Target service group (ex: redis.default)")
         (@arg VERSION_NUMBER: +required
@@ -643,6 +718,32 @@ fn sub_config_apply() -> App<'static, 'static> {
         (@arg FILE: {file_exists_or_stdin}
             "Path to local file on disk (ex: /tmp/config.toml, default: <stdin>)")
         (@arg ORG: --org +takes_value "Name of service organization")
+        (@arg GATEWAY: -g --gateway +takes_value
+            "The HTTP gateway of a Habitat Supervisor running the service group, which checks \
+            the configuration against the package's config schema before it's applied \
+            (default: http://127.0.0.1:9631)")
+        (@arg TOKEN: --token +takes_value
+            "Bearer token for the HTTP gateway (default: $HAB_SUP_HTTP_AUTH_TOKEN)")
+    )
+}
+
+fn sub_render() -> App<'static, 'static> {
+    clap_app!(@subcommand render =>
+        (about: "Renders the configuration templates and hooks of a package or plan into a \
+            directory, checking them against a mock census without running the service")
+        (@arg PKG_IDENT_OR_PLAN: +required +takes_value
+            "An installed package identifier (ex: core/redis) or a directory containing a \
+            `plan.sh` file or a `habitat/` directory which contains the `plan.sh` file")
+        (@arg GROUP: --group +takes_value
+            "The service group; shared config and topology [default: default]")
+        (@arg USER_TOML: --("user-toml") +takes_value
+            "A TOML file applied over default.toml, as a service's user.toml would be")
+        (@arg CENSUS: --census +takes_value
+            "A JSON file describing the members of the service's and bound service groups")
+        (@arg BIND: --bind +takes_value +multiple
+            "One or more service groups to bind to a configuration")
+        (@arg OUTPUT: --output -o +takes_value
+            "The directory the rendered files are written to [default: ./results/render]")
     )
 }
 
@@ -721,6 +822,29 @@ fn file_exists_or_stdin(val: String) -> result::Result<(), String> {
     if val == "-" { Ok(()) } else { file_exists(val) }
 }
 
+fn valid_numeric(val: String) -> result::Result<(), String> {
+    match val.parse::<u64>() {
+        Ok(_) => Ok(()),
+        Err(_) => Err(format!("'{}' is not a valid number", &val)),
+    }
+}
+
+fn valid_gossip_transport(val: String) -> result::Result<(), String> {
+    match val.as_str() {
+        "zeromq" | "tcp" => Ok(()),
+        _ => Err(format!(
+            "Gossip transport should be either 'zeromq' or 'tcp'"
+        )),
+    }
+}
+
+fn valid_output_format(val: String) -> result::Result<(), String> {
+    match val.as_str() {
+        "table" | "json" => Ok(()),
+        _ => Err(format!("'{}' is not a valid output format", &val)),
+    }
+}
+
 fn valid_pair_type(val: String) -> result::Result<(), String> {
     match PairType::from_str(&val) {
         Ok(_) => Ok(()),
@@ -695,9 +695,22 @@ fn exec_subcommand_if_called(ui: &mut UI) -> Result<()> {
             args.insert(0, OsString::from("config"));
             command::butterfly::start(ui, args)
         }
-        ("config", _, _) | ("file", _, _) => {
+        ("config", _, _) |
+        ("file", _, _) |
+        ("ring", "key", "rotate") |
+        ("ring", "compact", _) |
+        ("ring", "members", _) |
+        ("ring", "services", _) |
+        ("ring", "configs", _) |
+        ("ring", "files", _) => {
             command::butterfly::start(ui, env::args_os().skip(1).collect())
         }
+        ("pkg", "render", _) |
+        ("plan", "render", _) => {
+            let mut args: Vec<OsString> = env::args_os().skip(3).collect();
+            args.insert(0, OsString::from("render"));
+            command::sup::start(ui, args)
+        }
         ("pkg", "export", "docker") => {
             command::pkg::export::docker::start(ui, env::args_os().skip(4).collect())
         }
@@ -84,6 +84,13 @@ impl ApiClient {
         })
     }
 
+    /// Sets how long the client waits on a read or a write of its socket before giving up, in
+    /// place of the default of `CLIENT_SOCKET_RW_TIMEOUT` seconds.
+    pub fn set_timeout(&mut self, timeout: Duration) {
+        self.inner.set_read_timeout(Some(timeout));
+        self.inner.set_write_timeout(Some(timeout));
+    }
+
     /// Builds an HTTP GET request for a given path.
     pub fn get(&self, path: &str) -> RequestBuilder {
         self.get_with_custom_url(path, |_| {})
@@ -14,7 +14,10 @@
 
 use std::collections::HashMap;
 use std::io;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
 
 use core::os::process::Pid;
 use ipc_channel::ipc::{IpcOneShotServer, IpcReceiver, IpcSender};
@@ -26,9 +29,49 @@ use error::{Error, Result};
 type Env = HashMap<String, String>;
 type IpcServer = IpcOneShotServer<Vec<u8>>;
 
+/// How long to wait for the Launcher to answer an `ExitStatus` request. Launchers older than the
+/// Supervisor drop messages they don't know, and never answer it.
+const EXIT_STATUS_TIMEOUT_MS: u64 = 2_000;
+
+/// How a process started by the Launcher exited.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ExitStatus {
+    /// Exit code of the process, `None` if it was terminated by a signal.
+    pub code: Option<i32>,
+    /// True if the process was killed for exceeding its memory limit.
+    pub oom_killed: bool,
+}
+
+/// How the output of a spawned process is logged as JSON records.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct JsonLog {
+    /// Member id of the Supervisor, included in every record.
+    pub member_id: String,
+    /// File the records are also appended to.
+    pub log_file: PathBuf,
+    /// Size, in bytes, after which the log file is rotated.
+    pub max_size: u64,
+    /// Age, in seconds, after which the log file is rotated.
+    pub max_age: u64,
+}
+
+/// Resource limits applied to a spawned process, if supported by the host.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ResourceLimits {
+    /// CPU time in thousandths of a CPU.
+    pub cpu: Option<u32>,
+    /// Memory in bytes.
+    pub memory: Option<u64>,
+    /// Maximum number of processes and threads.
+    pub pids: Option<u64>,
+}
+
 pub struct LauncherCli {
     tx: IpcSender<Vec<u8>>,
     rx: IpcReceiver<Vec<u8>>,
+    /// Cleared once the Launcher failed to answer an `ExitStatus` request, so that an older
+    /// Launcher isn't asked again.
+    exit_status_supported: AtomicBool,
 }
 
 impl LauncherCli {
@@ -40,7 +83,11 @@ impl LauncherCli {
         Self::send(&tx, &cmd)?;
         let (rx, raw) = ipc_srv.accept().map_err(|_| Error::AcceptConn)?;
         Self::read::<protocol::NetOk>(&raw)?;
-        Ok(LauncherCli { tx: tx, rx: rx })
+        Ok(LauncherCli {
+            tx: tx,
+            rx: rx,
+            exit_status_supported: AtomicBool::new(true),
+        })
     }
 
     /// Read a launcher protocol message from a byte array
@@ -70,6 +117,23 @@ impl LauncherCli {
         }
     }
 
+    /// Receive and read protocol message from an IpcReceiver, giving up after the given timeout
+    fn recv_timeout<T>(rx: &IpcReceiver<Vec<u8>>, timeout: Duration) -> Result<T>
+    where
+        T: protobuf::MessageStatic,
+    {
+        let deadline = Instant::now() + timeout;
+        loop {
+            if let Some(msg) = Self::try_recv::<T>(rx)? {
+                return Ok(msg);
+            }
+            if Instant::now() >= deadline {
+                return Err(Error::Timeout);
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+
     /// Send a command to a Launcher
     fn send<T>(tx: &IpcSender<Vec<u8>>, message: &T) -> Result<()>
     where
@@ -96,6 +160,38 @@ impl LauncherCli {
         }
     }
 
+    /// Retrieve the exit status of a process which was started by the Launcher and has since
+    /// exited.
+    ///
+    /// A Launcher which doesn't answer in time is taken to be older than the Supervisor, and
+    /// every later call fails straight away with `Error::ExitStatusUnsupported`.
+    pub fn exit_status(&self, pid: Pid) -> Result<ExitStatus> {
+        if !self.exit_status_supported.load(Ordering::Relaxed) {
+            return Err(Error::ExitStatusUnsupported);
+        }
+        let mut msg = protocol::ExitStatus::new();
+        msg.set_pid(pid.into());
+        Self::send(&self.tx, &msg)?;
+        let timeout = Duration::from_millis(EXIT_STATUS_TIMEOUT_MS);
+        let reply = match Self::recv_timeout::<protocol::ExitStatusOk>(&self.rx, timeout) {
+            Ok(reply) => reply,
+            Err(Error::Timeout) => {
+                self.exit_status_supported.store(false, Ordering::Relaxed);
+                return Err(Error::ExitStatusUnsupported);
+            }
+            Err(err) => return Err(err),
+        };
+        let code = if reply.has_exit_code() {
+            Some(reply.get_exit_code())
+        } else {
+            None
+        };
+        Ok(ExitStatus {
+            code: code,
+            oom_killed: reply.get_oom_killed(),
+        })
+    }
+
     pub fn is_stopping(&self) -> bool {
         match Self::try_recv::<protocol::Shutdown>(&self.rx) {
             Ok(Some(_)) |
@@ -115,6 +211,9 @@ impl LauncherCli {
     }
 
     /// Send a process spawn command to the connected Launcher
+    ///
+    /// The output of the process is written as JSON records, and appended to a rotated log file,
+    /// when `json_log` is set.
     pub fn spawn<I, B, U, G, P>(
         &self,
         id: I,
@@ -123,6 +222,8 @@ impl LauncherCli {
         group: G,
         password: Option<P>,
         env: Env,
+        limits: ResourceLimits,
+        json_log: Option<JsonLog>,
     ) -> Result<Pid>
     where
         I: ToString,
@@ -139,6 +240,22 @@ impl LauncherCli {
             msg.set_svc_password(password.to_string());
         }
         msg.set_env(env);
+        if let Some(cpu) = limits.cpu {
+            msg.set_cpu_limit(cpu);
+        }
+        if let Some(memory) = limits.memory {
+            msg.set_memory_limit(memory);
+        }
+        if let Some(pids) = limits.pids {
+            msg.set_pids_limit(pids);
+        }
+        if let Some(json_log) = json_log {
+            msg.set_json_log(true);
+            msg.set_member_id(json_log.member_id);
+            msg.set_log_file(json_log.log_file.to_string_lossy().into_owned());
+            msg.set_log_max_size(json_log.max_size);
+            msg.set_log_max_age(json_log.max_age);
+        }
         msg.set_id(id.to_string());
         Self::send(&self.tx, &msg)?;
         let reply = Self::recv::<protocol::SpawnOk>(&self.rx)?;
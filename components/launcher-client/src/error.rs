@@ -29,11 +29,13 @@ pub enum Error {
     BadPipe(io::Error),
     Connect(io::Error),
     Deserialize(protobuf::ProtobufError),
+    ExitStatusUnsupported,
     IPCBincode(String),
     IPCIO(io::ErrorKind),
     Protocol(protocol::NetErr),
     Send(ipc_channel::Error),
     Serialize(protobuf::ProtobufError),
+    Timeout,
 }
 
 pub type Result<T> = result::Result<T, Error>;
@@ -47,6 +49,9 @@ impl fmt::Display for Error {
             Error::Deserialize(ref e) => {
                 format!("Unable to deserialize message from Launcher, {}", e)
             }
+            Error::ExitStatusUnsupported => {
+                format!("Launcher doesn't report exit statuses, it may be older than the Supervisor")
+            }
             Error::IPCBincode(ref e) => {
                 format!("Unable to read message frame from Launcher, {}", e)
             }
@@ -54,6 +59,7 @@ impl fmt::Display for Error {
             Error::Protocol(ref e) => format!("{}", e),
             Error::Send(ref e) => format!("Unable to send to Launcher's pipe, {}", e),
             Error::Serialize(ref e) => format!("Unable to serialize message to Launcher, {}", e),
+            Error::Timeout => format!("Timed out waiting for an answer from Launcher"),
         };
         write!(f, "{}", msg)
     }
@@ -66,11 +72,13 @@ impl error::Error for Error {
             Error::BadPipe(_) => "Unable to open pipe to Launcher",
             Error::Connect(_) => "Unable to connect to Launcher's pipe",
             Error::Deserialize(_) => "Unable to deserialize message from Launcher",
+            Error::ExitStatusUnsupported => "Launcher doesn't report exit statuses",
             Error::IPCBincode(_) => "Unable to encode/decode message framing to/from Launcher",
             Error::IPCIO(_) => "Unable to receive message from Launcher",
             Error::Protocol(_) => "Received an error from Launcher",
             Error::Send(_) => "Unable to send to Launcher's pipe",
             Error::Serialize(_) => "Unable to serialize message to Launcher",
+            Error::Timeout => "Timed out waiting for an answer from Launcher",
         }
     }
 }
@@ -21,9 +21,9 @@ pub mod error;
 mod client;
 
 pub use protocol::{LAUNCHER_LOCK_CLEAN_ENV, LAUNCHER_PID_ENV, ERR_NO_RETRY_EXCODE,
-                   OK_NO_RETRY_EXCODE};
+                   OK_NO_RETRY_EXCODE, LogFile};
 
-pub use client::LauncherCli;
+pub use client::{ExitStatus, JsonLog, LauncherCli, ResourceLimits};
 pub use error::Error;
 
 pub fn env_pipe() -> Option<String> {
@@ -13,13 +13,17 @@
 // limitations under the License.
 
 extern crate protobuf;
+#[cfg(test)]
+extern crate tempdir;
 
+mod log_file;
 mod message;
 
 use std::fmt;
 
 use protobuf::Message;
 
+pub use log_file::{LogFile, ROTATED_LOG_FILES};
 pub use message::error::*;
 pub use message::launcher::*;
 pub use message::supervisor::*;
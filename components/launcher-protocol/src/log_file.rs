@@ -0,0 +1,188 @@
+// Copyright (c) 2017 Chef Software Inc. and/or applicable contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A log file which is appended to and rotated by size and age.
+//!
+//! It is shared by the Supervisor, which writes the output of a service's hooks to it, and the
+//! Launcher, which writes the output of the service's process.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// Number of rotated log files kept next to the current one.
+pub const ROTATED_LOG_FILES: usize = 5;
+
+/// A log file which is appended to and rotated once it grows larger than its maximum size or
+/// older than its maximum age.
+///
+/// The age of the file is counted from when it was opened or last rotated, since not every
+/// filesystem records when a file was created. Rotated files are renamed with a numbered suffix,
+/// `.1` being the most recent one, and only the last few of them are kept.
+#[derive(Debug)]
+pub struct LogFile {
+    path: PathBuf,
+    file: File,
+    size: u64,
+    opened: Instant,
+    max_size: u64,
+    max_age: Duration,
+}
+
+impl LogFile {
+    pub fn open<P>(path: P, max_size: u64, max_age: u64) -> io::Result<Self>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref().to_path_buf();
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let size = file.metadata()?.len();
+        Ok(LogFile {
+            path: path,
+            file: file,
+            size: size,
+            opened: Instant::now(),
+            max_size: max_size,
+            max_age: Duration::from_secs(max_age),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn write_line(&mut self, line: &str) -> io::Result<()> {
+        let line = line.trim_right_matches(|c| c == '\n' || c == '\r');
+        if self.should_rotate(Instant::now()) {
+            self.rotate()?;
+        }
+        self.file.write_all(line.as_bytes())?;
+        self.file.write_all(b"\n")?;
+        self.size += line.len() as u64 + 1;
+        Ok(())
+    }
+
+    fn should_rotate(&self, now: Instant) -> bool {
+        if self.size == 0 {
+            return false;
+        }
+        self.size >= self.max_size || now.duration_since(self.opened) >= self.max_age
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        for n in (1..ROTATED_LOG_FILES).rev() {
+            let from = self.rotated_path(n);
+            if from.exists() {
+                fs::rename(&from, self.rotated_path(n + 1))?;
+            }
+        }
+        fs::rename(&self.path, self.rotated_path(1))?;
+        self.file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        self.size = 0;
+        self.opened = Instant::now();
+        Ok(())
+    }
+
+    fn rotated_path(&self, n: usize) -> PathBuf {
+        let mut name = self.path.clone().into_os_string();
+        name.push(format!(".{}", n));
+        PathBuf::from(name)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs::File;
+    use std::io::Read;
+    use std::path::Path;
+    use std::time::{Duration, Instant};
+
+    use tempdir::TempDir;
+
+    use super::*;
+
+    const MAX_AGE: u64 = 24 * 60 * 60;
+
+    fn read(path: &Path) -> String {
+        let mut content = String::new();
+        File::open(path)
+            .expect("failed to open file")
+            .read_to_string(&mut content)
+            .expect("failed to read file");
+        content
+    }
+
+    #[test]
+    fn rotates_by_size() {
+        let tmpdir = TempDir::new("logfile").unwrap();
+        let path = tmpdir.path().join("hooks.log");
+        let mut file = LogFile::open(&path, 10, MAX_AGE).unwrap();
+        file.write_line("first line").unwrap();
+        file.write_line("second line\n").unwrap();
+        file.write_line("third line").unwrap();
+
+        assert_eq!(read(&path), "third line\n");
+        assert_eq!(read(&tmpdir.path().join("hooks.log.1")), "second line\n");
+        assert_eq!(read(&tmpdir.path().join("hooks.log.2")), "first line\n");
+    }
+
+    #[test]
+    fn keeps_a_limited_number_of_rotated_files() {
+        let tmpdir = TempDir::new("logfile").unwrap();
+        let path = tmpdir.path().join("hooks.log");
+        let mut file = LogFile::open(&path, 1, MAX_AGE).unwrap();
+        for n in 0..(ROTATED_LOG_FILES + 3) {
+            file.write_line(&n.to_string()).unwrap();
+        }
+
+        assert!(
+            tmpdir
+                .path()
+                .join(format!("hooks.log.{}", ROTATED_LOG_FILES))
+                .exists()
+        );
+        assert!(!tmpdir
+            .path()
+            .join(format!("hooks.log.{}", ROTATED_LOG_FILES + 1))
+            .exists());
+    }
+
+    #[test]
+    fn rotates_by_age() {
+        let tmpdir = TempDir::new("logfile").unwrap();
+        let path = tmpdir.path().join("hooks.log");
+        let mut file = LogFile::open(&path, 1024, 0).unwrap();
+        file.write_line("old").unwrap();
+        file.write_line("new").unwrap();
+
+        assert_eq!(read(&path), "new\n");
+        assert_eq!(read(&tmpdir.path().join("hooks.log.1")), "old\n");
+    }
+
+    #[test]
+    fn age_is_counted_from_when_the_file_was_opened() {
+        let tmpdir = TempDir::new("logfile").unwrap();
+        let path = tmpdir.path().join("hooks.log");
+        LogFile::open(&path, 1024, MAX_AGE)
+            .unwrap()
+            .write_line("written by a previous Supervisor")
+            .unwrap();
+        let file = LogFile::open(&path, 1024, 60).unwrap();
+        let now = Instant::now();
+
+        assert!(!file.should_rotate(now));
+        assert!(file.should_rotate(now + Duration::from_secs(60)));
+    }
+}
@@ -21,6 +21,399 @@
 use protobuf::Message as Message_imported_for_functions;
 use protobuf::ProtobufEnum as ProtobufEnum_imported_for_functions;
 
+#[derive(PartialEq,Clone,Default)]
+pub struct ExitStatus {
+    // message fields
+    pid: ::std::option::Option<i64>,
+    // special fields
+    unknown_fields: ::protobuf::UnknownFields,
+    cached_size: ::protobuf::CachedSize,
+}
+
+// see codegen.rs for the explanation why impl Sync explicitly
+unsafe impl ::std::marker::Sync for ExitStatus {}
+
+impl ExitStatus {
+    pub fn new() -> ExitStatus {
+        ::std::default::Default::default()
+    }
+
+    pub fn default_instance() -> &'static ExitStatus {
+        static mut instance: ::protobuf::lazy::Lazy<ExitStatus> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ExitStatus,
+        };
+        unsafe {
+            instance.get(ExitStatus::new)
+        }
+    }
+
+    // optional int64 pid = 1;
+
+    pub fn clear_pid(&mut self) {
+        self.pid = ::std::option::Option::None;
+    }
+
+    pub fn has_pid(&self) -> bool {
+        self.pid.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_pid(&mut self, v: i64) {
+        self.pid = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_pid(&self) -> i64 {
+        self.pid.unwrap_or(0)
+    }
+
+    fn get_pid_for_reflect(&self) -> &::std::option::Option<i64> {
+        &self.pid
+    }
+
+    fn mut_pid_for_reflect(&mut self) -> &mut ::std::option::Option<i64> {
+        &mut self.pid
+    }
+}
+
+impl ::protobuf::Message for ExitStatus {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_int64()?;
+                    self.pid = ::std::option::Option::Some(tmp);
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if let Some(v) = self.pid {
+            my_size += ::protobuf::rt::value_size(1, v, ::protobuf::wire_format::WireTypeVarint);
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
+        if let Some(v) = self.pid {
+            os.write_int64(1, v)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &::std::any::Any {
+        self as &::std::any::Any
+    }
+    fn as_any_mut(&mut self) -> &mut ::std::any::Any {
+        self as &mut ::std::any::Any
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        ::protobuf::MessageStatic::descriptor_static(None::<Self>)
+    }
+}
+
+impl ::protobuf::MessageStatic for ExitStatus {
+    fn new() -> ExitStatus {
+        ExitStatus::new()
+    }
+
+    fn descriptor_static(_: ::std::option::Option<ExitStatus>) -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeInt64>(
+                    "pid",
+                    ExitStatus::get_pid_for_reflect,
+                    ExitStatus::mut_pid_for_reflect,
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<ExitStatus>(
+                    "ExitStatus",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+}
+
+impl ::protobuf::Clear for ExitStatus {
+    fn clear(&mut self) {
+        self.clear_pid();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for ExitStatus {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for ExitStatus {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
+#[derive(PartialEq,Clone,Default)]
+pub struct ExitStatusOk {
+    // message fields
+    exit_code: ::std::option::Option<i32>,
+    oom_killed: ::std::option::Option<bool>,
+    // special fields
+    unknown_fields: ::protobuf::UnknownFields,
+    cached_size: ::protobuf::CachedSize,
+}
+
+// see codegen.rs for the explanation why impl Sync explicitly
+unsafe impl ::std::marker::Sync for ExitStatusOk {}
+
+impl ExitStatusOk {
+    pub fn new() -> ExitStatusOk {
+        ::std::default::Default::default()
+    }
+
+    pub fn default_instance() -> &'static ExitStatusOk {
+        static mut instance: ::protobuf::lazy::Lazy<ExitStatusOk> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ExitStatusOk,
+        };
+        unsafe {
+            instance.get(ExitStatusOk::new)
+        }
+    }
+
+    // optional int32 exit_code = 1;
+
+    pub fn clear_exit_code(&mut self) {
+        self.exit_code = ::std::option::Option::None;
+    }
+
+    pub fn has_exit_code(&self) -> bool {
+        self.exit_code.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_exit_code(&mut self, v: i32) {
+        self.exit_code = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_exit_code(&self) -> i32 {
+        self.exit_code.unwrap_or(0)
+    }
+
+    fn get_exit_code_for_reflect(&self) -> &::std::option::Option<i32> {
+        &self.exit_code
+    }
+
+    fn mut_exit_code_for_reflect(&mut self) -> &mut ::std::option::Option<i32> {
+        &mut self.exit_code
+    }
+
+    // optional bool oom_killed = 2;
+
+    pub fn clear_oom_killed(&mut self) {
+        self.oom_killed = ::std::option::Option::None;
+    }
+
+    pub fn has_oom_killed(&self) -> bool {
+        self.oom_killed.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_oom_killed(&mut self, v: bool) {
+        self.oom_killed = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_oom_killed(&self) -> bool {
+        self.oom_killed.unwrap_or(false)
+    }
+
+    fn get_oom_killed_for_reflect(&self) -> &::std::option::Option<bool> {
+        &self.oom_killed
+    }
+
+    fn mut_oom_killed_for_reflect(&mut self) -> &mut ::std::option::Option<bool> {
+        &mut self.oom_killed
+    }
+}
+
+impl ::protobuf::Message for ExitStatusOk {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn merge_from(&mut self, is: &mut ::protobuf::CodedInputStream) -> ::protobuf::ProtobufResult<()> {
+        while !is.eof()? {
+            let (field_number, wire_type) = is.read_tag_unpack()?;
+            match field_number {
+                1 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_int32()?;
+                    self.exit_code = ::std::option::Option::Some(tmp);
+                },
+                2 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_bool()?;
+                    self.oom_killed = ::std::option::Option::Some(tmp);
+                },
+                _ => {
+                    ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
+                },
+            };
+        }
+        ::std::result::Result::Ok(())
+    }
+
+    // Compute sizes of nested messages
+    #[allow(unused_variables)]
+    fn compute_size(&self) -> u32 {
+        let mut my_size = 0;
+        if let Some(v) = self.exit_code {
+            my_size += ::protobuf::rt::value_size(1, v, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if let Some(v) = self.oom_killed {
+            my_size += 2;
+        }
+        my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
+        self.cached_size.set(my_size);
+        my_size
+    }
+
+    fn write_to_with_cached_sizes(&self, os: &mut ::protobuf::CodedOutputStream) -> ::protobuf::ProtobufResult<()> {
+        if let Some(v) = self.exit_code {
+            os.write_int32(1, v)?;
+        }
+        if let Some(v) = self.oom_killed {
+            os.write_bool(2, v)?;
+        }
+        os.write_unknown_fields(self.get_unknown_fields())?;
+        ::std::result::Result::Ok(())
+    }
+
+    fn get_cached_size(&self) -> u32 {
+        self.cached_size.get()
+    }
+
+    fn get_unknown_fields(&self) -> &::protobuf::UnknownFields {
+        &self.unknown_fields
+    }
+
+    fn mut_unknown_fields(&mut self) -> &mut ::protobuf::UnknownFields {
+        &mut self.unknown_fields
+    }
+
+    fn as_any(&self) -> &::std::any::Any {
+        self as &::std::any::Any
+    }
+    fn as_any_mut(&mut self) -> &mut ::std::any::Any {
+        self as &mut ::std::any::Any
+    }
+    fn into_any(self: Box<Self>) -> ::std::boxed::Box<::std::any::Any> {
+        self
+    }
+
+    fn descriptor(&self) -> &'static ::protobuf::reflect::MessageDescriptor {
+        ::protobuf::MessageStatic::descriptor_static(None::<Self>)
+    }
+}
+
+impl ::protobuf::MessageStatic for ExitStatusOk {
+    fn new() -> ExitStatusOk {
+        ExitStatusOk::new()
+    }
+
+    fn descriptor_static(_: ::std::option::Option<ExitStatusOk>) -> &'static ::protobuf::reflect::MessageDescriptor {
+        static mut descriptor: ::protobuf::lazy::Lazy<::protobuf::reflect::MessageDescriptor> = ::protobuf::lazy::Lazy {
+            lock: ::protobuf::lazy::ONCE_INIT,
+            ptr: 0 as *const ::protobuf::reflect::MessageDescriptor,
+        };
+        unsafe {
+            descriptor.get(|| {
+                let mut fields = ::std::vec::Vec::new();
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeInt32>(
+                    "exit_code",
+                    ExitStatusOk::get_exit_code_for_reflect,
+                    ExitStatusOk::mut_exit_code_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeBool>(
+                    "oom_killed",
+                    ExitStatusOk::get_oom_killed_for_reflect,
+                    ExitStatusOk::mut_oom_killed_for_reflect,
+                ));
+                ::protobuf::reflect::MessageDescriptor::new::<ExitStatusOk>(
+                    "ExitStatusOk",
+                    fields,
+                    file_descriptor_proto()
+                )
+            })
+        }
+    }
+}
+
+impl ::protobuf::Clear for ExitStatusOk {
+    fn clear(&mut self) {
+        self.clear_exit_code();
+        self.clear_oom_killed();
+        self.unknown_fields.clear();
+    }
+}
+
+impl ::std::fmt::Debug for ExitStatusOk {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        ::protobuf::text_format::fmt(self, f)
+    }
+}
+
+impl ::protobuf::reflect::ProtobufValue for ExitStatusOk {
+    fn as_ref(&self) -> ::protobuf::reflect::ProtobufValueRef {
+        ::protobuf::reflect::ProtobufValueRef::Message(self)
+    }
+}
+
 #[derive(PartialEq,Clone,Default)]
 pub struct Register {
     // message fields
@@ -389,6 +782,14 @@ pub struct Spawn {
     svc_group: ::protobuf::SingularField<::std::string::String>,
     svc_password: ::protobuf::SingularField<::std::string::String>,
     pub env: ::std::collections::HashMap<::std::string::String, ::std::string::String>,
+    cpu_limit: ::std::option::Option<u32>,
+    memory_limit: ::std::option::Option<u64>,
+    pids_limit: ::std::option::Option<u64>,
+    json_log: ::std::option::Option<bool>,
+    member_id: ::protobuf::SingularField<::std::string::String>,
+    log_file: ::protobuf::SingularField<::std::string::String>,
+    log_max_size: ::std::option::Option<u64>,
+    log_max_age: ::std::option::Option<u64>,
     // special fields
     unknown_fields: ::protobuf::UnknownFields,
     cached_size: ::protobuf::CachedSize,
@@ -664,6 +1065,256 @@ impl Spawn {
     fn mut_env_for_reflect(&mut self) -> &mut ::std::collections::HashMap<::std::string::String, ::std::string::String> {
         &mut self.env
     }
+
+    // optional uint32 cpu_limit = 7;
+
+    pub fn clear_cpu_limit(&mut self) {
+        self.cpu_limit = ::std::option::Option::None;
+    }
+
+    pub fn has_cpu_limit(&self) -> bool {
+        self.cpu_limit.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_cpu_limit(&mut self, v: u32) {
+        self.cpu_limit = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_cpu_limit(&self) -> u32 {
+        self.cpu_limit.unwrap_or(0)
+    }
+
+    fn get_cpu_limit_for_reflect(&self) -> &::std::option::Option<u32> {
+        &self.cpu_limit
+    }
+
+    fn mut_cpu_limit_for_reflect(&mut self) -> &mut ::std::option::Option<u32> {
+        &mut self.cpu_limit
+    }
+
+    // optional uint64 memory_limit = 8;
+
+    pub fn clear_memory_limit(&mut self) {
+        self.memory_limit = ::std::option::Option::None;
+    }
+
+    pub fn has_memory_limit(&self) -> bool {
+        self.memory_limit.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_memory_limit(&mut self, v: u64) {
+        self.memory_limit = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_memory_limit(&self) -> u64 {
+        self.memory_limit.unwrap_or(0)
+    }
+
+    fn get_memory_limit_for_reflect(&self) -> &::std::option::Option<u64> {
+        &self.memory_limit
+    }
+
+    fn mut_memory_limit_for_reflect(&mut self) -> &mut ::std::option::Option<u64> {
+        &mut self.memory_limit
+    }
+
+    // optional uint64 pids_limit = 9;
+
+    pub fn clear_pids_limit(&mut self) {
+        self.pids_limit = ::std::option::Option::None;
+    }
+
+    pub fn has_pids_limit(&self) -> bool {
+        self.pids_limit.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_pids_limit(&mut self, v: u64) {
+        self.pids_limit = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_pids_limit(&self) -> u64 {
+        self.pids_limit.unwrap_or(0)
+    }
+
+    fn get_pids_limit_for_reflect(&self) -> &::std::option::Option<u64> {
+        &self.pids_limit
+    }
+
+    fn mut_pids_limit_for_reflect(&mut self) -> &mut ::std::option::Option<u64> {
+        &mut self.pids_limit
+    }
+
+    // optional bool json_log = 10;
+
+    pub fn clear_json_log(&mut self) {
+        self.json_log = ::std::option::Option::None;
+    }
+
+    pub fn has_json_log(&self) -> bool {
+        self.json_log.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_json_log(&mut self, v: bool) {
+        self.json_log = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_json_log(&self) -> bool {
+        self.json_log.unwrap_or(false)
+    }
+
+    fn get_json_log_for_reflect(&self) -> &::std::option::Option<bool> {
+        &self.json_log
+    }
+
+    fn mut_json_log_for_reflect(&mut self) -> &mut ::std::option::Option<bool> {
+        &mut self.json_log
+    }
+
+    // optional string member_id = 11;
+
+    pub fn clear_member_id(&mut self) {
+        self.member_id.clear();
+    }
+
+    pub fn has_member_id(&self) -> bool {
+        self.member_id.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_member_id(&mut self, v: ::std::string::String) {
+        self.member_id = ::protobuf::SingularField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_member_id(&mut self) -> &mut ::std::string::String {
+        if self.member_id.is_none() {
+            self.member_id.set_default();
+        }
+        self.member_id.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_member_id(&mut self) -> ::std::string::String {
+        self.member_id.take().unwrap_or_else(|| ::std::string::String::new())
+    }
+
+    pub fn get_member_id(&self) -> &str {
+        match self.member_id.as_ref() {
+            Some(v) => &v,
+            None => "",
+        }
+    }
+
+    fn get_member_id_for_reflect(&self) -> &::protobuf::SingularField<::std::string::String> {
+        &self.member_id
+    }
+
+    fn mut_member_id_for_reflect(&mut self) -> &mut ::protobuf::SingularField<::std::string::String> {
+        &mut self.member_id
+    }
+
+    // optional string log_file = 12;
+
+    pub fn clear_log_file(&mut self) {
+        self.log_file.clear();
+    }
+
+    pub fn has_log_file(&self) -> bool {
+        self.log_file.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_log_file(&mut self, v: ::std::string::String) {
+        self.log_file = ::protobuf::SingularField::some(v);
+    }
+
+    // Mutable pointer to the field.
+    // If field is not initialized, it is initialized with default value first.
+    pub fn mut_log_file(&mut self) -> &mut ::std::string::String {
+        if self.log_file.is_none() {
+            self.log_file.set_default();
+        }
+        self.log_file.as_mut().unwrap()
+    }
+
+    // Take field
+    pub fn take_log_file(&mut self) -> ::std::string::String {
+        self.log_file.take().unwrap_or_else(|| ::std::string::String::new())
+    }
+
+    pub fn get_log_file(&self) -> &str {
+        match self.log_file.as_ref() {
+            Some(v) => &v,
+            None => "",
+        }
+    }
+
+    fn get_log_file_for_reflect(&self) -> &::protobuf::SingularField<::std::string::String> {
+        &self.log_file
+    }
+
+    fn mut_log_file_for_reflect(&mut self) -> &mut ::protobuf::SingularField<::std::string::String> {
+        &mut self.log_file
+    }
+
+    // optional uint64 log_max_size = 13;
+
+    pub fn clear_log_max_size(&mut self) {
+        self.log_max_size = ::std::option::Option::None;
+    }
+
+    pub fn has_log_max_size(&self) -> bool {
+        self.log_max_size.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_log_max_size(&mut self, v: u64) {
+        self.log_max_size = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_log_max_size(&self) -> u64 {
+        self.log_max_size.unwrap_or(0)
+    }
+
+    fn get_log_max_size_for_reflect(&self) -> &::std::option::Option<u64> {
+        &self.log_max_size
+    }
+
+    fn mut_log_max_size_for_reflect(&mut self) -> &mut ::std::option::Option<u64> {
+        &mut self.log_max_size
+    }
+
+    // optional uint64 log_max_age = 14;
+
+    pub fn clear_log_max_age(&mut self) {
+        self.log_max_age = ::std::option::Option::None;
+    }
+
+    pub fn has_log_max_age(&self) -> bool {
+        self.log_max_age.is_some()
+    }
+
+    // Param is passed by value, moved
+    pub fn set_log_max_age(&mut self, v: u64) {
+        self.log_max_age = ::std::option::Option::Some(v);
+    }
+
+    pub fn get_log_max_age(&self) -> u64 {
+        self.log_max_age.unwrap_or(0)
+    }
+
+    fn get_log_max_age_for_reflect(&self) -> &::std::option::Option<u64> {
+        &self.log_max_age
+    }
+
+    fn mut_log_max_age_for_reflect(&mut self) -> &mut ::std::option::Option<u64> {
+        &mut self.log_max_age
+    }
 }
 
 impl ::protobuf::Message for Spawn {
@@ -693,6 +1344,54 @@ impl ::protobuf::Message for Spawn {
                 6 => {
                     ::protobuf::rt::read_map_into::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(wire_type, is, &mut self.env)?;
                 },
+                7 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint32()?;
+                    self.cpu_limit = ::std::option::Option::Some(tmp);
+                },
+                8 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.memory_limit = ::std::option::Option::Some(tmp);
+                },
+                9 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.pids_limit = ::std::option::Option::Some(tmp);
+                },
+                10 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_bool()?;
+                    self.json_log = ::std::option::Option::Some(tmp);
+                },
+                11 => {
+                    ::protobuf::rt::read_singular_string_into(wire_type, is, &mut self.member_id)?;
+                },
+                12 => {
+                    ::protobuf::rt::read_singular_string_into(wire_type, is, &mut self.log_file)?;
+                },
+                13 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.log_max_size = ::std::option::Option::Some(tmp);
+                },
+                14 => {
+                    if wire_type != ::protobuf::wire_format::WireTypeVarint {
+                        return ::std::result::Result::Err(::protobuf::rt::unexpected_wire_type(wire_type));
+                    }
+                    let tmp = is.read_uint64()?;
+                    self.log_max_age = ::std::option::Option::Some(tmp);
+                },
                 _ => {
                     ::protobuf::rt::read_unknown_or_skip_group(field_number, wire_type, is, self.mut_unknown_fields())?;
                 },
@@ -721,6 +1420,30 @@ impl ::protobuf::Message for Spawn {
             my_size += ::protobuf::rt::string_size(5, &v);
         }
         my_size += ::protobuf::rt::compute_map_size::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(6, &self.env);
+        if let Some(v) = self.cpu_limit {
+            my_size += ::protobuf::rt::value_size(7, v, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if let Some(v) = self.memory_limit {
+            my_size += ::protobuf::rt::value_size(8, v, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if let Some(v) = self.pids_limit {
+            my_size += ::protobuf::rt::value_size(9, v, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if let Some(v) = self.json_log {
+            my_size += 2;
+        }
+        if let Some(ref v) = self.member_id.as_ref() {
+            my_size += ::protobuf::rt::string_size(11, &v);
+        }
+        if let Some(ref v) = self.log_file.as_ref() {
+            my_size += ::protobuf::rt::string_size(12, &v);
+        }
+        if let Some(v) = self.log_max_size {
+            my_size += ::protobuf::rt::value_size(13, v, ::protobuf::wire_format::WireTypeVarint);
+        }
+        if let Some(v) = self.log_max_age {
+            my_size += ::protobuf::rt::value_size(14, v, ::protobuf::wire_format::WireTypeVarint);
+        }
         my_size += ::protobuf::rt::unknown_fields_size(self.get_unknown_fields());
         self.cached_size.set(my_size);
         my_size
@@ -743,6 +1466,30 @@ impl ::protobuf::Message for Spawn {
             os.write_string(5, &v)?;
         }
         ::protobuf::rt::write_map_with_cached_sizes::<::protobuf::types::ProtobufTypeString, ::protobuf::types::ProtobufTypeString>(6, &self.env, os)?;
+        if let Some(v) = self.cpu_limit {
+            os.write_uint32(7, v)?;
+        }
+        if let Some(v) = self.memory_limit {
+            os.write_uint64(8, v)?;
+        }
+        if let Some(v) = self.pids_limit {
+            os.write_uint64(9, v)?;
+        }
+        if let Some(v) = self.json_log {
+            os.write_bool(10, v)?;
+        }
+        if let Some(ref v) = self.member_id.as_ref() {
+            os.write_string(11, &v)?;
+        }
+        if let Some(ref v) = self.log_file.as_ref() {
+            os.write_string(12, &v)?;
+        }
+        if let Some(v) = self.log_max_size {
+            os.write_uint64(13, v)?;
+        }
+        if let Some(v) = self.log_max_age {
+            os.write_uint64(14, v)?;
+        }
         os.write_unknown_fields(self.get_unknown_fields())?;
         ::std::result::Result::Ok(())
     }
@@ -817,6 +1564,46 @@ impl ::protobuf::MessageStatic for Spawn {
                     Spawn::get_env_for_reflect,
                     Spawn::mut_env_for_reflect,
                 ));
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint32>(
+                    "cpu_limit",
+                    Spawn::get_cpu_limit_for_reflect,
+                    Spawn::mut_cpu_limit_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                    "memory_limit",
+                    Spawn::get_memory_limit_for_reflect,
+                    Spawn::mut_memory_limit_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                    "pids_limit",
+                    Spawn::get_pids_limit_for_reflect,
+                    Spawn::mut_pids_limit_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeBool>(
+                    "json_log",
+                    Spawn::get_json_log_for_reflect,
+                    Spawn::mut_json_log_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_singular_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "member_id",
+                    Spawn::get_member_id_for_reflect,
+                    Spawn::mut_member_id_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_singular_field_accessor::<_, ::protobuf::types::ProtobufTypeString>(
+                    "log_file",
+                    Spawn::get_log_file_for_reflect,
+                    Spawn::mut_log_file_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                    "log_max_size",
+                    Spawn::get_log_max_size_for_reflect,
+                    Spawn::mut_log_max_size_for_reflect,
+                ));
+                fields.push(::protobuf::reflect::accessor::make_option_accessor::<_, ::protobuf::types::ProtobufTypeUint64>(
+                    "log_max_age",
+                    Spawn::get_log_max_age_for_reflect,
+                    Spawn::mut_log_max_age_for_reflect,
+                ));
                 ::protobuf::reflect::MessageDescriptor::new::<Spawn>(
                     "Spawn",
                     fields,
@@ -835,6 +1622,14 @@ impl ::protobuf::Clear for Spawn {
         self.clear_svc_group();
         self.clear_svc_password();
         self.clear_env();
+        self.clear_cpu_limit();
+        self.clear_memory_limit();
+        self.clear_pids_limit();
+        self.clear_json_log();
+        self.clear_member_id();
+        self.clear_log_file();
+        self.clear_log_max_size();
+        self.clear_log_max_age();
         self.unknown_fields.clear();
     }
 }
@@ -1470,78 +2265,136 @@ impl ::protobuf::reflect::ProtobufValue for ShutdownMethod {
 }
 
 static file_descriptor_proto_data: &'static [u8] = b"\
-    \n\x18protocols/launcher.proto\x12\x08launcher\"\x1e\n\x08Register\x12\
-    \x12\n\x04pipe\x18\x01\x20\x01(\tR\x04pipe\"\x1b\n\x07Restart\x12\x10\n\
-    \x03pid\x18\x01\x20\x01(\x03R\x03pid\"\xee\x01\n\x05Spawn\x12\x0e\n\x02i\
-    d\x18\x01\x20\x01(\tR\x02id\x12\x16\n\x06binary\x18\x02\x20\x01(\tR\x06b\
-    inary\x12\x19\n\x08svc_user\x18\x03\x20\x01(\tR\x07svcUser\x12\x1b\n\tsv\
-    c_group\x18\x04\x20\x01(\tR\x08svcGroup\x12!\n\x0csvc_password\x18\x05\
-    \x20\x01(\tR\x0bsvcPassword\x12*\n\x03env\x18\x06\x20\x03(\x0b2\x18.laun\
-    cher.Spawn.EnvEntryR\x03env\x1a6\n\x08EnvEntry\x12\x10\n\x03key\x18\x01\
-    \x20\x01(\tR\x03key\x12\x14\n\x05value\x18\x02\x20\x01(\tR\x05value:\x02\
-    8\x01\"\x1b\n\x07SpawnOk\x12\x10\n\x03pid\x18\x01\x20\x01(\x03R\x03pid\"\
-    \x1d\n\tTerminate\x12\x10\n\x03pid\x18\x01\x20\x01(\x03R\x03pid\"m\n\x0b\
-    TerminateOk\x12\x1b\n\texit_code\x18\x01\x20\x01(\x05R\x08exitCode\x12A\
-    \n\x0fshutdown_method\x18\x02\x20\x01(\x0e2\x18.launcher.ShutdownMethodR\
-    \x0eshutdownMethod*H\n\x0eShutdownMethod\x12\x11\n\rAlreadyExited\x10\0\
-    \x12\x17\n\x13GracefulTermination\x10\x01\x12\n\n\x06Killed\x10\x02J\xfc\
-    \x08\n\x06\x12\x04\0\0&\x01\n\x08\n\x01\x0c\x12\x03\0\0\x12\n\x08\n\x01\
-    \x02\x12\x03\x02\x08\x10\n\n\n\x02\x04\0\x12\x04\x04\0\x06\x01\n\n\n\x03\
-    \x04\0\x01\x12\x03\x04\x08\x10\n\x0b\n\x04\x04\0\x02\0\x12\x03\x05\x02\
-    \x1b\n\x0c\n\x05\x04\0\x02\0\x04\x12\x03\x05\x02\n\n\x0c\n\x05\x04\0\x02\
-    \0\x05\x12\x03\x05\x0b\x11\n\x0c\n\x05\x04\0\x02\0\x01\x12\x03\x05\x12\
-    \x16\n\x0c\n\x05\x04\0\x02\0\x03\x12\x03\x05\x19\x1a\n\n\n\x02\x04\x01\
-    \x12\x04\x08\0\n\x01\n\n\n\x03\x04\x01\x01\x12\x03\x08\x08\x0f\n\x0b\n\
-    \x04\x04\x01\x02\0\x12\x03\t\x02\x19\n\x0c\n\x05\x04\x01\x02\0\x04\x12\
-    \x03\t\x02\n\n\x0c\n\x05\x04\x01\x02\0\x05\x12\x03\t\x0b\x10\n\x0c\n\x05\
-    \x04\x01\x02\0\x01\x12\x03\t\x11\x14\n\x0c\n\x05\x04\x01\x02\0\x03\x12\
-    \x03\t\x17\x18\n\n\n\x02\x04\x02\x12\x04\x0c\0\x13\x01\n\n\n\x03\x04\x02\
-    \x01\x12\x03\x0c\x08\r\n\x0b\n\x04\x04\x02\x02\0\x12\x03\r\x02\x19\n\x0c\
-    \n\x05\x04\x02\x02\0\x04\x12\x03\r\x02\n\n\x0c\n\x05\x04\x02\x02\0\x05\
-    \x12\x03\r\x0b\x11\n\x0c\n\x05\x04\x02\x02\0\x01\x12\x03\r\x12\x14\n\x0c\
-    \n\x05\x04\x02\x02\0\x03\x12\x03\r\x17\x18\n\x0b\n\x04\x04\x02\x02\x01\
-    \x12\x03\x0e\x02\x1d\n\x0c\n\x05\x04\x02\x02\x01\x04\x12\x03\x0e\x02\n\n\
-    \x0c\n\x05\x04\x02\x02\x01\x05\x12\x03\x0e\x0b\x11\n\x0c\n\x05\x04\x02\
-    \x02\x01\x01\x12\x03\x0e\x12\x18\n\x0c\n\x05\x04\x02\x02\x01\x03\x12\x03\
-    \x0e\x1b\x1c\n\x0b\n\x04\x04\x02\x02\x02\x12\x03\x0f\x02\x1f\n\x0c\n\x05\
-    \x04\x02\x02\x02\x04\x12\x03\x0f\x02\n\n\x0c\n\x05\x04\x02\x02\x02\x05\
-    \x12\x03\x0f\x0b\x11\n\x0c\n\x05\x04\x02\x02\x02\x01\x12\x03\x0f\x12\x1a\
-    \n\x0c\n\x05\x04\x02\x02\x02\x03\x12\x03\x0f\x1d\x1e\n\x0b\n\x04\x04\x02\
-    \x02\x03\x12\x03\x10\x02\x20\n\x0c\n\x05\x04\x02\x02\x03\x04\x12\x03\x10\
-    \x02\n\n\x0c\n\x05\x04\x02\x02\x03\x05\x12\x03\x10\x0b\x11\n\x0c\n\x05\
-    \x04\x02\x02\x03\x01\x12\x03\x10\x12\x1b\n\x0c\n\x05\x04\x02\x02\x03\x03\
-    \x12\x03\x10\x1e\x1f\n\x0b\n\x04\x04\x02\x02\x04\x12\x03\x11\x02#\n\x0c\
-    \n\x05\x04\x02\x02\x04\x04\x12\x03\x11\x02\n\n\x0c\n\x05\x04\x02\x02\x04\
-    \x05\x12\x03\x11\x0b\x11\n\x0c\n\x05\x04\x02\x02\x04\x01\x12\x03\x11\x12\
-    \x1e\n\x0c\n\x05\x04\x02\x02\x04\x03\x12\x03\x11!\"\n\x0b\n\x04\x04\x02\
-    \x02\x05\x12\x03\x12\x02\x1e\n\r\n\x05\x04\x02\x02\x05\x04\x12\x04\x12\
-    \x02\x11#\n\x0c\n\x05\x04\x02\x02\x05\x06\x12\x03\x12\x02\x15\n\x0c\n\
-    \x05\x04\x02\x02\x05\x01\x12\x03\x12\x16\x19\n\x0c\n\x05\x04\x02\x02\x05\
-    \x03\x12\x03\x12\x1c\x1d\n\n\n\x02\x04\x03\x12\x04\x15\0\x17\x01\n\n\n\
-    \x03\x04\x03\x01\x12\x03\x15\x08\x0f\n\x0b\n\x04\x04\x03\x02\0\x12\x03\
-    \x16\x02\x19\n\x0c\n\x05\x04\x03\x02\0\x04\x12\x03\x16\x02\n\n\x0c\n\x05\
-    \x04\x03\x02\0\x05\x12\x03\x16\x0b\x10\n\x0c\n\x05\x04\x03\x02\0\x01\x12\
-    \x03\x16\x11\x14\n\x0c\n\x05\x04\x03\x02\0\x03\x12\x03\x16\x17\x18\n\n\n\
-    \x02\x04\x04\x12\x04\x19\0\x1b\x01\n\n\n\x03\x04\x04\x01\x12\x03\x19\x08\
-    \x11\n\x0b\n\x04\x04\x04\x02\0\x12\x03\x1a\x02\x19\n\x0c\n\x05\x04\x04\
-    \x02\0\x04\x12\x03\x1a\x02\n\n\x0c\n\x05\x04\x04\x02\0\x05\x12\x03\x1a\
-    \x0b\x10\n\x0c\n\x05\x04\x04\x02\0\x01\x12\x03\x1a\x11\x14\n\x0c\n\x05\
-    \x04\x04\x02\0\x03\x12\x03\x1a\x17\x18\n\n\n\x02\x04\x05\x12\x04\x1d\0\
-    \x20\x01\n\n\n\x03\x04\x05\x01\x12\x03\x1d\x08\x13\n\x0b\n\x04\x04\x05\
-    \x02\0\x12\x03\x1e\x02\x1f\n\x0c\n\x05\x04\x05\x02\0\x04\x12\x03\x1e\x02\
-    \n\n\x0c\n\x05\x04\x05\x02\0\x05\x12\x03\x1e\x0b\x10\n\x0c\n\x05\x04\x05\
-    \x02\0\x01\x12\x03\x1e\x11\x1a\n\x0c\n\x05\x04\x05\x02\0\x03\x12\x03\x1e\
-    \x1d\x1e\n\x0b\n\x04\x04\x05\x02\x01\x12\x03\x1f\x02.\n\x0c\n\x05\x04\
-    \x05\x02\x01\x04\x12\x03\x1f\x02\n\n\x0c\n\x05\x04\x05\x02\x01\x06\x12\
-    \x03\x1f\x0b\x19\n\x0c\n\x05\x04\x05\x02\x01\x01\x12\x03\x1f\x1a)\n\x0c\
-    \n\x05\x04\x05\x02\x01\x03\x12\x03\x1f,-\n\n\n\x02\x05\0\x12\x04\"\0&\
-    \x01\n\n\n\x03\x05\0\x01\x12\x03\"\x05\x13\n\x0b\n\x04\x05\0\x02\0\x12\
-    \x03#\x02\x14\n\x0c\n\x05\x05\0\x02\0\x01\x12\x03#\x02\x0f\n\x0c\n\x05\
-    \x05\0\x02\0\x02\x12\x03#\x12\x13\n\x0b\n\x04\x05\0\x02\x01\x12\x03$\x02\
-    \x1a\n\x0c\n\x05\x05\0\x02\x01\x01\x12\x03$\x02\x15\n\x0c\n\x05\x05\0\
-    \x02\x01\x02\x12\x03$\x18\x19\n\x0b\n\x04\x05\0\x02\x02\x12\x03%\x02\r\n\
-    \x0c\n\x05\x05\0\x02\x02\x01\x12\x03%\x02\x08\n\x0c\n\x05\x05\0\x02\x02\
-    \x02\x12\x03%\x0b\x0c\
+    \n\x18protocols/launcher.proto\x12\x08launcher\"\x1e\n\nExitStatus\x12\
+    \x10\n\x03pid\x18\x01\x20\x01(\x03R\x03pid\"J\n\x0cExitStatusOk\x12\x1b\
+    \n\texit_code\x18\x01\x20\x01(\x05R\x08exitCode\x12\x1d\n\noom_killed\
+    \x18\x02\x20\x01(\x08R\toomKilled\"\x1e\n\x08Register\x12\x12\n\x04pipe\
+    \x18\x01\x20\x01(\tR\x04pipe\"\x1b\n\x07Restart\x12\x10\n\x03pid\x18\x01\
+    \x20\x01(\x03R\x03pid\"\xe2\x03\n\x05Spawn\x12\x0e\n\x02id\x18\x01\x20\
+    \x01(\tR\x02id\x12\x16\n\x06binary\x18\x02\x20\x01(\tR\x06binary\x12\x19\
+    \n\x08svc_user\x18\x03\x20\x01(\tR\x07svcUser\x12\x1b\n\tsvc_group\x18\
+    \x04\x20\x01(\tR\x08svcGroup\x12!\n\x0csvc_password\x18\x05\x20\x01(\tR\
+    \x0bsvcPassword\x12*\n\x03env\x18\x06\x20\x03(\x0b2\x18.launcher.Spawn.E\
+    nvEntryR\x03env\x12\x1b\n\tcpu_limit\x18\x07\x20\x01(\rR\x08cpuLimit\x12\
+    !\n\x0cmemory_limit\x18\x08\x20\x01(\x04R\x0bmemoryLimit\x12\x1d\n\npids\
+    _limit\x18\t\x20\x01(\x04R\tpidsLimit\x12\x19\n\x08json_log\x18\n\x20\
+    \x01(\x08R\x07jsonLog\x12\x1b\n\tmember_id\x18\x0b\x20\x01(\tR\x08member\
+    Id\x12\x19\n\x08log_file\x18\x0c\x20\x01(\tR\x07logFile\x12\x20\n\x0clog\
+    _max_size\x18\r\x20\x01(\x04R\nlogMaxSize\x12\x1e\n\x0blog_max_age\x18\
+    \x0e\x20\x01(\x04R\tlogMaxAge\x1a6\n\x08EnvEntry\x12\x10\n\x03key\x18\
+    \x01\x20\x01(\tR\x03key\x12\x14\n\x05value\x18\x02\x20\x01(\tR\x05value:\
+    \x028\x01\"\x1b\n\x07SpawnOk\x12\x10\n\x03pid\x18\x01\x20\x01(\x03R\x03p\
+    id\"\x1d\n\tTerminate\x12\x10\n\x03pid\x18\x01\x20\x01(\x03R\x03pid\"m\n\
+    \x0bTerminateOk\x12\x1b\n\texit_code\x18\x01\x20\x01(\x05R\x08exitCode\
+    \x12A\n\x0fshutdown_method\x18\x02\x20\x01(\x0e2\x18.launcher.ShutdownMe\
+    thodR\x0eshutdownMethod*H\n\x0eShutdownMethod\x12\x11\n\rAlreadyExited\
+    \x10\0\x12\x17\n\x13GracefulTermination\x10\x01\x12\n\n\x06Killed\x10\
+    \x02J\xb1\x13\n\x06\x12\x04\0\0?\x01\n\x08\n\x01\x0c\x12\x03\0\0\x12\n\
+    \x08\n\x01\x02\x12\x03\x02\0\x11\n\n\n\x02\x04\0\x12\x04\x04\0\x06\x01\n\
+    \n\n\x03\x04\0\x01\x12\x03\x04\x08\x12\n\x0b\n\x04\x04\0\x02\0\x12\x03\
+    \x05\x02\x19\n\x0c\n\x05\x04\0\x02\0\x04\x12\x03\x05\x02\n\n\x0c\n\x05\
+    \x04\0\x02\0\x05\x12\x03\x05\x0b\x10\n\x0c\n\x05\x04\0\x02\0\x01\x12\x03\
+    \x05\x11\x14\n\x0c\n\x05\x04\0\x02\0\x03\x12\x03\x05\x17\x18\n\n\n\x02\
+    \x04\x01\x12\x04\x08\0\r\x01\n\n\n\x03\x04\x01\x01\x12\x03\x08\x08\x14\n\
+    @\n\x04\x04\x01\x02\0\x12\x03\n\x02\x1f\x1a3\x20Not\x20set\x20if\x20the\
+    \x20process\x20was\x20terminated\x20by\x20a\x20signal\n\n\x0c\n\x05\x04\
+    \x01\x02\0\x04\x12\x03\n\x02\n\n\x0c\n\x05\x04\x01\x02\0\x05\x12\x03\n\
+    \x0b\x10\n\x0c\n\x05\x04\x01\x02\0\x01\x12\x03\n\x11\x1a\n\x0c\n\x05\x04\
+    \x01\x02\0\x03\x12\x03\n\x1d\x1e\nK\n\x04\x04\x01\x02\x01\x12\x03\x0c\
+    \x02\x1f\x1a>\x20Set\x20if\x20the\x20process\x20was\x20killed\x20for\x20\
+    exceeding\x20its\x20memory\x20limit\n\n\x0c\n\x05\x04\x01\x02\x01\x04\
+    \x12\x03\x0c\x02\n\n\x0c\n\x05\x04\x01\x02\x01\x05\x12\x03\x0c\x0b\x0f\n\
+    \x0c\n\x05\x04\x01\x02\x01\x01\x12\x03\x0c\x10\x1a\n\x0c\n\x05\x04\x01\
+    \x02\x01\x03\x12\x03\x0c\x1d\x1e\n\n\n\x02\x04\x02\x12\x04\x0f\0\x11\x01\
+    \n\n\n\x03\x04\x02\x01\x12\x03\x0f\x08\x10\n\x0b\n\x04\x04\x02\x02\0\x12\
+    \x03\x10\x02\x1b\n\x0c\n\x05\x04\x02\x02\0\x04\x12\x03\x10\x02\n\n\x0c\n\
+    \x05\x04\x02\x02\0\x05\x12\x03\x10\x0b\x11\n\x0c\n\x05\x04\x02\x02\0\x01\
+    \x12\x03\x10\x12\x16\n\x0c\n\x05\x04\x02\x02\0\x03\x12\x03\x10\x19\x1a\n\
+    \n\n\x02\x04\x03\x12\x04\x13\0\x15\x01\n\n\n\x03\x04\x03\x01\x12\x03\x13\
+    \x08\x0f\n\x0b\n\x04\x04\x03\x02\0\x12\x03\x14\x02\x19\n\x0c\n\x05\x04\
+    \x03\x02\0\x04\x12\x03\x14\x02\n\n\x0c\n\x05\x04\x03\x02\0\x05\x12\x03\
+    \x14\x0b\x10\n\x0c\n\x05\x04\x03\x02\0\x01\x12\x03\x14\x11\x14\n\x0c\n\
+    \x05\x04\x03\x02\0\x03\x12\x03\x14\x17\x18\n\n\n\x02\x04\x04\x12\x04\x17\
+    \0,\x01\n\n\n\x03\x04\x04\x01\x12\x03\x17\x08\r\n\x0b\n\x04\x04\x04\x02\
+    \0\x12\x03\x18\x02\x19\n\x0c\n\x05\x04\x04\x02\0\x04\x12\x03\x18\x02\n\n\
+    \x0c\n\x05\x04\x04\x02\0\x05\x12\x03\x18\x0b\x11\n\x0c\n\x05\x04\x04\x02\
+    \0\x01\x12\x03\x18\x12\x14\n\x0c\n\x05\x04\x04\x02\0\x03\x12\x03\x18\x17\
+    \x18\n\x0b\n\x04\x04\x04\x02\x01\x12\x03\x19\x02\x1d\n\x0c\n\x05\x04\x04\
+    \x02\x01\x04\x12\x03\x19\x02\n\n\x0c\n\x05\x04\x04\x02\x01\x05\x12\x03\
+    \x19\x0b\x11\n\x0c\n\x05\x04\x04\x02\x01\x01\x12\x03\x19\x12\x18\n\x0c\n\
+    \x05\x04\x04\x02\x01\x03\x12\x03\x19\x1b\x1c\n\x0b\n\x04\x04\x04\x02\x02\
+    \x12\x03\x1a\x02\x1f\n\x0c\n\x05\x04\x04\x02\x02\x04\x12\x03\x1a\x02\n\n\
+    \x0c\n\x05\x04\x04\x02\x02\x05\x12\x03\x1a\x0b\x11\n\x0c\n\x05\x04\x04\
+    \x02\x02\x01\x12\x03\x1a\x12\x1a\n\x0c\n\x05\x04\x04\x02\x02\x03\x12\x03\
+    \x1a\x1d\x1e\n\x0b\n\x04\x04\x04\x02\x03\x12\x03\x1b\x02\x20\n\x0c\n\x05\
+    \x04\x04\x02\x03\x04\x12\x03\x1b\x02\n\n\x0c\n\x05\x04\x04\x02\x03\x05\
+    \x12\x03\x1b\x0b\x11\n\x0c\n\x05\x04\x04\x02\x03\x01\x12\x03\x1b\x12\x1b\
+    \n\x0c\n\x05\x04\x04\x02\x03\x03\x12\x03\x1b\x1e\x1f\n\x0b\n\x04\x04\x04\
+    \x02\x04\x12\x03\x1c\x02#\n\x0c\n\x05\x04\x04\x02\x04\x04\x12\x03\x1c\
+    \x02\n\n\x0c\n\x05\x04\x04\x02\x04\x05\x12\x03\x1c\x0b\x11\n\x0c\n\x05\
+    \x04\x04\x02\x04\x01\x12\x03\x1c\x12\x1e\n\x0c\n\x05\x04\x04\x02\x04\x03\
+    \x12\x03\x1c!\"\n\x0b\n\x04\x04\x04\x02\x05\x12\x03\x1d\x02\x1e\n\x0c\n\
+    \x05\x04\x04\x02\x05\x06\x12\x03\x1d\x02\x15\n\x0c\n\x05\x04\x04\x02\x05\
+    \x01\x12\x03\x1d\x16\x19\n\x0c\n\x05\x04\x04\x02\x05\x03\x12\x03\x1d\x1c\
+    \x1d\n\x9a\x01\n\x04\x04\x04\x02\x06\x12\x03\x20\x02\x20\x1a\x8c\x01\x20\
+    Resource\x20limits\x20applied\x20to\x20the\x20process,\x20if\x20supporte\
+    d\x20by\x20the\x20host.\x20The\x20CPU\x20limit\x20is\x20in\n\x20thousand\
+    ths\x20of\x20a\x20CPU\x20and\x20the\x20memory\x20limit\x20in\x20bytes.\n\
+    \n\x0c\n\x05\x04\x04\x02\x06\x04\x12\x03\x20\x02\n\n\x0c\n\x05\x04\x04\
+    \x02\x06\x05\x12\x03\x20\x0b\x11\n\x0c\n\x05\x04\x04\x02\x06\x01\x12\x03\
+    \x20\x12\x1b\n\x0c\n\x05\x04\x04\x02\x06\x03\x12\x03\x20\x1e\x1f\n\x0b\n\
+    \x04\x04\x04\x02\x07\x12\x03!\x02#\n\x0c\n\x05\x04\x04\x02\x07\x04\x12\
+    \x03!\x02\n\n\x0c\n\x05\x04\x04\x02\x07\x05\x12\x03!\x0b\x11\n\x0c\n\x05\
+    \x04\x04\x02\x07\x01\x12\x03!\x12\x1e\n\x0c\n\x05\x04\x04\x02\x07\x03\
+    \x12\x03!!\"\n\x0b\n\x04\x04\x04\x02\x08\x12\x03\"\x02!\n\x0c\n\x05\x04\
+    \x04\x02\x08\x04\x12\x03\"\x02\n\n\x0c\n\x05\x04\x04\x02\x08\x05\x12\x03\
+    \"\x0b\x11\n\x0c\n\x05\x04\x04\x02\x08\x01\x12\x03\"\x12\x1c\n\x0c\n\x05\
+    \x04\x04\x02\x08\x03\x12\x03\"\x1f\x20\n\x93\x01\n\x04\x04\x04\x02\t\x12\
+    \x03%\x02\x1e\x1a\x85\x01\x20Write\x20the\x20process's\x20output\x20to\
+    \x20stdout\x20as\x20JSON\x20records\x20instead\x20of\x20prefixed\x20line\
+    s\x20of\x20text.\x20The\n\x20member\x20id\x20is\x20included\x20in\x20eve\
+    ry\x20record.\n\n\x0c\n\x05\x04\x04\x02\t\x04\x12\x03%\x02\n\n\x0c\n\x05\
+    \x04\x04\x02\t\x05\x12\x03%\x0b\x0f\n\x0c\n\x05\x04\x04\x02\t\x01\x12\
+    \x03%\x10\x18\n\x0c\n\x05\x04\x04\x02\t\x03\x12\x03%\x1b\x1d\n\x0b\n\x04\
+    \x04\x04\x02\n\x12\x03&\x02!\n\x0c\n\x05\x04\x04\x02\n\x04\x12\x03&\x02\
+    \n\n\x0c\n\x05\x04\x04\x02\n\x05\x12\x03&\x0b\x11\n\x0c\n\x05\x04\x04\
+    \x02\n\x01\x12\x03&\x12\x1b\n\x0c\n\x05\x04\x04\x02\n\x03\x12\x03&\x1e\
+    \x20B\x99\x01\n\x04\x04\x04\x02\x0b\x12\x03)\x02\x20\x1a\x8b\x01\x20Also\
+    \x20append\x20the\x20JSON\x20records\x20to\x20this\x20file,\x20which\x20\
+    is\x20rotated\x20once\x20it\x20is\x20larger\x20than\n\x20log_max_size\
+    \x20bytes\x20or\x20older\x20than\x20log_max_age\x20seconds.\nB\x0c\n\x05\
+    \x04\x04\x02\x0b\x04\x12\x03)\x02\nB\x0c\n\x05\x04\x04\x02\x0b\x05\x12\
+    \x03)\x0b\x11B\x0c\n\x05\x04\x04\x02\x0b\x01\x12\x03)\x12\x1aB\x0c\n\x05\
+    \x04\x04\x02\x0b\x03\x12\x03)\x1d\x1fB\x0b\n\x04\x04\x04\x02\x0c\x12\x03\
+    *\x02$B\x0c\n\x05\x04\x04\x02\x0c\x04\x12\x03*\x02\nB\x0c\n\x05\x04\x04\
+    \x02\x0c\x05\x12\x03*\x0b\x11B\x0c\n\x05\x04\x04\x02\x0c\x01\x12\x03*\
+    \x12\x1eB\x0c\n\x05\x04\x04\x02\x0c\x03\x12\x03*!#B\x0b\n\x04\x04\x04\
+    \x02\r\x12\x03+\x02#B\x0c\n\x05\x04\x04\x02\r\x04\x12\x03+\x02\nB\x0c\n\
+    \x05\x04\x04\x02\r\x05\x12\x03+\x0b\x11B\x0c\n\x05\x04\x04\x02\r\x01\x12\
+    \x03+\x12\x1dB\x0c\n\x05\x04\x04\x02\r\x03\x12\x03+\x20\"\n\n\n\x02\x04\
+    \x05\x12\x04.\00\x01\n\n\n\x03\x04\x05\x01\x12\x03.\x08\x0f\n\x0b\n\x04\
+    \x04\x05\x02\0\x12\x03/\x02\x19\n\x0c\n\x05\x04\x05\x02\0\x04\x12\x03/\
+    \x02\n\n\x0c\n\x05\x04\x05\x02\0\x05\x12\x03/\x0b\x10\n\x0c\n\x05\x04\
+    \x05\x02\0\x01\x12\x03/\x11\x14\n\x0c\n\x05\x04\x05\x02\0\x03\x12\x03/\
+    \x17\x18\n\n\n\x02\x04\x06\x12\x042\04\x01\n\n\n\x03\x04\x06\x01\x12\x03\
+    2\x08\x11\n\x0b\n\x04\x04\x06\x02\0\x12\x033\x02\x19\n\x0c\n\x05\x04\x06\
+    \x02\0\x04\x12\x033\x02\n\n\x0c\n\x05\x04\x06\x02\0\x05\x12\x033\x0b\x10\
+    \n\x0c\n\x05\x04\x06\x02\0\x01\x12\x033\x11\x14\n\x0c\n\x05\x04\x06\x02\
+    \0\x03\x12\x033\x17\x18\n\n\n\x02\x04\x07\x12\x046\09\x01\n\n\n\x03\x04\
+    \x07\x01\x12\x036\x08\x13\n\x0b\n\x04\x04\x07\x02\0\x12\x037\x02\x1f\n\
+    \x0c\n\x05\x04\x07\x02\0\x04\x12\x037\x02\n\n\x0c\n\x05\x04\x07\x02\0\
+    \x05\x12\x037\x0b\x10\n\x0c\n\x05\x04\x07\x02\0\x01\x12\x037\x11\x1a\n\
+    \x0c\n\x05\x04\x07\x02\0\x03\x12\x037\x1d\x1e\n\x0b\n\x04\x04\x07\x02\
+    \x01\x12\x038\x02.\n\x0c\n\x05\x04\x07\x02\x01\x04\x12\x038\x02\n\n\x0c\
+    \n\x05\x04\x07\x02\x01\x06\x12\x038\x0b\x19\n\x0c\n\x05\x04\x07\x02\x01\
+    \x01\x12\x038\x1a)\n\x0c\n\x05\x04\x07\x02\x01\x03\x12\x038,-\n\n\n\x02\
+    \x05\0\x12\x04;\0?\x01\n\n\n\x03\x05\0\x01\x12\x03;\x05\x13\n\x0b\n\x04\
+    \x05\0\x02\0\x12\x03<\x02\x14\n\x0c\n\x05\x05\0\x02\0\x01\x12\x03<\x02\
+    \x0f\n\x0c\n\x05\x05\0\x02\0\x02\x12\x03<\x12\x13\n\x0b\n\x04\x05\0\x02\
+    \x01\x12\x03=\x02\x1a\n\x0c\n\x05\x05\0\x02\x01\x01\x12\x03=\x02\x15\n\
+    \x0c\n\x05\x05\0\x02\x01\x02\x12\x03=\x18\x19\n\x0b\n\x04\x05\0\x02\x02\
+    \x12\x03>\x02\r\n\x0c\n\x05\x05\0\x02\x02\x01\x12\x03>\x02\x08\n\x0c\n\
+    \x05\x05\0\x02\x02\x02\x12\x03>\x0b\x0c\
 ";
 
 static mut file_descriptor_proto_lazy: ::protobuf::lazy::Lazy<::protobuf::descriptor::FileDescriptorProto> = ::protobuf::lazy::Lazy {
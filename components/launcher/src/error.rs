@@ -15,6 +15,7 @@
 use std::error;
 use std::fmt;
 use std::io;
+use std::path::PathBuf;
 use std::result;
 
 use ipc_channel;
@@ -26,6 +27,8 @@ use {SUP_CMD, SUP_PACKAGE_IDENT};
 #[derive(Debug)]
 pub enum Error {
     AcceptConn,
+    BadResourceLimit(String),
+    Cgroup(PathBuf, io::Error),
     Connect(io::Error),
     Deserialize(protobuf::ProtobufError),
     ExecWait(io::Error),
@@ -47,6 +50,10 @@ impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let msg = match *self {
             Error::AcceptConn => format!("Unable to accept connection from Supervisor"),
+            Error::BadResourceLimit(ref limit) => format!("Invalid resource limit, {}", limit),
+            Error::Cgroup(ref path, ref e) => {
+                format!("Unable to configure cgroup, {}, {}", path.display(), e)
+            }
             Error::Connect(ref e) => {
                 format!("Unable to connect to Supervisor's comm channel, {}", e)
             }
@@ -77,6 +84,8 @@ impl error::Error for Error {
     fn description(&self) -> &str {
         match *self {
             Error::AcceptConn => "Unable to accept connection from Supervisor",
+            Error::BadResourceLimit(_) => "Invalid resource limit",
+            Error::Cgroup(_, _) => "Unable to configure cgroup for service",
             Error::Connect(_) => "Unable to connect to Supervisor's pipe",
             Error::Deserialize(_) => "Unable to deserialize message from Supervisor",
             Error::GroupNotFound(_) => "No matching GID for group found",
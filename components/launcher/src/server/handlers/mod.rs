@@ -12,10 +12,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod exit_status;
 mod restart;
 mod spawn;
 mod terminate;
 
+pub use self::exit_status::*;
 pub use self::restart::*;
 pub use self::spawn::*;
 pub use self::terminate::*;
@@ -36,7 +36,9 @@ impl Handler for RestartHandler {
         service.kill();
         match service.wait() {
             Ok(_status) => {
-                match service::run(service.take_args()) {
+                let args = service.take_args();
+                let log_file = services.log_file_for(&args);
+                match service::run(args, log_file) {
                     Ok(new_service) => {
                         let mut reply = protocol::SpawnOk::new();
                         reply.set_pid(new_service.id().into());
@@ -24,7 +24,8 @@ impl Handler for SpawnHandler {
     type Reply = protocol::SpawnOk;
 
     fn handle(msg: Self::Message, services: &mut ServiceTable) -> HandleResult<Self::Reply> {
-        match service::run(msg) {
+        let log_file = services.log_file_for(&msg);
+        match service::run(msg, log_file) {
             Ok(service) => {
                 let mut reply = protocol::SpawnOk::new();
                 reply.set_pid(service.id().into());
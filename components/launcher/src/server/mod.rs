@@ -15,11 +15,12 @@
 mod handlers;
 
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::{Child, Command, Stdio};
 use std::str::FromStr;
+use std::sync::{Arc, Mutex};
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use core;
 use core::package::{PackageIdent, PackageInstall};
@@ -32,9 +33,11 @@ use protocol::{self, ERR_NO_RETRY_EXCODE, OK_NO_RETRY_EXCODE};
 use self::handlers::Handler;
 use {SUP_CMD, SUP_PACKAGE_IDENT};
 use error::{Error, Result};
-use service::Service;
+use service::{self, Service};
 
 const SUP_CMD_ENVVAR: &'static str = "HAB_SUP_BINARY";
+/// How long the exit status of a reaped child is kept for the Supervisor to collect.
+const EXIT_STATUS_TTL_SECS: u64 = 300;
 static LOGKEY: &'static str = "SV";
 
 type Receiver = IpcReceiver<Vec<u8>>;
@@ -167,56 +170,127 @@ impl Server {
 }
 
 #[derive(Debug, Default)]
-pub struct ServiceTable(HashMap<Pid, Service>);
+pub struct ServiceTable {
+    services: HashMap<Pid, Service>,
+    /// Exit statuses of reaped children which haven't been collected by the Supervisor yet, with
+    /// the time they were reaped.
+    exits: HashMap<Pid, (Instant, protocol::ExitStatusOk)>,
+    /// Log files of services, by service id. They are kept across restarts of a service, so that
+    /// a log file's age counts from when the service was first started rather than from its
+    /// latest restart.
+    log_files: HashMap<String, Arc<Mutex<protocol::LogFile>>>,
+}
 
 impl ServiceTable {
     pub fn get(&self, pid: Pid) -> Option<&Service> {
-        self.0.get(&pid)
+        self.services.get(&pid)
     }
 
     pub fn get_mut(&mut self, pid: Pid) -> Option<&mut Service> {
-        self.0.get_mut(&pid)
+        self.services.get_mut(&pid)
     }
 
     pub fn insert(&mut self, service: Service) {
-        self.0.insert(service.id(), service);
+        self.exits.remove(&service.id());
+        self.services.insert(service.id(), service);
     }
 
     pub fn remove(&mut self, pid: Pid) -> Option<Service> {
-        self.0.remove(&pid)
+        self.services.remove(&pid)
+    }
+
+    /// Returns the log file the service to spawn appends its JSON records to, if it has one,
+    /// reusing the one its previous process wrote to.
+    pub fn log_file_for(
+        &mut self,
+        spawn: &protocol::Spawn,
+    ) -> Option<Arc<Mutex<protocol::LogFile>>> {
+        if !spawn.get_json_log() || !spawn.has_log_file() {
+            self.log_files.remove(spawn.get_id());
+            return None;
+        }
+        if let Some(log_file) = self.log_files.get(spawn.get_id()) {
+            let path = log_file.lock().expect("Log file lock is poisoned").path().to_path_buf();
+            if path.as_path() == Path::new(spawn.get_log_file()) {
+                return Some(log_file.clone());
+            }
+        }
+        let log_file = service::open_log_file(spawn);
+        match log_file {
+            Some(ref log_file) => {
+                self.log_files.insert(
+                    spawn.get_id().to_string(),
+                    log_file.clone(),
+                );
+            }
+            None => {
+                self.log_files.remove(spawn.get_id());
+            }
+        }
+        log_file
+    }
+
+    /// Remove and return the exit status of a reaped child.
+    pub fn take_exit(&mut self, pid: Pid) -> Option<protocol::ExitStatusOk> {
+        self.exits.remove(&pid).map(|(_, exit)| exit)
+    }
+
+    /// Forget the exit statuses nobody collected within `EXIT_STATUS_TTL_SECS`.
+    fn prune_exits(&mut self, now: Instant) {
+        let ttl = Duration::from_secs(EXIT_STATUS_TTL_SECS);
+        self.exits.retain(|_, &mut (reaped_at, _)| {
+            now.duration_since(reaped_at) < ttl
+        });
     }
 
     fn kill_all(&mut self) {
-        for service in self.0.values_mut() {
+        for service in self.services.values_mut() {
             outputln!(preamble service.name(), "Stopping...");
             let shutdown_method = service.kill();
             outputln!(preamble service.name(), "Shutdown OK: {}", shutdown_method);
         }
+        self.services.clear();
+        self.exits.clear();
+        self.log_files.clear();
     }
 
     fn reap_zombies(&mut self) {
-        let mut dead: Vec<Pid> = vec![];
-        for service in self.0.values_mut() {
+        let mut dead: Vec<(Pid, protocol::ExitStatusOk)> = vec![];
+        for service in self.services.values_mut() {
+            let mut exit = protocol::ExitStatusOk::new();
             match service.try_wait() {
-                Ok(None) => (),
-                Ok(Some(code)) => {
+                Ok(None) => continue,
+                Ok(Some(status)) => {
                     outputln!(
                         "Child for service '{}' with PID {} exited with code {}",
                         service.name(),
                         service.id(),
-                        code
+                        status
                     );
-                    dead.push(service.id());
+                    if let Some(code) = status.code() {
+                        exit.set_exit_code(code);
+                    }
+                    if service.oom_killed() {
+                        outputln!(
+                            "Child for service '{}' with PID {} exceeded its memory limit",
+                            service.name(),
+                            service.id()
+                        );
+                        exit.set_oom_killed(true);
+                    }
                 }
                 Err(err) => {
                     warn!("Error waiting for child, {}, {}", service.id(), err);
-                    dead.push(service.id());
                 }
             }
+            dead.push((service.id(), exit));
         }
-        for pid in dead {
-            self.0.remove(&pid);
+        let now = Instant::now();
+        for (pid, exit) in dead {
+            self.services.remove(&pid);
+            self.exits.insert(pid, (now, exit));
         }
+        self.prune_exits(now);
     }
 }
 
@@ -279,6 +353,7 @@ fn dispatch(tx: &Sender, bytes: &[u8], services: &mut ServiceTable) {
         }
     };
     let func = match msg.message_id() {
+        "ExitStatus" => handlers::ExitStatusHandler::run,
         "Restart" => handlers::RestartHandler::run,
         "Spawn" => handlers::SpawnHandler::run,
         "Terminate" => handlers::TerminateHandler::run,
@@ -16,12 +16,15 @@ use std::fmt;
 use std::io::{self, BufRead, BufReader, Read, Write};
 #[cfg(unix)]
 use std::process::{ChildStderr, ChildStdout, ExitStatus};
+use std::sync::{Arc, Mutex};
 use std::thread;
 
 #[cfg(windows)]
 use core::os::process::windows_child::{ChildStderr, ChildStdout, ExitStatus};
 use core::os::process::Pid;
 use protocol;
+use serde_json;
+use time;
 
 pub use sys::service::*;
 use error::Result;
@@ -38,19 +41,20 @@ impl Service {
         process: Process,
         stdout: Option<ChildStdout>,
         stderr: Option<ChildStderr>,
+        log_file: Option<Arc<Mutex<protocol::LogFile>>>,
     ) -> Self {
         if let Some(stdout) = stdout {
-            let id = spawn.get_id().to_string();
+            let format = OutputFormat::new(&spawn, log_file.clone());
             thread::Builder::new()
                 .name(format!("{}-out", spawn.get_id()))
-                .spawn(move || pipe_stdout(stdout, id))
+                .spawn(move || pipe_stdout(stdout, format))
                 .ok();
         }
         if let Some(stderr) = stderr {
-            let id = spawn.get_id().to_string();
+            let format = OutputFormat::new(&spawn, log_file);
             thread::Builder::new()
                 .name(format!("{}-err", spawn.get_id()))
-                .spawn(move || pipe_stderr(stderr, id))
+                .spawn(move || pipe_stderr(stderr, format))
                 .ok();
         }
         Service {
@@ -78,6 +82,11 @@ impl Service {
         self.args.get_id()
     }
 
+    /// Returns true if the service's process was killed for exceeding its memory limit.
+    pub fn oom_killed(&self) -> bool {
+        self.process.oom_killed()
+    }
+
     pub fn take_args(self) -> protocol::Spawn {
         self.args
     }
@@ -102,32 +111,164 @@ impl fmt::Debug for Service {
     }
 }
 
+/// Opens the log file the JSON records of a service are appended to, if it has one.
+///
+/// The file is shared by the threads piping the service's stdout and stderr.
+pub fn open_log_file(spawn: &protocol::Spawn) -> Option<Arc<Mutex<protocol::LogFile>>> {
+    if !spawn.get_json_log() || !spawn.has_log_file() {
+        return None;
+    }
+    match protocol::LogFile::open(
+        spawn.get_log_file(),
+        spawn.get_log_max_size(),
+        spawn.get_log_max_age(),
+    ) {
+        Ok(file) => Some(Arc::new(Mutex::new(file))),
+        Err(err) => {
+            error!("Unable to open log file {}, {}", spawn.get_log_file(), err);
+            None
+        }
+    }
+}
+
+/// One line of output of a service, as written in the JSON format. It has the same fields as the
+/// records the Supervisor writes for the output of hooks.
+#[derive(Debug, Serialize)]
+struct LogRecord<'a> {
+    timestamp: String,
+    service_group: &'a str,
+    member_id: &'a str,
+    stream: &'a str,
+    hook: &'a str,
+    message: &'a str,
+}
+
+/// How the lines of output of a service are written by the Launcher.
+struct OutputFormat {
+    id: String,
+    /// The member id of the Supervisor, if lines are written as JSON records.
+    member_id: Option<String>,
+    /// The log file JSON records are also appended to.
+    log_file: Option<Arc<Mutex<protocol::LogFile>>>,
+}
+
+impl OutputFormat {
+    fn new(spawn: &protocol::Spawn, log_file: Option<Arc<Mutex<protocol::LogFile>>>) -> Self {
+        OutputFormat {
+            id: spawn.get_id().to_string(),
+            member_id: if spawn.get_json_log() {
+                Some(spawn.get_member_id().to_string())
+            } else {
+                None
+            },
+            log_file: log_file,
+        }
+    }
+
+    /// Appends a formatted line to the log file, if there is one.
+    fn log(&self, line: &str) {
+        if let Some(ref log_file) = self.log_file {
+            let mut log_file = log_file.lock().expect("Log file lock is poisoned");
+            if let Err(err) = log_file.write_line(line) {
+                error!(
+                    "Unable to write log file {}, {}",
+                    log_file.path().display(),
+                    err
+                );
+            }
+        }
+    }
+
+    fn format(&self, stream: &str, buffer: &str) -> String {
+        match self.member_id {
+            Some(ref member_id) => {
+                let record = LogRecord {
+                    timestamp: time::now_utc().rfc3339().to_string(),
+                    service_group: &self.id,
+                    member_id: member_id,
+                    stream: stream,
+                    hook: "run",
+                    message: buffer.trim_right_matches(|c| c == '\n' || c == '\r'),
+                };
+                let mut line =
+                    serde_json::to_string(&record).expect("Log record serialization cannot fail");
+                line.push('\n');
+                line
+            }
+            None => {
+                let mut line = if stream == "stdout" {
+                    output_format!(preamble &self.id, logkey "O")
+                } else {
+                    output_format!(preamble &self.id, logkey "E")
+                };
+                line.push_str(buffer);
+                line
+            }
+        }
+    }
+}
+
 /// Consume output from a child process until EOF, then finish
-fn pipe_stdout<T>(out: T, id: String)
+fn pipe_stdout<T>(out: T, format: OutputFormat)
 where
     T: Read,
 {
     let mut reader = BufReader::new(out);
     let mut buffer = String::new();
     while reader.read_line(&mut buffer).unwrap() > 0 {
-        let mut line = output_format!(preamble &id, logkey "O");
-        line.push_str(&buffer);
+        let line = format.format("stdout", &buffer);
         write!(&mut io::stdout(), "{}", line).expect("unable to write to stdout");
+        format.log(&line);
         buffer.clear();
     }
 }
 
 /// Consume standard error from a child process until EOF, then finish
-fn pipe_stderr<T>(err: T, id: String)
+fn pipe_stderr<T>(err: T, format: OutputFormat)
 where
     T: Read,
 {
     let mut reader = BufReader::new(err);
     let mut buffer = String::new();
     while reader.read_line(&mut buffer).unwrap() > 0 {
-        let mut line = output_format!(preamble &id, logkey "E");
-        line.push_str(&buffer);
+        let line = format.format("stderr", &buffer);
         write!(&mut io::stderr(), "{}", line).expect("unable to write to stderr");
+        format.log(&line);
         buffer.clear();
     }
 }
+
+#[cfg(test)]
+mod test {
+    use serde_json::{self, Value as Json};
+
+    use super::OutputFormat;
+
+    fn json_format() -> OutputFormat {
+        OutputFormat {
+            id: String::from("redis.default"),
+            member_id: Some(String::from("abc123")),
+            log_file: None,
+        }
+    }
+
+    #[test]
+    fn json_records_are_one_line() {
+        let line = json_format().format("stdout", "ready\r\n");
+        assert!(line.ends_with("}\n"));
+        assert_eq!(line.matches('\n').count(), 1);
+    }
+
+    #[test]
+    fn json_records_escape_the_message() {
+        let message = "say \"hi\"\\n\tto \u{1}\u{7f} \u{e9}t\u{e9}";
+        let line = json_format().format("stderr", &format!("{}\n", message));
+        let record: Json = serde_json::from_str(&line).unwrap();
+
+        assert_eq!(record["service_group"], "redis.default");
+        assert_eq!(record["member_id"], "abc123");
+        assert_eq!(record["stream"], "stderr");
+        assert_eq!(record["hook"], "run");
+        assert_eq!(record["message"], message);
+    }
+}
@@ -0,0 +1,146 @@
+// Copyright (c) 2017 Chef Software Inc. and/or applicable contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Resource limits for services, applied through a cgroup v2 sub-tree.
+//!
+//! Every service with limits gets its own cgroup, named after the service, under a `hab` cgroup
+//! at the root of the unified hierarchy.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+use protocol;
+
+use error::{Error, Result};
+
+static LOGKEY: &'static str = "CG";
+const CGROUP_ROOT: &'static str = "/sys/fs/cgroup";
+const HAB_CGROUP: &'static str = "hab";
+/// Period, in microseconds, over which the CPU limit of a service is enforced.
+const CPU_PERIOD: u64 = 100_000;
+
+pub struct Cgroup {
+    path: PathBuf,
+    oom_kills: u64,
+}
+
+impl Cgroup {
+    /// Create the cgroup of a service and apply the resource limits of its spawn message.
+    ///
+    /// Returns `None` if the service has no limits, or if the host doesn't provide a cgroup v2
+    /// hierarchy.
+    pub fn create(msg: &protocol::Spawn) -> Result<Option<Cgroup>> {
+        if msg.has_cpu_limit() && msg.get_cpu_limit() == 0 {
+            return Err(Error::BadResourceLimit(
+                "cpu=0, the CPU limit must be greater than 0".to_string(),
+            ));
+        }
+        let mut controllers = vec![];
+        if msg.has_cpu_limit() {
+            controllers.push("+cpu");
+        }
+        if msg.has_memory_limit() {
+            controllers.push("+memory");
+        }
+        if msg.has_pids_limit() {
+            controllers.push("+pids");
+        }
+        if controllers.is_empty() {
+            return Ok(None);
+        }
+        let root = Path::new(CGROUP_ROOT);
+        if !root.join("cgroup.controllers").is_file() {
+            outputln!(preamble msg.get_id(),
+                      "Resource limits are ignored; cgroup v2 is not available on this host");
+            return Ok(None);
+        }
+        let controllers = controllers.join(" ");
+        let parent = root.join(HAB_CGROUP);
+        fs::create_dir_all(&parent).map_err(|e| Error::Cgroup(parent.clone(), e))?;
+        write(&root.join("cgroup.subtree_control"), &controllers)?;
+        write(&parent.join("cgroup.subtree_control"), &controllers)?;
+        let path = parent.join(msg.get_id());
+        fs::create_dir_all(&path).map_err(|e| Error::Cgroup(path.clone(), e))?;
+        if msg.has_cpu_limit() {
+            let quota = msg.get_cpu_limit() as u64 * CPU_PERIOD / 1_000;
+            write(&path.join("cpu.max"), &format!("{} {}", quota, CPU_PERIOD))?;
+        }
+        if msg.has_memory_limit() {
+            write(&path.join("memory.max"), &msg.get_memory_limit().to_string())?;
+        }
+        if msg.has_pids_limit() {
+            write(&path.join("pids.max"), &msg.get_pids_limit().to_string())?;
+        }
+        let mut cgroup = Cgroup {
+            path: path,
+            oom_kills: 0,
+        };
+        // The cgroup may have been left behind by a previous process of the service, so only OOM
+        // kills which happen from now on are attributed to the new process.
+        cgroup.oom_kills = cgroup.read_oom_kills();
+        Ok(Some(cgroup))
+    }
+
+    /// Opens the file through which processes join the cgroup.
+    pub fn procs_file(&self) -> Result<File> {
+        let path = self.path.join("cgroup.procs");
+        OpenOptions::new().write(true).open(&path).map_err(
+            |e| Error::Cgroup(path, e),
+        )
+    }
+
+    /// Returns true if a process of the cgroup was killed by the OOM killer since the cgroup was
+    /// created.
+    pub fn oom_killed(&self) -> bool {
+        self.read_oom_kills() > self.oom_kills
+    }
+
+    /// Remove the cgroup. This fails if it still contains processes, in which case the cgroup is
+    /// left behind and re-used by the next process of the service.
+    pub fn remove(&self) {
+        if let Err(err) = fs::remove_dir(&self.path) {
+            debug!("Unable to remove cgroup {}, {}", self.path.display(), err);
+        }
+    }
+
+    fn read_oom_kills(&self) -> u64 {
+        let mut events = String::new();
+        if let Err(err) = File::open(self.path.join("memory.events"))
+            .and_then(|mut f| f.read_to_string(&mut events))
+        {
+            debug!("Unable to read memory events of {}, {}", self.path.display(), err);
+            return 0;
+        }
+        events
+            .lines()
+            .filter_map(|line| {
+                let mut parts = line.split_whitespace();
+                match (parts.next(), parts.next()) {
+                    (Some("oom_kill"), Some(count)) => count.parse().ok(),
+                    _ => None,
+                }
+            })
+            .next()
+            .unwrap_or(0)
+    }
+}
+
+fn write(path: &Path, value: &str) -> Result<()> {
+    OpenOptions::new()
+        .write(true)
+        .open(path)
+        .and_then(|mut f| f.write_all(value.as_bytes()))
+        .map_err(|e| Error::Cgroup(path.to_path_buf(), e))
+}
@@ -12,31 +12,40 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::fs::File;
 use std::io;
 use std::ops::Neg;
+use std::os::unix::io::{AsRawFd, RawFd};
 use std::os::unix::process::{CommandExt, ExitStatusExt};
+use std::ptr;
 use std::process::{Command, ExitStatus, Stdio};
 use std::result;
+use std::sync::{Arc, Mutex};
 
 use core::os;
 use core::os::process::{Pid, signal, Signal};
-use libc::{self, c_int, pid_t};
-use protocol::{self, ShutdownMethod};
+use libc::{self, c_int, gid_t, pid_t, uid_t};
+use protocol::{self, LogFile, ShutdownMethod};
 use time::{Duration, SteadyTime};
 
 use error::{Error, Result};
 use service::Service;
+use super::cgroup::Cgroup;
 
 pub struct Process {
     pid: pid_t,
     status: Option<ExitStatus>,
+    cgroup: Option<Cgroup>,
+    oom_killed: bool,
 }
 
 impl Process {
-    fn new(pid: u32) -> Self {
+    fn new(pid: u32, cgroup: Option<Cgroup>) -> Self {
         Process {
             pid: pid as pid_t,
             status: None,
+            cgroup: cgroup,
+            oom_killed: false,
         }
     }
 
@@ -44,6 +53,11 @@ impl Process {
         self.pid
     }
 
+    /// Returns true if the process was killed for exceeding its memory limit.
+    pub fn oom_killed(&self) -> bool {
+        self.oom_killed
+    }
+
     /// Attempt to gracefully terminate a proccess and then forcefully kill it after
     /// 8 seconds if it has not terminated.
     pub fn kill(&mut self) -> ShutdownMethod {
@@ -94,7 +108,7 @@ impl Process {
             0 => Ok(None),
             -1 => Err(Error::ExecWait(io::Error::last_os_error())),
             _ => {
-                self.status = Some(ExitStatus::from_raw(status));
+                self.exited(ExitStatus::from_raw(status));
                 Ok(Some(ExitStatus::from_raw(status)))
             }
         }
@@ -108,14 +122,24 @@ impl Process {
         match unsafe { libc::waitpid(self.pid, &mut status, 0) } {
             -1 => Err(Error::ExecWait(io::Error::last_os_error())),
             _ => {
-                self.status = Some(ExitStatus::from_raw(status));
+                self.exited(ExitStatus::from_raw(status));
                 Ok(ExitStatus::from_raw(status))
             }
         }
     }
+
+    fn exited(&mut self, status: ExitStatus) {
+        self.status = Some(status);
+        if let Some(cgroup) = self.cgroup.take() {
+            // Any process of the service may have been picked by the OOM killer, not only the
+            // main one, so the cgroup's events are the only reliable source.
+            self.oom_killed = cgroup.oom_killed();
+            cgroup.remove();
+        }
+    }
 }
 
-pub fn run(msg: protocol::Spawn) -> Result<Service> {
+pub fn run(msg: protocol::Spawn, log_file: Option<Arc<Mutex<LogFile>>>) -> Result<Service> {
     debug!("launcher is spawning {}", msg.get_binary());
     let mut cmd = Command::new(msg.get_binary());
     let uid = os::users::get_uid_by_name(msg.get_svc_user()).ok_or(
@@ -124,18 +148,94 @@ pub fn run(msg: protocol::Spawn) -> Result<Service> {
     let gid = os::users::get_gid_by_name(msg.get_svc_group()).ok_or(
         Error::GroupNotFound(msg.get_svc_group().to_string()),
     )?;
+    // A service whose limits can't be applied still runs, only without them.
+    let (cgroup, procs) = match Cgroup::create(&msg).and_then(open_procs) {
+        Ok(Some((cgroup, procs))) => (Some(cgroup), Some(procs)),
+        Ok(None) => (None, None),
+        Err(err) => {
+            outputln!(preamble msg.get_id(),
+                      "Running without resource limits; they could not be applied: {}", err);
+            (None, None)
+        }
+    };
     cmd.before_exec(owned_pgid);
-    cmd.stdin(Stdio::null())
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .uid(uid)
-        .gid(gid);
+    match procs.as_ref().map(|f| f.as_raw_fd()) {
+        // The child joins its cgroup before it execs, so that neither it nor anything it forks
+        // ever runs outside of the service's limits. Joining requires root, so the service's
+        // user and group are only taken on afterwards.
+        Some(fd) => {
+            cmd.before_exec(move || join_cgroup(fd));
+            cmd.before_exec(move || drop_privileges(uid, gid));
+        }
+        None => {
+            cmd.uid(uid).gid(gid);
+        }
+    }
+    cmd.stdin(Stdio::null()).stdout(Stdio::piped()).stderr(
+        Stdio::piped(),
+    );
     for (key, val) in msg.get_env().iter() {
         cmd.env(key, val);
     }
-    let child = cmd.spawn().map_err(Error::Spawn)?;
-    let process = Process::new(child.id());
-    Ok(Service::new(msg, process, child.stdout, child.stderr))
+    let child = match cmd.spawn() {
+        Ok(child) => child,
+        Err(err) => {
+            if let Some(cgroup) = cgroup {
+                cgroup.remove();
+            }
+            return Err(Error::Spawn(err));
+        }
+    };
+    drop(procs);
+    let process = Process::new(child.id(), cgroup);
+    Ok(Service::new(
+        msg,
+        process,
+        child.stdout,
+        child.stderr,
+        log_file,
+    ))
+}
+
+/// Opens the `cgroup.procs` file of a freshly created cgroup, removing the cgroup if it can't be.
+fn open_procs(cgroup: Option<Cgroup>) -> Result<Option<(Cgroup, File)>> {
+    match cgroup {
+        Some(cgroup) => {
+            match cgroup.procs_file() {
+                Ok(procs) => Ok(Some((cgroup, procs))),
+                Err(err) => {
+                    cgroup.remove();
+                    Err(err)
+                }
+            }
+        }
+        None => Ok(None),
+    }
+}
+
+/// Moves the calling process into the cgroup whose `cgroup.procs` file is open as `fd`. Writing
+/// `0` designates the writer itself, which spares formatting a pid between fork and exec.
+fn join_cgroup(fd: RawFd) -> result::Result<(), io::Error> {
+    if unsafe { libc::write(fd, b"0".as_ptr() as *const libc::c_void, 1) } == 1 {
+        Ok(())
+    } else {
+        Err(io::Error::last_os_error())
+    }
+}
+
+/// Takes on the service's user and group, in the same way as `Command::uid` and `Command::gid`.
+fn drop_privileges(uid: uid_t, gid: gid_t) -> result::Result<(), io::Error> {
+    unsafe {
+        // Only root may drop its supplementary groups. When root can't, the service would run
+        // with them, so it isn't started at all.
+        if libc::geteuid() == 0 && libc::setgroups(0, ptr::null()) != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        if libc::setgid(gid) != 0 || libc::setuid(uid) != 0 {
+            return Err(io::Error::last_os_error());
+        }
+    }
+    Ok(())
 }
 
 // we want the command to spawn processes in their own process group
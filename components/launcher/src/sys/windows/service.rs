@@ -15,11 +15,12 @@
 use std::collections::HashMap;
 use std::io;
 use std::mem;
+use std::sync::{Arc, Mutex};
 
 use core::os::process::handle_from_pid;
 use core::os::process::windows_child::{Child, ExitStatus, Handle};
 use kernel32;
-use protocol::{self, ShutdownMethod};
+use protocol::{self, LogFile, ShutdownMethod};
 use time::{Duration, SteadyTime};
 use winapi;
 
@@ -46,6 +47,12 @@ impl Process {
         unsafe { kernel32::GetProcessId(self.handle.raw()) as u32 }
     }
 
+    /// Resource limits aren't supported on Windows so a process is never killed for exceeding
+    /// its memory limit.
+    pub fn oom_killed(&self) -> bool {
+        false
+    }
+
     pub fn kill(&mut self) -> ShutdownMethod {
         if self.status().is_some() {
             return ShutdownMethod::AlreadyExited;
@@ -115,7 +122,10 @@ impl Process {
     }
 }
 
-pub fn run(mut msg: protocol::Spawn) -> Result<Service> {
+pub fn run(
+    mut msg: protocol::Spawn,
+    log_file: Option<Arc<Mutex<LogFile>>>,
+) -> Result<Service> {
     debug!("launcher is spawning {}", msg.get_binary());
     let ps_cmd = format!("iex $(gc {} | out-string)", msg.get_binary());
     let password = if msg.get_svc_password().is_empty() {
@@ -132,7 +142,13 @@ pub fn run(mut msg: protocol::Spawn) -> Result<Service> {
     ) {
         Ok(child) => {
             let process = Process::new(child.handle);
-            Ok(Service::new(msg, process, child.stdout, child.stderr))
+            Ok(Service::new(
+                msg,
+                process,
+                child.stdout,
+                child.stderr,
+                log_file,
+            ))
         }
         Err(_) => Err(Error::Spawn(io::Error::last_os_error())),
     }
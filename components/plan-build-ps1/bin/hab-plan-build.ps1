@@ -116,6 +116,8 @@ $script:pkg_exports = @{}
 $script:pkg_svc_user = "hab"
 # The group to run the service as
 $script:pkg_svc_group = "$pkg_svc_user"
+# Whether the service's configuration templates and hooks are compiled in strict mode
+$script:pkg_strict_templates = $false
 
 # Initially set $pkg_svc_* variables. This happens before the Plan is sourced,
 # meaning that `$pkg_name` is not yet set. However, `$pkg_svc_run` wants
@@ -1061,7 +1063,7 @@ function Invoke-DefaultInstall {
 }
 
 # Copy the `./config` directory, relative to the Plan, to `$pkg_prefix\config`.
-# Do the same with `default.toml`. Delegates most of the implementation to the
+# Do the same with `default.toml` and the config schema. Delegates most of the implementation to the
 # `Invoke-DefaultBuildConfig` function.
 function Invoke-BuildConfig {
     Invoke-DefaultBuildConfig
@@ -1097,6 +1099,11 @@ function Invoke-DefaultBuildConfig {
     if (Test-Path "$PLAN_CONTEXT/default.toml") {
         cp "$PLAN_CONTEXT/default.toml" $pkg_prefix
     }
+    foreach ($schema in @("config_schema.json", "config_schema.toml")) {
+        if (Test-Path "$PLAN_CONTEXT/$schema") {
+            cp "$PLAN_CONTEXT/$schema" $pkg_prefix
+        }
+    }
 }
 
 # Write out the `$pkg_prefix\run` file. If a file named `hooks\run`
@@ -1275,6 +1282,9 @@ function _Write-Metadata {
         "$pkg_svc_group" |
             Out-File "$pkg_prefix\SVC_GROUP" -Encoding ascii
     }
+    if ("$pkg_strict_templates" -eq "true") {
+        "true" | Out-File "$pkg_prefix\STRICT_TEMPLATES" -Encoding ascii
+    }
 
     # Generate the blake2b hashes of all the files in the package. This
     # is not in the resulting MANIFEST because MANIFEST is included!
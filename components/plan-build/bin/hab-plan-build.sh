@@ -211,6 +211,26 @@
 #   [storage]="port host"
 # )
 #
+# ### pkg_svc_limits
+# An associative array of resource limits applied to the service's process, on hosts which
+# support them. `cpu` is in thousandths of a CPU, `memory` in bytes and `pids` is the maximum
+# number of processes and threads. Limits set in the service spec take precedence.
+# ```
+# pkg_svc_limits=(
+#   [cpu]=500
+#   [memory]=268435456
+#   [pids]=64
+# )
+# ```
+#
+# ### pkg_strict_templates
+# Set to `true` to compile the service's configuration templates and hooks in strict mode. A
+# variable which isn't set then fails the compilation, and the Supervisor keeps the service's
+# current configuration rather than writing a broken one.
+# ```
+# pkg_strict_templates=true
+# ```
+#
 # ### pkg_origin
 # A string to use for the origin. The origin is used to denote a particular upstream of a
 # package; when we resolve dependencies, we consider a version of a package to be equal
@@ -408,10 +428,13 @@ pkg_exposes=()
 declare -A pkg_exports
 declare -A pkg_binds
 declare -A pkg_binds_optional
+declare -A pkg_svc_limits
 # The user to run the service as
 pkg_svc_user=hab
 # The group to run the service as
 pkg_svc_group=$pkg_svc_user
+# Whether the service's templates fail to compile when they use a variable which isn't set
+pkg_strict_templates=false
 
 # Initially set $pkg_svc_* variables. This happens before the Plan is sourced,
 # meaning that `$pkg_name` is not yet set. However, `$pkg_svc_run` wants
@@ -1724,6 +1747,8 @@ do_default_install() {
 # * `$pkg_prefix/EXPOSES` - An array of `pkg_exports` for which ports that this package exposes
 # * `$pkg_prefix/BINDS` - A list of services you connect to and keys that you expect to be exported
 # * `$pkg_prefix/BINDS_OPTIONAL` - Same as `BINDS` but not required for the service to start
+# * `$pkg_prefix/SVC_LIMITS` - Resource limits applied to the service's process
+# * `$pkg_prefix/STRICT_TEMPLATES` - Present if the service's templates are compiled in strict mode
 # * `$pkg_prefix/FILES` - blake2b checksums of all files in the package
 # * `$pkg_prefix/LDFLAGS` - Any LDFLAGS for things that link against us
 # * `$pkg_prefix/LD_RUN_PATH` - The LD_RUN_PATH for things that link against us
@@ -1755,19 +1780,21 @@ _build_metadata() {
   _render_metadata_RUNTIME_ENVIRONMENT
   _render_metadata_RUNTIME_ENVIRONMENT_PROVENANCE
 
-  # Only generate `SVC_USER` & `SVC_GROUP` files if this package is a service.
-  # We determine this by checking if there is a `hooks/run` script and/or
-  # a set `$pkg_svc_run` value.
+  # Only generate `SVC_USER`, `SVC_GROUP`, `SVC_LIMITS` & `STRICT_TEMPLATES`
+  # files if this package is a service. We determine this by checking if there
+  # is a `hooks/run` script and/or a set `$pkg_svc_run` value.
   if [[ -f "$PLAN_CONTEXT/hooks/run" || -n "${pkg_svc_run:-}" ]]; then
     _render_metadata_SVC_USER
     _render_metadata_SVC_GROUP
+    _render_metadata_SVC_LIMITS
+    _render_metadata_STRICT_TEMPLATES
   fi
 
   return 0
 }
 
 # Copy the `./config` directory, relative to the Plan, to `$pkg_prefix/config`.
-# Do the same with `default.toml`. Delegates most of the implementation to the
+# Do the same with `default.toml` and the config schema. Delegates most of the implementation to the
 # `do_default_build_config()` function.
 do_build_config() {
   do_default_build_config
@@ -1805,6 +1832,11 @@ do_default_build_config() {
   if [[ -f "$PLAN_CONTEXT/default.toml" ]]; then
     cp "$PLAN_CONTEXT/default.toml" $pkg_prefix
   fi
+  for schema in config_schema.json config_schema.toml; do
+    if [[ -f "$PLAN_CONTEXT/$schema" ]]; then
+      cp "$PLAN_CONTEXT/$schema" $pkg_prefix
+    fi
+  done
   return 0
 }
 
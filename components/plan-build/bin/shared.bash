@@ -203,11 +203,22 @@ _render_metadata_RUNTIME_ENVIRONMENT_PROVENANCE(){
     _render_associative_array_file ${pkg_prefix} RUNTIME_ENVIRONMENT_PROVENANCE __runtime_environment_provenance
 }
 
+_render_metadata_STRICT_TEMPLATES() {
+  if [[ "$pkg_strict_templates" == "true" ]]; then
+    debug "Rendering STRICT_TEMPLATES metadata file"
+    echo "true" > $pkg_prefix/STRICT_TEMPLATES
+  fi
+}
+
 _render_metadata_SVC_GROUP() {
   debug "Rendering SVC_GROUP metadata file"
   echo "$pkg_svc_group" > $pkg_prefix/SVC_GROUP
 }
 
+_render_metadata_SVC_LIMITS() {
+    _render_associative_array_file ${pkg_prefix} SVC_LIMITS pkg_svc_limits
+}
+
 _render_metadata_SVC_USER() {
   debug "Rendering SVC_USER metadata file"
   echo "$pkg_svc_user" > $pkg_prefix/SVC_USER
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::cmp;
 use std::collections::{BTreeMap, HashMap};
 use std::fmt;
 use std::str::FromStr;
@@ -24,6 +25,8 @@ use butterfly::rumor::service_config::ServiceConfig as ServiceConfigRumor;
 use butterfly::rumor::election::Election as ElectionRumor;
 use butterfly::rumor::election::Election_Status as ElectionStatusRumor;
 use butterfly::rumor::election::ElectionUpdate as ElectionUpdateRumor;
+use butterfly::rumor::canary::Canary as CanaryRumor;
+use butterfly::rumor::canary::Canary_Status as CanaryStatusRumor;
 use butterfly::rumor::service::SysInfo;
 use hcore;
 use hcore::service::ServiceGroup;
@@ -31,6 +34,7 @@ use hcore::package::PackageIdent;
 use toml;
 
 use error::{Error, SupError};
+use manager::service::HealthCheck;
 
 static LOGKEY: &'static str = "CE";
 
@@ -48,6 +52,7 @@ pub struct CensusRing {
     last_membership_counter: usize,
     last_service_config_counter: usize,
     last_service_file_counter: usize,
+    last_canary_counter: usize,
 }
 
 impl CensusRing {
@@ -71,6 +76,7 @@ impl CensusRing {
             last_membership_counter: 0,
             last_service_config_counter: 0,
             last_service_file_counter: 0,
+            last_canary_counter: 0,
         }
     }
 
@@ -82,6 +88,7 @@ impl CensusRing {
         member_list: &MemberList,
         service_config_rumors: &RumorStore<ServiceConfigRumor>,
         service_file_rumors: &RumorStore<ServiceFileRumor>,
+        canary_rumors: &RumorStore<CanaryRumor>,
     ) {
         // If ANY new rumor, of any type, has been received,
         // reconstruct the entire census state to ensure consistency
@@ -90,7 +97,8 @@ impl CensusRing {
             (election_rumors.get_update_counter() > self.last_election_counter) ||
             (election_update_rumors.get_update_counter() > self.last_election_update_counter) ||
             (service_config_rumors.get_update_counter() > self.last_service_config_counter) ||
-            (service_file_rumors.get_update_counter() > self.last_service_file_counter)
+            (service_file_rumors.get_update_counter() > self.last_service_file_counter) ||
+            (canary_rumors.get_update_counter() > self.last_canary_counter)
         {
 
             self.changed = true;
@@ -100,6 +108,7 @@ impl CensusRing {
             self.update_from_election_update_store(election_update_rumors);
             self.update_from_service_config(service_config_rumors);
             self.update_from_service_files(service_file_rumors);
+            self.update_from_canary_store(canary_rumors);
 
             // Update our counters to reflect current state.
             self.last_membership_counter = member_list.get_update_counter();
@@ -108,6 +117,7 @@ impl CensusRing {
             self.last_election_update_counter = election_update_rumors.get_update_counter();
             self.last_service_config_counter = service_config_rumors.get_update_counter();
             self.last_service_file_counter = service_file_rumors.get_update_counter();
+            self.last_canary_counter = canary_rumors.get_update_counter();
 
         } else {
             self.changed = false;
@@ -218,9 +228,21 @@ impl CensusRing {
             census_group.update_from_service_file_rumors(rumors);
         });
     }
+
+    fn update_from_canary_store(&mut self, canary_rumors: &RumorStore<CanaryRumor>) {
+        canary_rumors.with_keys(|(service_group, rumors)| if let Ok(sg) =
+            service_group_from_str(service_group)
+        {
+            if let Some(canary) = rumors.get("canary") {
+                if let Some(census_group) = self.census_groups.get_mut(&sg) {
+                    census_group.update_from_canary_rumor(canary);
+                }
+            }
+        });
+    }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
 pub enum ElectionStatus {
     None,
     ElectionInProgress,
@@ -270,6 +292,52 @@ impl From<ElectionStatusRumor> for ElectionStatus {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum CanaryStatus {
+    Testing,
+    Promoted,
+    RolledBack,
+}
+
+impl fmt::Display for CanaryStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let value = match *self {
+            CanaryStatus::Testing => "testing",
+            CanaryStatus::Promoted => "promoted",
+            CanaryStatus::RolledBack => "rolled-back",
+        };
+        write!(f, "{}", value)
+    }
+}
+
+impl From<CanaryStatusRumor> for CanaryStatus {
+    fn from(val: CanaryStatusRumor) -> CanaryStatus {
+        match val {
+            CanaryStatusRumor::Testing => CanaryStatus::Testing,
+            CanaryStatusRumor::Promoted => CanaryStatus::Promoted,
+            CanaryStatusRumor::RolledBack => CanaryStatus::RolledBack,
+        }
+    }
+}
+
+/// The state of a canary update of a service group, as last gossiped by its update leader or by
+/// one of its canaries.
+#[derive(Debug, Clone, Serialize)]
+pub struct Canary {
+    pub incarnation: u64,
+    pub pkg: PackageIdent,
+    pub previous_pkg: PackageIdent,
+    pub canaries: Vec<MemberId>,
+    pub status: CanaryStatus,
+}
+
+impl Canary {
+    /// Whether the given member was picked to test the new package.
+    pub fn is_canary(&self, member_id: &str) -> bool {
+        self.canaries.iter().any(|id| id == member_id)
+    }
+}
+
 #[derive(Debug, Default, Serialize)]
 pub struct ServiceFile {
     pub filename: String,
@@ -290,6 +358,7 @@ pub struct CensusGroup {
     pub update_election_status: ElectionStatus,
     pub leader_id: Option<MemberId>,
     pub service_config: Option<ServiceConfig>,
+    pub canary: Option<Canary>,
 
     local_member_id: MemberId,
     population: BTreeMap<MemberId, CensusMember>,
@@ -309,6 +378,7 @@ impl CensusGroup {
             leader_id: None,
             update_leader_id: None,
             service_config: None,
+            canary: None,
             service_files: HashMap::new(),
             changed_service_files: Vec::new(),
         }
@@ -338,6 +408,22 @@ impl CensusGroup {
         self.population.values().map(|cm| cm).collect()
     }
 
+    /// Returns the members carrying every one of the given tags.
+    pub fn members_with_tags(&self, tags: &BTreeMap<String, String>) -> Vec<&CensusMember> {
+        self.population
+            .values()
+            .filter(|cm| cm.has_tags(tags))
+            .collect()
+    }
+
+    /// Is any of the members carrying every one of the given tags alive? A bind filtered by these
+    /// tags is only satisfied if one is.
+    pub fn has_live_member_with_tags(&self, tags: &BTreeMap<String, String>) -> bool {
+        self.population.values().any(
+            |cm| cm.alive() && cm.has_tags(tags),
+        )
+    }
+
     pub fn changed_service_files(&self) -> Vec<&ServiceFile> {
         self.changed_service_files
             .iter()
@@ -345,6 +431,26 @@ impl CensusGroup {
             .collect()
     }
 
+    /// Picks the members which test a new package first in a canary update: `percent` of the
+    /// alive members of the group, rounded up, always starting with the running Supervisor.
+    pub fn pick_canaries(&self, percent: u32) -> Vec<MemberId> {
+        let alive: Vec<&MemberId> = self.population
+            .values()
+            .filter(|cm| cm.alive)
+            .map(|cm| &cm.member_id)
+            .collect();
+        let count = cmp::max(1, (alive.len() * percent as usize + 99) / 100);
+        let mut canaries = vec![self.local_member_id.clone()];
+        canaries.extend(
+            alive
+                .into_iter()
+                .filter(|id| **id != self.local_member_id)
+                .take(count - 1)
+                .cloned(),
+        );
+        canaries
+    }
+
     /// Return previous alive peer, the peer to your left in the ordered members list, or None if
     /// you have no alive peers.
     pub fn previous_peer(&self) -> Option<&CensusMember> {
@@ -439,6 +545,29 @@ impl CensusGroup {
         }
     }
 
+    fn update_from_canary_rumor(&mut self, canary: &CanaryRumor) {
+        let pkg = PackageIdent::from_str(canary.get_pkg());
+        let previous_pkg = PackageIdent::from_str(canary.get_previous_pkg());
+        match (pkg, previous_pkg) {
+            (Ok(pkg), Ok(previous_pkg)) => {
+                self.canary = Some(Canary {
+                    incarnation: canary.get_incarnation(),
+                    pkg: pkg,
+                    previous_pkg: previous_pkg,
+                    canaries: canary.get_canaries().to_vec(),
+                    status: canary.get_status().into(),
+                });
+            }
+            (Err(err), _) | (_, Err(err)) => {
+                warn!(
+                    "Ignoring canary rumor for {} with a bad package: {}",
+                    self.service_group,
+                    err
+                )
+            }
+        }
+    }
+
     fn update_from_service_file_rumors(
         &mut self,
         service_file_rumors: &HashMap<String, ServiceFileRumor>,
@@ -497,11 +626,15 @@ pub struct CensusMember {
     pub update_election_is_running: bool,
     pub update_election_is_no_quorum: bool,
     pub update_election_is_finished: bool,
+    /// Result of the member's last health check, as gossiped in its service rumor.
+    pub health_check: HealthCheck,
     pub sys: SysInfo,
     alive: bool,
     suspect: bool,
     confirmed: bool,
     departed: bool,
+    /// User-defined labels of the member's Supervisor.
+    pub tags: BTreeMap<String, String>,
     // Maps must be represented last in a serializable struct for the current version of the toml
     // crate. Additionally, this deserialization method is required to correct any ordering issues
     // with the table being serialized - https://docs.rs/toml/0.4.0/toml/ser/fn.tables_last.html
@@ -525,7 +658,9 @@ impl CensusMember {
             Ok(ident) => self.pkg = Some(ident),
             Err(err) => warn!("Received a bad package ident from gossip data, err={}", err),
         };
+        self.health_check = HealthCheck::from(rumor.get_health_check());
         self.sys = rumor.get_sys().clone().into();
+        self.tags = rumor.get_sys().get_tags().clone().into_iter().collect();
         self.cfg = toml::from_slice(rumor.get_cfg()).unwrap_or(toml::value::Table::default());
     }
 
@@ -584,6 +719,13 @@ impl CensusMember {
     pub fn alive(&self) -> bool {
         self.alive
     }
+
+    /// Does this member carry every one of the given tags?
+    pub fn has_tags(&self, tags: &BTreeMap<String, String>) -> bool {
+        tags.iter().all(
+            |(key, value)| self.tags.get(key) == Some(value),
+        )
+    }
 }
 
 fn service_group_from_str(sg: &str) -> Result<ServiceGroup, hcore::Error> {
@@ -599,6 +741,8 @@ fn service_group_from_str(sg: &str) -> Result<ServiceGroup, hcore::Error> {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeMap;
+
     use hcore::package::ident::PackageIdent;
     use hcore::service::ServiceGroup;
     use butterfly::member::MemberList;
@@ -607,9 +751,11 @@ mod tests {
     use butterfly::rumor::service_file::ServiceFile as ServiceFileRumor;
     use butterfly::rumor::election::Election as ElectionRumor;
     use butterfly::rumor::election::ElectionUpdate as ElectionUpdateRumor;
+    use butterfly::rumor::canary::Canary as CanaryRumor;
     use butterfly::rumor::service::SysInfo;
     use butterfly::rumor::RumorStore;
-    use census::CensusRing;
+    use census::{CanaryStatus, CensusRing};
+    use manager::service::HealthCheck;
 
     #[test]
     fn update_from_rumors() {
@@ -620,6 +766,11 @@ mod tests {
         sys_info.set_gossip_port(7777);
         sys_info.set_http_gateway_ip("0.0.0.0".to_string());
         sys_info.set_http_gateway_port(9631);
+        let mut tagged_sys_info = sys_info.clone();
+        tagged_sys_info.mut_tags().insert(
+            "zone".to_string(),
+            "us-east-1a".to_string(),
+        );
         let pg_id = PackageIdent::new(
             "starkandwayne",
             "shield",
@@ -632,10 +783,11 @@ mod tests {
         let service_one =
             ServiceRumor::new("member-a".to_string(), &pg_id, &sg_one, &sys_info, None);
         let sg_two = ServiceGroup::new(None, "shield", "two", None).unwrap();
-        let service_two =
+        let mut service_two =
             ServiceRumor::new("member-b".to_string(), &pg_id, &sg_two, &sys_info, None);
+        service_two.set_health_check(0);
         let service_three =
-            ServiceRumor::new("member-a".to_string(), &pg_id, &sg_two, &sys_info, None);
+            ServiceRumor::new("member-a".to_string(), &pg_id, &sg_two, &tagged_sys_info, None);
 
         service_store.insert(service_one);
         service_store.insert(service_two);
@@ -655,6 +807,15 @@ mod tests {
 
         let service_config_store: RumorStore<ServiceConfigRumor> = RumorStore::default();
         let service_file_store: RumorStore<ServiceFileRumor> = RumorStore::default();
+        let canary_store: RumorStore<CanaryRumor> = RumorStore::default();
+        let canary = CanaryRumor::new(
+            "member-b",
+            sg_two.clone(),
+            "starkandwayne/shield/0.10.5/20170519115548",
+            "starkandwayne/shield/0.10.4/20170419115548",
+            vec!["member-b".to_string()],
+        );
+        canary_store.insert(canary);
         let mut ring = CensusRing::new("member-b".to_string());
         ring.update_from_rumors(
             &service_store,
@@ -663,6 +824,7 @@ mod tests {
             &member_list,
             &service_config_store,
             &service_file_store,
+            &canary_store,
         );
         let census_group_one = ring.census_group_for(&sg_one).unwrap();
         assert!(census_group_one.me().is_none());
@@ -682,5 +844,25 @@ mod tests {
         let members = census_group_two.members();
         assert_eq!(members[0].member_id, "member-a");
         assert_eq!(members[1].member_id, "member-b");
+        let mut zone = BTreeMap::new();
+        zone.insert("zone".to_string(), "us-east-1a".to_string());
+        assert!(members[0].has_tags(&zone));
+        assert!(!members[1].has_tags(&zone));
+        assert!(members[1].has_tags(&BTreeMap::new()));
+        // A member that hasn't reported a health check yet counts as unknown
+        assert_eq!(members[0].health_check, HealthCheck::Unknown);
+        assert_eq!(members[1].health_check, HealthCheck::Ok);
+        // Only the untagged local member is alive, so a bind to the zone has no one to talk to
+        assert!(!census_group_two.has_live_member_with_tags(&zone));
+        assert!(census_group_two.has_live_member_with_tags(&BTreeMap::new()));
+
+        assert!(census_group_one.canary.is_none());
+        let canary = census_group_two.canary.as_ref().unwrap();
+        assert_eq!(canary.status, CanaryStatus::Testing);
+        assert_eq!(canary.previous_pkg, pg_id);
+        assert!(canary.is_canary("member-b"));
+        assert!(!canary.is_canary("member-a"));
+        // Only the local member is known to be alive
+        assert_eq!(census_group_two.pick_canaries(50), vec!["member-b".to_string()]);
     }
 }
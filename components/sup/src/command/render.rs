@@ -0,0 +1,642 @@
+// Copyright (c) 2017 Chef Software Inc. and/or applicable contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Renders the configuration templates and hooks of a package or plan without a running
+//! Supervisor.
+//!
+//! The render context is built from the package's `default.toml`, an optional user TOML file and
+//! a mock census described in JSON, such as:
+//!
+//! ```json
+//! {
+//!   "me": { "member_id": "a", "sys": { "ip": "10.0.0.1" }, "tags": { "zone": "us-east-1a" } },
+//!   "config": { "port": 6380 },
+//!   "groups": {
+//!     "redis.default": {
+//!       "pkg": "core/redis/3.2.4/20170514150022",
+//!       "leader": "b",
+//!       "members": [
+//!         { "member_id": "b", "sys": { "ip": "10.0.0.2" }, "cfg": { "port": 6379 } },
+//!         { "member_id": "c", "health": "confirmed" }
+//!       ]
+//!     }
+//!   },
+//!   "secrets": { "db/password": "hunter2" }
+//! }
+//! ```
+//!
+//! `me` is the member rendering the templates and `config` is the configuration applied to its
+//! service group with `hab config apply`. `secrets` are the values returned by the `secret`
+//! helper. Every file under `config/` and `hooks/` is rendered into
+//! the output directory. Templates are rendered in strict mode, so a variable missing from the
+//! context is reported with the file and line it's used on, just like a syntax error.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{Read, Write};
+use std::net::{IpAddr, Ipv4Addr};
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Arc;
+
+use butterfly::member::{Health, Member, MemberList};
+use butterfly::rumor::RumorStore;
+use butterfly::rumor::election::Election as ElectionRumor;
+use butterfly::rumor::service::Service as ServiceRumor;
+use hcore::fs::FS_ROOT_PATH;
+use hcore::package::{PackageIdent, PackageInstall};
+use hcore::service::ServiceGroup;
+use serde_json;
+use toml;
+
+use VERSION;
+use census::CensusRing;
+use config::GossipListenAddr;
+use error::{Error, Result};
+use http_gateway;
+use manager::Sys;
+use manager::service::{Cfg, Pkg, ServiceBind};
+use manager::service::secrets::{self, SecretProvider, Secrets};
+use templating::{RenderContext, TemplateRenderer};
+
+/// Our output key
+static LOGKEY: &'static str = "RD";
+/// Member id of the rendering member when the census doesn't name it.
+const LOCAL_MEMBER_ID: &'static str = "render";
+/// Release given to packages rendered from a plan, so the output doesn't change between runs.
+const PLAN_RELEASE: &'static str = "00000000000000";
+/// Directories of a package which hold templates, rendered into directories of the same name.
+const TEMPLATE_DIRS: &'static [&'static str] = &["config", "hooks"];
+
+/// The census the templates are rendered against.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct MockCensus {
+    /// The member rendering the templates.
+    pub me: MockMember,
+    /// Configuration applied to the rendered service's group.
+    pub config: Option<toml::value::Table>,
+    /// Members of service groups, keyed by service group. The rendered service's own group may
+    /// be listed to give it peers.
+    pub groups: HashMap<String, MockGroup>,
+    /// Values of the secrets read with the `secret` helper, keyed by path.
+    pub secrets: HashMap<String, String>,
+}
+
+/// Provides the secrets listed by the mock census.
+#[derive(Debug)]
+struct MockSecrets(HashMap<String, String>);
+
+impl SecretProvider for MockSecrets {
+    fn fetch(&self, path: &str) -> Result<String> {
+        self.0.get(path).cloned().ok_or_else(|| {
+            sup_error!(Error::SecretNotFound(path.to_string()))
+        })
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct MockGroup {
+    /// Package of the group's members which don't name their own.
+    pub pkg: Option<String>,
+    /// Member id of the elected leader, if any.
+    pub leader: Option<String>,
+    pub members: Vec<MockMember>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct MockMember {
+    pub member_id: String,
+    pub pkg: Option<String>,
+    /// One of `alive`, `suspect`, `confirmed` or `departed`; members are alive by default.
+    pub health: Option<String>,
+    pub sys: MockSys,
+    pub tags: BTreeMap<String, String>,
+    /// The configuration the member's package exports.
+    pub cfg: toml::value::Table,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct MockSys {
+    pub ip: Option<IpAddr>,
+    pub hostname: Option<String>,
+    pub gossip_ip: Option<IpAddr>,
+    pub gossip_port: Option<u16>,
+    pub http_gateway_ip: Option<IpAddr>,
+    pub http_gateway_port: Option<u16>,
+    pub permanent: bool,
+}
+
+impl MockMember {
+    fn member_id(&self) -> &str {
+        if self.member_id.is_empty() {
+            LOCAL_MEMBER_ID
+        } else {
+            &self.member_id
+        }
+    }
+
+    fn health(&self) -> Option<Health> {
+        match self.health.as_ref().map(|h| h.to_lowercase()) {
+            None => Some(Health::Alive),
+            Some(health) => {
+                match health.as_str() {
+                    "alive" => Some(Health::Alive),
+                    "suspect" => Some(Health::Suspect),
+                    "confirmed" => Some(Health::Confirmed),
+                    "departed" => Some(Health::Departed),
+                    _ => None,
+                }
+            }
+        }
+    }
+
+    /// The member's system information, falling back on the defaults of a local Supervisor.
+    fn to_sys(&self) -> Sys {
+        let gossip = GossipListenAddr::default();
+        let http = http_gateway::ListenAddr::default();
+        Sys {
+            version: VERSION.to_string(),
+            member_id: self.member_id().to_string(),
+            ip: self.sys.ip.unwrap_or(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1))),
+            hostname: self.sys.hostname.clone().unwrap_or("localhost".to_string()),
+            gossip_ip: self.sys.gossip_ip.unwrap_or(gossip.ip()),
+            gossip_port: self.sys.gossip_port.unwrap_or(gossip.port()),
+            http_gateway_ip: self.sys.http_gateway_ip.unwrap_or(http.ip()),
+            http_gateway_port: self.sys.http_gateway_port.unwrap_or(http.port()),
+            permanent: self.sys.permanent,
+            tags: self.tags.clone(),
+        }
+    }
+}
+
+/// A template which failed to render, with the location of the problem when it's known.
+#[derive(Debug)]
+pub struct RenderFailure {
+    pub file: PathBuf,
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+    pub reason: String,
+}
+
+impl RenderFailure {
+    fn new<T: fmt::Display>(file: &Path, reason: T) -> Self {
+        RenderFailure {
+            file: file.to_path_buf(),
+            line: None,
+            column: None,
+            reason: reason.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for RenderFailure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (self.line, self.column) {
+            (Some(line), Some(column)) => {
+                write!(f, "{}:{}:{}: {}", self.file.display(), line, column, self.reason)
+            }
+            (Some(line), None) => write!(f, "{}:{}: {}", self.file.display(), line, self.reason),
+            _ => write!(f, "{}: {}", self.file.display(), self.reason),
+        }
+    }
+}
+
+/// Renders the templates of an installed package, or of the plan in the given directory, into
+/// `output`.
+pub fn start(
+    pkg_ident_or_plan: &str,
+    group: &str,
+    user_toml: Option<&Path>,
+    census: Option<&Path>,
+    binds: &[ServiceBind],
+    output: &Path,
+) -> Result<()> {
+    let pkg = load_pkg(pkg_ident_or_plan)?;
+    let mock = match census {
+        Some(path) => read_census(path)?,
+        None => MockCensus::default(),
+    };
+    let failures = render(&pkg, group, user_toml, &mock, binds, output)?;
+    for failure in failures.iter() {
+        outputln!("{}", failure);
+    }
+    if failures.is_empty() {
+        outputln!("Rendered {} into {}", pkg.ident, output.display());
+        Ok(())
+    } else {
+        Err(sup_error!(Error::RenderFailed(failures.len())))
+    }
+}
+
+/// Renders every template of the package into `output` and returns the templates which failed.
+pub fn render(
+    pkg: &Pkg,
+    group: &str,
+    user_toml: Option<&Path>,
+    mock: &MockCensus,
+    binds: &[ServiceBind],
+    output: &Path,
+) -> Result<Vec<RenderFailure>> {
+    let service_group = ServiceGroup::new(None, &pkg.name, group, None)?;
+    let sys = mock.me.to_sys();
+    let mut cfg = Cfg::new(pkg, None)?;
+    // Only the given user TOML applies, never one lying around on the rendering machine.
+    cfg.user = match user_toml {
+        Some(path) => Some(read_toml(path)?),
+        None => None,
+    };
+    cfg.gossip = mock.config.clone().map(toml::Value::Table);
+    cfg.validate()?;
+    let census = census_ring(pkg, &service_group, &sys, &cfg, mock)?;
+    let ctx = RenderContext::new(&service_group, &sys, pkg, &cfg, &census, binds.iter());
+    let data = serde_json::to_value(&ctx).map_err(|e| {
+        sup_error!(Error::RenderContextSerialization(e))
+    })?;
+    let mock_secrets = MockSecrets(mock.secrets.clone());
+    let _registration = secrets::Registration::new(
+        &service_group,
+        Arc::new(Secrets::new(Box::new(mock_secrets))),
+    );
+
+    let mut failures = Vec::new();
+    for dir in TEMPLATE_DIRS {
+        let templates_path = pkg.path.join(dir);
+        if !templates_path.is_dir() {
+            continue;
+        }
+        let output_path = output.join(dir);
+        fs::create_dir_all(&output_path)?;
+        for entry in fs::read_dir(&templates_path)? {
+            let entry = entry?;
+            if !entry.file_type()?.is_file() {
+                continue;
+            }
+            let file = Path::new(dir).join(entry.file_name());
+            match render_file(&entry.path(), &file, &data) {
+                Ok(rendered) => {
+                    let mut out = File::create(output_path.join(entry.file_name()))?;
+                    out.write_all(rendered.as_bytes())?;
+                }
+                Err(failure) => failures.push(failure),
+            }
+        }
+    }
+    failures.sort_by(|a, b| a.file.cmp(&b.file));
+    Ok(failures)
+}
+
+fn render_file(
+    path: &Path,
+    file: &Path,
+    data: &serde_json::Value,
+) -> ::std::result::Result<String, RenderFailure> {
+    let mut template = String::new();
+    File::open(path)
+        .and_then(|mut f| f.read_to_string(&mut template))
+        .map_err(|e| RenderFailure::new(file, e))?;
+    let name = file.to_string_lossy().into_owned();
+    let mut renderer = TemplateRenderer::new();
+    renderer.set_strict_mode(true);
+    renderer.register_template_string(&name, template).map_err(
+        |e| {
+            RenderFailure {
+                file: file.to_path_buf(),
+                line: e.line_no,
+                column: e.column_no,
+                reason: e.reason.to_string(),
+            }
+        },
+    )?;
+    renderer.deref().render(&name, data).map_err(|e| {
+        RenderFailure {
+            file: file.to_path_buf(),
+            line: e.line_no,
+            column: e.column_no,
+            reason: e.desc,
+        }
+    })
+}
+
+/// Loads an installed package, or describes the package of the plan in the given directory.
+fn load_pkg(pkg_ident_or_plan: &str) -> Result<Pkg> {
+    let path = Path::new(pkg_ident_or_plan);
+    if path.is_dir() {
+        let plan_path = plan_dir(path);
+        let ident = plan_ident(&plan_path)?;
+        return Pkg::from_plan(ident, plan_path);
+    }
+    let ident = PackageIdent::from_str(pkg_ident_or_plan)?;
+    let package = PackageInstall::load(&ident, Some(Path::new(&*FS_ROOT_PATH)))?;
+    Pkg::from_install(package)
+}
+
+/// A project keeps its plan in a `habitat` directory, if it's not next to the plan itself.
+fn plan_dir(path: &Path) -> PathBuf {
+    let habitat = path.join("habitat");
+    if find_plan(&habitat).is_some() {
+        habitat
+    } else {
+        path.to_path_buf()
+    }
+}
+
+fn find_plan(path: &Path) -> Option<PathBuf> {
+    ["plan.sh", "plan.ps1"].iter().map(|f| path.join(f)).find(
+        |p| p.is_file(),
+    )
+}
+
+/// Reads the origin, name and version of the package from the plan. A version computed at build
+/// time can't be known here and is rendered as `0.0.0`.
+fn plan_ident(plan_path: &Path) -> Result<PackageIdent> {
+    let plan = find_plan(plan_path).ok_or(sup_error!(Error::BadPlan(
+        plan_path.to_path_buf(),
+        "no plan.sh or plan.ps1 found".to_string(),
+    )))?;
+    let mut body = String::new();
+    File::open(&plan)?.read_to_string(&mut body)?;
+    let mut vars = HashMap::new();
+    for line in body.lines() {
+        let mut parts = line.trim().trim_left_matches('$').splitn(2, '=');
+        if let (Some(key), Some(value)) = (parts.next(), parts.next()) {
+            let value = value.trim().trim_matches(|c: char| c == '"' || c == '\'');
+            if !value.is_empty() && !value.contains('$') {
+                vars.entry(key.trim().to_string()).or_insert(value.to_string());
+            }
+        }
+    }
+    let var = |name: &str| {
+        vars.get(name).cloned().ok_or(sup_error!(Error::BadPlan(
+            plan.clone(),
+            format!("{} is not set", name),
+        )))
+    };
+    let version = var("pkg_version").unwrap_or("0.0.0".to_string());
+    Ok(PackageIdent::new(
+        var("pkg_origin")?,
+        var("pkg_name")?,
+        Some(version),
+        Some(PLAN_RELEASE.to_string()),
+    ))
+}
+
+fn read_census(path: &Path) -> Result<MockCensus> {
+    let bad_census = |e: &fmt::Display| {
+        sup_error!(Error::BadRenderCensus(format!("{}, {}", path.display(), e)))
+    };
+    let file = File::open(path).map_err(|e| bad_census(&e))?;
+    serde_json::from_reader(file).map_err(|e| bad_census(&e))
+}
+
+fn read_toml(path: &Path) -> Result<toml::Value> {
+    let mut body = String::new();
+    File::open(path)?.read_to_string(&mut body)?;
+    let table = toml::de::from_str(&body).map_err(
+        |e| sup_error!(Error::TomlParser(e)),
+    )?;
+    Ok(toml::Value::Table(table))
+}
+
+/// Builds the census from the rumors and memberships the mock census stands for, exactly as a
+/// Supervisor builds it from gossip.
+fn census_ring(
+    pkg: &Pkg,
+    service_group: &ServiceGroup,
+    sys: &Sys,
+    cfg: &Cfg,
+    mock: &MockCensus,
+) -> Result<CensusRing> {
+    let bad_census = |reason: String| sup_error!(Error::BadRenderCensus(reason));
+    let service_store = RumorStore::default();
+    let election_store = RumorStore::default();
+    let member_list = MemberList::new();
+
+    let exported = cfg.to_exported(pkg)?;
+    service_store.insert(ServiceRumor::new(
+        sys.member_id.clone(),
+        &pkg.ident,
+        service_group,
+        &sys.as_sys_info(),
+        Some(&exported),
+    ));
+    insert_member(&member_list, &mock.me, sys)?;
+
+    for (name, group) in mock.groups.iter() {
+        let sg = ServiceGroup::from_str(name).map_err(|e| bad_census(e.to_string()))?;
+        for member in group.members.iter() {
+            let ident = match member.pkg.as_ref().or(group.pkg.as_ref()) {
+                Some(ident) => PackageIdent::from_str(ident)?,
+                None => {
+                    PackageIdent::new("render", sg.service(), Some("0.0.0"), Some(PLAN_RELEASE))
+                }
+            };
+            if !ident.fully_qualified() || ident.name != sg.service() {
+                return Err(bad_census(format!(
+                    "{} is not a fully qualified package of {}",
+                    ident,
+                    sg.service()
+                )));
+            }
+            let member_sys = member.to_sys();
+            service_store.insert(ServiceRumor::new(
+                member.member_id(),
+                &ident,
+                &sg,
+                &member_sys.as_sys_info(),
+                Some(&member.cfg),
+            ));
+            insert_member(&member_list, member, &member_sys)?;
+        }
+        if let Some(ref leader) = group.leader {
+            let known = group.members.iter().any(|m| m.member_id() == leader.as_str()) ||
+                (&sg == service_group && leader == &sys.member_id);
+            if !known {
+                return Err(bad_census(
+                    format!("leader {} is not a member of {}", leader, sg),
+                ));
+            }
+            let mut election = ElectionRumor::new(leader.as_str(), sg.clone(), 0);
+            election.finish();
+            election_store.insert(election);
+        }
+    }
+
+    let mut ring = CensusRing::new(sys.member_id.clone());
+    ring.update_from_rumors(
+        &service_store,
+        &election_store,
+        &RumorStore::default(),
+        &member_list,
+        &RumorStore::default(),
+        &RumorStore::default(),
+        &RumorStore::default(),
+    );
+    Ok(ring)
+}
+
+fn insert_member(member_list: &MemberList, mock: &MockMember, sys: &Sys) -> Result<()> {
+    let health = mock.health().ok_or(sup_error!(Error::BadRenderCensus(format!(
+        "unknown health {} of member {}",
+        mock.health.as_ref().unwrap(),
+        mock.member_id()
+    ))))?;
+    let mut member = Member::default();
+    member.set_id(mock.member_id().to_string());
+    member.set_address(sys.gossip_ip.to_string());
+    member.set_gossip_port(sys.gossip_port as i32);
+    member_list.insert(member, health);
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs::{self, File};
+    use std::io::{Read, Write};
+    use std::path::Path;
+    use std::str::FromStr;
+
+    use hcore::package::PackageIdent;
+    use serde_json;
+    use tempdir::TempDir;
+
+    use super::*;
+    use manager::service::{Pkg, ServiceBind};
+
+    fn write_file(path: &Path, body: &str) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        File::create(path).unwrap().write_all(body.as_bytes()).unwrap();
+    }
+
+    fn read_file(path: &Path) -> String {
+        let mut body = String::new();
+        File::open(path).unwrap().read_to_string(&mut body).unwrap();
+        body
+    }
+
+    fn plan(tmp: &TempDir) -> Pkg {
+        let plan = tmp.path().join("plan");
+        write_file(
+            &plan.join("plan.sh"),
+            "pkg_origin=core\npkg_name=\"app\"\npkg_version=1.2.3\n",
+        );
+        write_file(&plan.join("default.toml"), "port = 8080\n");
+        write_file(
+            &plan.join("config/app.conf"),
+            "port={{cfg.port}}\n\
+             {{#each bind.db.members}}db={{sys.ip}}\n{{/each~}}",
+        );
+        write_file(
+            &plan.join("hooks/run"),
+            "#!/bin/sh\nexec app --port {{cfg.prot}}\n",
+        );
+        let ident = plan_ident(&plan_dir(&plan)).unwrap();
+        Pkg::from_plan(ident, plan).unwrap()
+    }
+
+    #[test]
+    fn plan_ident_from_plan_sh() {
+        let tmp = TempDir::new("render").unwrap();
+        let pkg = plan(&tmp);
+        assert_eq!(
+            pkg.ident,
+            PackageIdent::new("core", "app", Some("1.2.3"), Some(PLAN_RELEASE))
+        );
+    }
+
+    #[test]
+    fn render_reports_missing_variables_with_their_line() {
+        let tmp = TempDir::new("render").unwrap();
+        let pkg = plan(&tmp);
+        let user_toml = tmp.path().join("user.toml");
+        write_file(&user_toml, "port = 9090\n");
+        let mock: MockCensus = serde_json::from_str(
+            r#"{
+                "groups": {
+                    "db.default": {
+                        "members": [
+                            { "member_id": "b", "sys": { "ip": "10.0.0.2" } },
+                            { "member_id": "c", "sys": { "ip": "10.0.0.3" } }
+                        ]
+                    }
+                }
+            }"#,
+        ).unwrap();
+        let binds = vec![ServiceBind::from_str("db:db.default").unwrap()];
+        let output = tmp.path().join("results");
+
+        let failures = render(&pkg, "default", Some(&user_toml), &mock, &binds, &output).unwrap();
+
+        let config = read_file(&output.join("config/app.conf"));
+        assert!(config.starts_with("port=9090\n"));
+        assert!(config.contains("db=10.0.0.2\n"));
+        assert!(config.contains("db=10.0.0.3\n"));
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].file, Path::new("hooks").join("run"));
+        assert_eq!(failures[0].line, Some(2));
+        assert!(!output.join("hooks/run").exists());
+    }
+
+    #[test]
+    fn unknown_leader_is_rejected() {
+        let tmp = TempDir::new("render").unwrap();
+        let pkg = plan(&tmp);
+        let mock: MockCensus = serde_json::from_str(
+            r#"{
+                "groups": {
+                    "db.default": { "leader": "z", "members": [{ "member_id": "b" }] }
+                }
+            }"#,
+        ).unwrap();
+        let output = tmp.path().join("results");
+
+        assert!(render(&pkg, "default", None, &mock, &[], &output).is_err());
+    }
+
+    #[test]
+    fn render_reads_secrets_from_census() {
+        let tmp = TempDir::new("render").unwrap();
+        let pkg = plan(&tmp);
+        write_file(
+            &pkg.path.join("config/secret.conf"),
+            "password={{secret \"db/password\"}}\n",
+        );
+        write_file(
+            &pkg.path.join("config/token.conf"),
+            "token={{secret \"api/token\"}}\n",
+        );
+        let mock: MockCensus = serde_json::from_str(
+            r#"{ "secrets": { "db/password": "hunter2" } }"#,
+        ).unwrap();
+        let output = tmp.path().join("results");
+
+        let failures = render(&pkg, "secrets", None, &mock, &[], &output).unwrap();
+
+        let files: Vec<String> = failures
+            .iter()
+            .map(|f| f.file.to_string_lossy().into_owned())
+            .collect();
+        assert!(!files.contains(&"config/secret.conf".to_string()));
+        assert!(files.contains(&"config/token.conf".to_string()));
+        assert_eq!(
+            read_file(&output.join("config/secret.conf")),
+            "password=hunter2\n"
+        );
+    }
+}
@@ -0,0 +1,342 @@
+// Copyright (c) 2017 Chef Software Inc. and/or applicable contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Local control API of the Supervisor.
+//!
+//! The Supervisor listens on a Unix socket in its state directory for the commands of the `hab
+//! svc` subcommands. Every connection carries a single request and its reply, each encoded as
+//! one line of JSON. Unlike writing spec files for the `SpecWatcher` to pick up, this lets the
+//! CLI know whether the Supervisor accepted a command and why it didn't.
+
+use manager::service::{ProcessState, ServiceSpec};
+
+#[cfg(unix)]
+pub use self::unix::{send, Server};
+
+#[derive(Debug, Deserialize, Serialize)]
+pub enum CtlRequest {
+    /// Load a service, replacing its spec if it is already loaded and `force` is set.
+    Load { spec: ServiceSpec, force: bool },
+    /// Unload the service running the given package.
+    Unload { ident: String },
+    /// Retrieve the state of the process of the service running the given package.
+    Status { ident: String },
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub enum CtlReply {
+    Ok,
+    /// The command was rejected, with the reason why.
+    Err(String),
+    /// The state of a service's process, `None` if the service isn't running.
+    Status(Option<ProcessState>),
+}
+
+#[cfg(unix)]
+mod unix {
+    use std::fs;
+    use std::io::{BufRead, BufReader, Write};
+    use std::os::unix::fs::PermissionsExt;
+    use std::os::unix::net::{UnixListener, UnixStream};
+    use std::path::{Path, PathBuf};
+    use std::str::FromStr;
+    use std::sync::Arc;
+    use std::thread::{self, JoinHandle};
+    use std::time::Duration;
+
+    use hcore::package::PackageIdent;
+    use serde_json;
+
+    use error::{Error, Result};
+    use manager::{Manager, ManagerConfig};
+    use manager::service::ServiceSpec;
+    use super::{CtlReply, CtlRequest};
+    use util;
+
+    static LOGKEY: &'static str = "CG";
+    /// How long a connection may take to send its request before it is dropped.
+    const REQUEST_TIMEOUT_SECS: u64 = 5;
+
+    pub struct Server {
+        cfg: Arc<ManagerConfig>,
+        socket_path: PathBuf,
+    }
+
+    impl Server {
+        pub fn new(cfg: Arc<ManagerConfig>, socket_path: PathBuf) -> Self {
+            Server {
+                cfg: cfg,
+                socket_path: socket_path,
+            }
+        }
+
+        pub fn start(self) -> Result<JoinHandle<()>> {
+            // A socket left behind by a previous Supervisor would prevent binding a new one.
+            if self.socket_path.exists() {
+                fs::remove_file(&self.socket_path).map_err(|e| {
+                    sup_error!(Error::CtlGateway(e))
+                })?;
+            }
+            let listener = UnixListener::bind(&self.socket_path).map_err(|e| {
+                sup_error!(Error::CtlGateway(e))
+            })?;
+            // Only the user running the Supervisor may control it.
+            fs::set_permissions(&self.socket_path, fs::Permissions::from_mode(0o600))
+                .map_err(|e| sup_error!(Error::CtlGateway(e)))?;
+            let handle = thread::Builder::new()
+                .name("ctl-gateway".to_string())
+                .spawn(move || {
+                    for conn in listener.incoming() {
+                        match conn {
+                            Ok(stream) => {
+                                if let Err(err) = self.serve(stream) {
+                                    debug!("Unable to serve ctl-gateway request, {}", err);
+                                }
+                            }
+                            Err(err) => debug!("Unable to accept ctl-gateway connection, {}", err),
+                        }
+                    }
+                })?;
+            Ok(handle)
+        }
+
+        fn serve(&self, mut stream: UnixStream) -> Result<()> {
+            stream
+                .set_read_timeout(Some(Duration::from_secs(REQUEST_TIMEOUT_SECS)))
+                .map_err(|e| sup_error!(Error::CtlGateway(e)))?;
+            let mut line = String::new();
+            BufReader::new(&stream).read_line(&mut line).map_err(|e| {
+                sup_error!(Error::CtlGateway(e))
+            })?;
+            let reply = match serde_json::from_str(&line) {
+                Ok(request) => handle(&self.cfg, request),
+                Err(err) => CtlReply::Err(format!("Malformed request, {}", err)),
+            };
+            write_message(&mut stream, &reply)
+        }
+    }
+
+    /// Send a request to the Supervisor listening on the given socket and wait for its reply.
+    pub fn send<P>(socket_path: P, request: &CtlRequest) -> Result<CtlReply>
+    where
+        P: AsRef<Path>,
+    {
+        let mut stream = UnixStream::connect(socket_path.as_ref()).map_err(|e| {
+            sup_error!(Error::CtlGateway(e))
+        })?;
+        write_message(&mut stream, request)?;
+        let mut line = String::new();
+        BufReader::new(&stream).read_line(&mut line).map_err(|e| {
+            sup_error!(Error::CtlGateway(e))
+        })?;
+        serde_json::from_str(&line).map_err(|e| sup_error!(Error::CtlMalformedReply(e)))
+    }
+
+    fn write_message<T>(stream: &mut UnixStream, message: &T) -> Result<()>
+    where
+        T: ::serde::Serialize,
+    {
+        let mut line = serde_json::to_string(message).expect("Control messages always serialize");
+        line.push('\n');
+        stream.write_all(line.as_bytes()).map_err(|e| {
+            sup_error!(Error::CtlGateway(e))
+        })
+    }
+
+    fn handle(cfg: &ManagerConfig, request: CtlRequest) -> CtlReply {
+        let result = match request {
+            CtlRequest::Load { spec, force } => load(cfg, spec, force),
+            CtlRequest::Unload { ident } => unload(cfg, &ident),
+            CtlRequest::Status { ident } => return status(cfg, &ident),
+        };
+        match result {
+            Ok(()) => CtlReply::Ok,
+            Err(err) => CtlReply::Err(err.to_string()),
+        }
+    }
+
+    fn load(cfg: &ManagerConfig, spec: ServiceSpec, force: bool) -> Result<()> {
+        let package = match util::pkg::installed(&spec.ident) {
+            Some(package) => package,
+            None => return Err(sup_error!(Error::PackageNotFound(spec.ident.clone()))),
+        };
+        spec.validate(&package)?;
+        save(cfg, &spec, force)
+    }
+
+    /// Writes the spec file of a service, unless the service is already loaded and `force` isn't
+    /// set.
+    fn save(cfg: &ManagerConfig, spec: &ServiceSpec, force: bool) -> Result<()> {
+        if Manager::spec_path_for(cfg, spec).is_file() && !force {
+            return Err(sup_error!(Error::ServiceLoaded(spec.ident.clone())));
+        }
+        Manager::save_spec_for(cfg, spec)
+    }
+
+    fn unload(cfg: &ManagerConfig, ident: &str) -> Result<()> {
+        let ident = PackageIdent::from_str(ident)?;
+        let file = Manager::spec_path_for(cfg, &ServiceSpec::default_for(ident));
+        fs::remove_file(&file).map_err(|err| {
+            sup_error!(Error::ServiceSpecFileIO(file.clone(), err))
+        })?;
+        outputln!("Unloading {:?}", file);
+        Ok(())
+    }
+
+    fn status(cfg: &ManagerConfig, ident: &str) -> CtlReply {
+        let ident = match PackageIdent::from_str(ident) {
+            Ok(ident) => ident,
+            Err(err) => return CtlReply::Err(err.to_string()),
+        };
+        match Manager::service_status(cfg, &ident) {
+            Ok(status) => CtlReply::Status(Some(status.process.state)),
+            Err(_) => CtlReply::Status(None),
+        }
+    }
+
+    #[cfg(test)]
+    mod test {
+        use tempdir::TempDir;
+
+        use manager::service::DesiredState;
+        use super::*;
+
+        fn cfg(tmpdir: &TempDir) -> ManagerConfig {
+            let mut cfg = ManagerConfig::default();
+            cfg.set_state_path(tmpdir.path());
+            fs::create_dir_all(tmpdir.path().join("specs")).unwrap();
+            cfg
+        }
+
+        fn spec(ident: &str) -> ServiceSpec {
+            ServiceSpec::default_for(PackageIdent::from_str(ident).unwrap())
+        }
+
+        fn start(cfg: &ManagerConfig) -> PathBuf {
+            let socket_path = Manager::ctl_socket_path(cfg);
+            Server::new(Arc::new(cfg.clone()), socket_path.clone())
+                .start()
+                .unwrap();
+            socket_path
+        }
+
+        #[test]
+        fn requests_and_replies_round_trip_as_json() {
+            let request = CtlRequest::Load {
+                spec: spec("core/redis"),
+                force: false,
+            };
+            let json = serde_json::to_string(&request).unwrap();
+            match serde_json::from_str(&json).unwrap() {
+                CtlRequest::Load { spec, force } => {
+                    assert_eq!(spec.ident, PackageIdent::from_str("core/redis").unwrap());
+                    assert!(!force);
+                }
+                request => panic!("Unexpected request {:?}", request),
+            }
+
+            let json = serde_json::to_string(&CtlReply::Err("nope".to_string())).unwrap();
+            match serde_json::from_str(&json).unwrap() {
+                CtlReply::Err(reason) => assert_eq!(reason, "nope"),
+                reply => panic!("Unexpected reply {:?}", reply),
+            }
+        }
+
+        #[test]
+        fn save_replaces_a_loaded_spec_only_when_forced() {
+            let tmpdir = TempDir::new("ctl-gateway").unwrap();
+            let cfg = cfg(&tmpdir);
+            let mut spec = spec("core/redis");
+            save(&cfg, &spec, false).unwrap();
+
+            spec.desired_state = DesiredState::Down;
+            assert!(save(&cfg, &spec, false).is_err());
+            let saved = ServiceSpec::from_file(Manager::spec_path_for(&cfg, &spec)).unwrap();
+            assert_eq!(saved.desired_state, DesiredState::Up);
+
+            save(&cfg, &spec, true).unwrap();
+            let saved = ServiceSpec::from_file(Manager::spec_path_for(&cfg, &spec)).unwrap();
+            assert_eq!(saved.desired_state, DesiredState::Down);
+        }
+
+        #[test]
+        fn load_rejects_a_package_which_is_not_installed() {
+            let tmpdir = TempDir::new("ctl-gateway").unwrap();
+            let cfg = cfg(&tmpdir);
+            let request = CtlRequest::Load {
+                spec: spec("acme/not-installed/1.0.0/20170101000000"),
+                force: true,
+            };
+
+            match handle(&cfg, request) {
+                CtlReply::Err(_) => (),
+                reply => panic!("Unexpected reply {:?}", reply),
+            }
+        }
+
+        #[test]
+        fn unload_removes_the_spec_file() {
+            let tmpdir = TempDir::new("ctl-gateway").unwrap();
+            let cfg = cfg(&tmpdir);
+            let socket_path = start(&cfg);
+            let spec = spec("core/redis");
+            Manager::save_spec_for(&cfg, &spec).unwrap();
+            let request = CtlRequest::Unload { ident: "core/redis".to_string() };
+
+            match send(&socket_path, &request).unwrap() {
+                CtlReply::Ok => (),
+                reply => panic!("Unexpected reply {:?}", reply),
+            }
+            assert!(!Manager::spec_path_for(&cfg, &spec).exists());
+            match send(&socket_path, &request).unwrap() {
+                CtlReply::Err(_) => (),
+                reply => panic!("Unexpected reply {:?}", reply),
+            }
+        }
+
+        #[test]
+        fn status_of_a_service_which_is_not_running() {
+            let tmpdir = TempDir::new("ctl-gateway").unwrap();
+            let cfg = cfg(&tmpdir);
+            let socket_path = start(&cfg);
+            let request = CtlRequest::Status { ident: "core/redis".to_string() };
+
+            match send(&socket_path, &request).unwrap() {
+                CtlReply::Status(None) => (),
+                reply => panic!("Unexpected reply {:?}", reply),
+            }
+            let request = CtlRequest::Status { ident: "not/a/valid/ident/at/all".to_string() };
+            match send(&socket_path, &request).unwrap() {
+                CtlReply::Err(_) => (),
+                reply => panic!("Unexpected reply {:?}", reply),
+            }
+        }
+
+        #[test]
+        fn malformed_request_is_rejected() {
+            let tmpdir = TempDir::new("ctl-gateway").unwrap();
+            let cfg = cfg(&tmpdir);
+            let socket_path = start(&cfg);
+            let mut stream = UnixStream::connect(&socket_path).unwrap();
+            stream.write_all(b"{\"Reboot\":{}}\n").unwrap();
+            let mut line = String::new();
+            BufReader::new(&stream).read_line(&mut line).unwrap();
+
+            match serde_json::from_str(&line).unwrap() {
+                CtlReply::Err(reason) => assert!(reason.starts_with("Malformed request")),
+                reply => panic!("Unexpected reply {:?}", reply),
+            }
+        }
+    }
+}
@@ -108,12 +108,24 @@ pub enum Error {
     BadDataFile(PathBuf, io::Error),
     BadDataPath(PathBuf, io::Error),
     BadDesiredState(String),
+    BadConfigSchema(PathBuf, String),
     BadElectionStatus(String),
+    BadHttpAuthTokenFile(PathBuf, String),
     BadPackage(PackageInstall, hcore::error::Error),
+    BadPlan(PathBuf, String),
+    BadRenderCensus(String),
+    BadSecretsUrl(String),
     BadSpecsPath(PathBuf, io::Error),
     BadStartStyle(String),
+    BadRestartPolicy(String),
+    BadResourceLimit(String),
     BadEnvConfig(String),
+    BadLogFormat(String),
     ButterflyError(butterfly::error::Error),
+    CtlGateway(io::Error),
+    CtlMalformedReply(serde_json::Error),
+    CtlRejected(String),
+    CtlWaitTimeout(package::PackageIdent, String),
     DepotClient(depot_client::Error),
     EnvJoinPathsError(env::JoinPathsError),
     ExecCommandNotFound(String),
@@ -121,13 +133,16 @@ pub enum Error {
     FileWatcherFileIsRoot,
     HabitatCommon(common::Error),
     HabitatCore(hcore::Error),
+    HttpGatewayTls(String),
     TemplateFileError(handlebars::TemplateFileError),
     TemplateRenderError(handlebars::RenderError),
     InvalidBinding(String),
     InvalidBinds(Vec<String>),
     InvalidCompositeBinding(String),
+    InvalidConfig(String, Vec<String>),
     InvalidKeyParameter(String),
     InvalidPidFile,
+    InvalidTag(String),
     InvalidTopology(String),
     InvalidUpdateStrategy(String),
     Io(io::Error),
@@ -149,7 +164,10 @@ pub enum Error {
     ProcessLocked(Pid),
     ProcessLockIO(PathBuf, io::Error),
     RecvError(mpsc::RecvError),
+    RenderFailed(usize),
     RenderContextSerialization(serde_json::Error),
+    SecretNotFound(String),
+    SecretsProvider(String),
     ServiceDeserializationError(serde_json::Error),
     ServiceLoaded(package::PackageIdent),
     ServiceNotLoaded(package::PackageIdent),
@@ -203,8 +221,28 @@ impl fmt::Display for SupError {
             Error::BadDesiredState(ref state) => {
                 format!("Unknown service desired state style '{}'", state)
             }
+            Error::BadHttpAuthTokenFile(ref path, ref err) => {
+                format!(
+                    "Unable to read the HTTP gateway's auth token from {}, {}",
+                    path.display(),
+                    err
+                )
+            }
+            Error::BadConfigSchema(ref path, ref reason) => {
+                format!("Invalid config schema {}, {}", path.display(), reason)
+            }
             Error::BadElectionStatus(ref status) => format!("Unknown election status '{}'", status),
             Error::BadPackage(ref pkg, ref err) => format!("Bad package, {}, {}", pkg, err),
+            Error::BadPlan(ref path, ref reason) => {
+                format!("Unable to read the plan in {}, {}", path.display(), reason)
+            }
+            Error::BadRenderCensus(ref reason) => format!("Invalid census, {}", reason),
+            Error::BadSecretsUrl(ref url) => {
+                format!(
+                    "Unknown secrets provider '{}', expected a file:// or http(s):// URL",
+                    url
+                )
+            }
             Error::BadSpecsPath(ref path, ref err) => {
                 format!(
                     "Unable to create the specs directory '{}' ({})",
@@ -213,16 +251,34 @@ impl fmt::Display for SupError {
                 )
             }
             Error::BadStartStyle(ref style) => format!("Unknown service start style '{}'", style),
+            Error::BadRestartPolicy(ref policy) => {
+                format!("Unknown service restart policy '{}'", policy)
+            }
+            Error::BadResourceLimit(ref limit) => format!("Invalid resource limit '{}'", limit),
             Error::BadEnvConfig(ref varname) => {
                 format!("Unable to find valid TOML or JSON in {} ENVVAR", varname)
             }
+            Error::BadLogFormat(ref format) => format!("Unknown log format '{}'", format),
             Error::ButterflyError(ref err) => format!("Butterfly error: {}", err),
+            Error::CtlGateway(ref err) => format!("Unable to use the control socket, {}", err),
+            Error::CtlMalformedReply(ref err) => {
+                format!("Malformed reply received from the Supervisor, {}", err)
+            }
+            Error::CtlRejected(ref reason) => {
+                format!("The Supervisor rejected the command, {}", reason)
+            }
+            Error::CtlWaitTimeout(ref ident, ref state) => {
+                format!("Timed out waiting for {} to be {}", ident, state)
+            }
             Error::ExecCommandNotFound(ref c) => {
                 format!("`{}' was not found on the filesystem or in PATH", c)
             }
             Error::Permissions(ref err) => format!("{}", err),
             Error::HabitatCommon(ref err) => format!("{}", err),
             Error::HabitatCore(ref err) => format!("{}", err),
+            Error::HttpGatewayTls(ref err) => {
+                format!("Unable to load the HTTP gateway's TLS certificate or key, {}", err)
+            }
             Error::TemplateFileError(ref err) => format!("{:?}", err),
             Error::TemplateRenderError(ref err) => format!("{}", err),
             Error::DepotClient(ref err) => format!("{}", err),
@@ -231,8 +287,10 @@ impl fmt::Display for SupError {
             Error::FileWatcherFileIsRoot => format!("Watched file is root"),
             Error::InvalidBinding(ref binding) => {
                 format!(
-                    "Invalid binding \"{}\", must be of the form <NAME>:<SERVICE_GROUP> where \
-                         <NAME> is a service name and <SERVICE_GROUP> is a valid service group",
+                    "Invalid binding \"{}\", must be of the form \
+                     <NAME>:<SERVICE_GROUP>[?<KEY>=<VALUE>[&<KEY>=<VALUE>...]] where <NAME> is a \
+                     service name, <SERVICE_GROUP> is a valid service group and each \
+                     <KEY>=<VALUE> is a tag its providers must have",
                     binding
                 )
             }
@@ -245,10 +303,24 @@ impl fmt::Display for SupError {
                     binding
                 )
             }
+            Error::InvalidConfig(ref layer, ref errors) => {
+                format!(
+                    "The {} configuration does not match the package's config schema, {}",
+                    layer,
+                    errors.join(", ")
+                )
+            }
             Error::InvalidKeyParameter(ref e) => {
                 format!("Invalid parameter for key generation: {:?}", e)
             }
             Error::InvalidPidFile => format!("Invalid child process PID file"),
+            Error::InvalidTag(ref t) => {
+                format!(
+                    "Invalid tag \"{}\", must be of the form <KEY>=<VALUE> where both only \
+                     contain letters, digits, '-', '_' and '.'",
+                    t
+                )
+            }
             Error::InvalidTopology(ref t) => format!("Invalid topology: {}", t),
             Error::InvalidUpdateStrategy(ref s) => format!("Invalid update strategy: {}", s),
             Error::Io(ref err) => format!("{}", err),
@@ -298,9 +370,14 @@ impl fmt::Display for SupError {
                 )
             }
             Error::RecvError(ref err) => format!("{}", err),
+            Error::RenderFailed(count) => format!("{} template(s) failed to render", count),
             Error::RenderContextSerialization(ref e) => {
                 format!("Unable to serialize rendering context, {}", e)
             }
+            Error::SecretNotFound(ref path) => format!("Secret '{}' not found", path),
+            Error::SecretsProvider(ref reason) => {
+                format!("Unable to read from the secrets provider, {}", reason)
+            }
             Error::ServiceDeserializationError(ref e) => {
                 format!("Can't deserialize service status: {}", e)
             }
@@ -361,18 +438,31 @@ impl error::Error for SupError {
             Error::Departed => "Supervisor has been manually departed",
             Error::BadDataFile(_, _) => "Unable to read or write to a data file",
             Error::BadDataPath(_, _) => "Unable to read or write to data directory",
+            Error::BadConfigSchema(_, _) => "Invalid config schema",
             Error::BadElectionStatus(_) => "Unknown election status",
             Error::BadDesiredState(_) => "Unknown desired state in service spec",
+            Error::BadHttpAuthTokenFile(_, _) => "Unable to read the HTTP gateway's auth token",
             Error::BadPackage(_, _) => "Package was malformed or contained malformed contents",
+            Error::BadPlan(_, _) => "Unable to read the plan",
+            Error::BadRenderCensus(_) => "Invalid census",
+            Error::BadSecretsUrl(_) => "Unknown secrets provider",
             Error::BadSpecsPath(_, _) => "Unable to create the specs directory",
             Error::BadStartStyle(_) => "Unknown start style in service spec",
+            Error::BadRestartPolicy(_) => "Unknown restart policy in service spec",
+            Error::BadResourceLimit(_) => "Invalid resource limit",
             Error::BadEnvConfig(_) => "Unknown syntax in Env Configuration",
+            Error::BadLogFormat(_) => "Unknown log format",
             Error::ButterflyError(ref err) => err.description(),
+            Error::CtlGateway(_) => "Unable to use the Supervisor's control socket",
+            Error::CtlMalformedReply(_) => "Malformed reply received from the Supervisor",
+            Error::CtlRejected(_) => "The Supervisor rejected the command",
+            Error::CtlWaitTimeout(_, _) => "Timed out waiting for a service to change state",
             Error::ExecCommandNotFound(_) => "Exec command was not found on filesystem or in PATH",
             Error::TemplateFileError(ref err) => err.description(),
             Error::TemplateRenderError(ref err) => err.description(),
             Error::HabitatCommon(ref err) => err.description(),
             Error::HabitatCore(ref err) => err.description(),
+            Error::HttpGatewayTls(_) => "Unable to load the HTTP gateway's TLS certificate or key",
             Error::DepotClient(ref err) => err.description(),
             Error::EnvJoinPathsError(ref err) => err.description(),
             Error::FileNotFound(_) => "File not found",
@@ -382,8 +472,12 @@ impl error::Error for SupError {
                 "Service binds detected that are neither required nor optional package binds"
             }
             Error::InvalidCompositeBinding(_) => "Invalid binding parameter",
+            Error::InvalidConfig(_, _) => {
+                "Configuration does not match the package's config schema"
+            }
             Error::InvalidKeyParameter(_) => "Key parameter error",
             Error::InvalidPidFile => "Invalid child process PID file",
+            Error::InvalidTag(_) => "Invalid tag",
             Error::InvalidTopology(_) => "Invalid topology",
             Error::InvalidUpdateStrategy(_) => "Invalid update strategy",
             Error::Io(ref err) => err.description(),
@@ -413,7 +507,10 @@ impl error::Error for SupError {
             }
             Error::ProcessLockIO(_, _) => "Unable to read or write to a process lock",
             Error::RecvError(_) => "A channel failed to receive a response",
+            Error::RenderFailed(_) => "One or more templates failed to render",
             Error::RenderContextSerialization(_) => "Unable to serialize rendering context",
+            Error::SecretNotFound(_) => "Secret not found",
+            Error::SecretsProvider(_) => "Unable to read from the secrets provider",
             Error::ServiceDeserializationError(_) => "Can't deserialize service status",
             Error::ServiceNotLoaded(_) => "Service status called when service not loaded",
             Error::ServiceLoaded(_) => "Service load or start called when service already loaded",
@@ -13,34 +13,46 @@
 // limitations under the License.
 
 use std::collections::HashMap;
+use std::error;
 use std::fmt;
 use std::fs::File;
+use std::fs;
 use std::io::{self, Read};
 use std::net::{IpAddr, Ipv4Addr, ToSocketAddrs, SocketAddr, SocketAddrV4};
 use std::ops::{Deref, DerefMut};
 use std::option;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::result;
 use std::str::FromStr;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
+use std::sync::mpsc::{self, RecvTimeoutError};
 use std::thread::{self, JoinHandle};
+use std::time::Duration;
 
 use hcore::service::{ApplicationEnvironment, ServiceGroup};
+use hyper_openssl::OpensslServer;
 use iron::prelude::*;
+use iron::middleware::BeforeMiddleware;
 use iron::{headers, status, typemap};
 use iron::modifiers::Header;
+use openssl::memcmp;
 use persistent;
 use prometheus::{self, CounterVec, HistogramVec, TextEncoder, Encoder};
 use router::Router;
 use serde_json::{self, Value as Json};
+use toml;
+use util;
 
 use error::{Result, Error, SupError};
-use manager;
-use manager::service::HealthCheck;
+use manager::{self, CommandReply, CommandSender, Manager, ManagerConfig, ServiceCommand};
+use manager::service::{Cfg, HealthCheck, PackageConfigPaths, Pkg, ServiceSpec, StartStyle};
 use manager::service::hooks::{self, HealthCheckHook};
 
 static LOGKEY: &'static str = "HG";
 const APIDOCS: &'static str = include_str!(concat!(env!("OUT_DIR"), "/api.html"));
+/// How long to wait for the Manager to carry out a command. It checks for commands once a
+/// second, but starting a service may run its init hook first.
+const COMMAND_TIMEOUT_SECS: u64 = 30;
 
 // Simple macro to encapsulate the HTTP metrics for each endpoint
 macro_rules! with_metrics {
@@ -135,16 +147,40 @@ impl fmt::Display for ListenAddr {
     }
 }
 
+/// Paths to the PEM encoded certificate and private key the gateway serves TLS with.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TlsCfg {
+    pub cert_path: PathBuf,
+    pub key_path: PathBuf,
+}
+
 struct ManagerFs;
 
 impl typemap::Key for ManagerFs {
     type Value = manager::FsCfg;
 }
 
-pub struct Server(Iron<Chain>, ListenAddr);
+struct ManagerCfg;
+
+impl typemap::Key for ManagerCfg {
+    type Value = ManagerConfig;
+}
+
+struct ManagerCommands;
+
+impl typemap::Key for ManagerCommands {
+    type Value = Mutex<CommandSender>;
+}
+
+pub struct Server(Iron<Chain>, ListenAddr, Option<TlsCfg>);
 
 impl Server {
-    pub fn new(manager_state: Arc<manager::FsCfg>, listen_addr: ListenAddr) -> Self {
+    pub fn new(
+        manager_state: Arc<manager::FsCfg>,
+        manager_cfg: Arc<ManagerConfig>,
+        listen_addr: ListenAddr,
+        commands: CommandSender,
+    ) -> Self {
         let router =
             router!(
             doc: get "/" => with_metrics!(doc, "doc"),
@@ -165,27 +201,117 @@ impl Server {
             service_config_org: get "/services/:svc/:group/:org/config" => {
                 with_metrics!(config, "config")
             },
+            service_config_validate: post "/services/:svc/:group/config/validate" => {
+                with_metrics!(config_validate, "config_validate")
+            },
+            service_config_validate_org: post "/services/:svc/:group/:org/config/validate" => {
+                with_metrics!(config_validate, "config_validate")
+            },
             service_health_org: get "/services/:svc/:group/:org/health" => {
                 with_metrics!(health, "config")
+            },
+            service_load: post "/services" => with_metrics!(service_load, "service_load"),
+            service_unload: delete "/services/:svc/:group" => {
+                with_metrics!(service_unload, "service_unload")
+            },
+            service_unload_org: delete "/services/:svc/:group/:org" => {
+                with_metrics!(service_unload, "service_unload")
+            },
+            service_start: post "/services/:svc/:group/start" => {
+                with_metrics!(service_start, "service_start")
+            },
+            service_start_org: post "/services/:svc/:group/:org/start" => {
+                with_metrics!(service_start, "service_start")
+            },
+            service_stop: post "/services/:svc/:group/stop" => {
+                with_metrics!(service_stop, "service_stop")
+            },
+            service_stop_org: post "/services/:svc/:group/:org/stop" => {
+                with_metrics!(service_stop, "service_stop")
             }
         );
+        let tls = manager_cfg.http_tls.clone();
         let mut chain = Chain::new(router);
+        chain.link_before(Authenticator(manager_cfg.http_auth_token.clone()));
         chain.link(persistent::Read::<ManagerFs>::both(manager_state));
-        Server(Iron::new(chain), listen_addr)
+        chain.link(persistent::Read::<ManagerCfg>::both(manager_cfg));
+        chain.link_before(persistent::Read::<ManagerCommands>::one(Mutex::new(commands)));
+        Server(Iron::new(chain), listen_addr, tls)
     }
 
     pub fn start(self) -> Result<JoinHandle<()>> {
+        // Load the certificate before spawning the listener so a bad certificate or key is
+        // reported to the caller instead of killing the gateway thread.
+        let ssl = match self.2 {
+            Some(ref tls) => {
+                let ssl = OpensslServer::from_files(&tls.key_path, &tls.cert_path)
+                    .map_err(|err| sup_error!(Error::HttpGatewayTls(err.to_string())))?;
+                Some(ssl)
+            }
+            None => None,
+        };
         let handle = thread::Builder::new()
             .name("http-gateway".to_string())
             .spawn(move || {
-                self.0.http(*self.1).expect(
-                    "unable to start http-gateway thread",
-                );
+                match ssl {
+                    Some(ssl) => self.0.https(*self.1, ssl),
+                    None => self.0.http(*self.1),
+                }.expect("unable to start http-gateway thread");
             })?;
         Ok(handle)
     }
 }
 
+/// Rejects any request which doesn't carry the bearer token configured for the gateway. All
+/// requests are let through when no token is configured.
+struct Authenticator(Option<String>);
+
+impl BeforeMiddleware for Authenticator {
+    fn before(&self, req: &mut Request) -> IronResult<()> {
+        if self.authenticated(&req.headers) {
+            return Ok(());
+        }
+        let mut response = Response::with(status::Unauthorized);
+        response.headers.set_raw(
+            "WWW-Authenticate",
+            vec![b"Bearer".to_vec()],
+        );
+        Err(IronError::new(Unauthorized, response))
+    }
+}
+
+impl Authenticator {
+    fn authenticated(&self, headers: &headers::Headers) -> bool {
+        let token = match self.0 {
+            Some(ref token) => token,
+            None => return true,
+        };
+        match headers.get::<headers::Authorization<headers::Bearer>>() {
+            // Compare in constant time so the token can't be guessed byte by byte from how
+            // long a rejection takes. `memcmp::eq` panics on inputs of different lengths.
+            Some(&headers::Authorization(headers::Bearer { token: ref given })) => {
+                given.len() == token.len() && memcmp::eq(given.as_bytes(), token.as_bytes())
+            }
+            None => false,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Unauthorized;
+
+impl fmt::Display for Unauthorized {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Missing or invalid bearer token")
+    }
+}
+
+impl error::Error for Unauthorized {
+    fn description(&self) -> &str {
+        "Missing or invalid bearer token"
+    }
+}
+
 #[derive(Default, Serialize)]
 struct HealthCheckBody {
     stdout: String,
@@ -231,6 +357,53 @@ fn config(req: &mut Request) -> IronResult<Response> {
     }
 }
 
+/// Checks a configuration about to be applied to a service group against the schema of the
+/// loaded package, merged on top of the service's other configuration layers. The request body
+/// is the TOML to apply; nothing is applied or gossiped.
+fn config_validate(req: &mut Request) -> IronResult<Response> {
+    let cfg = req.get::<persistent::Read<ManagerCfg>>().unwrap();
+    let spec = match spec_from_request(req, &cfg) {
+        Ok(spec) => spec,
+        Err(response) => return Ok(response),
+    };
+    let mut body = String::new();
+    if let Err(err) = req.body.read_to_string(&mut body) {
+        return Ok(Response::with((status::BadRequest, err.to_string())));
+    }
+    let package = match util::pkg::installed(&spec.ident) {
+        Some(package) => package,
+        None => {
+            let err = sup_error!(Error::PackageNotFound(spec.ident.clone()));
+            return Ok(Response::with((status::UnprocessableEntity, err.to_string())));
+        }
+    };
+    match Pkg::from_install(package) {
+        Ok(pkg) => Ok(validate_config(&pkg, spec.config_from.as_ref(), &body)),
+        Err(err) => Ok(Response::with((status::UnprocessableEntity, err.to_string()))),
+    }
+}
+
+/// Answers whether `body`, applied as the gossip layer on top of the package's other
+/// configuration layers, matches the package's config schema.
+fn validate_config<P>(package: &P, config_from: Option<&PathBuf>, body: &str) -> Response
+where
+    P: PackageConfigPaths,
+{
+    let gossip = match toml::from_str(body) {
+        Ok(table) => toml::Value::Table(table),
+        Err(err) => return Response::with((status::BadRequest, err.to_string())),
+    };
+    let mut service_cfg = match Cfg::new(package, config_from) {
+        Ok(service_cfg) => service_cfg,
+        Err(err) => return Response::with((status::UnprocessableEntity, err.to_string())),
+    };
+    service_cfg.gossip = Some(gossip);
+    match service_cfg.validate() {
+        Ok(()) => Response::with(status::NoContent),
+        Err(err) => Response::with((status::UnprocessableEntity, err.to_string())),
+    }
+}
+
 fn health(req: &mut Request) -> IronResult<Response> {
     let state = req.get::<persistent::Read<ManagerFs>>().unwrap();
     let (health_file, stdout_path, stderr_path) = match build_service_group(req) {
@@ -295,6 +468,132 @@ fn services(req: &mut Request) -> IronResult<Response> {
     }
 }
 
+/// Load a new service, or replace the spec of an already loaded service when the `force` query
+/// parameter is given. The request body is a TOML encoded `ServiceSpec`.
+fn service_load(req: &mut Request) -> IronResult<Response> {
+    if let Some(response) = authorize(req) {
+        return Ok(response);
+    }
+    let cfg = req.get::<persistent::Read<ManagerCfg>>().unwrap();
+    let mut body = String::new();
+    if let Err(err) = req.body.read_to_string(&mut body) {
+        return Ok(Response::with((status::BadRequest, err.to_string())));
+    }
+    let mut spec = match ServiceSpec::from_str(&body) {
+        Ok(spec) => spec,
+        Err(err) => return Ok(Response::with((status::BadRequest, err.to_string()))),
+    };
+    // "load" == persistent services, by definition
+    spec.start_style = StartStyle::Persistent;
+    let package = match util::pkg::installed(&spec.ident) {
+        Some(package) => package,
+        None => {
+            let err = sup_error!(Error::PackageNotFound(spec.ident.clone()));
+            return Ok(Response::with((status::UnprocessableEntity, err.to_string())));
+        }
+    };
+    if let Err(err) = spec.validate(&package) {
+        return Ok(Response::with((status::UnprocessableEntity, err.to_string())));
+    }
+    let spec_file = Manager::spec_path_for(&cfg, &spec);
+    let replaced = spec_file.is_file();
+    if replaced && !force_requested(req) {
+        let err = sup_error!(Error::ServiceLoaded(spec.ident.clone()));
+        return Ok(Response::with((status::Conflict, err.to_string())));
+    }
+    if let Err(err) = Manager::save_spec_for(&cfg, &spec) {
+        return Ok(Response::with((status::InternalServerError, err.to_string())));
+    }
+    outputln!("The {} service was successfully loaded", spec.ident);
+    let status = if replaced {
+        status::Ok
+    } else {
+        status::Created
+    };
+    Ok(Response::with((
+        status,
+        Header(headers::ContentType::json()),
+        serde_json::to_string(&spec).unwrap(),
+    )))
+}
+
+fn service_unload(req: &mut Request) -> IronResult<Response> {
+    if let Some(response) = authorize(req) {
+        return Ok(response);
+    }
+    let cfg = req.get::<persistent::Read<ManagerCfg>>().unwrap();
+    let spec = match spec_from_request(req, &cfg) {
+        Ok(spec) => spec,
+        Err(response) => return Ok(response),
+    };
+    let file = Manager::spec_path_for(&cfg, &spec);
+    if let Err(err) = fs::remove_file(&file) {
+        let err = sup_error!(Error::ServiceSpecFileIO(file, err));
+        return Ok(Response::with((status::InternalServerError, err.to_string())));
+    }
+    outputln!("Unloading {:?}", file);
+    Ok(Response::with(status::NoContent))
+}
+
+fn service_start(req: &mut Request) -> IronResult<Response> {
+    service_command(req, ServiceCommand::Start)
+}
+
+fn service_stop(req: &mut Request) -> IronResult<Response> {
+    service_command(req, ServiceCommand::Stop)
+}
+
+/// Has the Manager carry out a command on the service group named in the request's route, and
+/// responds once it has.
+fn service_command<F>(req: &mut Request, command: F) -> IronResult<Response>
+where
+    F: FnOnce(ServiceGroup) -> ServiceCommand,
+{
+    if let Some(response) = authorize(req) {
+        return Ok(response);
+    }
+    let service_group = match build_service_group(req) {
+        Ok(sg) => sg,
+        Err(_) => return Ok(Response::with(status::BadRequest)),
+    };
+    let commands = req.get::<persistent::Read<ManagerCommands>>().unwrap();
+    Ok(send_command(
+        &commands,
+        command(service_group),
+        Duration::from_secs(COMMAND_TIMEOUT_SECS),
+    ))
+}
+
+fn send_command(
+    commands: &Mutex<CommandSender>,
+    command: ServiceCommand,
+    timeout: Duration,
+) -> Response {
+    let (tx, rx) = mpsc::channel();
+    let sent = commands
+        .lock()
+        .expect("Command sender lock is poisoned")
+        .send((command, tx));
+    if sent.is_err() {
+        return Response::with((status::ServiceUnavailable, "The Supervisor is shutting down"));
+    }
+    match rx.recv_timeout(timeout) {
+        Ok(CommandReply::Done) |
+        Ok(CommandReply::Unchanged) => Response::with(status::NoContent),
+        Ok(CommandReply::NotLoaded) => Response::with(status::NotFound),
+        Ok(CommandReply::Failed(reason)) => Response::with((status::InternalServerError, reason)),
+        Err(RecvTimeoutError::Timeout) => {
+            Response::with((
+                status::GatewayTimeout,
+                "The Supervisor did not carry out the command in time",
+            ))
+        }
+        Err(RecvTimeoutError::Disconnected) => {
+            Response::with((status::ServiceUnavailable, "The Supervisor is shutting down"))
+        }
+    }
+}
+
 fn doc(_req: &mut Request) -> IronResult<Response> {
     Ok(Response::with(
         (status::Ok, Header(headers::ContentType::html()), APIDOCS),
@@ -306,10 +605,13 @@ fn metrics(_req: &mut Request) -> IronResult<Response> {
     let encoder = TextEncoder::new();
     let metric_familys = prometheus::gather();
     encoder.encode(&metric_familys, &mut buffer).unwrap();
+    let content_type = encoder.format_type().parse().unwrap();
 
-    Ok(Response::with(
-        (status::Ok, String::from_utf8(buffer).unwrap()),
-    ))
+    Ok(Response::with((
+        status::Ok,
+        Header(headers::ContentType(content_type)),
+        String::from_utf8(buffer).unwrap(),
+    )))
 }
 
 impl Into<Response> for HealthCheck {
@@ -358,6 +660,44 @@ fn build_service_group(req: &mut Request) -> Result<ServiceGroup> {
     Ok(sg)
 }
 
+/// Write endpoints are only served when the gateway requires a bearer token; the token itself
+/// was already checked by the `Authenticator`. Returns the response to send back to the client
+/// if the request may not proceed.
+fn authorize(req: &mut Request) -> Option<Response> {
+    let cfg = req.get::<persistent::Read<ManagerCfg>>().unwrap();
+    if cfg.http_auth_token.is_some() {
+        None
+    } else {
+        Some(Response::with((
+            status::Forbidden,
+            "Write endpoints are disabled; no authentication token was configured",
+        )))
+    }
+}
+
+fn force_requested(req: &Request) -> bool {
+    match req.url.query() {
+        Some(query) => query.split('&').any(|p| p == "force" || p == "force=true"),
+        None => false,
+    }
+}
+
+/// Finds the loaded `ServiceSpec` for the service group named in the request's route.
+fn spec_from_request(
+    req: &mut Request,
+    cfg: &ManagerConfig,
+) -> result::Result<ServiceSpec, Response> {
+    let service_group = match build_service_group(req) {
+        Ok(sg) => sg,
+        Err(_) => return Err(Response::with(status::BadRequest)),
+    };
+    match Manager::spec_for_service_group(cfg, &service_group) {
+        Ok(Some(spec)) => Ok(spec),
+        Ok(None) => Err(Response::with(status::NotFound)),
+        Err(err) => Err(Response::with((status::InternalServerError, err.to_string()))),
+    }
+}
+
 fn service_from_file<T>(
     service_group: &ServiceGroup,
     services_data_path: T,
@@ -379,3 +719,194 @@ where
         Err(err) => Err(err),
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::fs::{self, File};
+    use std::io::Write;
+    use std::path::PathBuf;
+    use std::sync::Mutex;
+    use std::sync::mpsc;
+    use std::thread;
+    use std::time::Duration;
+
+    use hcore::service::ServiceGroup;
+    use iron::headers::{Authorization, Bearer, Headers};
+    use iron::prelude::*;
+    use iron::status;
+    use tempdir::TempDir;
+
+    use super::{send_command, validate_config, Authenticator};
+    use manager::{CommandReply, ServiceCommand};
+    use manager::service::PackageConfigPaths;
+
+    fn bearer(token: &str) -> Headers {
+        let mut headers = Headers::new();
+        headers.set(Authorization(Bearer { token: token.to_string() }));
+        headers
+    }
+
+    struct TestPkg {
+        base_path: PathBuf,
+    }
+
+    impl PackageConfigPaths for TestPkg {
+        fn name(&self) -> String {
+            String::from("testing")
+        }
+        fn default_config_dir(&self) -> PathBuf {
+            self.base_path.join("root")
+        }
+        fn recommended_user_config_dir(&self) -> PathBuf {
+            self.base_path.join("user")
+        }
+        fn deprecated_user_config_dir(&self) -> PathBuf {
+            self.base_path.join("svc")
+        }
+    }
+
+    fn write_file(path: PathBuf, text: &str) {
+        let mut file = File::create(path).expect("create file");
+        file.write_all(text.as_bytes()).expect("write file");
+    }
+
+    fn schema_pkg(tmp: &TempDir) -> TestPkg {
+        let pkg = TestPkg { base_path: tmp.path().to_owned() };
+        fs::create_dir_all(pkg.default_config_dir()).expect("create default config dir");
+        write_file(
+            pkg.default_config_dir().join("config_schema.json"),
+            r#"{"properties": {"port": {"type": "integer"}}}"#,
+        );
+        write_file(pkg.default_config_dir().join("default.toml"), "port = 6379");
+        pkg
+    }
+
+    fn body_of(response: Response) -> String {
+        let mut body = Vec::new();
+        if let Some(mut writer) = response.body {
+            writer.write_body(&mut body).expect("write response body");
+        }
+        String::from_utf8(body).expect("utf-8 response body")
+    }
+
+    #[test]
+    fn config_validate_accepts_a_config_matching_the_schema() {
+        let tmp = TempDir::new("habitat_http_gateway_test").expect("create temp dir");
+        let pkg = schema_pkg(&tmp);
+
+        let response = validate_config(&pkg, None, "port = 6380");
+        assert_eq!(response.status, Some(status::NoContent));
+    }
+
+    #[test]
+    fn config_validate_rejects_a_config_breaking_the_schema() {
+        let tmp = TempDir::new("habitat_http_gateway_test").expect("create temp dir");
+        let pkg = schema_pkg(&tmp);
+
+        let response = validate_config(&pkg, None, "port = \"abc\"");
+        assert_eq!(response.status, Some(status::UnprocessableEntity));
+        assert!(body_of(response).contains("gossip"));
+    }
+
+    #[test]
+    fn config_validate_rejects_a_body_which_is_not_toml() {
+        let tmp = TempDir::new("habitat_http_gateway_test").expect("create temp dir");
+        let pkg = schema_pkg(&tmp);
+
+        let response = validate_config(&pkg, None, "port = ");
+        assert_eq!(response.status, Some(status::BadRequest));
+    }
+
+    #[test]
+    fn authenticator_lets_everything_through_without_a_token() {
+        let auth = Authenticator(None);
+        assert!(auth.authenticated(&Headers::new()));
+        assert!(auth.authenticated(&bearer("anything")));
+    }
+
+    #[test]
+    fn authenticator_accepts_the_configured_token() {
+        let auth = Authenticator(Some("s3cr3t".to_string()));
+        assert!(auth.authenticated(&bearer("s3cr3t")));
+    }
+
+    #[test]
+    fn authenticator_rejects_a_wrong_token() {
+        let auth = Authenticator(Some("s3cr3t".to_string()));
+        assert!(!auth.authenticated(&bearer("guess")));
+        assert!(!auth.authenticated(&bearer("")));
+    }
+
+    #[test]
+    fn authenticator_rejects_a_near_miss_token() {
+        let auth = Authenticator(Some("s3cr3t".to_string()));
+        assert!(!auth.authenticated(&bearer("s3cr3x")));
+        assert!(!auth.authenticated(&bearer("s3cr3t2")));
+    }
+
+    #[test]
+    fn authenticator_rejects_a_missing_or_non_bearer_header() {
+        let auth = Authenticator(Some("s3cr3t".to_string()));
+        assert!(!auth.authenticated(&Headers::new()));
+        let mut basic = Headers::new();
+        basic.set_raw("Authorization", vec![b"Basic czNjcjN0".to_vec()]);
+        assert!(!auth.authenticated(&basic));
+    }
+
+    /// Sends a start command to a Manager which replies with `reply`, and returns the status of
+    /// the response.
+    fn command_status(reply: CommandReply) -> Option<status::Status> {
+        let (tx, rx) = mpsc::channel();
+        let manager = thread::spawn(move || {
+            let (command, reply_to) = rx.recv().unwrap();
+            match command {
+                ServiceCommand::Start(_) => reply_to.send(reply).unwrap(),
+                ServiceCommand::Stop(_) => panic!("Expected a start command"),
+            }
+        });
+        let sg = ServiceGroup::new(None, "redis", "default", None).unwrap();
+        let response = send_command(
+            &Mutex::new(tx),
+            ServiceCommand::Start(sg),
+            Duration::from_secs(5),
+        );
+        manager.join().unwrap();
+        response.status
+    }
+
+    #[test]
+    fn service_commands_are_answered_with_the_manager_reply() {
+        assert_eq!(command_status(CommandReply::Done), Some(status::NoContent));
+        assert_eq!(command_status(CommandReply::Unchanged), Some(status::NoContent));
+        assert_eq!(command_status(CommandReply::NotLoaded), Some(status::NotFound));
+        assert_eq!(
+            command_status(CommandReply::Failed("nope".to_string())),
+            Some(status::InternalServerError)
+        );
+    }
+
+    #[test]
+    fn service_commands_fail_when_the_manager_is_gone() {
+        let (tx, rx) = mpsc::channel();
+        drop(rx);
+        let sg = ServiceGroup::new(None, "redis", "default", None).unwrap();
+        let response = send_command(
+            &Mutex::new(tx),
+            ServiceCommand::Stop(sg),
+            Duration::from_secs(5),
+        );
+        assert_eq!(response.status, Some(status::ServiceUnavailable));
+    }
+
+    #[test]
+    fn service_commands_time_out_when_the_manager_does_not_reply() {
+        let (tx, _rx) = mpsc::channel();
+        let sg = ServiceGroup::new(None, "redis", "default", None).unwrap();
+        let response = send_command(
+            &Mutex::new(tx),
+            ServiceCommand::Stop(sg),
+            Duration::from_millis(10),
+        );
+        assert_eq!(response.status, Some(status::GatewayTimeout));
+    }
+}
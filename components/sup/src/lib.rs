@@ -40,6 +40,7 @@
 //! * [The Habitat Supervisor Sidecar; http interface to promises](sidecar)
 
 extern crate ansi_term;
+extern crate base64;
 #[macro_use]
 extern crate bitflags;
 extern crate byteorder;
@@ -54,8 +55,12 @@ extern crate habitat_common as common;
 extern crate habitat_core as hcore;
 extern crate habitat_depot_client as depot_client;
 extern crate habitat_eventsrv_client as eventsrv_client;
+extern crate habitat_http_client as http_client;
 extern crate habitat_launcher_client as launcher_client;
 extern crate handlebars;
+#[macro_use]
+extern crate hyper;
+extern crate hyper_openssl;
 extern crate iron;
 #[macro_use]
 extern crate lazy_static;
@@ -63,6 +68,7 @@ extern crate libc;
 #[macro_use]
 extern crate log;
 extern crate notify;
+extern crate openssl;
 extern crate persistent;
 #[macro_use]
 extern crate prometheus;
@@ -97,12 +103,15 @@ macro_rules! sup_error {
 pub mod command;
 pub mod config;
 pub mod census;
+pub mod ctl_gateway;
 pub mod error;
 pub mod fs;
 pub mod http_gateway;
+pub mod logging;
 pub mod manager;
 pub mod templating;
 pub mod util;
+mod metrics;
 mod sys;
 
 use std::env;
@@ -0,0 +1,273 @@
+// Copyright (c) 2017 Chef Software Inc. and/or applicable contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Structured logging of the output of services.
+//!
+//! By default the output of a service's hooks is written to the Supervisor's output as lines of
+//! text prefixed with the service group and the hook's name. When the JSON log format is
+//! selected every line becomes a JSON record instead, which is also appended to a log file of the
+//! service that is rotated by size and age. The Launcher does the same for the output of the
+//! service's process, in a log file of its own.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::PathBuf;
+use std::result;
+use std::str::FromStr;
+use std::sync::{Mutex, RwLock};
+
+use hcore::service::ServiceGroup;
+use launcher_client::{JsonLog, LogFile};
+use serde_json;
+use time;
+
+use error::{Error, SupError};
+use fs as supfs;
+
+static LOGKEY: &'static str = "LG";
+/// Size, in bytes, after which a log file is rotated.
+pub const DEFAULT_LOG_MAX_SIZE: u64 = 10 * 1024 * 1024;
+/// Age, in seconds, after which a log file is rotated.
+pub const DEFAULT_LOG_MAX_AGE: u64 = 24 * 60 * 60;
+const LOG_FILE_NAME: &'static str = "hooks.log";
+const RUN_LOG_FILE_NAME: &'static str = "run.log";
+
+lazy_static! {
+    static ref LOG_CFG: RwLock<LogCfg> = RwLock::new(LogCfg::default());
+    static ref MEMBER_ID: RwLock<String> = RwLock::new(String::new());
+    /// Open log files of services, keyed by service group.
+    static ref LOG_FILES: Mutex<HashMap<String, LogFile>> = Mutex::new(HashMap::new());
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LogFormat {
+    Text,
+    Json,
+}
+
+impl Default for LogFormat {
+    fn default() -> LogFormat {
+        LogFormat::Text
+    }
+}
+
+impl fmt::Display for LogFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let value = match *self {
+            LogFormat::Text => "text",
+            LogFormat::Json => "json",
+        };
+        write!(f, "{}", value)
+    }
+}
+
+impl FromStr for LogFormat {
+    type Err = SupError;
+
+    fn from_str(value: &str) -> result::Result<Self, Self::Err> {
+        match value {
+            "text" => Ok(LogFormat::Text),
+            "json" => Ok(LogFormat::Json),
+            _ => Err(sup_error!(Error::BadLogFormat(value.to_string()))),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LogCfg {
+    pub format: LogFormat,
+    /// Size, in bytes, after which a service's log file is rotated.
+    pub max_size: u64,
+    /// Age, in seconds, after which a service's log file is rotated.
+    pub max_age: u64,
+}
+
+impl Default for LogCfg {
+    fn default() -> Self {
+        LogCfg {
+            format: LogFormat::default(),
+            max_size: DEFAULT_LOG_MAX_SIZE,
+            max_age: DEFAULT_LOG_MAX_AGE,
+        }
+    }
+}
+
+/// Set how the output of services is logged by this Supervisor.
+pub fn configure(cfg: LogCfg) {
+    *LOG_CFG.write().expect("Log config lock is poisoned") = cfg;
+}
+
+pub fn cfg() -> LogCfg {
+    LOG_CFG.read().expect("Log config lock is poisoned").clone()
+}
+
+pub fn is_json() -> bool {
+    cfg().format == LogFormat::Json
+}
+
+/// Set the member id of this Supervisor, which is included in every log record.
+pub fn set_member_id(member_id: &str) {
+    *MEMBER_ID.write().expect("Member id lock is poisoned") = member_id.to_string();
+}
+
+pub fn member_id() -> String {
+    MEMBER_ID.read().expect("Member id lock is poisoned").clone()
+}
+
+/// Path to the structured log file of a service's hooks.
+pub fn log_file_path(service_group: &ServiceGroup) -> PathBuf {
+    supfs::svc_logs_path(service_group.service()).join(LOG_FILE_NAME)
+}
+
+/// Path to the structured log file of a service's process, which is written by the Launcher.
+pub fn run_log_file_path(service_group: &ServiceGroup) -> PathBuf {
+    supfs::svc_logs_path(service_group.service()).join(RUN_LOG_FILE_NAME)
+}
+
+/// How the Launcher should log the output of a service's process, `None` unless the JSON log
+/// format is selected.
+pub fn json_log(service_group: &ServiceGroup) -> Option<JsonLog> {
+    json_log_with(&cfg(), service_group)
+}
+
+fn json_log_with(cfg: &LogCfg, service_group: &ServiceGroup) -> Option<JsonLog> {
+    if cfg.format != LogFormat::Json {
+        return None;
+    }
+    Some(JsonLog {
+        member_id: member_id(),
+        log_file: run_log_file_path(service_group),
+        max_size: cfg.max_size,
+        max_age: cfg.max_age,
+    })
+}
+
+/// One line of output of a service.
+#[derive(Debug, Serialize)]
+pub struct LogRecord<'a> {
+    pub timestamp: String,
+    pub service_group: String,
+    pub member_id: String,
+    pub stream: &'a str,
+    pub hook: &'a str,
+    pub message: &'a str,
+}
+
+impl<'a> LogRecord<'a> {
+    pub fn new(
+        service_group: &ServiceGroup,
+        stream: &'a str,
+        hook: &'a str,
+        message: &'a str,
+    ) -> Self {
+        LogRecord {
+            timestamp: time::now_utc().rfc3339().to_string(),
+            service_group: service_group.to_string(),
+            member_id: member_id(),
+            stream: stream,
+            hook: hook,
+            message: message,
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("Log record serialization cannot fail")
+    }
+}
+
+/// Appends log records to the log file of a service, rotating it as configured.
+///
+/// The file is opened on the first write and kept open until the service is unloaded. Failures
+/// are reported and otherwise ignored, since they shouldn't prevent a service from running.
+pub fn append(service_group: &ServiceGroup, records: &[String]) {
+    if records.is_empty() {
+        return;
+    }
+    let mut files = LOG_FILES.lock().expect("Log files lock is poisoned");
+    let key = service_group.to_string();
+    if !files.contains_key(&key) {
+        let cfg = cfg();
+        let path = log_file_path(service_group);
+        match LogFile::open(&path, cfg.max_size, cfg.max_age) {
+            Ok(file) => {
+                files.insert(key.clone(), file);
+            }
+            Err(err) => {
+                outputln!(preamble service_group,
+                    "Unable to open log file {}, {}", path.display(), err);
+                return;
+            }
+        }
+    }
+    let file = files.get_mut(&key).expect("Log file was just opened");
+    for record in records {
+        if let Err(err) = file.write_line(record) {
+            outputln!(preamble service_group,
+                "Unable to write log file {}, {}", file.path().display(), err);
+            return;
+        }
+    }
+}
+
+/// Closes the log file of a service, if it is open.
+pub fn close(service_group: &ServiceGroup) {
+    LOG_FILES
+        .lock()
+        .expect("Log files lock is poisoned")
+        .remove(&service_group.to_string());
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use hcore::service::ServiceGroup;
+    use serde_json;
+
+    use super::*;
+
+    #[test]
+    fn log_format_from_str() {
+        assert_eq!(LogFormat::from_str("json").unwrap(), LogFormat::Json);
+        assert_eq!(LogFormat::from_str("text").unwrap(), LogFormat::Text);
+        assert!(LogFormat::from_str("xml").is_err());
+    }
+
+    #[test]
+    fn log_record_to_json() {
+        let sg = ServiceGroup::from_str("redis.default").unwrap();
+        let record = LogRecord::new(&sg, "stdout", "init", "say \"hi\"");
+        let json: serde_json::Value = serde_json::from_str(&record.to_json()).unwrap();
+
+        assert_eq!(json["service_group"], "redis.default");
+        assert_eq!(json["stream"], "stdout");
+        assert_eq!(json["hook"], "init");
+        assert_eq!(json["message"], "say \"hi\"");
+    }
+
+    #[test]
+    fn json_log_only_with_the_json_format() {
+        let sg = ServiceGroup::from_str("redis.default").unwrap();
+        let mut cfg = LogCfg::default();
+        assert_eq!(json_log_with(&cfg, &sg), None);
+
+        cfg.format = LogFormat::Json;
+        cfg.max_size = 1024;
+        let json_log = json_log_with(&cfg, &sg).unwrap();
+        assert_eq!(json_log.log_file, run_log_file_path(&sg));
+        assert_ne!(json_log.log_file, log_file_path(&sg));
+        assert_eq!(json_log.max_size, 1024);
+        assert_eq!(json_log.max_age, DEFAULT_LOG_MAX_AGE);
+    }
+}
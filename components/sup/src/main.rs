@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+extern crate habitat_butterfly as butterfly;
 extern crate habitat_common as common;
 #[macro_use]
 extern crate habitat_core as hcore;
@@ -24,6 +25,7 @@ extern crate ansi_term;
 extern crate libc;
 #[macro_use]
 extern crate clap;
+extern crate serde_json;
 extern crate time;
 extern crate url;
 extern crate tabwriter;
@@ -31,13 +33,18 @@ extern crate tabwriter;
 use std::collections::{HashMap, HashSet};
 use std::collections::hash_map::Entry;
 use std::env;
-use std::io::{self, Write};
+use std::fs::File;
+use std::io::{self, Read, Write};
 use std::net::{SocketAddr, ToSocketAddrs};
 use std::path::{Path, PathBuf};
 use std::process;
 use std::result;
 use std::str::FromStr;
+use std::thread;
+use std::time::{Duration, Instant};
 
+use butterfly::server::keyring::active_ring_key;
+use butterfly::transport::GossipTransportKind;
 use clap::{App, ArgMatches};
 use common::command::package::install::InstallSource;
 use common::ui::{UI, Coloring, NONINTERACTIVE_ENVVAR};
@@ -58,21 +65,29 @@ use url::Url;
 
 use sup::VERSION;
 use sup::config::{GossipListenAddr, GOSSIP_DEFAULT_PORT};
+#[cfg(unix)]
+use sup::ctl_gateway;
+use sup::ctl_gateway::{CtlReply, CtlRequest};
 use sup::error::{Error, Result, SupError};
 use sup::feat;
 use sup::command;
 use sup::http_gateway;
 use sup::http_gateway::ListenAddr;
-use sup::manager::{Manager, ManagerConfig, ServiceStatus};
-use sup::manager::service::{DesiredState, ServiceBind, Topology, UpdateStrategy};
-use sup::manager::service::{CompositeSpec, ServiceSpec, StartStyle};
+use sup::logging::LogFormat;
+use sup::manager::{parse_tag, Manager, ManagerConfig, ServiceStatus};
+use sup::manager::service::{DesiredState, ProcessState, ServiceBind, Topology, UpdateStrategy};
+use sup::manager::service::{CompositeSpec, QuorumBasis, RestartPolicy, ServiceSpec, StartStyle};
+use sup::manager::service::secrets;
 use sup::util;
 
 /// Our output key
 static LOGKEY: &'static str = "MN";
+/// How often to check the state of a service while waiting for it to reach its desired state.
+const WAIT_POLL_MS: u64 = 500;
 
 static RING_ENVVAR: &'static str = "HAB_RING";
 static RING_KEY_ENVVAR: &'static str = "HAB_RING_KEY";
+static HTTP_AUTH_TOKEN_ENVVAR: &'static str = "HAB_SUP_HTTP_AUTH_TOKEN";
 
 fn main() {
     if let Err(err) = start() {
@@ -122,6 +137,7 @@ fn start() -> Result<()> {
         ("bash", Some(m)) => sub_bash(m),
         ("config", Some(m)) => sub_config(m),
         ("load", Some(m)) => sub_load(m),
+        ("render", Some(m)) => sub_render(m),
         ("run", Some(m)) => {
             let launcher = launcher.ok_or(sup_error!(Error::NoLauncher))?;
             sub_run(m, launcher)
@@ -184,11 +200,40 @@ fn cli<'a, 'b>() -> App<'a, 'b> {
             (@arg TOPOLOGY: --topology -t +takes_value {valid_topology}
                 "Service topology; [default: none]")
             (@arg STRATEGY: --strategy -s +takes_value {valid_update_strategy}
-                "The update strategy; [default: none] [values: none, at-once, rolling]")
+                "The update strategy; [default: none] [values: none, at-once, rolling, canary]")
+            (@arg CANARY_PERCENT: --("canary-percent") +takes_value {valid_canary_percent}
+                "The percentage of the service group's members which test a new package first \
+                with the canary update strategy; [default: 10]")
+            (@arg CANARY_SOAK: --("canary-soak") +takes_value {valid_numeric}
+                "How long, in seconds, the canaries must stay healthy before the rest of the \
+                service group updates; [default: 300]")
+            (@arg ROLLBACK_WINDOW: --("rollback-window") +takes_value {valid_numeric}
+                "Roll a package update back if the new release fails its smoke test or health \
+                check within this many seconds; 0 disables rollbacks [default: 300]")
+            (@arg QUORUM_BASIS: --("quorum-basis") +takes_value {valid_quorum_basis}
+                "The members a leader election's quorum is counted against; [default: known] \
+                [values: known, alive]")
+            (@arg LEADER_LEASE: --("leader-lease") +takes_value {valid_numeric}
+                "How long, in seconds, an elected leader holds its lease without renewing it; \
+                0 disables leases [default: 0]")
+            (@arg RESTART_POLICY: --("restart-policy") +takes_value {valid_restart_policy}
+                "Whether to restart the service when its process exits; [default: always] \
+                [values: always, on-failure, never]")
             (@arg BIND: --bind +takes_value +multiple
                 "One or more service groups to bind to a configuration")
+            (@arg STRICT_TEMPLATES: --("strict-templates")
+                "Fail the compilation of the service's templates when they use a variable which \
+                isn't set, keeping its current configuration")
+            (@arg NO_STRICT_TEMPLATES: --("no-strict-templates") conflicts_with[STRICT_TEMPLATES]
+                "Compile the service's templates leniently again, unless its plan asks for strict \
+                templates")
+            (@arg SECRETS: --secrets +takes_value {valid_secrets_url}
+                "URL of the provider of the secrets read by the service's templates, a file:// \
+                directory or a Vault-compatible http(s):// API (ex: file:///hab/secrets/myapp)")
             (@arg FORCE: --force -f "Load or reload an already loaded service. If the service was \
                 previously loaded and running this operation will also restart the service")
+            (@arg WAIT: --wait +takes_value {valid_numeric}
+                "Wait up to this many seconds for the service to reach its desired state")
         )
         (@subcommand unload =>
             (about: "Unload a persistent or transient service started by the Habitat \
@@ -200,13 +245,59 @@ fn cli<'a, 'b>() -> App<'a, 'b> {
                 "The name for the state directory if there is more than one Supervisor running \
                 [default: default]")
         )
+        (@subcommand render =>
+            (about: "Renders the configuration templates and hooks of a package or plan into a \
+                directory, without running the service")
+            (aliases: &["re", "ren", "rend", "rende"])
+            (@arg PKG_IDENT_OR_PLAN: +required +takes_value
+                "An installed package identifier (ex: core/redis) or the directory of a plan")
+            (@arg GROUP: --group +takes_value
+                "The service group; shared config and topology [default: default]")
+            (@arg USER_TOML: --("user-toml") +takes_value {file_exists}
+                "A TOML file applied over default.toml, as a service's user.toml would be")
+            (@arg CENSUS: --census +takes_value {file_exists}
+                "A JSON file describing the members of the service's and bound service groups")
+            (@arg BIND: --bind +takes_value +multiple
+                "One or more service groups to bind to a configuration")
+            (@arg OUTPUT: --output -o +takes_value
+                "The directory the rendered files are written to [default: ./results/render]")
+        )
         (@subcommand run =>
             (about: "Run the Habitat Supervisor")
             (aliases: &["r", "ru"])
             (@arg LISTEN_GOSSIP: --("listen-gossip") +takes_value {valid_listen_gossip}
                 "The listen address for the gossip system [default: 0.0.0.0:9638]")
+            (@arg GOSSIP_TRANSPORT: --("gossip-transport") +takes_value {valid_gossip_transport}
+                "How rumors are sent between Supervisors, either zeromq or tcp. Every member of \
+                the ring must use the same transport [default: zeromq]")
+            (@arg GOSSIP_SUSPICION_MULTIPLIER: --("gossip-suspicion-multiplier") +takes_value
+                {valid_numeric} "How many times longer than the minimum a suspect member is given \
+                before it is confirmed dead when no other member backs the suspicion. Set to 1 to \
+                always use the minimum [default: 6]")
             (@arg LISTEN_HTTP: --("listen-http") +takes_value {valid_listen_http}
                 "The listen address for the HTTP gateway [default: 0.0.0.0:9631]")
+            (@arg HTTP_TLS_CERT: --("http-tls-cert") +takes_value requires[HTTP_TLS_KEY]
+                {file_exists} "Serve the HTTP gateway over TLS with this PEM encoded certificate")
+            (@arg HTTP_TLS_KEY: --("http-tls-key") +takes_value requires[HTTP_TLS_CERT]
+                {file_exists} "The PEM encoded private key of the HTTP gateway's certificate")
+            (@arg HTTP_AUTH_TOKEN: --("http-auth-token") +takes_value
+                "Require this bearer token on every request to the HTTP gateway, and serve its \
+                write endpoints. A token given here is visible to other users in the process \
+                list; prefer $HAB_SUP_HTTP_AUTH_TOKEN or --http-auth-token-file \
+                [default: $HAB_SUP_HTTP_AUTH_TOKEN]")
+            (@arg HTTP_AUTH_TOKEN_FILE: --("http-auth-token-file") +takes_value
+                conflicts_with[HTTP_AUTH_TOKEN] {file_exists}
+                "Read the bearer token of the HTTP gateway from this file instead")
+            (@arg LOG_FORMAT: --("log-format") +takes_value {valid_log_format}
+                "The format of the output of services, either text or json. In json format the \
+                output of hooks and of services is also appended to each service's log files \
+                [default: text]")
+            (@arg LOG_MAX_SIZE: --("log-max-size") +takes_value {valid_numeric}
+                "The size, in bytes, after which a service's log file is rotated \
+                [default: 10485760]")
+            (@arg LOG_MAX_AGE: --("log-max-age") +takes_value {valid_numeric}
+                "The age, in seconds, after which a service's log file is rotated \
+                [default: 86400]")
             (@arg NAME: --("override-name") +takes_value
                 "The name of the Supervisor if launching more than one [default: default]")
             (@arg ORGANIZATION: --org +takes_value
@@ -215,6 +306,9 @@ fn cli<'a, 'b>() -> App<'a, 'b> {
             (@arg PEER: --peer +takes_value +multiple
                 "The listen address of an initial peer (IP[:PORT])")
             (@arg PERMANENT_PEER: --("permanent-peer") -I "If this Supervisor is a permanent peer")
+            (@arg TAG: --tag +takes_value +multiple {valid_tag}
+                "A KEY=VALUE tag, such as zone=us-east-1a, gossiped with this Supervisor's \
+                membership")
             (@arg PEER_WATCH_FILE: --("peer-watch-file") +takes_value conflicts_with[peer]
                 "Watch this file for connecting to the ring"
             )
@@ -240,8 +334,37 @@ fn cli<'a, 'b>() -> App<'a, 'b> {
             (aliases: &["sta", "star"])
             (@arg LISTEN_GOSSIP: --("listen-gossip") +takes_value {valid_listen_gossip}
                 "The listen address for the gossip system [default: 0.0.0.0:9638]")
+            (@arg GOSSIP_TRANSPORT: --("gossip-transport") +takes_value {valid_gossip_transport}
+                "How rumors are sent between Supervisors, either zeromq or tcp. Every member of \
+                the ring must use the same transport [default: zeromq]")
+            (@arg GOSSIP_SUSPICION_MULTIPLIER: --("gossip-suspicion-multiplier") +takes_value
+                {valid_numeric} "How many times longer than the minimum a suspect member is given \
+                before it is confirmed dead when no other member backs the suspicion. Set to 1 to \
+                always use the minimum [default: 6]")
             (@arg LISTEN_HTTP: --("listen-http") +takes_value {valid_listen_http}
                 "The listen address for the HTTP gateway [default: 0.0.0.0:9631]")
+            (@arg HTTP_TLS_CERT: --("http-tls-cert") +takes_value requires[HTTP_TLS_KEY]
+                {file_exists} "Serve the HTTP gateway over TLS with this PEM encoded certificate")
+            (@arg HTTP_TLS_KEY: --("http-tls-key") +takes_value requires[HTTP_TLS_CERT]
+                {file_exists} "The PEM encoded private key of the HTTP gateway's certificate")
+            (@arg HTTP_AUTH_TOKEN: --("http-auth-token") +takes_value
+                "Require this bearer token on every request to the HTTP gateway, and serve its \
+                write endpoints. A token given here is visible to other users in the process \
+                list; prefer $HAB_SUP_HTTP_AUTH_TOKEN or --http-auth-token-file \
+                [default: $HAB_SUP_HTTP_AUTH_TOKEN]")
+            (@arg HTTP_AUTH_TOKEN_FILE: --("http-auth-token-file") +takes_value
+                conflicts_with[HTTP_AUTH_TOKEN] {file_exists}
+                "Read the bearer token of the HTTP gateway from this file instead")
+            (@arg LOG_FORMAT: --("log-format") +takes_value {valid_log_format}
+                "The format of the output of services, either text or json. In json format the \
+                output of hooks and of services is also appended to each service's log files \
+                [default: text]")
+            (@arg LOG_MAX_SIZE: --("log-max-size") +takes_value {valid_numeric}
+                "The size, in bytes, after which a service's log file is rotated \
+                [default: 10485760]")
+            (@arg LOG_MAX_AGE: --("log-max-age") +takes_value {valid_numeric}
+                "The age, in seconds, after which a service's log file is rotated \
+                [default: 86400]")
             (@arg NAME: --("override-name") +takes_value
                 "The name for the state directory if launching more than one Supervisor \
                 [default: default]")
@@ -251,6 +374,9 @@ fn cli<'a, 'b>() -> App<'a, 'b> {
             (@arg PEER: --peer +takes_value +multiple
                 "The listen address of an initial peer (IP[:PORT])")
             (@arg PERMANENT_PEER: --("permanent-peer") -I "If this Supervisor is a permanent peer")
+            (@arg TAG: --tag +takes_value +multiple {valid_tag}
+                "A KEY=VALUE tag, such as zone=us-east-1a, gossiped with this Supervisor's \
+                membership")
             (@arg PEER_WATCH_FILE: --("peer-watch-file") +takes_value conflicts_with[peer]
                 "Watch this file for connecting to the ring"
             )
@@ -272,11 +398,40 @@ fn cli<'a, 'b>() -> App<'a, 'b> {
             (@arg TOPOLOGY: --topology -t +takes_value {valid_topology}
                 "Service topology; [default: none]")
             (@arg STRATEGY: --strategy -s +takes_value {valid_update_strategy}
-                "The update strategy; [default: none] [values: none, at-once, rolling]")
+                "The update strategy; [default: none] [values: none, at-once, rolling, canary]")
+            (@arg CANARY_PERCENT: --("canary-percent") +takes_value {valid_canary_percent}
+                "The percentage of the service group's members which test a new package first \
+                with the canary update strategy; [default: 10]")
+            (@arg CANARY_SOAK: --("canary-soak") +takes_value {valid_numeric}
+                "How long, in seconds, the canaries must stay healthy before the rest of the \
+                service group updates; [default: 300]")
+            (@arg ROLLBACK_WINDOW: --("rollback-window") +takes_value {valid_numeric}
+                "Roll a package update back if the new release fails its smoke test or health \
+                check within this many seconds; 0 disables rollbacks [default: 300]")
+            (@arg QUORUM_BASIS: --("quorum-basis") +takes_value {valid_quorum_basis}
+                "The members a leader election's quorum is counted against; [default: known] \
+                [values: known, alive]")
+            (@arg LEADER_LEASE: --("leader-lease") +takes_value {valid_numeric}
+                "How long, in seconds, an elected leader holds its lease without renewing it; \
+                0 disables leases [default: 0]")
+            (@arg RESTART_POLICY: --("restart-policy") +takes_value {valid_restart_policy}
+                "Whether to restart the service when its process exits; [default: always] \
+                [values: always, on-failure, never]")
             (@arg BIND: --bind +takes_value +multiple
                 "One or more service groups to bind to a configuration")
+            (@arg STRICT_TEMPLATES: --("strict-templates")
+                "Fail the compilation of the service's templates when they use a variable which \
+                isn't set, keeping its current configuration")
+            (@arg NO_STRICT_TEMPLATES: --("no-strict-templates") conflicts_with[STRICT_TEMPLATES]
+                "Compile the service's templates leniently again, unless its plan asks for strict \
+                templates")
+            (@arg SECRETS: --secrets +takes_value {valid_secrets_url}
+                "URL of the provider of the secrets read by the service's templates, a file:// \
+                directory or a Vault-compatible http(s):// API (ex: file:///hab/secrets/myapp)")
             (@arg CONFIG_DIR: --("config-from") +takes_value {dir_exists}
                 "Use package config from this path, rather than the package itself")
+            (@arg WAIT: --wait +takes_value {valid_numeric}
+                "Wait up to this many seconds for the service to reach its desired state")
             (@arg AUTO_UPDATE: --("auto-update") -A "Enable automatic updates for the Supervisor \
                 itself")
             (@arg EVENTS: --events -n +takes_value {valid_service_group} "Name of the service \
@@ -286,6 +441,8 @@ fn cli<'a, 'b>() -> App<'a, 'b> {
             (about: "Query the status of Habitat services.")
             (aliases: &["stat", "statu", "status"])
             (@arg PKG_IDENT: +takes_value "A Habitat package identifier (ex: core/redis)")
+            (@arg FORMAT: --format +takes_value {valid_status_format}
+                "Output format; [default: table] [values: table, json]")
             (@arg NAME: --("override-name") +takes_value
                 "The name for the state directory if there is more than one Supervisor running \
                 [default: default]")
@@ -294,6 +451,8 @@ fn cli<'a, 'b>() -> App<'a, 'b> {
             (about: "Stop a running Habitat service.")
             (aliases: &["sto"])
             (@arg PKG_IDENT: +required +takes_value "A Habitat package identifier (ex: core/redis)")
+            (@arg WAIT: --wait +takes_value {valid_numeric}
+                "Wait up to this many seconds for the service to reach its desired state")
             (@arg NAME: --("override-name") +takes_value
                 "The name for the state directory if there is more than one Supervisor running \
                 [default: default]")
@@ -351,11 +510,40 @@ fn cli<'a, 'b>() -> App<'a, 'b> {
             (@arg TOPOLOGY: --topology -t +takes_value {valid_topology}
                 "Service topology; [default: none]")
             (@arg STRATEGY: --strategy -s +takes_value {valid_update_strategy}
-                "The update strategy; [default: none] [values: none, at-once, rolling]")
+                "The update strategy; [default: none] [values: none, at-once, rolling, canary]")
+            (@arg CANARY_PERCENT: --("canary-percent") +takes_value {valid_canary_percent}
+                "The percentage of the service group's members which test a new package first \
+                with the canary update strategy; [default: 10]")
+            (@arg CANARY_SOAK: --("canary-soak") +takes_value {valid_numeric}
+                "How long, in seconds, the canaries must stay healthy before the rest of the \
+                service group updates; [default: 300]")
+            (@arg ROLLBACK_WINDOW: --("rollback-window") +takes_value {valid_numeric}
+                "Roll a package update back if the new release fails its smoke test or health \
+                check within this many seconds; 0 disables rollbacks [default: 300]")
+            (@arg QUORUM_BASIS: --("quorum-basis") +takes_value {valid_quorum_basis}
+                "The members a leader election's quorum is counted against; [default: known] \
+                [values: known, alive]")
+            (@arg LEADER_LEASE: --("leader-lease") +takes_value {valid_numeric}
+                "How long, in seconds, an elected leader holds its lease without renewing it; \
+                0 disables leases [default: 0]")
+            (@arg RESTART_POLICY: --("restart-policy") +takes_value {valid_restart_policy}
+                "Whether to restart the service when its process exits; [default: always] \
+                [values: always, on-failure, never]")
             (@arg BIND: --bind +takes_value +multiple
                 "One or more service groups to bind to a configuration")
+            (@arg STRICT_TEMPLATES: --("strict-templates")
+                "Fail the compilation of the service's templates when they use a variable which \
+                isn't set, keeping its current configuration")
+            (@arg NO_STRICT_TEMPLATES: --("no-strict-templates") conflicts_with[STRICT_TEMPLATES]
+                "Compile the service's templates leniently again, unless its plan asks for strict \
+                templates")
+            (@arg SECRETS: --secrets +takes_value {valid_secrets_url}
+                "URL of the provider of the secrets read by the service's templates, a file:// \
+                directory or a Vault-compatible http(s):// API (ex: file:///hab/secrets/myapp)")
             (@arg FORCE: --force -f "Load or reload an already loaded service. If the service was \
                 previously loaded and running this operation will also restart the service")
+            (@arg WAIT: --wait +takes_value {valid_numeric}
+                "Wait up to this many seconds for the service to reach its desired state")
                 (@arg PASSWORD: --password +takes_value
                     "Password of the service user")
         )
@@ -369,13 +557,59 @@ fn cli<'a, 'b>() -> App<'a, 'b> {
                 "The name for the state directory if there is more than one Supervisor running \
                 [default: default]")
         )
+        (@subcommand render =>
+            (about: "Renders the configuration templates and hooks of a package or plan into a \
+                directory, without running the service")
+            (aliases: &["re", "ren", "rend", "rende"])
+            (@arg PKG_IDENT_OR_PLAN: +required +takes_value
+                "An installed package identifier (ex: core/redis) or the directory of a plan")
+            (@arg GROUP: --group +takes_value
+                "The service group; shared config and topology [default: default]")
+            (@arg USER_TOML: --("user-toml") +takes_value {file_exists}
+                "A TOML file applied over default.toml, as a service's user.toml would be")
+            (@arg CENSUS: --census +takes_value {file_exists}
+                "A JSON file describing the members of the service's and bound service groups")
+            (@arg BIND: --bind +takes_value +multiple
+                "One or more service groups to bind to a configuration")
+            (@arg OUTPUT: --output -o +takes_value
+                "The directory the rendered files are written to [default: ./results/render]")
+        )
         (@subcommand run =>
             (about: "Run the Habitat Supervisor")
             (aliases: &["r", "ru"])
             (@arg LISTEN_GOSSIP: --("listen-gossip") +takes_value {valid_listen_gossip}
                 "The listen address for the gossip system [default: 0.0.0.0:9638]")
+            (@arg GOSSIP_TRANSPORT: --("gossip-transport") +takes_value {valid_gossip_transport}
+                "How rumors are sent between Supervisors, either zeromq or tcp. Every member of \
+                the ring must use the same transport [default: zeromq]")
+            (@arg GOSSIP_SUSPICION_MULTIPLIER: --("gossip-suspicion-multiplier") +takes_value
+                {valid_numeric} "How many times longer than the minimum a suspect member is given \
+                before it is confirmed dead when no other member backs the suspicion. Set to 1 to \
+                always use the minimum [default: 6]")
             (@arg LISTEN_HTTP: --("listen-http") +takes_value {valid_listen_http}
                 "The listen address for the HTTP gateway [default: 0.0.0.0:9631]")
+            (@arg HTTP_TLS_CERT: --("http-tls-cert") +takes_value requires[HTTP_TLS_KEY]
+                {file_exists} "Serve the HTTP gateway over TLS with this PEM encoded certificate")
+            (@arg HTTP_TLS_KEY: --("http-tls-key") +takes_value requires[HTTP_TLS_CERT]
+                {file_exists} "The PEM encoded private key of the HTTP gateway's certificate")
+            (@arg HTTP_AUTH_TOKEN: --("http-auth-token") +takes_value
+                "Require this bearer token on every request to the HTTP gateway, and serve its \
+                write endpoints. A token given here is visible to other users in the process \
+                list; prefer $HAB_SUP_HTTP_AUTH_TOKEN or --http-auth-token-file \
+                [default: $HAB_SUP_HTTP_AUTH_TOKEN]")
+            (@arg HTTP_AUTH_TOKEN_FILE: --("http-auth-token-file") +takes_value
+                conflicts_with[HTTP_AUTH_TOKEN] {file_exists}
+                "Read the bearer token of the HTTP gateway from this file instead")
+            (@arg LOG_FORMAT: --("log-format") +takes_value {valid_log_format}
+                "The format of the output of services, either text or json. In json format the \
+                output of hooks and of services is also appended to each service's log files \
+                [default: text]")
+            (@arg LOG_MAX_SIZE: --("log-max-size") +takes_value {valid_numeric}
+                "The size, in bytes, after which a service's log file is rotated \
+                [default: 10485760]")
+            (@arg LOG_MAX_AGE: --("log-max-age") +takes_value {valid_numeric}
+                "The age, in seconds, after which a service's log file is rotated \
+                [default: 86400]")
             (@arg NAME: --("override-name") +takes_value
                 "The name of the Supervisor if launching more than one [default: default]")
             (@arg ORGANIZATION: --org +takes_value
@@ -384,6 +618,9 @@ fn cli<'a, 'b>() -> App<'a, 'b> {
             (@arg PEER: --peer +takes_value +multiple
                 "The listen address of an initial peer (IP[:PORT])")
             (@arg PERMANENT_PEER: --("permanent-peer") -I "If this Supervisor is a permanent peer")
+            (@arg TAG: --tag +takes_value +multiple {valid_tag}
+                "A KEY=VALUE tag, such as zone=us-east-1a, gossiped with this Supervisor's \
+                membership")
             (@arg PEER_WATCH_FILE: --("peer-watch-file") +takes_value conflicts_with[peer]
                 "Watch this file for connecting to the ring"
             )
@@ -409,8 +646,37 @@ fn cli<'a, 'b>() -> App<'a, 'b> {
             (aliases: &["sta", "star"])
             (@arg LISTEN_GOSSIP: --("listen-gossip") +takes_value {valid_listen_gossip}
                 "The listen address for the gossip system [default: 0.0.0.0:9638]")
+            (@arg GOSSIP_TRANSPORT: --("gossip-transport") +takes_value {valid_gossip_transport}
+                "How rumors are sent between Supervisors, either zeromq or tcp. Every member of \
+                the ring must use the same transport [default: zeromq]")
+            (@arg GOSSIP_SUSPICION_MULTIPLIER: --("gossip-suspicion-multiplier") +takes_value
+                {valid_numeric} "How many times longer than the minimum a suspect member is given \
+                before it is confirmed dead when no other member backs the suspicion. Set to 1 to \
+                always use the minimum [default: 6]")
             (@arg LISTEN_HTTP: --("listen-http") +takes_value {valid_listen_http}
                 "The listen address for the HTTP gateway [default: 0.0.0.0:9631]")
+            (@arg HTTP_TLS_CERT: --("http-tls-cert") +takes_value requires[HTTP_TLS_KEY]
+                {file_exists} "Serve the HTTP gateway over TLS with this PEM encoded certificate")
+            (@arg HTTP_TLS_KEY: --("http-tls-key") +takes_value requires[HTTP_TLS_CERT]
+                {file_exists} "The PEM encoded private key of the HTTP gateway's certificate")
+            (@arg HTTP_AUTH_TOKEN: --("http-auth-token") +takes_value
+                "Require this bearer token on every request to the HTTP gateway, and serve its \
+                write endpoints. A token given here is visible to other users in the process \
+                list; prefer $HAB_SUP_HTTP_AUTH_TOKEN or --http-auth-token-file \
+                [default: $HAB_SUP_HTTP_AUTH_TOKEN]")
+            (@arg HTTP_AUTH_TOKEN_FILE: --("http-auth-token-file") +takes_value
+                conflicts_with[HTTP_AUTH_TOKEN] {file_exists}
+                "Read the bearer token of the HTTP gateway from this file instead")
+            (@arg LOG_FORMAT: --("log-format") +takes_value {valid_log_format}
+                "The format of the output of services, either text or json. In json format the \
+                output of hooks and of services is also appended to each service's log files \
+                [default: text]")
+            (@arg LOG_MAX_SIZE: --("log-max-size") +takes_value {valid_numeric}
+                "The size, in bytes, after which a service's log file is rotated \
+                [default: 10485760]")
+            (@arg LOG_MAX_AGE: --("log-max-age") +takes_value {valid_numeric}
+                "The age, in seconds, after which a service's log file is rotated \
+                [default: 86400]")
             (@arg NAME: --("override-name") +takes_value
                 "The name for the state directory if launching more than one Supervisor \
                 [default: default]")
@@ -420,6 +686,9 @@ fn cli<'a, 'b>() -> App<'a, 'b> {
             (@arg PEER: --peer +takes_value +multiple
                 "The listen address of an initial peer (IP[:PORT])")
             (@arg PERMANENT_PEER: --("permanent-peer") -I "If this Supervisor is a permanent peer")
+            (@arg TAG: --tag +takes_value +multiple {valid_tag}
+                "A KEY=VALUE tag, such as zone=us-east-1a, gossiped with this Supervisor's \
+                membership")
             (@arg PEER_WATCH_FILE: --("peer-watch-file") +takes_value conflicts_with[peer]
                 "Watch this file for connecting to the ring"
             )
@@ -441,11 +710,40 @@ fn cli<'a, 'b>() -> App<'a, 'b> {
             (@arg TOPOLOGY: --topology -t +takes_value {valid_topology}
                 "Service topology; [default: none]")
             (@arg STRATEGY: --strategy -s +takes_value {valid_update_strategy}
-                "The update strategy; [default: none] [values: none, at-once, rolling]")
+                "The update strategy; [default: none] [values: none, at-once, rolling, canary]")
+            (@arg CANARY_PERCENT: --("canary-percent") +takes_value {valid_canary_percent}
+                "The percentage of the service group's members which test a new package first \
+                with the canary update strategy; [default: 10]")
+            (@arg CANARY_SOAK: --("canary-soak") +takes_value {valid_numeric}
+                "How long, in seconds, the canaries must stay healthy before the rest of the \
+                service group updates; [default: 300]")
+            (@arg ROLLBACK_WINDOW: --("rollback-window") +takes_value {valid_numeric}
+                "Roll a package update back if the new release fails its smoke test or health \
+                check within this many seconds; 0 disables rollbacks [default: 300]")
+            (@arg QUORUM_BASIS: --("quorum-basis") +takes_value {valid_quorum_basis}
+                "The members a leader election's quorum is counted against; [default: known] \
+                [values: known, alive]")
+            (@arg LEADER_LEASE: --("leader-lease") +takes_value {valid_numeric}
+                "How long, in seconds, an elected leader holds its lease without renewing it; \
+                0 disables leases [default: 0]")
+            (@arg RESTART_POLICY: --("restart-policy") +takes_value {valid_restart_policy}
+                "Whether to restart the service when its process exits; [default: always] \
+                [values: always, on-failure, never]")
             (@arg BIND: --bind +takes_value +multiple
                 "One or more service groups to bind to a configuration")
+            (@arg STRICT_TEMPLATES: --("strict-templates")
+                "Fail the compilation of the service's templates when they use a variable which \
+                isn't set, keeping its current configuration")
+            (@arg NO_STRICT_TEMPLATES: --("no-strict-templates") conflicts_with[STRICT_TEMPLATES]
+                "Compile the service's templates leniently again, unless its plan asks for strict \
+                templates")
+            (@arg SECRETS: --secrets +takes_value {valid_secrets_url}
+                "URL of the provider of the secrets read by the service's templates, a file:// \
+                directory or a Vault-compatible http(s):// API (ex: file:///hab/secrets/myapp)")
             (@arg CONFIG_DIR: --("config-from") +takes_value {dir_exists}
                 "Use package config from this path, rather than the package itself")
+            (@arg WAIT: --wait +takes_value {valid_numeric}
+                "Wait up to this many seconds for the service to reach its desired state")
             (@arg AUTO_UPDATE: --("auto-update") -A "Enable automatic updates for the Supervisor \
                 itself")
             (@arg EVENTS: --events -n +takes_value {valid_service_group} "Name of the service \
@@ -456,6 +754,8 @@ fn cli<'a, 'b>() -> App<'a, 'b> {
             (about: "Query the status of Habitat services.")
             (aliases: &["stat", "statu", "status"])
             (@arg PKG_IDENT: +takes_value "A Habitat package identifier (ex: core/redis)")
+            (@arg FORMAT: --format +takes_value {valid_status_format}
+                "Output format; [default: table] [values: table, json]")
             (@arg NAME: --("override-name") +takes_value
                 "The name for the state directory if there is more than one Supervisor running \
                 [default: default]")
@@ -464,6 +764,8 @@ fn cli<'a, 'b>() -> App<'a, 'b> {
             (about: "Stop a running Habitat service.")
             (aliases: &["sto"])
             (@arg PKG_IDENT: +required +takes_value "A Habitat package identifier (ex: core/redis)")
+            (@arg WAIT: --wait +takes_value {valid_numeric}
+                "Wait up to this many seconds for the service to reach its desired state")
             (@arg NAME: --("override-name") +takes_value
                 "The name for the state directory if there is more than one Supervisor running \
                 [default: default]")
@@ -491,20 +793,24 @@ fn sub_load(m: &ArgMatches) -> Result<()> {
 
     let cfg = mgrcfg_from_matches(m)?;
     let install_source = install_source_from_input(m)?;
+    load_specs(m, &cfg, &install_source)?;
+    wait_for_desired_state(m, &cfg, install_source.as_ref())
+}
 
+fn load_specs(m: &ArgMatches, cfg: &ManagerConfig, install_source: &InstallSource) -> Result<()> {
     // TODO (CM): should load be able to download new artifacts if
     // you're re-loading with --force?
     // If we've already got a spec for this thing, we don't want to
     // inadvertently download a new version
 
-    match existing_specs_for_ident(&cfg, install_source.as_ref().clone())? {
+    match existing_specs_for_ident(cfg, install_source.as_ref().clone())? {
         None => {
             // We don't have any record of this thing; let's set it
             // up!
             //
             // This will install the latest version from Builder
             let installed =
-                util::pkg::install(&mut ui(), &bldr_url(m), &install_source, &channel(m))?;
+                util::pkg::install(&mut ui(), &bldr_url(m), install_source, &channel(m))?;
 
             let original_ident = install_source.as_ref();
             let mut specs = generate_new_specs_from_package(original_ident, &installed, m)?;
@@ -512,7 +818,7 @@ fn sub_load(m: &ArgMatches) -> Result<()> {
             for spec in specs.iter_mut() {
                 // "load" == persistent services, by definition
                 spec.start_style = StartStyle::Persistent;
-                Manager::save_spec_for(&cfg, spec)?;
+                save_spec(cfg, spec, m.is_present("FORCE"))?;
                 outputln!("The {} service was successfully loaded", spec.ident);
             }
 
@@ -520,7 +826,7 @@ fn sub_load(m: &ArgMatches) -> Result<()> {
             if let Ok(composite_spec) =
                 CompositeSpec::from_package_install(&original_ident, &installed)
             {
-                Manager::save_composite_spec_for(&cfg, &composite_spec)?;
+                Manager::save_composite_spec_for(cfg, &composite_spec)?;
                 outputln!(
                     "The {} composite was successfully loaded",
                     composite_spec.ident()
@@ -554,12 +860,12 @@ fn sub_load(m: &ArgMatches) -> Result<()> {
                     // Also make sure you're pulling from where you're
                     // supposed to be pulling from!
                     install_package_if_not_present(
-                        &install_source,
+                        install_source,
                         &service_spec.bldr_url,
                         &service_spec.channel,
                     )?;
 
-                    Manager::save_spec_for(&cfg, &service_spec)?;
+                    save_spec(cfg, &service_spec, m.is_present("FORCE"))?;
                     outputln!("The {} service was successfully loaded", service_spec.ident);
                     Ok(())
                 }
@@ -579,7 +885,7 @@ fn sub_load(m: &ArgMatches) -> Result<()> {
                         )?;
 
                         for service_spec in existing_service_specs.iter() {
-                            Manager::save_spec_for(&cfg, service_spec)?;
+                            save_spec(cfg, service_spec, m.is_present("FORCE"))?;
                             outputln!("The {} service was successfully loaded", service_spec.ident);
                         }
                         outputln!(
@@ -599,7 +905,7 @@ fn sub_load(m: &ArgMatches) -> Result<()> {
                         // (because they were added to the composite).
 
                         let installed_package = install_package_if_not_present(
-                            &install_source,
+                            install_source,
                             // This (updating from the command-line
                             // args) is a difference from
                             // force-loading a spec, because
@@ -632,11 +938,7 @@ fn sub_load(m: &ArgMatches) -> Result<()> {
                             old_spec_names.difference(&new_spec_names).collect();
                         for spec in existing_service_specs.iter() {
                             if specs_to_delete.contains(&spec.ident.name) {
-                                let file = Manager::spec_path_for(&cfg, spec);
-                                outputln!("Unloading {:?}", file);
-                                std::fs::remove_file(&file).map_err(|err| {
-                                    sup_error!(Error::ServiceSpecFileIO(file, err))
-                                })?;
+                                unload_spec(cfg, spec)?;
                             }
                         }
                         // <-- end of deletion
@@ -647,7 +949,7 @@ fn sub_load(m: &ArgMatches) -> Result<()> {
                         // name, so they'll be taken care of here (we
                         // don't need to treat them differently)
                         for spec in new_service_specs.iter() {
-                            Manager::save_spec_for(&cfg, spec)?;
+                            save_spec(cfg, spec, m.is_present("FORCE"))?;
                             outputln!("The {} service was successfully loaded", spec.ident);
                         }
 
@@ -656,7 +958,7 @@ fn sub_load(m: &ArgMatches) -> Result<()> {
                             install_source.as_ref(),
                             &installed_package,
                         )?;
-                        Manager::save_composite_spec_for(&cfg, &new_composite_spec)?;
+                        Manager::save_composite_spec_for(cfg, &new_composite_spec)?;
                         outputln!(
                             "The {} composite was successfully loaded",
                             new_composite_spec.ident()
@@ -676,31 +978,45 @@ fn sub_unload(m: &ArgMatches) -> Result<()> {
     let cfg = mgrcfg_from_matches(m)?;
     let ident = PackageIdent::from_str(m.value_of("PKG_IDENT").unwrap())?;
 
-    // Gather up the paths to all the spec files we care about. This
-    // includes all service specs as well as any composite spec.
-    let spec_paths = match existing_specs_for_ident(&cfg, ident)? {
-        Some(Spec::Service(spec)) => vec![Manager::spec_path_for(&cfg, &spec)],
+    match existing_specs_for_ident(&cfg, ident)? {
+        Some(Spec::Service(spec)) => unload_spec(&cfg, &spec)?,
         Some(Spec::Composite(composite_spec, specs)) => {
-            let mut paths = Vec::with_capacity(specs.len() + 1);
             for spec in specs.iter() {
-                paths.push(Manager::spec_path_for(&cfg, spec));
+                unload_spec(&cfg, spec)?;
             }
-            paths.push(Manager::composite_path_for(&cfg, &composite_spec));
-            paths
+            // Composites aren't known to the running Supervisor, only their services are.
+            let file = Manager::composite_path_for(&cfg, &composite_spec);
+            outputln!("Unloading {:?}", file);
+            std::fs::remove_file(&file).map_err(|err| {
+                sup_error!(Error::ServiceSpecFileIO(file, err))
+            })?;
         }
-        None => vec![],
-    };
-
-    for file in spec_paths {
-        outputln!("Unloading {:?}", file);
-        std::fs::remove_file(&file).map_err(|err| {
-            sup_error!(Error::ServiceSpecFileIO(file, err))
-        })?;
+        None => (),
     }
 
     Ok(())
 }
 
+fn sub_render(m: &ArgMatches) -> Result<()> {
+    toggle_verbosity(m);
+    toggle_color(m);
+
+    let mut binds = Vec::new();
+    if let Some(bind_strs) = m.values_of("BIND") {
+        for bind_str in bind_strs {
+            binds.push(ServiceBind::from_str(bind_str)?);
+        }
+    }
+    command::render::start(
+        m.value_of("PKG_IDENT_OR_PLAN").unwrap(),
+        m.value_of("GROUP").unwrap_or("default"),
+        m.value_of("USER_TOML").map(Path::new),
+        m.value_of("CENSUS").map(Path::new),
+        &binds,
+        Path::new(m.value_of("OUTPUT").unwrap_or("results/render")),
+    )
+}
+
 fn sub_run(m: &ArgMatches, launcher: LauncherCli) -> Result<()> {
     let cfg = mgrcfg_from_matches(m)?;
     let mut manager = Manager::load(cfg, launcher)?;
@@ -783,8 +1099,9 @@ fn sub_start(m: &ArgMatches, launcher: LauncherCli) -> Result<()> {
 
     let specs_changed = updated_specs.len() > 0;
 
+    // Starting a service changes the desired state of its existing spec.
     for spec in updated_specs.iter() {
-        Manager::save_spec_for(&cfg, spec)?;
+        save_spec(&cfg, spec, true)?;
     }
 
     if Manager::is_running(&cfg)? {
@@ -793,7 +1110,7 @@ fn sub_start(m: &ArgMatches, launcher: LauncherCli) -> Result<()> {
                 "Supervisor starting {}. See the Supervisor output for more details.",
                 original_ident
             );
-            Ok(())
+            wait_for_desired_state(m, &cfg, original_ident)
         } else {
             // TODO (CM): somehow, this doesn't actually seem to be
             // exiting with a non-zero exit code
@@ -828,6 +1145,7 @@ fn sub_status(m: &ArgMatches) -> Result<()> {
                 }
             };
             print_statuses(
+                m,
                 specs
                     .iter()
                     .filter_map(|spec| Manager::service_status(&cfg, &spec.ident).ok())
@@ -835,13 +1153,20 @@ fn sub_status(m: &ArgMatches) -> Result<()> {
             )?;
         }
         None => {
-            print_statuses(Manager::status(&cfg)?)?;
+            print_statuses(m, Manager::status(&cfg)?)?;
         }
     }
     Ok(())
 }
 
-fn print_statuses(statuses: Vec<ServiceStatus>) -> Result<()> {
+fn print_statuses(m: &ArgMatches, statuses: Vec<ServiceStatus>) -> Result<()> {
+    if m.value_of("FORMAT") == Some("json") {
+        let json = serde_json::to_string_pretty(&statuses).map_err(|e| {
+            sup_error!(Error::ServiceSerializationError(e))
+        })?;
+        println!("{}", json);
+        return Ok(());
+    }
     if statuses.is_empty() {
         println!("No services loaded.");
         return Ok(());
@@ -854,23 +1179,90 @@ fn print_statuses(statuses: Vec<ServiceStatus>) -> Result<()> {
         "pid",
         "group",
         "style",
+        "health",
+        "election",
+        "role",
+        "update",
+        "unsatisfied binds",
+        "last hook failure",
+        "last rollback",
+        "template failure",
     ];
     let mut tw = TabWriter::new(io::stdout());
     write!(tw, "{}\n", titles.join("\t"))?;
     for status in statuses {
+        let state = if status.process.failed {
+            "failed".to_string()
+        } else {
+            status.process.state.to_string()
+        };
+        let election = match status.topology {
+            Topology::Leader => status.election_status.to_string(),
+            Topology::Standalone => "-".to_string(),
+        };
+        let update = match status.update_status {
+            Some(update) => format!("{} ({})", status.update_strategy, update),
+            None => status.update_strategy.to_string(),
+        };
+        let binds = if status.unsatisfied_binds.is_empty() {
+            "-".to_string()
+        } else {
+            status.unsatisfied_binds.join(",")
+        };
+        let hook_failure = match status.last_hook_failure {
+            Some(failure) => {
+                match failure.exit_code {
+                    Some(code) => {
+                        format!("{} exited {} at {}", failure.hook, code, failure.timestamp)
+                    }
+                    None => format!("{} failed at {}", failure.hook, failure.timestamp),
+                }
+            }
+            None => "-".to_string(),
+        };
+        let rollback = match status.last_rollback {
+            Some(rollback) => {
+                format!(
+                    "{} to {} at {} ({})",
+                    rollback.from,
+                    rollback.to,
+                    rollback.timestamp,
+                    rollback.reason
+                )
+            }
+            None => "-".to_string(),
+        };
+        let template_failure = match status.last_template_failure {
+            Some(failure) => {
+                format!(
+                    "{} at {}",
+                    failure.template.unwrap_or("template".to_string()),
+                    failure.timestamp
+                )
+            }
+            None => "-".to_string(),
+        };
         write!(
             tw,
-            "{}\t{}\t{}\t{}\t{}\t{}\t{}\n",
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\n",
             status.pkg.ident,
             status.composite.unwrap_or("standalone".to_string()),
-            status.process.state,
+            state,
             status.process.elapsed.num_seconds(),
             status.process.pid.map(|p| p.to_string()).unwrap_or(
                 "<none>"
                     .to_string(),
             ),
             status.service_group,
-            status.start_style
+            status.start_style,
+            status.health_check,
+            election,
+            status.role.map(|r| r.to_string()).unwrap_or("-".to_string()),
+            update,
+            binds,
+            hook_failure,
+            rollback,
+            template_failure
         )?;
     }
     tw.flush()?;
@@ -885,7 +1277,7 @@ fn sub_stop(m: &ArgMatches) -> Result<()> {
 
     // PKG_IDENT is required, so unwrap() is safe
     let ident = PackageIdent::from_str(m.value_of("PKG_IDENT").unwrap())?;
-    let mut specs = match existing_specs_for_ident(&cfg, ident)? {
+    let mut specs = match existing_specs_for_ident(&cfg, ident.clone())? {
         Some(Spec::Service(spec)) => vec![spec],
         Some(Spec::Composite(_, specs)) => specs,
         None => vec![],
@@ -893,10 +1285,10 @@ fn sub_stop(m: &ArgMatches) -> Result<()> {
 
     for spec in specs.iter_mut() {
         spec.desired_state = DesiredState::Down;
-        Manager::save_spec_for(&cfg, &spec)?;
+        save_spec(&cfg, &spec, true)?;
     }
 
-    Ok(())
+    wait_for_desired_state(m, &cfg, &ident)
 }
 
 fn sub_term(m: &ArgMatches) -> Result<()> {
@@ -971,6 +1363,109 @@ fn existing_specs_for_ident(cfg: &ManagerConfig, ident: PackageIdent) -> Result<
     }
 }
 
+/// Hand a spec to the running Supervisor, which validates it before loading it. If no
+/// Supervisor is listening for commands the spec file is written for it to pick up later.
+///
+/// The Supervisor refuses to replace the spec of a service which is already loaded unless
+/// `force` is set.
+fn save_spec(cfg: &ManagerConfig, spec: &ServiceSpec, force: bool) -> Result<()> {
+    let request = CtlRequest::Load {
+        spec: spec.clone(),
+        force: force,
+    };
+    match ctl_request(cfg, &request)? {
+        Some(_) => Ok(()),
+        None => Manager::save_spec_for(cfg, spec),
+    }
+}
+
+/// Unload a service through the running Supervisor, or remove its spec file directly if no
+/// Supervisor is listening for commands.
+fn unload_spec(cfg: &ManagerConfig, spec: &ServiceSpec) -> Result<()> {
+    let file = Manager::spec_path_for(cfg, spec);
+    outputln!("Unloading {:?}", file);
+    let request = CtlRequest::Unload { ident: spec.ident.to_string() };
+    if ctl_request(cfg, &request)?.is_none() {
+        std::fs::remove_file(&file).map_err(|err| {
+            sup_error!(Error::ServiceSpecFileIO(file, err))
+        })?;
+    }
+    Ok(())
+}
+
+/// Returns the state of the process of a service, `None` if the service isn't running.
+fn service_state(cfg: &ManagerConfig, ident: &PackageIdent) -> Result<Option<ProcessState>> {
+    let request = CtlRequest::Status { ident: ident.to_string() };
+    match ctl_request(cfg, &request)? {
+        Some(CtlReply::Status(state)) => Ok(state),
+        _ => Ok(
+            Manager::service_status(cfg, ident)
+                .ok()
+                .map(|status| status.process.state),
+        ),
+    }
+}
+
+/// Block until every service of the given package has reached its desired state, if the `--wait`
+/// option was given.
+fn wait_for_desired_state(m: &ArgMatches, cfg: &ManagerConfig, ident: &PackageIdent) -> Result<()> {
+    let timeout = match m.value_of("WAIT") {
+        Some(secs) => Duration::from_secs(secs.parse().expect("WAIT validated by clap")),
+        None => return Ok(()),
+    };
+    if !Manager::is_running(cfg)? {
+        return Ok(());
+    }
+    let specs = match existing_specs_for_ident(cfg, ident.clone())? {
+        Some(Spec::Service(spec)) => vec![spec],
+        Some(Spec::Composite(_, specs)) => specs,
+        None => vec![],
+    };
+    let deadline = Instant::now() + timeout;
+    for spec in specs {
+        let expected = match spec.desired_state {
+            DesiredState::Up => ProcessState::Up,
+            DesiredState::Down => ProcessState::Down,
+        };
+        loop {
+            match service_state(cfg, &spec.ident)? {
+                Some(ref state) if *state == expected => break,
+                // A service which isn't running is as down as it gets.
+                None if expected == ProcessState::Down => break,
+                _ => (),
+            }
+            if Instant::now() >= deadline {
+                return Err(sup_error!(Error::CtlWaitTimeout(
+                    spec.ident.clone(),
+                    spec.desired_state.to_string(),
+                )));
+            }
+            thread::sleep(Duration::from_millis(WAIT_POLL_MS));
+        }
+        outputln!("The {} service is {}", spec.ident, spec.desired_state);
+    }
+    Ok(())
+}
+
+/// Send a command to the control gateway of the running Supervisor. Returns `None` if there is no
+/// Supervisor listening for commands.
+#[cfg(unix)]
+fn ctl_request(cfg: &ManagerConfig, request: &CtlRequest) -> Result<Option<CtlReply>> {
+    let socket_path = Manager::ctl_socket_path(cfg);
+    if !Manager::is_running(cfg)? || !socket_path.exists() {
+        return Ok(None);
+    }
+    match ctl_gateway::send(&socket_path, request)? {
+        CtlReply::Err(reason) => Err(sup_error!(Error::CtlRejected(reason))),
+        reply => Ok(Some(reply)),
+    }
+}
+
+#[cfg(windows)]
+fn ctl_request(_cfg: &ManagerConfig, _request: &CtlRequest) -> Result<Option<CtlReply>> {
+    Ok(None)
+}
+
 fn mgrcfg_from_matches(m: &ArgMatches) -> Result<ManagerConfig> {
     let mut cfg = ManagerConfig::default();
 
@@ -980,9 +1475,32 @@ fn mgrcfg_from_matches(m: &ArgMatches) -> Result<ManagerConfig> {
     if let Some(addr_str) = m.value_of("LISTEN_GOSSIP") {
         cfg.gossip_listen = GossipListenAddr::from_str(addr_str)?;
     }
+    if let Some(transport) = m.value_of("GOSSIP_TRANSPORT") {
+        cfg.gossip_transport = GossipTransportKind::from_str(transport)?;
+    }
+    if let Some(multiplier) = m.value_of("GOSSIP_SUSPICION_MULTIPLIER") {
+        cfg.gossip_suspicion_multiplier = Some(multiplier.parse().expect(
+            "GOSSIP_SUSPICION_MULTIPLIER validated by clap",
+        ));
+    }
     if let Some(addr_str) = m.value_of("LISTEN_HTTP") {
         cfg.http_listen = http_gateway::ListenAddr::from_str(addr_str)?;
     }
+    if let (Some(cert), Some(key)) = (m.value_of("HTTP_TLS_CERT"), m.value_of("HTTP_TLS_KEY")) {
+        cfg.http_tls = Some(http_gateway::TlsCfg {
+            cert_path: PathBuf::from(cert),
+            key_path: PathBuf::from(key),
+        });
+    }
+    if let Some(format) = m.value_of("LOG_FORMAT") {
+        cfg.log.format = LogFormat::from_str(format)?;
+    }
+    if let Some(size) = m.value_of("LOG_MAX_SIZE") {
+        cfg.log.max_size = size.parse().expect("LOG_MAX_SIZE validated by clap");
+    }
+    if let Some(age) = m.value_of("LOG_MAX_AGE") {
+        cfg.log.max_age = age.parse().expect("LOG_MAX_AGE validated by clap");
+    }
     if let Some(name_str) = m.value_of("NAME") {
         cfg.name = Some(String::from(name_str));
         outputln!("");
@@ -995,6 +1513,12 @@ fn mgrcfg_from_matches(m: &ArgMatches) -> Result<ManagerConfig> {
     }
     cfg.organization = m.value_of("ORGANIZATION").map(|org| org.to_string());
     cfg.gossip_permanent = m.is_present("PERMANENT_PEER");
+    if let Some(tags) = m.values_of("TAG") {
+        for tag in tags {
+            let (key, value) = parse_tag(tag)?;
+            cfg.tags.insert(key, value);
+        }
+    }
     // TODO fn: Clean this up--using a for loop doesn't feel good however an iterator was
     // causing a lot of developer/compiler type confusion
     let mut gossip_peers: Vec<SocketAddr> = Vec::new();
@@ -1020,10 +1544,13 @@ fn mgrcfg_from_matches(m: &ArgMatches) -> Result<ManagerConfig> {
     if let Some(watch_peer_file) = m.value_of("PEER_WATCH_FILE") {
         cfg.watch_peer_file = Some(String::from(watch_peer_file));
     }
+    // A revision of the ring key which is still waiting for its activation time is already in
+    // the cache; join with the one the ring is sending with.
     let ring = match m.value_of("RING") {
-        Some(val) => Some(SymKey::get_latest_pair_for(
+        Some(val) => Some(active_ring_key(
             &val,
             &default_cache_key_path(None),
+            time::get_time().sec,
         )?),
         None => {
             match henv::var(RING_KEY_ENVVAR) {
@@ -1035,9 +1562,10 @@ fn mgrcfg_from_matches(m: &ArgMatches) -> Result<ManagerConfig> {
                 Err(_) => {
                     match henv::var(RING_ENVVAR) {
                         Ok(val) => {
-                            Some(SymKey::get_latest_pair_for(
+                            Some(active_ring_key(
                                 &val,
                                 &default_cache_key_path(None),
+                                time::get_time().sec,
                             )?)
                         }
                         Err(_) => None,
@@ -1052,9 +1580,40 @@ fn mgrcfg_from_matches(m: &ArgMatches) -> Result<ManagerConfig> {
     if let Some(events) = m.value_of("EVENTS") {
         cfg.eventsrv_group = ServiceGroup::from_str(events).ok();
     }
+    cfg.http_auth_token = match m.value_of("HTTP_AUTH_TOKEN") {
+        Some(token) => Some(token.to_string()),
+        None => {
+            match m.value_of("HTTP_AUTH_TOKEN_FILE") {
+                Some(path) => Some(read_http_auth_token(Path::new(path))?),
+                None => henv::var(HTTP_AUTH_TOKEN_ENVVAR).ok(),
+            }
+        }
+    };
     Ok(cfg)
 }
 
+/// Reads the HTTP gateway's bearer token from a file, ignoring the whitespace and trailing
+/// newline around it.
+fn read_http_auth_token(path: &Path) -> Result<String> {
+    let mut token = String::new();
+    File::open(path)
+        .and_then(|mut file| file.read_to_string(&mut token))
+        .map_err(|err| {
+            sup_error!(Error::BadHttpAuthTokenFile(
+                path.to_path_buf(),
+                err.to_string(),
+            ))
+        })?;
+    let token = token.trim();
+    if token.is_empty() {
+        return Err(sup_error!(Error::BadHttpAuthTokenFile(
+            path.to_path_buf(),
+            "the file is empty".to_string(),
+        )));
+    }
+    Ok(token.to_string())
+}
+
 // Various CLI Parsing Functions
 ////////////////////////////////////////////////////////////////////////
 
@@ -1156,13 +1715,75 @@ fn set_topology_from_input(spec: &mut ServiceSpec, m: &ArgMatches) {
     }
 }
 
-/// Set an update strategy only if specified by the user as a CLI
+/// Set an update strategy and its canary settings only if specified by the user as a CLI
 /// argument.
 fn set_strategy_from_input(spec: &mut ServiceSpec, m: &ArgMatches) {
     if let Some(s) = m.value_of("STRATEGY") {
         // unwrap() is safe, because the input is validated by `valid_update_strategy`
         spec.update_strategy = UpdateStrategy::from_str(s).unwrap();
     }
+    if let Some(p) = m.value_of("CANARY_PERCENT") {
+        // unwrap() is safe, because the input is validated by `valid_canary_percent`
+        spec.canary_percent = p.parse().unwrap();
+    }
+    if let Some(s) = m.value_of("CANARY_SOAK") {
+        // unwrap() is safe, because the input is validated by `valid_numeric`
+        spec.canary_soak = s.parse().unwrap();
+    }
+}
+
+/// Set a restart policy only if specified by the user as a CLI
+/// argument.
+fn set_restart_policy_from_input(spec: &mut ServiceSpec, m: &ArgMatches) {
+    if let Some(p) = m.value_of("RESTART_POLICY") {
+        // unwrap() is safe, because the input is validated by `valid_restart_policy`
+        spec.restart_policy = RestartPolicy::from_str(p).unwrap();
+    }
+}
+
+/// Set a rollback window only if specified by the user as a CLI
+/// argument.
+fn set_rollback_window_from_input(spec: &mut ServiceSpec, m: &ArgMatches) {
+    if let Some(w) = m.value_of("ROLLBACK_WINDOW") {
+        // unwrap() is safe, because the input is validated by `valid_numeric`
+        spec.rollback_window = w.parse().unwrap();
+    }
+}
+
+/// Set a quorum basis only if specified by the user as a CLI
+/// argument.
+fn set_quorum_basis_from_input(spec: &mut ServiceSpec, m: &ArgMatches) {
+    if let Some(b) = m.value_of("QUORUM_BASIS") {
+        // unwrap() is safe, because the input is validated by `valid_quorum_basis`
+        spec.quorum_basis = QuorumBasis::from_str(b).unwrap();
+    }
+}
+
+/// Set a leader lease only if specified by the user as a CLI
+/// argument.
+fn set_leader_lease_from_input(spec: &mut ServiceSpec, m: &ArgMatches) {
+    if let Some(l) = m.value_of("LEADER_LEASE") {
+        // unwrap() is safe, because the input is validated by `valid_numeric`
+        spec.leader_lease = l.parse().unwrap();
+    }
+}
+
+/// Turn strict templates on or off only if specified by the user as a
+/// CLI argument.
+fn set_strict_templates_from_input(spec: &mut ServiceSpec, m: &ArgMatches) {
+    if m.is_present("STRICT_TEMPLATES") {
+        spec.strict_templates = true;
+    } else if m.is_present("NO_STRICT_TEMPLATES") {
+        spec.strict_templates = false;
+    }
+}
+
+/// Set the secrets provider if specified by the user as a CLI
+/// argument.
+fn set_secrets_from_input(spec: &mut ServiceSpec, m: &ArgMatches) {
+    if let Some(url) = m.value_of("SECRETS") {
+        spec.secrets = Some(url.to_string());
+    }
 }
 
 /// Set bind values if given on the command line.
@@ -1283,6 +1904,12 @@ fn new_service_spec(ident: PackageIdent, m: &ArgMatches) -> Result<ServiceSpec>
     set_app_env_from_input(&mut spec, m)?;
     set_group_from_input(&mut spec, m);
     set_strategy_from_input(&mut spec, m);
+    set_restart_policy_from_input(&mut spec, m);
+    set_rollback_window_from_input(&mut spec, m);
+    set_quorum_basis_from_input(&mut spec, m);
+    set_leader_lease_from_input(&mut spec, m);
+    set_strict_templates_from_input(&mut spec, m);
+    set_secrets_from_input(&mut spec, m);
     set_topology_from_input(&mut spec, m);
     set_binds_from_input(&mut spec, m)?;
     set_config_from_input(&mut spec, m)?;
@@ -1299,6 +1926,12 @@ fn update_spec_from_input(mut spec: &mut ServiceSpec, m: &ArgMatches) -> Result<
     set_app_env_from_input(&mut spec, m)?;
     set_group_from_input(&mut spec, m);
     set_strategy_from_input(&mut spec, m);
+    set_restart_policy_from_input(&mut spec, m);
+    set_rollback_window_from_input(&mut spec, m);
+    set_quorum_basis_from_input(&mut spec, m);
+    set_leader_lease_from_input(&mut spec, m);
+    set_strict_templates_from_input(&mut spec, m);
+    set_secrets_from_input(&mut spec, m);
     set_topology_from_input(&mut spec, m);
 
     // TODO (CM): Remove these for composite-member specs
@@ -1337,6 +1970,12 @@ fn base_composite_service_spec(composite_name: &str, m: &ArgMatches) -> Result<S
     // update strategy and topology, though we may want to revisit
     // this in the future (particularly for topology).
     set_strategy_from_input(&mut spec, m);
+    set_restart_policy_from_input(&mut spec, m);
+    set_rollback_window_from_input(&mut spec, m);
+    set_quorum_basis_from_input(&mut spec, m);
+    set_leader_lease_from_input(&mut spec, m);
+    set_strict_templates_from_input(&mut spec, m);
+    set_secrets_from_input(&mut spec, m);
     set_topology_from_input(&mut spec, m);
 
     // TODO (CM): Not dealing with service passwords for now, since
@@ -1391,6 +2030,10 @@ fn set_composite_binds(
         // `hab svc start`. We don't have a way from `hab svc load` to
         // access the organization setting of an active supervisor,
         // and so we can't generate binds that include organizations.
+        //
+        // The composite can't say which members provide a bind, so
+        // any tags the bind was given on the CLI when the composite
+        // was last loaded are carried over.
         for bind_mapping in bind_mappings.iter() {
             let group = ServiceGroup::new(
                 spec.application_environment.as_ref(),
@@ -1398,9 +2041,15 @@ fn set_composite_binds(
                 &spec.group,
                 None, // <-- organization
             )?;
+            let tags = spec.binds
+                .iter()
+                .find(|b| b.name == bind_mapping.bind_name)
+                .map(|b| b.tags.clone())
+                .unwrap_or_default();
             let bind = ServiceBind {
                 name: bind_mapping.bind_name.clone(),
                 service_group: group,
+                tags: tags,
             };
             final_binds.insert(bind.name.clone(), bind);
         }
@@ -1434,6 +2083,21 @@ fn dir_exists(val: String) -> result::Result<(), String> {
     }
 }
 
+fn file_exists(val: String) -> result::Result<(), String> {
+    if Path::new(&val).is_file() {
+        Ok(())
+    } else {
+        Err(format!("File: '{}' cannot be found", &val))
+    }
+}
+
+fn valid_secrets_url(val: String) -> result::Result<(), String> {
+    match secrets::provider_from_url(&val) {
+        Ok(_) => Ok(()),
+        Err(err) => Err(err.to_string()),
+    }
+}
+
 fn valid_service_group(val: String) -> result::Result<(), String> {
     match ServiceGroup::validate(&val) {
         Ok(()) => Ok(()),
@@ -1457,6 +2121,15 @@ fn valid_listen_gossip(val: String) -> result::Result<(), String> {
     }
 }
 
+fn valid_gossip_transport(val: String) -> result::Result<(), String> {
+    match GossipTransportKind::from_str(&val) {
+        Ok(_) => Ok(()),
+        Err(_) => Err(format!(
+            "Gossip transport should be either 'zeromq' or 'tcp'"
+        )),
+    }
+}
+
 fn valid_listen_http(val: String) -> result::Result<(), String> {
     match ListenAddr::from_str(&val) {
         Ok(_) => Ok(()),
@@ -1466,6 +2139,13 @@ fn valid_listen_http(val: String) -> result::Result<(), String> {
     }
 }
 
+fn valid_tag(val: String) -> result::Result<(), String> {
+    match parse_tag(&val) {
+        Ok(_) => Ok(()),
+        Err(err) => Err(err.to_string()),
+    }
+}
+
 fn valid_update_strategy(val: String) -> result::Result<(), String> {
     match UpdateStrategy::from_str(&val) {
         Ok(_) => Ok(()),
@@ -1473,6 +2153,48 @@ fn valid_update_strategy(val: String) -> result::Result<(), String> {
     }
 }
 
+fn valid_quorum_basis(val: String) -> result::Result<(), String> {
+    match QuorumBasis::from_str(&val) {
+        Ok(_) => Ok(()),
+        Err(_) => Err(format!("Quorum basis: '{}' is not valid", &val)),
+    }
+}
+
+fn valid_canary_percent(val: String) -> result::Result<(), String> {
+    match val.parse::<u32>() {
+        Ok(percent) if percent >= 1 && percent <= 100 => Ok(()),
+        _ => Err(format!("'{}' is not a percentage between 1 and 100", &val)),
+    }
+}
+
+fn valid_restart_policy(val: String) -> result::Result<(), String> {
+    match RestartPolicy::from_str(&val) {
+        Ok(_) => Ok(()),
+        Err(_) => Err(format!("Restart policy: '{}' is not valid", &val)),
+    }
+}
+
+fn valid_log_format(val: String) -> result::Result<(), String> {
+    match LogFormat::from_str(&val) {
+        Ok(_) => Ok(()),
+        Err(_) => Err(format!("Log format: '{}' is not valid", &val)),
+    }
+}
+
+fn valid_status_format(val: String) -> result::Result<(), String> {
+    match val.as_str() {
+        "table" | "json" => Ok(()),
+        _ => Err(format!("Status format: '{}' is not valid", &val)),
+    }
+}
+
+fn valid_numeric(val: String) -> result::Result<(), String> {
+    match val.parse::<u64>() {
+        Ok(_) => Ok(()),
+        Err(_) => Err(format!("'{}' is not a valid number", &val)),
+    }
+}
+
 fn valid_url(val: String) -> result::Result<(), String> {
     match Url::parse(&val) {
         Ok(_) => Ok(()),
@@ -1582,6 +2304,11 @@ fn update_composite_service_specs(
         set_app_env_from_input(spec, m)?;
         set_group_from_input(spec, m);
         set_strategy_from_input(spec, m);
+        set_restart_policy_from_input(spec, m);
+        set_rollback_window_from_input(spec, m);
+        set_quorum_basis_from_input(spec, m);
+        set_leader_lease_from_input(spec, m);
+        set_strict_templates_from_input(spec, m);
         set_topology_from_input(spec, m);
 
         // No setting of config or password either; see notes in
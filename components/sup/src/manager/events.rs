@@ -24,11 +24,12 @@ use eventsrv_client::message::{EventEnvelope, EventEnvelope_Type,
                                PackageIdent as PackageIdentProto, SysInfo as SysInfoProto};
 use hcore::service::ServiceGroup;
 use protobuf::Message;
+use serde_json;
 use toml;
 
 use PRODUCT;
 use census::{CensusMember, CensusRing};
-use manager::service::Service;
+use manager::service::{Rollback, RollbackReason, Service};
 
 enum Command {
     SendEvent(EventEnvelope),
@@ -65,6 +66,23 @@ impl EventsCli {
         self.tx.send(Command::SendEvent(event)).unwrap();
     }
 
+    /// Reports a package update of the given service which was rolled back. The event service
+    /// protocol has no message for rollbacks so they're sent as JSON.
+    pub fn send_rollback(&self, service: &Service, rollback: &Rollback) {
+        let mut payload_buf: Vec<u8> = vec![];
+        let mut json_size = vec![0; 8];
+        let mut bytes = build_rollback(service, rollback).into_bytes();
+        LittleEndian::write_u64(&mut json_size, bytes.len() as u64);
+        payload_buf.append(&mut json_size);
+        payload_buf.append(&mut bytes);
+        let mut event = EventEnvelope::new();
+        event.set_field_type(EventEnvelope_Type::JSON);
+        event.set_payload(payload_buf);
+        event.set_member_id(service.sys.member_id.clone());
+        event.set_service(PRODUCT.to_string());
+        self.tx.send(Command::SendEvent(event)).unwrap();
+    }
+
     pub fn try_connect(&self, census: &CensusRing) {
         if let Some(cg) = census.census_group_for(&self.group) {
             // JW TODO: We're over allocating here. We should determine who we are already
@@ -204,3 +222,55 @@ fn build_service_update(member: &CensusMember, service: &Service) -> ServiceUpda
     sep.set_update_election_is_finished(member.update_election_is_finished);
     sep
 }
+
+/// A package update which was rolled back, as reported to the event service.
+#[derive(Serialize)]
+struct RollbackEvent<'a> {
+    member_id: &'a str,
+    service_group: String,
+    from: &'a str,
+    to: &'a str,
+    reason: RollbackReason,
+    timestamp: &'a str,
+}
+
+fn build_rollback(service: &Service, rollback: &Rollback) -> String {
+    rollback_event(&service.sys.member_id, &service.service_group, rollback)
+}
+
+fn rollback_event(member_id: &str, service_group: &ServiceGroup, rollback: &Rollback) -> String {
+    let event = RollbackEvent {
+        member_id: member_id,
+        service_group: service_group.to_string(),
+        from: &rollback.from,
+        to: &rollback.to,
+        reason: rollback.reason,
+        timestamp: &rollback.timestamp,
+    };
+    serde_json::to_string(&event).expect("Rollback events always serialize")
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::Value;
+
+    use super::*;
+
+    #[test]
+    fn rollback_event_is_json() {
+        let rollback = Rollback {
+            from: "core/redis/3.2.4/20170520150022".to_string(),
+            to: "core/redis/3.2.4/20170514150022".to_string(),
+            reason: RollbackReason::SmokeTestFailed(2),
+            timestamp: "2017-05-20T15:00:22Z".to_string(),
+        };
+        let sg = ServiceGroup::from_str("redis.default").unwrap();
+        let event: Value = serde_json::from_str(&rollback_event("abc", &sg, &rollback)).unwrap();
+        assert_eq!("abc", event["member_id"]);
+        assert_eq!("redis.default", event["service_group"]);
+        assert_eq!("core/redis/3.2.4/20170520150022", event["from"]);
+        assert_eq!("core/redis/3.2.4/20170514150022", event["to"]);
+        assert_eq!(2, event["reason"]["SmokeTestFailed"]);
+        assert_eq!("2017-05-20T15:00:22Z", event["timestamp"]);
+    }
+}
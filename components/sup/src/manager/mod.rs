@@ -25,7 +25,7 @@ mod peer_watcher;
 mod user_config_watcher;
 mod sys;
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fmt;
 use std::fs::{self, File, OpenOptions};
 use std::io::{self, BufRead, BufReader, BufWriter, Read, Write};
@@ -35,15 +35,18 @@ use std::result;
 use std::thread;
 use std::str::FromStr;
 use std::sync::{Arc, RwLock};
+use std::sync::mpsc::{self, Receiver, Sender};
 use std::time::Duration;
 use std::mem;
 use std::ops::DerefMut;
 
 use butterfly;
-use butterfly::member::Member;
+use butterfly::member::{Health, Member};
+use butterfly::rumor::election::ElectionConfig;
 use butterfly::trace::Trace;
 use butterfly::server::timing::Timing;
 use butterfly::server::Suitability;
+use butterfly::transport::{GossipTransportKind, LINGER_DEFAULT_MS};
 use hcore::crypto::{default_cache_key_path, SymKey};
 use hcore::env;
 use hcore::fs::FS_ROOT_PATH;
@@ -56,9 +59,12 @@ use serde_json;
 use time::{self, Timespec, Duration as TimeDuration};
 
 pub use self::service::{CompositeSpec, Service, ServiceBind, ServiceSpec, UpdateStrategy, Topology};
-pub use self::sys::Sys;
+pub use self::sys::{parse_tag, Sys};
 use self::self_updater::{SUP_PKG_IDENT, SelfUpdater};
-use self::service::{DesiredState, Pkg, ProcessState, StartStyle};
+use self::service::{DesiredState, ElectionRole, HealthCheck, Pkg, ProcessState, Rollback,
+                    StartStyle, TemplateFailure, UpdateStatus};
+use self::service::hooks::HookFailure;
+use self::service::secrets;
 use self::service_updater::ServiceUpdater;
 use self::spec_watcher::{SpecWatcher, SpecWatcherEvent};
 use self::peer_watcher::PeerWatcher;
@@ -66,11 +72,16 @@ use self::user_config_watcher::UserConfigWatcher;
 use VERSION;
 use error::{Error, Result, SupError};
 use config::GossipListenAddr;
-use census::CensusRing;
+use census::{CensusRing, ElectionStatus};
+#[cfg(unix)]
+use ctl_gateway;
 use http_gateway;
+use logging::{self, LogCfg};
+use metrics;
 
 const MEMBER_ID_FILE: &'static str = "MEMBER_ID";
 const PROC_LOCK_FILE: &'static str = "LOCK";
+const CTL_SOCKET_FILE: &'static str = "ctl.sock";
 
 static LOGKEY: &'static str = "MR";
 
@@ -81,6 +92,31 @@ lazy_static! {
     };
 }
 
+/// A command for the Manager to carry out on a loaded service, sent by the `http_gateway`.
+#[derive(Debug)]
+pub enum ServiceCommand {
+    /// Start the service running the given service group, and keep it up from now on.
+    Start(ServiceGroup),
+    /// Stop the service running the given service group, and keep it down from now on.
+    Stop(ServiceGroup),
+}
+
+/// The Manager's reply to a `ServiceCommand`.
+#[derive(Debug, PartialEq)]
+pub enum CommandReply {
+    /// The service was started or stopped.
+    Done,
+    /// The service was already in the requested state.
+    Unchanged,
+    /// No service is loaded for the service group.
+    NotLoaded,
+    /// The command failed, with the reason why.
+    Failed(String),
+}
+
+/// Sends `ServiceCommand`s to the Manager, each with the channel to send its reply on.
+pub type CommandSender = Sender<(ServiceCommand, Sender<CommandReply>)>;
+
 /// FileSystem paths that the Manager uses to persist data to disk.
 ///
 /// This is shared with the `http_gateway` and `service` modules for reading and writing
@@ -96,6 +132,7 @@ pub struct FsCfg {
     composites_path: PathBuf,
     member_id_file: PathBuf,
     proc_lock_file: PathBuf,
+    ctl_socket_file: PathBuf,
 }
 
 impl FsCfg {
@@ -114,6 +151,7 @@ impl FsCfg {
             data_path: data_path,
             member_id_file: sup_svc_root.join(MEMBER_ID_FILE),
             proc_lock_file: sup_svc_root.join(PROC_LOCK_FILE),
+            ctl_socket_file: sup_svc_root.join(CTL_SOCKET_FILE),
         }
     }
 
@@ -122,6 +160,13 @@ impl FsCfg {
             format!("{}.health", service_group.service()),
         )
     }
+
+    /// Returns the file listing the releases of a service's package which were rolled back.
+    pub fn bad_releases_file(&self, service_group: &ServiceGroup) -> PathBuf {
+        self.data_path.join(
+            format!("{}.bad-releases", service_group.service()),
+        )
+    }
 }
 
 #[derive(Clone, Default)]
@@ -131,6 +176,10 @@ pub struct ManagerConfig {
     pub update_url: String,
     pub update_channel: String,
     pub gossip_listen: GossipListenAddr,
+    /// How rumors are sent to and received from other members.
+    pub gossip_transport: GossipTransportKind,
+    /// Overrides how many times longer than the minimum an unconfirmed suspicion lasts.
+    pub gossip_suspicion_multiplier: Option<i64>,
     pub http_listen: http_gateway::ListenAddr,
     pub gossip_peers: Vec<SocketAddr>,
     pub gossip_permanent: bool,
@@ -138,13 +187,35 @@ pub struct ManagerConfig {
     pub name: Option<String>,
     pub organization: Option<String>,
     pub watch_peer_file: Option<String>,
+    /// User-defined labels of the Supervisor, such as its zone, rack or role.
+    pub tags: BTreeMap<String, String>,
+    /// Bearer token which must accompany every request to the HTTP gateway. The write endpoints
+    /// are disabled when no token is configured.
+    pub http_auth_token: Option<String>,
+    pub http_tls: Option<http_gateway::TlsCfg>,
+    /// How the output of services is logged.
+    pub log: LogCfg,
 
     custom_state_path: Option<PathBuf>,
 }
 
+impl ManagerConfig {
+    /// Keep the state of the Supervisor in the given directory instead of the default one.
+    #[cfg(test)]
+    pub fn set_state_path<P>(&mut self, path: P)
+    where
+        P: Into<PathBuf>,
+    {
+        self.custom_state_path = Some(path.into());
+    }
+}
+
 pub struct Manager {
     butterfly: butterfly::Server,
+    cfg: Arc<ManagerConfig>,
     census_ring: CensusRing,
+    commands: Receiver<(ServiceCommand, Sender<CommandReply>)>,
+    command_sender: CommandSender,
     events_group: Option<ServiceGroup>,
     fs_cfg: Arc<FsCfg>,
     launcher: LauncherCli,
@@ -213,6 +284,11 @@ impl Manager {
         serde_json::from_reader(&dat).map_err(|e| sup_error!(Error::ServiceDeserializationError(e)))
     }
 
+    /// Path to the control socket of the Supervisor running with the given configuration.
+    pub fn ctl_socket_path(cfg: &ManagerConfig) -> PathBuf {
+        FsCfg::new(Self::state_path_from(cfg)).ctl_socket_file
+    }
+
     pub fn term(cfg: &ManagerConfig) -> Result<()> {
         let state_path = Self::state_path_from(&cfg);
         let fs_cfg = FsCfg::new(state_path);
@@ -263,6 +339,7 @@ impl Manager {
     }
 
     fn new(cfg: ManagerConfig, fs_cfg: FsCfg, launcher: LauncherCli) -> Result<Manager> {
+        let mgr_cfg = Arc::new(cfg.clone());
         let current = PackageIdent::from_str(&format!("{}/{}", SUP_PKG_IDENT, VERSION)).unwrap();
         let self_updater = if cfg.auto_update {
             if current.fully_qualified() {
@@ -278,8 +355,11 @@ impl Manager {
         } else {
             None
         };
+        logging::configure(cfg.log.clone());
         let mut sys = Sys::new(cfg.gossip_permanent, cfg.gossip_listen, cfg.http_listen);
+        sys.tags = cfg.tags.clone();
         let member = Self::load_member(&mut sys, &fs_cfg)?;
+        logging::set_member_id(&sys.member_id);
         let ring_key = match cfg.ring {
             Some(ref ring_with_revision) => {
                 outputln!("Joining ring {}", ring_with_revision);
@@ -291,7 +371,7 @@ impl Manager {
             None => None,
         };
         let services = Arc::new(RwLock::new(Vec::new()));
-        let server = butterfly::Server::new(
+        let mut server = butterfly::Server::new(
             sys.gossip_listen(),
             sys.gossip_listen(),
             member,
@@ -301,6 +381,10 @@ impl Manager {
             Some(&fs_cfg.data_path),
             Box::new(SuitabilityLookup(services.clone())),
         )?;
+        server.set_gossip_transport(cfg.gossip_transport.transport(LINGER_DEFAULT_MS)?);
+        // New ring key revisions spread by `hab ring key rotate` land next to the one we joined
+        // with, so the latest revision is picked up on restart.
+        server.set_ring_key_cache(default_cache_key_path(None));
         outputln!("Supervisor Member-ID {}", sys.member_id);
         for peer_addr in &cfg.gossip_peers {
             let mut peer = Member::default();
@@ -315,11 +399,15 @@ impl Manager {
         } else {
             None
         };
+        let (command_sender, commands) = mpsc::channel();
         Ok(Manager {
             self_updater: self_updater,
             updater: ServiceUpdater::new(server.clone()),
             census_ring: CensusRing::new(sys.member_id.clone()),
+            commands: commands,
+            command_sender: command_sender,
             butterfly: server,
+            cfg: mgr_cfg,
             events_group: cfg.eventsrv_group,
             launcher: launcher,
             services: services,
@@ -366,6 +454,7 @@ impl Manager {
         }
         sys.member_id = member.get_id().to_string();
         member.set_persistent(sys.permanent);
+        member.set_tags(sys.tags.clone().into_iter().collect());
         Ok(member)
     }
 
@@ -388,6 +477,34 @@ impl Manager {
         spec.to_file(Self::spec_path_for(cfg, spec))
     }
 
+    /// Returns the `ServiceSpec` on disk which runs the given service group, if one has been
+    /// loaded.
+    pub fn spec_for_service_group(
+        cfg: &ManagerConfig,
+        service_group: &ServiceGroup,
+    ) -> Result<Option<ServiceSpec>> {
+        let specs_path = Self::specs_path(&Self::state_path_from(cfg));
+        for spec_file in SpecWatcher::spec_files(&specs_path)? {
+            let spec = match ServiceSpec::from_file(&spec_file) {
+                Ok(spec) => spec,
+                Err(err) => {
+                    debug!("Skipping unreadable spec {}, {}", spec_file.display(), err);
+                    continue;
+                }
+            };
+            let spec_group = ServiceGroup::new(
+                spec.application_environment.as_ref(),
+                &spec.ident.name,
+                &spec.group,
+                cfg.organization.as_ref().map(|org| org.as_str()),
+            )?;
+            if &spec_group == service_group {
+                return Ok(Some(spec));
+            }
+        }
+        Ok(None)
+    }
+
     pub fn save_composite_spec_for(cfg: &ManagerConfig, spec: &CompositeSpec) -> Result<()> {
         spec.to_file(Self::composite_path_for(cfg, spec))
     }
@@ -521,6 +638,18 @@ impl Manager {
 
         self.gossip_latest_service_rumor(&service);
         if service.topology == Topology::Leader {
+            let lease = if service.leader_lease > 0 {
+                Some(Duration::from_secs(service.leader_lease))
+            } else {
+                None
+            };
+            self.butterfly.set_election_config(
+                &service.service_group,
+                ElectionConfig {
+                    quorum_basis: service.quorum_basis,
+                    lease: lease,
+                },
+            );
             self.butterfly.start_election(
                 service.service_group.clone(),
                 0,
@@ -550,14 +679,23 @@ impl Manager {
             "Starting gossip-listener on {}",
             self.butterfly.gossip_addr()
         );
-        self.butterfly.start(Timing::default())?;
+        let mut timing = Timing::default();
+        if let Some(multiplier) = self.cfg.gossip_suspicion_multiplier {
+            timing.suspicion_max_timeout_multiplier = multiplier;
+        }
+        self.butterfly.start(timing)?;
         debug!("gossip-listener started");
         self.persist_state();
         let http_listen_addr = self.sys.http_listen();
         outputln!("Starting http-gateway on {}", &http_listen_addr);
-        http_gateway::Server::new(self.fs_cfg.clone(), http_listen_addr)
-            .start()?;
+        http_gateway::Server::new(
+            self.fs_cfg.clone(),
+            self.cfg.clone(),
+            http_listen_addr,
+            self.command_sender.clone(),
+        ).start()?;
         debug!("http-gateway started");
+        self.start_ctl_gateway()?;
         let events = match self.events_group {
             Some(ref evg) => Some(events::EventsMgr::start(evg.clone())),
             None => None,
@@ -581,6 +719,7 @@ impl Manager {
                 return Ok(());
             }
             self.update_running_services_from_spec_watcher()?;
+            self.handle_service_commands();
             self.update_peers_from_watch_file()?;
             self.update_running_services_from_user_config_watcher();
             self.check_for_updated_packages();
@@ -592,6 +731,7 @@ impl Manager {
                 &self.butterfly.member_list,
                 &self.butterfly.service_config_store,
                 &self.butterfly.service_file_store,
+                &self.butterfly.canary_store,
             );
 
             if self.check_for_changed_services() {
@@ -629,7 +769,13 @@ impl Manager {
                 if service.tick(&self.census_ring, &self.launcher) {
                     self.gossip_latest_service_rumor(&service);
                 }
+                if let Some(rollback) = service.take_unreported_rollback() {
+                    if let Some(ref events) = events {
+                        events.send_rollback(service, &rollback);
+                    }
+                }
             }
+            self.record_metrics();
             let time_to_wait = (next_check - time::get_time()).num_milliseconds();
             if time_to_wait > 0 {
                 thread::sleep(Duration::from_millis(time_to_wait as u64));
@@ -637,6 +783,23 @@ impl Manager {
         }
     }
 
+    #[cfg(unix)]
+    fn start_ctl_gateway(&self) -> Result<()> {
+        outputln!(
+            "Starting ctl-gateway on {}",
+            self.fs_cfg.ctl_socket_file.display()
+        );
+        ctl_gateway::Server::new(self.cfg.clone(), self.fs_cfg.ctl_socket_file.clone())
+            .start()?;
+        debug!("ctl-gateway started");
+        Ok(())
+    }
+
+    #[cfg(windows)]
+    fn start_ctl_gateway(&self) -> Result<()> {
+        Ok(())
+    }
+
     fn check_for_updated_supervisor(&mut self) -> Option<PackageInstall> {
         if let Some(ref mut updater) = self.self_updater {
             return updater.updated();
@@ -664,6 +827,7 @@ impl Manager {
             {
                 self.gossip_latest_service_rumor(&service);
             }
+            service.update_status = self.updater.status(&service.service_group);
         }
     }
 
@@ -717,6 +881,59 @@ impl Manager {
         }
     }
 
+    /// Refresh the gauges which are sampled from the state of running services and of the
+    /// Butterfly server, rather than being updated as events happen.
+    fn record_metrics(&self) {
+        let now = time::get_time();
+        for service in self.services
+            .read()
+            .expect("Services lock is poisoned!")
+            .iter()
+        {
+            let sg = service.service_group.to_string();
+            metrics::SERVICE_STATE_CHANGE_SECONDS
+                .with_label_values(&[sg.as_str()])
+                .set((now - service.last_state_change()).num_seconds() as f64);
+        }
+
+        metrics::BUTTERFLY_SWIM_ROUNDS.set(self.butterfly.swim_rounds() as f64);
+        metrics::BUTTERFLY_GOSSIP_ROUNDS.set(self.butterfly.gossip_rounds() as f64);
+
+        let mut members = HashMap::new();
+        for health in &[
+            Health::Alive,
+            Health::Suspect,
+            Health::Confirmed,
+            Health::Departed,
+        ]
+        {
+            members.insert(health.to_string(), 0);
+        }
+        self.butterfly.member_list.with_members(|member| {
+            if let Some(health) = self.butterfly.member_list.health_of(member) {
+                *members.entry(health.to_string()).or_insert(0) += 1;
+            }
+        });
+        for (health, count) in members {
+            metrics::BUTTERFLY_MEMBERS
+                .with_label_values(&[health.as_str()])
+                .set(count as f64);
+        }
+
+        metrics::record_rumors(
+            &[
+                ("service", self.butterfly.service_store.len()),
+                ("service_config", self.butterfly.service_config_store.len()),
+                ("service_file", self.butterfly.service_file_store.len()),
+                ("election", self.butterfly.election_store.len()),
+                ("election_update", self.butterfly.update_store.len()),
+                ("departure", self.butterfly.departure_store.len()),
+                ("canary", self.butterfly.canary_store.len()),
+                ("ring_key", self.butterfly.ring_key_store.len()),
+            ],
+        );
+    }
+
     fn persist_state(&self) {
         debug!("Writing census state to disk");
         self.persist_census_state();
@@ -866,6 +1083,9 @@ impl Manager {
                 );
             }
         }
+        metrics::remove_service(&service.service_group);
+        secrets::unregister(&service.service_group);
+        logging::close(&service.service_group);
         if let Err(err) = fs::remove_file(self.fs_cfg.health_check_cache(&service.service_group)) {
             outputln!(
                 "Unable to cleanup service health cache, {}, {}",
@@ -966,6 +1186,80 @@ impl Manager {
         Ok(())
     }
 
+    /// Carry out the `ServiceCommand`s sent since the last tick, and reply to each.
+    fn handle_service_commands(&mut self) {
+        while let Ok((command, reply)) = self.commands.try_recv() {
+            let result = match command {
+                ServiceCommand::Start(service_group) => self.start_service(&service_group),
+                ServiceCommand::Stop(service_group) => self.stop_service(&service_group),
+            };
+            // The sender may have given up waiting for the reply; the command was carried out
+            // all the same.
+            let _ = reply.send(result);
+        }
+    }
+
+    /// Start the service running the given service group, saving its spec as up so it stays up
+    /// when the Supervisor restarts.
+    fn start_service(&mut self, service_group: &ServiceGroup) -> CommandReply {
+        let mut spec = match Self::spec_for_service_group(&self.cfg, service_group) {
+            Ok(Some(spec)) => spec,
+            Ok(None) => return CommandReply::NotLoaded,
+            Err(err) => return CommandReply::Failed(err.to_string()),
+        };
+        if self.is_service_running(service_group) {
+            return CommandReply::Unchanged;
+        }
+        spec.desired_state = DesiredState::Up;
+        if let Err(err) = Self::save_spec_for(&self.cfg, &spec) {
+            return CommandReply::Failed(err.to_string());
+        }
+        self.add_service(spec);
+        if self.is_service_running(service_group) {
+            CommandReply::Done
+        } else {
+            CommandReply::Failed(format!("Unable to start {}", service_group))
+        }
+    }
+
+    /// Stop the service running the given service group, saving its spec as down so it stays
+    /// down when the Supervisor restarts.
+    fn stop_service(&mut self, service_group: &ServiceGroup) -> CommandReply {
+        let mut spec = match Self::spec_for_service_group(&self.cfg, service_group) {
+            Ok(Some(spec)) => spec,
+            Ok(None) => return CommandReply::NotLoaded,
+            Err(err) => return CommandReply::Failed(err.to_string()),
+        };
+        if spec.desired_state == DesiredState::Down && !self.is_service_running(service_group) {
+            return CommandReply::Unchanged;
+        }
+        spec.desired_state = DesiredState::Down;
+        if let Err(err) = Self::save_spec_for(&self.cfg, &spec) {
+            return CommandReply::Failed(err.to_string());
+        }
+        let service = {
+            let mut services = self.services.write().expect("Services lock is poisoned");
+            match services.iter().position(
+                |s| &s.service_group == service_group,
+            ) {
+                Some(idx) => Some(services.remove(idx)),
+                None => None,
+            }
+        };
+        if let Some(mut service) = service {
+            self.remove_service(&mut service, true);
+        }
+        CommandReply::Done
+    }
+
+    fn is_service_running(&self, service_group: &ServiceGroup) -> bool {
+        self.services
+            .read()
+            .expect("Services lock is poisoned")
+            .iter()
+            .any(|s| &s.service_group == service_group)
+    }
+
     fn update_peers_from_watch_file(&mut self) -> Result<()> {
         if !self.butterfly.need_peer_seeding() {
             return Ok(());
@@ -1030,12 +1324,16 @@ impl Manager {
     }
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, Serialize)]
 pub struct ProcessStatus {
-    #[serde(deserialize_with = "deserialize_time", rename = "state_entered")]
+    #[serde(deserialize_with = "deserialize_time", serialize_with = "serialize_time",
+            rename(deserialize = "state_entered", serialize = "uptime"))]
     pub elapsed: TimeDuration,
     pub pid: Option<u32>,
     pub state: ProcessState,
+    /// True if the process was left down because it exceeded its restart limit.
+    #[serde(default)]
+    pub failed: bool,
 }
 
 impl fmt::Display for ProcessStatus {
@@ -1048,21 +1346,44 @@ impl fmt::Display for ProcessStatus {
                     self.state,
                     self.elapsed,
                     pid
-                )
+                )?
             }
-            None => write!(f, "state:{}, time:{}", self.state, self.elapsed),
+            None => write!(f, "state:{}, time:{}", self.state, self.elapsed)?,
         }
-
+        if self.failed {
+            write!(f, ", failed")?;
+        }
+        Ok(())
     }
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, Serialize)]
 pub struct ServiceStatus {
     pub pkg: Pkg,
     pub process: ProcessStatus,
     pub service_group: ServiceGroup,
     pub start_style: StartStyle,
     pub composite: Option<String>,
+    #[serde(default)]
+    pub health_check: HealthCheck,
+    #[serde(default)]
+    pub topology: Topology,
+    #[serde(default, rename = "last_election_status")]
+    pub election_status: ElectionStatus,
+    #[serde(default)]
+    pub role: Option<ElectionRole>,
+    #[serde(default)]
+    pub update_strategy: UpdateStrategy,
+    #[serde(default)]
+    pub update_status: Option<UpdateStatus>,
+    #[serde(default)]
+    pub unsatisfied_binds: Vec<String>,
+    #[serde(default)]
+    pub last_hook_failure: Option<HookFailure>,
+    #[serde(default)]
+    pub last_rollback: Option<Rollback>,
+    #[serde(default)]
+    pub last_template_failure: Option<TemplateFailure>,
 }
 
 impl fmt::Display for ServiceStatus {
@@ -1122,6 +1443,13 @@ where
     d.deserialize_u64(FromTimespec)
 }
 
+fn serialize_time<S>(elapsed: &TimeDuration, s: S) -> result::Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    s.serialize_i64(elapsed.num_seconds())
+}
+
 fn obtain_process_lock(fs_cfg: &FsCfg) -> Result<()> {
     match write_process_lock(&fs_cfg.proc_lock_file) {
         Ok(()) => Ok(()),
@@ -18,9 +18,11 @@ use std;
 use std::ascii::AsciiExt;
 use std::env;
 use std::fs::File;
+use std::mem;
 use std::io::prelude::*;
 use std::path::{Path, PathBuf};
 use std::result;
+use std::sync::Arc;
 
 use fs;
 use hcore::{crypto, util};
@@ -31,6 +33,8 @@ use serde_json;
 use toml;
 
 use super::Pkg;
+use super::schema::ConfigSchema;
+use super::secrets::Secrets;
 use census::CensusGroup;
 use error::{Error, Result};
 use templating::{TemplateRenderer, RenderContext};
@@ -115,6 +119,14 @@ pub struct Cfg {
 
     /// Source of the user configuration
     pub user_config_path: UserConfigPath,
+
+    /// Provider of the values read by the `secret` template helper. Secrets are not part of the
+    /// serialized configuration, so they are never gossiped or served by the HTTP gateway.
+    pub secrets: Option<Arc<Secrets>>,
+
+    /// Schema shipped by the package next to its `default.toml`, which every configuration
+    /// layer must abide by once merged.
+    pub schema: Option<ConfigSchema>,
 }
 
 impl Cfg {
@@ -126,20 +138,61 @@ impl Cfg {
         let user_config_path = Self::determine_user_config_path(package);
         let user = Self::load_user(user_config_path.get_path())?;
         let environment = Self::load_environment(package)?;
-        return Ok(Self {
+        let schema = ConfigSchema::load(&pkg_root)?;
+        let cfg = Self {
             default: default,
             user: user,
             gossip: None,
             environment: environment,
             gossip_incarnation: 0,
             user_config_path: user_config_path,
-        });
+            secrets: None,
+            schema: schema,
+        };
+        cfg.validate()?;
+        Ok(cfg)
+    }
+
+    /// Checks the configuration against the package's schema, if it ships one. Layers are merged
+    /// in the order they apply, and the error names the layer after which the configuration
+    /// never becomes valid again, so that a value only provided by `user.toml` does not fail
+    /// `default.toml`.
+    pub fn validate(&self) -> Result<()> {
+        let schema = match self.schema {
+            Some(ref schema) => schema,
+            None => return Ok(()),
+        };
+        let layers = [
+            ("default", &self.default),
+            ("environment", &self.environment),
+            ("user", &self.user),
+            ("gossip", &self.gossip),
+        ];
+        let mut merged = toml::value::Table::new();
+        let mut culprit = None;
+        let mut errors = Vec::new();
+        for &(name, layer) in layers.iter() {
+            if let Some(toml::Value::Table(ref table)) = *layer {
+                toml_merge(&mut merged, table)?;
+                errors = schema.validate(&toml::Value::Table(merged.clone()));
+                if errors.is_empty() {
+                    culprit = None;
+                } else if culprit.is_none() {
+                    culprit = Some(name);
+                }
+            }
+        }
+        match culprit {
+            Some(name) => Err(sup_error!(Error::InvalidConfig(name.to_string(), errors))),
+            None => Ok(()),
+        }
     }
 
     /// Updates the service configuration with data from a census group if the census group has
     /// newer data than the current configuration.
     ///
-    /// Returns `true` if the configuration was updated.
+    /// Returns `true` if the configuration was updated. A configuration which doesn't match the
+    /// package's schema is skipped, and the previous one is kept.
     pub fn update(&mut self, census_group: &CensusGroup) -> bool {
         match census_group.service_config {
             Some(ref config) => {
@@ -147,13 +200,28 @@ impl Cfg {
                     return false;
                 }
                 self.gossip_incarnation = config.incarnation;
-                self.gossip = Some(config.value.clone());
+                let previous = mem::replace(&mut self.gossip, Some(config.value.clone()));
+                if let Err(err) = self.validate() {
+                    outputln!(
+                        "Rejected configuration incarnation {} for {}: {}",
+                        config.incarnation,
+                        census_group.service_group,
+                        err
+                    );
+                    self.gossip = previous;
+                    return false;
+                }
                 true
             }
             None => false,
         }
     }
 
+    /// Returns `true` if a secret read by the templates was rotated upstream since it was read.
+    pub fn secrets_rotated(&self) -> bool {
+        self.secrets.as_ref().map(|s| s.rotated()).unwrap_or(false)
+    }
+
     /// Returns a subset of the overall configuration whitelisted by the given package's exports.
     pub fn to_exported(&self, pkg: &Pkg) -> Result<toml::value::Table> {
         let mut map = toml::value::Table::default();
@@ -260,10 +328,15 @@ impl Cfg {
         Self::load_toml_file(path, USER_CONFIG_FILE)
     }
 
-    /// Reloads the user configuration file.
+    /// Reloads the user configuration file. A file which doesn't match the package's schema is
+    /// not loaded, and the previous one is kept.
     pub fn reload_user(&mut self) -> Result<()> {
         let user = Self::load_user(self.user_config_path.get_path())?;
-        self.user = user;
+        let previous = mem::replace(&mut self.user, user);
+        if let Err(err) = self.validate() {
+            self.user = previous;
+            return Err(err);
+        }
         Ok(())
     }
 
@@ -356,11 +429,14 @@ impl Serialize for Cfg {
 pub struct CfgRenderer(TemplateRenderer);
 
 impl CfgRenderer {
-    pub fn new<T>(templates_path: T) -> Result<Self>
+    /// Loads the configuration templates of the given directory. In strict mode, a variable
+    /// missing from the render context fails the compilation.
+    pub fn new<T>(templates_path: T, strict: bool) -> Result<Self>
     where
         T: AsRef<Path>,
     {
         let mut template = TemplateRenderer::new();
+        template.set_strict_mode(strict);
         if let Ok(entries) = std::fs::read_dir(templates_path) {
             for entry in entries {
                 if let Ok(entry) = entry {
@@ -389,7 +465,22 @@ impl CfgRenderer {
         Ok(CfgRenderer(template))
     }
 
-    /// Compile and write all configuration files to the configuration directory.
+    /// Renders every configuration template without writing anything, failing on the first one
+    /// which doesn't render.
+    pub fn check(&self, ctx: &RenderContext) -> Result<()> {
+        self.render_all(ctx).map(|_| ())
+    }
+
+    fn render_all(&self, ctx: &RenderContext) -> Result<Vec<(String, String)>> {
+        let mut compiled = Vec::new();
+        for (template, _) in self.0.get_templates() {
+            compiled.push((template.clone(), self.0.render(&template, ctx)?));
+        }
+        Ok(compiled)
+    }
+
+    /// Compile and write all configuration files to the configuration directory. Nothing is
+    /// written unless every template renders, so a failure leaves the previous files in place.
     ///
     /// Returns `true` if the configuration has changed.
     pub fn compile(&self, pkg: &Pkg, ctx: &RenderContext) -> Result<bool> {
@@ -398,8 +489,7 @@ impl CfgRenderer {
         // through this and pipe the service group through to let people know which service is
         // having issues and be more descriptive about what happened.
         let mut changed = false;
-        for (template, _) in self.0.get_templates() {
-            let compiled = self.0.render(&template, ctx)?;
+        for (template, compiled) in self.render_all(ctx)? {
             let compiled_hash = crypto::hash::hash_string(&compiled);
             let cfg_dest = pkg.svc_config_path.join(&template);
             let file_hash = match crypto::hash::hash_file(&cfg_dest) {
@@ -515,11 +605,23 @@ mod test {
     use std::fs;
     use std::fs::OpenOptions;
 
+    use butterfly::member::MemberList;
+    use butterfly::rumor::RumorStore;
+    use butterfly::rumor::canary::Canary as CanaryRumor;
+    use butterfly::rumor::election::Election as ElectionRumor;
+    use butterfly::rumor::election::ElectionUpdate as ElectionUpdateRumor;
+    use butterfly::rumor::service::{Service as ServiceRumor, SysInfo};
+    use butterfly::rumor::service_config::ServiceConfig as ServiceConfigRumor;
+    use butterfly::rumor::service_file::ServiceFile as ServiceFileRumor;
+    use hcore::package::ident::PackageIdent;
+    use hcore::service::ServiceGroup;
     use toml;
     use tempdir::TempDir;
 
     use super::*;
-    use error::Error;
+    use census::CensusRing;
+    use error::{Error, SupError};
+    use manager::service::secrets::FileProvider;
 
     fn toml_from_str(content: &str) -> toml::value::Table {
         toml::from_str(content).expect(&format!("Content should parse as TOML: {}", content))
@@ -762,17 +864,19 @@ mod test {
         }
     }
 
-    fn write_toml<P: AsRef<Path>>(path: &P, text: &str) {
+    fn write_file<P: AsRef<Path>>(path: &P, text: &str) {
         let mut file = OpenOptions::new()
             .write(true)
             .create(true)
             .truncate(true)
             .open(path)
-            .expect("create toml file");
-        file.write_all(text.as_bytes()).expect(
-            "write raw toml value",
-        );
-        file.flush().expect("flush changes in toml file");
+            .expect("create file");
+        file.write_all(text.as_bytes()).expect("write file");
+        file.flush().expect("flush changes in file");
+    }
+
+    fn write_toml<P: AsRef<Path>>(path: &P, text: &str) {
+        write_file(path, text)
     }
 
     fn toml_value_from_str(text: &str) -> toml::Value {
@@ -859,6 +963,141 @@ mod test {
         assert_eq!(cfg.user, Some(toml_value_from_str(toml)));
     }
 
+    const SCHEMA: &'static str = r#"{
+        "required": ["port"],
+        "properties": {"port": {"type": "integer"}}
+    }"#;
+
+    fn invalid_layer(result: Result<()>) -> String {
+        match result {
+            Err(SupError { err: Error::InvalidConfig(layer, _), .. }) => layer,
+            other => panic!("Expected an invalid config, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn accept_required_value_from_a_later_layer() {
+        let cfg_data = CfgTestData::new();
+        write_file(
+            &cfg_data.pkg.default_config_dir().join("config_schema.json"),
+            SCHEMA,
+        );
+        write_toml(
+            &cfg_data.pkg.default_config_dir().join("default.toml"),
+            "name = \"redis\"",
+        );
+        write_toml(&cfg_data.rucp, "port = 6379");
+        let cfg = Cfg::new(&cfg_data.pkg, None).expect("create config");
+
+        assert!(cfg.schema.is_some());
+    }
+
+    #[test]
+    fn reject_invalid_user_toml() {
+        let cfg_data = CfgTestData::new();
+        write_file(
+            &cfg_data.pkg.default_config_dir().join("config_schema.json"),
+            SCHEMA,
+        );
+        write_toml(
+            &cfg_data.pkg.default_config_dir().join("default.toml"),
+            "port = 6379",
+        );
+        write_toml(&cfg_data.rucp, "port = \"abc\"");
+
+        assert_eq!(
+            invalid_layer(Cfg::new(&cfg_data.pkg, None).map(|_| ())),
+            "user"
+        );
+    }
+
+    #[test]
+    fn keep_previous_user_toml_when_reloading_an_invalid_one() {
+        let cfg_data = CfgTestData::new();
+        write_file(
+            &cfg_data.pkg.default_config_dir().join("config_schema.json"),
+            SCHEMA,
+        );
+        write_toml(&cfg_data.rucp, "port = 6379");
+        let mut cfg = Cfg::new(&cfg_data.pkg, None).expect("create config");
+
+        write_toml(&cfg_data.rucp, "port = \"abc\"");
+        assert_eq!(invalid_layer(cfg.reload_user()), "user");
+        assert_eq!(cfg.user, Some(toml_value_from_str("port = 6379")));
+    }
+
+    #[test]
+    fn reject_invalid_gossip_layer() {
+        let cfg_data = CfgTestData::new();
+        write_file(
+            &cfg_data.pkg.default_config_dir().join("config_schema.json"),
+            SCHEMA,
+        );
+        write_toml(
+            &cfg_data.pkg.default_config_dir().join("default.toml"),
+            "port = 6379",
+        );
+        let mut cfg = Cfg::new(&cfg_data.pkg, None).expect("create config");
+        cfg.gossip = Some(toml_value_from_str("port = \"abc\""));
+
+        assert_eq!(invalid_layer(cfg.validate()), "gossip");
+        cfg.gossip = Some(toml_value_from_str("port = 6380"));
+        assert!(cfg.validate().is_ok());
+    }
+
+    fn census_ring_with_config(incarnation: u64, config: &str) -> CensusRing {
+        let sg = ServiceGroup::new(None, "testing", "default", None).unwrap();
+        let pg_id = PackageIdent::new("core", "testing", Some("1.0.0"), Some("20170514150022"));
+        let service_store: RumorStore<ServiceRumor> = RumorStore::default();
+        service_store.insert(ServiceRumor::new(
+            "member-a".to_string(),
+            &pg_id,
+            &sg,
+            &SysInfo::new(),
+            None,
+        ));
+        let service_config_store: RumorStore<ServiceConfigRumor> = RumorStore::default();
+        let mut service_config =
+            ServiceConfigRumor::new("member-a", sg, config.as_bytes().to_vec());
+        service_config.set_incarnation(incarnation);
+        service_config_store.insert(service_config);
+
+        let mut ring = CensusRing::new("member-a".to_string());
+        ring.update_from_rumors(
+            &service_store,
+            &RumorStore::<ElectionRumor>::default(),
+            &RumorStore::<ElectionUpdateRumor>::default(),
+            &MemberList::new(),
+            &service_config_store,
+            &RumorStore::<ServiceFileRumor>::default(),
+            &RumorStore::<CanaryRumor>::default(),
+        );
+        ring
+    }
+
+    #[test]
+    fn update_skips_a_gossiped_config_breaking_the_schema() {
+        let cfg_data = CfgTestData::new();
+        write_file(
+            &cfg_data.pkg.default_config_dir().join("config_schema.json"),
+            SCHEMA,
+        );
+        write_toml(
+            &cfg_data.pkg.default_config_dir().join("default.toml"),
+            "port = 6379",
+        );
+        let mut cfg = Cfg::new(&cfg_data.pkg, None).expect("create config");
+        let sg = ServiceGroup::new(None, "testing", "default", None).unwrap();
+
+        let ring = census_ring_with_config(1, "port = \"abc\"");
+        assert!(!cfg.update(ring.census_group_for(&sg).unwrap()));
+        assert_eq!(cfg.gossip, None);
+
+        let ring = census_ring_with_config(2, "port = 6380");
+        assert!(cfg.update(ring.census_group_for(&sg).unwrap()));
+        assert_eq!(cfg.gossip, Some(toml_value_from_str("port = 6380")));
+    }
+
     #[test]
     fn serialize_config() {
         let concrete_path = TempDir::new("habitat_config_test").expect("create temp dir");
@@ -876,4 +1115,20 @@ mod test {
 
         assert_eq!(default_toml, toml::to_string(&cfg).unwrap());
     }
+
+    #[test]
+    fn serialize_config_without_secrets() {
+        let concrete_path = TempDir::new("habitat_config_test").expect("create temp dir");
+        let pkg = TestPkg::new(&concrete_path);
+        let secrets_path = concrete_path.path().join("secrets");
+        fs::create_dir_all(&secrets_path).expect("create secrets dir");
+        write_file(&secrets_path.join("password"), "hunter2");
+        let mut cfg = Cfg::new(&pkg, None).expect("Could not create config");
+        let secrets = Secrets::new(Box::new(FileProvider::new(secrets_path)));
+        assert_eq!(secrets.get("password").unwrap(), "hunter2");
+        cfg.secrets = Some(Arc::new(secrets));
+
+        assert!(!toml::to_string(&cfg).unwrap().contains("hunter2"));
+        assert!(!format!("{:?}", cfg).contains("hunter2"));
+    }
 }
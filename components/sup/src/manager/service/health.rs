@@ -14,7 +14,7 @@
 
 use std::fmt;
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize, Serialize)]
 pub enum HealthCheck {
     Ok,
     Warning,
@@ -40,6 +40,28 @@ impl From<i8> for HealthCheck {
     }
 }
 
+impl From<u32> for HealthCheck {
+    fn from(value: u32) -> HealthCheck {
+        match value {
+            0 => HealthCheck::Ok,
+            1 => HealthCheck::Warning,
+            2 => HealthCheck::Critical,
+            _ => HealthCheck::Unknown,
+        }
+    }
+}
+
+impl From<HealthCheck> for u32 {
+    fn from(value: HealthCheck) -> u32 {
+        match value {
+            HealthCheck::Ok => 0,
+            HealthCheck::Warning => 1,
+            HealthCheck::Critical => 2,
+            HealthCheck::Unknown => 3,
+        }
+    }
+}
+
 impl fmt::Display for HealthCheck {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let msg = match *self {
@@ -32,12 +32,50 @@ use serde::{Serialize, Serializer};
 use super::{health, Pkg};
 use error::{Result, SupError};
 use fs;
+use logging::{self, LogRecord};
+use metrics;
 use templating::{RenderContext, TemplateRenderer};
+use time;
 use util::exec;
 
 pub const HOOK_PERMISSIONS: u32 = 0o755;
 static LOGKEY: &'static str = "HK";
 
+/// An unsuccessful run of one of a service's hooks.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub struct HookFailure {
+    pub hook: String,
+    /// Exit code of the hook, `None` if it couldn't be run or was terminated by a signal.
+    pub exit_code: Option<i32>,
+    pub timestamp: String,
+}
+
+/// Remember an unsuccessful run of a hook as the last hook failure of its service.
+fn record_failure<T>(last_failure: &mut Option<HookFailure>, exit_code: Option<i32>)
+where
+    T: Hook,
+{
+    *last_failure = Some(HookFailure {
+        hook: T::file_name().to_string(),
+        exit_code: exit_code,
+        timestamp: time::now_utc().rfc3339().to_string(),
+    });
+}
+
+/// Forget the last hook failure of a service once the hook which failed succeeds.
+fn clear_failure<T>(last_failure: &mut Option<HookFailure>)
+where
+    T: Hook,
+{
+    let cleared = match *last_failure {
+        Some(ref failure) => failure.hook == T::file_name(),
+        None => false,
+    };
+    if cleared {
+        *last_failure = None;
+    }
+}
+
 pub fn stdout_log_path<T>(service_group: &ServiceGroup) -> PathBuf
 where
     T: Hook,
@@ -66,7 +104,12 @@ pub trait Hook: fmt::Debug + Sized {
 
     fn file_name() -> &'static str;
 
-    fn load<C, T>(service_group: &ServiceGroup, concrete_path: C, template_path: T) -> Option<Self>
+    fn load<C, T>(
+        service_group: &ServiceGroup,
+        concrete_path: C,
+        template_path: T,
+        strict: bool,
+    ) -> Option<Self>
     where
         C: AsRef<Path>,
         T: AsRef<Path>,
@@ -75,7 +118,7 @@ pub trait Hook: fmt::Debug + Sized {
         let template = template_path.as_ref().join(Self::file_name());
         match std::fs::metadata(&template) {
             Ok(_) => {
-                let pair = match RenderPair::new(concrete, &template) {
+                let pair = match RenderPair::new(concrete, &template, strict) {
                     Ok(pair) => pair,
                     Err(err) => {
                         outputln!(preamble service_group, "Failed to load hook: {}", err);
@@ -97,6 +140,12 @@ pub trait Hook: fmt::Debug + Sized {
 
     fn new(service_group: &ServiceGroup, render_pair: RenderPair) -> Self;
 
+    /// Whether a run of the hook which exited with the given code, `None` if it was terminated by
+    /// a signal, counts as a hook failure.
+    fn is_failure(exit_code: Option<i32>) -> bool {
+        exit_code != Some(0)
+    }
+
     /// Compile a hook into its destination service directory.
     ///
     /// Returns `true` if the hook has changed.
@@ -118,11 +167,15 @@ pub trait Hook: fmt::Debug + Sized {
     }
 
     /// Run a compiled hook.
+    ///
+    /// An unsuccessful run is recorded as the last hook failure of the service, which is cleared
+    /// again by the next successful run of the same hook.
     fn run<T>(
         &self,
         service_group: &ServiceGroup,
         pkg: &Pkg,
         svc_encrypted_password: Option<T>,
+        last_failure: &mut Option<HookFailure>,
     ) -> Self::ExitValue
     where
         T: ToString,
@@ -132,16 +185,28 @@ pub trait Hook: fmt::Debug + Sized {
             Err(err) => {
                 outputln!(preamble service_group,
                     "Hook failed to run, {}, {}", Self::file_name(), err);
+                record_failure::<Self>(last_failure, None);
                 return Self::ExitValue::default();
             }
         };
         let mut hook_output = HookOutput::new(self.stdout_log_path(), self.stderr_log_path());
         hook_output.stream_output::<Self>(service_group, &mut child);
         match child.wait() {
-            Ok(status) => self.handle_exit(service_group, &hook_output, &status),
+            Ok(status) => {
+                if let Some(code) = status.code() {
+                    metrics::record_hook_exit::<Self>(service_group, code);
+                }
+                if Self::is_failure(status.code()) {
+                    record_failure::<Self>(last_failure, status.code());
+                } else {
+                    clear_failure::<Self>(last_failure);
+                }
+                self.handle_exit(service_group, &hook_output, &status)
+            }
             Err(err) => {
                 outputln!(preamble service_group,
                     "Hook failed to run, {}, {}", Self::file_name(), err);
+                record_failure::<Self>(last_failure, None);
                 Self::ExitValue::default()
             }
         }
@@ -233,6 +298,15 @@ impl Hook for HealthCheckHook {
         }
     }
 
+    /// A warning or critical health is a result of the check rather than a failure of the hook;
+    /// only an unknown health, or a code the check doesn't define, is.
+    fn is_failure(exit_code: Option<i32>) -> bool {
+        match exit_code {
+            Some(0) | Some(1) | Some(2) => false,
+            _ => true,
+        }
+    }
+
     fn handle_exit<'a>(
         &self,
         service_group: &ServiceGroup,
@@ -770,6 +844,66 @@ impl Hook for PostStopHook {
     }
 }
 
+#[derive(Debug, Serialize)]
+pub struct DemoteHook {
+    render_pair: RenderPair,
+    stdout_log_path: PathBuf,
+    stderr_log_path: PathBuf,
+}
+
+impl Hook for DemoteHook {
+    type ExitValue = bool;
+
+    fn file_name() -> &'static str {
+        "demote"
+    }
+
+    fn new(service_group: &ServiceGroup, pair: RenderPair) -> Self {
+        DemoteHook {
+            render_pair: pair,
+            stdout_log_path: stdout_log_path::<Self>(service_group),
+            stderr_log_path: stderr_log_path::<Self>(service_group),
+        }
+    }
+
+    fn handle_exit<'a>(
+        &self,
+        service_group: &ServiceGroup,
+        _: &'a HookOutput,
+        status: &ExitStatus,
+    ) -> Self::ExitValue {
+        match status.code() {
+            Some(0) => true,
+            Some(code) => {
+                outputln!(preamble service_group, "Demote failed! '{}' exited with \
+                    status code {}", Self::file_name(), code);
+                false
+            }
+            None => {
+                outputln!(preamble service_group, "Demote failed! '{}' exited without a \
+                    status code", Self::file_name());
+                false
+            }
+        }
+    }
+
+    fn path(&self) -> &Path {
+        &self.render_pair.path
+    }
+
+    fn renderer(&self) -> &TemplateRenderer {
+        &self.render_pair.renderer
+    }
+
+    fn stdout_log_path(&self) -> &Path {
+        &self.stdout_log_path
+    }
+
+    fn stderr_log_path(&self) -> &Path {
+        &self.stderr_log_path
+    }
+}
+
 
 /// Cryptographically hash the contents of the compiled hook
 /// file.
@@ -814,11 +948,18 @@ pub struct HookTable {
     pub post_run: Option<PostRunHook>,
     pub smoke_test: Option<SmokeTestHook>,
     pub post_stop: Option<PostStopHook>,
+    pub demote: Option<DemoteHook>,
 }
 
 impl HookTable {
-    /// Read all available hook templates from the table's package directory into the table.
-    pub fn load<P, T>(service_group: &ServiceGroup, templates: T, hooks_path: P) -> Self
+    /// Read all available hook templates from the table's package directory into the table. In
+    /// strict mode, a variable missing from the render context fails the compilation of a hook.
+    pub fn load<P, T>(
+        service_group: &ServiceGroup,
+        templates: T,
+        hooks_path: P,
+        strict: bool,
+    ) -> Self
     where
         P: AsRef<Path>,
         T: AsRef<Path>,
@@ -826,16 +967,23 @@ impl HookTable {
         let mut table = HookTable::default();
         if let Some(meta) = std::fs::metadata(templates.as_ref()).ok() {
             if meta.is_dir() {
-                table.file_updated = FileUpdatedHook::load(service_group, &hooks_path, &templates);
-                table.health_check = HealthCheckHook::load(service_group, &hooks_path, &templates);
-                table.suitability = SuitabilityHook::load(service_group, &hooks_path, &templates);
-                table.init = InitHook::load(service_group, &hooks_path, &templates);
-                table.reload = ReloadHook::load(service_group, &hooks_path, &templates);
-                table.reconfigure = ReconfigureHook::load(service_group, &hooks_path, &templates);
-                table.run = RunHook::load(service_group, &hooks_path, &templates);
-                table.post_run = PostRunHook::load(service_group, &hooks_path, &templates);
-                table.smoke_test = SmokeTestHook::load(service_group, &hooks_path, &templates);
-                table.post_stop = PostStopHook::load(service_group, &hooks_path, &templates);
+                table.file_updated =
+                    FileUpdatedHook::load(service_group, &hooks_path, &templates, strict);
+                table.health_check =
+                    HealthCheckHook::load(service_group, &hooks_path, &templates, strict);
+                table.suitability =
+                    SuitabilityHook::load(service_group, &hooks_path, &templates, strict);
+                table.init = InitHook::load(service_group, &hooks_path, &templates, strict);
+                table.reload = ReloadHook::load(service_group, &hooks_path, &templates, strict);
+                table.reconfigure =
+                    ReconfigureHook::load(service_group, &hooks_path, &templates, strict);
+                table.run = RunHook::load(service_group, &hooks_path, &templates, strict);
+                table.post_run = PostRunHook::load(service_group, &hooks_path, &templates, strict);
+                table.smoke_test =
+                    SmokeTestHook::load(service_group, &hooks_path, &templates, strict);
+                table.post_stop =
+                    PostStopHook::load(service_group, &hooks_path, &templates, strict);
+                table.demote = DemoteHook::load(service_group, &hooks_path, &templates, strict);
             }
         }
         debug!(
@@ -884,10 +1032,29 @@ impl HookTable {
         if let Some(ref hook) = self.post_stop {
             changed = self.compile_one(hook, service_group, ctx) || changed;
         }
+        if let Some(ref hook) = self.demote {
+            changed = self.compile_one(hook, service_group, ctx) || changed;
+        }
         outputln!(preamble service_group, "Hooks compiled");
         changed
     }
 
+    /// Renders every loaded hook without writing anything, failing on the first one which
+    /// doesn't render.
+    pub fn check(&self, ctx: &RenderContext) -> Result<()> {
+        check_one(&self.file_updated, ctx)?;
+        check_one(&self.health_check, ctx)?;
+        check_one(&self.init, ctx)?;
+        check_one(&self.reload, ctx)?;
+        check_one(&self.reconfigure, ctx)?;
+        check_one(&self.suitability, ctx)?;
+        check_one(&self.run, ctx)?;
+        check_one(&self.post_run, ctx)?;
+        check_one(&self.smoke_test, ctx)?;
+        check_one(&self.post_stop, ctx)?;
+        check_one(&self.demote, ctx)
+    }
+
     fn compile_one<H>(&self, hook: &H, service_group: &ServiceGroup, ctx: &RenderContext) -> bool
     where
         H: Hook,
@@ -903,18 +1070,29 @@ impl HookTable {
     }
 }
 
+fn check_one<H>(hook: &Option<H>, ctx: &RenderContext) -> Result<()>
+where
+    H: Hook,
+{
+    if let Some(ref hook) = *hook {
+        hook.renderer().render(H::file_name(), ctx)?;
+    }
+    Ok(())
+}
+
 pub struct RenderPair {
     pub path: PathBuf,
     pub renderer: TemplateRenderer,
 }
 
 impl RenderPair {
-    pub fn new<C, T>(concrete_path: C, template_path: T) -> Result<Self>
+    pub fn new<C, T>(concrete_path: C, template_path: T, strict: bool) -> Result<Self>
     where
         C: Into<PathBuf>,
         T: AsRef<Path>,
     {
         let mut renderer = TemplateRenderer::new();
+        renderer.set_strict_mode(strict);
         let name = template_path
             .as_ref()
             .file_name()
@@ -982,10 +1160,19 @@ impl<'a> HookOutput<'a> {
             File::create(&self.stderr_log_file).expect("couldn't create log output file");
 
         let preamble_str = self.stream_preamble::<H>(service_group);
+        let json = logging::is_json();
+        let mut records = vec![];
         if let Some(ref mut stdout) = process.stdout {
             for line in BufReader::new(stdout).lines() {
                 if let Some(ref l) = line.ok() {
-                    outputln!(preamble preamble_str, l);
+                    if json {
+                        let record =
+                            LogRecord::new(service_group, "stdout", H::file_name(), l).to_json();
+                        println!("{}", record);
+                        records.push(record);
+                    } else {
+                        outputln!(preamble preamble_str, l);
+                    }
                     stdout_log.write_fmt(format_args!("{}\n", l)).expect(
                         "couldn't write line",
                     );
@@ -995,13 +1182,21 @@ impl<'a> HookOutput<'a> {
         if let Some(ref mut stderr) = process.stderr {
             for line in BufReader::new(stderr).lines() {
                 if let Some(ref l) = line.ok() {
-                    outputln!(preamble preamble_str, l);
+                    if json {
+                        let record =
+                            LogRecord::new(service_group, "stderr", H::file_name(), l).to_json();
+                        println!("{}", record);
+                        records.push(record);
+                    } else {
+                        outputln!(preamble preamble_str, l);
+                    }
                     stderr_log.write_fmt(format_args!("{}\n", l)).expect(
                         "couldn't write line",
                     );
                 }
             }
         }
+        logging::append(service_group, &records);
     }
 
     fn stream_preamble<H: Hook>(&self, service_group: &ServiceGroup) -> String {
@@ -1029,6 +1224,7 @@ mod tests {
 
     use hcore::service::ServiceGroup;
     use butterfly::member::MemberList;
+    use butterfly::rumor::canary::Canary as CanaryRumor;
     use butterfly::rumor::service::Service as ServiceRumor;
     use butterfly::rumor::service_config::ServiceConfig as ServiceConfigRumor;
     use butterfly::rumor::service_file::ServiceFile as ServiceFileRumor;
@@ -1118,7 +1314,7 @@ mod tests {
         let concrete_path = rendered_hooks_path();
         let template_path = hook_templates_path();
 
-        let hook = InitHook::load(&service_group, &concrete_path, &template_path)
+        let hook = InitHook::load(&service_group, &concrete_path, &template_path, false)
             .expect("Could not create testing init hook");
 
         let content = r#"
@@ -1139,7 +1335,7 @@ echo "The message is Hello World"
         let service_group = service_group();
         let concrete_path = rendered_hooks_path();
         let template_path = hook_templates_path();
-        let hook = InitHook::load(&service_group, &concrete_path, &template_path)
+        let hook = InitHook::load(&service_group, &concrete_path, &template_path, false)
             .expect("Could not create testing init hook");
 
         assert_eq!(hash_content(hook.path()).unwrap(), "");
@@ -1151,7 +1347,7 @@ echo "The message is Hello World"
         let concrete_path = rendered_hooks_path();
         let template_path = hook_templates_path();
 
-        let hook = InitHook::load(&service_group, &concrete_path, &template_path)
+        let hook = InitHook::load(&service_group, &concrete_path, &template_path, false)
             .expect("Could not create testing init hook");
 
         // Since we're trying to update a file that should already
@@ -1181,7 +1377,7 @@ echo "The message is Hello World"
         let concrete_path = rendered_hooks_path();
         let template_path = hook_templates_path();
 
-        let hook = InitHook::load(&service_group, &concrete_path, &template_path)
+        let hook = InitHook::load(&service_group, &concrete_path, &template_path, false)
             .expect("Could not create testing init hook");
 
         // In this test, we'll start with *no* rendered content.
@@ -1208,7 +1404,7 @@ echo "The message is Hello World"
         let concrete_path = rendered_hooks_path();
         let template_path = hook_templates_path();
 
-        let hook = InitHook::load(&service_group, &concrete_path, &template_path)
+        let hook = InitHook::load(&service_group, &concrete_path, &template_path, false)
             .expect("Could not create testing init hook");
 
         let initial_content = r#"
@@ -1265,7 +1461,7 @@ echo "The message is Hola Mundo"
         let concrete_path = rendered_hooks_path();
         let template_path = hook_templates_path();
 
-        let hook = InitHook::load(&service_group, &concrete_path, &template_path)
+        let hook = InitHook::load(&service_group, &concrete_path, &template_path, false)
             .expect("Could not create testing init hook");
 
         ////////////////////////////////////////////////////////////////////////
@@ -1322,6 +1518,7 @@ echo "The message is Hola Mundo"
 
         let service_config_store: RumorStore<ServiceConfigRumor> = RumorStore::default();
         let service_file_store: RumorStore<ServiceFileRumor> = RumorStore::default();
+        let canary_store: RumorStore<CanaryRumor> = RumorStore::default();
 
         let mut ring = CensusRing::new("member-a");
         ring.update_from_rumors(
@@ -1331,6 +1528,7 @@ echo "The message is Hola Mundo"
             &member_list,
             &service_config_store,
             &service_file_store,
+            &canary_store,
         );
 
         let bindings = iter::empty::<&ServiceBind>();
@@ -1424,6 +1622,7 @@ echo "The message is Hello"
 
         let service_config_store: RumorStore<ServiceConfigRumor> = RumorStore::default();
         let service_file_store: RumorStore<ServiceFileRumor> = RumorStore::default();
+        let canary_store: RumorStore<CanaryRumor> = RumorStore::default();
 
         let mut ring = CensusRing::new("member-a");
         ring.update_from_rumors(
@@ -1433,6 +1632,7 @@ echo "The message is Hello"
             &member_list,
             &service_config_store,
             &service_file_store,
+            &canary_store,
         );
 
         let bindings = iter::empty::<&ServiceBind>();
@@ -1442,7 +1642,7 @@ echo "The message is Hello"
         // END RENDER CONTEXT SETUP
         ////////////////////////////////////////////////////////////////////////
 
-        let hook_table = HookTable::load(&service_group, &template_path, &hooks_path);
+        let hook_table = HookTable::load(&service_group, &template_path, &hooks_path, false);
         assert_eq!(hook_table.compile(&service_group, &ctx), true);
 
         // Verify init hook
@@ -1475,6 +1675,128 @@ echo "The message is Hello"
         );
     }
 
+    #[test]
+    fn strict_hook_table_keeps_the_last_compiled_hooks_when_a_variable_goes_missing() {
+        let tmp_root = rendered_hooks_path();
+        let hooks_path = tmp_root.path().join(
+            supfs::svc_hooks_path("test_service")
+                .strip_prefix("/")
+                .unwrap(),
+        );
+        fs::create_dir_all(&hooks_path).unwrap();
+        let cfg_root = rendered_hooks_path();
+
+        let service_group = service_group();
+        let template_path = hook_templates_path();
+
+        ////////////////////////////////////////////////////////////////////////
+        // BEGIN RENDER CONTEXT SETUP
+        // (See comment above)
+
+        let sys = Sys::new(true, GossipListenAddr::default(), ListenAddr::default());
+
+        let pg_id = PackageIdent::new(
+            "testing",
+            &service_group.service(),
+            Some("1.0.0"),
+            Some("20170712000000"),
+        );
+
+        let pkg_install = PackageInstall::new_from_parts(
+            pg_id.clone(),
+            PathBuf::from("/tmp"),
+            PathBuf::from("/tmp"),
+            PathBuf::from("/tmp"),
+        );
+        let pkg = Pkg::from_install(pkg_install).expect("Could not create package!");
+
+        // One configuration with the message the init hook uses, and one without
+        create_with_content(
+            hooks_path.join("default.toml"),
+            &String::from("message = \"Hello\""),
+        );
+        let cfg = Cfg::new(&pkg, Some(&hooks_path)).expect("Could not create config");
+        create_with_content(
+            cfg_root.path().join("default.toml"),
+            &String::from("greeting = \"Hello\""),
+        );
+        let cfg_without_message = Cfg::new(&pkg, Some(&cfg_root.path().to_path_buf()))
+            .expect("Could not create config");
+
+        // SysInfo is basic Swim infrastructure information
+        let mut sys_info = SysInfo::new();
+        sys_info.set_ip("1.2.3.4".to_string());
+        sys_info.set_hostname("hostname".to_string());
+        sys_info.set_gossip_ip("0.0.0.0".to_string());
+        sys_info.set_gossip_port(7777);
+        sys_info.set_http_gateway_ip("0.0.0.0".to_string());
+        sys_info.set_http_gateway_port(9631);
+
+        let service_store: RumorStore<ServiceRumor> = RumorStore::default();
+        let service_one = ServiceRumor::new("member-a", &pg_id, &service_group, &sys_info, None);
+        service_store.insert(service_one);
+
+        let election_store: RumorStore<ElectionRumor> = RumorStore::default();
+        let mut election = ElectionRumor::new("member-a", service_group.clone(), 10);
+        election.finish();
+        election_store.insert(election);
+
+        let election_update_store: RumorStore<ElectionUpdateRumor> = RumorStore::default();
+
+        let member_list = MemberList::new();
+
+        let service_config_store: RumorStore<ServiceConfigRumor> = RumorStore::default();
+        let service_file_store: RumorStore<ServiceFileRumor> = RumorStore::default();
+        let canary_store: RumorStore<CanaryRumor> = RumorStore::default();
+
+        let mut ring = CensusRing::new("member-a");
+        ring.update_from_rumors(
+            &service_store,
+            &election_store,
+            &election_update_store,
+            &member_list,
+            &service_config_store,
+            &service_file_store,
+            &canary_store,
+        );
+
+        let ctx = RenderContext::new(
+            &service_group,
+            &sys,
+            &pkg,
+            &cfg,
+            &ring,
+            iter::empty::<&ServiceBind>(),
+        );
+        let ctx_without_message = RenderContext::new(
+            &service_group,
+            &sys,
+            &pkg,
+            &cfg_without_message,
+            &ring,
+            iter::empty::<&ServiceBind>(),
+        );
+
+        // END RENDER CONTEXT SETUP
+        ////////////////////////////////////////////////////////////////////////
+
+        let hook_table = HookTable::load(&service_group, &template_path, &hooks_path, true);
+        assert!(hook_table.check(&ctx).is_ok());
+        assert_eq!(hook_table.compile(&service_group, &ctx), true);
+
+        // The check fails, so the service never compiles the hooks and keeps the last ones
+        assert!(hook_table.check(&ctx_without_message).is_err());
+        let init_hook_content = file_content(&hook_table.init.as_ref().expect("no init hook??"));
+        assert_eq!(
+            init_hook_content,
+            "#!/bin/bash\n\necho \"The message is Hello\"\n"
+        );
+
+        // A lenient table renders the missing message as an empty string instead
+        let lenient_table = HookTable::load(&service_group, &template_path, &hooks_path, false);
+        assert!(lenient_table.check(&ctx_without_message).is_ok());
+    }
+
     ////////////////////////////////////////////////////////////////////////
 
     #[test]
@@ -1522,4 +1844,35 @@ echo "The message is Hello"
 
         fs::remove_dir_all(tmp_dir).expect("remove temp dir");
     }
+
+    #[test]
+    fn hook_failure_is_recorded_then_cleared() {
+        let mut last_failure = None;
+        record_failure::<ReconfigureHook>(&mut last_failure, Some(2));
+        {
+            let failure = last_failure.as_ref().expect("failure wasn't recorded");
+            assert_eq!(failure.hook, "reconfigure");
+            assert_eq!(failure.exit_code, Some(2));
+        }
+
+        // Only a successful run of the hook which failed clears its failure
+        clear_failure::<InitHook>(&mut last_failure);
+        assert!(last_failure.is_some());
+        clear_failure::<ReconfigureHook>(&mut last_failure);
+        assert_eq!(last_failure, None);
+    }
+
+    #[test]
+    fn only_health_checks_of_unknown_health_are_hook_failures() {
+        assert!(!HealthCheckHook::is_failure(Some(0)));
+        assert!(!HealthCheckHook::is_failure(Some(1)));
+        assert!(!HealthCheckHook::is_failure(Some(2)));
+        assert!(HealthCheckHook::is_failure(Some(3)));
+        assert!(HealthCheckHook::is_failure(Some(4)));
+        assert!(HealthCheckHook::is_failure(None));
+
+        assert!(!ReconfigureHook::is_failure(Some(0)));
+        assert!(ReconfigureHook::is_failure(Some(1)));
+        assert!(ReconfigureHook::is_failure(None));
+    }
 }
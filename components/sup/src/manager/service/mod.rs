@@ -13,17 +13,19 @@
 // limitations under the License.
 
 pub mod hooks;
+pub mod secrets;
 mod composite_spec;
 mod config;
 mod health;
 mod package;
+mod schema;
 mod spec;
 mod supervisor;
 
 use std;
 use std::fmt;
 use std::fs::File;
-use std::io::BufWriter;
+use std::io::{BufReader, BufWriter};
 use std::io::prelude::*;
 use std::path::{Path, PathBuf};
 use std::result;
@@ -36,34 +38,40 @@ use hcore::crypto::hash;
 use hcore::fs::FS_ROOT_PATH;
 use hcore::package::{PackageIdent, PackageInstall};
 use hcore::service::ServiceGroup;
-use hcore::util::deserialize_using_from_str;
+use hcore::util::{deserialize_using_from_str, serialize_using_to_string};
 use hcore::util::perm::{set_owner, set_permissions};
 use launcher_client::LauncherCli;
 use serde;
-use time::Timespec;
+use time::{self, Timespec};
 
 use super::Sys;
 use self::config::CfgRenderer;
-use self::hooks::{HOOK_PERMISSIONS, Hook, HookTable};
+use self::hooks::{HOOK_PERMISSIONS, Hook, HookFailure, HookTable};
+use self::secrets::Secrets;
 use self::supervisor::Supervisor;
 use error::{Error, Result, SupError};
 use fs;
 use manager;
+use metrics;
 use census::{ServiceFile, CensusRing, ElectionStatus};
 use templating::RenderContext;
 use util;
 
-pub use self::config::{Cfg, UserConfigPath};
+pub use self::config::{Cfg, PackageConfigPaths, UserConfigPath};
 pub use self::health::{HealthCheck, SmokeCheck};
 pub use self::package::Pkg;
 pub use self::composite_spec::CompositeSpec;
-pub use self::spec::{DesiredState, ServiceBind, ServiceSpec, StartStyle};
+pub use butterfly::rumor::election::QuorumBasis;
+pub use self::spec::{DesiredState, ResourceLimits, RestartPolicy, ServiceBind, ServiceSpec,
+                     StartStyle};
 pub use self::supervisor::ProcessState;
 
 static LOGKEY: &'static str = "SR";
 
 pub const SVC_DIR_PERMISSIONS: u32 = 0o770;
 pub const GOSSIP_FILE_PERMISSIONS: u32 = 0o640;
+/// How many health checks in a row must report `Critical` before an update is rolled back.
+const ROLLBACK_CRITICAL_CHECKS: u32 = 3;
 
 lazy_static! {
     static ref HEALTH_CHECK_INTERVAL: Duration = {
@@ -81,6 +89,16 @@ pub struct Service {
     pub start_style: StartStyle,
     pub topology: Topology,
     pub update_strategy: UpdateStrategy,
+    pub canary_percent: u32,
+    pub canary_soak: u64,
+    /// How long, in seconds, after a package update the new release is rolled back if it fails.
+    pub rollback_window: u64,
+    #[serde(serialize_with = "serialize_using_to_string")]
+    pub quorum_basis: QuorumBasis,
+    /// How long, in seconds, an elected leader holds its lease without renewing it.
+    pub leader_lease: u64,
+    /// Progress of the update strategy, set by the `ServiceUpdater`.
+    pub update_status: Option<UpdateStatus>,
     pub cfg: Cfg,
     pub pkg: Pkg,
     pub sys: Arc<Sys>,
@@ -90,13 +108,35 @@ pub struct Service {
     #[serde(skip_serializing)]
     config_renderer: CfgRenderer,
     health_check: HealthCheck,
+    /// How many health checks in a row reported `Critical`.
+    #[serde(skip_serializing)]
+    critical_checks: u32,
     last_election_status: ElectionStatus,
+    role: Option<ElectionRole>,
     needs_reload: bool,
     needs_reconfiguration: bool,
     smoke_check: SmokeCheck,
     binds: Vec<ServiceBind>,
+    /// Names of the binds whose service group has no live members.
+    unsatisfied_binds: Vec<String>,
     hooks: HookTable,
+    last_hook_failure: Option<HookFailure>,
+    /// The package running before the last update, until its rollback window has passed.
+    previous_pkg: Option<PackageIdent>,
+    #[serde(skip_serializing)]
+    updated_at: Option<Instant>,
+    /// Releases which were rolled back and are never updated to again.
+    bad_releases: Vec<PackageIdent>,
+    last_rollback: Option<Rollback>,
+    #[serde(skip_serializing)]
+    rollback_reported: bool,
     config_from: Option<PathBuf>,
+    /// Whether the service spec asks for strict templates; the package's plan may also do so.
+    strict_templates: bool,
+    /// The last compilation of the service's templates, if it failed in strict mode.
+    last_template_failure: Option<TemplateFailure>,
+    #[serde(skip_serializing)]
+    secrets: Option<String>,
     #[serde(skip_serializing)]
     last_health_check: Option<Instant>,
     manager_fs_cfg: Arc<manager::FsCfg>,
@@ -125,35 +165,64 @@ impl Service {
         )?;
         let config_root = Self::config_root(&pkg, spec.config_from.as_ref());
         let hooks_root = Self::hooks_root(&pkg, spec.config_from.as_ref());
+        let strict = spec.strict_templates || pkg.strict_templates;
+        let supervisor = Supervisor::new(&service_group, &spec);
+        let bad_releases = Self::load_bad_releases(&manager_fs_cfg, &service_group);
+        let mut cfg = Cfg::new(&pkg, spec.config_from.as_ref())?;
+        if let Some(ref url) = spec.secrets {
+            let provider = Arc::new(Secrets::from_url(url)?);
+            Secrets::start_refreshing(&provider)?;
+            secrets::register(&service_group, provider.clone());
+            cfg.secrets = Some(provider);
+        }
         Ok(Service {
             sys: sys,
-            cfg: Cfg::new(&pkg, spec.config_from.as_ref())?,
-            config_renderer: CfgRenderer::new(&config_root)?,
+            cfg: cfg,
+            config_renderer: CfgRenderer::new(&config_root, strict)?,
             bldr_url: spec.bldr_url,
             channel: spec.channel,
             health_check: HealthCheck::default(),
+            critical_checks: 0,
             hooks: HookTable::load(
                 &service_group,
                 &hooks_root,
                 fs::svc_hooks_path(&service_group.service()),
+                strict,
             ),
             initialized: false,
             last_election_status: ElectionStatus::None,
+            role: None,
             needs_reload: false,
             needs_reconfiguration: false,
             user_config_updated: false,
             manager_fs_cfg: manager_fs_cfg,
-            supervisor: Supervisor::new(&service_group),
+            supervisor: supervisor,
             pkg: pkg,
             service_group: service_group,
             smoke_check: SmokeCheck::default(),
             binds: spec.binds,
+            unsatisfied_binds: Vec::new(),
+            last_hook_failure: None,
+            previous_pkg: None,
+            updated_at: None,
+            bad_releases: bad_releases,
+            last_rollback: None,
+            rollback_reported: true,
             spec_ident: spec.ident,
             spec_file: spec_file,
             start_style: spec.start_style,
             topology: spec.topology,
             update_strategy: spec.update_strategy,
+            canary_percent: spec.canary_percent,
+            canary_soak: spec.canary_soak,
+            rollback_window: spec.rollback_window,
+            quorum_basis: spec.quorum_basis,
+            leader_lease: spec.leader_lease,
+            update_status: None,
             config_from: spec.config_from,
+            strict_templates: spec.strict_templates,
+            last_template_failure: None,
+            secrets: spec.secrets,
             last_health_check: None,
             svc_encrypted_password: spec.svc_encrypted_password,
             composite: spec.composite,
@@ -284,10 +353,16 @@ impl Service {
                 &self.service_group,
                 &self.pkg,
                 self.svc_encrypted_password.as_ref(),
+                &mut self.last_hook_failure,
             );
         }
     }
 
+    /// Returns the result of the last health check of the service.
+    pub fn health(&self) -> HealthCheck {
+        self.health_check
+    }
+
     pub fn last_state_change(&self) -> Timespec {
         self.supervisor.state_entered
     }
@@ -296,6 +371,7 @@ impl Service {
     ///
     /// Returns `true` if the service was updated.
     pub fn tick(&mut self, census_ring: &CensusRing, launcher: &LauncherCli) -> bool {
+        self.unsatisfied_binds = self.find_unsatisfied_binds(census_ring);
         if !self.initialized {
             if !self.all_binds_satisfied(census_ring) {
                 outputln!(preamble self.service_group, "Waiting for service binds...");
@@ -307,6 +383,8 @@ impl Service {
         if self.update_service_files(census_ring) {
             self.file_updated();
         }
+        let pkg_ident = self.pkg.ident.clone();
+        let health_check = self.health_check;
 
         match self.topology {
             Topology::Standalone => {
@@ -316,6 +394,21 @@ impl Service {
                 let census_group = census_ring.census_group_for(&self.service_group).expect(
                     "Service Group's census entry missing from list!",
                 );
+                let role = match (census_group.election_status, census_group.me()) {
+                    (ElectionStatus::ElectionFinished, Some(me)) => {
+                        if census_group.leader_id.as_ref() == Some(&me.member_id) {
+                            Some(ElectionRole::Leader)
+                        } else {
+                            Some(ElectionRole::Follower)
+                        }
+                    }
+                    _ => None,
+                };
+                if loses_leadership(self.role, role) {
+                    outputln!(preamble self.service_group, "No longer the leader");
+                    self.demote();
+                }
+                self.role = role;
                 match census_group.election_status {
                     ElectionStatus::None => {
                         if self.last_election_status != census_group.election_status {
@@ -355,7 +448,9 @@ impl Service {
                 }
             }
         }
-        svc_updated
+        // A rolled back package or a new health check result has to be gossiped like an
+        // updated config
+        svc_updated || self.pkg.ident != pkg_ident || self.health_check != health_check
     }
 
     pub fn to_spec(&self) -> ServiceSpec {
@@ -371,9 +466,21 @@ impl Service {
         spec.channel = self.channel.clone();
         spec.topology = self.topology;
         spec.update_strategy = self.update_strategy;
+        spec.canary_percent = self.canary_percent;
+        spec.canary_soak = self.canary_soak;
+        spec.rollback_window = self.rollback_window;
+        spec.quorum_basis = self.quorum_basis;
+        spec.leader_lease = self.leader_lease;
         spec.binds = self.binds.clone();
         spec.start_style = self.start_style;
+        spec.restart_policy = self.supervisor.restart_policy;
+        spec.restart_backoff_cap = self.supervisor.restart_backoff_cap;
+        spec.restart_limit = self.supervisor.restart_limit;
+        spec.restart_window = self.supervisor.restart_window;
+        spec.limits = self.supervisor.limits;
         spec.config_from = self.config_from.clone();
+        spec.strict_templates = self.strict_templates;
+        spec.secrets = self.secrets.clone();
         if let Some(ref password) = self.svc_encrypted_password {
             spec.svc_encrypted_password = Some(password.clone())
         }
@@ -384,7 +491,7 @@ impl Service {
         let mut ret = true;
         for ref bind in self.binds.iter() {
             if let Some(group) = census_ring.census_group_for(&bind.service_group) {
-                if group.members().iter().all(|m| !m.alive()) {
+                if !group.has_live_member_with_tags(&bind.tags) {
                     ret = false;
                     outputln!(preamble self.service_group,
                               "The specified service group '{}' for binding '{}' is present in the \
@@ -405,6 +512,18 @@ impl Service {
         ret
     }
 
+    /// Returns the names of the binds whose service group has no live members.
+    fn find_unsatisfied_binds(&self, census_ring: &CensusRing) -> Vec<String> {
+        self.binds
+            .iter()
+            .filter(|bind| match census_ring.census_group_for(&bind.service_group) {
+                Some(group) => !group.has_live_member_with_tags(&bind.tags),
+                None => true,
+            })
+            .map(|bind| bind.name.clone())
+            .collect()
+    }
+
     /// Updates the process state of the service's supervisor
     fn check_process(&mut self) -> bool {
         self.supervisor.check_process()
@@ -424,6 +543,10 @@ impl Service {
         );
         let cfg_updated_from_rumors = self.cfg.update(census_group);
         let cfg_changed = cfg_updated_from_rumors || self.user_config_updated;
+        let secrets_rotated = self.cfg.secrets_rotated();
+        if secrets_rotated {
+            outputln!(preamble self.service_group, "Secrets rotated upstream");
+        }
 
         if self.user_config_updated {
             if let Err(e) = self.cfg.reload_user() {
@@ -434,22 +557,36 @@ impl Service {
         }
 
 
-        if cfg_changed || census_ring.changed() {
-            let (reload, reconfigure) = {
+        if cfg_changed || secrets_rotated || census_ring.changed() {
+            let (reload, reconfigure, failure) = {
                 let ctx = self.render_context(census_ring);
 
-                // If any hooks have changed, execute the `reload` hook (if present) or restart the
-                // service.
-                let reload = self.compile_hooks(&ctx);
-
-                // If the configuration has changed, execute the `reload` and `reconfigure` hooks.
-                // Note that the configuration does not necessarily change every time the user config has (e.g.
-                // when only a comment has been added to the latter)
-                let reconfigure = self.compile_configuration(&ctx);
-
-                (reload, reconfigure)
+                // In strict mode nothing is written unless every template compiles, so the service
+                // keeps running with its previous configuration and hooks.
+                match self.check_templates(&ctx) {
+                    Err(err) => (false, false, Some(err)),
+                    Ok(()) => {
+                        // If any hooks have changed, execute the `reload` hook (if present) or
+                        // restart the service.
+                        let reload = self.compile_hooks(&ctx);
+
+                        // If the configuration has changed, execute the `reload` and
+                        // `reconfigure` hooks. Note that the configuration does not necessarily
+                        // change every time the user config has (e.g. when only a comment has
+                        // been added to the latter)
+                        let reconfigure = self.compile_configuration(&ctx);
+
+                        (reload, reconfigure, None)
+                    }
+                }
             };
 
+            if let Some(ref err) = failure {
+                outputln!(preamble self.service_group,
+                          "Templates failed to compile, keeping the current configuration: {}",
+                          err);
+            }
+            self.last_template_failure = failure.as_ref().map(TemplateFailure::new);
             self.needs_reload = reload;
             self.needs_reconfiguration = reconfigure;
         }
@@ -457,31 +594,55 @@ impl Service {
         cfg_changed
     }
 
-    /// Replace the package of the running service and restart its system process.
+    /// Replace the package of the running service and restart its system process. The previous
+    /// package is restored if the new one fails within the rollback window.
     pub fn update_package(&mut self, package: PackageInstall, launcher: &LauncherCli) {
+        let previous = self.pkg.ident.clone();
+        if self.switch_package(package, launcher) && self.rollback_window > 0 {
+            self.previous_pkg = Some(previous);
+            self.updated_at = Some(Instant::now());
+            self.smoke_check = SmokeCheck::Pending;
+        }
+    }
+
+    /// Returns the releases of the service's package which were rolled back.
+    pub fn bad_releases(&self) -> &[PackageIdent] {
+        &self.bad_releases
+    }
+
+    /// Returns `false` if the package couldn't be loaded.
+    fn switch_package(&mut self, package: PackageInstall, launcher: &LauncherCli) -> bool {
         match Pkg::from_install(package) {
             Ok(pkg) => {
                 outputln!(preamble self.service_group,
                             "Updating service {} to {}", self.pkg.ident, pkg.ident);
-                match CfgRenderer::new(&Self::config_root(&pkg, self.config_from.as_ref())) {
+                let strict = self.strict_templates || pkg.strict_templates;
+                match CfgRenderer::new(
+                    &Self::config_root(&pkg, self.config_from.as_ref()),
+                    strict,
+                ) {
                     Ok(renderer) => self.config_renderer = renderer,
                     Err(e) => {
                         outputln!(preamble self.service_group,
                                   "Failed to load config templates after updating package, {}", e);
-                        return;
+                        return false;
                     }
                 }
                 self.hooks = HookTable::load(
                     &self.service_group,
                     &Self::hooks_root(&pkg, self.config_from.as_ref()),
                     fs::svc_hooks_path(self.service_group.service()),
+                    strict,
                 );
                 self.pkg = pkg;
+                // The last health check was of the previous package
+                self.health_check = HealthCheck::default();
+                self.critical_checks = 0;
             }
             Err(err) => {
                 outputln!(preamble self.service_group,
                           "Unexpected error while updating package, {}", err);
-                return;
+                return false;
             }
         }
         if let Err(err) = self.supervisor.stop(launcher) {
@@ -489,6 +650,95 @@ impl Service {
                       "Error stopping process while updating package: {}", err);
         }
         self.initialized = false;
+        true
+    }
+
+    /// Rolls the last package update back if the new release failed its smoke test or stayed
+    /// critical within the rollback window. Returns `true` if the package was rolled back.
+    fn check_for_rollback(&mut self, launcher: &LauncherCli) -> bool {
+        let updated_at = match self.updated_at {
+            Some(updated_at) => updated_at,
+            None => return false,
+        };
+        match rollback_check(
+            updated_at,
+            Duration::from_secs(self.rollback_window),
+            Instant::now(),
+            &self.smoke_check,
+            self.critical_checks,
+        ) {
+            RollbackCheck::Expired => {
+                self.previous_pkg = None;
+                self.updated_at = None;
+                false
+            }
+            RollbackCheck::Keep => false,
+            RollbackCheck::RollBack(reason) => self.roll_back(reason, launcher),
+        }
+    }
+
+    /// Returns the last rollback of the service if it hasn't been reported to the event stream
+    /// yet.
+    pub fn take_unreported_rollback(&mut self) -> Option<Rollback> {
+        if self.rollback_reported {
+            return None;
+        }
+        self.rollback_reported = true;
+        self.last_rollback.clone()
+    }
+
+    fn roll_back(&mut self, reason: RollbackReason, launcher: &LauncherCli) -> bool {
+        self.updated_at = None;
+        let previous = match self.previous_pkg.take() {
+            Some(previous) => previous,
+            None => return false,
+        };
+        let fs_root_path = Path::new(&*FS_ROOT_PATH);
+        let package = match PackageInstall::load(&previous, Some(fs_root_path)) {
+            Ok(package) => package,
+            Err(err) => {
+                outputln!(preamble self.service_group,
+                          "Unable to roll back to {}, {}", previous, err);
+                return false;
+            }
+        };
+        let bad_release = self.pkg.ident.clone();
+        outputln!(preamble self.service_group,
+                  "Rolling back from {} to {}; {}", bad_release, previous, reason);
+        if !self.switch_package(package, launcher) {
+            return false;
+        }
+        self.last_rollback = Some(Rollback {
+            from: bad_release.to_string(),
+            to: previous.to_string(),
+            reason: reason,
+            timestamp: time::now_utc().rfc3339().to_string(),
+        });
+        self.rollback_reported = false;
+        self.pin_bad_release(bad_release);
+        true
+    }
+
+    fn load_bad_releases(
+        manager_fs_cfg: &manager::FsCfg,
+        service_group: &ServiceGroup,
+    ) -> Vec<PackageIdent> {
+        read_bad_releases(&manager_fs_cfg.bad_releases_file(service_group))
+    }
+
+    fn pin_bad_release(&mut self, ident: PackageIdent) {
+        if self.bad_releases.contains(&ident) {
+            return;
+        }
+        self.bad_releases.push(ident);
+        let pins_file = self.manager_fs_cfg.bad_releases_file(&self.service_group);
+        if let Err(err) = write_bad_releases(&pins_file, &self.bad_releases) {
+            warn!(
+                "Couldn't write bad releases file, {}, {}",
+                self.service_group,
+                err
+            );
+        }
     }
 
     pub fn to_rumor(&self, incarnation: u64) -> ServiceRumor {
@@ -509,6 +759,7 @@ impl Service {
             exported.as_ref(),
         );
         rumor.set_incarnation(incarnation);
+        rumor.set_health_check(self.health_check.into());
         rumor
     }
 
@@ -524,6 +775,7 @@ impl Service {
                 &self.service_group,
                 &self.pkg,
                 self.svc_encrypted_password.as_ref(),
+                &mut self.last_hook_failure,
             )
         }
     }
@@ -536,6 +788,7 @@ impl Service {
                 &self.service_group,
                 &self.pkg,
                 self.svc_encrypted_password.as_ref(),
+                &mut self.last_hook_failure,
             );
         }
     }
@@ -546,6 +799,18 @@ impl Service {
                 &self.service_group,
                 &self.pkg,
                 self.svc_encrypted_password.as_ref(),
+                &mut self.last_hook_failure,
+            );
+        }
+    }
+
+    fn demote(&mut self) {
+        if let Some(ref hook) = self.hooks.demote {
+            hook.run(
+                &self.service_group,
+                &self.pkg,
+                self.svc_encrypted_password.as_ref(),
+                &mut self.last_hook_failure,
             );
         }
     }
@@ -556,6 +821,7 @@ impl Service {
                 &self.service_group,
                 &self.pkg,
                 self.svc_encrypted_password.as_ref(),
+                &mut self.last_hook_failure,
             );
         }
     }
@@ -564,11 +830,14 @@ impl Service {
         if !self.initialized {
             return None;
         }
+        // The hook is run by elections outside of the main loop, which owns the last hook
+        // failure, so its failures are only logged.
         self.hooks.suitability.as_ref().and_then(|hook| {
             hook.run(
                 &self.service_group,
                 &self.pkg,
                 self.svc_encrypted_password.as_ref(),
+                &mut None,
             )
         })
     }
@@ -620,6 +889,16 @@ impl Service {
         }
     }
 
+    /// Renders the service's hooks and configuration templates without writing them, if the
+    /// service's templates are strict.
+    fn check_templates(&self, ctx: &RenderContext) -> Result<()> {
+        if self.strict_templates || self.pkg.strict_templates {
+            self.hooks.check(ctx)?;
+            self.config_renderer.check(ctx)?;
+        }
+        Ok(())
+    }
+
     /// Helper for compiling configuration templates into configuration files.
     ///
     /// Returns `true` if the configuration has changed.
@@ -686,6 +965,13 @@ impl Service {
                 self.initialized = true;
                 return;
             }
+            // Nothing has been written for a service whose strict templates never compiled, so
+            // there is no configuration to initialize or start it with.
+            if self.last_template_failure.is_some() {
+                outputln!(preamble self.service_group,
+                          "Waiting for the templates to compile before starting");
+                return;
+            }
             self.initialize();
             if self.initialized {
                 self.start(launcher);
@@ -701,6 +987,24 @@ impl Service {
                 }
                 None => self.run_health_check_hook(),
             }
+            if self.smoke_check == SmokeCheck::Pending && self.updated_at.is_some() &&
+                !self.process_down()
+            {
+                self.run_smoke_test_hook();
+            }
+            if self.check_for_rollback(launcher) {
+                return;
+            }
+
+            // A process which is down is only started again when its restart policy allows it.
+            // The reconfigure hook still runs, so that the new configuration is not missed by a
+            // service which stays down.
+            if self.process_down() && !self.supervisor.restart_due(launcher) {
+                if self.needs_reconfiguration {
+                    self.reconfigure();
+                }
+                return;
+            }
 
             // NOTE: if you need reconfiguration and you DON'T have a
             // reload script, you're going to restart anyway.
@@ -715,13 +1019,14 @@ impl Service {
     }
 
     /// Run file_updated hook if present.
-    fn file_updated(&self) -> bool {
+    fn file_updated(&mut self) -> bool {
         if self.initialized {
             if let Some(ref hook) = self.hooks.file_updated {
                 return hook.run(
                     &self.service_group,
                     &self.pkg,
                     self.svc_encrypted_password.as_ref(),
+                    &mut self.last_hook_failure,
                 );
             }
         }
@@ -782,6 +1087,7 @@ impl Service {
                 &self.service_group,
                 &self.pkg,
                 self.svc_encrypted_password.as_ref(),
+                &mut self.last_hook_failure,
             )
         } else {
             match self.supervisor.status() {
@@ -789,10 +1095,36 @@ impl Service {
                 (false, _) => HealthCheck::Critical,
             }
         };
+        let check_result = template_health(check_result, self.last_template_failure.as_ref());
         self.last_health_check = Some(Instant::now());
+        self.health_check = check_result;
+        self.critical_checks = match check_result {
+            HealthCheck::Critical => self.critical_checks + 1,
+            _ => 0,
+        };
+        metrics::record_health_check(&self.service_group, check_result);
         self.cache_health_check(check_result);
     }
 
+    /// Runs the smoke test hook of a freshly updated package once its process is up.
+    fn run_smoke_test_hook(&mut self) {
+        self.smoke_check = match self.hooks.smoke_test {
+            Some(ref hook) => {
+                match hook.run(
+                    &self.service_group,
+                    &self.pkg,
+                    self.svc_encrypted_password.as_ref(),
+                    &mut self.last_hook_failure,
+                ) {
+                    // The hook couldn't be run at all
+                    SmokeCheck::Pending => SmokeCheck::Failed(-1),
+                    check => check,
+                }
+            }
+            None => SmokeCheck::Ok,
+        };
+    }
+
     // Returns `false` if the write fails.
     fn cache_service_file(&mut self, service_file: &ServiceFile) -> bool {
         let file = self.pkg.svc_files_path.join(&service_file.filename);
@@ -924,6 +1256,7 @@ pub enum UpdateStrategy {
     None,
     AtOnce,
     Rolling,
+    Canary,
 }
 
 impl UpdateStrategy {
@@ -932,6 +1265,7 @@ impl UpdateStrategy {
             UpdateStrategy::None => "none",
             UpdateStrategy::AtOnce => "at-once",
             UpdateStrategy::Rolling => "rolling",
+            UpdateStrategy::Canary => "canary",
         }
     }
 }
@@ -944,6 +1278,7 @@ impl FromStr for UpdateStrategy {
             "none" => Ok(UpdateStrategy::None),
             "at-once" => Ok(UpdateStrategy::AtOnce),
             "rolling" => Ok(UpdateStrategy::Rolling),
+            "canary" => Ok(UpdateStrategy::Canary),
             _ => Err(sup_error!(
                 Error::InvalidUpdateStrategy(String::from(strategy))
             )),
@@ -981,13 +1316,200 @@ impl serde::Serialize for UpdateStrategy {
     }
 }
 
+/// Progress of a service's update strategy.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum UpdateStatus {
+    /// Polling for a newer version of the package.
+    Polling,
+    /// Waiting for the service group to elect an update leader.
+    AwaitingElection,
+    InElection,
+    /// Update leader waiting for all followers to run its version.
+    LeaderWaiting,
+    /// Update leader polling for a newer version of the package.
+    LeaderPolling,
+    /// Follower waiting for its turn to update.
+    FollowerWaiting,
+    FollowerUpdating,
+    /// Update leader waiting for the canaries to soak a new package before promoting it.
+    CanarySoaking,
+    /// Waiting for the update leader to start or finish a canary update.
+    CanaryWaiting,
+    /// Installing the package under test, the promoted package or the package rolled back to.
+    CanaryUpdating,
+}
+
+impl fmt::Display for UpdateStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let value = match *self {
+            UpdateStatus::Polling => "polling",
+            UpdateStatus::AwaitingElection => "awaiting-election",
+            UpdateStatus::InElection => "in-election",
+            UpdateStatus::LeaderWaiting => "leader-waiting",
+            UpdateStatus::LeaderPolling => "leader-polling",
+            UpdateStatus::FollowerWaiting => "follower-waiting",
+            UpdateStatus::FollowerUpdating => "follower-updating",
+            UpdateStatus::CanarySoaking => "canary-soaking",
+            UpdateStatus::CanaryWaiting => "canary-waiting",
+            UpdateStatus::CanaryUpdating => "canary-updating",
+        };
+        write!(f, "{}", value)
+    }
+}
+
+/// A compilation of a service's templates which failed in strict mode.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub struct TemplateFailure {
+    /// The template which failed, when it's known.
+    pub template: Option<String>,
+    pub error: String,
+    pub timestamp: String,
+}
+
+impl TemplateFailure {
+    fn new(err: &SupError) -> Self {
+        let template = match err.err {
+            Error::TemplateRenderError(ref e) => e.template_name.clone(),
+            _ => None,
+        };
+        TemplateFailure {
+            template: template,
+            error: err.to_string(),
+            timestamp: time::now_utc().rfc3339().to_string(),
+        }
+    }
+}
+
+/// A package update which was reverted because the new release failed.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub struct Rollback {
+    pub from: String,
+    pub to: String,
+    pub reason: RollbackReason,
+    pub timestamp: String,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub enum RollbackReason {
+    HealthCheckCritical,
+    /// The smoke test hook exited with the given code.
+    SmokeTestFailed(i32),
+}
+
+impl fmt::Display for RollbackReason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            RollbackReason::HealthCheckCritical => write!(f, "health check is critical"),
+            RollbackReason::SmokeTestFailed(code) => write!(f, "smoke test exited {}", code),
+        }
+    }
+}
+
+/// What to do about the package a service was last updated to.
+#[derive(Debug, PartialEq, Eq)]
+enum RollbackCheck {
+    /// The rollback window has passed and the update is kept for good.
+    Expired,
+    /// The update is still inside its rollback window and hasn't failed.
+    Keep,
+    RollBack(RollbackReason),
+}
+
+/// Decides whether an update made at `updated_at` should be rolled back given the result of the
+/// smoke test and the number of health checks in a row which reported `Critical` at `now`. A
+/// single critical check isn't enough, the health must stay critical.
+fn rollback_check(
+    updated_at: Instant,
+    window: Duration,
+    now: Instant,
+    smoke_check: &SmokeCheck,
+    critical_checks: u32,
+) -> RollbackCheck {
+    if now.duration_since(updated_at) >= window {
+        return RollbackCheck::Expired;
+    }
+    match *smoke_check {
+        SmokeCheck::Failed(code) => RollbackCheck::RollBack(RollbackReason::SmokeTestFailed(code)),
+        _ if critical_checks >= ROLLBACK_CRITICAL_CHECKS => {
+            RollbackCheck::RollBack(RollbackReason::HealthCheckCritical)
+        }
+        _ => RollbackCheck::Keep,
+    }
+}
+
+/// Reads the releases pinned as bad from the given file, skipping any line which isn't a package
+/// identifier. A missing file has no bad releases.
+fn read_bad_releases(path: &Path) -> Vec<PackageIdent> {
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(_) => return Vec::new(),
+    };
+    BufReader::new(file)
+        .lines()
+        .filter_map(|line| line.ok())
+        .filter_map(|line| PackageIdent::from_str(line.trim()).ok())
+        .collect()
+}
+
+/// Writes the releases pinned as bad to the given file, through a temporary file so a crash never
+/// leaves it half written.
+fn write_bad_releases(path: &Path, idents: &[PackageIdent]) -> std::io::Result<()> {
+    let tmp_file = path.with_extension("tmp");
+    {
+        let mut writer = BufWriter::new(File::create(&tmp_file)?);
+        for ident in idents {
+            writeln!(writer, "{}", ident)?;
+        }
+        writer.flush()?;
+    }
+    std::fs::rename(&tmp_file, path)
+}
+
+/// A service kept on its previous configuration because its templates don't compile is
+/// degraded, however healthy its process is.
+fn template_health(check: HealthCheck, failure: Option<&TemplateFailure>) -> HealthCheck {
+    match (check, failure) {
+        (HealthCheck::Ok, Some(_)) => HealthCheck::Warning,
+        (check, _) => check,
+    }
+}
+
+/// Decides whether a member moving from the `old` to the `new` role of its election runs its
+/// demote hook: it does whenever it stops being the leader, be it for a new leader or because
+/// it stepped down and restarted the election.
+fn loses_leadership(old: Option<ElectionRole>, new: Option<ElectionRole>) -> bool {
+    old == Some(ElectionRole::Leader) && new != Some(ElectionRole::Leader)
+}
+
+/// Role of a member of a service group with a leader topology once its election has finished.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum ElectionRole {
+    Leader,
+    Follower,
+}
+
+impl fmt::Display for ElectionRole {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let value = match *self {
+            ElectionRole::Leader => "leader",
+            ElectionRole::Follower => "follower",
+        };
+        write!(f, "{}", value)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use std::str::FromStr;
+    use std::time::{Duration, Instant};
 
+    use hcore::package::PackageIdent;
+    use tempdir::TempDir;
     use toml;
 
-    use super::{Topology, UpdateStrategy};
+    use super::{loses_leadership, read_bad_releases, rollback_check, template_health,
+                write_bad_releases, ElectionRole, HealthCheck, RollbackCheck, RollbackReason,
+                SmokeCheck, TemplateFailure, Topology, UpdateStatus, UpdateStrategy};
     use error::Error::*;
 
     #[test]
@@ -1117,4 +1639,161 @@ mod test {
 
         assert!(toml.starts_with(r#"key = "at-once""#));
     }
+
+    #[test]
+    fn update_status_to_string() {
+        assert_eq!("awaiting-election", UpdateStatus::AwaitingElection.to_string());
+        assert_eq!("follower-updating", UpdateStatus::FollowerUpdating.to_string());
+        assert_eq!("canary-soaking", UpdateStatus::CanarySoaking.to_string());
+    }
+
+    #[test]
+    fn rollback_reason_to_string() {
+        assert_eq!(
+            "health check is critical",
+            RollbackReason::HealthCheckCritical.to_string()
+        );
+        assert_eq!(
+            "smoke test exited 3",
+            RollbackReason::SmokeTestFailed(3).to_string()
+        );
+    }
+
+    #[test]
+    fn rollback_check_keeps_a_healthy_update_in_the_window() {
+        let updated_at = Instant::now();
+        let window = Duration::from_secs(60);
+        assert_eq!(
+            RollbackCheck::Keep,
+            rollback_check(
+                updated_at,
+                window,
+                updated_at + Duration::from_secs(59),
+                &SmokeCheck::Ok,
+                0,
+            )
+        );
+        assert_eq!(
+            RollbackCheck::Keep,
+            rollback_check(
+                updated_at,
+                window,
+                updated_at,
+                &SmokeCheck::Pending,
+                0,
+            )
+        );
+    }
+
+    #[test]
+    fn rollback_check_rolls_back_a_failed_smoke_test() {
+        let updated_at = Instant::now();
+        assert_eq!(
+            RollbackCheck::RollBack(RollbackReason::SmokeTestFailed(2)),
+            rollback_check(
+                updated_at,
+                Duration::from_secs(60),
+                updated_at + Duration::from_secs(1),
+                &SmokeCheck::Failed(2),
+                ROLLBACK_CRITICAL_CHECKS,
+            )
+        );
+    }
+
+    #[test]
+    fn rollback_check_rolls_back_a_health_check_which_stays_critical() {
+        let updated_at = Instant::now();
+        assert_eq!(
+            RollbackCheck::RollBack(RollbackReason::HealthCheckCritical),
+            rollback_check(
+                updated_at,
+                Duration::from_secs(60),
+                updated_at + Duration::from_secs(1),
+                &SmokeCheck::Ok,
+                ROLLBACK_CRITICAL_CHECKS,
+            )
+        );
+    }
+
+    #[test]
+    fn rollback_check_keeps_an_update_through_a_passing_critical_health_check() {
+        let updated_at = Instant::now();
+        assert_eq!(
+            RollbackCheck::Keep,
+            rollback_check(
+                updated_at,
+                Duration::from_secs(60),
+                updated_at + Duration::from_secs(1),
+                &SmokeCheck::Ok,
+                ROLLBACK_CRITICAL_CHECKS - 1,
+            )
+        );
+    }
+
+    #[test]
+    fn rollback_check_expires_after_the_window() {
+        let updated_at = Instant::now();
+        assert_eq!(
+            RollbackCheck::Expired,
+            rollback_check(
+                updated_at,
+                Duration::from_secs(60),
+                updated_at + Duration::from_secs(60),
+                &SmokeCheck::Failed(1),
+                ROLLBACK_CRITICAL_CHECKS,
+            )
+        );
+    }
+
+    #[test]
+    fn bad_releases_round_trip() {
+        let tmp = TempDir::new("bad-releases").unwrap();
+        let path = tmp.path().join("redis.default.bad-releases");
+        assert!(read_bad_releases(&path).is_empty());
+
+        let idents = vec![
+            PackageIdent::from_str("core/redis/3.2.4/20170514150022").unwrap(),
+            PackageIdent::from_str("core/redis/3.2.4/20170520150022").unwrap(),
+        ];
+        write_bad_releases(&path, &idents).unwrap();
+        assert_eq!(idents, read_bad_releases(&path));
+        assert!(!path.with_extension("tmp").exists());
+    }
+
+    #[test]
+    fn election_role_to_string() {
+        assert_eq!("leader", ElectionRole::Leader.to_string());
+        assert_eq!("follower", ElectionRole::Follower.to_string());
+    }
+
+    #[test]
+    fn a_template_failure_downgrades_an_ok_health_check_to_a_warning() {
+        let failure = TemplateFailure {
+            template: Some("app.conf".to_string()),
+            error: "Variable not found in strict mode".to_string(),
+            timestamp: "2017-11-09T12:00:00Z".to_string(),
+        };
+        assert_eq!(
+            template_health(HealthCheck::Ok, Some(&failure)),
+            HealthCheck::Warning
+        );
+        assert_eq!(
+            template_health(HealthCheck::Critical, Some(&failure)),
+            HealthCheck::Critical
+        );
+        assert_eq!(template_health(HealthCheck::Ok, None), HealthCheck::Ok);
+    }
+
+    #[test]
+    fn a_leader_that_steps_down_or_is_replaced_is_demoted() {
+        let leader = Some(ElectionRole::Leader);
+        let follower = Some(ElectionRole::Follower);
+        assert!(loses_leadership(leader, None));
+        assert!(loses_leadership(leader, follower));
+        assert!(!loses_leadership(leader, leader));
+        assert!(!loses_leadership(follower, None));
+        assert!(!loses_leadership(follower, leader));
+        assert!(!loses_leadership(None, leader));
+        assert!(!loses_leadership(None, None));
+    }
 }
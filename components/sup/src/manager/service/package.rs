@@ -14,8 +14,11 @@
 
 use std::collections::HashMap;
 use std::env;
+use std::fs::File;
+use std::io::{self, Read};
 use std::ops::Deref;
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
 
 use hcore::fs::FS_ROOT_PATH;
 use hcore::package::{PackageIdent, PackageInstall};
@@ -23,9 +26,12 @@ use hcore::util::{deserialize_using_from_str, serialize_using_to_string};
 
 use error::{Error, Result};
 use fs;
+use manager::service::ResourceLimits;
 use util;
 
 const PATH_KEY: &'static str = "PATH";
+const SVC_LIMITS_FILE: &'static str = "SVC_LIMITS";
+const STRICT_TEMPLATES_FILE: &'static str = "STRICT_TEMPLATES";
 static LOGKEY: &'static str = "PK";
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -99,11 +105,19 @@ pub struct Pkg {
     pub svc_run: PathBuf,
     pub svc_user: String,
     pub svc_group: String,
+    /// Resource limits declared by the package's plan.
+    #[serde(default)]
+    pub svc_limits: ResourceLimits,
+    /// Whether the package's plan asks for its templates to be compiled in strict mode.
+    #[serde(default)]
+    pub strict_templates: bool,
 }
 
 impl Pkg {
     pub fn from_install(package: PackageInstall) -> Result<Self> {
         let (svc_user, svc_group) = util::users::get_user_and_group(&package)?;
+        let svc_limits = svc_limits(&package.installed_path)?;
+        let strict_templates = strict_templates(&package.installed_path)?;
         let pkg = Pkg {
             svc_path: fs::svc_path(&package.ident.name),
             svc_config_path: fs::svc_config_path(&package.ident.name),
@@ -115,6 +129,8 @@ impl Pkg {
             svc_pid_file: fs::svc_pid_file(&package.ident.name),
             svc_user: svc_user,
             svc_group: svc_group,
+            svc_limits: svc_limits,
+            strict_templates: strict_templates,
             env: Env::new(&package)?,
             deps: package.tdeps().map_err(|e| {
                 sup_error!(Error::BadPackage(package.clone(), e))
@@ -138,4 +154,62 @@ impl Pkg {
         };
         Ok(pkg)
     }
+
+    /// Describes a package which hasn't been built yet from the directory of its plan, so that
+    /// its templates can be rendered. The package has no dependencies, runtime environment,
+    /// exposes or exports.
+    pub fn from_plan(ident: PackageIdent, plan_path: PathBuf) -> Result<Self> {
+        let (svc_user, svc_group) = util::users::default_user_and_group()?;
+        let pkg = Pkg {
+            svc_path: fs::svc_path(&ident.name),
+            svc_config_path: fs::svc_config_path(&ident.name),
+            svc_data_path: fs::svc_data_path(&ident.name),
+            svc_files_path: fs::svc_files_path(&ident.name),
+            svc_run: fs::svc_path(&ident.name).join("run"),
+            svc_static_path: fs::svc_static_path(&ident.name),
+            svc_var_path: fs::svc_var_path(&ident.name),
+            svc_pid_file: fs::svc_pid_file(&ident.name),
+            svc_user: svc_user,
+            svc_group: svc_group,
+            svc_limits: ResourceLimits::default(),
+            strict_templates: false,
+            env: Env(HashMap::new()),
+            deps: Vec::new(),
+            exposes: Vec::new(),
+            exports: HashMap::new(),
+            path: plan_path,
+            origin: ident.origin.clone(),
+            name: ident.name.clone(),
+            version: ident.version.clone().unwrap_or_default(),
+            release: ident.release.clone().unwrap_or_default(),
+            ident: ident,
+        };
+        Ok(pkg)
+    }
+}
+
+/// Reads the resource limits declared by a package's plan, if any.
+fn svc_limits(installed_path: &Path) -> Result<ResourceLimits> {
+    let mut file = match File::open(installed_path.join(SVC_LIMITS_FILE)) {
+        Ok(file) => file,
+        Err(ref err) if err.kind() == io::ErrorKind::NotFound => {
+            return Ok(ResourceLimits::default())
+        }
+        Err(err) => return Err(sup_error!(Error::Io(err))),
+    };
+    let mut body = String::new();
+    file.read_to_string(&mut body)?;
+    ResourceLimits::from_str(&body)
+}
+
+/// Reads whether the package's plan turned on strict templates.
+fn strict_templates(installed_path: &Path) -> Result<bool> {
+    let mut file = match File::open(installed_path.join(STRICT_TEMPLATES_FILE)) {
+        Ok(file) => file,
+        Err(ref err) if err.kind() == io::ErrorKind::NotFound => return Ok(false),
+        Err(err) => return Err(sup_error!(Error::Io(err))),
+    };
+    let mut body = String::new();
+    file.read_to_string(&mut body)?;
+    Ok(body.trim() == "true")
 }
@@ -0,0 +1,430 @@
+// Copyright (c) 2017 Chef Software Inc. and/or applicable contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+//! Config schemas shipped by packages next to their `default.toml`.
+//!
+//! A package may declare the shape of its configuration in `config_schema.json` or, for plans
+//! which prefer TOML, `config_schema.toml`. The schema is a subset of JSON Schema: `type` (a name
+//! or a list of names), `properties`, `required`, `additionalProperties`, `items`, `enum`,
+//! `minimum`, `maximum`, `minLength`, `maxLength`, `pattern`, `minItems` and `maxItems`. Other
+//! keywords are ignored. Every configuration layer merged by the Supervisor is checked against
+//! it, so that `port = "abc"` is rejected instead of silently breaking the service.
+
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+use std::result;
+
+use regex::Regex;
+use serde_json::{self, Map, Value as Json};
+use toml;
+
+use error::{Error, Result};
+
+static LOGKEY: &'static str = "CS";
+
+/// Names of the schema files looked up in a package's config directory, in order of preference.
+pub const CONFIG_SCHEMA_FILES: &'static [&'static str] =
+    &["config_schema.json", "config_schema.toml"];
+
+const TYPES: &'static [&'static str] =
+    &["array", "boolean", "integer", "null", "number", "object", "string"];
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConfigSchema(Json);
+
+impl ConfigSchema {
+    /// Loads the schema shipped in the given config directory, if any.
+    pub fn load<T: AsRef<Path>>(dir: T) -> Result<Option<Self>> {
+        for name in CONFIG_SCHEMA_FILES {
+            let path = dir.as_ref().join(name);
+            let mut file = match File::open(&path) {
+                Ok(file) => file,
+                Err(_) => continue,
+            };
+            let mut content = String::new();
+            file.read_to_string(&mut content)?;
+            let schema = if name.ends_with(".json") {
+                serde_json::from_str(&content).map_err(|e| e.to_string())
+            } else {
+                toml::from_str::<toml::Value>(&content)
+                    .map_err(|e| e.to_string())
+                    .and_then(|toml| serde_json::to_value(toml).map_err(|e| e.to_string()))
+            };
+            return schema
+                .and_then(Self::from_json)
+                .map(Some)
+                .map_err(|reason| sup_error!(Error::BadConfigSchema(path, reason)));
+        }
+        Ok(None)
+    }
+
+    /// Checks that the given JSON is a schema this module understands.
+    pub fn from_json(json: Json) -> result::Result<Self, String> {
+        check_schema(&json, "")?;
+        Ok(ConfigSchema(json))
+    }
+
+    /// Returns every violation of the schema found in the given configuration, each one prefixed
+    /// with the path of the offending value (e.g. `server.port`).
+    pub fn validate(&self, config: &toml::Value) -> Vec<String> {
+        let mut errors = Vec::new();
+        match serde_json::to_value(config) {
+            Ok(json) => validate(&self.0, &json, "", &mut errors),
+            Err(err) => errors.push(format!("cannot be checked, {}", err)),
+        }
+        errors
+    }
+}
+
+fn check_schema(schema: &Json, path: &str) -> result::Result<(), String> {
+    let schema = match *schema {
+        Json::Object(ref schema) => schema,
+        _ => return Err(format!("the schema of {} is not an object", display(path))),
+    };
+    if let Some(types) = schema.get("type") {
+        let names = match *types {
+            Json::String(ref name) => vec![name.as_str()],
+            Json::Array(ref names) => names.iter().filter_map(|n| n.as_str()).collect(),
+            _ => vec![],
+        };
+        if names.is_empty() || names.iter().any(|n| !TYPES.contains(n)) {
+            return Err(format!("unknown type {} for {}", types, display(path)));
+        }
+    }
+    if let Some(pattern) = schema.get("pattern") {
+        match pattern.as_str().map(Regex::new) {
+            Some(Ok(_)) => (),
+            _ => return Err(format!("invalid pattern {} for {}", pattern, display(path))),
+        }
+    }
+    if let Some(properties) = schema.get("properties") {
+        match *properties {
+            Json::Object(ref properties) => {
+                for (key, property) in properties.iter() {
+                    check_schema(property, &join(path, key))?;
+                }
+            }
+            _ => return Err(format!("the properties of {} are not an object", display(path))),
+        }
+    }
+    if let Some(additional @ &Json::Object(_)) = schema.get("additionalProperties") {
+        check_schema(additional, &join(path, "*"))?;
+    }
+    if let Some(items) = schema.get("items") {
+        check_schema(items, &format!("{}[]", path))?;
+    }
+    Ok(())
+}
+
+fn validate(schema: &Json, value: &Json, path: &str, errors: &mut Vec<String>) {
+    let schema = match *schema {
+        Json::Object(ref schema) => schema,
+        _ => return,
+    };
+    if let Some(types) = schema.get("type") {
+        let matches = match *types {
+            Json::String(ref name) => is_type(value, name),
+            Json::Array(ref names) => {
+                names.iter().filter_map(|n| n.as_str()).any(
+                    |n| is_type(value, n),
+                )
+            }
+            _ => true,
+        };
+        if !matches {
+            errors.push(format!(
+                "{}: expected {}, found {}",
+                display(path),
+                expected(types),
+                value
+            ));
+            return;
+        }
+    }
+    if let Some(&Json::Array(ref values)) = schema.get("enum") {
+        if !values.contains(value) {
+            errors.push(format!(
+                "{}: {} is not one of {}",
+                display(path),
+                value,
+                Json::Array(values.clone())
+            ));
+        }
+    }
+    match *value {
+        Json::Number(ref n) => {
+            let n = n.as_f64().unwrap_or(0.0);
+            if let Some(min) = schema.get("minimum").and_then(Json::as_f64) {
+                if n < min {
+                    errors.push(format!("{}: {} is less than {}", display(path), value, min));
+                }
+            }
+            if let Some(max) = schema.get("maximum").and_then(Json::as_f64) {
+                if n > max {
+                    errors.push(format!("{}: {} is greater than {}", display(path), value, max));
+                }
+            }
+        }
+        Json::String(ref s) => {
+            let len = s.chars().count() as u64;
+            if let Some(min) = schema.get("minLength").and_then(Json::as_u64) {
+                if len < min {
+                    errors.push(format!(
+                        "{}: {} is shorter than {} characters",
+                        display(path),
+                        value,
+                        min
+                    ));
+                }
+            }
+            if let Some(max) = schema.get("maxLength").and_then(Json::as_u64) {
+                if len > max {
+                    errors.push(format!(
+                        "{}: {} is longer than {} characters",
+                        display(path),
+                        value,
+                        max
+                    ));
+                }
+            }
+            if let Some(pattern) = schema.get("pattern").and_then(Json::as_str) {
+                // Patterns were compiled once already when the schema was loaded.
+                if let Ok(re) = Regex::new(pattern) {
+                    if !re.is_match(s) {
+                        errors.push(format!(
+                            "{}: {} does not match the pattern {}",
+                            display(path),
+                            value,
+                            pattern
+                        ));
+                    }
+                }
+            }
+        }
+        Json::Array(ref items) => {
+            let len = items.len() as u64;
+            if let Some(min) = schema.get("minItems").and_then(Json::as_u64) {
+                if len < min {
+                    errors.push(format!("{}: expected at least {} items", display(path), min));
+                }
+            }
+            if let Some(max) = schema.get("maxItems").and_then(Json::as_u64) {
+                if len > max {
+                    errors.push(format!("{}: expected at most {} items", display(path), max));
+                }
+            }
+            if let Some(item_schema) = schema.get("items") {
+                for (i, item) in items.iter().enumerate() {
+                    validate(item_schema, item, &format!("{}[{}]", path, i), errors);
+                }
+            }
+        }
+        Json::Object(ref table) => validate_table(schema, table, path, errors),
+        _ => (),
+    }
+}
+
+fn validate_table(
+    schema: &Map<String, Json>,
+    table: &Map<String, Json>,
+    path: &str,
+    errors: &mut Vec<String>,
+) {
+    if let Some(&Json::Array(ref required)) = schema.get("required") {
+        for key in required.iter().filter_map(|k| k.as_str()) {
+            if !table.contains_key(key) {
+                errors.push(format!("{}: missing required value", join(path, key)));
+            }
+        }
+    }
+    let properties = schema.get("properties").and_then(Json::as_object);
+    for (key, value) in table.iter() {
+        match properties.and_then(|p| p.get(key)) {
+            Some(property) => validate(property, value, &join(path, key), errors),
+            None => {
+                match schema.get("additionalProperties") {
+                    Some(&Json::Bool(false)) => {
+                        errors.push(format!("{}: unknown setting", join(path, key)))
+                    }
+                    Some(additional) => validate(additional, value, &join(path, key), errors),
+                    None => (),
+                }
+            }
+        }
+    }
+}
+
+fn is_type(value: &Json, name: &str) -> bool {
+    match name {
+        "array" => value.is_array(),
+        "boolean" => value.is_boolean(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "null" => value.is_null(),
+        "number" => value.is_number(),
+        "object" => value.is_object(),
+        "string" => value.is_string(),
+        _ => false,
+    }
+}
+
+fn expected(types: &Json) -> String {
+    match *types {
+        Json::Array(ref names) => {
+            names
+                .iter()
+                .filter_map(|n| n.as_str())
+                .collect::<Vec<&str>>()
+                .join(" or ")
+        }
+        ref name => name.as_str().unwrap_or("").to_string(),
+    }
+}
+
+fn join(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", path, key)
+    }
+}
+
+fn display(path: &str) -> &str {
+    if path.is_empty() { "the configuration" } else { path }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs::File;
+    use std::io::Write;
+
+    use tempdir::TempDir;
+
+    use super::*;
+
+    fn schema(json: &str) -> ConfigSchema {
+        ConfigSchema::from_json(serde_json::from_str(json).unwrap()).unwrap()
+    }
+
+    fn config(toml: &str) -> toml::Value {
+        toml::Value::Table(toml::from_str(toml).unwrap())
+    }
+
+    const SCHEMA: &'static str = r#"{
+        "type": "object",
+        "required": ["port"],
+        "properties": {
+            "port": {"type": "integer", "minimum": 1, "maximum": 65535},
+            "log_level": {"enum": ["debug", "info", "warn"]},
+            "name": {"type": "string", "pattern": "^[a-z]+$", "maxLength": 8},
+            "peers": {"type": "array", "items": {"type": "string"}, "maxItems": 2},
+            "server": {
+                "type": "object",
+                "additionalProperties": false,
+                "properties": {"timeout": {"type": ["integer", "string"]}}
+            }
+        }
+    }"#;
+
+    #[test]
+    fn accepts_a_valid_config() {
+        let config = config(
+            r#"
+            port = 8080
+            log_level = "info"
+            name = "redis"
+            peers = ["a", "b"]
+            unknown = true
+            [server]
+            timeout = "30s"
+            "#,
+        );
+        assert!(schema(SCHEMA).validate(&config).is_empty());
+    }
+
+    #[test]
+    fn reports_every_violation_with_its_path() {
+        let config = config(
+            r#"
+            log_level = "trace"
+            name = "Redis"
+            peers = ["a", 2]
+            [server]
+            timeout = 1.5
+            retries = 3
+            "#,
+        );
+        assert_eq!(
+            schema(SCHEMA).validate(&config),
+            vec![
+                "port: missing required value".to_string(),
+                "log_level: \"trace\" is not one of [\"debug\",\"info\",\"warn\"]".to_string(),
+                "name: \"Redis\" does not match the pattern ^[a-z]+$".to_string(),
+                "peers[1]: expected string, found 2".to_string(),
+                "server.retries: unknown setting".to_string(),
+                "server.timeout: expected integer or string, found 1.5".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_a_port_given_as_a_string() {
+        assert_eq!(
+            schema(SCHEMA).validate(&config("port = \"abc\"")),
+            vec!["port: expected integer, found \"abc\"".to_string()]
+        );
+        assert_eq!(
+            schema(SCHEMA).validate(&config("port = 70000")),
+            vec!["port: 70000 is greater than 65535".to_string()]
+        );
+    }
+
+    #[test]
+    fn rejects_bad_schemas() {
+        let bad = vec![
+            r#"{"type": "int"}"#,
+            r#"{"properties": {"name": {"pattern": "("}}}"#,
+            r#"{"properties": []}"#,
+            r#"[]"#,
+        ];
+        for json in bad {
+            assert!(ConfigSchema::from_json(serde_json::from_str(json).unwrap()).is_err());
+        }
+    }
+
+    #[test]
+    fn load_prefers_json_to_toml() {
+        let tmp = TempDir::new("config_schema").unwrap();
+        assert_eq!(ConfigSchema::load(tmp.path()).unwrap(), None);
+
+        let mut file = File::create(tmp.path().join("config_schema.toml")).unwrap();
+        file.write_all(b"[properties.port]\ntype = \"integer\"\n").unwrap();
+        let loaded = ConfigSchema::load(tmp.path()).unwrap().unwrap();
+        assert_eq!(loaded, schema(r#"{"properties": {"port": {"type": "integer"}}}"#));
+
+        let mut file = File::create(tmp.path().join("config_schema.json")).unwrap();
+        file.write_all(br#"{"properties": {"port": {"type": "string"}}}"#).unwrap();
+        let loaded = ConfigSchema::load(tmp.path()).unwrap().unwrap();
+        assert_eq!(loaded, schema(r#"{"properties": {"port": {"type": "string"}}}"#));
+    }
+
+    #[test]
+    fn load_fails_on_a_malformed_schema() {
+        let tmp = TempDir::new("config_schema").unwrap();
+        let mut file = File::create(tmp.path().join("config_schema.json")).unwrap();
+        file.write_all(b"{\"type\": ").unwrap();
+        assert!(ConfigSchema::load(tmp.path()).is_err());
+    }
+}
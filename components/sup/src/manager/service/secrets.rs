@@ -0,0 +1,549 @@
+// Copyright (c) 2017 Chef Software Inc. and/or applicable contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Secrets read by a service's templates with the `secret` helper, e.g.
+//! `{{secret "db/password"}}`.
+//!
+//! A service's secrets come from the provider named by the `secrets` URL of its spec: either a
+//! directory holding one file per secret (`file:///hab/secrets/myapp`) or a Vault-compatible
+//! HTTP API (`https://vault.example.com:8200/v1/secret`). Values are only kept in memory, so they
+//! are never gossiped, written to the data file or served by the HTTP gateway. The secrets read so
+//! far are fetched again every `REFRESH_INTERVAL_SECS` on a thread of their own, so that a rotation
+//! upstream recompiles the service's templates without a slow provider holding up the
+//! Supervisor's main loop; templates always render from the cached values.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::Duration;
+
+use http_client::ApiClient;
+use hyper::status::StatusCode;
+use hcore::env as henv;
+use hcore::service::ServiceGroup;
+use serde_json::{self, Value as Json};
+use url::Url;
+
+use error::{Error, Result};
+use {PRODUCT, VERSION};
+
+static LOGKEY: &'static str = "SC";
+
+/// Environment variable holding the token sent to an HTTP secrets provider.
+pub const SECRETS_TOKEN_ENVVAR: &'static str = "HAB_SECRETS_TOKEN";
+/// How often the secrets read by a service are fetched again to pick up rotations.
+const REFRESH_INTERVAL_SECS: u64 = 60;
+/// How long an HTTP provider may take to answer a read or accept a write before it's given up.
+const HTTP_TIMEOUT_SECS: u64 = 5;
+
+header! { (XVaultToken, "X-Vault-Token") => [String] }
+
+lazy_static! {
+    static ref SECRETS: RwLock<HashMap<String, Arc<Secrets>>> = RwLock::new(HashMap::new());
+}
+
+/// Makes the given secrets available to the templates of a service group.
+pub fn register(service_group: &ServiceGroup, secrets: Arc<Secrets>) {
+    SECRETS
+        .write()
+        .expect("Secrets lock is poisoned")
+        .insert(service_group.to_string(), secrets);
+}
+
+/// Forget the secrets of a service group which is no longer supervised.
+pub fn unregister(service_group: &ServiceGroup) {
+    SECRETS.write().expect("Secrets lock is poisoned").remove(
+        &service_group.to_string(),
+    );
+}
+
+/// Keeps secrets registered for a service group until it's dropped, however its holder returns.
+#[derive(Debug)]
+pub struct Registration(ServiceGroup);
+
+impl Registration {
+    pub fn new(service_group: &ServiceGroup, secrets: Arc<Secrets>) -> Self {
+        register(service_group, secrets);
+        Registration(service_group.clone())
+    }
+}
+
+impl Drop for Registration {
+    fn drop(&mut self) {
+        unregister(&self.0);
+    }
+}
+
+/// Returns the secret at the given path for the templates of a service group.
+pub fn get(service_group: &str, path: &str) -> Result<String> {
+    let secrets = SECRETS
+        .read()
+        .expect("Secrets lock is poisoned")
+        .get(service_group)
+        .cloned();
+    match secrets {
+        Some(secrets) => secrets.get(path),
+        None => Err(sup_error!(Error::SecretsProvider(format!(
+            "no secrets provider is configured for {}",
+            service_group
+        )))),
+    }
+}
+
+/// A source of secret values.
+pub trait SecretProvider: fmt::Debug + Send + Sync {
+    /// Returns the current value of the secret at the given path.
+    fn fetch(&self, path: &str) -> Result<String>;
+}
+
+/// Reads each secret from a file of the same path under a root directory.
+#[derive(Debug)]
+pub struct FileProvider {
+    root: PathBuf,
+}
+
+impl FileProvider {
+    pub fn new<T: Into<PathBuf>>(root: T) -> Self {
+        FileProvider { root: root.into() }
+    }
+}
+
+impl SecretProvider for FileProvider {
+    fn fetch(&self, path: &str) -> Result<String> {
+        validate_path(path)?;
+        let file = self.root.join(path);
+        let mut content = String::new();
+        match File::open(&file) {
+            Ok(mut f) => {
+                f.read_to_string(&mut content).map_err(|e| {
+                    sup_error!(Error::SecretsProvider(format!("{}, {}", file.display(), e)))
+                })?;
+            }
+            Err(ref e) if e.kind() == io::ErrorKind::NotFound => {
+                return Err(sup_error!(Error::SecretNotFound(path.to_string())));
+            }
+            Err(e) => {
+                return Err(sup_error!(
+                    Error::SecretsProvider(format!("{}, {}", file.display(), e))
+                ))
+            }
+        }
+        Ok(content.trim_right_matches(|c| c == '\n' || c == '\r').to_string())
+    }
+}
+
+/// Reads secrets from a Vault-compatible HTTP API. The value of a secret is the `value` key of
+/// its data, in the response format of either version of Vault's key/value backend.
+pub struct HttpProvider {
+    client: ApiClient,
+    token: Option<String>,
+}
+
+impl HttpProvider {
+    pub fn new(url: Url, token: Option<String>) -> Result<Self> {
+        let mut client = ApiClient::new(url, PRODUCT, VERSION, None).map_err(|e| {
+            sup_error!(Error::SecretsProvider(e.to_string()))
+        })?;
+        client.set_timeout(Duration::from_secs(HTTP_TIMEOUT_SECS));
+        Ok(HttpProvider {
+            client: client,
+            token: token,
+        })
+    }
+}
+
+impl fmt::Debug for HttpProvider {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // The token must not end up in logs.
+        write!(f, "HttpProvider {{ client: {:?} }}", self.client)
+    }
+}
+
+impl SecretProvider for HttpProvider {
+    fn fetch(&self, path: &str) -> Result<String> {
+        validate_path(path)?;
+        let mut request = self.client.get(path);
+        if let Some(ref token) = self.token {
+            request = request.header(XVaultToken(token.clone()));
+        }
+        let mut response = request.send().map_err(|e| {
+            sup_error!(Error::SecretsProvider(format!("{}, {}", path, e)))
+        })?;
+        match response.status {
+            StatusCode::Ok => (),
+            StatusCode::NotFound => {
+                return Err(sup_error!(Error::SecretNotFound(path.to_string())));
+            }
+            ref status => {
+                return Err(sup_error!(Error::SecretsProvider(
+                    format!("{}, unexpected response {}", path, status),
+                )))
+            }
+        }
+        let mut body = String::new();
+        response.read_to_string(&mut body).map_err(|e| {
+            sup_error!(Error::SecretsProvider(format!("{}, {}", path, e)))
+        })?;
+        let json: Json = serde_json::from_str(&body).map_err(|e| {
+            sup_error!(Error::SecretsProvider(format!("{}, {}", path, e)))
+        })?;
+        let data = &json["data"];
+        let value = if data["data"].is_object() {
+            &data["data"]["value"]
+        } else {
+            &data["value"]
+        };
+        match *value {
+            Json::String(ref s) => Ok(s.clone()),
+            Json::Null => Err(sup_error!(Error::SecretNotFound(path.to_string()))),
+            ref other => Ok(other.to_string()),
+        }
+    }
+}
+
+/// The secrets of a service, cached in memory as they are read by its templates.
+pub struct Secrets {
+    provider: Box<SecretProvider>,
+    values: RwLock<HashMap<String, String>>,
+    rotated: AtomicBool,
+}
+
+impl Secrets {
+    pub fn new(provider: Box<SecretProvider>) -> Self {
+        Secrets {
+            provider: provider,
+            values: RwLock::new(HashMap::new()),
+            rotated: AtomicBool::new(false),
+        }
+    }
+
+    /// Creates the secrets of a service from the URL of their provider. The token of an HTTP
+    /// provider is read from `HAB_SECRETS_TOKEN`.
+    pub fn from_url(url: &str) -> Result<Self> {
+        Ok(Self::new(provider_from_url(url)?))
+    }
+
+    /// Returns the secret at the given path from the cache. Only the first read of a path waits
+    /// on the provider.
+    pub fn get(&self, path: &str) -> Result<String> {
+        if let Some(value) = self.values.read().expect("Secrets lock is poisoned").get(path) {
+            return Ok(value.clone());
+        }
+        let value = self.provider.fetch(path)?;
+        self.values
+            .write()
+            .expect("Secrets lock is poisoned")
+            .insert(path.to_string(), value.clone());
+        Ok(value)
+    }
+
+    /// Fetches every secret read so far again every `REFRESH_INTERVAL_SECS`, on a thread which
+    /// stops once the secrets are dropped.
+    pub fn start_refreshing(secrets: &Arc<Secrets>) -> Result<()> {
+        let secrets = Arc::downgrade(secrets);
+        thread::Builder::new()
+            .name(String::from("secrets-refresh"))
+            .spawn(move || loop {
+                thread::sleep(Duration::from_secs(REFRESH_INTERVAL_SECS));
+                match secrets.upgrade() {
+                    Some(secrets) => {
+                        secrets.refresh();
+                    }
+                    None => break,
+                }
+            })?;
+        Ok(())
+    }
+
+    /// Returns `true` if any secret changed upstream since the last call. Never waits on the
+    /// provider.
+    pub fn rotated(&self) -> bool {
+        self.rotated.swap(false, Ordering::SeqCst)
+    }
+
+    /// Fetches every secret read so far again. Returns `true` if any of them changed. A secret
+    /// which can't be fetched keeps its last value.
+    pub fn refresh(&self) -> bool {
+        let paths: Vec<String> = self.values
+            .read()
+            .expect("Secrets lock is poisoned")
+            .keys()
+            .cloned()
+            .collect();
+        let mut changed = false;
+        for path in paths {
+            match self.provider.fetch(&path) {
+                Ok(value) => {
+                    let mut values = self.values.write().expect("Secrets lock is poisoned");
+                    if values.get(&path) != Some(&value) {
+                        values.insert(path, value);
+                        changed = true;
+                    }
+                }
+                Err(err) => warn!("Unable to refresh secret '{}', {}", path, err),
+            }
+        }
+        if changed {
+            self.rotated.store(true, Ordering::SeqCst);
+        }
+        changed
+    }
+}
+
+impl fmt::Debug for Secrets {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Only the provider is shown; the values must not end up in logs.
+        write!(f, "Secrets {{ provider: {:?} }}", self.provider)
+    }
+}
+
+/// Returns the provider for a `file://`, `http://` or `https://` URL.
+pub fn provider_from_url(url: &str) -> Result<Box<SecretProvider>> {
+    let parsed = Url::parse(url).map_err(|_| sup_error!(Error::BadSecretsUrl(url.to_string())))?;
+    match parsed.scheme() {
+        "file" => {
+            let root = parsed.to_file_path().map_err(|_| {
+                sup_error!(Error::BadSecretsUrl(url.to_string()))
+            })?;
+            Ok(Box::new(FileProvider::new(root)))
+        }
+        "http" | "https" => {
+            let token = henv::var(SECRETS_TOKEN_ENVVAR).ok();
+            Ok(Box::new(HttpProvider::new(parsed, token)?))
+        }
+        _ => Err(sup_error!(Error::BadSecretsUrl(url.to_string()))),
+    }
+}
+
+/// Only relative paths below the provider's root are allowed.
+fn validate_path(path: &str) -> Result<()> {
+    let valid = !path.is_empty() &&
+        Path::new(path).components().all(|c| match c {
+            Component::Normal(_) => true,
+            _ => false,
+        });
+    if valid {
+        Ok(())
+    } else {
+        Err(sup_error!(Error::SecretsProvider(
+            format!("invalid secret path '{}'", path),
+        )))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs::{self, File};
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::thread;
+    use std::time::Instant;
+
+    use tempdir::TempDir;
+
+    use super::*;
+
+    fn write_secret(root: &Path, path: &str, value: &str) {
+        let file = root.join(path);
+        fs::create_dir_all(file.parent().unwrap()).unwrap();
+        let mut f = File::create(file).unwrap();
+        f.write_all(value.as_bytes()).unwrap();
+    }
+
+    /// Serves a single HTTP request with the given status and body, and returns the request's
+    /// head.
+    fn stub_server(status: &'static str, body: &'static str) -> (Url, thread::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = Url::parse(&format!("http://{}/v1/secret", listener.local_addr().unwrap()))
+            .unwrap();
+        let handle = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut head = String::new();
+            {
+                let mut reader = BufReader::new(&stream);
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line == "\r\n" || line.is_empty() {
+                        break;
+                    }
+                    head.push_str(&line);
+                }
+            }
+            write!(
+                stream,
+                "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            ).unwrap();
+            head
+        });
+        (url, handle)
+    }
+
+    #[test]
+    fn file_provider_reads_secret() {
+        let tmp = TempDir::new("secrets").unwrap();
+        write_secret(tmp.path(), "db/password", "hunter2\n");
+        let provider = FileProvider::new(tmp.path());
+        assert_eq!(provider.fetch("db/password").unwrap(), "hunter2");
+    }
+
+    #[test]
+    fn file_provider_rejects_paths_outside_root() {
+        let tmp = TempDir::new("secrets").unwrap();
+        let provider = FileProvider::new(tmp.path().join("vault"));
+        write_secret(tmp.path(), "outside", "nope");
+        assert!(provider.fetch("../outside").is_err());
+        assert!(provider.fetch("/etc/passwd").is_err());
+    }
+
+    #[test]
+    fn file_provider_missing_secret() {
+        let tmp = TempDir::new("secrets").unwrap();
+        let provider = FileProvider::new(tmp.path());
+        match provider.fetch("db/password") {
+            Err(e) => {
+                match e.err {
+                    Error::SecretNotFound(ref path) => assert_eq!(path, "db/password"),
+                    ref e => panic!("Unexpected error {:?}", e),
+                }
+            }
+            Ok(_) => panic!("Missing secret was read"),
+        }
+    }
+
+    #[test]
+    fn refresh_detects_rotation() {
+        let tmp = TempDir::new("secrets").unwrap();
+        write_secret(tmp.path(), "db/password", "hunter2");
+        let secrets = Secrets::new(Box::new(FileProvider::new(tmp.path())));
+        assert_eq!(secrets.get("db/password").unwrap(), "hunter2");
+        assert!(!secrets.refresh());
+
+        write_secret(tmp.path(), "db/password", "correct horse");
+        // Cached until refreshed
+        assert_eq!(secrets.get("db/password").unwrap(), "hunter2");
+        assert!(secrets.refresh());
+        assert_eq!(secrets.get("db/password").unwrap(), "correct horse");
+        // The rotation is reported once
+        assert!(secrets.rotated());
+        assert!(!secrets.rotated());
+    }
+
+    #[test]
+    fn refreshing_stops_once_the_secrets_are_dropped() {
+        let tmp = TempDir::new("secrets").unwrap();
+        let secrets = Arc::new(Secrets::new(Box::new(FileProvider::new(tmp.path()))));
+        Secrets::start_refreshing(&secrets).unwrap();
+        let weak = Arc::downgrade(&secrets);
+        drop(secrets);
+        // The refresh thread only holds on to the secrets while it refreshes them
+        assert!(weak.upgrade().is_none());
+    }
+
+    #[test]
+    fn registration_unregisters_when_dropped() {
+        let tmp = TempDir::new("secrets").unwrap();
+        write_secret(tmp.path(), "db/password", "hunter2");
+        let sg = ServiceGroup::new(None, "registered", "default", None).unwrap();
+        {
+            let _registration = Registration::new(
+                &sg,
+                Arc::new(Secrets::new(Box::new(FileProvider::new(tmp.path())))),
+            );
+            assert_eq!(get("registered.default", "db/password").unwrap(), "hunter2");
+        }
+        assert!(get("registered.default", "db/password").is_err());
+    }
+
+    #[test]
+    fn refresh_keeps_value_of_unavailable_secret() {
+        let tmp = TempDir::new("secrets").unwrap();
+        write_secret(tmp.path(), "db/password", "hunter2");
+        let secrets = Secrets::new(Box::new(FileProvider::new(tmp.path())));
+        secrets.get("db/password").unwrap();
+        fs::remove_file(tmp.path().join("db/password")).unwrap();
+        assert!(!secrets.refresh());
+        assert_eq!(secrets.get("db/password").unwrap(), "hunter2");
+    }
+
+    #[test]
+    fn http_provider_reads_secret() {
+        let (url, server) = stub_server(
+            "200 OK",
+            r#"{"lease_duration": 3600, "data": {"value": "hunter2"}}"#,
+        );
+        let provider = HttpProvider::new(url, Some("s3cr3t".to_string())).unwrap();
+        assert_eq!(provider.fetch("db/password").unwrap(), "hunter2");
+        let head = server.join().unwrap().to_lowercase();
+        assert!(head.starts_with("get /v1/secret/db/password "));
+        assert!(head.contains("x-vault-token: s3cr3t"));
+    }
+
+    #[test]
+    fn http_provider_reads_versioned_secret() {
+        let (url, server) = stub_server(
+            "200 OK",
+            r#"{"data": {"data": {"value": "hunter2"}, "metadata": {"version": 2}}}"#,
+        );
+        let provider = HttpProvider::new(url, None).unwrap();
+        assert_eq!(provider.fetch("db/password").unwrap(), "hunter2");
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn http_provider_gives_up_on_a_silent_server() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = Url::parse(&format!("http://{}/v1/secret", listener.local_addr().unwrap()))
+            .unwrap();
+        // Accept the connection, but never answer
+        let server = thread::spawn(move || listener.accept().unwrap());
+        let provider = HttpProvider::new(url, None).unwrap();
+        let started = Instant::now();
+        assert!(provider.fetch("db/password").is_err());
+        assert!(started.elapsed() < Duration::from_secs(HTTP_TIMEOUT_SECS * 2));
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn http_provider_missing_secret() {
+        let (url, server) = stub_server("404 Not Found", r#"{"errors": []}"#);
+        let provider = HttpProvider::new(url, None).unwrap();
+        match provider.fetch("db/password") {
+            Err(e) => {
+                match e.err {
+                    Error::SecretNotFound(_) => (),
+                    ref e => panic!("Unexpected error {:?}", e),
+                }
+            }
+            Ok(_) => panic!("Missing secret was read"),
+        }
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn provider_from_url_schemes() {
+        assert!(provider_from_url("file:///hab/secrets").is_ok());
+        assert!(provider_from_url("https://vault.example.com:8200/v1/secret").is_ok());
+        assert!(provider_from_url("ftp://vault.example.com/secret").is_err());
+        assert!(provider_from_url("not a url").is_err());
+    }
+}
@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 use std::fmt;
 use std::fs::{self, File};
 use std::io::{BufReader, Read, Write};
@@ -21,20 +21,28 @@ use std::path::{Path, PathBuf};
 use std::result;
 use std::str::FromStr;
 
+use butterfly::rumor::election::QuorumBasis;
 use hcore::channel::STABLE_CHANNEL;
 use hcore::package::{PackageIdent, PackageInstall};
 use hcore::service::{ApplicationEnvironment, ServiceGroup};
 use hcore::url::DEFAULT_BLDR_URL;
 use hcore::util::{deserialize_using_from_str, serialize_using_to_string};
+use launcher_client;
 use rand::{Rng, thread_rng};
 use serde::{self, Deserialize};
 use toml;
 
 use super::{Topology, UpdateStrategy};
+use manager::parse_tag;
 use error::{Error, Result, SupError};
 
 static LOGKEY: &'static str = "SS";
 static DEFAULT_GROUP: &'static str = "default";
+const DEFAULT_RESTART_BACKOFF_CAP: u64 = 60;
+const DEFAULT_RESTART_WINDOW: u64 = 300;
+const DEFAULT_CANARY_PERCENT: u32 = 10;
+const DEFAULT_CANARY_SOAK: u64 = 300;
+const DEFAULT_ROLLBACK_WINDOW: u64 = 300;
 const SPEC_FILE_EXT: &'static str = "spec";
 
 #[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
@@ -101,17 +109,52 @@ pub struct ServiceSpec {
     pub channel: String,
     pub topology: Topology,
     pub update_strategy: UpdateStrategy,
+    // The percentage of the service group's members which test a new package first when using
+    // the canary update strategy
+    pub canary_percent: u32,
+    // How long, in seconds, the canaries must stay healthy before the rest of the group updates
+    pub canary_soak: u64,
+    // How long, in seconds, after a package update the previous release is restored if the new
+    // one fails its smoke test or health check. A value of 0 disables automatic rollbacks.
+    pub rollback_window: u64,
+    // Whether a leader election counts its quorum against all known members of the service group
+    // or only against the ones currently alive
+    #[serde(deserialize_with = "deserialize_using_from_str",
+            serialize_with = "serialize_using_to_string")]
+    pub quorum_basis: QuorumBasis,
+    // How long, in seconds, an elected leader holds its lease without renewing it. A leader which
+    // can't renew its lease steps down half way through it, and no new leader is elected before
+    // it has expired. A value of 0 disables leases.
+    pub leader_lease: u64,
     pub binds: Vec<ServiceBind>,
     pub config_from: Option<PathBuf>,
+    // Whether a variable missing from the render context fails the compilation of the service's
+    // templates, which leaves its current configuration and hooks in place. The package's plan
+    // may also turn this on.
+    pub strict_templates: bool,
+    // URL of the provider of the secrets read by the service's templates, either `file://` or a
+    // Vault-compatible `http(s)://` API.
+    pub secrets: Option<String>,
     #[serde(deserialize_with = "deserialize_using_from_str",
             serialize_with = "serialize_using_to_string")]
     pub desired_state: DesiredState,
     #[serde(deserialize_with = "deserialize_using_from_str",
             serialize_with = "serialize_using_to_string")]
     pub start_style: StartStyle,
+    #[serde(deserialize_with = "deserialize_using_from_str",
+            serialize_with = "serialize_using_to_string")]
+    pub restart_policy: RestartPolicy,
+    // The longest delay, in seconds, between two restarts of the service's process
+    pub restart_backoff_cap: u64,
+    // The number of restarts within `restart_window` seconds after which the service is marked as
+    // failed and no longer restarted. A value of 0 allows an unlimited number of restarts.
+    pub restart_limit: u32,
+    pub restart_window: u64,
     pub svc_encrypted_password: Option<String>,
     // The name of the composite this service is a part of
     pub composite: Option<String>,
+    // Resource limits which take precedence over the ones declared by the package
+    pub limits: ResourceLimits,
 }
 
 impl ServiceSpec {
@@ -181,6 +224,20 @@ impl ServiceSpec {
 
     pub fn validate(&self, package: &PackageInstall) -> Result<()> {
         self.validate_binds(package)?;
+        self.validate_limits()?;
+        Ok(())
+    }
+
+    /// Validates the resource limits, which may not come from a package's `SVC_LIMITS` file and
+    /// thus haven't been checked when they were parsed.
+    ///
+    /// # Errors
+    ///
+    /// * If the CPU limit is 0, which would keep the service from ever running
+    fn validate_limits(&self) -> Result<()> {
+        if self.limits.cpu == Some(0) {
+            return Err(sup_error!(Error::BadResourceLimit("cpu=0".to_string())));
+        }
         Ok(())
     }
 
@@ -238,12 +295,24 @@ impl Default for ServiceSpec {
             channel: STABLE_CHANNEL.to_string(),
             topology: Topology::default(),
             update_strategy: UpdateStrategy::default(),
+            canary_percent: DEFAULT_CANARY_PERCENT,
+            canary_soak: DEFAULT_CANARY_SOAK,
+            rollback_window: DEFAULT_ROLLBACK_WINDOW,
+            quorum_basis: QuorumBasis::default(),
+            leader_lease: 0,
             binds: Vec::default(),
             config_from: None,
+            strict_templates: false,
+            secrets: None,
             desired_state: DesiredState::default(),
             start_style: StartStyle::default(),
+            restart_policy: RestartPolicy::default(),
+            restart_backoff_cap: DEFAULT_RESTART_BACKOFF_CAP,
+            restart_limit: 0,
+            restart_window: DEFAULT_RESTART_WINDOW,
             svc_encrypted_password: None,
             composite: None,
+            limits: ResourceLimits::default(),
         }
     }
 }
@@ -266,27 +335,48 @@ impl FromStr for ServiceSpec {
 pub struct ServiceBind {
     pub name: String,
     pub service_group: ServiceGroup,
+    /// Only the members of the service group carrying all of these tags provide the bind.
+    pub tags: BTreeMap<String, String>,
 }
 
 impl FromStr for ServiceBind {
     type Err = SupError;
 
     fn from_str(bind_str: &str) -> result::Result<Self, Self::Err> {
-        let values: Vec<&str> = bind_str.split(':').collect();
+        let mut parts = bind_str.splitn(2, '?');
+        let values: Vec<&str> = parts.next().unwrap_or("").split(':').collect();
         if values.len() != 2 {
             return Err(sup_error!(Error::InvalidBinding(bind_str.to_string())));
         }
+        let mut tags = BTreeMap::new();
+        if let Some(filter) = parts.next() {
+            for tag in filter.split('&') {
+                let (key, value) = parse_tag(tag).map_err(|_| {
+                    sup_error!(Error::InvalidBinding(bind_str.to_string()))
+                })?;
+                tags.insert(key, value);
+            }
+        }
 
         Ok(ServiceBind {
             name: values[0].to_string(),
             service_group: ServiceGroup::from_str(values[1])?,
+            tags: tags,
         })
     }
 }
 
 impl fmt::Display for ServiceBind {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}:{}", self.name, self.service_group)
+        write!(f, "{}:{}", self.name, self.service_group)?;
+        let tags: Vec<String> = self.tags
+            .iter()
+            .map(|(key, value)| format!("{}={}", key, value))
+            .collect();
+        if !tags.is_empty() {
+            write!(f, "?{}", tags.join("&"))?;
+        }
+        Ok(())
     }
 }
 
@@ -342,6 +432,106 @@ impl FromStr for StartStyle {
     }
 }
 
+/// Determines whether the Supervisor restarts a service's process after it exits.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub enum RestartPolicy {
+    Always,
+    OnFailure,
+    Never,
+}
+
+impl Default for RestartPolicy {
+    fn default() -> RestartPolicy {
+        RestartPolicy::Always
+    }
+}
+
+impl fmt::Display for RestartPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let value = match *self {
+            RestartPolicy::Always => "always",
+            RestartPolicy::OnFailure => "on-failure",
+            RestartPolicy::Never => "never",
+        };
+        write!(f, "{}", value)
+    }
+}
+
+impl FromStr for RestartPolicy {
+    type Err = SupError;
+
+    fn from_str(value: &str) -> result::Result<Self, Self::Err> {
+        match value.to_lowercase().as_ref() {
+            "always" => Ok(RestartPolicy::Always),
+            "on-failure" => Ok(RestartPolicy::OnFailure),
+            "never" => Ok(RestartPolicy::Never),
+            _ => Err(sup_error!(Error::BadRestartPolicy(value.to_string()))),
+        }
+    }
+}
+
+/// Resource limits applied to a service's process, on hosts which support them.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[serde(default)]
+pub struct ResourceLimits {
+    // CPU time in thousandths of a CPU
+    pub cpu: Option<u32>,
+    // Memory in bytes
+    pub memory: Option<u64>,
+    // Maximum number of processes and threads
+    pub pids: Option<u64>,
+}
+
+impl ResourceLimits {
+    /// Returns these limits, taking any limit which isn't set from `other`.
+    pub fn or(self, other: ResourceLimits) -> ResourceLimits {
+        ResourceLimits {
+            cpu: self.cpu.or(other.cpu),
+            memory: self.memory.or(other.memory),
+            pids: self.pids.or(other.pids),
+        }
+    }
+}
+
+impl From<ResourceLimits> for launcher_client::ResourceLimits {
+    fn from(limits: ResourceLimits) -> launcher_client::ResourceLimits {
+        launcher_client::ResourceLimits {
+            cpu: limits.cpu,
+            memory: limits.memory,
+            pids: limits.pids,
+        }
+    }
+}
+
+/// Parses the `key=value` lines of a package's `SVC_LIMITS` metadata file.
+impl FromStr for ResourceLimits {
+    type Err = SupError;
+
+    fn from_str(value: &str) -> result::Result<Self, Self::Err> {
+        let mut limits = ResourceLimits::default();
+        for line in value.lines().map(|l| l.trim()).filter(|l| !l.is_empty()) {
+            let bad = || sup_error!(Error::BadResourceLimit(line.to_string()));
+            let mut parts = line.splitn(2, '=');
+            let (key, val) = match (parts.next(), parts.next()) {
+                (Some(key), Some(val)) => (key.trim(), val.trim()),
+                _ => return Err(bad()),
+            };
+            match key {
+                "cpu" => {
+                    match val.parse() {
+                        Ok(0) | Err(_) => return Err(bad()),
+                        Ok(cpu) => limits.cpu = Some(cpu),
+                    }
+                }
+                "memory" => limits.memory = Some(val.parse().map_err(|_| bad())?),
+                "pids" => limits.pids = Some(val.parse().map_err(|_| bad())?),
+                _ => return Err(bad()),
+            }
+        }
+        Ok(limits)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use std::fs::{self, File};
@@ -387,9 +577,18 @@ mod test {
             bldr_url = "http://example.com/depot"
             topology = "leader"
             update_strategy = "rolling"
+            canary_percent = 25
+            rollback_window = 0
+            quorum_basis = "alive"
+            leader_lease = 15
             binds = ["cache:redis.cache@acmecorp", "db:postgres.app@acmecorp"]
             start_style = "persistent"
+            restart_policy = "on-failure"
+            restart_backoff_cap = 30
+            restart_limit = 5
             config_from = "/only/for/development"
+            strict_templates = true
+            secrets = "file:///hab/secrets/name"
 
             extra_stuff = "should be ignored"
             "#;
@@ -409,6 +608,11 @@ mod test {
         assert_eq!(spec.bldr_url, String::from("http://example.com/depot"));
         assert_eq!(spec.topology, Topology::Leader);
         assert_eq!(spec.update_strategy, UpdateStrategy::Rolling);
+        assert_eq!(spec.canary_percent, 25);
+        assert_eq!(spec.canary_soak, DEFAULT_CANARY_SOAK);
+        assert_eq!(spec.rollback_window, 0);
+        assert_eq!(spec.quorum_basis, QuorumBasis::Alive);
+        assert_eq!(spec.leader_lease, 15);
         assert_eq!(
             spec.binds,
             vec![
@@ -420,7 +624,56 @@ mod test {
             spec.config_from,
             Some(PathBuf::from("/only/for/development"))
         );
+        assert!(spec.strict_templates);
+        assert_eq!(spec.secrets, Some(String::from("file:///hab/secrets/name")));
         assert_eq!(spec.start_style, StartStyle::Persistent);
+        assert_eq!(spec.restart_policy, RestartPolicy::OnFailure);
+        assert_eq!(spec.restart_backoff_cap, 30);
+        assert_eq!(spec.restart_limit, 5);
+        assert_eq!(spec.restart_window, DEFAULT_RESTART_WINDOW);
+    }
+
+    #[test]
+    fn service_spec_from_str_with_limits() {
+        let toml = r#"
+            ident = "origin/name/1.2.3/20170223130020"
+
+            [limits]
+            cpu = 500
+            memory = 268435456
+            "#;
+        let spec = ServiceSpec::from_str(toml).unwrap();
+
+        assert_eq!(
+            spec.limits,
+            ResourceLimits {
+                cpu: Some(500),
+                memory: Some(268435456),
+                pids: None,
+            }
+        );
+        assert!(spec.validate_limits().is_ok());
+    }
+
+    #[test]
+    fn service_spec_validate_limits_rejects_a_zero_cpu_limit() {
+        let toml = r#"
+            ident = "origin/name/1.2.3/20170223130020"
+
+            [limits]
+            cpu = 0
+            "#;
+        let spec = ServiceSpec::from_str(toml).unwrap();
+
+        match spec.validate_limits() {
+            Err(e) => {
+                match e.err {
+                    BadResourceLimit(limit) => assert_eq!(limit, "cpu=0"),
+                    wrong => panic!("Unexpected error returned: {:?}", wrong),
+                }
+            }
+            Ok(_) => panic!("A zero CPU limit should be rejected"),
+        }
     }
 
     #[test]
@@ -456,6 +709,24 @@ mod test {
         }
     }
 
+    #[test]
+    fn service_spec_from_str_invalid_restart_policy() {
+        let toml = r#"
+            ident = "origin/name/1.2.3/20170223130020"
+            restart_policy = "sometimes"
+            "#;
+
+        match ServiceSpec::from_str(toml) {
+            Err(e) => {
+                match e.err {
+                    ServiceSpecParse(_) => assert!(true),
+                    e => panic!("Unexpected error returned: {:?}", e),
+                }
+            }
+            Ok(_) => panic!("Spec TOML should fail to parse"),
+        }
+    }
+
     #[test]
     fn service_spec_from_str_invalid_binds() {
         let toml = r#"
@@ -488,15 +759,31 @@ mod test {
             channel: String::from("unstable"),
             topology: Topology::Leader,
             update_strategy: UpdateStrategy::AtOnce,
+            canary_percent: 20,
+            canary_soak: 600,
+            rollback_window: 120,
+            quorum_basis: QuorumBasis::Alive,
+            leader_lease: 30,
             binds: vec![
                 ServiceBind::from_str("cache:redis.cache@acmecorp").unwrap(),
                 ServiceBind::from_str("db:postgres.app@acmecorp").unwrap(),
             ],
             config_from: Some(PathBuf::from("/only/for/development")),
+            strict_templates: true,
+            secrets: Some(String::from("https://vault.example.com:8200/v1/secret")),
             desired_state: DesiredState::Down,
             start_style: StartStyle::Persistent,
+            restart_policy: RestartPolicy::Never,
+            restart_backoff_cap: 30,
+            restart_limit: 5,
+            restart_window: 120,
             svc_encrypted_password: None,
             composite: None,
+            limits: ResourceLimits {
+                cpu: None,
+                memory: Some(1048576),
+                pids: Some(32),
+            },
         };
         let toml = spec.to_toml_string().unwrap();
 
@@ -511,11 +798,27 @@ mod test {
         assert!(toml.contains(r#"channel = "unstable""#));
         assert!(toml.contains(r#"topology = "leader""#));
         assert!(toml.contains(r#"update_strategy = "at-once""#));
+        assert!(toml.contains(r#"canary_percent = 20"#));
+        assert!(toml.contains(r#"canary_soak = 600"#));
+        assert!(toml.contains(r#"rollback_window = 120"#));
+        assert!(toml.contains(r#"quorum_basis = "alive""#));
+        assert!(toml.contains(r#"leader_lease = 30"#));
+        assert!(toml.contains(r#"strict_templates = true"#));
+        assert!(toml.contains(
+            r#"secrets = "https://vault.example.com:8200/v1/secret""#,
+        ));
         assert!(toml.contains(r#""cache:redis.cache@acmecorp""#));
         assert!(toml.contains(r#""db:postgres.app@acmecorp""#));
         assert!(toml.contains(r#"desired_state = "down""#));
         assert!(toml.contains(r#"start_style = "persistent""#));
+        assert!(toml.contains(r#"restart_policy = "never""#));
+        assert!(toml.contains(r#"restart_backoff_cap = 30"#));
+        assert!(toml.contains(r#"restart_limit = 5"#));
+        assert!(toml.contains(r#"restart_window = 120"#));
         assert!(toml.contains(r#"config_from = "/only/for/development""#));
+        assert!(toml.contains(r#"memory = 1048576"#));
+        assert!(toml.contains(r#"pids = 32"#));
+        assert!(!toml.contains(r#"cpu ="#));
     }
 
     #[test]
@@ -647,15 +950,31 @@ mod test {
             channel: String::from("unstable"),
             topology: Topology::Leader,
             update_strategy: UpdateStrategy::AtOnce,
+            canary_percent: 20,
+            canary_soak: 600,
+            rollback_window: 120,
+            quorum_basis: QuorumBasis::Alive,
+            leader_lease: 30,
             binds: vec![
                 ServiceBind::from_str("cache:redis.cache@acmecorp").unwrap(),
                 ServiceBind::from_str("db:postgres.app@acmecorp").unwrap(),
             ],
             config_from: Some(PathBuf::from("/only/for/development")),
+            strict_templates: true,
+            secrets: Some(String::from("https://vault.example.com:8200/v1/secret")),
             desired_state: DesiredState::Down,
             start_style: StartStyle::Persistent,
+            restart_policy: RestartPolicy::Never,
+            restart_backoff_cap: 30,
+            restart_limit: 5,
+            restart_window: 120,
             svc_encrypted_password: None,
             composite: None,
+            limits: ResourceLimits {
+                cpu: None,
+                memory: Some(1048576),
+                pids: Some(32),
+            },
         };
         spec.to_file(&path).unwrap();
         let toml = string_from_file(path);
@@ -728,6 +1047,36 @@ mod test {
         );
     }
 
+    #[test]
+    fn service_bind_from_str_with_tags() {
+        let bind_str = "name:service.group?zone=us-east-1a&rack=4";
+        let bind = ServiceBind::from_str(bind_str).unwrap();
+
+        assert_eq!(bind.name, String::from("name"));
+        assert_eq!(
+            bind.service_group,
+            ServiceGroup::from_str("service.group").unwrap()
+        );
+        assert_eq!(bind.tags.len(), 2);
+        assert_eq!(bind.tags.get("zone"), Some(&String::from("us-east-1a")));
+        assert_eq!(bind.tags.get("rack"), Some(&String::from("4")));
+    }
+
+    #[test]
+    fn service_bind_from_str_invalid_tags() {
+        let bind_str = "name:service.group?zone";
+
+        match ServiceBind::from_str(bind_str) {
+            Err(e) => {
+                match e.err {
+                    InvalidBinding(val) => assert_eq!("name:service.group?zone", val),
+                    wrong => panic!("Unexpected error returned: {:?}", wrong),
+                }
+            }
+            Ok(_) => panic!("String should fail to parse"),
+        }
+    }
+
     #[test]
     fn service_bind_from_str_missing_colon() {
         let bind_str = "uhoh";
@@ -780,11 +1129,19 @@ mod test {
         let bind = ServiceBind {
             name: String::from("name"),
             service_group: ServiceGroup::from_str("service.group").unwrap(),
+            tags: BTreeMap::new(),
         };
 
         assert_eq!("name:service.group", bind.to_string());
     }
 
+    #[test]
+    fn service_bind_with_tags_to_string() {
+        let bind = ServiceBind::from_str("name:service.group?zone=us-east-1a&rack=4").unwrap();
+
+        assert_eq!("name:service.group?rack=4&zone=us-east-1a", bind.to_string());
+    }
+
     #[test]
     fn service_bind_toml_deserialize() {
         #[derive(Deserialize)]
@@ -812,10 +1169,58 @@ mod test {
             key: ServiceBind {
                 name: String::from("name"),
                 service_group: ServiceGroup::from_str("service.group").unwrap(),
+                tags: BTreeMap::new(),
             },
         };
         let toml = toml::to_string(&data).unwrap();
 
         assert!(toml.starts_with(r#"key = "name:service.group""#));
     }
+
+    #[test]
+    fn resource_limits_from_str() {
+        let limits = ResourceLimits::from_str("cpu=1500\nmemory=536870912\n").unwrap();
+
+        assert_eq!(limits.cpu, Some(1500));
+        assert_eq!(limits.memory, Some(536870912));
+        assert_eq!(limits.pids, None);
+    }
+
+    #[test]
+    fn resource_limits_from_str_invalid() {
+        for input in &["cpu=half", "cpu=0", "disk=10", "pids"] {
+            match ResourceLimits::from_str(input) {
+                Err(e) => {
+                    match e.err {
+                        BadResourceLimit(limit) => assert_eq!(limit, *input),
+                        wrong => panic!("Unexpected error returned: {:?}", wrong),
+                    }
+                }
+                Ok(_) => panic!("Resource limits should fail to parse"),
+            }
+        }
+    }
+
+    #[test]
+    fn resource_limits_or() {
+        let spec = ResourceLimits {
+            cpu: Some(250),
+            memory: None,
+            pids: None,
+        };
+        let pkg = ResourceLimits {
+            cpu: Some(1000),
+            memory: Some(1024),
+            pids: None,
+        };
+
+        assert_eq!(
+            spec.or(pkg),
+            ResourceLimits {
+                cpu: Some(250),
+                memory: Some(1024),
+                pids: None,
+            }
+        );
+    }
 }
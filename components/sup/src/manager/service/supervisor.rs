@@ -16,9 +16,11 @@
 ///
 /// The Supervisor is responsible for running any services we are asked to start. It handles
 /// spawning the new process, watching for failure, and ensuring the service is either up or down.
-/// If the process dies, the Supervisor will restart it.
+/// If the process dies, the Supervisor will restart it according to the service's restart policy.
 
 use std;
+use std::cmp;
+use std::collections::VecDeque;
 use std::fmt;
 use std::fs::File;
 use std::io::BufReader;
@@ -26,16 +28,19 @@ use std::io::prelude::*;
 use std::path::{Path, PathBuf};
 use hcore::os::process::{self, Pid};
 use std::result;
+use std::time::{Duration, Instant};
 
 use hcore::service::ServiceGroup;
-use launcher_client::LauncherCli;
+use launcher_client::{self, LauncherCli};
 use serde::{Serialize, Serializer};
 use serde::ser::SerializeStruct;
 use time::{self, Timespec};
 
 use error::{Result, Error};
 use fs;
-use manager::service::Pkg;
+use logging;
+use manager::service::{Pkg, ResourceLimits, RestartPolicy, ServiceSpec};
+use metrics;
 
 static LOGKEY: &'static str = "SV";
 
@@ -60,18 +65,47 @@ pub struct Supervisor {
     pub preamble: String,
     pub state: ProcessState,
     pub state_entered: Timespec,
+    pub restart_policy: RestartPolicy,
+    pub restart_backoff_cap: u64,
+    pub restart_limit: u32,
+    pub restart_window: u64,
+    /// Resource limits from the service spec, which take precedence over the package's.
+    pub limits: ResourceLimits,
     pid: Option<Pid>,
     pid_file: PathBuf,
+    /// The pid of a process which exited on its own and hasn't been dealt with yet.
+    exited_pid: Option<Pid>,
+    /// When the process is to be restarted, if a restart has been scheduled.
+    next_restart: Option<Instant>,
+    /// Times of the restarts which happened within the restart window.
+    restarts: VecDeque<Instant>,
+    /// True if the process is down and won't be restarted by the restart policy.
+    halted: bool,
+    /// True if the process was halted because it exceeded the restart limit.
+    failed: bool,
+    /// True if the last process was killed for exceeding its memory limit.
+    oom_killed: bool,
 }
 
 impl Supervisor {
-    pub fn new(service_group: &ServiceGroup) -> Supervisor {
+    pub fn new(service_group: &ServiceGroup, spec: &ServiceSpec) -> Supervisor {
         Supervisor {
             preamble: service_group.to_string(),
             state: ProcessState::Down,
             state_entered: time::get_time(),
+            restart_policy: spec.restart_policy,
+            restart_backoff_cap: spec.restart_backoff_cap,
+            restart_limit: spec.restart_limit,
+            restart_window: spec.restart_window,
+            limits: spec.limits,
             pid: None,
             pid_file: fs::svc_pid_file(service_group.service()),
+            exited_pid: None,
+            next_restart: None,
+            restarts: VecDeque::new(),
+            halted: false,
+            failed: false,
+            oom_killed: false,
         }
     }
 
@@ -95,6 +129,9 @@ impl Supervisor {
             }
         }
         debug!("Could not find a live process with pid {:?}", self.pid);
+        if self.state == ProcessState::Up {
+            self.exited_pid = pid;
+        }
         self.change_state(ProcessState::Down);
         self.cleanup_pidfile();
         self.pid = None;
@@ -120,13 +157,111 @@ impl Supervisor {
             &pkg.svc_group,
             svc_password,
             (*pkg.env).clone(),
+            launcher_client::ResourceLimits::from(self.limits.or(pkg.svc_limits)),
+            logging::json_log(group),
         )?;
         self.pid = Some(pid);
         self.create_pidfile()?;
         self.change_state(ProcessState::Up);
+        self.next_restart = None;
+        self.halted = false;
+        self.failed = false;
+        self.oom_killed = false;
         Ok(())
     }
 
+    /// Determines, according to the restart policy, whether a process which is down should be
+    /// restarted now.
+    ///
+    /// The first time this is called after the process went down a restart is scheduled with an
+    /// exponential backoff, unless the restart policy doesn't allow one or the restart limit was
+    /// reached within the restart window, in which case the process is left down.
+    pub fn restart_due(&mut self, launcher: &LauncherCli) -> bool {
+        if self.halted || self.next_restart.is_some() {
+            return self.restart_due_at(None, Instant::now());
+        }
+        // A process which never started, or whose exit status can't be retrieved, is treated as
+        // a failure.
+        let exit_code = match self.exited_pid.take() {
+            Some(pid) => {
+                match launcher.exit_status(pid) {
+                    Ok(status) => {
+                        if status.oom_killed {
+                            outputln!(preamble self.preamble,
+                                      "Process was killed for exceeding its memory limit");
+                            metrics::record_oom_kill(&self.preamble);
+                        }
+                        self.oom_killed = status.oom_killed;
+                        status.code
+                    }
+                    Err(err) => {
+                        debug!("Unable to retrieve exit status of {}, {}", pid, err);
+                        None
+                    }
+                }
+            }
+            None => None,
+        };
+        self.restart_due_at(exit_code, Instant::now())
+    }
+
+    /// Applies the restart policy at the given time to a process which exited with the given
+    /// code.
+    fn restart_due_at(&mut self, exit_code: Option<i32>, now: Instant) -> bool {
+        if self.halted {
+            return false;
+        }
+        if let Some(next_restart) = self.next_restart {
+            if now < next_restart {
+                return false;
+            }
+            self.next_restart = None;
+            return true;
+        }
+        match self.restart_policy {
+            RestartPolicy::Always => (),
+            RestartPolicy::Never => {
+                outputln!(preamble self.preamble,
+                          "Process is down; not restarting it (restart policy: never)");
+                self.halted = true;
+                return false;
+            }
+            RestartPolicy::OnFailure => {
+                if exit_code == Some(0) {
+                    outputln!(preamble self.preamble,
+                              "Process exited successfully; not restarting it \
+                              (restart policy: on-failure)");
+                    self.halted = true;
+                    return false;
+                }
+            }
+        }
+        let window = Duration::from_secs(self.restart_window);
+        while self.restarts.front().map_or(false, |t| now.duration_since(*t) > window) {
+            self.restarts.pop_front();
+        }
+        if self.restart_limit > 0 && self.restarts.len() >= self.restart_limit as usize {
+            outputln!(preamble self.preamble,
+                      "Process restarted {} times in the last {}s; marking service as failed",
+                      self.restarts.len(),
+                      self.restart_window);
+            self.halted = true;
+            self.failed = true;
+            return false;
+        }
+        let backoff = cmp::min(
+            1u64 << cmp::min(self.restarts.len(), 32),
+            self.restart_backoff_cap,
+        );
+        self.restarts.push_back(now);
+        if backoff == 0 {
+            return true;
+        }
+        outputln!(preamble self.preamble, "Process is down; restarting it in {}s", backoff);
+        self.next_restart = Some(now + Duration::from_secs(backoff));
+        false
+    }
+
     pub fn status(&self) -> (bool, String) {
         let status = format!(
             "{}: {} for {}",
@@ -161,6 +296,7 @@ impl Supervisor {
     where
         T: ToString,
     {
+        metrics::record_restart(&self.preamble);
         match self.pid {
             Some(pid) => {
                 match launcher.restart(pid) {
@@ -225,13 +361,16 @@ impl Serialize for Supervisor {
     where
         S: Serializer,
     {
-        let mut strukt = serializer.serialize_struct("supervisor", 5)?;
+        let mut strukt = serializer.serialize_struct("supervisor", 6)?;
         strukt.serialize_field("pid", &self.pid)?;
         strukt.serialize_field("state", &self.state)?;
         strukt.serialize_field(
             "state_entered",
             &self.state_entered.sec,
         )?;
+        strukt.serialize_field("restart_policy", &self.restart_policy)?;
+        strukt.serialize_field("failed", &self.failed)?;
+        strukt.serialize_field("oom_killed", &self.oom_killed)?;
         strukt.end()
     }
 }
@@ -262,3 +401,95 @@ where
         )),
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::time::{Duration, Instant};
+
+    use hcore::service::ServiceGroup;
+
+    use super::*;
+    use manager::service::{RestartPolicy, ServiceSpec};
+
+    fn supervisor(policy: RestartPolicy, cap: u64, limit: u32, window: u64) -> Supervisor {
+        let mut spec = ServiceSpec::default();
+        spec.restart_policy = policy;
+        spec.restart_backoff_cap = cap;
+        spec.restart_limit = limit;
+        spec.restart_window = window;
+        let sg = ServiceGroup::new(None, "redis", "default", None).unwrap();
+        Supervisor::new(&sg, &spec)
+    }
+
+    /// Lets the process fail at `at`, and returns after how many seconds it's restarted.
+    fn backoff_after_failure(sup: &mut Supervisor, at: Instant) -> Option<u64> {
+        if sup.restart_due_at(Some(1), at) {
+            return Some(0);
+        }
+        let next = match sup.next_restart {
+            Some(next) => next,
+            None => return None,
+        };
+        let backoff = next.duration_since(at);
+        assert!(!sup.restart_due_at(None, next - Duration::from_millis(1)));
+        assert!(sup.restart_due_at(None, next));
+        assert!(sup.next_restart.is_none());
+        Some(backoff.as_secs())
+    }
+
+    #[test]
+    fn restart_backoff_doubles_up_to_the_cap() {
+        let mut sup = supervisor(RestartPolicy::Always, 4, 0, 600);
+        let now = Instant::now();
+        let backoffs: Vec<Option<u64>> = (0..5)
+            .map(|i| backoff_after_failure(&mut sup, now + Duration::from_secs(i * 10)))
+            .collect();
+
+        assert_eq!(backoffs, vec![Some(1), Some(2), Some(4), Some(4), Some(4)]);
+    }
+
+    #[test]
+    fn restart_backoff_resets_after_the_restart_window() {
+        let mut sup = supervisor(RestartPolicy::Always, 60, 0, 30);
+        let now = Instant::now();
+        assert_eq!(backoff_after_failure(&mut sup, now), Some(1));
+        assert_eq!(backoff_after_failure(&mut sup, now + Duration::from_secs(5)), Some(2));
+
+        assert_eq!(
+            backoff_after_failure(&mut sup, now + Duration::from_secs(100)),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn restart_limit_marks_the_service_as_failed() {
+        let mut sup = supervisor(RestartPolicy::Always, 1, 2, 60);
+        let now = Instant::now();
+        assert_eq!(backoff_after_failure(&mut sup, now), Some(1));
+        assert_eq!(backoff_after_failure(&mut sup, now + Duration::from_secs(5)), Some(1));
+
+        assert_eq!(backoff_after_failure(&mut sup, now + Duration::from_secs(10)), None);
+        assert!(sup.halted);
+        assert!(sup.failed);
+        assert!(!sup.restart_due_at(None, now + Duration::from_secs(1000)));
+    }
+
+    #[test]
+    fn restart_policy_never_leaves_the_process_down() {
+        let mut sup = supervisor(RestartPolicy::Never, 1, 0, 60);
+
+        assert!(!sup.restart_due_at(Some(1), Instant::now()));
+        assert!(sup.halted);
+        assert!(!sup.failed);
+    }
+
+    #[test]
+    fn restart_policy_on_failure_only_restarts_failures() {
+        let mut sup = supervisor(RestartPolicy::OnFailure, 1, 0, 60);
+        let now = Instant::now();
+        assert_eq!(backoff_after_failure(&mut sup, now), Some(1));
+
+        assert!(!sup.restart_due_at(Some(0), now + Duration::from_secs(5)));
+        assert!(sup.halted);
+    }
+}
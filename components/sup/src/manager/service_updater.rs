@@ -15,17 +15,19 @@
 use std::collections::HashMap;
 use std::sync::mpsc::{sync_channel, Receiver, SyncSender, TryRecvError};
 use std::thread;
+use std::time::{Duration, Instant};
 
 use butterfly;
+use butterfly::rumor::canary::Canary_Status;
 use common::ui::UI;
 use env;
 use hcore::package::{PackageIdent, PackageInstall};
 use hcore::service::ServiceGroup;
 use launcher_client::LauncherCli;
 
-use census::CensusRing;
+use census::{Canary, CanaryStatus, CensusGroup, CensusRing};
 use manager::periodic::Periodic;
-use manager::service::{Service, Topology, UpdateStrategy};
+use manager::service::{HealthCheck, Service, Topology, UpdateStatus, UpdateStrategy};
 use util;
 
 static LOGKEY: &'static str = "SU";
@@ -39,6 +41,7 @@ type UpdaterStateList = HashMap<ServiceGroup, UpdaterState>;
 enum UpdaterState {
     AtOnce(Receiver<PackageInstall>),
     Rolling(RollingState),
+    Canary(CanaryState),
 }
 
 enum RollingState {
@@ -62,6 +65,23 @@ enum FollowerState {
     Updating(Receiver<PackageInstall>),
 }
 
+/// Current package update state of a member of a service group using the canary update strategy.
+/// The update leader polls for new packages and decides when to promote them; every member
+/// installs whatever the group's canary rumor tells it to.
+enum CanaryState {
+    AwaitingElection,
+    InElection,
+    /// Update leader polling for a newer package to test on the canaries
+    Polling(Receiver<PackageInstall>),
+    /// Update leader waiting for the canaries to run the package under test for the soak
+    /// period, holding the time at which they all started running it
+    Soaking(Option<Instant>),
+    /// Waiting to be told which package to run
+    Waiting,
+    /// Currently installing the package under test, the promoted or the rolled back package
+    Updating(Receiver<PackageInstall>),
+}
+
 /// The ServiceUpdater is in charge of updating a Service when a more recent version of a package
 /// has been published to a depot or installed to the local package cache.
 /// To use an update strategy, the supervisor must be configured to watch a depot for new versions.
@@ -99,9 +119,50 @@ impl ServiceUpdater {
                 );
                 true
             }
+            UpdateStrategy::Canary => {
+                self.states.entry(service.service_group.clone()).or_insert(
+                    UpdaterState::Canary(CanaryState::AwaitingElection),
+                );
+                true
+            }
         }
     }
 
+    /// Returns the progress of the update strategy of the given service group, `None` if it
+    /// doesn't have one.
+    pub fn status(&self, service_group: &ServiceGroup) -> Option<UpdateStatus> {
+        let state = match self.states.get(service_group) {
+            Some(state) => state,
+            None => return None,
+        };
+        let status = match *state {
+            UpdaterState::AtOnce(_) => UpdateStatus::Polling,
+            UpdaterState::Rolling(RollingState::AwaitingElection) => {
+                UpdateStatus::AwaitingElection
+            }
+            UpdaterState::Rolling(RollingState::InElection) => UpdateStatus::InElection,
+            UpdaterState::Rolling(RollingState::Leader(LeaderState::Waiting)) => {
+                UpdateStatus::LeaderWaiting
+            }
+            UpdaterState::Rolling(RollingState::Leader(LeaderState::Polling(_))) => {
+                UpdateStatus::LeaderPolling
+            }
+            UpdaterState::Rolling(RollingState::Follower(FollowerState::Waiting)) => {
+                UpdateStatus::FollowerWaiting
+            }
+            UpdaterState::Rolling(RollingState::Follower(FollowerState::Updating(_))) => {
+                UpdateStatus::FollowerUpdating
+            }
+            UpdaterState::Canary(CanaryState::AwaitingElection) => UpdateStatus::AwaitingElection,
+            UpdaterState::Canary(CanaryState::InElection) => UpdateStatus::InElection,
+            UpdaterState::Canary(CanaryState::Polling(_)) => UpdateStatus::LeaderPolling,
+            UpdaterState::Canary(CanaryState::Soaking(_)) => UpdateStatus::CanarySoaking,
+            UpdaterState::Canary(CanaryState::Waiting) => UpdateStatus::CanaryWaiting,
+            UpdaterState::Canary(CanaryState::Updating(_)) => UpdateStatus::CanaryUpdating,
+        };
+        Some(status)
+    }
+
     // TODO (CM): How do we remove something from the updater? e.g.,
     // when we stop or unload a service?
 
@@ -245,6 +306,10 @@ impl ServiceUpdater {
                                             debug!("We're in an update but it's not our turn");
                                             return false;
                                         }
+                                        if is_bad_release(service, leader.pkg.as_ref()) {
+                                            debug!("The update leader runs a rolled back release");
+                                            return false;
+                                        }
                                         debug!("We're in an update and it's our turn");
                                         let rx = Worker::new(service).start(
                                             &service.service_group,
@@ -296,14 +361,319 @@ impl ServiceUpdater {
                     *state = FollowerState::Waiting;
                 }
             }
+            Some(&mut UpdaterState::Canary(ref mut st)) => {
+                let census_group = match census_ring.census_group_for(&service.service_group) {
+                    Some(census_group) => census_group,
+                    None => return false,
+                };
+                let next = match *st {
+                    CanaryState::AwaitingElection => {
+                        self.butterfly.start_update_election(
+                            service.service_group.clone(),
+                            0,
+                            0,
+                        );
+                        Some(CanaryState::InElection)
+                    }
+                    CanaryState::InElection => {
+                        match (census_group.me(), census_group.update_leader()) {
+                            (Some(me), Some(leader)) => {
+                                let testing = census_group.canary.as_ref().map_or(false, |c| {
+                                    c.status == CanaryStatus::Testing
+                                });
+                                if me.member_id == leader.member_id && testing {
+                                    debug!("We're the update leader of a running canary update");
+                                    Some(CanaryState::Soaking(None))
+                                } else {
+                                    Some(CanaryState::Waiting)
+                                }
+                            }
+                            _ => return false,
+                        }
+                    }
+                    CanaryState::Polling(ref mut rx) => {
+                        match rx.try_recv() {
+                            Ok(package) => {
+                                let canaries = census_group.pick_canaries(service.canary_percent);
+                                outputln!(preamble service.service_group,
+                                          "Testing {} on canaries {}",
+                                          package.ident(),
+                                          canaries.join(", "));
+                                self.butterfly.start_canary(
+                                    service.service_group.clone(),
+                                    package.ident().to_string(),
+                                    service.pkg.ident.to_string(),
+                                    canaries,
+                                );
+                                service.update_package(package, launcher);
+                                updated = true;
+                                Some(CanaryState::Soaking(None))
+                            }
+                            Err(TryRecvError::Empty) => return false,
+                            Err(TryRecvError::Disconnected) => {
+                                debug!("Service Updater worker has died; restarting...");
+                                *rx = Worker::new(service)
+                                    .skipping(rolled_back_package(census_group))
+                                    .start(&service.service_group, None);
+                                None
+                            }
+                        }
+                    }
+                    CanaryState::Soaking(ref mut running_since) => {
+                        let canary = match census_group.canary {
+                            Some(ref canary) if canary.pkg == service.pkg.ident => canary,
+                            // Our own rumor hasn't reached the census yet
+                            _ => return false,
+                        };
+                        let canaries = census_group
+                            .members()
+                            .into_iter()
+                            .filter(|cm| canary.is_canary(&cm.member_id))
+                            .collect::<Vec<_>>();
+                        let canaries_updated =
+                            canaries.iter().all(|cm| cm.pkg.as_ref() == Some(&canary.pkg));
+                        let canaries_healthy =
+                            canaries.iter().all(|cm| is_healthy(cm.health_check));
+                        match soak_step(
+                            canary.status,
+                            service.health(),
+                            canaries_updated,
+                            canaries_healthy,
+                            running_since,
+                            Duration::from_secs(service.canary_soak),
+                            Instant::now(),
+                        ) {
+                            SoakStep::Finished => Some(CanaryState::Waiting),
+                            SoakStep::RollBack => {
+                                outputln!(preamble service.service_group,
+                                          "Canary is {}, rolling back to {}",
+                                          service.health(),
+                                          canary.previous_pkg);
+                                self.butterfly.set_canary_status(
+                                    &service.service_group,
+                                    Canary_Status::RolledBack,
+                                );
+                                Some(CanaryState::Waiting)
+                            }
+                            SoakStep::Soak => {
+                                if !canaries_updated {
+                                    debug!(
+                                        "Update leader still waiting for canaries to update..."
+                                    );
+                                }
+                                None
+                            }
+                            SoakStep::Promote => {
+                                outputln!(preamble service.service_group,
+                                          "Canaries are healthy, promoting {}",
+                                          canary.pkg);
+                                self.butterfly.set_canary_status(
+                                    &service.service_group,
+                                    Canary_Status::Promoted,
+                                );
+                                Some(CanaryState::Waiting)
+                            }
+                        }
+                    }
+                    CanaryState::Waiting => {
+                        let me = match census_group.me() {
+                            Some(me) => me,
+                            None => return false,
+                        };
+                        let mut target = None;
+                        if let Some(ref canary) = census_group.canary {
+                            match canary_step(
+                                canary,
+                                &me.member_id,
+                                &service.pkg.ident,
+                                service.health(),
+                                service.bad_releases(),
+                            ) {
+                                CanaryStep::Install(package) => target = Some(package),
+                                CanaryStep::RollBack => {
+                                    outputln!(preamble service.service_group,
+                                              "Canary is {}, rolling back to {}",
+                                              service.health(),
+                                              canary.previous_pkg);
+                                    self.butterfly.set_canary_status(
+                                        &service.service_group,
+                                        Canary_Status::RolledBack,
+                                    );
+                                }
+                                CanaryStep::Nothing => (),
+                            }
+                        }
+                        let is_leader = census_group.update_leader().map_or(false, |leader| {
+                            leader.member_id == me.member_id
+                        });
+                        let testing = census_group.canary.as_ref().map_or(false, |c| {
+                            c.status == CanaryStatus::Testing
+                        });
+                        match target {
+                            Some(package) => {
+                                debug!("Canary update, installing {}", package);
+                                let rx = Worker::new(service).start(
+                                    &service.service_group,
+                                    Some(package),
+                                );
+                                Some(CanaryState::Updating(rx))
+                            }
+                            None if is_leader && !testing => {
+                                if census_group.members().iter().any(|cm| cm.pkg != me.pkg) {
+                                    debug!("Update leader still waiting for the group...");
+                                    return false;
+                                }
+                                let rx = Worker::new(service)
+                                    .skipping(rolled_back_package(census_group))
+                                    .start(&service.service_group, None);
+                                Some(CanaryState::Polling(rx))
+                            }
+                            None => return false,
+                        }
+                    }
+                    CanaryState::Updating(ref mut rx) => {
+                        match rx.try_recv() {
+                            Ok(package) => {
+                                service.update_package(package, launcher);
+                                updated = true;
+                                Some(CanaryState::Waiting)
+                            }
+                            Err(TryRecvError::Empty) => return false,
+                            Err(TryRecvError::Disconnected) => {
+                                debug!("Service Updater worker has died; restarting...");
+                                Some(CanaryState::Waiting)
+                            }
+                        }
+                    }
+                };
+                if let Some(next) = next {
+                    *st = next;
+                }
+            }
             None => {}
         }
         updated
     }
 }
 
+/// What the update leader of a canary update does next while the canaries test a package.
+#[derive(Debug, PartialEq, Eq)]
+enum SoakStep {
+    /// The update was already promoted or rolled back
+    Finished,
+    /// The leader's own canary is unhealthy, so the update must be rolled back
+    RollBack,
+    /// Keep waiting for every canary to run the package, then for the soak period to pass
+    Soak,
+    /// The canaries ran the package for the whole soak period and report healthy, so it can be
+    /// promoted
+    Promote,
+}
+
+/// Decides how a canary update led by this member proceeds. The soak period starts over
+/// whenever one of the canaries isn't running the package under test. Once it has passed, the
+/// package is promoted only while the leader and every canary report an OK health check.
+fn soak_step(
+    status: CanaryStatus,
+    health: HealthCheck,
+    canaries_updated: bool,
+    canaries_healthy: bool,
+    running_since: &mut Option<Instant>,
+    soak_period: Duration,
+    now: Instant,
+) -> SoakStep {
+    if status != CanaryStatus::Testing {
+        return SoakStep::Finished;
+    }
+    if is_unhealthy(health) {
+        return SoakStep::RollBack;
+    }
+    if !canaries_updated {
+        *running_since = None;
+        return SoakStep::Soak;
+    }
+    let since = *running_since.get_or_insert(now);
+    if now.duration_since(since) >= soak_period && is_healthy(health) && canaries_healthy {
+        SoakStep::Promote
+    } else {
+        SoakStep::Soak
+    }
+}
+
+/// What a member of a service group does about the group's canary update.
+#[derive(Debug, PartialEq, Eq)]
+enum CanaryStep {
+    /// Install the given package
+    Install(PackageIdent),
+    /// Roll the update back since this canary is unhealthy
+    RollBack,
+    Nothing,
+}
+
+/// Decides what the given member, currently running `current`, does about a canary update.
+/// Canaries install the package under test, and everyone installs the promoted package or goes
+/// back to the previous one once the update is rolled back. A release the member rolled back
+/// before is never installed again.
+fn canary_step(
+    canary: &Canary,
+    member_id: &str,
+    current: &PackageIdent,
+    health: HealthCheck,
+    bad_releases: &[PackageIdent],
+) -> CanaryStep {
+    let step = match canary.status {
+        CanaryStatus::Testing if canary.is_canary(member_id) => {
+            if *current != canary.pkg {
+                CanaryStep::Install(canary.pkg.clone())
+            } else if is_unhealthy(health) {
+                CanaryStep::RollBack
+            } else {
+                CanaryStep::Nothing
+            }
+        }
+        CanaryStatus::Promoted if *current != canary.pkg => CanaryStep::Install(canary.pkg.clone()),
+        CanaryStatus::RolledBack if *current == canary.pkg => {
+            CanaryStep::Install(canary.previous_pkg.clone())
+        }
+        _ => CanaryStep::Nothing,
+    };
+    match step {
+        CanaryStep::Install(ref package) if bad_releases.contains(package) => CanaryStep::Nothing,
+        step => step,
+    }
+}
+
+/// Whether the given package is a release the service rolled back, and must not install again.
+fn is_bad_release(service: &Service, ident: Option<&PackageIdent>) -> bool {
+    ident.map_or(false, |ident| service.bad_releases().contains(ident))
+}
+
+/// Whether a member with the given health may have a package under test promoted. A health
+/// that isn't known yet doesn't count as healthy.
+fn is_healthy(health: HealthCheck) -> bool {
+    health == HealthCheck::Ok
+}
+
+/// Whether a canary with the given health should roll its update back.
+fn is_unhealthy(health: HealthCheck) -> bool {
+    match health {
+        HealthCheck::Warning | HealthCheck::Critical => true,
+        HealthCheck::Ok | HealthCheck::Unknown => false,
+    }
+}
+
+/// Returns the package of the group's last canary update if it was rolled back, so that the
+/// update leader doesn't test it again.
+fn rolled_back_package(census_group: &CensusGroup) -> Option<PackageIdent> {
+    match census_group.canary {
+        Some(ref canary) if canary.status == CanaryStatus::RolledBack => Some(canary.pkg.clone()),
+        _ => None,
+    }
+}
+
 struct Worker {
     current: PackageIdent,
+    skipped: Vec<PackageIdent>,
     spec_ident: PackageIdent,
     builder_url: String,
     channel: String,
@@ -365,12 +735,19 @@ impl Worker {
     fn new(service: &Service) -> Self {
         Worker {
             current: service.pkg.ident.clone(),
+            skipped: service.bad_releases().to_vec(),
             spec_ident: service.spec_ident.clone(),
             builder_url: service.bldr_url.clone(),
             channel: service.channel.clone(),
         }
     }
 
+    /// Never poll for the given package, even if it is newer than the current one.
+    fn skipping(mut self, ident: Option<PackageIdent>) -> Self {
+        self.skipped.extend(ident);
+        self
+    }
+
     /// Start a new update worker.
     ///
     /// Passing an optional package identifier will make the worker perform a run-once update to
@@ -453,7 +830,9 @@ impl Worker {
                 &self.channel,
             ) {
                 Ok(maybe_newer_package) => {
-                    if self.current < *maybe_newer_package.ident() {
+                    if self.skipped.contains(maybe_newer_package.ident()) {
+                        debug!("Package found was rolled back, skipping it");
+                    } else if self.current < *maybe_newer_package.ident() {
                         outputln!(
                             "Updating from {} to {}",
                             self.current,
@@ -475,3 +854,266 @@ impl Worker {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use super::*;
+
+    const SOAK: u64 = 60;
+
+    fn canary(status: CanaryStatus) -> Canary {
+        Canary {
+            incarnation: 1,
+            pkg: PackageIdent::from_str("core/redis/4.0.0/20180101000000").unwrap(),
+            previous_pkg: PackageIdent::from_str("core/redis/3.2.4/20170101000000").unwrap(),
+            canaries: vec!["canary".to_string()],
+            status: status,
+        }
+    }
+
+    fn soak(
+        health: HealthCheck,
+        canaries_updated: bool,
+        running_since: &mut Option<Instant>,
+        now: Instant,
+    ) -> SoakStep {
+        soak_step(
+            CanaryStatus::Testing,
+            health,
+            canaries_updated,
+            true,
+            running_since,
+            Duration::from_secs(SOAK),
+            now,
+        )
+    }
+
+    #[test]
+    fn promote_once_the_soak_period_has_passed() {
+        let start = Instant::now();
+        let mut running_since = None;
+
+        assert_eq!(
+            soak(HealthCheck::Ok, true, &mut running_since, start),
+            SoakStep::Soak
+        );
+        assert_eq!(running_since, Some(start));
+        assert_eq!(
+            soak(
+                HealthCheck::Ok,
+                true,
+                &mut running_since,
+                start + Duration::from_secs(SOAK - 1),
+            ),
+            SoakStep::Soak
+        );
+        assert_eq!(
+            soak(
+                HealthCheck::Ok,
+                true,
+                &mut running_since,
+                start + Duration::from_secs(SOAK),
+            ),
+            SoakStep::Promote
+        );
+    }
+
+    #[test]
+    fn soak_period_starts_once_every_canary_runs_the_package() {
+        let start = Instant::now();
+        let mut running_since = Some(start);
+        let later = start + Duration::from_secs(SOAK);
+
+        assert_eq!(
+            soak(HealthCheck::Ok, false, &mut running_since, later),
+            SoakStep::Soak
+        );
+        assert_eq!(running_since, None);
+        assert_eq!(
+            soak(HealthCheck::Ok, true, &mut running_since, later),
+            SoakStep::Soak
+        );
+        assert_eq!(running_since, Some(later));
+    }
+
+    #[test]
+    fn soak_period_times_out_without_promoting_an_unknown_health() {
+        let start = Instant::now();
+        let mut running_since = Some(start);
+
+        assert_eq!(
+            soak(
+                HealthCheck::Unknown,
+                true,
+                &mut running_since,
+                start + Duration::from_secs(SOAK * 2),
+            ),
+            SoakStep::Soak
+        );
+    }
+
+    #[test]
+    fn soak_period_times_out_without_promoting_unhealthy_canaries() {
+        let start = Instant::now();
+        let mut running_since = Some(start);
+        let later = start + Duration::from_secs(SOAK);
+
+        assert_eq!(
+            soak_step(
+                CanaryStatus::Testing,
+                HealthCheck::Ok,
+                true,
+                false,
+                &mut running_since,
+                Duration::from_secs(SOAK),
+                later,
+            ),
+            SoakStep::Soak
+        );
+        assert_eq!(running_since, Some(start));
+        assert_eq!(
+            soak_step(
+                CanaryStatus::Testing,
+                HealthCheck::Ok,
+                true,
+                true,
+                &mut running_since,
+                Duration::from_secs(SOAK),
+                later,
+            ),
+            SoakStep::Promote
+        );
+    }
+
+    #[test]
+    fn only_an_ok_health_is_healthy() {
+        assert!(is_healthy(HealthCheck::Ok));
+        assert!(!is_healthy(HealthCheck::Unknown));
+        assert!(!is_healthy(HealthCheck::Warning));
+        assert!(!is_healthy(HealthCheck::Critical));
+    }
+
+    #[test]
+    fn abort_when_the_leader_is_unhealthy() {
+        let start = Instant::now();
+        let mut running_since = Some(start);
+
+        assert_eq!(
+            soak(HealthCheck::Critical, true, &mut running_since, start),
+            SoakStep::RollBack
+        );
+        assert_eq!(
+            soak(HealthCheck::Warning, false, &mut running_since, start),
+            SoakStep::RollBack
+        );
+    }
+
+    #[test]
+    fn soaking_finishes_once_the_update_is_over() {
+        let mut running_since = None;
+        for status in vec![CanaryStatus::Promoted, CanaryStatus::RolledBack] {
+            assert_eq!(
+                soak_step(
+                    status,
+                    HealthCheck::Ok,
+                    true,
+                    true,
+                    &mut running_since,
+                    Duration::from_secs(SOAK),
+                    Instant::now(),
+                ),
+                SoakStep::Finished
+            );
+        }
+    }
+
+    #[test]
+    fn canaries_install_the_package_under_test() {
+        let canary = canary(CanaryStatus::Testing);
+
+        assert_eq!(
+            canary_step(&canary, "canary", &canary.previous_pkg, HealthCheck::Ok, &[]),
+            CanaryStep::Install(canary.pkg.clone())
+        );
+        assert_eq!(
+            canary_step(&canary, "canary", &canary.pkg, HealthCheck::Ok, &[]),
+            CanaryStep::Nothing
+        );
+        assert_eq!(
+            canary_step(&canary, "other", &canary.previous_pkg, HealthCheck::Ok, &[]),
+            CanaryStep::Nothing
+        );
+    }
+
+    #[test]
+    fn unhealthy_canary_aborts_the_update() {
+        let canary = canary(CanaryStatus::Testing);
+
+        assert_eq!(
+            canary_step(&canary, "canary", &canary.pkg, HealthCheck::Critical, &[]),
+            CanaryStep::RollBack
+        );
+        assert_eq!(
+            canary_step(&canary, "other", &canary.previous_pkg, HealthCheck::Critical, &[]),
+            CanaryStep::Nothing
+        );
+    }
+
+    #[test]
+    fn everyone_installs_a_promoted_package() {
+        let canary = canary(CanaryStatus::Promoted);
+
+        assert_eq!(
+            canary_step(&canary, "other", &canary.previous_pkg, HealthCheck::Ok, &[]),
+            CanaryStep::Install(canary.pkg.clone())
+        );
+        assert_eq!(
+            canary_step(&canary, "canary", &canary.pkg, HealthCheck::Ok, &[]),
+            CanaryStep::Nothing
+        );
+    }
+
+    #[test]
+    fn canaries_go_back_to_the_previous_package_after_a_rollback() {
+        let canary = canary(CanaryStatus::RolledBack);
+
+        assert_eq!(
+            canary_step(&canary, "canary", &canary.pkg, HealthCheck::Ok, &[]),
+            CanaryStep::Install(canary.previous_pkg.clone())
+        );
+        assert_eq!(
+            canary_step(&canary, "other", &canary.previous_pkg, HealthCheck::Ok, &[]),
+            CanaryStep::Nothing
+        );
+    }
+
+    #[test]
+    fn bad_releases_are_never_installed_again() {
+        let testing = canary(CanaryStatus::Testing);
+        let bad_releases = vec![testing.pkg.clone()];
+        assert_eq!(
+            canary_step(
+                &testing,
+                "canary",
+                &testing.previous_pkg,
+                HealthCheck::Ok,
+                &bad_releases,
+            ),
+            CanaryStep::Nothing
+        );
+
+        let promoted = canary(CanaryStatus::Promoted);
+        assert_eq!(
+            canary_step(
+                &promoted,
+                "other",
+                &promoted.previous_pkg,
+                HealthCheck::Ok,
+                &bad_releases,
+            ),
+            CanaryStep::Nothing
+        );
+    }
+}
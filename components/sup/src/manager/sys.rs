@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::BTreeMap;
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 use std::str;
 
@@ -36,6 +37,8 @@ pub struct Sys {
     pub http_gateway_ip: IpAddr,
     pub http_gateway_port: u16,
     pub permanent: bool,
+    /// User-defined labels of this Supervisor, gossiped with its membership and its services.
+    pub tags: BTreeMap<String, String>,
 }
 
 impl Sys {
@@ -66,6 +69,7 @@ impl Sys {
             http_gateway_ip: http.ip(),
             http_gateway_port: http.port(),
             permanent: permanent,
+            tags: BTreeMap::new(),
         }
     }
 
@@ -77,6 +81,7 @@ impl Sys {
         sys_info.set_gossip_port(self.gossip_port as u32);
         sys_info.set_http_gateway_ip(self.http_gateway_ip.to_string());
         sys_info.set_http_gateway_port(self.http_gateway_port as u32);
+        sys_info.set_tags(self.tags.clone().into_iter().collect());
         sys_info
     }
 
@@ -89,6 +94,25 @@ impl Sys {
     }
 }
 
+/// Parse a `key=value` tag, as given to `--tag` or in the tag filter of a bind. Keys and values
+/// may only contain letters, digits, `-`, `_` and `.`.
+pub fn parse_tag(tag: &str) -> Result<(String, String)> {
+    let valid = |s: &str| {
+        !s.is_empty() &&
+            s.chars().all(|c| match c {
+                'a'...'z' | 'A'...'Z' | '0'...'9' | '-' | '_' | '.' => true,
+                _ => false,
+            })
+    };
+    let mut parts = tag.splitn(2, '=');
+    match (parts.next(), parts.next()) {
+        (Some(key), Some(value)) if valid(key) && valid(value) => {
+            Ok((key.to_string(), value.to_string()))
+        }
+        _ => Err(sup_error!(Error::InvalidTag(tag.to_string()))),
+    }
+}
+
 pub fn lookup_ip() -> Result<IpAddr> {
     match hcore::util::sys::ip() {
         Ok(s) => Ok(s),
@@ -102,3 +126,32 @@ pub fn lookup_hostname() -> Result<String> {
         Err(_) => Err(sup_error!(Error::IPFailed)),
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::parse_tag;
+    use error::Error;
+
+    #[test]
+    fn parse_tag_splits_key_and_value() {
+        assert_eq!(
+            parse_tag("zone=us-east-1a").unwrap(),
+            (String::from("zone"), String::from("us-east-1a"))
+        );
+    }
+
+    #[test]
+    fn parse_tag_invalid() {
+        for tag in &["zone", "=us-east-1a", "zone=", "zone=a=b", "my zone=a", "rack=4&zone=a"] {
+            match parse_tag(tag) {
+                Err(e) => {
+                    match e.err {
+                        Error::InvalidTag(val) => assert_eq!(*tag, val),
+                        wrong => panic!("Unexpected error returned: {:?}", wrong),
+                    }
+                }
+                Ok(_) => panic!("{} should fail to parse", tag),
+            }
+        }
+    }
+}
@@ -0,0 +1,215 @@
+// Copyright (c) 2017 Chef Software Inc. and/or applicable contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Prometheus metrics describing the Supervisor, the services it runs and its gossip layer.
+//!
+//! Every metric is registered with the default registry and is served by the HTTP gateway's
+//! `/metrics` endpoint.
+
+use hcore::service::ServiceGroup;
+use prometheus::{CounterVec, Gauge, GaugeVec};
+
+use manager::service::hooks::{DemoteHook, FileUpdatedHook, HealthCheckHook, Hook, InitHook,
+                              PostRunHook, PostStopHook, ReconfigureHook, ReloadHook, RunHook,
+                              SmokeTestHook, SuitabilityHook};
+use manager::service::HealthCheck;
+
+lazy_static! {
+    pub static ref SERVICE_HEALTH_CHECK: GaugeVec = register_gauge_vec!(
+        opts!(
+            "hab_sup_service_health_check",
+            "Result of the last health check (0 = ok, 1 = warning, 2 = critical, 3 = unknown)."),
+        &["service_group"]).unwrap();
+
+    pub static ref SERVICE_RESTARTS: CounterVec = register_counter_vec!(
+        opts!(
+            "hab_sup_service_restarts_total",
+            "Total number of times a service's process was restarted."),
+        &["service_group"]).unwrap();
+
+    pub static ref SERVICE_OOM_KILLS: CounterVec = register_counter_vec!(
+        opts!(
+            "hab_sup_service_oom_kills_total",
+            "Total number of times a service's process was killed for exceeding its memory limit."),
+        &["service_group"]).unwrap();
+
+    pub static ref SERVICE_STATE_CHANGE_SECONDS: GaugeVec = register_gauge_vec!(
+        opts!(
+            "hab_sup_service_seconds_since_state_change",
+            "Seconds elapsed since a service's process last went up or down."),
+        &["service_group"]).unwrap();
+
+    pub static ref HOOK_EXIT_CODE: GaugeVec = register_gauge_vec!(
+        opts!(
+            "hab_sup_hook_exit_code",
+            "Exit code of the last run of a service's hook."),
+        &["service_group", "hook"]).unwrap();
+
+    pub static ref BUTTERFLY_SWIM_ROUNDS: Gauge = register_gauge!(
+        opts!(
+            "hab_sup_butterfly_swim_rounds",
+            "Number of completed rounds of the SWIM membership protocol.")).unwrap();
+
+    pub static ref BUTTERFLY_GOSSIP_ROUNDS: Gauge = register_gauge!(
+        opts!(
+            "hab_sup_butterfly_gossip_rounds",
+            "Number of completed rounds of the gossip protocol.")).unwrap();
+
+    pub static ref BUTTERFLY_MEMBERS: GaugeVec = register_gauge_vec!(
+        opts!(
+            "hab_sup_butterfly_members",
+            "Number of members in the ring, by health."),
+        &["health"]).unwrap();
+
+    pub static ref BUTTERFLY_RUMORS: GaugeVec = register_gauge_vec!(
+        opts!(
+            "hab_sup_butterfly_rumors",
+            "Number of rumors held in each rumor store, by rumor type."),
+        &["rumor_type"]).unwrap();
+}
+
+pub fn record_health_check(service_group: &ServiceGroup, check: HealthCheck) {
+    let sg = service_group.to_string();
+    SERVICE_HEALTH_CHECK
+        .with_label_values(&[sg.as_str()])
+        .set(check as i8 as f64);
+}
+
+pub fn record_restart(service_group: &str) {
+    SERVICE_RESTARTS.with_label_values(&[service_group]).inc();
+}
+
+pub fn record_oom_kill(service_group: &str) {
+    SERVICE_OOM_KILLS.with_label_values(&[service_group]).inc();
+}
+
+/// Sets the number of rumors held in each of the Butterfly server's rumor stores.
+pub fn record_rumors(counts: &[(&str, usize)]) {
+    for &(rumor_type, count) in counts {
+        BUTTERFLY_RUMORS
+            .with_label_values(&[rumor_type])
+            .set(count as f64);
+    }
+}
+
+pub fn record_hook_exit<H: Hook>(service_group: &ServiceGroup, code: i32) {
+    let sg = service_group.to_string();
+    HOOK_EXIT_CODE
+        .with_label_values(&[sg.as_str(), H::file_name()])
+        .set(code as f64);
+}
+
+/// Drop every series labeled with the given service group, so that unloaded services stop being
+/// reported.
+pub fn remove_service(service_group: &ServiceGroup) {
+    let sg = service_group.to_string();
+    let _ = SERVICE_HEALTH_CHECK.remove_label_values(&[sg.as_str()]);
+    let _ = SERVICE_RESTARTS.remove_label_values(&[sg.as_str()]);
+    let _ = SERVICE_OOM_KILLS.remove_label_values(&[sg.as_str()]);
+    let _ = SERVICE_STATE_CHANGE_SECONDS.remove_label_values(&[sg.as_str()]);
+    for hook in &[
+        DemoteHook::file_name(),
+        FileUpdatedHook::file_name(),
+        HealthCheckHook::file_name(),
+        InitHook::file_name(),
+        PostRunHook::file_name(),
+        PostStopHook::file_name(),
+        ReconfigureHook::file_name(),
+        ReloadHook::file_name(),
+        RunHook::file_name(),
+        SmokeTestHook::file_name(),
+        SuitabilityHook::file_name(),
+    ]
+    {
+        let _ = HOOK_EXIT_CODE.remove_label_values(&[sg.as_str(), *hook]);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use prometheus;
+
+    use super::*;
+
+    fn service_group(name: &str) -> ServiceGroup {
+        ServiceGroup::new(None, name, "test", None).unwrap()
+    }
+
+    #[test]
+    fn record_restarts_and_oom_kills() {
+        record_restart("metrics-restart.test");
+        record_restart("metrics-restart.test");
+        record_oom_kill("metrics-restart.test");
+
+        assert_eq!(
+            SERVICE_RESTARTS
+                .with_label_values(&["metrics-restart.test"])
+                .get(),
+            2.0
+        );
+        assert_eq!(
+            SERVICE_OOM_KILLS
+                .with_label_values(&["metrics-restart.test"])
+                .get(),
+            1.0
+        );
+    }
+
+    #[test]
+    fn record_health_checks_and_hook_exits() {
+        let sg = service_group("metrics-health");
+        record_health_check(&sg, HealthCheck::Critical);
+        record_hook_exit::<RunHook>(&sg, 3);
+
+        assert_eq!(
+            SERVICE_HEALTH_CHECK
+                .with_label_values(&["metrics-health.test"])
+                .get(),
+            2.0
+        );
+        assert_eq!(
+            HOOK_EXIT_CODE
+                .with_label_values(&["metrics-health.test", RunHook::file_name()])
+                .get(),
+            3.0
+        );
+    }
+
+    #[test]
+    fn record_every_rumor_store() {
+        record_rumors(&[("canary", 2), ("ring_key", 1)]);
+
+        assert_eq!(BUTTERFLY_RUMORS.with_label_values(&["canary"]).get(), 2.0);
+        assert_eq!(BUTTERFLY_RUMORS.with_label_values(&["ring_key"]).get(), 1.0);
+        assert!(prometheus::gather().iter().any(|family| {
+            family.get_name() == "hab_sup_butterfly_rumors"
+        }));
+    }
+
+    #[test]
+    fn remove_service_drops_its_series() {
+        let sg = service_group("metrics-remove");
+        record_restart("metrics-remove.test");
+        record_health_check(&sg, HealthCheck::Ok);
+
+        remove_service(&sg);
+
+        assert!(SERVICE_RESTARTS
+            .remove_label_values(&["metrics-remove.test"])
+            .is_err());
+        assert!(SERVICE_HEALTH_CHECK
+            .remove_label_values(&["metrics-remove.test"])
+            .is_err());
+    }
+}
@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use hcore::service::ServiceGroup;
 
@@ -31,7 +31,7 @@ impl<'a> Binds<'a> {
         let mut map = HashMap::default();
         for bind in bindings {
             if let Some(group) = census.census_group_for(&bind.service_group) {
-                map.insert(bind.name.to_string(), BindGroup::new(group));
+                map.insert(bind.name.to_string(), BindGroup::new(group, &bind.tags));
             }
         }
         Binds(map)
@@ -45,10 +45,17 @@ pub struct BindGroup<'a> {
 }
 
 impl<'a> BindGroup<'a> {
-    fn new(group: &'a CensusGroup) -> Self {
+    /// Only the members carrying all of the bind's tags provide it; the leader comes first when
+    /// it is one of them.
+    fn new(group: &'a CensusGroup, tags: &BTreeMap<String, String>) -> Self {
+        let members = group.members_with_tags(tags);
+        let first = match group.leader() {
+            Some(leader) if leader.has_tags(tags) => Some(SvcMember(leader)),
+            _ => members.first().map(|m| SvcMember(m)),
+        };
         BindGroup {
-            first: select_first(group),
-            members: group.members().iter().map(|m| SvcMember(m)).collect(),
+            first: first,
+            members: members.iter().map(|m| SvcMember(m)).collect(),
         }
     }
 }
@@ -92,6 +99,7 @@ impl<'a> RenderContext<'a> {
 
 #[derive(Clone, Debug, Serialize)]
 pub struct Svc<'a> {
+    pub service_group: &'a ServiceGroup,
     pub service: &'a str,
     pub group: &'a str,
     pub org: Option<&'a str>,
@@ -111,6 +119,7 @@ pub struct Svc<'a> {
 impl<'a> Svc<'a> {
     fn new(census_group: &'a CensusGroup) -> Self {
         Svc {
+            service_group: &census_group.service_group,
             service: census_group.service_group.service(),
             group: census_group.service_group.group(),
             org: census_group.service_group.org(),
@@ -152,3 +161,83 @@ fn select_first(census_group: &CensusGroup) -> Option<SvcMember> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use hcore::package::ident::PackageIdent;
+    use hcore::service::ServiceGroup;
+    use butterfly::member::MemberList;
+    use butterfly::rumor::service::Service as ServiceRumor;
+    use butterfly::rumor::service_config::ServiceConfig as ServiceConfigRumor;
+    use butterfly::rumor::service_file::ServiceFile as ServiceFileRumor;
+    use butterfly::rumor::election::Election as ElectionRumor;
+    use butterfly::rumor::election::ElectionUpdate as ElectionUpdateRumor;
+    use butterfly::rumor::canary::Canary as CanaryRumor;
+    use butterfly::rumor::service::SysInfo;
+    use butterfly::rumor::RumorStore;
+    use census::CensusRing;
+
+    use super::BindGroup;
+
+    fn zone() -> BTreeMap<String, String> {
+        let mut tags = BTreeMap::new();
+        tags.insert("zone".to_string(), "us-east-1a".to_string());
+        tags
+    }
+
+    #[test]
+    fn bind_group_only_holds_the_members_carrying_the_tags_of_the_bind() {
+        let pg_id = PackageIdent::new("core", "redis", Some("3.2.4"), Some("20170514150022"));
+        let sg = ServiceGroup::new(None, "redis", "prod", None).unwrap();
+        let sys_info = SysInfo::new();
+        let mut tagged_sys_info = SysInfo::new();
+        tagged_sys_info.mut_tags().insert(
+            "zone".to_string(),
+            "us-east-1a".to_string(),
+        );
+
+        let service_store: RumorStore<ServiceRumor> = RumorStore::default();
+        service_store.insert(ServiceRumor::new(
+            "member-a".to_string(),
+            &pg_id,
+            &sg,
+            &tagged_sys_info,
+            None,
+        ));
+        service_store.insert(ServiceRumor::new(
+            "member-b".to_string(),
+            &pg_id,
+            &sg,
+            &sys_info,
+            None,
+        ));
+        let election_store: RumorStore<ElectionRumor> = RumorStore::default();
+        let mut election = ElectionRumor::new("member-b", sg.clone(), 10);
+        election.finish();
+        election_store.insert(election);
+
+        let mut ring = CensusRing::new("member-b".to_string());
+        ring.update_from_rumors(
+            &service_store,
+            &election_store,
+            &RumorStore::<ElectionUpdateRumor>::default(),
+            &MemberList::new(),
+            &RumorStore::<ServiceConfigRumor>::default(),
+            &RumorStore::<ServiceFileRumor>::default(),
+            &RumorStore::<CanaryRumor>::default(),
+        );
+        let group = ring.census_group_for(&sg).unwrap();
+
+        // The untagged leader is left out, so the first member is the tagged one
+        let bind = BindGroup::new(group, &zone());
+        let members: Vec<&str> = bind.members.iter().map(|m| &m.0.member_id[..]).collect();
+        assert_eq!(members, vec!["member-a"]);
+        assert_eq!(bind.first.unwrap().0.member_id, "member-a");
+
+        let bind = BindGroup::new(group, &BTreeMap::new());
+        assert_eq!(bind.members.len(), 2);
+        assert_eq!(bind.first.unwrap().0.member_id, "member-b");
+    }
+}
@@ -0,0 +1,55 @@
+// Copyright (c) 2017 Chef Software Inc. and/or applicable contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use handlebars::{Handlebars, Helper, HelperDef, RenderContext, RenderError};
+use hcore::crypto::hash;
+
+use super::super::RenderResult;
+use super::json_to_text;
+
+#[derive(Clone, Copy)]
+pub struct Blake2bHelper;
+
+impl HelperDef for Blake2bHelper {
+    fn call(&self, h: &Helper, _: &Handlebars, rc: &mut RenderContext) -> RenderResult<()> {
+        let param = h.param(0)
+            .map(|v| json_to_text(v.value()))
+            .ok_or_else(|| RenderError::new("Expected 1 parameter for \"blake2b\""))?;
+        // The same digest `hab pkg hash` prints for a file of this content.
+        let digest = hash::hash_string(&param);
+        rc.writer.write(digest.into_bytes().as_ref())?;
+        Ok(())
+    }
+}
+
+pub static BLAKE2B: Blake2bHelper = Blake2bHelper;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_blake2b_helper() {
+        let json = json!({
+            "cfg": { "password": "hunter2" }
+        });
+        let mut handlebars = Handlebars::new();
+        handlebars.register_helper("blake2b", Box::new(BLAKE2B));
+        assert_eq!(
+            "733ec559845c8942ee01dcbba29ef7a44e31bc38a6184bc7f044155f7a3ec0f8",
+            handlebars.template_render("{{blake2b cfg.password}}", &json).unwrap()
+        );
+    }
+}
@@ -0,0 +1,123 @@
+// Copyright (c) 2017 Chef Software Inc. and/or applicable contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use handlebars::{Handlebars, Helper, HelperDef, RenderContext, RenderError};
+use serde_json::Value as Json;
+
+use super::super::RenderResult;
+use super::{json_eq, render_condition};
+
+#[derive(Clone, Copy)]
+pub struct EqHelper;
+
+impl HelperDef for EqHelper {
+    fn call(&self, h: &Helper, r: &Handlebars, rc: &mut RenderContext) -> RenderResult<()> {
+        let (a, b) = operands(h, "eq")?;
+        let condition = json_eq(a, b);
+        render_condition(h, r, rc, condition)
+    }
+}
+
+pub static EQ: EqHelper = EqHelper;
+
+#[derive(Clone, Copy)]
+pub struct NeHelper;
+
+impl HelperDef for NeHelper {
+    fn call(&self, h: &Helper, r: &Handlebars, rc: &mut RenderContext) -> RenderResult<()> {
+        let (a, b) = operands(h, "ne")?;
+        let condition = !json_eq(a, b);
+        render_condition(h, r, rc, condition)
+    }
+}
+
+pub static NE: NeHelper = NeHelper;
+
+#[derive(Clone, Copy)]
+pub struct GtHelper;
+
+impl HelperDef for GtHelper {
+    fn call(&self, h: &Helper, r: &Handlebars, rc: &mut RenderContext) -> RenderResult<()> {
+        let condition = {
+            let (a, b) = operands(h, "gt")?;
+            match (a, b) {
+                (&Json::Number(ref x), &Json::Number(ref y)) => {
+                    x.as_f64().unwrap_or(0.0) > y.as_f64().unwrap_or(0.0)
+                }
+                (&Json::String(ref x), &Json::String(ref y)) => x > y,
+                _ => {
+                    return Err(RenderError::new(
+                        "Expected 2 numbers or 2 strings for \"gt\"",
+                    ))
+                }
+            }
+        };
+        render_condition(h, r, rc, condition)
+    }
+}
+
+pub static GT: GtHelper = GtHelper;
+
+fn operands<'a>(h: &'a Helper, name: &str) -> RenderResult<(&'a Json, &'a Json)> {
+    match (h.param(0), h.param(1)) {
+        (Some(a), Some(b)) => Ok((a.value(), b.value())),
+        _ => Err(RenderError::new(
+            format!("Expected 2 parameters for \"{}\"", name),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn render(template: &str) -> String {
+        let json = json!({
+            "topology": "leader",
+            "port": 8080,
+            "version": "1.2.0"
+        });
+        let mut handlebars = Handlebars::new();
+        handlebars.register_helper("eq", Box::new(EQ));
+        handlebars.register_helper("ne", Box::new(NE));
+        handlebars.register_helper("gt", Box::new(GT));
+        handlebars.template_render(template, &json).unwrap()
+    }
+
+    #[test]
+    fn test_eq_helper() {
+        assert_eq!(
+            "leader",
+            render("{{#eq topology \"leader\"}}leader{{else}}standalone{{/eq}}")
+        );
+        assert_eq!("true", render("{{eq port 8080.0}}"));
+        assert_eq!("false", render("{{eq port \"8080\"}}"));
+    }
+
+    #[test]
+    fn test_ne_helper() {
+        assert_eq!(
+            "standalone",
+            render("{{#ne topology \"leader\"}}leader{{else}}standalone{{/ne}}")
+        );
+        assert_eq!("true", render("{{ne port 80}}"));
+    }
+
+    #[test]
+    fn test_gt_helper() {
+        assert_eq!("high", render("{{#gt port 1024}}high{{else}}low{{/gt}}"));
+        assert_eq!("true", render("{{gt version \"1.1.0\"}}"));
+    }
+}
@@ -0,0 +1,82 @@
+// Copyright (c) 2017 Chef Software Inc. and/or applicable contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use handlebars::{Handlebars, Helper, HelperDef, RenderContext, RenderError};
+use serde_json::Value as Json;
+
+use super::super::RenderResult;
+use super::{json_eq, json_to_text, render_condition};
+
+#[derive(Clone, Copy)]
+pub struct ContainsHelper;
+
+impl HelperDef for ContainsHelper {
+    fn call(&self, h: &Helper, r: &Handlebars, rc: &mut RenderContext) -> RenderResult<()> {
+        let condition = {
+            let (haystack, needle) = match (h.param(0), h.param(1)) {
+                (Some(a), Some(b)) => (a.value(), b.value()),
+                _ => return Err(RenderError::new("Expected 2 parameters for \"contains\"")),
+            };
+            match *haystack {
+                Json::String(ref s) => s.contains(&json_to_text(needle)),
+                Json::Array(ref list) => list.iter().any(|v| json_eq(v, needle)),
+                Json::Object(ref obj) => obj.contains_key(&json_to_text(needle)),
+                Json::Null => false,
+                _ => {
+                    return Err(RenderError::new(
+                        "Expected a string, list or object for \"contains\"",
+                    ))
+                }
+            }
+        };
+        render_condition(h, r, rc, condition)
+    }
+}
+
+pub static CONTAINS: ContainsHelper = ContainsHelper;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn render(template: &str) -> String {
+        let json = json!({
+            "features": ["tls", "metrics"],
+            "ports": [80, 443],
+            "url": "https://example.com",
+            "users": { "admin": {} }
+        });
+        let mut handlebars = Handlebars::new();
+        handlebars.register_helper("contains", Box::new(CONTAINS));
+        handlebars.template_render(template, &json).unwrap()
+    }
+
+    #[test]
+    fn test_contains_helper_on_lists() {
+        assert_eq!(
+            "on",
+            render("{{#contains features \"tls\"}}on{{else}}off{{/contains}}")
+        );
+        assert_eq!("true", render("{{contains ports 443}}"));
+        assert_eq!("false", render("{{contains ports 8080}}"));
+    }
+
+    #[test]
+    fn test_contains_helper_on_strings_and_objects() {
+        assert_eq!("true", render("{{contains url \"https://\"}}"));
+        assert_eq!("true", render("{{contains users \"admin\"}}"));
+        assert_eq!("false", render("{{contains missing \"admin\"}}"));
+    }
+}
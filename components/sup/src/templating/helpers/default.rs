@@ -0,0 +1,67 @@
+// Copyright (c) 2017 Chef Software Inc. and/or applicable contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use handlebars::{Handlebars, Helper, HelperDef, RenderContext, RenderError};
+use serde_json::Value as Json;
+
+use super::super::RenderResult;
+use super::json_to_text;
+
+#[derive(Clone, Copy)]
+pub struct DefaultHelper;
+
+impl HelperDef for DefaultHelper {
+    fn call(&self, h: &Helper, _: &Handlebars, rc: &mut RenderContext) -> RenderResult<()> {
+        let value = h.param(0)
+            .ok_or_else(|| RenderError::new("Expected 2 parameters for \"default\""))?
+            .value();
+        let fallback = h.param(1)
+            .ok_or_else(|| RenderError::new("Expected 2 parameters for \"default\""))?
+            .value();
+        let text = match *value {
+            Json::Null => json_to_text(fallback),
+            Json::String(ref s) if s.is_empty() => json_to_text(fallback),
+            ref v => json_to_text(v),
+        };
+        rc.writer.write(text.into_bytes().as_ref())?;
+        Ok(())
+    }
+}
+
+pub static DEFAULT: DefaultHelper = DefaultHelper;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_default_helper() {
+        let json = json!({
+            "port": 8080,
+            "host": ""
+        });
+        let mut handlebars = Handlebars::new();
+        handlebars.register_helper("default", Box::new(DEFAULT));
+        assert_eq!(
+            "8080 localhost info",
+            handlebars
+                .template_render(
+                    "{{default port 80}} {{default host \"localhost\"}} {{default level \"info\"}}",
+                    &json,
+                )
+                .unwrap()
+        );
+    }
+}
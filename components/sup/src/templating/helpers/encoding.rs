@@ -0,0 +1,88 @@
+// Copyright (c) 2017 Chef Software Inc. and/or applicable contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use base64;
+use handlebars::{Handlebars, Helper, HelperDef, RenderContext, RenderError};
+
+use super::super::RenderResult;
+use super::json_to_text;
+
+#[derive(Clone, Copy)]
+pub struct Base64EncodeHelper;
+
+impl HelperDef for Base64EncodeHelper {
+    fn call(&self, h: &Helper, _: &Handlebars, rc: &mut RenderContext) -> RenderResult<()> {
+        let param = h.param(0)
+            .map(|v| json_to_text(v.value()))
+            .ok_or_else(|| RenderError::new("Expected 1 parameter for \"base64Encode\""))?;
+        rc.writer.write(
+            base64::encode(param.as_bytes()).into_bytes().as_ref(),
+        )?;
+        Ok(())
+    }
+}
+
+pub static BASE64_ENCODE: Base64EncodeHelper = Base64EncodeHelper;
+
+#[derive(Clone, Copy)]
+pub struct Base64DecodeHelper;
+
+impl HelperDef for Base64DecodeHelper {
+    fn call(&self, h: &Helper, _: &Handlebars, rc: &mut RenderContext) -> RenderResult<()> {
+        let param = h.param(0).and_then(|v| v.value().as_str()).ok_or_else(|| {
+            RenderError::new("Expected a string parameter for \"base64Decode\"")
+        })?;
+        let bytes = base64::decode(param.trim()).map_err(|e| {
+            RenderError::new(format!("Can't decode parameter from base64: {}", e))
+        })?;
+        let decoded = String::from_utf8(bytes).map_err(|_| {
+            RenderError::new("Decoded parameter of \"base64Decode\" isn't valid UTF-8")
+        })?;
+        rc.writer.write(decoded.into_bytes().as_ref())?;
+        Ok(())
+    }
+}
+
+pub static BASE64_DECODE: Base64DecodeHelper = Base64DecodeHelper;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_base64_helpers() {
+        let json = json!({
+            "creds": "admin:hunter2",
+            "encoded": "YWRtaW46aHVudGVyMg==",
+            "invalid": "not base64!"
+        });
+        let mut handlebars = Handlebars::new();
+        handlebars.register_helper("base64Encode", Box::new(BASE64_ENCODE));
+        handlebars.register_helper("base64Decode", Box::new(BASE64_DECODE));
+        assert_eq!(
+            "YWRtaW46aHVudGVyMg==",
+            handlebars.template_render("{{base64Encode creds}}", &json).unwrap()
+        );
+        assert_eq!(
+            "admin:hunter2",
+            handlebars.template_render("{{base64Decode encoded}}", &json).unwrap()
+        );
+        assert!(
+            handlebars
+                .template_render("{{base64Decode invalid}}", &json)
+                .is_err()
+        );
+    }
+}
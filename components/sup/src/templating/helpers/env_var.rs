@@ -0,0 +1,104 @@
+// Copyright (c) 2017 Chef Software Inc. and/or applicable contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use handlebars::{Handlebars, Helper, HelperDef, RenderContext, RenderError};
+use hcore::env as henv;
+
+use super::super::RenderResult;
+use super::json_to_text;
+
+/// Parts of a variable name which mark it as the Supervisor's own or as holding a secret; such
+/// variables are never rendered into a template.
+const DENIED_PREFIXES: &'static [&'static str] = &["HAB_"];
+const DENIED_WORDS: &'static [&'static str] =
+    &["SECRET", "PASSWORD", "PASSWD", "TOKEN", "CREDENTIAL", "KEY"];
+
+#[derive(Clone, Copy)]
+pub struct EnvVarHelper;
+
+impl HelperDef for EnvVarHelper {
+    fn call(&self, h: &Helper, _: &Handlebars, rc: &mut RenderContext) -> RenderResult<()> {
+        let name = h.param(0).and_then(|v| v.value().as_str()).ok_or_else(|| {
+            RenderError::new("Expected a string parameter for \"envVar\"")
+        })?;
+        if is_denied(name) {
+            return Err(RenderError::new(
+                format!("\"envVar\" can't read the environment variable {}", name),
+            ));
+        }
+        // Variables of the Supervisor's environment; the 2nd parameter, if any, is used when the
+        // variable isn't set.
+        let value = match henv::var(name) {
+            Ok(value) => value,
+            Err(_) => h.param(1).map(|v| json_to_text(v.value())).unwrap_or_default(),
+        };
+        rc.writer.write(value.into_bytes().as_ref())?;
+        Ok(())
+    }
+}
+
+pub static ENV_VAR: EnvVarHelper = EnvVarHelper;
+
+fn is_denied(name: &str) -> bool {
+    let name = name.to_uppercase();
+    DENIED_PREFIXES.iter().any(|prefix| name.starts_with(prefix)) ||
+        DENIED_WORDS.iter().any(|word| name.contains(word))
+}
+
+#[cfg(test)]
+mod test {
+    use std::env;
+
+    use super::*;
+
+    #[test]
+    fn test_env_var_helper() {
+        env::set_var("TEST_ENV_VAR_HELPER", "from-env");
+        let json = json!({});
+        let mut handlebars = Handlebars::new();
+        handlebars.register_helper("envVar", Box::new(ENV_VAR));
+        assert_eq!(
+            "from-env",
+            handlebars
+                .template_render("{{envVar \"TEST_ENV_VAR_HELPER\"}}", &json)
+                .unwrap()
+        );
+        assert_eq!(
+            "fallback",
+            handlebars
+                .template_render("{{envVar \"TEST_ENV_VAR_UNSET\" \"fallback\"}}", &json)
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_env_var_helper_denies_supervisor_and_secret_variables() {
+        env::set_var("TEST_ENV_VAR_HELPER_API_TOKEN", "hunter2");
+        let json = json!({});
+        let mut handlebars = Handlebars::new();
+        handlebars.register_helper("envVar", Box::new(ENV_VAR));
+        for name in &[
+            "HAB_AUTH_TOKEN",
+            "hab_license",
+            "TEST_ENV_VAR_HELPER_API_TOKEN",
+            "AWS_SECRET_ACCESS_KEY",
+            "DB_PASSWORD",
+        ]
+        {
+            let template = format!("{{{{envVar \"{}\" \"fallback\"}}}}", name);
+            assert!(handlebars.template_render(&template, &json).is_err());
+        }
+    }
+}
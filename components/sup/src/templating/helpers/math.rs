@@ -0,0 +1,150 @@
+// Copyright (c) 2017 Chef Software Inc. and/or applicable contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use handlebars::{Handlebars, Helper, HelperDef, RenderContext, RenderError};
+use serde_json::Value as Json;
+
+use super::super::RenderResult;
+
+#[derive(Clone, Copy)]
+pub struct AddHelper;
+
+impl HelperDef for AddHelper {
+    fn call(&self, h: &Helper, _: &Handlebars, rc: &mut RenderContext) -> RenderResult<()> {
+        let result = fold(h, "add", i64::checked_add, |a, b| a + b)?;
+        rc.writer.write(result.into_bytes().as_ref())?;
+        Ok(())
+    }
+}
+
+pub static ADD: AddHelper = AddHelper;
+
+#[derive(Clone, Copy)]
+pub struct MulHelper;
+
+impl HelperDef for MulHelper {
+    fn call(&self, h: &Helper, _: &Handlebars, rc: &mut RenderContext) -> RenderResult<()> {
+        let result = fold(h, "mul", i64::checked_mul, |a, b| a * b)?;
+        rc.writer.write(result.into_bytes().as_ref())?;
+        Ok(())
+    }
+}
+
+pub static MUL: MulHelper = MulHelper;
+
+enum Number {
+    Int(i64),
+    Float(f64),
+}
+
+/// Applies an operation to all the parameters of a helper. The result is an integer as long as
+/// every parameter is one, and a float otherwise.
+fn fold<I, F>(h: &Helper, name: &str, int_op: I, float_op: F) -> RenderResult<String>
+where
+    I: Fn(i64, i64) -> Option<i64>,
+    F: Fn(f64, f64) -> f64,
+{
+    if h.params().len() < 2 {
+        return Err(RenderError::new(
+            format!("Expected at least 2 parameters for \"{}\"", name),
+        ));
+    }
+    let mut numbers = Vec::new();
+    for param in h.params() {
+        numbers.push(to_number(param.value()).ok_or_else(|| {
+            RenderError::new(format!("Expected numeric parameters for \"{}\"", name))
+        })?);
+    }
+    let mut result = numbers.remove(0);
+    for number in numbers {
+        result = match (result, number) {
+            (Number::Int(a), Number::Int(b)) => {
+                match int_op(a, b) {
+                    Some(n) => Number::Int(n),
+                    None => Number::Float(float_op(a as f64, b as f64)),
+                }
+            }
+            (a, b) => Number::Float(float_op(as_f64(a), as_f64(b))),
+        };
+    }
+    Ok(match result {
+        Number::Int(n) => n.to_string(),
+        Number::Float(f) => f.to_string(),
+    })
+}
+
+/// Numbers may also be given as strings, such as values set from the environment.
+fn to_number(value: &Json) -> Option<Number> {
+    match *value {
+        Json::Number(ref n) => {
+            n.as_i64().map(Number::Int).or_else(
+                || n.as_f64().map(Number::Float),
+            )
+        }
+        Json::String(ref s) => {
+            s.trim().parse::<i64>().map(Number::Int).ok().or_else(|| {
+                s.trim().parse::<f64>().map(Number::Float).ok()
+            })
+        }
+        _ => None,
+    }
+}
+
+fn as_f64(number: Number) -> f64 {
+    match number {
+        Number::Int(n) => n as f64,
+        Number::Float(f) => f,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn render(template: &str) -> String {
+        let json = json!({
+            "workers": 4,
+            "ratio": 1.5,
+            "base": "8000"
+        });
+        let mut handlebars = Handlebars::new();
+        handlebars.register_helper("add", Box::new(ADD));
+        handlebars.register_helper("mul", Box::new(MUL));
+        handlebars.template_render(template, &json).unwrap()
+    }
+
+    #[test]
+    fn test_add_helper() {
+        assert_eq!("8005", render("{{add base workers 1}}"));
+        assert_eq!("5.5", render("{{add workers ratio}}"));
+    }
+
+    #[test]
+    fn test_mul_helper() {
+        assert_eq!("16", render("{{mul workers 4}}"));
+        assert_eq!("6", render("{{mul workers ratio}}"));
+    }
+
+    #[test]
+    fn test_math_helpers_reject_non_numbers() {
+        let mut handlebars = Handlebars::new();
+        handlebars.register_helper("add", Box::new(ADD));
+        assert!(
+            handlebars
+                .template_render("{{add name 1}}", &json!({"name": "redis"}))
+                .is_err()
+        );
+    }
+}
@@ -12,11 +12,21 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod blake2b;
+mod compare;
+mod contains;
+mod default;
 mod each_alive;
+mod encoding;
+mod env_var;
+mod math;
 mod pkg_path_for;
+mod secret;
+mod split;
 mod str_concat;
 mod str_join;
 mod str_replace;
+mod to_ini;
 mod to_json;
 mod to_lowercase;
 mod to_toml;
@@ -24,14 +34,27 @@ mod to_uppercase;
 mod to_yaml;
 
 
+use handlebars::{Handlebars, Helper, Renderable, RenderContext};
 use serde::Serialize;
 use serde_json::{self, Value as Json};
 
+use super::RenderResult;
+
+pub use self::blake2b::BLAKE2B;
+pub use self::compare::{EQ, GT, NE};
+pub use self::contains::CONTAINS;
+pub use self::default::DEFAULT;
 pub use self::each_alive::EACH_ALIVE;
+pub use self::encoding::{BASE64_DECODE, BASE64_ENCODE};
+pub use self::env_var::ENV_VAR;
+pub use self::math::{ADD, MUL};
 pub use self::pkg_path_for::PKG_PATH_FOR;
+pub use self::secret::SECRET;
+pub use self::split::SPLIT;
 pub use self::str_concat::STR_CONCAT;
 pub use self::str_join::STR_JOIN;
 pub use self::str_replace::STR_REPLACE;
+pub use self::to_ini::TO_INI;
 pub use self::to_json::TO_JSON;
 pub use self::to_lowercase::TO_LOWERCASE;
 pub use self::to_toml::TO_TOML;
@@ -64,3 +87,46 @@ where
 {
     serde_json::to_value(src).unwrap_or(Json::Null)
 }
+
+/// Returns the text a helper writes for a value: strings as they are and anything else as JSON.
+fn json_to_text(value: &Json) -> String {
+    match *value {
+        Json::String(ref s) => s.clone(),
+        Json::Null => String::new(),
+        ref other => other.to_string(),
+    }
+}
+
+/// Compares two values, numbers by their value whether they are integers or floats.
+fn json_eq(a: &Json, b: &Json) -> bool {
+    match (a, b) {
+        (&Json::Number(ref x), &Json::Number(ref y)) => x.as_f64() == y.as_f64(),
+        _ => a == b,
+    }
+}
+
+/// Renders the block of a conditional helper if the condition holds, or its `else` block
+/// otherwise. Used inline, the helper writes `true` or `false`.
+fn render_condition(
+    h: &Helper,
+    r: &Handlebars,
+    rc: &mut RenderContext,
+    condition: bool,
+) -> RenderResult<()> {
+    match h.template() {
+        Some(template) => {
+            if condition {
+                template.render(r, rc)
+            } else {
+                match h.inverse() {
+                    Some(else_template) => else_template.render(r, rc),
+                    None => Ok(()),
+                }
+            }
+        }
+        None => {
+            rc.writer.write(condition.to_string().into_bytes().as_ref())?;
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,121 @@
+// Copyright (c) 2017 Chef Software Inc. and/or applicable contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use std::collections::BTreeMap;
+
+use handlebars::{Handlebars, Helper, HelperDef, Renderable, RenderContext, RenderError};
+
+use super::super::RenderResult;
+use super::{json_to_text, to_json};
+
+#[derive(Clone, Copy)]
+pub struct SplitHelper;
+
+impl HelperDef for SplitHelper {
+    fn call(&self, h: &Helper, r: &Handlebars, rc: &mut RenderContext) -> RenderResult<()> {
+        let value = h.param(0)
+            .map(|v| json_to_text(v.value()))
+            .ok_or_else(|| RenderError::new("Expected 2 parameters for \"split\""))?;
+        let separator = h.param(1).and_then(|v| v.value().as_str()).ok_or_else(|| {
+            RenderError::new("Expected 2 parameters for \"split\"")
+        })?;
+        let parts: Vec<&str> = if value.is_empty() {
+            Vec::new()
+        } else {
+            value.split(separator).collect()
+        };
+
+        match h.template() {
+            // As a block, the template is rendered for each part, given as its block parameter.
+            Some(template) => {
+                if parts.is_empty() {
+                    if let Some(else_template) = h.inverse() {
+                        else_template.render(r, rc)?;
+                    }
+                    return Ok(());
+                }
+                rc.promote_local_vars();
+                let len = parts.len();
+                for (i, part) in parts.iter().enumerate() {
+                    let mut local_rc = rc.derive();
+                    local_rc.set_local_var("@first".to_string(), to_json(&(i == 0usize)));
+                    local_rc.set_local_var("@last".to_string(), to_json(&(i == len - 1)));
+                    local_rc.set_local_var("@index".to_string(), to_json(&i));
+
+                    if let Some(block_param) = h.block_param() {
+                        let mut map = BTreeMap::new();
+                        map.insert(block_param.to_string(), to_json(part));
+                        local_rc.push_block_context(&map)?;
+                    }
+
+                    template.render(r, &mut local_rc)?;
+
+                    if h.block_param().is_some() {
+                        local_rc.pop_block_context();
+                    }
+                }
+                rc.demote_local_vars();
+                Ok(())
+            }
+            // Inline, the part at the given index is written.
+            None => {
+                let index = h.param(2).and_then(|v| v.value().as_u64()).ok_or_else(|| {
+                    RenderError::new("Expected an index as 3rd parameter for \"split\"")
+                })?;
+                let part = parts.get(index as usize).unwrap_or(&"");
+                rc.writer.write(part.as_bytes())?;
+                Ok(())
+            }
+        }
+    }
+}
+
+pub static SPLIT: SplitHelper = SplitHelper;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn render(template: &str) -> String {
+        let json = json!({
+            "hosts": "10.0.0.2,10.0.0.3",
+            "listen": "0.0.0.0:8080",
+            "none": ""
+        });
+        let mut handlebars = Handlebars::new();
+        handlebars.register_helper("split", Box::new(SPLIT));
+        handlebars.template_render(template, &json).unwrap()
+    }
+
+    #[test]
+    fn test_split_helper_block() {
+        assert_eq!(
+            "server.0=10.0.0.2\nserver.1=10.0.0.3\n",
+            render(
+                "{{#split hosts \",\" as |host|}}server.{{@index}}={{host}}\n{{/split}}",
+            )
+        );
+        assert_eq!(
+            "no hosts",
+            render("{{#split none \",\" as |host|}}{{host}}{{else}}no hosts{{/split}}")
+        );
+    }
+
+    #[test]
+    fn test_split_helper_inline() {
+        assert_eq!("8080", render("{{split listen \":\" 1}}"));
+        assert_eq!("", render("{{split listen \":\" 2}}"));
+    }
+}
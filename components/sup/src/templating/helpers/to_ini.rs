@@ -0,0 +1,107 @@
+// Copyright (c) 2017 Chef Software Inc. and/or applicable contributors
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use handlebars::{Handlebars, Helper, HelperDef, RenderContext, RenderError};
+use serde_json::{Map, Value as Json};
+
+use super::super::RenderResult;
+use super::json_to_text;
+
+#[derive(Clone, Copy)]
+pub struct ToIniHelper;
+
+impl HelperDef for ToIniHelper {
+    fn call(&self, h: &Helper, _: &Handlebars, rc: &mut RenderContext) -> RenderResult<()> {
+        let param = h.param(0).and_then(|v| v.value().as_object()).ok_or_else(|| {
+            RenderError::new("Expected an object parameter for \"toIni\"")
+        })?;
+        let mut ini = String::new();
+        write_section(&mut ini, None, param);
+        rc.writer.write(ini.into_bytes().as_ref())?;
+        Ok(())
+    }
+}
+
+pub static TO_INI: ToIniHelper = ToIniHelper;
+
+/// Writes the keys of a table, followed by its tables as sections. Nested tables are written as
+/// sections named with dotted paths, as INI has no way back to a parent section.
+fn write_section(ini: &mut String, name: Option<&str>, table: &Map<String, Json>) {
+    let has_keys = table.values().any(|v| !v.is_object());
+    if let (Some(name), true) = (name, has_keys) {
+        if !ini.is_empty() {
+            ini.push('\n');
+        }
+        ini.push_str(&format!("[{}]\n", name));
+    }
+    for (key, value) in table.iter().filter(|&(_, v)| !v.is_object()) {
+        ini.push_str(&format!("{}={}\n", key, ini_value(value)));
+    }
+    for (key, value) in table.iter() {
+        if let Json::Object(ref section) = *value {
+            let section_name = match name {
+                Some(name) => format!("{}.{}", name, key),
+                None => key.to_string(),
+            };
+            write_section(ini, Some(&section_name), section);
+        }
+    }
+}
+
+/// Lists are written as comma separated values.
+fn ini_value(value: &Json) -> String {
+    match *value {
+        Json::Array(ref list) => {
+            list.iter().map(json_to_text).collect::<Vec<String>>().join(",")
+        }
+        ref v => json_to_text(v),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_to_ini_helper() {
+        let json = json!({
+            "cfg": {
+                "debug": false,
+                "database": {
+                    "host": "10.0.0.2",
+                    "port": 5432,
+                    "replicas": ["10.0.0.3", "10.0.0.4"],
+                    "pool": { "size": 10 }
+                },
+                "empty": {}
+            }
+        });
+        let mut handlebars = Handlebars::new();
+        handlebars.register_helper("toIni", Box::new(TO_INI));
+        let expected = "debug=false\n\
+                        \n\
+                        [database]\n\
+                        host=10.0.0.2\n\
+                        port=5432\n\
+                        replicas=10.0.0.3,10.0.0.4\n\
+                        \n\
+                        [database.pool]\n\
+                        size=10\n";
+        assert_eq!(
+            expected,
+            handlebars.template_render("{{toIni cfg}}", &json).unwrap()
+        );
+    }
+}
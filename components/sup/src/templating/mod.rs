@@ -35,11 +35,25 @@ pub struct TemplateRenderer(Handlebars);
 impl TemplateRenderer {
     pub fn new() -> Self {
         let mut handlebars = Handlebars::new();
+        handlebars.register_helper("add", Box::new(helpers::ADD));
+        handlebars.register_helper("base64Decode", Box::new(helpers::BASE64_DECODE));
+        handlebars.register_helper("base64Encode", Box::new(helpers::BASE64_ENCODE));
+        handlebars.register_helper("blake2b", Box::new(helpers::BLAKE2B));
+        handlebars.register_helper("contains", Box::new(helpers::CONTAINS));
+        handlebars.register_helper("default", Box::new(helpers::DEFAULT));
         handlebars.register_helper("eachAlive", Box::new(helpers::EACH_ALIVE));
+        handlebars.register_helper("envVar", Box::new(helpers::ENV_VAR));
+        handlebars.register_helper("eq", Box::new(helpers::EQ));
+        handlebars.register_helper("gt", Box::new(helpers::GT));
+        handlebars.register_helper("mul", Box::new(helpers::MUL));
+        handlebars.register_helper("ne", Box::new(helpers::NE));
         handlebars.register_helper("pkgPathFor", Box::new(helpers::PKG_PATH_FOR));
+        handlebars.register_helper("secret", Box::new(helpers::SECRET));
+        handlebars.register_helper("split", Box::new(helpers::SPLIT));
         handlebars.register_helper("strConcat", Box::new(helpers::STR_CONCAT));
         handlebars.register_helper("strJoin", Box::new(helpers::STR_JOIN));
         handlebars.register_helper("strReplace", Box::new(helpers::STR_REPLACE));
+        handlebars.register_helper("toIni", Box::new(helpers::TO_INI));
         handlebars.register_helper("toUppercase", Box::new(helpers::TO_UPPERCASE));
         handlebars.register_helper("toLowercase", Box::new(helpers::TO_LOWERCASE));
         handlebars.register_helper("toJson", Box::new(helpers::TO_JSON));
@@ -310,4 +324,21 @@ test: something"#
 
         assert_eq!(each_alive_render, each_if_render);
     }
+
+    #[test]
+    fn helpers_content() {
+        let mut renderer = TemplateRenderer::new();
+        renderer
+            .register_template_file("helpers", templates().join("helpers.txt"))
+            .unwrap();
+
+        let data = service_config_json_from_toml_file("helpers_config.toml");
+        let mut expected = String::new();
+        File::open(templates().join("helpers_rendered.txt"))
+            .unwrap()
+            .read_to_string(&mut expected)
+            .unwrap();
+
+        assert_eq!(renderer.render("helpers", &data).unwrap(), expected);
+    }
 }
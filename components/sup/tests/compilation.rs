@@ -253,3 +253,41 @@ hook_value = "default"
     assert_eq!(hook_before_apply, hook_after_apply);
     assert_eq!(pid_before_apply, pid_after_apply);
 }
+
+#[test]
+fn strict_templates_which_fail_to_compile_keep_the_service_from_starting() {
+    let hab_root = utils::HabRoot::new(
+        "strict_templates_which_fail_to_compile_keep_the_service_from_starting",
+    );
+
+    let origin_name = "sup-integration-test";
+    let package_name = "strict-templates-fail";
+    let service_group = "default";
+
+    utils::setup_package_files(
+        &origin_name,
+        &package_name,
+        &service_group,
+        &FIXTURE_ROOT,
+        &hab_root,
+    );
+
+    let mut test_sup = utils::TestSup::new_with_random_ports(
+        &hab_root,
+        &origin_name,
+        &package_name,
+        &service_group,
+    );
+
+    test_sup.start();
+    utils::sleep_seconds(3);
+
+    assert!(!hab_root.has_pid(package_name));
+
+    test_sup.apply_config(r#"config_value = "finally set""#);
+    utils::sleep_seconds(2);
+
+    assert!(hab_root.has_pid(package_name));
+    let config = hab_root.compiled_config_contents(&package_name, "config.toml");
+    assert_eq!(config, "config_value = \"finally set\"\n");
+}
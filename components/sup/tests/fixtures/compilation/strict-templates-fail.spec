@@ -0,0 +1,10 @@
+ident = "sup-integration-test/strict-templates-fail"
+group = "default"
+bldr_url = "http://hab.sup.test"
+channel = "unstable"
+topology = "standalone"
+update_strategy = "at-once"
+binds = []
+desired_state = "up"
+start_style = "persistent"
+strict_templates = true
@@ -148,6 +148,14 @@ impl HabRoot {
             .expect("Couldn't parse PID file content as u32!")
     }
 
+    /// Whether the process of a package has been started, i.e. whether it has a PID file.
+    pub fn has_pid<P>(&self, pkg_name: P) -> bool
+    where
+        P: AsRef<Path>,
+    {
+        self.svc_path(pkg_name.as_ref()).join("PID").exists()
+    }
+
     /// Path to the service directory for a package
     fn svc_path<P>(&self, pkg_name: P) -> PathBuf
     where